@@ -11,6 +11,7 @@
 
 use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::Path;
+use std::slice;
 use std::sync::{Arc, Mutex, MutexGuard};
 use std::time::{Duration, Instant};
 
@@ -23,6 +24,8 @@ use lazy_static::lazy_static;
 use log::{info, trace};
 use ore::collections::CollectionExt;
 use regex::Regex;
+use repr::adt::apd::APD_DATUM_MAX_PRECISION;
+use repr::adt::datetime::Timezone;
 use repr::Timestamp;
 use serde::{Deserialize, Serialize};
 
@@ -190,6 +193,31 @@ pub struct Source {
     pub connector: SourceConnector,
     pub bare_desc: RelationDesc,
     pub desc: RelationDesc,
+    /// The value schemas the source read with before its current schema, from
+    /// oldest to newest. Only sources whose schema has been refreshed with
+    /// `ALTER SOURCE ... REFRESH SCHEMA` have a history.
+    pub schema_history: Vec<String>,
+}
+
+impl Source {
+    /// Constructs the companion of the source with ID `source_id`, which
+    /// presents the records that the source fails to decode. The companion
+    /// shares the source's `create_sql`, as it is created by the same statement.
+    pub fn dead_letters(source_id: GlobalId, create_sql: String, plan_cx: PlanContext) -> Source {
+        let desc = SourceConnector::dead_letters_desc();
+        Source {
+            create_sql,
+            plan_cx,
+            optimized_expr: OptimizedMirRelationExpr::declare_optimized(MirRelationExpr::Get {
+                id: Id::LocalBareSource,
+                typ: desc.typ().clone(),
+            }),
+            connector: SourceConnector::DeadLetters(source_id),
+            bare_desc: desc.clone(),
+            desc,
+            schema_history: vec![],
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -336,6 +364,10 @@ impl CatalogItem {
             CatalogItem::Func(_) => &[],
             CatalogItem::Index(idx) => &idx.depends_on,
             CatalogItem::Sink(sink) => &sink.depends_on,
+            CatalogItem::Source(Source {
+                connector: SourceConnector::DeadLetters(source_id),
+                ..
+            }) => slice::from_ref(source_id),
             CatalogItem::Source(_) => &[],
             CatalogItem::Table(table) => &table.depends_on,
             CatalogItem::Type(typ) => &typ.depends_on,
@@ -597,6 +629,7 @@ impl Catalog {
                             optimized_expr,
                             connector: dataflow_types::SourceConnector::Local,
                             bare_desc: log.variant.desc(),
+                            schema_history: vec![],
                             desc: log.variant.desc(),
                         }),
                     );
@@ -712,6 +745,7 @@ impl Catalog {
                                 }
                                 postgres_types::Kind::Pseudo => TypeInner::Pseudo,
                                 postgres_types::Kind::Simple => TypeInner::Base,
+                                postgres_types::Kind::Range(_) => TypeInner::Base,
                                 _ => unreachable!(),
                             },
                             depends_on: vec![],
@@ -1591,6 +1625,19 @@ impl Catalog {
                     });
                     actions
                 }
+                Op::UpdateItem { id, to_item } => {
+                    let entry = self.by_id.get(&id).unwrap();
+                    if !to_item.is_temporary() {
+                        let serialized_item = self.serialize_item(&to_item);
+                        tx.update_item(id, &entry.name.item, &serialized_item)?;
+                    }
+                    builtin_table_updates.extend(self.pack_item_update(id, -1));
+                    vec![Action::UpdateItem {
+                        id,
+                        to_name: entry.name.clone(),
+                        item: to_item,
+                    }]
+                }
             });
         }
         tx.commit()?;
@@ -1748,26 +1795,41 @@ impl Catalog {
             CatalogItem::Table(table) => SerializedCatalogItem::V1 {
                 create_sql: table.create_sql.clone(),
                 eval_env: Some(table.plan_cx.clone().into()),
+                schema_history: vec![],
+                dead_letters_of: None,
             },
             CatalogItem::Source(source) => SerializedCatalogItem::V1 {
                 create_sql: source.create_sql.clone(),
                 eval_env: Some(source.plan_cx.clone().into()),
+                schema_history: source.schema_history.clone(),
+                dead_letters_of: match source.connector {
+                    SourceConnector::DeadLetters(source_id) => Some(source_id),
+                    _ => None,
+                },
             },
             CatalogItem::View(view) => SerializedCatalogItem::V1 {
                 create_sql: view.create_sql.clone(),
                 eval_env: Some(view.plan_cx.clone().into()),
+                schema_history: vec![],
+                dead_letters_of: None,
             },
             CatalogItem::Index(index) => SerializedCatalogItem::V1 {
                 create_sql: index.create_sql.clone(),
                 eval_env: Some(index.plan_cx.clone().into()),
+                schema_history: vec![],
+                dead_letters_of: None,
             },
             CatalogItem::Sink(sink) => SerializedCatalogItem::V1 {
                 create_sql: sink.create_sql.clone(),
                 eval_env: Some(sink.plan_cx.clone().into()),
+                schema_history: vec![],
+                dead_letters_of: None,
             },
             CatalogItem::Type(typ) => SerializedCatalogItem::V1 {
                 create_sql: typ.create_sql.clone(),
                 eval_env: Some(typ.plan_cx.clone().into()),
+                schema_history: vec![],
+                dead_letters_of: None,
             },
             CatalogItem::Func(_) => unreachable!("cannot serialize functions yet"),
         };
@@ -1778,6 +1840,8 @@ impl Catalog {
         let SerializedCatalogItem::V1 {
             create_sql,
             eval_env,
+            schema_history,
+            dead_letters_of,
         } = serde_json::from_slice(&bytes)?;
         let pcx = match eval_env {
             // Old sources and sinks don't have plan contexts, but it's safe to
@@ -1786,7 +1850,18 @@ impl Catalog {
             None => PlanContext::default(),
             Some(eval_env) => eval_env.into(),
         };
-        self.parse_item(create_sql, pcx)
+        // Dead-letter companions are not planned from their `create_sql`,
+        // which is the statement that created the source they belong to.
+        if let Some(source_id) = dead_letters_of {
+            return Ok(CatalogItem::Source(Source::dead_letters(
+                source_id, create_sql, pcx,
+            )));
+        }
+        let mut item = self.parse_item(create_sql, pcx)?;
+        if let CatalogItem::Source(source) = &mut item {
+            source.schema_history = schema_history;
+        }
+        Ok(item)
     }
 
     fn parse_item(
@@ -1818,6 +1893,7 @@ impl Catalog {
                     connector: source.connector,
                     bare_desc: source.bare_desc,
                     desc: transformed_desc,
+                    schema_history: vec![],
                 })
             }
             Plan::CreateView(CreateViewPlan {
@@ -1973,6 +2049,7 @@ impl Catalog {
                     _ => Unknown,
                 },
                 SourceConnector::Local => Volatile,
+                SourceConnector::DeadLetters(source_id) => self.is_volatile(*source_id),
             },
             CatalogItem::Index(_) | CatalogItem::View(_) | CatalogItem::Sink(_) => {
                 // Volatility follows trinary logic like SQL. If even one
@@ -2084,6 +2161,12 @@ pub enum Op {
         id: GlobalId,
         to_name: String,
     },
+    /// Replaces the definition of the identified item. The new definition
+    /// must depend on the same items as the old definition.
+    UpdateItem {
+        id: GlobalId,
+        to_item: CatalogItem,
+    },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -2092,6 +2175,12 @@ enum SerializedCatalogItem {
         create_sql: String,
         // The name "eval_env" is historical.
         eval_env: Option<SerializedPlanContext>,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        schema_history: Vec<String>,
+        // The source whose undecodable records this item presents, if it is
+        // a dead-letter companion.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        dead_letters_of: Option<GlobalId>,
     },
 }
 
@@ -2099,12 +2188,15 @@ enum SerializedCatalogItem {
 struct SerializedPlanContext {
     pub logical_time: Option<u64>,
     pub wall_time: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub timezone: Option<Timezone>,
 }
 
 impl From<SerializedPlanContext> for PlanContext {
     fn from(cx: SerializedPlanContext) -> PlanContext {
         PlanContext {
             wall_time: cx.wall_time.unwrap_or_else(|| Utc.timestamp(0, 0)),
+            timezone: cx.timezone.unwrap_or(Timezone::UTC),
         }
     }
 }
@@ -2114,6 +2206,7 @@ impl From<PlanContext> for SerializedPlanContext {
         SerializedPlanContext {
             logical_time: None,
             wall_time: Some(cx.wall_time),
+            timezone: Some(cx.timezone),
         }
     }
 }
@@ -2199,10 +2292,18 @@ impl ExprHumanizer for ConnCatalog<'_> {
                     // qualified object name to refer to type.
                     self.get_item_by_oid(&pgrepr_type.oid()).name().to_string()
                 };
-                if let ScalarType::Decimal(p, s) = typ {
-                    format!("{}({},{})", res, p, s)
-                } else {
-                    res
+                match typ {
+                    ScalarType::Decimal(p, s) => format!("{}({},{})", res, p, s),
+                    ScalarType::APD { scale: Some(s) } => {
+                        format!("{}({},{})", res, APD_DATUM_MAX_PRECISION, s)
+                    }
+                    ScalarType::Char {
+                        length: Some(length),
+                    }
+                    | ScalarType::VarChar {
+                        max_length: Some(length),
+                    } => format!("{}({})", res, length),
+                    _ => res,
                 }
             }
         }
@@ -422,12 +422,103 @@ pub const TYPE_BPCHAR: BuiltinType = BuiltinType {
     pgtype: &postgres_types::Type::BPCHAR,
 };
 
+pub const TYPE_INT4_RANGE: BuiltinType = BuiltinType {
+    schema: PG_CATALOG_SCHEMA,
+    id: GlobalId::System(1043),
+    pgtype: &postgres_types::Type::INT4_RANGE,
+};
+
+pub const TYPE_INT4_RANGE_ARRAY: BuiltinType = BuiltinType {
+    schema: PG_CATALOG_SCHEMA,
+    id: GlobalId::System(1044),
+    pgtype: &postgres_types::Type::INT4_RANGE_ARRAY,
+};
+
+pub const TYPE_INT8_RANGE: BuiltinType = BuiltinType {
+    schema: PG_CATALOG_SCHEMA,
+    id: GlobalId::System(1045),
+    pgtype: &postgres_types::Type::INT8_RANGE,
+};
+
+pub const TYPE_INT8_RANGE_ARRAY: BuiltinType = BuiltinType {
+    schema: PG_CATALOG_SCHEMA,
+    id: GlobalId::System(1046),
+    pgtype: &postgres_types::Type::INT8_RANGE_ARRAY,
+};
+
+pub const TYPE_DATE_RANGE: BuiltinType = BuiltinType {
+    schema: PG_CATALOG_SCHEMA,
+    id: GlobalId::System(1047),
+    pgtype: &postgres_types::Type::DATE_RANGE,
+};
+
+pub const TYPE_DATE_RANGE_ARRAY: BuiltinType = BuiltinType {
+    schema: PG_CATALOG_SCHEMA,
+    id: GlobalId::System(1048),
+    pgtype: &postgres_types::Type::DATE_RANGE_ARRAY,
+};
+
+pub const TYPE_TS_RANGE: BuiltinType = BuiltinType {
+    schema: PG_CATALOG_SCHEMA,
+    id: GlobalId::System(1049),
+    pgtype: &postgres_types::Type::TS_RANGE,
+};
+
+pub const TYPE_TS_RANGE_ARRAY: BuiltinType = BuiltinType {
+    schema: PG_CATALOG_SCHEMA,
+    id: GlobalId::System(1050),
+    pgtype: &postgres_types::Type::TS_RANGE_ARRAY,
+};
+
+pub const TYPE_TSTZ_RANGE: BuiltinType = BuiltinType {
+    schema: PG_CATALOG_SCHEMA,
+    id: GlobalId::System(1051),
+    pgtype: &postgres_types::Type::TSTZ_RANGE,
+};
+
+pub const TYPE_TSTZ_RANGE_ARRAY: BuiltinType = BuiltinType {
+    schema: PG_CATALOG_SCHEMA,
+    id: GlobalId::System(1052),
+    pgtype: &postgres_types::Type::TSTZ_RANGE_ARRAY,
+};
+
+pub const TYPE_BPCHAR_ARRAY: BuiltinType = BuiltinType {
+    schema: PG_CATALOG_SCHEMA,
+    id: GlobalId::System(1053),
+    pgtype: &postgres_types::Type::BPCHAR_ARRAY,
+};
+
+pub const TYPE_VARCHAR_ARRAY: BuiltinType = BuiltinType {
+    schema: PG_CATALOG_SCHEMA,
+    id: GlobalId::System(1054),
+    pgtype: &postgres_types::Type::VARCHAR_ARRAY,
+};
+
+pub const TYPE_INET: BuiltinType = BuiltinType {
+    schema: PG_CATALOG_SCHEMA,
+    id: GlobalId::System(1055),
+    pgtype: &postgres_types::Type::INET,
+};
+
+pub const TYPE_INET_ARRAY: BuiltinType = BuiltinType {
+    schema: PG_CATALOG_SCHEMA,
+    id: GlobalId::System(1056),
+    pgtype: &postgres_types::Type::INET_ARRAY,
+};
+
+pub const TYPE_CIDR: BuiltinType = BuiltinType {
+    schema: PG_CATALOG_SCHEMA,
+    id: GlobalId::System(1057),
+    pgtype: &postgres_types::Type::CIDR,
+};
+
+pub const TYPE_CIDR_ARRAY: BuiltinType = BuiltinType {
+    schema: PG_CATALOG_SCHEMA,
+    id: GlobalId::System(1058),
+    pgtype: &postgres_types::Type::CIDR_ARRAY,
+};
+
 lazy_static! {
-    pub static ref TYPE_APD: BuiltinType = BuiltinType {
-        schema: PG_CATALOG_SCHEMA,
-        id: GlobalId::System(1997),
-        pgtype: &pgrepr::APDType,
-    };
     pub static ref TYPE_LIST: BuiltinType = BuiltinType {
         schema: PG_CATALOG_SCHEMA,
         id: GlobalId::System(1998),
@@ -576,6 +667,30 @@ pub const MZ_KAFKA_BROKER_RTT: BuiltinLog = BuiltinLog {
     index_id: GlobalId::System(3033),
 };
 
+pub const MZ_UPSERT_STATE: BuiltinLog = BuiltinLog {
+    name: "mz_upsert_state",
+    schema: MZ_CATALOG_SCHEMA,
+    variant: LogVariant::Materialized(MaterializedLog::UpsertState),
+    id: GlobalId::System(3034),
+    index_id: GlobalId::System(3035),
+};
+
+pub const MZ_SOURCE_INGESTION: BuiltinLog = BuiltinLog {
+    name: "mz_source_ingestion",
+    schema: MZ_CATALOG_SCHEMA,
+    variant: LogVariant::Materialized(MaterializedLog::SourceIngestion),
+    id: GlobalId::System(3036),
+    index_id: GlobalId::System(3037),
+};
+
+pub const MZ_WORKER_SOURCE_ERRORS: BuiltinLog = BuiltinLog {
+    name: "mz_worker_source_errors",
+    schema: MZ_CATALOG_SCHEMA,
+    variant: LogVariant::Materialized(MaterializedLog::SourceErrors),
+    id: GlobalId::System(3038),
+    index_id: GlobalId::System(3039),
+};
+
 lazy_static! {
     pub static ref MZ_VIEW_KEYS: BuiltinTable = BuiltinTable {
         name: "mz_view_keys",
@@ -839,6 +954,17 @@ lazy_static! {
         id: GlobalId::System(4047),
         index_id: GlobalId::System(4048),
     };
+    pub static ref MZ_SOURCE_SCHEMA_VERSIONS: BuiltinTable = BuiltinTable {
+        name: "mz_source_schema_versions",
+        schema: MZ_CATALOG_SCHEMA,
+        desc: RelationDesc::empty()
+            .with_named_column("source_id", ScalarType::String.nullable(false))
+            .with_named_column("version", ScalarType::Int64.nullable(false))
+            .with_named_column("value_schema", ScalarType::String.nullable(false))
+            .with_key(vec![0, 1]),
+        id: GlobalId::System(4049),
+        index_id: GlobalId::System(4050),
+    };
 }
 
 pub const MZ_RELATIONS: BuiltinView = BuiltinView {
@@ -1097,6 +1223,65 @@ JOIN mz_catalog.mz_catalog_names mcn_source ON mcn_source.global_id = source_inf
     needs_logs: true,
 };
 
+pub const MZ_SOURCE_LAG: BuiltinView = BuiltinView {
+    name: "mz_source_lag",
+    schema: MZ_CATALOG_SCHEMA,
+    sql: "CREATE VIEW mz_source_lag AS SELECT
+    ingestion.source_name,
+    ingestion.source_id,
+    ingestion.dataflow_id,
+    ingestion.partition_id,
+    kafka.hi_offset AS high_watermark,
+    ingestion.offset AS consumed_offset,
+    CASE WHEN kafka.hi_offset < ingestion.offset THEN 0 ELSE kafka.hi_offset - ingestion.offset END AS lag_records,
+    ingestion.lag_ms
+FROM mz_catalog.mz_source_ingestion ingestion
+LEFT JOIN mz_catalog.mz_kafka_consumer_partitions kafka
+    ON kafka.source_id = ingestion.source_id
+    AND kafka.dataflow_id = ingestion.dataflow_id
+    AND kafka.partition_id = ingestion.partition_id",
+    id: GlobalId::System(5025),
+    needs_logs: true,
+};
+
+pub const MZ_SOURCE_THROUGHPUT: BuiltinView = BuiltinView {
+    name: "mz_source_throughput",
+    schema: MZ_CATALOG_SCHEMA,
+    sql: "CREATE VIEW mz_source_throughput AS SELECT
+    source_name,
+    source_id,
+    dataflow_id,
+    partition_id,
+    messages,
+    raw_bytes,
+    decoded_bytes,
+    messages_per_second,
+    raw_bytes_per_second,
+    decoded_bytes_per_second
+FROM mz_catalog.mz_source_ingestion",
+    id: GlobalId::System(5026),
+    needs_logs: true,
+};
+
+pub const MZ_SOURCE_ERRORS: BuiltinView = BuiltinView {
+    name: "mz_source_errors",
+    schema: MZ_CATALOG_SCHEMA,
+    sql: "CREATE VIEW mz_source_errors AS SELECT
+    errors.source_id,
+    names.name AS source_name,
+    errors.partition_id,
+    errors.offset,
+    errors.encoding,
+    errors.retryable,
+    errors.error,
+    pg_catalog.count(*) AS count
+FROM mz_catalog.mz_worker_source_errors errors
+LEFT JOIN mz_catalog.mz_catalog_names names ON names.global_id = errors.source_id
+GROUP BY errors.source_id, names.name, errors.partition_id, errors.offset, errors.encoding, errors.retryable, errors.error",
+    id: GlobalId::System(5027),
+    needs_logs: true,
+};
+
 pub const PG_NAMESPACE: BuiltinView = BuiltinView {
     name: "pg_namespace",
     schema: PG_CATALOG_SCHEMA,
@@ -1282,22 +1467,32 @@ lazy_static! {
             Builtin::Type(&TYPE_BYTEA),
             Builtin::Type(&TYPE_BYTEA_ARRAY),
             Builtin::Type(&TYPE_BPCHAR),
+            Builtin::Type(&TYPE_BPCHAR_ARRAY),
             Builtin::Type(&TYPE_CHAR),
+            Builtin::Type(&TYPE_CIDR),
+            Builtin::Type(&TYPE_CIDR_ARRAY),
             Builtin::Type(&TYPE_DATE),
             Builtin::Type(&TYPE_DATE_ARRAY),
+            Builtin::Type(&TYPE_DATE_RANGE),
+            Builtin::Type(&TYPE_DATE_RANGE_ARRAY),
             Builtin::Type(&TYPE_FLOAT4),
             Builtin::Type(&TYPE_FLOAT4_ARRAY),
             Builtin::Type(&TYPE_FLOAT8),
             Builtin::Type(&TYPE_FLOAT8_ARRAY),
+            Builtin::Type(&TYPE_INET),
+            Builtin::Type(&TYPE_INET_ARRAY),
             Builtin::Type(&TYPE_INT4),
             Builtin::Type(&TYPE_INT4_ARRAY),
+            Builtin::Type(&TYPE_INT4_RANGE),
+            Builtin::Type(&TYPE_INT4_RANGE_ARRAY),
             Builtin::Type(&TYPE_INT8),
             Builtin::Type(&TYPE_INT8_ARRAY),
+            Builtin::Type(&TYPE_INT8_RANGE),
+            Builtin::Type(&TYPE_INT8_RANGE_ARRAY),
             Builtin::Type(&TYPE_INTERVAL),
             Builtin::Type(&TYPE_INTERVAL_ARRAY),
             Builtin::Type(&TYPE_JSONB),
             Builtin::Type(&TYPE_JSONB_ARRAY),
-            Builtin::Type(&TYPE_APD),
             Builtin::Type(&TYPE_LIST),
             Builtin::Type(&TYPE_MAP),
             Builtin::Type(&TYPE_NUMERIC),
@@ -1316,9 +1511,14 @@ lazy_static! {
             Builtin::Type(&TYPE_TIMESTAMP_ARRAY),
             Builtin::Type(&TYPE_TIMESTAMPTZ),
             Builtin::Type(&TYPE_TIMESTAMPTZ_ARRAY),
+            Builtin::Type(&TYPE_TS_RANGE),
+            Builtin::Type(&TYPE_TS_RANGE_ARRAY),
+            Builtin::Type(&TYPE_TSTZ_RANGE),
+            Builtin::Type(&TYPE_TSTZ_RANGE_ARRAY),
             Builtin::Type(&TYPE_UUID),
             Builtin::Type(&TYPE_UUID_ARRAY),
             Builtin::Type(&TYPE_VARCHAR),
+            Builtin::Type(&TYPE_VARCHAR_ARRAY),
             Builtin::Log(&MZ_DATAFLOW_OPERATORS),
             Builtin::Log(&MZ_DATAFLOW_OPERATORS_ADDRESSES),
             Builtin::Log(&MZ_DATAFLOW_CHANNELS),
@@ -1336,6 +1536,9 @@ lazy_static! {
             Builtin::Log(&MZ_MESSAGE_COUNTS),
             Builtin::Log(&MZ_KAFKA_CONSUMER_PARTITIONS),
             Builtin::Log(&MZ_KAFKA_BROKER_RTT),
+            Builtin::Log(&MZ_UPSERT_STATE),
+            Builtin::Log(&MZ_SOURCE_INGESTION),
+            Builtin::Log(&MZ_WORKER_SOURCE_ERRORS),
             Builtin::Table(&MZ_VIEW_KEYS),
             Builtin::Table(&MZ_VIEW_FOREIGN_KEYS),
             Builtin::Table(&MZ_KAFKA_SINKS),
@@ -1347,6 +1550,7 @@ lazy_static! {
             Builtin::Table(&MZ_INDEX_COLUMNS),
             Builtin::Table(&MZ_TABLES),
             Builtin::Table(&MZ_SOURCES),
+            Builtin::Table(&MZ_SOURCE_SCHEMA_VERSIONS),
             Builtin::Table(&MZ_SINKS),
             Builtin::Table(&MZ_VIEWS),
             Builtin::Table(&MZ_TYPES),
@@ -1375,6 +1579,9 @@ lazy_static! {
             Builtin::View(&MZ_PERF_PEEK_DURATIONS_AGGREGATES),
             Builtin::View(&MZ_MATERIALIZATION_FRONTIERS),
             Builtin::View(&MZ_PERF_DEPENDENCY_FRONTIERS),
+            Builtin::View(&MZ_SOURCE_LAG),
+            Builtin::View(&MZ_SOURCE_THROUGHPUT),
+            Builtin::View(&MZ_SOURCE_ERRORS),
             Builtin::View(&PG_NAMESPACE),
             Builtin::View(&PG_CLASS),
             Builtin::View(&PG_DATABASE),
@@ -10,7 +10,9 @@
 use std::convert::TryFrom;
 use std::os::unix::ffi::OsStringExt;
 
-use dataflow_types::{AvroOcfSinkConnector, KafkaSinkConnector};
+use dataflow_types::{
+    AvroEncoding, AvroOcfSinkConnector, DataEncoding, KafkaSinkConnector, SourceConnector,
+};
 use expr::{GlobalId, MirScalarExpr};
 use ore::collections::CollectionExt;
 use repr::adt::array::ArrayDimension;
@@ -21,11 +23,12 @@ use sql::names::DatabaseSpecifier;
 use crate::catalog::builtin::{
     MZ_ARRAY_TYPES, MZ_AVRO_OCF_SINKS, MZ_BASE_TYPES, MZ_COLUMNS, MZ_DATABASES, MZ_FUNCTIONS,
     MZ_INDEXES, MZ_INDEX_COLUMNS, MZ_KAFKA_SINKS, MZ_LIST_TYPES, MZ_MAP_TYPES, MZ_PSEUDO_TYPES,
-    MZ_ROLES, MZ_SCHEMAS, MZ_SINKS, MZ_SOURCES, MZ_TABLES, MZ_TYPES, MZ_VIEWS,
+    MZ_ROLES, MZ_SCHEMAS, MZ_SINKS, MZ_SOURCES, MZ_SOURCE_SCHEMA_VERSIONS, MZ_TABLES, MZ_TYPES,
+    MZ_VIEWS,
 };
 use crate::catalog::{
-    Catalog, CatalogItem, Func, Index, Sink, SinkConnector, SinkConnectorState, Type, TypeInner,
-    SYSTEM_CONN_ID,
+    Catalog, CatalogItem, Func, Index, Sink, SinkConnector, SinkConnectorState, Source, Type,
+    TypeInner, SYSTEM_CONN_ID,
 };
 
 /// An update to a built-in table.
@@ -104,7 +107,9 @@ impl Catalog {
         let mut updates = match entry.item() {
             CatalogItem::Index(index) => self.pack_index_update(id, oid, name, index, diff),
             CatalogItem::Table(_) => self.pack_table_update(id, oid, schema_id, name, diff),
-            CatalogItem::Source(_) => self.pack_source_update(id, oid, schema_id, name, diff),
+            CatalogItem::Source(source) => {
+                self.pack_source_update(id, oid, schema_id, name, source, diff)
+            }
             CatalogItem::View(_) => self.pack_view_update(id, oid, schema_id, name, diff),
             CatalogItem::Sink(sink) => self.pack_sink_update(id, oid, schema_id, name, sink, diff),
             CatalogItem::Type(ty) => self.pack_type_update(id, oid, schema_id, name, ty, diff),
@@ -160,9 +165,10 @@ impl Catalog {
         oid: u32,
         schema_id: i64,
         name: &str,
+        source: &Source,
         diff: isize,
     ) -> Vec<BuiltinTableUpdate> {
-        vec![BuiltinTableUpdate {
+        let mut updates = vec![BuiltinTableUpdate {
             id: MZ_SOURCES.id,
             row: Row::pack_slice(&[
                 Datum::String(&id.to_string()),
@@ -172,7 +178,29 @@ impl Catalog {
                 Datum::String(self.is_volatile(id).as_str()),
             ]),
             diff,
-        }]
+        }];
+        if let SourceConnector::External { encoding, .. } = &source.connector {
+            if let DataEncoding::Avro(AvroEncoding {
+                schema,
+                schema_registry_config: Some(_),
+                ..
+            }) = encoding.value_ref()
+            {
+                let versions = source.schema_history.iter().chain(std::iter::once(schema));
+                for (version, schema) in versions.enumerate() {
+                    updates.push(BuiltinTableUpdate {
+                        id: MZ_SOURCE_SCHEMA_VERSIONS.id,
+                        row: Row::pack_slice(&[
+                            Datum::String(&id.to_string()),
+                            Datum::Int64(version as i64 + 1),
+                            Datum::String(schema),
+                        ]),
+                        diff,
+                    });
+                }
+            }
+        }
+        updates
     }
 
     fn pack_view_update(
@@ -63,6 +63,7 @@ pub const CONTENT_MIGRATIONS: &[fn(&mut Catalog) -> Result<(), anyhow::Error>] =
             let SerializedCatalogItem::V1 {
                 create_sql,
                 eval_env,
+                schema_history,
             } = serde_json::from_slice(&def)?;
 
             let mut stmt = sql::parse::parse(&create_sql)?.into_element();
@@ -130,6 +131,7 @@ pub const CONTENT_MIGRATIONS: &[fn(&mut Catalog) -> Result<(), anyhow::Error>] =
             let serialized_item = SerializedCatalogItem::V1 {
                 create_sql: stmt.to_ast_string_stable(),
                 eval_env,
+                schema_history,
             };
 
             let serialized_item =
@@ -197,6 +199,7 @@ pub const CONTENT_MIGRATIONS: &[fn(&mut Catalog) -> Result<(), anyhow::Error>] =
             let SerializedCatalogItem::V1 {
                 create_sql,
                 eval_env,
+                schema_history,
             } = serde_json::from_slice(&def)?;
 
             let mut stmt = sql::parse::parse(&create_sql)?.into_element();
@@ -232,6 +235,7 @@ pub const CONTENT_MIGRATIONS: &[fn(&mut Catalog) -> Result<(), anyhow::Error>] =
                     name: _,
                     from: _,
                     connector: _,
+                    partition_by: _,
                     with_options: _,
                     format: _,
                     envelope: _,
@@ -256,6 +260,7 @@ pub const CONTENT_MIGRATIONS: &[fn(&mut Catalog) -> Result<(), anyhow::Error>] =
             let serialized_item = SerializedCatalogItem::V1 {
                 create_sql: stmt.to_ast_string_stable(),
                 eval_env,
+                schema_history,
             };
 
             let serialized_item =
@@ -289,6 +294,7 @@ pub const CONTENT_MIGRATIONS: &[fn(&mut Catalog) -> Result<(), anyhow::Error>] =
             let SerializedCatalogItem::V1 {
                 create_sql,
                 eval_env,
+                schema_history,
             } = serde_json::from_slice(&def)?;
 
             let mut stmt = sql::parse::parse(&create_sql)?.into_element();
@@ -317,6 +323,7 @@ pub const CONTENT_MIGRATIONS: &[fn(&mut Catalog) -> Result<(), anyhow::Error>] =
             let serialized_item = SerializedCatalogItem::V1 {
                 create_sql: stmt.to_ast_string_stable(),
                 eval_env,
+                schema_history,
             };
 
             let serialized_item =
@@ -343,6 +350,7 @@ pub const CONTENT_MIGRATIONS: &[fn(&mut Catalog) -> Result<(), anyhow::Error>] =
             let SerializedCatalogItem::V1 {
                 create_sql,
                 eval_env,
+                schema_history,
             } = serde_json::from_slice(&def)?;
 
             let stmt = sql::parse::parse(&create_sql)?.into_element();
@@ -352,6 +360,7 @@ pub const CONTENT_MIGRATIONS: &[fn(&mut Catalog) -> Result<(), anyhow::Error>] =
             let serialized_item = SerializedCatalogItem::V1 {
                 create_sql: resolved.to_ast_string_stable(),
                 eval_env,
+                schema_history,
             };
 
             let serialized_item =
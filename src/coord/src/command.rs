@@ -238,6 +238,8 @@ pub enum ExecuteResponse {
     Inserted(usize),
     /// Rows will be delivered via the specified future.
     SendingRows(#[derivative(Debug = "ignore")] RowsFuture),
+    /// The characteristics of the current transaction were set.
+    SetTransaction,
     /// The specified variable was set to a new value.
     SetVariable {
         name: String,
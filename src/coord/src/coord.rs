@@ -67,8 +67,9 @@ use dataflow::{
 };
 use dataflow_types::logging::LoggingConfig as DataflowLoggingConfig;
 use dataflow_types::{
-    DataflowDesc, ExternalSourceConnector, IndexDesc, PeekResponse, PostgresSourceConnector,
-    SinkConnector, SourceConnector, TailSinkConnector, TimestampSourceUpdate, Update,
+    AvroEncoding, DataEncoding, DataflowDesc, ExternalSourceConnector, IndexDesc, PeekResponse,
+    PostgresSourceConnector, SinkConnector, SourceConnector, TailSinkConnector,
+    TimestampSourceUpdate, Update,
 };
 use dataflow_types::{SinkAsOf, SinkEnvelope};
 use expr::{
@@ -76,6 +77,7 @@ use expr::{
     OptimizedMirRelationExpr,
 };
 use ore::antichain::AntichainToken;
+use ore::collections::CollectionExt;
 use ore::str::StrExt;
 use ore::thread::{JoinHandleExt, JoinOnDropHandle};
 use repr::{ColumnName, Datum, RelationDesc, Row, Timestamp};
@@ -83,20 +85,20 @@ use sql::ast::display::AstDisplay;
 use sql::ast::{
     Connector, CreateIndexStatement, CreateSchemaStatement, CreateSinkStatement,
     CreateSourceStatement, CreateTableStatement, DropObjectsStatement, ExplainStage,
-    FetchStatement, Ident, ObjectType, Raw, Statement,
+    FetchStatement, Ident, ObjectType, Raw, SetVariableStatement, Statement,
 };
 use sql::catalog::{Catalog as _, CatalogError};
 use sql::names::{DatabaseSpecifier, FullName};
 use sql::plan::StatementDesc;
 use sql::plan::{
-    AlterIndexResetOptionsPlan, AlterIndexSetOptionsPlan, AlterItemRenamePlan, CreateDatabasePlan,
-    CreateIndexPlan, CreateRolePlan, CreateSchemaPlan, CreateSinkPlan, CreateSourcePlan,
-    CreateTablePlan, CreateTypePlan, CreateViewPlan, CreateViewsPlan, DropDatabasePlan,
-    DropItemsPlan, DropRolesPlan, DropSchemaPlan, ExplainPlan, FetchPlan, IndexOption,
-    IndexOptionName, InsertPlan, MutationKind, Params, PeekPlan, PeekWhen, Plan, PlanContext,
-    SendDiffsPlan, SetVariablePlan, ShowVariablePlan, Source, TailPlan,
+    AlterIndexResetOptionsPlan, AlterIndexSetOptionsPlan, AlterItemRenamePlan,
+    AlterSourceRefreshSchemaPlan, CreateDatabasePlan, CreateIndexPlan, CreateRolePlan,
+    CreateSchemaPlan, CreateSinkPlan, CreateSourcePlan, CreateTablePlan, CreateTypePlan,
+    CreateViewPlan, CreateViewsPlan, DropDatabasePlan, DropItemsPlan, DropRolesPlan,
+    DropSchemaPlan, ExplainPlan, FetchPlan, IndexOption, IndexOptionName, InsertPlan, MutationKind,
+    Params, PeekPlan, PeekWhen, Plan, PlanContext, SendDiffsPlan, SetVariablePlan,
+    ShowVariablePlan, Source, TailFrom, TailPlan,
 };
-use storage::Message as PersistedMessage;
 use transform::Optimizer;
 
 use self::arrangement_state::{ArrangementFrontiers, Frontiers, SinkWrites};
@@ -110,7 +112,8 @@ use crate::command::{
 use crate::error::CoordError;
 use crate::persistence::{PersistenceConfig, PersistentTables};
 use crate::session::{
-    EndTransactionAction, PreparedStatement, Session, TransactionOps, TransactionStatus, WriteOp,
+    EndTransactionAction, IsolationLevel, PreparedStatement, Session, TransactionOps,
+    TransactionStatus, WriteOp,
 };
 use crate::sink_connector;
 use crate::timestamp::{TimestampMessage, Timestamper};
@@ -219,6 +222,11 @@ pub struct Coordinator {
     read_lower_bound: Timestamp,
     /// The timestamp that all local inputs have been advanced up to.
     closed_up_to: Timestamp,
+    /// The greatest timestamp assigned to a strict serializable read. Later
+    /// strict serializable reads are never assigned a lesser timestamp, so
+    /// that they observe everything observed by reads that completed before
+    /// they began.
+    strict_read_ts: Timestamp,
     /// Whether or not the most recent operation was a read.
     last_op_was_read: bool,
     /// Whether we need to advance local inputs (i.e., did someone observe a timestamp).
@@ -381,7 +389,10 @@ impl Coordinator {
                         // that everything else uses?
                         let frontiers = self.new_frontiers(entry.id(), Some(0), Some(1_000));
                         self.indexes.insert(entry.id(), frontiers);
-                    } else {
+                    } else if !self.indexes.contains_key(entry.id()) {
+                        // Indexes on a dead-letter companion are built in the
+                        // dataflow of an index on its source, which may have
+                        // already been shipped.
                         let df = self.dataflow_builder().build_index_dataflow(entry.id());
                         self.ship_dataflow(df).await;
                     }
@@ -390,42 +401,15 @@ impl Coordinator {
             }
         }
 
+        let mut persisted_updates = vec![];
         for entry in entries {
             match entry.item() {
                 CatalogItem::View(_) => (),
                 CatalogItem::Table(_) => {
-                    // TODO gross hack for now
-                    if !entry.id().is_system() {
-                        if let Some(tables) = &mut self.persisted_tables {
-                            if let Some(messages) = tables.resume(entry.id()) {
-                                let mut updates = vec![];
-                                for persisted_message in messages.into_iter() {
-                                    match persisted_message {
-                                        PersistedMessage::Progress(time) => {
-                                            // Send the messages accumulated so far + update
-                                            // progress
-                                            // TODO: I think we need to avoid downgrading capabilities until
-                                            // all rows have been sent so the table is not visible for reads
-                                            // before being fully reloaded.
-                                            let updates = std::mem::replace(&mut updates, vec![]);
-                                            if !updates.is_empty() {
-                                                self.broadcast(SequencedCommand::Insert {
-                                                    id: entry.id(),
-                                                    updates,
-                                                });
-                                                self.broadcast(
-                                                    SequencedCommand::AdvanceAllLocalInputs {
-                                                        advance_to: time,
-                                                    },
-                                                );
-                                            }
-                                        }
-                                        PersistedMessage::Data(update) => {
-                                            updates.push(update);
-                                        }
-                                    }
-                                }
-                            }
+                    if let Some(tables) = &mut self.persisted_tables {
+                        let updates = tables.resume(entry.id())?;
+                        if !updates.is_empty() {
+                            persisted_updates.push((entry.id(), updates));
                         }
                     }
                 }
@@ -446,6 +430,23 @@ impl Coordinator {
             }
         }
 
+        // Rehydrate persisted tables. Every table must be fully reloaded before
+        // the local inputs are advanced, both so that no table is visible to
+        // reads before it is complete and because the inputs cannot accept
+        // updates at times they have already been advanced past.
+        let mut advance_to = self.closed_up_to;
+        for (id, updates) in persisted_updates {
+            if let Some(max_ts) = updates.iter().map(|u| u.timestamp).max() {
+                advance_to = cmp::max(advance_to, max_ts + 1);
+            }
+            self.broadcast(SequencedCommand::Insert { id, updates });
+        }
+        if advance_to > self.closed_up_to {
+            self.broadcast(SequencedCommand::AdvanceAllLocalInputs { advance_to });
+            self.closed_up_to = advance_to;
+            self.read_lower_bound = cmp::max(self.read_lower_bound, advance_to);
+        }
+
         self.send_builtin_table_updates(builtin_table_updates).await;
 
         // Announce primary and foreign key relationships.
@@ -578,9 +579,6 @@ impl Coordinator {
                         > self.closed_up_to / self.logging_granularity.unwrap()
             {
                 if next_ts > self.closed_up_to {
-                    if let Some(tables) = &mut self.persisted_tables {
-                        tables.write_progress(next_ts);
-                    }
                     self.broadcast(SequencedCommand::AdvanceAllLocalInputs {
                         advance_to: next_ts,
                     });
@@ -839,14 +837,24 @@ impl Coordinator {
                             // being executed). Failed transactions have already been checked in pgwire for
                             // a safe statement (COMMIT, ROLLBACK, etc.) and can also proceed.
                             &TransactionStatus::Started(_) | &TransactionStatus::Failed => {
-                                if let Statement::Declare(_) = stmt {
-                                    // Declare is an exception. Although it's not against any spec to execute
-                                    // it, it will always result in nothing happening, since all portals will be
-                                    // immediately closed. Users don't know this detail, so this error helps them
-                                    // understand what's going wrong. Postgres does this too.
+                                // Declare is an exception. Although it's not against any spec to execute
+                                // it, it will always result in nothing happening, since all portals will be
+                                // immediately closed. Users don't know this detail, so this error helps them
+                                // understand what's going wrong. Postgres does this too. The same goes for
+                                // SET LOCAL and SET TRANSACTION, whose effects end with the transaction.
+                                let op = match &stmt {
+                                    Statement::Declare(_) => Some("DECLARE CURSOR"),
+                                    Statement::SetVariable(SetVariableStatement {
+                                        local: true,
+                                        ..
+                                    }) => Some("SET LOCAL"),
+                                    Statement::SetTransaction(_) => Some("SET TRANSACTION"),
+                                    _ => None,
+                                };
+                                if let Some(op) = op {
                                     let _ = tx.send(Response {
                                         result: Err(CoordError::OperationRequiresTransaction(
-                                            "DECLARE CURSOR".into(),
+                                            op.into(),
                                         )),
                                         session,
                                     });
@@ -878,6 +886,7 @@ impl Coordinator {
                                 | Statement::Rollback(_)
                                 | Statement::Select(_)
                                 | Statement::SetTransaction(_)
+                                | Statement::SetVariable(_)
                                 | Statement::ShowColumns(_)
                                 | Statement::ShowCreateIndex(_)
                                 | Statement::ShowCreateSink(_)
@@ -896,6 +905,7 @@ impl Coordinator {
                                 // Statements below must by run singly (in Started).
                                 Statement::AlterIndexOptions(_)
                                 | Statement::AlterObjectRename(_)
+                                | Statement::AlterSourceRefreshSchema(_)
                                 | Statement::CreateDatabase(_)
                                 | Statement::CreateIndex(_)
                                 | Statement::CreateRole(_)
@@ -909,7 +919,6 @@ impl Coordinator {
                                 | Statement::Delete(_)
                                 | Statement::DropDatabase(_)
                                 | Statement::DropObjects(_)
-                                | Statement::SetVariable(_)
                                 | Statement::Update(_) => {
                                     let _ = tx.send(Response {
                                         result: Err(CoordError::OperationProhibitsTransaction(
@@ -1098,7 +1107,31 @@ impl Coordinator {
 
         if !since_updates.is_empty() {
             if let Some(tables) = &mut self.persisted_tables {
-                tables.allow_compaction(&since_updates);
+                // Tables don't have frontiers of their own, so their persisted
+                // data can be compacted up to the least `since` of the indexes
+                // that are built from it.
+                let mut table_sinces = vec![];
+                for (id, _) in &since_updates {
+                    let table_id = match self.catalog.try_get_by_id(*id).map(|e| e.item()) {
+                        Some(CatalogItem::Index(index)) if tables.is_persisted(index.on) => {
+                            index.on
+                        }
+                        _ => continue,
+                    };
+                    let since = self
+                        .catalog
+                        .indexes()
+                        .get(&table_id)
+                        .into_iter()
+                        .flatten()
+                        .filter_map(|(index_id, _)| self.indexes.since_of(index_id))
+                        .filter_map(|since| since.elements().first().copied())
+                        .min();
+                    if let Some(since) = since {
+                        table_sinces.push((table_id, since));
+                    }
+                }
+                tables.allow_compaction(&table_sinces);
             }
             self.broadcast(SequencedCommand::AllowCompaction(since_updates));
         }
@@ -1274,7 +1307,7 @@ impl Coordinator {
         ];
         self.catalog_transact(ops).await?;
         let as_of = SinkAsOf {
-            frontier: self.determine_frontier(sink.from),
+            frontier: self.determine_frontier(&[sink.from]),
             strict: !sink.with_snapshot,
         };
         let df = self.dataflow_builder().build_sink_dataflow(
@@ -1386,15 +1419,34 @@ impl Coordinator {
                     session,
                 );
             }
-            Plan::StartTransaction => {
+            Plan::StartTransaction(plan) => {
                 let duplicated =
                     matches!(session.transaction(), TransactionStatus::InTransaction(_));
-                session.start_transaction();
+                // Like PostgreSQL, a `BEGIN` in a transaction block is ignored
+                // entirely, including its transaction modes.
+                let res = if duplicated {
+                    Ok(())
+                } else {
+                    session.start_transaction();
+                    session.set_transaction_modes(
+                        plan.access,
+                        plan.isolation_level.map(IsolationLevel::from),
+                    )
+                };
                 tx.send(
-                    Ok(ExecuteResponse::StartedTransaction { duplicated }),
+                    res.map(|()| ExecuteResponse::StartedTransaction { duplicated }),
                     session,
                 )
             }
+            Plan::SetTransaction(plan) => {
+                let res = session
+                    .set_transaction_modes(
+                        plan.access,
+                        plan.isolation_level.map(IsolationLevel::from),
+                    )
+                    .map(|()| ExecuteResponse::SetTransaction);
+                tx.send(res, session);
+            }
 
             Plan::CommitTransaction | Plan::AbortTransaction => {
                 let action = match plan {
@@ -1445,6 +1497,12 @@ impl Coordinator {
             Plan::AlterItemRename(plan) => {
                 tx.send(self.sequence_alter_item_rename(plan).await, session);
             }
+            Plan::AlterSourceRefreshSchema(plan) => {
+                tx.send(
+                    self.sequence_alter_source_refresh_schema(pcx, plan).await,
+                    session,
+                );
+            }
             Plan::AlterIndexSetOptions(plan) => {
                 tx.send(self.sequence_alter_index_set_options(plan), session);
             }
@@ -1625,7 +1683,7 @@ impl Coordinator {
         {
             Ok(_) => {
                 if let Some(tables) = &mut self.persisted_tables {
-                    tables.create(table_id);
+                    tables.create(table_id)?;
                 }
                 let df = self.dataflow_builder().build_index_dataflow(index_id);
                 self.ship_dataflow(df).await;
@@ -1679,18 +1737,24 @@ impl Coordinator {
     }
 
     async fn ship_sources(&mut self, metadata: Vec<(GlobalId, Option<GlobalId>, bool)>) {
-        for (source_id, idx_id, caching_enabled) in metadata {
-            // Do everything to instantiate the source at the coordinator and
-            // inform the timestamper and dataflow workers of its existence before
-            // shipping any dataflows that depend on its existence.
-            self.update_timestamper(source_id, true).await;
-            self.maybe_begin_caching(source_id, caching_enabled).await;
+        // Do everything to instantiate the sources at the coordinator and
+        // inform the timestamper and dataflow workers of their existence before
+        // shipping any dataflows that depend on their existence.
+        for (source_id, _, caching_enabled) in &metadata {
+            self.update_timestamper(*source_id, true).await;
+            self.maybe_begin_caching(*source_id, *caching_enabled).await;
             let frontiers =
-                self.new_frontiers(source_id, Some(0), self.logical_compaction_window_ms);
-            self.sources.insert(source_id, frontiers);
+                self.new_frontiers(*source_id, Some(0), self.logical_compaction_window_ms);
+            self.sources.insert(*source_id, frontiers);
+        }
+        for (_, idx_id, _) in metadata {
             if let Some(index_id) = idx_id {
-                let df = self.dataflow_builder().build_index_dataflow(index_id);
-                self.ship_dataflow(df).await;
+                // The indexes of a dead-letter companion may already have been
+                // built in the dataflow of an index on its source.
+                if !self.indexes.contains_key(index_id) {
+                    let df = self.dataflow_builder().build_index_dataflow(index_id);
+                    self.ship_dataflow(df).await;
+                }
             }
         }
     }
@@ -1708,6 +1772,7 @@ impl Coordinator {
                 name,
                 source,
                 materialized,
+                dead_letter_name,
                 ..
             } = plan;
             let optimized_expr = self
@@ -1721,43 +1786,62 @@ impl Coordinator {
                 connector: source.connector,
                 bare_desc: source.bare_desc,
                 desc: transformed_desc,
+                schema_history: vec![],
             };
             let source_id = self.catalog.allocate_id()?;
-            let source_oid = self.catalog.allocate_oid()?;
-            ops.push(catalog::Op::CreateItem {
-                id: source_id,
-                oid: source_oid,
-                name: name.clone(),
-                item: CatalogItem::Source(source.clone()),
-            });
-            let index_id = if materialized {
-                let mut index_name = name.clone();
-                index_name.item += "_primary_idx";
-                index_name = self
-                    .catalog
-                    .for_session(session)
-                    .find_available_name(index_name);
-                let index = auto_generate_primary_idx(
-                    index_name.item.clone(),
-                    name,
+            // A dead-lettering source presents the records that it fails to
+            // decode via a companion source, which is created and materialized
+            // along with it.
+            let dead_letters = dead_letter_name.map(|dead_letter_name| {
+                let dead_letters = catalog::Source::dead_letters(
                     source_id,
-                    &source.desc,
-                    None,
-                    vec![source_id],
+                    source.create_sql.clone(),
+                    pcx.clone(),
                 );
-                let index_id = self.catalog.allocate_id()?;
-                let index_oid = self.catalog.allocate_oid()?;
+                (dead_letter_name, dead_letters)
+            });
+            let mut sources = vec![(source_id, name, source)];
+            if let Some((dead_letter_name, dead_letters)) = dead_letters {
+                let dead_letters_id = self.catalog.allocate_id()?;
+                sources.push((dead_letters_id, dead_letter_name, dead_letters));
+            }
+            for (source_id, name, source) in sources {
+                let source_oid = self.catalog.allocate_oid()?;
                 ops.push(catalog::Op::CreateItem {
-                    id: index_id,
-                    oid: index_oid,
-                    name: index_name,
-                    item: CatalogItem::Index(index),
+                    id: source_id,
+                    oid: source_oid,
+                    name: name.clone(),
+                    item: CatalogItem::Source(source.clone()),
                 });
-                Some(index_id)
-            } else {
-                None
-            };
-            metadata.push((source_id, index_id, source.connector.caching_enabled()))
+                let index_id = if materialized {
+                    let mut index_name = name.clone();
+                    index_name.item += "_primary_idx";
+                    index_name = self
+                        .catalog
+                        .for_session(session)
+                        .find_available_name(index_name);
+                    let index = auto_generate_primary_idx(
+                        index_name.item.clone(),
+                        name,
+                        source_id,
+                        &source.desc,
+                        None,
+                        vec![source_id],
+                    );
+                    let index_id = self.catalog.allocate_id()?;
+                    let index_oid = self.catalog.allocate_oid()?;
+                    ops.push(catalog::Op::CreateItem {
+                        id: index_id,
+                        oid: index_oid,
+                        name: index_name,
+                        item: CatalogItem::Index(index),
+                    });
+                    Some(index_id)
+                } else {
+                    None
+                };
+                metadata.push((source_id, index_id, source.connector.caching_enabled()))
+            }
         }
         Ok((metadata, ops))
     }
@@ -2133,7 +2217,9 @@ impl Coordinator {
         session: &mut Session,
         plan: SetVariablePlan,
     ) -> Result<ExecuteResponse, CoordError> {
-        session.vars_mut().set(&plan.name, &plan.value)?;
+        session
+            .vars_mut()
+            .set(&plan.name, &plan.value, plan.local)?;
         Ok(ExecuteResponse::SetVariable { name: plan.name })
     }
 
@@ -2149,6 +2235,7 @@ impl Coordinator {
 
         let (drop_sinks, txn) = session.clear_transaction();
         self.drop_sinks(drop_sinks).await;
+        session.vars_mut().end_transaction(&action);
 
         // Allow compaction of sources from this transaction, regardless of the action.
         self.txn_reads.remove(&session.conn_id());
@@ -2159,12 +2246,13 @@ impl Coordinator {
         if let EndTransactionAction::Commit = action {
             match txn {
                 TransactionStatus::Default | TransactionStatus::Failed => {}
-                TransactionStatus::Started(ops)
-                | TransactionStatus::InTransaction(ops)
-                | TransactionStatus::InTransactionImplicit(ops) => {
-                    match ops {
+                TransactionStatus::Started(txn)
+                | TransactionStatus::InTransaction(txn)
+                | TransactionStatus::InTransactionImplicit(txn) => {
+                    match txn.ops {
                         TransactionOps::Writes(inserts) => {
                             let timestamp = self.get_write_ts();
+                            let mut writes = Vec::with_capacity(inserts.len());
                             for WriteOp { id, rows } in inserts {
                                 // Re-verify this id exists.
                                 if self.catalog.try_get_by_id(id).is_none() {
@@ -2181,10 +2269,16 @@ impl Coordinator {
                                         timestamp,
                                     })
                                     .collect();
+                                writes.push((id, updates));
+                            }
 
-                                if let Some(tables) = &mut self.persisted_tables {
-                                    tables.write(id, &updates);
-                                }
+                            // Only acknowledge the transaction once all of its
+                            // writes are durable. They are persisted together so
+                            // that a failure leaves none of them behind.
+                            if let Some(tables) = &mut self.persisted_tables {
+                                tables.write_atomic(&writes)?;
+                            }
+                            for (id, updates) in writes {
                                 self.broadcast(SequencedCommand::Insert { id, updates });
                             }
                         }
@@ -2223,6 +2317,7 @@ impl Coordinator {
         // single-statement transaction (TransactionStatus::Started), we don't need to
         // worry about preventing compaction or choosing a valid timestamp for future
         // queries.
+        let isolation = session.vars().transaction_isolation();
         let timestamp = if in_transaction && when == PeekWhen::Immediately {
             let timestamp = session.get_transaction_timestamp(|| {
                 // Determine a timestamp that will be valid for anything in any schema
@@ -2233,7 +2328,7 @@ impl Coordinator {
                 // We want to prevent compaction of the indexes consulted by
                 // determine_timestamp, not the ones listed in the query.
                 let (timestamp, timestamp_ids) =
-                    self.determine_timestamp(&timedomain_ids, PeekWhen::Immediately)?;
+                    self.determine_timestamp(&timedomain_ids, PeekWhen::Immediately, isolation)?;
                 let mut handles = vec![];
                 for id in timestamp_ids {
                     handles.push(self.indexes.get(&id).unwrap().since_handle(vec![timestamp]));
@@ -2275,7 +2370,8 @@ impl Coordinator {
 
             timestamp
         } else {
-            self.determine_timestamp(&source.global_uses(), when)?.0
+            self.determine_timestamp(&source.global_uses(), when, isolation)?
+                .0
         };
 
         let source = self.prep_relation_expr(
@@ -2441,9 +2537,10 @@ impl Coordinator {
         plan: TailPlan,
     ) -> Result<ExecuteResponse, CoordError> {
         let TailPlan {
-            id: source_id,
+            from,
             with_snapshot,
             ts,
+            up_to,
             copy_to,
             emit_progress,
             object_columns,
@@ -2457,44 +2554,67 @@ impl Coordinator {
             session.add_transaction_ops(TransactionOps::Tail)?;
         }
 
+        let uses_ids = match &from {
+            TailFrom::Id(id) => vec![*id],
+            TailFrom::Query { expr, .. } => expr.global_uses(),
+        };
+
         // Determine the frontier of updates to tail *from*.
         // Updates greater or equal to this frontier will be produced.
         let frontier = if let Some(ts) = ts {
             // If a timestamp was explicitly requested, use that.
             Antichain::from_elem(
-                self.determine_timestamp(&[source_id], PeekWhen::AtTimestamp(ts))?
-                    .0,
+                self.determine_timestamp(
+                    &uses_ids,
+                    PeekWhen::AtTimestamp(ts),
+                    session.vars().transaction_isolation(),
+                )?
+                .0,
             )
         } else {
-            self.determine_frontier(source_id)
+            self.determine_frontier(&uses_ids)
         };
-        let sink_name = format!(
-            "tail-source-{}",
-            self.catalog
-                .for_session(session)
-                .humanize_id(source_id)
-                .expect("Source id is known to exist in catalog")
-        );
         let sink_id = self.catalog.allocate_id()?;
-        session.add_drop_sink(sink_id);
         let (tx, rx) = mpsc::unbounded_channel();
+        let connector = SinkConnector::Tail(TailSinkConnector {
+            tx,
+            emit_progress,
+            object_columns,
+            value_desc: desc,
+            up_to,
+        });
+        let envelope = SinkEnvelope::Tail { emit_progress };
+        let as_of = SinkAsOf {
+            frontier,
+            strict: !with_snapshot,
+        };
 
-        let df = self.dataflow_builder().build_sink_dataflow(
-            sink_name,
-            sink_id,
-            source_id,
-            SinkConnector::Tail(TailSinkConnector {
-                tx,
-                emit_progress,
-                object_columns,
-                value_desc: desc,
-            }),
-            SinkEnvelope::Tail { emit_progress },
-            SinkAsOf {
-                frontier,
-                strict: !with_snapshot,
-            },
-        );
+        let df = match from {
+            TailFrom::Id(source_id) => {
+                let sink_name = format!(
+                    "tail-source-{}",
+                    self.catalog
+                        .for_session(session)
+                        .humanize_id(source_id)
+                        .expect("Source id is known to exist in catalog")
+                );
+                self.dataflow_builder()
+                    .build_sink_dataflow(sink_name, sink_id, source_id, connector, envelope, as_of)
+            }
+            TailFrom::Query { expr, desc } => {
+                // Build a transient view for the query, which the tail sink
+                // then reads from within the same dataflow.
+                let view_id = self.allocate_transient_id()?;
+                let expr = self.prep_relation_expr(expr, ExprPrepStyle::Static)?;
+                let mut df = DataflowDesc::new(format!("tail-query-{}", view_id));
+                df.set_as_of(as_of.frontier.clone());
+                self.dataflow_builder()
+                    .import_view_into_dataflow(&view_id, &expr, &mut df);
+                df.add_sink_export(sink_id, view_id, desc, connector, envelope, as_of);
+                df
+            }
+        };
+        session.add_drop_sink(sink_id);
         self.ship_dataflow(df).await;
 
         let resp = ExecuteResponse::Tailing { rx };
@@ -2515,10 +2635,16 @@ impl Coordinator {
     /// traces (each has a `since` and `upper` frontier, and are only valid after
     /// `since` and sure to be available not after `upper`). The set of indexes
     /// used is also returned.
+    ///
+    /// Under the strict serializable isolation level, a peek that happens
+    /// immediately is assigned a timestamp no less than that of any prior
+    /// strict serializable peek, even if that means waiting for its inputs to
+    /// advance.
     fn determine_timestamp(
         &mut self,
         uses_ids: &[GlobalId],
         when: PeekWhen,
+        isolation: IsolationLevel,
     ) -> Result<(Timestamp, Vec<GlobalId>), CoordError> {
         // Each involved trace has a validity interval `[since, upper)`.
         // The contents of a trace are only guaranteed to be correct when
@@ -2555,7 +2681,8 @@ impl Coordinator {
                         For more details, see https://materialize.com/s/non-materialized-error"
                     );
                 }
-                let mut candidate = if uses_ids.iter().any(|id| self.catalog.uses_tables(*id)) {
+                let uses_tables = uses_ids.iter().any(|id| self.catalog.uses_tables(*id));
+                let mut candidate = if uses_tables {
                     // If the view depends on any tables, we enforce
                     // linearizability by choosing the latest input time.
                     self.get_read_ts()
@@ -2602,6 +2729,23 @@ impl Coordinator {
                 if !since.less_equal(&candidate) {
                     candidate.advance_by(since.borrow());
                 }
+                if isolation == IsolationLevel::StrictSerializable
+                    && candidate != Timestamp::max_value()
+                {
+                    // Never read at a time earlier than a prior strict
+                    // serializable read. The peek will wait for any inputs
+                    // that have not yet advanced past the candidate.
+                    candidate = cmp::max(candidate, self.strict_read_ts);
+                    self.strict_read_ts = candidate;
+                    if uses_tables && candidate > self.read_lower_bound {
+                        // Ensure that the local inputs are advanced past the
+                        // candidate, and that subsequent writes are assigned
+                        // later timestamps.
+                        self.read_lower_bound = candidate;
+                        self.last_op_was_read = true;
+                        self.need_advance = true;
+                    }
+                }
                 candidate
             }
         };
@@ -2630,11 +2774,11 @@ impl Coordinator {
         }
     }
 
-    /// Determine the frontier of updates to start *from* for a sink based on
-    /// `source_id`.
+    /// Determine the frontier of updates to start *from* for a sink that reads
+    /// from `uses_ids`.
     ///
     /// Updates greater or equal to this frontier will be produced.
-    fn determine_frontier(&mut self, source_id: GlobalId) -> Antichain<Timestamp> {
+    fn determine_frontier(&mut self, uses_ids: &[GlobalId]) -> Antichain<Timestamp> {
         // This function differs from determine_timestamp because sinks/tail don't care
         // about indexes existing or timestamps being complete. If data don't exist
         // yet (upper = 0), it is not a problem for the sink to wait for it. If the
@@ -2642,12 +2786,12 @@ impl Coordinator {
         // produce timestamps describing when the diff occurred, so users can determine
         // if that's fresh enough.
 
-        // If source_id is already indexed, then nearest_indexes will return the
+        // If an id is already indexed, then nearest_indexes will return the
         // same index that default_index_for does, so we can stick with only using
         // nearest_indexes. We don't care about the indexes being incomplete because
         // callers of this function (CREATE SINK and TAIL) are responsible for creating
         // indexes if needed.
-        let (index_ids, unmaterialized_source_ids) = self.catalog.nearest_indexes(&[source_id]);
+        let (index_ids, unmaterialized_source_ids) = self.catalog.nearest_indexes(uses_ids);
         let mut since = self.indexes.least_valid_since(index_ids.iter().copied());
         since.join_assign(
             &self
@@ -2837,6 +2981,117 @@ impl Coordinator {
         }
     }
 
+    async fn sequence_alter_source_refresh_schema(
+        &mut self,
+        pcx: PlanContext,
+        plan: AlterSourceRefreshSchemaPlan,
+    ) -> Result<ExecuteResponse, CoordError> {
+        let AlterSourceRefreshSchemaPlan {
+            id,
+            source,
+            column_mapping,
+        } = plan;
+        let entry = self.catalog.get_by_id(&id);
+        let schema_history = match entry.item() {
+            CatalogItem::Source(old_source) => {
+                let mut schema_history = old_source.schema_history.clone();
+                if let SourceConnector::External { encoding, .. } = &old_source.connector {
+                    if let DataEncoding::Avro(AvroEncoding { schema, .. }) = encoding.value_ref() {
+                        schema_history.push(schema.clone());
+                    }
+                }
+                schema_history
+            }
+            _ => unreachable!("planning verified that the item is a source"),
+        };
+        let optimized_expr = self
+            .optimizer
+            .optimize(source.expr, self.catalog.indexes())?;
+        let desc = RelationDesc::new(optimized_expr.0.typ(), source.column_names);
+        let typ = desc.typ().clone();
+        let mut ops = vec![catalog::Op::UpdateItem {
+            id,
+            to_item: CatalogItem::Source(catalog::Source {
+                create_sql: source.create_sql,
+                plan_cx: pcx,
+                optimized_expr,
+                connector: source.connector,
+                bare_desc: source.bare_desc,
+                desc,
+                schema_history,
+            }),
+        }];
+
+        // Existing dependents continue to see the source as it was: views read
+        // the refreshed source through a projection onto the old columns, and
+        // indexes refer to the new positions of their key columns. Views'
+        // definitions are rewritten to select the old columns explicitly, so
+        // that they are planned the same way when the catalog is reloaded.
+        let old_columns: Vec<_> = entry
+            .desc()
+            .expect("sources have descriptions")
+            .iter_names()
+            .map(|name| Ident::new(name.map(|n| n.as_str()).unwrap_or("?column?")))
+            .collect();
+        let mut indexes = vec![];
+        for dependent_id in entry.used_by() {
+            let to_item = match self.catalog.get_by_id(dependent_id).item() {
+                CatalogItem::View(view) => {
+                    let mut view = view.clone();
+                    let mut create_stmt = sql::parse::parse(&view.create_sql)
+                        .map_err(|e| CoordError::Unstructured(e.into()))?
+                        .into_element();
+                    sql::ast::transform::create_stmt_pin_columns(
+                        &mut create_stmt,
+                        entry.name().clone(),
+                        &old_columns,
+                    );
+                    view.create_sql = create_stmt.to_ast_string_stable();
+                    view.optimized_expr.0.visit_mut(&mut |e| {
+                        if let MirRelationExpr::Get {
+                            id: Id::Global(get_id),
+                            ..
+                        } = e
+                        {
+                            if *get_id == id {
+                                *e = MirRelationExpr::Get {
+                                    id: Id::Global(id),
+                                    typ: typ.clone(),
+                                }
+                                .project(column_mapping.clone());
+                            }
+                        }
+                    });
+                    CatalogItem::View(view)
+                }
+                CatalogItem::Index(index) if index.on == id => {
+                    let mut index = index.clone();
+                    for key in &mut index.keys {
+                        key.permute(&column_mapping);
+                    }
+                    indexes.push(*dependent_id);
+                    CatalogItem::Index(index)
+                }
+                _ => continue,
+            };
+            ops.push(catalog::Op::UpdateItem {
+                id: *dependent_id,
+                to_item,
+            });
+        }
+        self.catalog_transact(ops).await?;
+
+        // Rebuild the indexes on the source so that they read with the
+        // refreshed schema. Dataflows built on the old indexes hold on to
+        // their arrangements until they are dropped.
+        for index_id in indexes {
+            self.drop_indexes(vec![index_id]).await;
+            let df = self.dataflow_builder().build_index_dataflow(index_id);
+            self.ship_dataflow(df).await;
+        }
+        Ok(ExecuteResponse::AlteredObject(ObjectType::Source))
+    }
+
     fn sequence_alter_index_set_options(
         &mut self,
         plan: AlterIndexSetOptionsPlan,
@@ -2912,7 +3167,7 @@ impl Coordinator {
         if !sources_to_drop.is_empty() {
             for &id in &sources_to_drop {
                 if let Some(tables) = &mut self.persisted_tables {
-                    tables.destroy(id);
+                    tables.destroy(id)?;
                 }
                 self.update_timestamper(id, false).await;
                 self.catalog.delete_timestamp_bindings(id)?;
@@ -3250,10 +3505,9 @@ pub async fn serve(
         None
     };
 
-    let persisted_tables = if let Some(persistence_config) = &persistence_config {
-        PersistentTables::new(persistence_config)
-    } else {
-        None
+    let persisted_tables = match &persistence_config {
+        Some(persistence_config) => Some(PersistentTables::new(persistence_config)?),
+        None => None,
     };
     let (internal_cmd_tx, internal_cmd_rx) = mpsc::unbounded_channel();
 
@@ -3284,6 +3538,7 @@ pub async fn serve(
         command_receivers: worker_rxs,
         timely_worker,
         experimental_mode,
+        upsert_state_directory: data_directory.join("upsert"),
     })
     .map_err(|s| CoordError::Unstructured(anyhow!("{}", s)))?;
 
@@ -3357,6 +3612,7 @@ pub async fn serve(
                 cache_tx,
                 closed_up_to: 1,
                 read_lower_bound: 1,
+                strict_read_ts: 0,
                 last_op_was_read: false,
                 need_advance: true,
                 transient_id_counter: 1,
@@ -77,8 +77,15 @@ impl<'a> DataflowBuilder<'a> {
                         SourceConnector::Local,
                         table.desc.clone(),
                         *id,
+                        None,
                     );
                 }
+                CatalogItem::Source(catalog::Source {
+                    connector: SourceConnector::DeadLetters(source_id),
+                    ..
+                }) => {
+                    self.import_dead_letters_into_dataflow(*source_id, dataflow);
+                }
                 CatalogItem::Source(source) => {
                     // If Materialize has caching enabled, check to see if the source has any
                     // already cached data that can be reused, and if so, augment the source
@@ -109,6 +116,7 @@ impl<'a> DataflowBuilder<'a> {
 
                     // Default back to the regular connector if we didn't get a augmented one.
                     let connector = connector.unwrap_or_else(|| source.connector.clone());
+                    let dead_letters = self.dead_letters_id(*id);
 
                     if source.optimized_expr.0.is_trivial_source() {
                         dataflow.add_source_import(
@@ -117,6 +125,7 @@ impl<'a> DataflowBuilder<'a> {
                             connector,
                             source.bare_desc.clone(),
                             *id,
+                            dead_letters,
                         );
                     } else {
                         // From the dataflow layer's perspective, the source transformation is just a view (across which it should be able to do whole-dataflow optimizations).
@@ -128,6 +137,7 @@ impl<'a> DataflowBuilder<'a> {
                             connector,
                             source.bare_desc.clone(),
                             *id,
+                            dead_letters,
                         );
                         let mut transformation = source.optimized_expr.clone();
                         transformation.0.visit_mut(&mut |node| {
@@ -150,6 +160,71 @@ impl<'a> DataflowBuilder<'a> {
         }
     }
 
+    /// Imports the records that the source with ID `source_id` fails to decode
+    /// into the provided dataflow description. They are presented by the
+    /// instances of the source in the dataflow, under the ID of the source's
+    /// dead-letter companion.
+    fn import_dead_letters_into_dataflow(
+        &mut self,
+        source_id: GlobalId,
+        dataflow: &mut DataflowDesc,
+    ) {
+        if dataflow
+            .source_imports
+            .values()
+            .any(|(_, orig_id)| *orig_id == source_id)
+        {
+            return;
+        }
+
+        // Unless the source is read through an index, importing it yields an
+        // instance of it.
+        let indexed = self.catalog.indexes()[&source_id]
+            .iter()
+            .any(|(id, _keys)| self.indexes.contains_key(*id));
+        if !indexed {
+            self.import_into_dataflow(&source_id, dataflow);
+            return;
+        }
+
+        let entry = self.catalog.get_by_id(&source_id);
+        let source = match entry.item() {
+            CatalogItem::Source(source) => source,
+            _ => unreachable!("dead letters belong to sources"),
+        };
+        let transient_id = *self.transient_id_counter;
+        *self.transient_id_counter = transient_id
+            .checked_add(1)
+            .expect("id counter overflows i64");
+        dataflow.add_source_import(
+            entry.name().to_string(),
+            GlobalId::Transient(transient_id),
+            source.connector.clone(),
+            source.bare_desc.clone(),
+            source_id,
+            self.dead_letters_id(source_id),
+        );
+    }
+
+    /// Returns the ID of the dead-letter companion of the source with the
+    /// specified ID, if it has one.
+    fn dead_letters_id(&self, source_id: GlobalId) -> Option<GlobalId> {
+        self.catalog
+            .get_by_id(&source_id)
+            .used_by()
+            .iter()
+            .copied()
+            .find(|id| {
+                matches!(
+                    self.catalog.get_by_id(id).item(),
+                    CatalogItem::Source(catalog::Source {
+                        connector: SourceConnector::DeadLetters(_),
+                        ..
+                    })
+                )
+            })
+    }
+
     /// Imports the view with the specified ID and expression into the provided
     /// dataflow description.
     pub fn import_view_into_dataflow(
@@ -204,6 +279,34 @@ impl<'a> DataflowBuilder<'a> {
         self.import_into_dataflow(&on_id, &mut dataflow);
         dataflow.add_index_to_build(id, on_id.clone(), on_type.clone(), keys.clone());
         dataflow.add_index_export(id, on_id, on_type, keys);
+
+        // The instance of a dead-lettering source that this dataflow reads also
+        // presents the records that the source fails to decode. Build any
+        // indexes on those that do not exist yet here too, rather than reading
+        // the source again in a dataflow of their own.
+        let reads_source = dataflow
+            .source_imports
+            .values()
+            .any(|(_, orig_id)| *orig_id == on_id);
+        if let (true, Some(dead_letters_id)) = (reads_source, self.dead_letters_id(on_id)) {
+            let dead_letters_type = SourceConnector::dead_letters_desc().typ().clone();
+            for (index_id, keys) in &self.catalog.indexes()[&dead_letters_id] {
+                if !self.indexes.contains_key(*index_id) {
+                    dataflow.add_index_to_build(
+                        *index_id,
+                        dead_letters_id,
+                        dead_letters_type.clone(),
+                        keys.clone(),
+                    );
+                    dataflow.add_index_export(
+                        *index_id,
+                        dead_letters_id,
+                        dead_letters_type.clone(),
+                        keys.clone(),
+                    );
+                }
+            }
+        }
         dataflow
     }
 
@@ -32,10 +32,18 @@ pub enum CoordError {
     IdExhaustionError,
     /// The value for the specified parameter does not have the right type.
     InvalidParameterType(&'static (dyn Var + Send + Sync)),
+    /// The value for the specified parameter is not one of its valid values.
+    InvalidParameterValue {
+        parameter: &'static (dyn Var + Send + Sync),
+        value: String,
+        valid_values: Option<&'static [&'static str]>,
+    },
     /// The named operation cannot be run in a transaction.
     OperationProhibitsTransaction(String),
     /// The named operation requires an active transaction.
     OperationRequiresTransaction(String),
+    /// An error occurred while persisting or reading persisted data.
+    Persistence(persist::error::Error),
     /// The transaction is in read-only mode.
     ReadOnlyTransaction,
     /// The specified session parameter is read-only.
@@ -88,6 +96,10 @@ impl CoordError {
         match self {
             CoordError::Catalog(c) => c.hint(),
             CoordError::Eval(e) => e.hint(),
+            CoordError::InvalidParameterValue {
+                valid_values: Some(valid_values),
+                ..
+            } => Some(format!("Available values: {}.", valid_values.join(", "))),
             CoordError::UnknownLoginRole(_) => {
                 // TODO(benesch): this will be a bad hint when people are used
                 // to creating roles in Materialize, since they might drop the
@@ -124,12 +136,21 @@ impl fmt::Display for CoordError {
                 p.name().quoted(),
                 p.type_name().quoted()
             ),
+            CoordError::InvalidParameterValue {
+                parameter, value, ..
+            } => write!(
+                f,
+                "invalid value for parameter {}: {}",
+                parameter.name().quoted(),
+                value.quoted()
+            ),
             CoordError::OperationProhibitsTransaction(op) => {
                 write!(f, "{} cannot be run inside a transaction block", op)
             }
             CoordError::OperationRequiresTransaction(op) => {
                 write!(f, "{} can only be used in transaction blocks", op)
             }
+            CoordError::Persistence(e) => write!(f, "persistence error: {}", e),
             CoordError::ReadOnlyTransaction => f.write_str("transaction in read-only mode"),
             CoordError::ReadOnlyParameter(p) => {
                 write!(f, "parameter {} cannot be changed", p.name().quoted())
@@ -193,6 +214,12 @@ impl From<EvalError> for CoordError {
     }
 }
 
+impl From<persist::error::Error> for CoordError {
+    fn from(e: persist::error::Error) -> CoordError {
+        CoordError::Persistence(e)
+    }
+}
+
 impl From<sql::catalog::CatalogError> for CoordError {
     fn from(e: sql::catalog::CatalogError) -> CoordError {
         CoordError::SqlCatalog(e)
@@ -8,243 +8,161 @@
 // by the Apache License, Version 2.0.
 
 /// `PersistentTables` is a wrapper that encapsulates what the Coordinator needs
-/// to do to properly write data to persistent storage.
+/// to do to properly write table data to persistent storage.
 ///
-/// The intention here is to wrap the WAL and Compacter interactions in a simple
-/// API that insulates the Coordinator against potential errors and allows us to
-/// disable table persistence after any error.
+/// The intention here is to wrap the interactions with the `persist` crate in a
+/// simple API keyed by `GlobalId` and speaking in terms of `Update`s.
+use std::collections::HashMap;
 use std::path::PathBuf;
 
-use log::{debug, error};
-use timely::progress::Antichain;
-use tokio::sync::mpsc;
+use log::error;
 
 use dataflow_types::Update;
 use expr::GlobalId;
-use repr::Timestamp;
-use storage::{Compacter, CompacterMessage, Message, Trace, WriteAheadLogs};
+use persist::error::Error;
+use persist::file::{FileBlob, FileBuffer};
+use persist::persister::{Meta, Snapshot};
+use persist::storage::{StoragePersister, StorageStream};
+use persist::{Id, PersistManager};
+use repr::{Row, Timestamp};
+
+type TablePersister = StoragePersister<FileBuffer, FileBlob>;
+type TableStream = StorageStream<FileBuffer, FileBlob>;
 
 #[derive(Clone, Debug)]
 pub struct PersistenceConfig {
-    pub wals_path: PathBuf,
-    pub traces_path: PathBuf,
+    /// The directory in which persisted table data is stored.
+    pub path: PathBuf,
 }
 
 pub struct PersistentTables {
-    // Map of each table's write-ahead log
-    wals: WriteAheadLogs,
-    // Channel to send table creations, deletions and other messages to the
-    // compacter
-    compacter_tx: mpsc::UnboundedSender<CompacterMessage>,
-    // Path where all WAL files are stored
-    wals_path: PathBuf,
-    // Path where all Batch data are stored.
-    traces_path: PathBuf,
-    // Flag that indicates whether we've encountered an error while trying to
-    // persist things. If true, every subsequent operation will be a no-op.
-    disabled: bool,
+    manager: PersistManager<TablePersister>,
+    // The write and metadata handles of each persisted table.
+    tables: HashMap<GlobalId, (TableStream, TableStream)>,
 }
 
 impl PersistentTables {
     /// Create the persistent tables subsystem.
     ///
-    /// Have to spawn a Compacter task and initialize a map of WALs.
-    pub fn new(config: &PersistenceConfig) -> Option<Self> {
-        let (compacter_tx, compacter_rx) = mpsc::unbounded_channel();
-        let mut compacter = match Compacter::new(
-            compacter_rx,
-            config.traces_path.clone(),
-            config.wals_path.clone(),
-        ) {
-            Ok(compacter) => compacter,
-            Err(e) => {
-                error!(
-                    "Encountered error while trying to initialize compacter task: {:#}",
-                    e
-                );
-                error!("No further records will be persisted.");
-                return None;
-            }
-        };
-        tokio::spawn(async move { compacter.run().await });
-        let wals = match WriteAheadLogs::new(config.wals_path.clone()) {
-            Ok(wals) => wals,
-            Err(e) => {
-                error!(
-                    "encountered error while trying to initialize write-ahead log: {:#}",
-                    e
-                );
-                error!("No additional records will be persisted.");
-                return None;
-            }
-        };
-
-        Some(Self {
-            wals,
-            compacter_tx,
-            wals_path: config.wals_path.clone(),
-            traces_path: config.traces_path.clone(),
-            disabled: false,
+    /// Fails if the persisted data is in use by another process.
+    pub fn new(config: &PersistenceConfig) -> Result<Self, Error> {
+        let buffer = FileBuffer::new(config.path.join("buffer"))?;
+        let blob = FileBlob::new(config.path.join("blob"))?;
+        let persister = StoragePersister::new(buffer, blob)?;
+        Ok(PersistentTables {
+            manager: PersistManager::new(persister),
+            tables: HashMap::new(),
         })
     }
 
-    /// Permanently shut off table persistence.
-    fn disable(&mut self) {
-        self.disabled = true;
-        error!("No additional records will be persisted");
-    }
-
-    /// Reload all of the persisted data for `id` into memory.
+    /// Reload all of the persisted data for table `id`.
     ///
-    /// Reinstantiate a Trace, reread all of the batch data and unused WAL segments,
-    /// and tell the compacter to resume managing that trace.
-    pub fn resume(&mut self, id: GlobalId) -> Option<Vec<Message>> {
-        if self.disabled {
-            return None;
-        }
-
-        let persisted_trace = match Trace::resume(id, &self.traces_path, &self.wals_path) {
-            Ok(trace) => trace,
-            Err(e) => {
-                error!(
-                    "encountered error while trying to restart table {}: {:#}",
-                    id, e
-                );
-                self.disable();
-                return None;
-            }
-        };
-
-        let messages = match persisted_trace.read() {
-            Ok(messages) => Some(messages),
-            Err(e) => {
-                error!(
-                    "encountered error trying to read from persisted table {}: {:#}",
-                    id, e
-                );
-                self.disable();
-                return None;
-            }
-        };
-
-        if let Err(e) = self.wals.resume(id) {
-            error!(
-                "encountered error trying to resume write-ahead log for persisted table {}: {:#}",
-                id, e
-            );
-            self.disable();
-            return None;
-        };
-
-        if let Err(e) = self
-            .compacter_tx
-            .send(CompacterMessage::Resume(id, persisted_trace))
-        {
-            debug!("compacter dropped, disabling WAL: {}", e);
-            self.disable();
-            return None;
+    /// The returned updates have been forwarded to the table's compaction
+    /// frontier and consolidated. Tables that are not persisted, like system
+    /// tables, have no updates.
+    pub fn resume(&mut self, id: GlobalId) -> Result<Vec<Update>, Error> {
+        if !self.register(id)? {
+            return Ok(vec![]);
         }
-
-        messages
+        let (_, meta) = &self.tables[&id];
+        let mut snap = meta.snapshot()?;
+        let mut buf = Vec::new();
+        while snap.read(&mut buf) {}
+        // The persisted bytes are checked rather than trusted, as a corrupted
+        // file must not turn into a corrupted row.
+        buf.into_iter()
+            .map(|((key, _), timestamp, diff)| {
+                let row = Row::from_bytes_checked(key).map_err(|e| {
+                    Error::from(format!("invalid row persisted for table {}: {}", id, e))
+                })?;
+                Ok(Update {
+                    row,
+                    timestamp,
+                    diff,
+                })
+            })
+            .collect()
     }
 
-    /// Write progress updates to all table WALs.
-    pub fn write_progress(&mut self, timestamp: Timestamp) {
-        if self.disabled {
-            return;
-        }
-
-        if let Err(e) = self.wals.write_progress(timestamp) {
-            error!(
-                "encountered error trying to write progress at ts {}: {:#}",
-                timestamp, e
-            );
-            self.disable();
-        }
+    /// Start persisting table `id`.
+    pub fn create(&mut self, id: GlobalId) -> Result<(), Error> {
+        self.register(id)?;
+        Ok(())
     }
 
-    /// Start persisting table `id`.
+    /// Stop persisting table `id` and delete its persisted data.
     ///
-    /// Create a new WAL map entry and directory for `id` and tell the compacter
-    /// to start managing it.
-    pub fn create(&mut self, id: GlobalId) {
-        if self.disabled {
-            return;
-        }
-
-        if let Err(e) = self.wals.create(id) {
-            error!(
-                "encountered error creating table for relation {}: {:#}",
-                id, e
-            );
-            self.disable();
-            return;
-        }
-        if let Err(e) = self.compacter_tx.send(CompacterMessage::Add(id)) {
-            error!("compacter dropped, disabling WAL: {:#}", e);
-            self.disable();
+    /// Ids that are not persisted are ignored.
+    pub fn destroy(&mut self, id: GlobalId) -> Result<(), Error> {
+        if self.tables.remove(&id).is_some() {
+            self.manager
+                .destroy(persist_id(id).expect("persisted id"))?;
         }
+        Ok(())
     }
 
-    /// Stop persisting table `id`.
+    /// Synchronously write the updates of a transaction, which may span
+    /// several tables.
     ///
-    /// Tell the Compacter to drop relation `id` and also delete the persisted WAL
-    /// and batch files. Also, remove the relation from the table from the in-memory
-    /// WAL map.
-    pub fn destroy(&mut self, id: GlobalId) {
-        if self.disabled {
-            return;
-        }
-
-        if let Err(e) = self.wals.destroy(id) {
-            error!(
-                "encountered error destroying table for relation {}: {:#}",
-                id, e
-            );
-            self.disable();
-            return;
-        }
-        if let Err(e) = self.compacter_tx.send(CompacterMessage::Drop(id)) {
-            error!("compacter dropped, disabling WAL: {:#}", e);
-            self.disable();
+    /// Either all of the updates are durable once this returns successfully
+    /// or none of them are. Updates to tables that are not persisted are
+    /// ignored.
+    pub fn write_atomic(&mut self, writes: &[(GlobalId, Vec<Update>)]) -> Result<(), Error> {
+        let updates: Vec<_> = writes
+            .iter()
+            .filter(|(id, _)| self.tables.contains_key(id))
+            .map(|(id, updates)| {
+                let updates = updates
+                    .iter()
+                    .map(|u| ((u.row.data().to_vec(), vec![]), u.timestamp, u.diff))
+                    .collect();
+                (persist_id(*id).expect("persisted id"), updates)
+            })
+            .collect();
+        if updates.is_empty() {
+            return Ok(());
         }
+        self.manager.write_atomic(&updates)
     }
 
-    /// Write updates to the WAL.
-    pub fn write(&mut self, id: GlobalId, updates: &[Update]) {
-        if self.disabled {
-            return;
-        }
-        if let Err(e) = self.wals.write(id, updates) {
-            error!(
-                "encountered error writing to table for relation {}: {:#}",
-                id, e
-            );
-            self.disable();
+    /// Allow the persisted data of the given tables to be compacted up to the
+    /// associated timestamps.
+    ///
+    /// Errors are logged rather than returned, as failing to compact does not
+    /// affect correctness.
+    pub fn allow_compaction(&mut self, since_updates: &[(GlobalId, Timestamp)]) {
+        for (id, since) in since_updates {
+            if let Some((_, meta)) = self.tables.get_mut(id) {
+                if let Err(e) = meta.allow_compaction(*since) {
+                    error!("encountered error compacting persisted table {}: {}", id, e);
+                }
+            }
         }
     }
 
-    /// Tell the Compacter to advance the compaction frontiers.
-    ///
-    /// Note that we might send frontier updates for relations that are not persisted
-    /// and the Compacter knows to ignore that.
-    pub fn allow_compaction(&mut self, since_updates: &[(GlobalId, Antichain<Timestamp>)]) {
-        if self.disabled {
-            return;
-        }
+    /// Returns whether table `id` is persisted.
+    pub fn is_persisted(&self, id: GlobalId) -> bool {
+        self.tables.contains_key(&id)
+    }
 
-        for (id, times) in since_updates {
-            let times_list = times.elements();
+    /// Registers table `id` with the persister, returning whether it is
+    /// persisted.
+    fn register(&mut self, id: GlobalId) -> Result<bool, Error> {
+        let persist_id = match persist_id(id) {
+            Some(persist_id) => persist_id,
+            None => return Ok(false),
+        };
+        let token = self.manager.create_or_load(persist_id)?;
+        self.tables.insert(id, token.into_inner());
+        Ok(true)
+    }
+}
 
-            if times_list.len() == 1 {
-                if let Err(e) = self
-                    .compacter_tx
-                    .send(CompacterMessage::AllowCompaction(*id, times_list[0]))
-                {
-                    error!("compacter dropped, disabling WAL: {:#}", e);
-                    self.disable();
-                }
-            }
-        }
+/// Maps a table's `GlobalId` to the id of its persisted stream, if the table
+/// is persisted.
+fn persist_id(id: GlobalId) -> Option<Id> {
+    match id {
+        GlobalId::User(id) => Some(Id(id)),
+        GlobalId::System(_) | GlobalId::Transient(_) | GlobalId::Explain => None,
     }
 }
@@ -14,19 +14,20 @@
 use std::collections::HashMap;
 use std::mem;
 
+use anyhow::anyhow;
 use derivative::Derivative;
 use futures::Stream;
 
 use expr::GlobalId;
 use repr::{Datum, Row, ScalarType, Timestamp};
-use sql::ast::{Raw, Statement};
+use sql::ast::{Raw, Statement, TransactionAccessMode};
 use sql::plan::{Params, PlanContext, StatementDesc};
 
 use crate::error::CoordError;
 
 mod vars;
 
-pub use self::vars::{Var, Vars};
+pub use self::vars::{IsolationLevel, Var, Vars};
 
 const DUMMY_CONNECTION_ID: u32 = 0;
 
@@ -78,23 +79,27 @@ impl Session {
 
     /// Returns (and creates if empty) the default PlanContext.
     ///
-    /// This is cleared at the end of a transaction.
+    /// The wall time is cleared at the end of a transaction. The timezone
+    /// always reflects the current value of the `TimeZone` session variable.
     pub fn pcx(&mut self) -> PlanContext {
         if self.pcx == None {
             self.pcx = Some(PlanContext::default());
         }
-        self.pcx.unwrap()
+        PlanContext {
+            timezone: self.vars.timezone(),
+            ..self.pcx.unwrap()
+        }
     }
 
     /// Starts a transaction.
     pub fn start_transaction(&mut self) {
         self.transaction = match &mut self.transaction {
             TransactionStatus::Default | TransactionStatus::Started(_) => {
-                TransactionStatus::InTransaction(TransactionOps::None)
+                TransactionStatus::InTransaction(Transaction::default())
             }
-            TransactionStatus::InTransaction(ops)
-            | TransactionStatus::InTransactionImplicit(ops) => {
-                TransactionStatus::InTransaction(mem::replace(ops, TransactionOps::None))
+            TransactionStatus::InTransaction(txn)
+            | TransactionStatus::InTransactionImplicit(txn) => {
+                TransactionStatus::InTransaction(mem::take(txn))
             }
             TransactionStatus::Failed => unreachable!(),
         };
@@ -104,16 +109,47 @@ impl Session {
     pub fn start_transaction_implicit(&mut self, stmts: usize) {
         if let TransactionStatus::Default = self.transaction {
             match stmts {
-                1 => self.transaction = TransactionStatus::Started(TransactionOps::None),
+                1 => self.transaction = TransactionStatus::Started(Transaction::default()),
                 n if n > 1 => {
                     self.transaction =
-                        TransactionStatus::InTransactionImplicit(TransactionOps::None)
+                        TransactionStatus::InTransactionImplicit(Transaction::default())
                 }
                 _ => {}
             }
         }
     }
 
+    /// Sets the characteristics of the current transaction, as requested by
+    /// `BEGIN` or `SET TRANSACTION`.
+    ///
+    /// The isolation level can only be changed before the transaction has
+    /// performed any operations. Characteristics of a transaction that is not
+    /// active are ignored.
+    pub fn set_transaction_modes(
+        &mut self,
+        access: Option<TransactionAccessMode>,
+        isolation_level: Option<IsolationLevel>,
+    ) -> Result<(), CoordError> {
+        let txn = match &mut self.transaction {
+            TransactionStatus::Started(txn)
+            | TransactionStatus::InTransaction(txn)
+            | TransactionStatus::InTransactionImplicit(txn) => txn,
+            TransactionStatus::Default | TransactionStatus::Failed => return Ok(()),
+        };
+        if let Some(isolation_level) = isolation_level {
+            if txn.ops != TransactionOps::None {
+                return Err(CoordError::Unstructured(anyhow!(
+                    "SET TRANSACTION ISOLATION LEVEL must be called before any query"
+                )));
+            }
+            self.vars.set_local_transaction_isolation(isolation_level);
+        }
+        if let Some(access) = access {
+            txn.access = Some(access);
+        }
+        Ok(())
+    }
+
     /// Clears a transaction, setting its state to Default and destroying all
     /// portals. Returned are:
     /// - sinks that were started in this transaction and need to be dropped
@@ -143,29 +179,46 @@ impl Session {
     }
 
     /// Adds operations to the current transaction. An error is produced if they
-    /// cannot be merged (i.e., a read cannot be merged to an insert).
+    /// cannot be merged (i.e., a read cannot be merged to an insert), or if
+    /// they are writes and the transaction is read-only.
     pub fn add_transaction_ops(&mut self, add_ops: TransactionOps) -> Result<(), CoordError> {
         match &mut self.transaction {
-            TransactionStatus::Started(txn_ops)
-            | TransactionStatus::InTransaction(txn_ops)
-            | TransactionStatus::InTransactionImplicit(txn_ops) => match txn_ops {
-                TransactionOps::None => *txn_ops = add_ops,
-                TransactionOps::Peeks(txn_ts) => match add_ops {
-                    TransactionOps::Peeks(add_ts) => {
-                        assert_eq!(*txn_ts, add_ts);
-                    }
-                    _ => return Err(CoordError::ReadOnlyTransaction),
-                },
-                TransactionOps::Tail => return Err(CoordError::TailOnlyTransaction),
-                TransactionOps::Writes(txn_writes) => match add_ops {
-                    TransactionOps::Writes(mut add_writes) => {
-                        txn_writes.append(&mut add_writes);
-                    }
-                    _ => {
-                        return Err(CoordError::WriteOnlyTransaction);
-                    }
-                },
-            },
+            TransactionStatus::Started(Transaction {
+                ops: txn_ops,
+                access,
+            })
+            | TransactionStatus::InTransaction(Transaction {
+                ops: txn_ops,
+                access,
+            })
+            | TransactionStatus::InTransactionImplicit(Transaction {
+                ops: txn_ops,
+                access,
+            }) => {
+                if let (TransactionOps::Writes(_), Some(TransactionAccessMode::ReadOnly)) =
+                    (&add_ops, access)
+                {
+                    return Err(CoordError::ReadOnlyTransaction);
+                }
+                match txn_ops {
+                    TransactionOps::None => *txn_ops = add_ops,
+                    TransactionOps::Peeks(txn_ts) => match add_ops {
+                        TransactionOps::Peeks(add_ts) => {
+                            assert_eq!(*txn_ts, add_ts);
+                        }
+                        _ => return Err(CoordError::ReadOnlyTransaction),
+                    },
+                    TransactionOps::Tail => return Err(CoordError::TailOnlyTransaction),
+                    TransactionOps::Writes(txn_writes) => match add_ops {
+                        TransactionOps::Writes(mut add_writes) => {
+                            txn_writes.append(&mut add_writes);
+                        }
+                        _ => {
+                            return Err(CoordError::WriteOnlyTransaction);
+                        }
+                    },
+                }
+            }
             TransactionStatus::Default | TransactionStatus::Failed => {
                 unreachable!()
             }
@@ -191,9 +244,18 @@ impl Session {
         // isn't in some other conflicting state because we want all of that logic to
         // reside in add_transaction_ops.
         let ts = match self.transaction {
-            TransactionStatus::Started(TransactionOps::Peeks(ts))
-            | TransactionStatus::InTransaction(TransactionOps::Peeks(ts))
-            | TransactionStatus::InTransactionImplicit(TransactionOps::Peeks(ts)) => ts,
+            TransactionStatus::Started(Transaction {
+                ops: TransactionOps::Peeks(ts),
+                ..
+            })
+            | TransactionStatus::InTransaction(Transaction {
+                ops: TransactionOps::Peeks(ts),
+                ..
+            })
+            | TransactionStatus::InTransactionImplicit(Transaction {
+                ops: TransactionOps::Peeks(ts),
+                ..
+            }) => ts,
             _ => get_ts()?,
         };
         self.add_transaction_ops(TransactionOps::Peeks(ts))?;
@@ -366,12 +428,12 @@ pub enum TransactionStatus {
     /// Idle. Matches `TBLOCK_DEFAULT`.
     Default,
     /// Running a single-query transaction. Matches `TBLOCK_STARTED`.
-    Started(TransactionOps),
+    Started(Transaction),
     /// Currently in a transaction issued from a `BEGIN`. Matches `TBLOCK_INPROGRESS`.
-    InTransaction(TransactionOps),
+    InTransaction(Transaction),
     /// Currently in an implicit transaction started from a multi-statement query
     /// with more than 1 statements. Matches `TBLOCK_IMPLICIT_INPROGRESS`.
-    InTransactionImplicit(TransactionOps),
+    InTransactionImplicit(Transaction),
     /// In a failed transaction that was started explicitly (i.e., previously
     /// InTransaction). We do not use Failed for implicit transactions because
     /// those cleanup after themselves. Matches `TBLOCK_ABORT`.
@@ -384,6 +446,25 @@ impl Default for TransactionStatus {
     }
 }
 
+/// State of an active transaction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Transaction {
+    /// The operations performed by the transaction.
+    pub ops: TransactionOps,
+    /// The access mode of the transaction, if one was requested via
+    /// `BEGIN` or `SET TRANSACTION`.
+    pub access: Option<TransactionAccessMode>,
+}
+
+impl Default for Transaction {
+    fn default() -> Self {
+        Transaction {
+            ops: TransactionOps::None,
+            access: None,
+        }
+    }
+}
+
 /// The type of operation being performed by the transaction.
 ///
 /// This is needed because we currently do not allow mixing reads and writes in
@@ -10,7 +10,12 @@
 use std::borrow::Borrow;
 use std::fmt;
 
+use chrono::FixedOffset;
+use repr::adt::datetime::Timezone;
+use sql::ast::TransactionIsolationLevel;
+
 use crate::error::CoordError;
+use crate::session::EndTransactionAction;
 
 const APPLICATION_NAME: ServerVar<str> = ServerVar {
     name: unicase::Ascii::new("application_name"),
@@ -85,16 +90,16 @@ const STANDARD_CONFORMING_STRINGS: ServerVar<bool> = ServerVar {
     description: "Causes '...' strings to treat backslashes literally (PostgreSQL).",
 };
 
-const TIMEZONE: ServerVar<str> = ServerVar {
+const TIMEZONE: ServerVar<Timezone> = ServerVar {
     // TimeZone has nonstandard capitalization for historical reasons.
     name: unicase::Ascii::new("TimeZone"),
-    value: "UTC",
+    value: &Timezone::UTC,
     description: "Sets the time zone for displaying and interpreting time stamps (PostgreSQL).",
 };
 
-const TRANSACTION_ISOLATION: ServerVar<str> = ServerVar {
+const TRANSACTION_ISOLATION: ServerVar<IsolationLevel> = ServerVar {
     name: unicase::Ascii::new("transaction_isolation"),
-    value: "serializable",
+    value: &IsolationLevel::Serializable,
     description: "Sets the current transaction's isolation level (PostgreSQL).",
 };
 
@@ -119,6 +124,11 @@ const TRANSACTION_ISOLATION: ServerVar<str> = ServerVar {
 /// The configuration parameters that exist are driven by compatibility with
 /// PostgreSQL drivers that expect them, not because they are particularly
 /// important.
+///
+/// As in PostgreSQL, changes to session parameters are transactional. A value
+/// set with `SET` only persists beyond the current transaction if that
+/// transaction commits, while a value set with `SET LOCAL` is discarded when
+/// the current transaction ends.
 #[derive(Debug)]
 pub struct Vars {
     application_name: SessionVar<str>,
@@ -132,8 +142,8 @@ pub struct Vars {
     server_version_num: ServerVar<i32>,
     sql_safe_updates: SessionVar<bool>,
     standard_conforming_strings: ServerVar<bool>,
-    timezone: ServerVar<str>,
-    transaction_isolation: ServerVar<str>,
+    timezone: SessionVar<Timezone>,
+    transaction_isolation: SessionVar<IsolationLevel>,
 }
 
 impl Default for Vars {
@@ -150,8 +160,8 @@ impl Default for Vars {
             server_version_num: SERVER_VERSION_NUM,
             sql_safe_updates: SessionVar::new(&SQL_SAFE_UPDATES),
             standard_conforming_strings: STANDARD_CONFORMING_STRINGS,
-            timezone: TIMEZONE,
-            transaction_isolation: TRANSACTION_ISOLATION,
+            timezone: SessionVar::new(&TIMEZONE),
+            transaction_isolation: SessionVar::new(&TRANSACTION_ISOLATION),
         }
     }
 }
@@ -189,6 +199,7 @@ impl Vars {
             &self.integer_datetimes,
             &self.server_version,
             &self.standard_conforming_strings,
+            &self.timezone,
         ]
         .into_iter()
     }
@@ -238,17 +249,22 @@ impl Vars {
     /// Sets the configuration parameter named `name` to the value represented
     /// by `value`.
     ///
+    /// If `local` is true, the new value only lasts until the end of the
+    /// current transaction. Otherwise the new value lasts for the remainder of
+    /// the session, provided that the current transaction commits. See
+    /// [`Vars::end_transaction`].
+    ///
     /// Like with [`Vars::get`], configuration parameters are matched case
     /// insensitively. If `value` is not valid, as determined by the underlying
     /// configuration parameter, or if the named configuration parameter does
     /// not exist, an error is returned.
-    pub fn set(&mut self, name: &str, value: &str) -> Result<(), CoordError> {
+    pub fn set(&mut self, name: &str, value: &str, local: bool) -> Result<(), CoordError> {
         if name == APPLICATION_NAME.name {
-            self.application_name.set(value)
+            self.application_name.set(value, local)
         } else if name == CLIENT_ENCODING.name {
             Err(CoordError::ReadOnlyParameter(&CLIENT_ENCODING))
         } else if name == DATABASE.name {
-            self.database.set(value)
+            self.database.set(value, local)
         } else if name == DATE_STYLE.name {
             for value in value.split(',') {
                 let value = unicase::Ascii::new(value.trim());
@@ -258,7 +274,7 @@ impl Vars {
             }
             Ok(())
         } else if name == EXTRA_FLOAT_DIGITS.name {
-            self.extra_float_digits.set(value)
+            self.extra_float_digits.set(value, local)
         } else if name == INTEGER_DATETIMES.name {
             Err(CoordError::ReadOnlyParameter(&INTEGER_DATETIMES))
         } else if name == SEARCH_PATH.name {
@@ -268,22 +284,66 @@ impl Vars {
         } else if name == SERVER_VERSION_NUM.name {
             Err(CoordError::ReadOnlyParameter(&SERVER_VERSION_NUM))
         } else if name == SQL_SAFE_UPDATES.name {
-            self.sql_safe_updates.set(value)
+            self.sql_safe_updates.set(value, local)
         } else if name == STANDARD_CONFORMING_STRINGS.name {
             Err(CoordError::ReadOnlyParameter(&STANDARD_CONFORMING_STRINGS))
         } else if name == TIMEZONE.name {
-            if unicase::Ascii::new(value) != TIMEZONE.value {
-                return Err(CoordError::ConstrainedParameter(&TIMEZONE));
-            } else {
-                Ok(())
-            }
+            self.timezone
+                .set(value, local)
+                .map_err(|_| CoordError::InvalidParameterValue {
+                    parameter: &TIMEZONE,
+                    value: value.into(),
+                    valid_values: None,
+                })
         } else if name == TRANSACTION_ISOLATION.name {
-            Err(CoordError::ReadOnlyParameter(&TRANSACTION_ISOLATION))
+            self.transaction_isolation.set(value, local).map_err(|_| {
+                CoordError::InvalidParameterValue {
+                    parameter: &TRANSACTION_ISOLATION,
+                    value: value.into(),
+                    valid_values: Some(&["serializable", "strict serializable"]),
+                }
+            })
         } else {
             Err(CoordError::UnknownParameter(name.into()))
         }
     }
 
+    /// Sets the isolation level of the current transaction, as if by
+    /// `SET LOCAL transaction_isolation`.
+    pub fn set_local_transaction_isolation(&mut self, level: IsolationLevel) {
+        self.transaction_isolation.set_local_value(level);
+    }
+
+    /// Commits or rolls back changes to configuration parameters made during
+    /// the current transaction.
+    ///
+    /// Values set with `SET LOCAL` are always discarded. Values set with `SET`
+    /// become the session's values if the transaction commits and are
+    /// discarded if it rolls back.
+    pub fn end_transaction(&mut self, action: &EndTransactionAction) {
+        let Vars {
+            application_name,
+            client_encoding: _,
+            database,
+            date_style: _,
+            extra_float_digits,
+            integer_datetimes: _,
+            search_path: _,
+            server_version: _,
+            server_version_num: _,
+            sql_safe_updates,
+            standard_conforming_strings: _,
+            timezone,
+            transaction_isolation,
+        } = self;
+        application_name.end_transaction(action);
+        database.end_transaction(action);
+        extra_float_digits.end_transaction(action);
+        sql_safe_updates.end_transaction(action);
+        timezone.end_transaction(action);
+        transaction_isolation.end_transaction(action);
+    }
+
     /// Returns the value of the `application_name` configuration parameter.
     pub fn application_name(&self) -> &str {
         self.application_name.value()
@@ -341,14 +401,14 @@ impl Vars {
     }
 
     /// Returns the value of the `timezone` configuration parameter.
-    pub fn timezone(&self) -> &'static str {
-        self.timezone.value
+    pub fn timezone(&self) -> Timezone {
+        *self.timezone.value()
     }
 
     /// Returns the value of the `transaction_isolation` configuration
     /// parameter.
-    pub fn transaction_isolation(&self) -> &'static str {
-        self.transaction_isolation.value
+    pub fn transaction_isolation(&self) -> IsolationLevel {
+        *self.transaction_isolation.value()
     }
 }
 
@@ -403,12 +463,19 @@ where
 
 /// A `SessionVar` is the session value for a configuration parameter. If unset,
 /// the server default is used instead.
+///
+/// Values set during a transaction are held separately from the session value
+/// until the transaction ends.
 #[derive(Debug)]
 pub struct SessionVar<V>
 where
     V: Value + fmt::Debug + ?Sized + 'static,
 {
     value: Option<V::Owned>,
+    /// The value set by `SET` in the current transaction, if any.
+    staged_value: Option<V::Owned>,
+    /// The value set by `SET LOCAL` in the current transaction, if any.
+    local_value: Option<V::Owned>,
     parent: &'static ServerVar<V>,
 }
 
@@ -419,23 +486,48 @@ where
     pub fn new(parent: &'static ServerVar<V>) -> SessionVar<V> {
         SessionVar {
             value: None,
+            staged_value: None,
+            local_value: None,
             parent,
         }
     }
 
-    pub fn set(&mut self, s: &str) -> Result<(), CoordError> {
+    pub fn set(&mut self, s: &str, local: bool) -> Result<(), CoordError> {
         match V::parse(s) {
             Ok(v) => {
-                self.value = Some(v);
+                if local {
+                    self.set_local_value(v);
+                } else {
+                    // Like PostgreSQL, a `SET` overrides any earlier
+                    // `SET LOCAL` in the same transaction.
+                    self.staged_value = Some(v);
+                    self.local_value = None;
+                }
                 Ok(())
             }
             Err(()) => Err(CoordError::InvalidParameterType(self.parent)),
         }
     }
 
+    pub fn set_local_value(&mut self, v: V::Owned) {
+        self.local_value = Some(v);
+    }
+
+    pub fn end_transaction(&mut self, action: &EndTransactionAction) {
+        self.local_value = None;
+        let staged_value = self.staged_value.take();
+        if let EndTransactionAction::Commit = action {
+            if staged_value.is_some() {
+                self.value = staged_value;
+            }
+        }
+    }
+
     pub fn value(&self) -> &V {
-        self.value
+        self.local_value
             .as_ref()
+            .or(self.staged_value.as_ref())
+            .or(self.value.as_ref())
             .map(|v| v.borrow())
             .unwrap_or(self.parent.value)
     }
@@ -528,3 +620,87 @@ impl Value for [&str] {
         self.join(", ")
     }
 }
+
+/// Like PostgreSQL, fixed offsets in the `TimeZone` setting follow the POSIX
+/// convention, in which positive offsets are west of Greenwich: `'+05:30'` is
+/// five and a half hours behind UTC. This is the opposite of the ISO 8601
+/// convention used by offsets in timestamp literals.
+impl Value for Timezone {
+    const TYPE_NAME: &'static str = "string";
+
+    fn parse(s: &str) -> Result<Self, ()> {
+        let s = s.trim();
+        if unicase::Ascii::new(s) == "utc" {
+            // The IANA database only knows the canonical capitalization, but
+            // PostgreSQL accepts any.
+            Ok(Timezone::UTC)
+        } else {
+            match s.parse().map_err(|_| ())? {
+                Timezone::FixedOffset(offset) => Ok(Timezone::FixedOffset(negate_offset(offset))),
+                tz => Ok(tz),
+            }
+        }
+    }
+
+    fn format(&self) -> String {
+        match self {
+            Timezone::FixedOffset(offset) => negate_offset(*offset).to_string(),
+            tz => tz.to_string(),
+        }
+    }
+}
+
+/// Converts between POSIX and ISO 8601 offsets.
+fn negate_offset(offset: FixedOffset) -> FixedOffset {
+    FixedOffset::east(-offset.local_minus_utc())
+}
+
+/// The isolation level of a transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IsolationLevel {
+    /// Transactions appear to execute in some serial order, but that order
+    /// need not respect real time. A read may observe a state that is older
+    /// than the state observed by a read that completed before it started.
+    Serializable,
+    /// Transactions appear to execute in a serial order that respects real
+    /// time. Reads may need to wait for the inputs they depend on to catch up
+    /// to the latest timestamp at which any read was performed.
+    StrictSerializable,
+}
+
+impl From<TransactionIsolationLevel> for IsolationLevel {
+    fn from(level: TransactionIsolationLevel) -> IsolationLevel {
+        match level {
+            // The SQL standard permits running a transaction at a stronger
+            // isolation level than the one requested, and serializable is the
+            // weakest level that Materialize provides.
+            TransactionIsolationLevel::ReadUncommitted
+            | TransactionIsolationLevel::ReadCommitted
+            | TransactionIsolationLevel::RepeatableRead
+            | TransactionIsolationLevel::Serializable => IsolationLevel::Serializable,
+            TransactionIsolationLevel::StrictSerializable => IsolationLevel::StrictSerializable,
+        }
+    }
+}
+
+impl Value for IsolationLevel {
+    const TYPE_NAME: &'static str = "string";
+
+    fn parse(s: &str) -> Result<Self, ()> {
+        let s = unicase::Ascii::new(s.trim());
+        if s == "serializable" {
+            Ok(IsolationLevel::Serializable)
+        } else if s == "strict serializable" {
+            Ok(IsolationLevel::StrictSerializable)
+        } else {
+            Err(())
+        }
+    }
+
+    fn format(&self) -> String {
+        match self {
+            IsolationLevel::Serializable => "serializable".into(),
+            IsolationLevel::StrictSerializable => "strict serializable".into(),
+        }
+    }
+}
@@ -182,32 +182,63 @@ fn decode_consistency_end_record(
     }
 }
 
-#[allow(clippy::too_many_arguments)]
-async fn register_kafka_topic(
+/// Ensures that the Kafka topic for a sink exists, creating it if necessary,
+/// and returns the number of partitions in the topic.
+///
+/// If the topic already exists, which is only permitted when
+/// `succeed_if_exists` is set, it is validated against the explicitly
+/// requested partition count, replication factor, and cleanup policy instead.
+async fn ensure_kafka_topic(
     client: &AdminClient<DefaultClientContext>,
     topic: &str,
     mut partition_count: i32,
     mut replication_factor: i32,
-    ccsr: &ccsr::Client,
-    value_schema: &str,
-    key_schema: Option<&str>,
+    compact: bool,
     succeed_if_exists: bool,
-) -> Result<(Option<i32>, i32), CoordError> {
+) -> Result<i32, CoordError> {
+    let metadata = client
+        .inner()
+        .fetch_metadata(None, Duration::from_secs(5))
+        .with_context(|| {
+            format!(
+                "error fetching metadata when creating new topic {} for sink",
+                topic
+            )
+        })?;
+
+    let existing = metadata
+        .topics()
+        .iter()
+        .find(|t| t.name() == topic)
+        .map(|t| {
+            let replicas = t.partitions().first().map_or(0, |p| p.replicas().len());
+            (t.partitions().len() as i32, replicas as i32)
+        });
+    if let Some((existing_partition_count, existing_replication_factor)) = existing {
+        if !succeed_if_exists {
+            coord_bail!(
+                "error creating topic {} for sink: topic already exists",
+                topic
+            );
+        }
+        validate_kafka_topic(
+            client,
+            topic,
+            partition_count,
+            replication_factor,
+            compact,
+            existing_partition_count,
+            existing_replication_factor,
+        )
+        .await?;
+        return Ok(existing_partition_count);
+    }
+
     // if either partition count or replication factor should be defaulted to the broker's config
     // (signaled by a value of -1), explicitly poll the broker to discover the defaults.
     // Newer versions of Kafka can instead send create topic requests with -1 and have this happen
     // behind the scenes, but this is unsupported and will result in errors on pre-2.4 Kafka
     if partition_count == -1 || replication_factor == -1 {
-        let metadata = client
-            .inner()
-            .fetch_metadata(None, Duration::from_secs(5))
-            .with_context(|| {
-                format!(
-                    "error fetching metadata when creating new topic {} for sink",
-                    topic
-                )
-            })?;
-
         if metadata.brokers().len() == 0 {
             coord_bail!("zero brokers discovered in metadata request");
         }
@@ -275,33 +306,141 @@ async fn register_kafka_topic(
         }
     }
 
-    let res = client
-        .create_topics(
-            &[NewTopic::new(
-                &topic,
-                partition_count,
-                TopicReplication::Fixed(replication_factor),
-            )],
+    let mut new_topic = NewTopic::new(
+        topic,
+        partition_count,
+        TopicReplication::Fixed(replication_factor),
+    );
+    if compact {
+        new_topic = new_topic.set("cleanup.policy", "compact");
+    }
+    ::kafka_util::admin::create_topic(
+        client,
+        &AdminOptions::new().request_timeout(Some(Duration::from_secs(5))),
+        &new_topic,
+    )
+    .await
+    .with_context(|| format!("error creating new topic {} for sink", topic))?;
+
+    Ok(partition_count)
+}
+
+/// Validates the configuration of an existing Kafka topic against the
+/// configuration requested for a sink.
+async fn validate_kafka_topic(
+    client: &AdminClient<DefaultClientContext>,
+    topic: &str,
+    partition_count: i32,
+    replication_factor: i32,
+    compact: bool,
+    existing_partition_count: i32,
+    existing_replication_factor: i32,
+) -> Result<(), CoordError> {
+    let existing_cleanup_policy = if compact {
+        fetch_cleanup_policy(client, topic).await?
+    } else {
+        None
+    };
+    check_kafka_topic(
+        topic,
+        partition_count,
+        replication_factor,
+        compact,
+        existing_partition_count,
+        existing_replication_factor,
+        existing_cleanup_policy.as_deref(),
+    )
+}
+
+/// Fetches the `cleanup.policy` of an existing Kafka topic, if it is set.
+async fn fetch_cleanup_policy(
+    client: &AdminClient<DefaultClientContext>,
+    topic: &str,
+) -> Result<Option<String>, CoordError> {
+    let configs = client
+        .describe_configs(
+            &[ResourceSpecifier::Topic(topic)],
             &AdminOptions::new().request_timeout(Some(Duration::from_secs(5))),
         )
         .await
-        .with_context(|| format!("error creating new topic {} for sink", topic))?;
-    if res.len() != 1 {
+        .with_context(|| format!("error fetching configuration of topic {}", topic))?;
+
+    if configs.len() != 1 {
+        coord_bail!(
+            "error validating topic {} for sink: {} config results were returned, but one was expected",
+            topic,
+            configs.len()
+        );
+    }
+
+    let config = configs.into_element().map_err(|e| {
+        anyhow!(
+            "error reading configuration of topic {} for sink: {}",
+            topic,
+            e
+        )
+    })?;
+
+    Ok(config
+        .entries
+        .into_iter()
+        .find(|entry| entry.name == "cleanup.policy")
+        .and_then(|entry| entry.value))
+}
+
+/// Checks that an existing Kafka topic's partition count, replication factor,
+/// and cleanup policy are compatible with the ones requested for a sink. A
+/// requested partition count or replication factor of -1 matches anything.
+fn check_kafka_topic(
+    topic: &str,
+    partition_count: i32,
+    replication_factor: i32,
+    compact: bool,
+    existing_partition_count: i32,
+    existing_replication_factor: i32,
+    existing_cleanup_policy: Option<&str>,
+) -> Result<(), CoordError> {
+    if partition_count != -1 && partition_count != existing_partition_count {
+        coord_bail!(
+            "topic {} already exists with {} partitions, but the sink requested {}",
+            topic,
+            existing_partition_count,
+            partition_count
+        );
+    }
+
+    if replication_factor != -1 && replication_factor != existing_replication_factor {
         coord_bail!(
-            "error creating topic {} for sink: \
-             kafka topic creation returned {} results, but exactly one result was expected",
+            "topic {} already exists with replication factor {}, but the sink requested {}",
             topic,
-            res.len()
+            existing_replication_factor,
+            replication_factor
         );
     }
-    if let Err((_, e)) = res.into_element() {
-        // if the topic already exists and we reuse_existing, don't fail - instead proceed
-        // to read the schema
-        if !(succeed_if_exists && e == rdkafka::types::RDKafkaErrorCode::TopicAlreadyExists) {
-            coord_bail!("error creating topic {} for sink: {}", topic, e)
+
+    if compact {
+        let is_compacted = existing_cleanup_policy.map_or(false, |policy| {
+            policy.split(',').any(|policy| policy.trim() == "compact")
+        });
+        if !is_compacted {
+            coord_bail!(
+                "topic {} already exists, but upsert sinks require cleanup.policy=compact",
+                topic
+            );
         }
     }
 
+    Ok(())
+}
+
+/// Publishes the key and value schemas for a sink's topic, returning their
+/// IDs in the schema registry.
+async fn publish_kafka_schemas(
+    ccsr: &ccsr::Client,
+    topic: &str,
+    value_schema: &str,
+    key_schema: Option<&str>,
+) -> Result<(Option<i32>, i32), CoordError> {
     // Publish value schema for the topic.
     //
     // TODO(benesch): do we need to delete the Kafka topic if publishing the
@@ -357,15 +496,21 @@ async fn build_kafka(
         .context("creating admin client failed")?;
     let ccsr = builder.ccsr_config.build()?;
 
-    let (key_schema_id, value_schema_id) = register_kafka_topic(
+    let partition_count = ensure_kafka_topic(
         &client,
         &topic,
         builder.partition_count,
         builder.replication_factor,
+        builder.compact,
+        builder.exactly_once,
+    )
+    .await
+    .context("error registering kafka topic for sink")?;
+    let (key_schema_id, value_schema_id) = publish_kafka_schemas(
         &ccsr,
+        &topic,
         &builder.value_schema,
         builder.key_schema.as_deref(),
-        builder.exactly_once,
     )
     .await
     .context("error registering kafka topic for sink")?;
@@ -379,18 +524,20 @@ async fn build_kafka(
         );
 
         // create consistency topic/schema and retrieve schema id
-        let (_, consistency_schema_id) = register_kafka_topic(
+        ensure_kafka_topic(
             &client,
             &consistency_topic,
             1,
             builder.replication_factor,
-            &ccsr,
-            &consistency_value_schema,
-            None,
+            false,
             builder.exactly_once,
         )
         .await
         .context("error registering kafka consistency topic for sink")?;
+        let (_, consistency_schema_id) =
+            publish_kafka_schemas(&ccsr, &consistency_topic, &consistency_value_schema, None)
+                .await
+                .context("error registering kafka consistency topic for sink")?;
 
         // get latest committed timestamp from consistencty topic
         let gate_ts = if builder.exactly_once {
@@ -430,6 +577,8 @@ async fn build_kafka(
         key_desc_and_indices: builder.key_desc_and_indices,
         value_desc: builder.value_desc,
         consistency,
+        partition_count,
+        partition_by: builder.partition_by,
         exactly_once: builder.exactly_once,
         transitive_source_dependencies: builder.transitive_source_dependencies,
         fuel: builder.fuel,
@@ -476,3 +625,44 @@ fn build_avro_ocf(
         value_desc: builder.value_desc,
     }))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_kafka_topic() {
+        let check = |partition_count, replication_factor, compact, cleanup_policy| {
+            check_kafka_topic(
+                "t",
+                partition_count,
+                replication_factor,
+                compact,
+                4,
+                3,
+                cleanup_policy,
+            )
+            .map_err(|e| e.to_string())
+        };
+
+        assert!(check(4, 3, false, None).is_ok());
+        assert!(check(-1, -1, false, None).is_ok());
+        assert!(check(-1, -1, true, Some("compact")).is_ok());
+        assert!(check(-1, -1, true, Some("delete, compact")).is_ok());
+
+        assert_eq!(
+            check(2, -1, false, None).unwrap_err(),
+            "topic t already exists with 4 partitions, but the sink requested 2"
+        );
+        assert_eq!(
+            check(-1, 1, false, None).unwrap_err(),
+            "topic t already exists with replication factor 3, but the sink requested 1"
+        );
+        for policy in &[None, Some("delete"), Some("compaction")] {
+            assert_eq!(
+                check(-1, -1, true, *policy).unwrap_err(),
+                "topic t already exists, but upsert sinks require cleanup.policy=compact"
+            );
+        }
+    }
+}
@@ -9,19 +9,76 @@
 
 use std::fmt::Display;
 
-use expr::EvalError;
+use expr::{EvalError, GlobalId};
 
 use serde::{Deserialize, Serialize};
 
+/// Identifies the record of a source from which an error originated.
+#[derive(Ord, PartialOrd, Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash)]
+pub struct SourceErrorProvenance {
+    /// The source that produced the record.
+    pub source_id: GlobalId,
+    /// The partition of the source that produced the record, if the source is
+    /// partitioned.
+    pub partition: Option<String>,
+    /// The offset of the record within its partition, if known.
+    pub offset: Option<i64>,
+    /// The encoding with which the record was being decoded, if any.
+    pub encoding: Option<String>,
+}
+
+impl Display for SourceErrorProvenance {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "source {}", self.source_id)?;
+        if let Some(partition) = &self.partition {
+            write!(f, ", partition {}", partition)?;
+        }
+        if let Some(offset) = self.offset {
+            write!(f, ", offset {}", offset)?;
+        }
+        if let Some(encoding) = &self.encoding {
+            write!(f, ", encoding {}", encoding)?;
+        }
+        Ok(())
+    }
+}
+
 #[derive(Ord, PartialOrd, Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash)]
 pub enum DecodeError {
     Text(String),
+    /// An error decoding a record whose provenance is known.
+    Record {
+        provenance: SourceErrorProvenance,
+        error: String,
+    },
+}
+
+impl DecodeError {
+    /// Attributes the error to the record described by `provenance`.
+    pub fn with_provenance(self, provenance: SourceErrorProvenance) -> DecodeError {
+        match self {
+            DecodeError::Text(error) | DecodeError::Record { error, .. } => {
+                DecodeError::Record { provenance, error }
+            }
+        }
+    }
+
+    /// Returns the record from which the error originated, if known.
+    pub fn provenance(&self) -> Option<&SourceErrorProvenance> {
+        match self {
+            DecodeError::Text(_) => None,
+            DecodeError::Record { provenance, .. } => Some(provenance),
+        }
+    }
 }
 
 impl Display for DecodeError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             DecodeError::Text(e) => write!(f, "Text: {}", e),
+            DecodeError::Record { provenance, error } => {
+                write!(f, "Text: {} ({})", error, provenance)
+            }
         }
     }
 }
@@ -48,7 +105,41 @@ impl Display for SourceError {
 #[derive(Ord, PartialOrd, Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash)]
 pub enum SourceErrorDetails {
     Initialization(String),
+    /// An I/O error, which might not recur if the source were read again.
     FileIO(String),
+    /// An error after initialization after which the source must be dropped
+    /// and recreated.
+    Fatal(String),
+    /// An error reading from the upstream system, along with where in the
+    /// source it was encountered.
+    Ingestion {
+        provenance: SourceErrorProvenance,
+        error: String,
+        /// Whether the read might succeed if it were retried, as opposed to
+        /// requiring that the source be dropped and recreated.
+        retryable: bool,
+    },
+}
+
+impl SourceErrorDetails {
+    /// Returns the record from which the error originated, if known.
+    pub fn provenance(&self) -> Option<&SourceErrorProvenance> {
+        match self {
+            SourceErrorDetails::Initialization(_)
+            | SourceErrorDetails::FileIO(_)
+            | SourceErrorDetails::Fatal(_) => None,
+            SourceErrorDetails::Ingestion { provenance, .. } => Some(provenance),
+        }
+    }
+
+    /// Reports whether the error may be transient.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            SourceErrorDetails::Initialization(_) | SourceErrorDetails::Fatal(_) => false,
+            SourceErrorDetails::FileIO(_) => true,
+            SourceErrorDetails::Ingestion { retryable, .. } => *retryable,
+        }
+    }
 }
 
 impl Display for SourceErrorDetails {
@@ -62,6 +153,20 @@ impl Display for SourceErrorDetails {
                 )
             }
             SourceErrorDetails::FileIO(e) => write!(f, "file IO: {}", e),
+            SourceErrorDetails::Fatal(e) => {
+                write!(f, "fatal error, must be dropped and recreated: {}", e)
+            }
+            SourceErrorDetails::Ingestion {
+                provenance,
+                error,
+                retryable,
+            } => {
+                write!(f, "{} ({}", error, provenance)?;
+                if *retryable {
+                    write!(f, ", retryable")?;
+                }
+                write!(f, ")")
+            }
         }
     }
 }
@@ -73,6 +178,28 @@ pub enum DataflowError {
     SourceError(SourceError),
 }
 
+impl DataflowError {
+    /// Returns the source record from which the error originated, if known.
+    pub fn provenance(&self) -> Option<&SourceErrorProvenance> {
+        match self {
+            DataflowError::DecodeError(e) => e.provenance(),
+            DataflowError::EvalError(_) => None,
+            DataflowError::SourceError(e) => e.error.provenance(),
+        }
+    }
+
+    /// Reports whether the error may be transient, in which case the same
+    /// computation might succeed if the offending input were reread.
+    ///
+    /// Decode and evaluation errors are deterministic and never retryable.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            DataflowError::DecodeError(_) | DataflowError::EvalError(_) => false,
+            DataflowError::SourceError(e) => e.error.is_retryable(),
+        }
+    }
+}
+
 impl Display for DataflowError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -56,7 +56,10 @@ pub enum MaterializedLog {
     KafkaConsumerInfo,
     PeekCurrent,
     PeekDuration,
+    SourceErrors,
     SourceInfo,
+    SourceIngestion,
+    UpsertState,
 }
 
 impl LogVariant {
@@ -160,6 +163,34 @@ impl LogVariant {
                 .with_named_column("timestamp", ScalarType::Int64.nullable(false))
                 .with_key(vec![0, 1, 2, 3]),
 
+            LogVariant::Materialized(MaterializedLog::SourceIngestion) => RelationDesc::empty()
+                .with_named_column("source_name", ScalarType::String.nullable(false))
+                .with_named_column("source_id", ScalarType::String.nullable(false))
+                .with_named_column("dataflow_id", ScalarType::Int64.nullable(false))
+                .with_named_column("partition_id", ScalarType::String.nullable(true))
+                .with_named_column("offset", ScalarType::Int64.nullable(false))
+                .with_named_column("messages", ScalarType::Int64.nullable(false))
+                .with_named_column("raw_bytes", ScalarType::Int64.nullable(false))
+                .with_named_column("lag_ms", ScalarType::Int64.nullable(false))
+                .with_named_column("messages_per_second", ScalarType::Int64.nullable(false))
+                .with_named_column("raw_bytes_per_second", ScalarType::Int64.nullable(false))
+                .with_named_column("decoded_bytes", ScalarType::Int64.nullable(false))
+                .with_named_column(
+                    "decoded_bytes_per_second",
+                    ScalarType::Int64.nullable(false),
+                )
+                .with_key(vec![0, 1, 2, 3]),
+
+            LogVariant::Materialized(MaterializedLog::SourceErrors) => RelationDesc::empty()
+                .with_named_column("source_id", ScalarType::String.nullable(false))
+                .with_named_column("dataflow_id", ScalarType::Int64.nullable(false))
+                .with_named_column("worker", ScalarType::Int64.nullable(false))
+                .with_named_column("partition_id", ScalarType::String.nullable(true))
+                .with_named_column("offset", ScalarType::Int64.nullable(true))
+                .with_named_column("encoding", ScalarType::String.nullable(true))
+                .with_named_column("retryable", ScalarType::Bool.nullable(false))
+                .with_named_column("error", ScalarType::String.nullable(false)),
+
             LogVariant::Materialized(MaterializedLog::DataflowDependency) => RelationDesc::empty()
                 .with_named_column("dataflow", ScalarType::String.nullable(false))
                 .with_named_column("source", ScalarType::String.nullable(false))
@@ -217,6 +248,15 @@ impl LogVariant {
                 .with_named_column("duration_ns", ScalarType::Int64.nullable(false))
                 .with_named_column("count", ScalarType::Int64.nullable(false))
                 .with_key(vec![0, 1]),
+
+            LogVariant::Materialized(MaterializedLog::UpsertState) => RelationDesc::empty()
+                .with_named_column("source_id", ScalarType::String.nullable(false))
+                .with_named_column("dataflow_id", ScalarType::Int64.nullable(false))
+                .with_named_column("worker", ScalarType::Int64.nullable(false))
+                .with_named_column("keys", ScalarType::Int64.nullable(false))
+                .with_named_column("memory_keys", ScalarType::Int64.nullable(false))
+                .with_named_column("disk_bytes", ScalarType::Int64.nullable(false))
+                .with_key(vec![0, 1, 2]),
         }
     }
 
@@ -271,8 +311,14 @@ impl LogVariant {
                 vec![(1, 1), (2, 2), (3, 3)],
             )],
             LogVariant::Materialized(MaterializedLog::PeekCurrent) => vec![],
+            LogVariant::Materialized(MaterializedLog::SourceErrors) => vec![],
             LogVariant::Materialized(MaterializedLog::SourceInfo) => vec![],
+            LogVariant::Materialized(MaterializedLog::SourceIngestion) => vec![(
+                LogVariant::Materialized(MaterializedLog::SourceInfo),
+                vec![(0, 0), (1, 1), (2, 2), (3, 3)],
+            )],
             LogVariant::Materialized(MaterializedLog::PeekDuration) => vec![],
+            LogVariant::Materialized(MaterializedLog::UpsertState) => vec![],
         }
     }
 }
@@ -134,15 +134,20 @@ impl DataflowDesc {
         connector: SourceConnector,
         bare_desc: RelationDesc,
         orig_id: GlobalId,
+        dead_letters: Option<GlobalId>,
     ) {
         let source_description = SourceDesc {
             name,
             connector,
             operators: None,
             bare_desc,
+            dead_letters,
         };
         self.source_imports
             .insert(id, (source_description, orig_id));
+        if let Some(dead_letters) = dead_letters {
+            self.add_dependency(dead_letters, id);
+        }
     }
 
     pub fn add_view_to_build(
@@ -285,6 +290,9 @@ impl DataflowDesc {
             if source_id == id {
                 return desc.bare_desc.arity();
             }
+            if desc.dead_letters.as_ref() == Some(id) {
+                return SourceConnector::dead_letters_desc().arity();
+            }
         }
         for (_index_id, (desc, typ)) in self.index_imports.iter() {
             if &desc.on_id == id {
@@ -357,27 +365,12 @@ impl SourceDataEncoding {
             SourceDataEncoding::KeyValue { key, value } => {
                 // Add columns for the key, if using the upsert envelope.
                 // TODO: this will be removed with upsert value rewriting
-                let desc = if matches!(envelope, SourceEnvelope::Upsert) {
+                let desc = if matches!(envelope, SourceEnvelope::Upsert(UpsertStyle::Default, _)) {
                     let key_desc = {
-                        let key_desc =
-                            key.desc(&SourceEnvelope::None, RelationDesc::empty(), None)?;
-
-                        // It doesn't make sense for the key to have keys.
-                        assert!(key_desc.typ().keys.is_empty());
-
+                        let key_desc = key.key_desc()?;
                         // Add the key columns as a key.
                         let key_indices = (0..key_desc.arity()).collect();
-                        let key_desc = key_desc.with_key(key_indices);
-
-                        // Rename key columns to "keyN" if the encoding is not Avro.
-                        match key {
-                            DataEncoding::Avro(_) => key_desc,
-                            _ => {
-                                let names =
-                                    (0..key_desc.arity()).map(|i| Some(format!("key{}", i)));
-                                key_desc.with_names(names)
-                            }
-                        }
+                        key_desc.with_key(key_indices)
                     };
                     let key_schema = if let DataEncoding::Avro(AvroEncoding { schema, .. }) = key {
                         Some(&**schema)
@@ -395,6 +388,24 @@ impl SourceDataEncoding {
 }
 
 impl DataEncoding {
+    /// Computes the [`RelationDesc`] for the columns produced by decoding a
+    /// message key with this data encoding.
+    pub fn key_desc(&self) -> Result<RelationDesc, anyhow::Error> {
+        let key_desc = self.desc(&SourceEnvelope::None, RelationDesc::empty(), None)?;
+
+        // It doesn't make sense for the key to have keys.
+        assert!(key_desc.typ().keys.is_empty());
+
+        // Rename key columns to "keyN" if the encoding is not Avro.
+        Ok(match self {
+            DataEncoding::Avro(_) => key_desc,
+            _ => {
+                let names = (0..key_desc.arity()).map(|i| Some(format!("key{}", i)));
+                key_desc.with_names(names)
+            }
+        })
+    }
+
     /// Computes the [`RelationDesc`] for the relation specified by the this
     /// data encoding and envelope.s
     fn desc(
@@ -563,6 +574,9 @@ pub struct SourceDesc {
     /// to the output of the source.
     pub operators: Option<LinearOperator>,
     pub bare_desc: RelationDesc,
+    /// The ID under which to present the records that the source fails to
+    /// decode, if it dead-letters them.
+    pub dead_letters: Option<GlobalId>,
 }
 
 /// A sink for updates to a relational collection.
@@ -594,16 +608,37 @@ pub struct SinkAsOf {
 pub enum SourceEnvelope {
     None,
     Debezium(DebeziumDeduplicationStrategy, DebeziumMode),
-    Upsert,
+    Upsert(UpsertStyle, UpsertStateBackend),
     CdcV2,
 }
 
+/// Where the key of each record of an upsert source comes from.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum UpsertStyle {
+    /// The key is decoded from the message key, and its columns precede the
+    /// value columns.
+    Default,
+    /// The key is formed by the given columns of the decoded value.
+    ValueColumns(Vec<usize>),
+}
+
+/// Where an upsert source keeps the latest value of each key.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum UpsertStateBackend {
+    /// Every value is kept in memory.
+    Memory,
+    /// Up to `memory_keys` of the most recently used values are kept in memory
+    /// on each worker, and the remainder are spilled to a log-structured store
+    /// under the data directory.
+    Disk { memory_keys: usize },
+}
+
 impl SourceEnvelope {
     pub fn get_avro_envelope_type(&self) -> avro::EnvelopeType {
         match self {
             SourceEnvelope::None => avro::EnvelopeType::None,
             SourceEnvelope::Debezium { .. } => avro::EnvelopeType::Debezium,
-            SourceEnvelope::Upsert => avro::EnvelopeType::Upsert,
+            SourceEnvelope::Upsert(..) => avro::EnvelopeType::Upsert,
             SourceEnvelope::CdcV2 => avro::EnvelopeType::CdcV2,
         }
     }
@@ -632,9 +667,25 @@ pub enum SourceConnector {
         ts_frequency: Duration,
     },
     Local,
+    /// The records that the source with the given ID fails to decode. They are
+    /// split off from that source's own decoding, so this connector is never
+    /// read from itself.
+    DeadLetters(GlobalId),
 }
 
 impl SourceConnector {
+    /// The description of the rows presented by a [`SourceConnector::DeadLetters`]
+    /// source: the raw key and value of each record that failed to decode, where
+    /// it was read from, and why it failed.
+    pub fn dead_letters_desc() -> RelationDesc {
+        RelationDesc::empty()
+            .with_named_column("key", ScalarType::Bytes.nullable(true))
+            .with_named_column("value", ScalarType::Bytes.nullable(true))
+            .with_named_column("partition", ScalarType::Int32.nullable(false))
+            .with_named_column("offset", ScalarType::Int64.nullable(false))
+            .with_named_column("error", ScalarType::String.nullable(false))
+    }
+
     /// Returns `true` if this connector yields input data (including
     /// timestamps) that is stable across restarts. This is important for
     /// exactly-once Sinks that need to ensure that the same data is written,
@@ -673,7 +724,7 @@ impl SourceConnector {
     pub fn caching_enabled(&self) -> bool {
         match self {
             SourceConnector::External { connector, .. } => connector.caching_enabled(),
-            SourceConnector::Local => false,
+            SourceConnector::Local | SourceConnector::DeadLetters(_) => false,
         }
     }
 }
@@ -753,6 +804,15 @@ impl ExternalSourceConnector {
         }
     }
 
+    /// Returns the per-message metadata columns requested via `INCLUDE`, in
+    /// the order in which they should be appended to each row.
+    pub fn include_metadata(&self) -> &[IncludedMetadata] {
+        match self {
+            ExternalSourceConnector::Kafka(k) => &k.include_metadata,
+            _ => &[],
+        }
+    }
+
     /// Returns whether or not source caching is enabled for this connector
     pub fn caching_enabled(&self) -> bool {
         match self {
@@ -858,6 +918,58 @@ pub struct KafkaSourceConnector {
     // This field gets set after the initial construction of this struct, so this is None if it has
     // not yet been set.
     pub cached_files: Option<Vec<PathBuf>>,
+    // Whether the decoded key columns are prepended to each row, as requested by `INCLUDE KEY`.
+    pub include_key: bool,
+    // The metadata columns to append to each row, as requested by `INCLUDE`.
+    pub include_metadata: Vec<IncludedMetadata>,
+}
+
+/// A piece of per-message metadata that can be exposed as a column of a
+/// source.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum IncludedMetadata {
+    /// The partition the message was read from, as an `int`.
+    Partition,
+    /// The upstream offset of the message within its partition, as a `bigint`.
+    Offset,
+    /// The upstream timestamp of the message, as a `timestamp`.
+    Timestamp,
+    /// The headers of the message, as a `list` of `(key text, value bytea)`
+    /// records.
+    Headers,
+}
+
+impl IncludedMetadata {
+    /// The default name of the column holding this metadata.
+    pub fn default_name(&self) -> &'static str {
+        match self {
+            IncludedMetadata::Partition => "partition",
+            IncludedMetadata::Offset => "offset",
+            IncludedMetadata::Timestamp => "timestamp",
+            IncludedMetadata::Headers => "headers",
+        }
+    }
+
+    /// The type of the column holding this metadata.
+    pub fn column_type(&self) -> ColumnType {
+        match self {
+            IncludedMetadata::Partition => ScalarType::Int32.nullable(false),
+            IncludedMetadata::Offset => ScalarType::Int64.nullable(false),
+            IncludedMetadata::Timestamp => ScalarType::Timestamp.nullable(true),
+            IncludedMetadata::Headers => ScalarType::List {
+                element_type: Box::new(ScalarType::Record {
+                    fields: vec![
+                        ("key".into(), ScalarType::String.nullable(false)),
+                        ("value".into(), ScalarType::Bytes.nullable(true)),
+                    ],
+                    custom_oid: None,
+                    custom_name: None,
+                }),
+                custom_oid: None,
+            }
+            .nullable(false),
+        }
+    }
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
@@ -933,6 +1045,13 @@ pub struct KafkaSinkConnector {
     pub key_schema_id: Option<i32>,
     pub value_schema_id: i32,
     pub consistency: Option<KafkaSinkConsistencyConnector>,
+    /// The number of partitions in the topic.
+    pub partition_count: i32,
+    /// An expression over the key columns that chooses the partition each
+    /// record is written to, modulo `partition_count`. If not set, or if the
+    /// expression evaluates to `NULL` or an error, the partition is chosen by
+    /// the producer.
+    pub partition_by: Option<MirScalarExpr>,
     pub exactly_once: bool,
     // Source dependencies for exactly-once sinks.
     pub transitive_source_dependencies: Vec<GlobalId>,
@@ -992,6 +1111,8 @@ pub struct TailSinkConnector {
     pub emit_progress: bool,
     pub object_columns: usize,
     pub value_desc: RelationDesc,
+    /// If set, the tail completes once its input frontier passes this time.
+    pub up_to: Option<Timestamp>,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
@@ -1023,6 +1144,10 @@ pub struct KafkaSinkConnectorBuilder {
     pub topic_suffix_nonce: String,
     pub partition_count: i32,
     pub replication_factor: i32,
+    /// Whether the topic should be created with `cleanup.policy=compact`.
+    pub compact: bool,
+    /// The `PARTITION BY` expression, planned against the key columns.
+    pub partition_by: Option<MirScalarExpr>,
     pub fuel: usize,
     pub consistency_value_schema: Option<String>,
     pub config_options: BTreeMap<String, String>,
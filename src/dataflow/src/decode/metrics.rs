@@ -9,8 +9,28 @@
 
 //! Prometheus metrics for decode
 
+use std::collections::HashMap;
+use std::time::Instant;
+
 use lazy_static::lazy_static;
-use prometheus::{register_uint_gauge_vec, UIntGaugeVec};
+use prometheus::core::AtomicI64;
+use prometheus::{
+    register_int_counter_vec, register_int_gauge_vec, register_uint_gauge_vec, DeleteOnDropCounter,
+    DeleteOnDropGauge, IntCounterVec, IntGaugeVec, UIntGaugeVec,
+};
+
+use expr::SourceInstanceId;
+
+use crate::logging::materialized::{Logger, MaterializedEvent};
+use crate::source::INGESTION_RATE_WINDOW;
+
+const PARTITION_LABELS: &[&str] = &[
+    "topic",
+    "source_id",
+    "source_instance",
+    "partition_id",
+    "worker_id",
+];
 
 lazy_static! {
     pub(crate) static ref DEBEZIUM_UPSERT_COUNT: UIntGaugeVec = register_uint_gauge_vec!(
@@ -19,4 +39,198 @@ lazy_static! {
         &["source_id", "worker_id"]
     )
     .unwrap();
+    static ref BYTES_DECODED: IntCounterVec = register_int_counter_vec!(
+        "mz_partition_bytes_decoded",
+        "The number of bytes of rows decoded per partition.",
+        PARTITION_LABELS
+    )
+    .unwrap();
+    static ref DECODED_BYTES_PER_SECOND: IntGaugeVec = register_int_gauge_vec!(
+        "mz_partition_decoded_bytes_per_second",
+        "The rate at which bytes of rows are decoded per partition.",
+        PARTITION_LABELS
+    )
+    .unwrap();
+}
+
+/// Decoding statistics for the partitions of a source, as decoded by one
+/// worker, recorded to both Prometheus and the `mz_source_ingestion` system
+/// table.
+///
+/// The statistics are logged as differences, so those of the workers that
+/// decode a partition sum to the partition's totals.
+pub struct DecodeMetrics {
+    source_name: String,
+    source_id: SourceInstanceId,
+    worker_id: usize,
+    logger: Option<Logger>,
+    partitions: HashMap<Option<String>, PartitionDecodeMetrics>,
+}
+
+impl DecodeMetrics {
+    /// Initialises decode metrics for a given (source_id, worker_id)
+    ///
+    /// `source_name` must match the name under which the source reports its
+    /// ingestion statistics.
+    pub fn new(
+        source_name: String,
+        source_id: SourceInstanceId,
+        worker_id: usize,
+        logger: Option<Logger>,
+    ) -> DecodeMetrics {
+        DecodeMetrics {
+            source_name,
+            source_id,
+            worker_id,
+            logger,
+            partitions: HashMap::new(),
+        }
+    }
+
+    /// Record the decoding of a record of `partition` into rows of `bytes`
+    /// bytes
+    pub fn record_decoded(&mut self, partition: &Option<String>, bytes: usize) {
+        if !self.partitions.contains_key(partition) {
+            let metrics = PartitionDecodeMetrics::new(
+                &self.source_name,
+                self.source_id,
+                partition,
+                self.worker_id,
+            );
+            self.partitions.insert(partition.clone(), metrics);
+        }
+        let metrics = self.partitions.get_mut(partition).unwrap();
+        let bytes = bytes as i64;
+        metrics.bytes_decoded.inc_by(bytes);
+        metrics.bytes += bytes;
+        metrics.window_bytes += bytes;
+    }
+
+    /// Update the decoding rates of every partition, and log any changes to
+    /// their decoding statistics.
+    pub fn record_partition_decoding(&mut self) {
+        let now = Instant::now();
+        for (partition, metrics) in self.partitions.iter_mut() {
+            metrics.update_rate(now);
+            let (bytes, bytes_per_second) = (metrics.bytes, metrics.bytes_per_second);
+            let (last_bytes, last_bytes_per_second) = metrics.last;
+            if (bytes, bytes_per_second) == metrics.last {
+                continue;
+            }
+            if let Some(logger) = self.logger.as_mut() {
+                logger.log(decoding_event(
+                    &self.source_name,
+                    self.source_id,
+                    partition,
+                    bytes - last_bytes,
+                    bytes_per_second - last_bytes_per_second,
+                ));
+            }
+            metrics.last = (bytes, bytes_per_second);
+        }
+    }
+}
+
+impl Drop for DecodeMetrics {
+    fn drop(&mut self) {
+        // retract our partitions from logging
+        if let Some(logger) = self.logger.as_mut() {
+            for (partition, metrics) in self.partitions.iter() {
+                let (last_bytes, last_bytes_per_second) = metrics.last;
+                logger.log(decoding_event(
+                    &self.source_name,
+                    self.source_id,
+                    partition,
+                    -last_bytes,
+                    -last_bytes_per_second,
+                ));
+            }
+        }
+    }
+}
+
+/// Constructs an ingestion event that changes only the decoding statistics of
+/// a partition; the source itself reports the others.
+fn decoding_event(
+    source_name: &str,
+    source_id: SourceInstanceId,
+    partition: &Option<String>,
+    decoded_bytes: i64,
+    decoded_bytes_per_second: i64,
+) -> MaterializedEvent {
+    MaterializedEvent::SourceIngestion {
+        source_name: source_name.to_string(),
+        source_id,
+        partition_id: partition.clone(),
+        offset: 0,
+        messages: 0,
+        raw_bytes: 0,
+        lag_ms: 0,
+        messages_per_second: 0,
+        raw_bytes_per_second: 0,
+        decoded_bytes,
+        decoded_bytes_per_second,
+    }
+}
+
+/// Partition-specific decoding metrics
+struct PartitionDecodeMetrics {
+    /// Total number of bytes of rows decoded
+    bytes_decoded: DeleteOnDropCounter<'static, AtomicI64>,
+    /// Bytes of rows decoded per second over the last complete rate window
+    decoded_bytes_per_second: DeleteOnDropGauge<'static, AtomicI64>,
+    bytes: i64,
+    bytes_per_second: i64,
+    /// The bytes and rate most recently logged
+    last: (i64, i64),
+    /// Start of the current rate window, and the bytes decoded since
+    window_start: Instant,
+    window_bytes: i64,
+}
+
+impl PartitionDecodeMetrics {
+    fn new(
+        source_name: &str,
+        source_id: SourceInstanceId,
+        partition: &Option<String>,
+        worker_id: usize,
+    ) -> PartitionDecodeMetrics {
+        let labels = &[
+            source_name,
+            &source_id.source_id.to_string(),
+            &source_id.dataflow_id.to_string(),
+            partition.as_deref().unwrap_or("none"),
+            &worker_id.to_string(),
+        ];
+        PartitionDecodeMetrics {
+            bytes_decoded: DeleteOnDropCounter::new_with_error_handler(
+                BYTES_DECODED.with_label_values(labels),
+                &BYTES_DECODED,
+                |e, v| log::debug!("unable to delete metric {}: {}", v.fq_name(), e),
+            ),
+            decoded_bytes_per_second: DeleteOnDropGauge::new_with_error_handler(
+                DECODED_BYTES_PER_SECOND.with_label_values(labels),
+                &DECODED_BYTES_PER_SECOND,
+                |e, v| log::debug!("unable to delete metric {}: {}", v.fq_name(), e),
+            ),
+            bytes: 0,
+            bytes_per_second: 0,
+            last: (0, 0),
+            window_start: Instant::now(),
+            window_bytes: 0,
+        }
+    }
+
+    /// Close the current rate window, if it has run for at least
+    /// `INGESTION_RATE_WINDOW`
+    fn update_rate(&mut self, now: Instant) {
+        let elapsed = now.duration_since(self.window_start);
+        if elapsed < INGESTION_RATE_WINDOW {
+            return;
+        }
+        self.bytes_per_second = (self.window_bytes as f64 / elapsed.as_secs_f64()) as i64;
+        self.decoded_bytes_per_second.set(self.bytes_per_second);
+        self.window_start = now;
+        self.window_bytes = 0;
+    }
 }
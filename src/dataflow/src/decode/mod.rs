@@ -48,6 +48,8 @@ mod csv;
 mod metrics;
 mod protobuf;
 
+pub use self::metrics::DecodeMetrics;
+
 /// Update row to blank out retractions of rows that we have never seen
 pub fn rewrite_for_upsert(
     val: Result<Row, DataflowError>,
@@ -532,10 +534,13 @@ where
                     for SourceOutput {
                         key,
                         value,
+                        partition,
                         position,
                         upstream_time_millis,
+                        metadata,
                     } in data.iter()
                     {
+                        let (raw_key, raw_value) = (key, value);
                         let key_cursor = &mut key.as_slice();
                         let key = if let (Some(key_decoder), false) =
                             (key_decoder.as_mut(), key.is_empty())
@@ -559,7 +564,10 @@ where
                             session.give(DecodeResult {
                                 key,
                                 value: None,
+                                partition: partition.into(),
                                 position: *position,
+                                metadata: metadata.clone(),
+                                raw: None,
                             });
                         } else {
                             let value = match &value {
@@ -594,15 +602,30 @@ where
                                 .into())),
                             };
 
-                            if matches!(&key, Some(Err(_))) || matches!(&value, Some(Err(_))) {
+                            let failed =
+                                matches!(&key, Some(Err(_))) || matches!(&value, Some(Err(_)));
+                            if failed {
                                 n_errors += 1;
                             } else if matches!(&value, Some(Ok(_))) {
                                 n_successes += 1;
                             }
+                            // Retain the undecodable record, so that it can be dead-lettered.
+                            let raw = if failed {
+                                let raw_value = match raw_value {
+                                    MessagePayload::Data(value) => value.clone(),
+                                    MessagePayload::EOF => vec![],
+                                };
+                                Some((raw_key.clone(), raw_value))
+                            } else {
+                                None
+                            };
                             session.give(DecodeResult {
                                 key,
                                 value,
+                                partition: partition.into(),
                                 position: *position,
+                                metadata: metadata.clone(),
+                                raw,
                             });
                         }
                     }
@@ -620,6 +643,40 @@ where
     (results, None)
 }
 
+/// Record the size of the rows decoded from each partition to `metrics`,
+/// passing the decoded records through unchanged.
+///
+/// Rates are updated whenever the operator is scheduled, which the source's
+/// regular frontier advancement ensures happens even when no records arrive.
+pub fn render_decode_metrics<G>(
+    stream: &Stream<G, DecodeResult>,
+    mut metrics: DecodeMetrics,
+) -> Stream<G, DecodeResult>
+where
+    G: Scope,
+{
+    let mut vector = Vec::new();
+    stream.unary_frontier(Pipeline, "DecodeMetrics", move |_, _| {
+        move |input, output| {
+            input.for_each(|cap, data| {
+                data.swap(&mut vector);
+                for result in vector.iter() {
+                    let bytes = [&result.key, &result.value]
+                        .iter()
+                        .map(|row| match row {
+                            Some(Ok(row)) => row.data().len(),
+                            _ => 0,
+                        })
+                        .sum();
+                    metrics.record_decoded(&result.partition, bytes);
+                }
+                output.session(&cap).give_vec(&mut vector);
+            });
+            metrics.record_partition_decoding();
+        }
+    })
+}
+
 /// Decode arbitrary chunks of bytes into rows.
 ///
 /// This decode API is used for upstream connectors
@@ -697,8 +754,10 @@ where
                 for SourceOutput {
                     key,
                     value,
+                    partition,
                     position: _,
                     upstream_time_millis,
+                    metadata: _,
                 } in data.iter()
                 {
                     let key_cursor = &mut key.as_slice();
@@ -748,7 +807,10 @@ where
                                     session.give(DecodeResult {
                                         key,
                                         value: Some(value),
+                                        partition: partition.into(),
                                         position: Some(n_seen),
+                                        metadata: Row::default(),
+                                        raw: None,
                                     });
                                     n_seen += 1;
                                 }
@@ -770,7 +832,10 @@ where
                         session.give(DecodeResult {
                             key,
                             value: None,
+                            partition: partition.into(),
                             position: None,
+                            metadata: Row::default(),
+                            raw: None,
                         });
                     } else {
                         let value_bytes_remaining = &mut value.as_slice();
@@ -811,7 +876,10 @@ where
                                 session.give(DecodeResult {
                                     key,
                                     value: Some(value),
+                                    partition: partition.into(),
                                     position: Some(n_seen),
+                                    metadata: Row::default(),
+                                    raw: None,
                                 });
                                 value_buf = vec![];
                                 break;
@@ -819,7 +887,10 @@ where
                                 session.give(DecodeResult {
                                     key: key.clone(),
                                     value: Some(value),
+                                    partition: partition.into(),
                                     position: Some(n_seen),
+                                    metadata: Row::default(),
+                                    raw: None,
                                 });
                             }
                             if is_err {
@@ -20,6 +20,7 @@ use timely::logging::WorkerIdentifier;
 
 use super::{LogVariant, MaterializedLog};
 use crate::arrangement::KeysValsHandle;
+use dataflow_types::DataflowError;
 use expr::{GlobalId, SourceInstanceId};
 use repr::{Datum, Row, Timestamp};
 
@@ -104,6 +105,15 @@ pub enum MaterializedEvent {
     },
     /// Peek command, true for install and false for retire.
     Peek(Peek, bool),
+    /// Tracks the errors produced by a source instance.
+    SourceError {
+        /// Source identifier
+        source_id: SourceInstanceId,
+        /// The error
+        error: DataflowError,
+        /// Change in the number of occurrences of the error
+        diff: i64,
+    },
     /// Tracks the source name, id, partition id, and received/ingested offsets
     SourceInfo {
         /// Name of the source
@@ -117,8 +127,46 @@ pub enum MaterializedEvent {
         /// Difference between the previous timestamp and current highest timestamp we've seen
         timestamp: i64,
     },
+    /// Tracks the ingestion statistics of a source partition, as the
+    /// difference from the previously reported statistics.
+    SourceIngestion {
+        /// Name of the source
+        source_name: String,
+        /// Source identifier
+        source_id: SourceInstanceId,
+        /// Partition identifier
+        partition_id: Option<String>,
+        /// Change in the offset of the most recently ingested message
+        offset: i64,
+        /// Change in the number of messages ingested
+        messages: i64,
+        /// Change in the number of bytes of message keys and values ingested
+        raw_bytes: i64,
+        /// Change in the lag of the most recently ingested message behind its upstream timestamp
+        lag_ms: i64,
+        /// Change in the rate at which messages are ingested
+        messages_per_second: i64,
+        /// Change in the rate at which bytes of message keys and values are ingested
+        raw_bytes_per_second: i64,
+        /// Change in the number of bytes of decoded rows
+        decoded_bytes: i64,
+        /// Change in the rate at which bytes of rows are decoded
+        decoded_bytes_per_second: i64,
+    },
     /// Available frontier information for views.
     Frontier(GlobalId, Timestamp, i64),
+    /// Tracks the size of the state of an upsert source, as the difference
+    /// from the previously reported size.
+    UpsertState {
+        /// Materialize source identifier
+        source_id: SourceInstanceId,
+        /// Change in the number of keys in the state
+        keys: i64,
+        /// Change in the number of keys whose values are held in memory
+        memory_keys: i64,
+        /// Change in the number of bytes the state occupies on disk
+        disk_bytes: i64,
+    },
 }
 
 /// A logged peek event.
@@ -171,7 +219,10 @@ pub fn construct<A: Allocate>(
         let (mut kafka_broker_rtt_out, kafka_broker_rtt) = demux.new_output();
         let (mut kafka_consumer_info_out, kafka_consumer_info) = demux.new_output();
         let (mut peek_out, peek) = demux.new_output();
+        let (mut source_errors_out, source_errors) = demux.new_output();
         let (mut source_info_out, source_info) = demux.new_output();
+        let (mut source_ingestion_out, source_ingestion) = demux.new_output();
+        let (mut upsert_state_out, upsert_state) = demux.new_output();
 
         let mut demux_buffer = Vec::new();
         demux.build(move |_capability| {
@@ -183,7 +234,10 @@ pub fn construct<A: Allocate>(
                 let mut kafka_broker_rtt = kafka_broker_rtt_out.activate();
                 let mut kafka_consumer_info = kafka_consumer_info_out.activate();
                 let mut peek = peek_out.activate();
+                let mut source_errors = source_errors_out.activate();
                 let mut source_info = source_info_out.activate();
+                let mut source_ingestion = source_ingestion_out.activate();
+                let mut upsert_state = upsert_state_out.activate();
 
                 input.for_each(|time, data| {
                     data.swap(&mut demux_buffer);
@@ -194,7 +248,10 @@ pub fn construct<A: Allocate>(
                     let mut kafka_broker_rtt_session = kafka_broker_rtt.session(&time);
                     let mut kafka_consumer_info_session = kafka_consumer_info.session(&time);
                     let mut peek_session = peek.session(&time);
+                    let mut source_errors_session = source_errors.session(&time);
                     let mut source_info_session = source_info.session(&time);
+                    let mut source_ingestion_session = source_ingestion.session(&time);
+                    let mut upsert_state_session = upsert_state.session(&time);
 
                     for (time, worker, datum) in demux_buffer.drain(..) {
                         let time_ns = time.as_nanos() as Timestamp;
@@ -328,6 +385,57 @@ pub fn construct<A: Allocate>(
                                     (offset, timestamp),
                                 ));
                             }
+                            MaterializedEvent::SourceError {
+                                source_id,
+                                error,
+                                diff,
+                            } => {
+                                source_errors_session.give((
+                                    (source_id, worker, error),
+                                    time_ms,
+                                    diff as isize,
+                                ));
+                            }
+                            MaterializedEvent::SourceIngestion {
+                                source_name,
+                                source_id,
+                                partition_id,
+                                offset,
+                                messages,
+                                raw_bytes,
+                                lag_ms,
+                                messages_per_second,
+                                raw_bytes_per_second,
+                                decoded_bytes,
+                                decoded_bytes_per_second,
+                            } => {
+                                source_ingestion_session.give((
+                                    (source_name, source_id, partition_id),
+                                    time_ms,
+                                    vec![
+                                        offset,
+                                        messages,
+                                        raw_bytes,
+                                        lag_ms,
+                                        messages_per_second,
+                                        raw_bytes_per_second,
+                                        decoded_bytes,
+                                        decoded_bytes_per_second,
+                                    ],
+                                ));
+                            }
+                            MaterializedEvent::UpsertState {
+                                source_id,
+                                keys,
+                                memory_keys,
+                                disk_bytes,
+                            } => {
+                                upsert_state_session.give((
+                                    (source_id, worker),
+                                    time_ms,
+                                    vec![keys, memory_keys, disk_bytes],
+                                ));
+                            }
                         }
                     }
                 });
@@ -433,6 +541,22 @@ pub fn construct<A: Allocate>(
                 }
             });
 
+        let source_errors_current = source_errors.as_collection().map({
+            move |(id, worker, error)| {
+                let provenance = error.provenance();
+                Row::pack_slice(&[
+                    Datum::String(&id.source_id.to_string()),
+                    Datum::Int64(id.dataflow_id as i64),
+                    Datum::Int64(worker as i64),
+                    Datum::from(provenance.and_then(|p| p.partition.as_deref())),
+                    Datum::from(provenance.and_then(|p| p.offset)),
+                    Datum::from(provenance.and_then(|p| p.encoding.as_deref())),
+                    Datum::from(error.is_retryable()),
+                    Datum::String(&error.to_string()),
+                ])
+            }
+        });
+
         let source_info_current = source_info.as_collection().count().map({
             move |((name, id, pid), (offset, timestamp))| {
                 Row::pack_slice(&[
@@ -446,6 +570,38 @@ pub fn construct<A: Allocate>(
             }
         });
 
+        let source_ingestion_current = source_ingestion.as_collection().count().map({
+            move |((name, id, pid), diff_vector)| {
+                Row::pack_slice(&[
+                    Datum::String(&name),
+                    Datum::String(&id.source_id.to_string()),
+                    Datum::Int64(id.dataflow_id as i64),
+                    Datum::from(pid.as_deref()),
+                    Datum::Int64(diff_vector[0]),
+                    Datum::Int64(diff_vector[1]),
+                    Datum::Int64(diff_vector[2]),
+                    Datum::Int64(diff_vector[3]),
+                    Datum::Int64(diff_vector[4]),
+                    Datum::Int64(diff_vector[5]),
+                    Datum::Int64(diff_vector[6]),
+                    Datum::Int64(diff_vector[7]),
+                ])
+            }
+        });
+
+        let upsert_state_current = upsert_state.as_collection().count().map({
+            move |((source_id, worker), diff_vector)| {
+                Row::pack_slice(&[
+                    Datum::String(&source_id.source_id.to_string()),
+                    Datum::Int64(source_id.dataflow_id as i64),
+                    Datum::Int64(worker as i64),
+                    Datum::Int64(diff_vector[0]),
+                    Datum::Int64(diff_vector[1]),
+                    Datum::Int64(diff_vector[2]),
+                ])
+            }
+        });
+
         // Duration statistics derive from the non-rounded event times.
         let peek_duration = peek
             .unary(
@@ -538,10 +694,22 @@ pub fn construct<A: Allocate>(
                 LogVariant::Materialized(MaterializedLog::PeekDuration),
                 peek_duration,
             ),
+            (
+                LogVariant::Materialized(MaterializedLog::SourceErrors),
+                source_errors_current,
+            ),
             (
                 LogVariant::Materialized(MaterializedLog::SourceInfo),
                 source_info_current,
             ),
+            (
+                LogVariant::Materialized(MaterializedLog::SourceIngestion),
+                source_ingestion_current,
+            ),
+            (
+                LogVariant::Materialized(MaterializedLog::UpsertState),
+                upsert_state_current,
+            ),
         ];
 
         use differential_dataflow::operators::arrange::arrangement::ArrangeByKey;
@@ -104,6 +104,7 @@ use std::any::Any;
 use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
 use std::iter;
+use std::path::PathBuf;
 use std::rc::Rc;
 use std::rc::Weak;
 
@@ -159,6 +160,9 @@ pub struct RenderState {
     /// Frontier of sink writes (all subsequent writes will be at times at or
     /// equal to this frontier)
     pub sink_write_frontiers: HashMap<GlobalId, Rc<RefCell<Antichain<Timestamp>>>>,
+    /// The directory in which upsert sources that spill to disk keep their
+    /// state.
+    pub upsert_state_directory: PathBuf,
 }
 
 /// A container for "tokens" that are relevant to an in-construction dataflow.
@@ -1662,6 +1662,7 @@ fn reduction_type(func: &AggregateFunc) -> ReductionType {
         | AggregateFunc::MaxFloat32
         | AggregateFunc::MaxFloat64
         | AggregateFunc::MaxDecimal
+        | AggregateFunc::MaxAPD
         | AggregateFunc::MaxBool
         | AggregateFunc::MaxString
         | AggregateFunc::MaxDate
@@ -1672,12 +1673,15 @@ fn reduction_type(func: &AggregateFunc) -> ReductionType {
         | AggregateFunc::MinFloat32
         | AggregateFunc::MinFloat64
         | AggregateFunc::MinDecimal
+        | AggregateFunc::MinAPD
         | AggregateFunc::MinBool
         | AggregateFunc::MinString
         | AggregateFunc::MinDate
         | AggregateFunc::MinTimestamp
         | AggregateFunc::MinTimestampTz => ReductionType::Hierarchical,
-        AggregateFunc::JsonbAgg | AggregateFunc::JsonbObjectAgg => ReductionType::Basic,
+        AggregateFunc::JsonbAgg | AggregateFunc::JsonbObjectAgg | AggregateFunc::RangeAgg => {
+            ReductionType::Basic
+        }
     }
 }
 
@@ -1762,6 +1766,7 @@ pub mod monoids {
             | AggregateFunc::MaxFloat32
             | AggregateFunc::MaxFloat64
             | AggregateFunc::MaxDecimal
+            | AggregateFunc::MaxAPD
             | AggregateFunc::MaxBool
             | AggregateFunc::MaxString
             | AggregateFunc::MaxDate
@@ -1772,6 +1777,7 @@ pub mod monoids {
             | AggregateFunc::MinFloat32
             | AggregateFunc::MinFloat64
             | AggregateFunc::MinDecimal
+            | AggregateFunc::MinAPD
             | AggregateFunc::MinBool
             | AggregateFunc::MinString
             | AggregateFunc::MinDate
@@ -1788,7 +1794,8 @@ pub mod monoids {
             | AggregateFunc::All
             | AggregateFunc::Dummy
             | AggregateFunc::JsonbAgg
-            | AggregateFunc::JsonbObjectAgg => None,
+            | AggregateFunc::JsonbObjectAgg
+            | AggregateFunc::RangeAgg => None,
         }
     }
 }
@@ -9,7 +9,9 @@
 
 //! Logic related to the creation of dataflow sources.
 
+use std::cmp;
 use std::collections::HashMap;
+use std::iter;
 use std::rc::Rc;
 
 use differential_dataflow::hashable::Hashable;
@@ -32,8 +34,10 @@ use repr::{Datum, Row, Timestamp};
 use crate::decode::decode_cdcv2;
 use crate::decode::render_decode;
 use crate::decode::render_decode_delimited;
+use crate::decode::render_decode_metrics;
 use crate::decode::rewrite_for_upsert;
-use crate::logging::materialized::Logger;
+use crate::decode::DecodeMetrics;
+use crate::logging::materialized::{Logger, MaterializedEvent};
 use crate::metrics;
 use crate::operator::{CollectionExt, StreamExt};
 use crate::render::context::Context;
@@ -123,6 +127,9 @@ where
                 // whose contents will be concatenated and inserted along the collection.
                 let mut error_collections = Vec::<Collection<_, _>>::new();
 
+                // The records that fail to decode, if the source dead-letters them.
+                let mut dead_letter_collection = None;
+
                 let fast_forwarded = match &connector {
                     ExternalSourceConnector::Kafka(KafkaSourceConnector {
                         start_offsets, ..
@@ -130,6 +137,26 @@ where
                     _ => false,
                 };
 
+                // Sources may request that the decoded key and per-message metadata be
+                // included alongside the value columns.
+                let include_key_arity = match (&connector, &encoding) {
+                    (
+                        ExternalSourceConnector::Kafka(KafkaSourceConnector {
+                            include_key: true,
+                            ..
+                        }),
+                        SourceDataEncoding::KeyValue { key, .. },
+                    ) => key
+                        .key_desc()
+                        .expect("key description computed during planning")
+                        .arity(),
+                    _ => 0,
+                };
+                let include_metadata = !connector.include_metadata().is_empty();
+                // Kafka offsets are 0-indexed, unlike the positions of the
+                // records that Kafka sources produce.
+                let zero_indexed_offsets = matches!(connector, ExternalSourceConnector::Kafka(_));
+
                 // All workers are responsible for reading in Kafka sources. Other sources
                 // support single-threaded ingestion only. Note that in all cases we want all
                 // readers of the same source or same partition to reside on the same worker,
@@ -152,8 +179,15 @@ where
                     .ts_histories
                     .get(&orig_id)
                     .map(|history| history.clone());
+                let name = format!("{}-{}", connector.name(), uid);
+                // The name under which the source reports its ingestion
+                // statistics, and so under which its decoding is reported, too.
+                let metrics_name = connector
+                    .upstream_name()
+                    .map(ToOwned::to_owned)
+                    .unwrap_or_else(|| name.clone());
                 let source_config = SourceConfig {
-                    name: format!("{}-{}", connector.name(), uid),
+                    name,
                     sql_name: src.name.clone(),
                     upstream_name: connector.upstream_name().map(ToOwned::to_owned),
                     id: uid,
@@ -165,7 +199,7 @@ where
                     timestamp_frequency: ts_frequency,
                     worker_id: scope.index(),
                     worker_count: scope.peers(),
-                    logger: materialized_logging,
+                    logger: materialized_logging.clone(),
                     encoding: encoding.clone(),
                     caching_tx,
                 };
@@ -268,6 +302,24 @@ where
                                     .push(Rc::new(token));
                                 (oks, None)
                             } else {
+                                // The decoders only see the value columns, so they cannot
+                                // apply operators that refer to the included key columns.
+                                // Nor can they apply them ahead of an upsert keyed by the
+                                // value, which needs the records the operators would drop.
+                                let mut no_operators = None;
+                                let decode_operators = if include_key_arity > 0
+                                    || matches!(
+                                        envelope,
+                                        SourceEnvelope::Upsert(UpsertStyle::ValueColumns(_), _)
+                                    ) {
+                                    &mut no_operators
+                                } else {
+                                    &mut linear_operators
+                                };
+                                let key_encoding_name = key_encoding
+                                    .as_ref()
+                                    .map(|key_encoding| key_encoding.op_name());
+                                let value_encoding_name = value_encoding.op_name();
                                 let (results, extra_token) = if is_connector_delimited {
                                     render_decode_delimited(
                                         &ok_source,
@@ -275,7 +327,7 @@ where
                                         value_encoding,
                                         &self.debug_name,
                                         &envelope,
-                                        &mut linear_operators,
+                                        decode_operators,
                                         fast_forwarded,
                                     )
                                 } else {
@@ -285,7 +337,7 @@ where
                                         value_encoding,
                                         &self.debug_name,
                                         &envelope,
-                                        &mut linear_operators,
+                                        decode_operators,
                                         fast_forwarded,
                                     )
                                 };
@@ -297,6 +349,49 @@ where
                                         .push(Rc::new(tok));
                                 }
 
+                                // Measure the decoded size of the records, which the
+                                // source itself only knows the raw size of.
+                                let results = render_decode_metrics(
+                                    &results,
+                                    DecodeMetrics::new(
+                                        metrics_name,
+                                        uid,
+                                        scope.index(),
+                                        materialized_logging.clone(),
+                                    ),
+                                );
+
+                                // Split off the records that fail to decode, so that they
+                                // are presented by the source's dead-letter companion
+                                // rather than in its error stream.
+                                let results = if let Some(dead_letters_id) = src.dead_letters {
+                                    let mut row_packer = Row::default();
+                                    let (results, dead_letters) = results.ok_err(move |result| {
+                                        match pack_dead_letter(&mut row_packer, &result) {
+                                            Some(row) => Err(row),
+                                            None => Ok(result),
+                                        }
+                                    });
+                                    dead_letter_collection = Some((
+                                        dead_letters_id,
+                                        dead_letters.pass_through("decode-dead-letters"),
+                                    ));
+                                    results
+                                } else {
+                                    results
+                                };
+
+                                // Attribute decoding errors to the records that produced them.
+                                let results = results.map(move |result| {
+                                    attribute_decode_errors(
+                                        result,
+                                        orig_id,
+                                        key_encoding_name,
+                                        value_encoding_name,
+                                        zero_indexed_offsets,
+                                    )
+                                });
+
                                 // render debezium or regular upsert
                                 match &envelope {
                                     SourceEnvelope::Debezium(_, DebeziumMode::Upsert) => {
@@ -336,12 +431,49 @@ where
                                             errors.pass_through("decode-errors").as_collection();
                                         (stream, Some(errors))
                                     }
-                                    SourceEnvelope::Upsert => super::upsert::upsert(
-                                        &results,
-                                        self.as_of_frontier.clone(),
-                                        &mut linear_operators,
-                                        src.bare_desc.typ().arity(),
-                                    ),
+                                    SourceEnvelope::Upsert(style, backend) => {
+                                        let state = super::upsert::UpsertState::new(
+                                            backend,
+                                            &render_state.upsert_state_directory,
+                                            src.name.clone(),
+                                            uid,
+                                            scope.index(),
+                                            materialized_logging.clone(),
+                                        );
+                                        super::upsert::upsert(
+                                            &results,
+                                            self.as_of_frontier.clone(),
+                                            &mut linear_operators,
+                                            src.bare_desc.typ().arity(),
+                                            style.clone(),
+                                            state,
+                                        )
+                                    }
+                                    _ if include_key_arity > 0 || include_metadata => {
+                                        let mut row_packer = Row::default();
+                                        let (stream, errors) = results
+                                            .flat_map(
+                                                move |DecodeResult {
+                                                          key, value, metadata, ..
+                                                      }| {
+                                                    value.map(|value| {
+                                                        pack_included_columns(
+                                                            &mut row_packer,
+                                                            include_key_arity,
+                                                            key,
+                                                            value,
+                                                            &metadata,
+                                                        )
+                                                    })
+                                                },
+                                            )
+                                            .ok_err(std::convert::identity);
+                                        let stream =
+                                            stream.pass_through("decode-ok").as_collection();
+                                        let errors =
+                                            errors.pass_through("decode-errors").as_collection();
+                                        (stream, Some(errors))
+                                    }
                                     _ => {
                                         let (stream, errors) = results
                                             .flat_map(|DecodeResult { key: _, value, .. }| value)
@@ -465,9 +597,16 @@ where
                     _ => collection::concatenate(scope, error_collections),
                 };
 
+                // Report the source's errors to the `mz_worker_source_errors` log.
+                if let Some(logger) = materialized_logging {
+                    let mut error_logger = SourceErrorLogger::new(logger, uid);
+                    err_collection = err_collection
+                        .inspect(move |(error, _time, diff)| error_logger.log(error, *diff));
+                }
+
                 // Apply `as_of` to each timestamp.
                 match &envelope {
-                    SourceEnvelope::Upsert => {}
+                    SourceEnvelope::Upsert(..) => {}
                     _ => {
                         let as_of_frontier1 = self.as_of_frontier.clone();
                         collection = collection
@@ -495,6 +634,22 @@ where
                 // Introduce the stream by name, as an unarranged collection.
                 self.collections.insert(get, (collection, err_collection));
 
+                // Introduce the records that failed to decode under the ID of the
+                // source's dead-letter companion.
+                if let Some((dead_letters_id, dead_letters)) = dead_letter_collection {
+                    let as_of_frontier = self.as_of_frontier.clone();
+                    let dead_letters = dead_letters
+                        .map_in_place(move |(_, time, _)| time.advance_by(as_of_frontier.borrow()))
+                        .as_collection();
+                    self.collections.insert(
+                        MirRelationExpr::global_get(
+                            dead_letters_id,
+                            SourceConnector::dead_letters_desc().typ().clone(),
+                        ),
+                        (dead_letters, Collection::empty(scope)),
+                    );
+                }
+
                 let token = Rc::new(capability);
                 tokens.source_tokens.insert(src_id, token.clone());
 
@@ -506,6 +661,188 @@ where
                     .or_insert_with(Vec::new)
                     .push(Rc::downgrade(&token));
             }
+
+            // Dead letters are split off from the decoding of the source they
+            // belong to, which dataflows import in their stead.
+            SourceConnector::DeadLetters(_) => {
+                unreachable!("dead letters are never imported as a source")
+            }
+        }
+    }
+}
+
+/// Packs a decoded value together with the columns requested via `INCLUDE`:
+/// the `key_arity` decoded key columns come first, padded with nulls if the
+/// message had no key, and the metadata columns come last.
+fn pack_included_columns(
+    row_packer: &mut Row,
+    key_arity: usize,
+    key: Option<Result<Row, DataflowError>>,
+    value: Result<Row, DataflowError>,
+    metadata: &Row,
+) -> Result<Row, DataflowError> {
+    let value = value?;
+    if key_arity > 0 {
+        match key {
+            Some(key) => row_packer.extend_by_row(&key?),
+            None => row_packer.extend(iter::repeat(Datum::Null).take(key_arity)),
+        }
+    }
+    row_packer.extend_by_row(&value);
+    row_packer.extend_by_row(metadata);
+    Ok(row_packer.finish_and_reuse())
+}
+
+/// Describes a record that failed to decode as a row of its source's
+/// dead-letter companion: its raw key and value, the partition and offset from
+/// which it was read, and the decoding error. Returns `None` if the record
+/// decoded successfully.
+///
+/// Only Kafka sources dead-letter records, so the partition is numeric and the
+/// offset is one less than the record's position.
+fn pack_dead_letter(row_packer: &mut Row, result: &DecodeResult) -> Option<Row> {
+    let error = match (&result.key, &result.value) {
+        (_, Some(Err(e))) | (Some(Err(e)), _) => e.to_string(),
+        _ => return None,
+    };
+    let (key, value) = result.raw.clone().unwrap_or_default();
+    for bytes in &[key, value] {
+        row_packer.push(if bytes.is_empty() {
+            Datum::Null
+        } else {
+            Datum::Bytes(bytes)
+        });
+    }
+    let partition: i32 = result
+        .partition
+        .as_deref()
+        .and_then(|partition| partition.parse().ok())
+        .expect("Kafka records have numeric partitions");
+    let position = result.position.expect("Kafka records have positions");
+    row_packer.push(Datum::Int32(partition));
+    row_packer.push(Datum::Int64(position - 1));
+    row_packer.push(Datum::String(&error));
+    Some(row_packer.finish_and_reuse())
+}
+
+/// Annotates any decoding errors in `result` with the provenance of the record
+/// from which they arose.
+///
+/// If `zero_indexed_offsets` is set, the record's offset in the upstream system
+/// is one less than its position.
+fn attribute_decode_errors(
+    result: DecodeResult,
+    source_id: GlobalId,
+    key_encoding: Option<&str>,
+    value_encoding: &str,
+    zero_indexed_offsets: bool,
+) -> DecodeResult {
+    let DecodeResult {
+        key,
+        value,
+        partition,
+        position,
+        metadata,
+        raw,
+    } = result;
+    let attribute = |error: DataflowError, encoding: Option<&str>| match error {
+        DataflowError::DecodeError(e) => {
+            DataflowError::DecodeError(e.with_provenance(SourceErrorProvenance {
+                source_id,
+                partition: partition.clone(),
+                offset: match position {
+                    Some(position) if zero_indexed_offsets => Some(position - 1),
+                    position => position,
+                },
+                encoding: encoding.map(ToOwned::to_owned),
+            }))
+        }
+        error => error,
+    };
+    let key = key.map(|key| key.map_err(|e| attribute(e, key_encoding)));
+    let value = value.map(|value| value.map_err(|e| attribute(e, Some(value_encoding))));
+    DecodeResult {
+        key,
+        value,
+        partition,
+        position,
+        metadata,
+        raw,
+    }
+}
+
+/// The maximum number of distinct errors that a worker reports for a source
+/// instance.
+///
+/// Errors carry the offset of the record that produced them, so nearly every
+/// error is distinct, and the logger must remember each one it reports in
+/// order to retract it when the instance is dropped. Without a bound, a source
+/// with millions of corrupt records would hold a second copy of each.
+const MAX_LOGGED_SOURCE_ERRORS: usize = 10_000;
+
+/// Reports the errors of a source instance to the `mz_worker_source_errors`
+/// log, retracting any errors that remain when the instance is dropped.
+///
+/// At most [`MAX_LOGGED_SOURCE_ERRORS`] distinct errors are reported at once.
+struct SourceErrorLogger {
+    logger: Logger,
+    source_id: SourceInstanceId,
+    errors: HashMap<DataflowError, isize>,
+    /// Whether an error has gone unreported for want of room
+    truncated: bool,
+}
+
+impl SourceErrorLogger {
+    fn new(logger: Logger, source_id: SourceInstanceId) -> SourceErrorLogger {
+        SourceErrorLogger {
+            logger,
+            source_id,
+            errors: HashMap::new(),
+            truncated: false,
+        }
+    }
+
+    fn log(&mut self, error: &DataflowError, diff: isize) {
+        let diff = if let Some(count) = self.errors.get_mut(error) {
+            // Only the reported occurrences of an error can be retracted.
+            let diff = cmp::max(diff, -*count);
+            *count += diff;
+            if *count == 0 {
+                self.errors.remove(error);
+            }
+            diff
+        } else if diff <= 0 {
+            return;
+        } else if self.errors.len() >= MAX_LOGGED_SOURCE_ERRORS {
+            if !self.truncated {
+                warn!(
+                    "source {} produced more than {} distinct errors; \
+                    reporting no more of them to mz_source_errors",
+                    self.source_id, MAX_LOGGED_SOURCE_ERRORS
+                );
+                self.truncated = true;
+            }
+            return;
+        } else {
+            self.errors.insert(error.clone(), diff);
+            diff
+        };
+        self.logger.log(MaterializedEvent::SourceError {
+            source_id: self.source_id,
+            error: error.clone(),
+            diff: diff as i64,
+        });
+    }
+}
+
+impl Drop for SourceErrorLogger {
+    fn drop(&mut self) {
+        for (error, count) in self.errors.drain() {
+            self.logger.log(MaterializedEvent::SourceError {
+                source_id: self.source_id,
+                error,
+                diff: -(count as i64),
+            });
         }
     }
 }
@@ -14,11 +14,11 @@ use differential_dataflow::lattice::Lattice;
 use differential_dataflow::{AsCollection, Collection};
 
 use timely::dataflow::channels::pact::Exchange;
-use timely::dataflow::operators::{generic::Operator, Concat, OkErr};
+use timely::dataflow::operators::{generic::Operator, Concat, Map, OkErr};
 use timely::dataflow::{Scope, Stream};
 use timely::progress::Antichain;
 
-use dataflow_types::{DataflowError, LinearOperator};
+use dataflow_types::{DataflowError, LinearOperator, SourceError, UpsertStyle};
 use expr::{EvalError, MirScalarExpr};
 use log::error;
 use repr::{Datum, Diff, Row, RowArena, Timestamp};
@@ -27,17 +27,32 @@ use crate::operator::StreamExt;
 use crate::source::DecodeResult;
 use crate::source::SourceData;
 
+pub use self::state::UpsertState;
+
+mod state;
+
 /// Entrypoint to the upsert-specific transformations involved
 /// in rendering a stream that came from an upsert source.
 /// Upsert-specific operators are different from the rest of
 /// the rendering pipeline in that their input is a stream
 /// with two components instead of one, and the second component
 /// can be null or empty.
+///
+/// Sources whose key is formed by columns of the value (`style` is
+/// `UpsertStyle::ValueColumns`) derive the key of each record from its
+/// decoded value, and present only the value columns.
+///
+/// The latest value of each key is kept in `state`, which determines whether
+/// values are held in memory or spilled to disk. If `state` could not be
+/// created, or fails to read or write a value, the failure is presented as an
+/// error of the source, and no further updates are produced.
 pub fn upsert<G>(
     stream: &Stream<G, DecodeResult>,
     as_of_frontier: Antichain<Timestamp>,
     operators: &mut Option<LinearOperator>,
     source_arity: usize,
+    style: UpsertStyle,
+    state: Result<UpsertState, SourceError>,
 ) -> (
     Collection<G, Row, Diff>,
     Option<Collection<G, dataflow_types::DataflowError, Diff>>,
@@ -123,31 +138,84 @@ where
         None
     };
 
+    let (stream, prepend_key) = match style {
+        UpsertStyle::Default => (stream.clone(), true),
+        UpsertStyle::ValueColumns(key_indices) => {
+            let mut row_packer = Row::default();
+            let stream = stream.map(move |mut result| {
+                // A value that fails to decode has no key, so it is presented
+                // as a key decoding error.
+                result.key = match &result.value {
+                    Some(Ok(value)) => {
+                        let datums = value.unpack();
+                        row_packer.extend(key_indices.iter().map(|i| datums[*i]));
+                        Some(Ok(row_packer.finish_and_reuse()))
+                    }
+                    Some(Err(err)) => Some(Err(err.clone())),
+                    None => None,
+                };
+                result
+            });
+            (stream, false)
+        }
+    };
+
     let result_stream = stream.unary_frontier(
         Exchange::new(move |DecodeResult { key, .. }| key.hashed()),
         "Upsert",
-        move |_cap, _info| {
+        move |cap, _info| {
             // this is a map of (time) -> (capability, ((key) -> (value with max
             // offset))) This is a BTreeMap because we want to ensure that if we
             // receive (key1, value1, time 5) and (key1, value2, time 7) that we
             // send (key1, value1, time 5) before (key1, value2, time 7)
             let mut to_send = BTreeMap::<_, (_, HashMap<_, SourceData>)>::new();
-            // this is a map of (decoded key) -> (decoded_value). We store the
+            // `state` maps (decoded key) -> (decoded_value). We store the
             // latest value for a given key that way we know what to retract if
             // a new value with the same key comes along
-            let mut current_values = HashMap::new();
 
             let mut vector = Vec::new();
             let mut row_packer = repr::Row::default();
+            // Whether `state` has failed, after which it no longer knows what
+            // to retract.
+            let mut failed = false;
+            // A failure to create `state`, to be presented at the first
+            // activation.
+            let (mut state, mut create_error) = match state {
+                Ok(state) => (Some(state), None),
+                Err(err) => {
+                    let mut time = cap.time().clone();
+                    time.advance_by(as_of_frontier.borrow());
+                    (None, Some((cap.delayed(&time), err)))
+                }
+            };
 
             move |input, output| {
+                if let Some((cap, err)) = create_error.take() {
+                    output
+                        .session(&cap)
+                        .give((Err(err.into()), cap.time().clone(), 1));
+                }
+                let state = match &mut state {
+                    Some(state) => state,
+                    None => {
+                        input.for_each(|_, _| ());
+                        return;
+                    }
+                };
                 // Digest each input, reduce by presented timestamp.
                 input.for_each(|cap, data| {
                     data.swap(&mut vector);
+                    if failed {
+                        vector.clear();
+                        return;
+                    }
                     for DecodeResult {
                         key,
                         value: new_value,
+                        partition: _,
                         position: new_position,
+                        metadata: _,
+                        raw: _,
                     } in vector.drain(..)
                     {
                         let mut time = cap.time().clone();
@@ -212,7 +280,9 @@ where
                                         let value = data.value.unwrap();
                                         value.and_then(|row| {
                                             let mut datums = Vec::with_capacity(source_arity);
-                                            datums.extend(decoded_key.iter());
+                                            if prepend_key {
+                                                datums.extend(decoded_key.iter());
+                                            }
                                             datums.extend(row.iter());
                                             evaluate(
                                                 &datums,
@@ -228,9 +298,17 @@ where
                                     // retracted if new rows show up for the same key.
                                     let new_value = decoded_value.transpose();
                                     let old_value = if let Some(new_value) = &new_value {
-                                        current_values.insert(decoded_key, new_value.clone())
+                                        state.insert(decoded_key, new_value.clone())
                                     } else {
-                                        current_values.remove(&decoded_key)
+                                        state.remove(&decoded_key)
+                                    };
+                                    let old_value = match old_value {
+                                        Ok(old_value) => old_value,
+                                        Err(err) => {
+                                            session.give((Err(err.into()), cap.time().clone(), 1));
+                                            failed = true;
+                                            break;
+                                        }
                                     };
                                     if let Some(old_value) = old_value {
                                         // retract old value
@@ -249,6 +327,9 @@ where
                                 }
                             }
                         }
+                        if failed {
+                            break;
+                        }
                     } else {
                         // because this is a BTreeMap, the rest of the times in
                         // the map will be greater than this time. So if the
@@ -261,6 +342,10 @@ where
                 for time in removed_times {
                     to_send.remove(&time);
                 }
+                if failed {
+                    to_send.clear();
+                }
+                state.report_size();
             }
         },
     );
@@ -0,0 +1,487 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Stores for the latest value of each key of an upsert source.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use differential_dataflow::hashable::Hashable;
+use log::error;
+
+use dataflow_types::{DataflowError, SourceError, SourceErrorDetails, UpsertStateBackend};
+use expr::SourceInstanceId;
+use repr::Row;
+
+use crate::logging::materialized::{Logger, MaterializedEvent};
+
+/// The value of a key: either its decoded value, or the error produced while
+/// decoding it.
+type Value = Result<Row, DataflowError>;
+
+/// The minimum size of the log of a disk store before it is compacted.
+const COMPACTION_MIN_BYTES: u64 = 64 << 20;
+
+/// The latest value of each key of an upsert source on one worker.
+///
+/// Changes in the size of the state are reported to the `mz_upsert_state`
+/// introspection source, and retracted when the state is dropped.
+///
+/// A store that fails to read or write its values no longer reflects the
+/// latest value of each key, so such failures are reported as fatal errors of
+/// the source named `source_name`.
+pub struct UpsertState {
+    store: Box<dyn Store>,
+    source_name: String,
+    source_id: SourceInstanceId,
+    logger: Option<Logger>,
+    reported: StoreSize,
+}
+
+impl UpsertState {
+    /// Constructs the state described by `backend` for source instance
+    /// `source_id` on worker `worker_id`. Disk stores are kept in a
+    /// subdirectory of `directory`.
+    ///
+    /// Failing to create a disk store is reported as a fatal error of the
+    /// source, like any other failure of the store.
+    pub fn new(
+        backend: &UpsertStateBackend,
+        directory: &Path,
+        source_name: String,
+        source_id: SourceInstanceId,
+        worker_id: usize,
+        logger: Option<Logger>,
+    ) -> Result<UpsertState, SourceError> {
+        let store: Box<dyn Store> = match backend {
+            UpsertStateBackend::Memory => Box::new(MemoryStore::default()),
+            UpsertStateBackend::Disk { memory_keys } => {
+                let path = directory.join(format!(
+                    "{}-{}-{}",
+                    source_id.source_id, source_id.dataflow_id, worker_id
+                ));
+                match DiskStore::new(path.clone(), *memory_keys) {
+                    Ok(store) => Box::new(store),
+                    Err(e) => {
+                        return Err(SourceError::new(
+                            source_name,
+                            SourceErrorDetails::Fatal(format!(
+                                "creating upsert state in {}: {}",
+                                path.display(),
+                                e
+                            )),
+                        ))
+                    }
+                }
+            }
+        };
+        Ok(UpsertState {
+            store,
+            source_name,
+            source_id,
+            logger,
+            reported: StoreSize::default(),
+        })
+    }
+
+    /// Sets the value of `key`, returning its previous value, if any.
+    pub fn insert(&mut self, key: Row, value: Value) -> Result<Option<Value>, SourceError> {
+        let result = self.store.insert(key, value);
+        result.map_err(|e| self.fatal(e))
+    }
+
+    /// Removes the value of `key`, returning it, if any.
+    pub fn remove(&mut self, key: &Row) -> Result<Option<Value>, SourceError> {
+        let result = self.store.remove(key);
+        result.map_err(|e| self.fatal(e))
+    }
+
+    fn fatal(&self, error: anyhow::Error) -> SourceError {
+        SourceError::new(
+            self.source_name.clone(),
+            SourceErrorDetails::Fatal(format!("{:#}", error)),
+        )
+    }
+
+    /// Reports the size of the state, if it has changed since it was last
+    /// reported.
+    pub fn report_size(&mut self) {
+        let size = self.store.size();
+        self.report(size);
+    }
+
+    fn report(&mut self, size: StoreSize) {
+        if size == self.reported {
+            return;
+        }
+        if let Some(logger) = &self.logger {
+            logger.log(MaterializedEvent::UpsertState {
+                source_id: self.source_id,
+                keys: size.keys - self.reported.keys,
+                memory_keys: size.memory_keys - self.reported.memory_keys,
+                disk_bytes: size.disk_bytes - self.reported.disk_bytes,
+            });
+        }
+        self.reported = size;
+    }
+}
+
+impl Drop for UpsertState {
+    fn drop(&mut self) {
+        self.report(StoreSize::default());
+    }
+}
+
+/// The size of a store.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct StoreSize {
+    /// The number of keys in the store.
+    keys: i64,
+    /// The number of keys whose values are held in memory.
+    memory_keys: i64,
+    /// The number of bytes the store occupies on disk.
+    disk_bytes: i64,
+}
+
+trait Store {
+    fn insert(&mut self, key: Row, value: Value) -> Result<Option<Value>, anyhow::Error>;
+    fn remove(&mut self, key: &Row) -> Result<Option<Value>, anyhow::Error>;
+    fn size(&self) -> StoreSize;
+}
+
+/// A store that keeps every value in memory.
+#[derive(Default)]
+struct MemoryStore {
+    values: HashMap<Row, Value>,
+}
+
+impl Store for MemoryStore {
+    fn insert(&mut self, key: Row, value: Value) -> Result<Option<Value>, anyhow::Error> {
+        Ok(self.values.insert(key, value))
+    }
+
+    fn remove(&mut self, key: &Row) -> Result<Option<Value>, anyhow::Error> {
+        Ok(self.values.remove(key))
+    }
+
+    fn size(&self) -> StoreSize {
+        let keys = self.values.len() as i64;
+        StoreSize {
+            keys,
+            memory_keys: keys,
+            disk_bytes: 0,
+        }
+    }
+}
+
+/// A store that keeps the most recently used values in memory, and spills the
+/// remainder to an append-only log on disk.
+///
+/// The log is indexed in memory by the hash of each spilled key, so each
+/// spilled key costs a few dozen bytes of memory rather than the size of its
+/// value. Spilled records that are read back or superseded become garbage in
+/// the log, which is reclaimed by rewriting the live records to a new log once
+/// garbage makes up the majority of the log.
+///
+/// The store is not durable: upsert sources rebuild their state from the
+/// upstream source on restart, so the store's directory is removed when the
+/// store is dropped.
+struct DiskStore {
+    /// The directory that holds the log.
+    path: PathBuf,
+    /// The maximum number of values to hold in memory.
+    memory_keys: usize,
+    /// The values held in memory, with the tick of their last use.
+    cache: HashMap<Row, (Value, u64)>,
+    /// The keys held in memory, by the tick of their last use.
+    recency: BTreeMap<u64, Row>,
+    /// The tick to assign to the next use of a key.
+    tick: u64,
+    /// The locations of spilled records in the log, by the hash of their key.
+    index: HashMap<u64, Vec<Location>>,
+    /// The number of spilled records.
+    spilled: usize,
+    log: Log,
+}
+
+/// The location of a record in the log.
+#[derive(Clone, Copy)]
+struct Location {
+    offset: u64,
+    len: u64,
+}
+
+impl DiskStore {
+    fn new(path: PathBuf, memory_keys: usize) -> Result<DiskStore, io::Error> {
+        match fs::remove_dir_all(&path) {
+            Ok(()) => (),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => (),
+            Err(e) => return Err(e),
+        }
+        fs::create_dir_all(&path)?;
+        let log = Log::create(path.join("log"))?;
+        Ok(DiskStore {
+            path,
+            memory_keys,
+            cache: HashMap::new(),
+            recency: BTreeMap::new(),
+            tick: 0,
+            index: HashMap::new(),
+            spilled: 0,
+            log,
+        })
+    }
+
+    /// Removes the value of `key` from memory or from the log.
+    fn take(&mut self, key: &Row) -> Result<Option<Value>, anyhow::Error> {
+        if let Some((value, tick)) = self.cache.remove(key) {
+            self.recency.remove(&tick);
+            return Ok(Some(value));
+        }
+        let hash = key.hashed();
+        let locations = match self.index.get_mut(&hash) {
+            Some(locations) => locations,
+            None => return Ok(None),
+        };
+        for i in 0..locations.len() {
+            let path = &self.path;
+            let (spilled_key, value) = self
+                .log
+                .read(locations[i])
+                .with_context(|| format!("reading upsert state in {}", path.display()))?;
+            if spilled_key == *key {
+                let location = locations.swap_remove(i);
+                if locations.is_empty() {
+                    self.index.remove(&hash);
+                }
+                self.log.live_bytes -= location.len;
+                self.spilled -= 1;
+                return Ok(Some(value));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Spills the least recently used values until at most `memory_keys`
+    /// values are held in memory.
+    fn evict(&mut self) -> Result<(), anyhow::Error> {
+        while self.cache.len() > self.memory_keys {
+            let tick = *self
+                .recency
+                .keys()
+                .next()
+                .expect("recency tracks every cached key");
+            let key = self.recency.remove(&tick).expect("tick is present");
+            let (value, _) = self.cache.remove(&key).expect("cached key has a value");
+            let hash = key.hashed();
+            let path = &self.path;
+            let location = self
+                .log
+                .append(&key, &value)
+                .with_context(|| format!("writing upsert state in {}", path.display()))?;
+            self.index.entry(hash).or_default().push(location);
+            self.spilled += 1;
+        }
+        Ok(())
+    }
+
+    /// Rewrites the live records of the log to a new log, if garbage makes up
+    /// the majority of the log.
+    fn maybe_compact(&mut self) -> Result<(), anyhow::Error> {
+        if self.log.len < COMPACTION_MIN_BYTES || self.log.len < 2 * self.log.live_bytes {
+            return Ok(());
+        }
+        let path = &self.path;
+        self.log
+            .compact(self.index.values_mut().flatten())
+            .with_context(|| format!("compacting upsert state in {}", path.display()))
+    }
+}
+
+impl Store for DiskStore {
+    fn insert(&mut self, key: Row, value: Value) -> Result<Option<Value>, anyhow::Error> {
+        let old_value = self.take(&key)?;
+        self.tick += 1;
+        self.recency.insert(self.tick, key.clone());
+        self.cache.insert(key, (value, self.tick));
+        self.evict()?;
+        self.maybe_compact()?;
+        Ok(old_value)
+    }
+
+    fn remove(&mut self, key: &Row) -> Result<Option<Value>, anyhow::Error> {
+        let old_value = self.take(key)?;
+        self.maybe_compact()?;
+        Ok(old_value)
+    }
+
+    fn size(&self) -> StoreSize {
+        StoreSize {
+            keys: (self.cache.len() + self.spilled) as i64,
+            memory_keys: self.cache.len() as i64,
+            disk_bytes: self.log.len as i64,
+        }
+    }
+}
+
+impl Drop for DiskStore {
+    fn drop(&mut self) {
+        if let Err(e) = fs::remove_dir_all(&self.path) {
+            error!(
+                "failed to remove upsert state in {}: {}",
+                self.path.display(),
+                e
+            );
+        }
+    }
+}
+
+/// An append-only file of serialized `(key, value)` records.
+struct Log {
+    path: PathBuf,
+    writer: BufWriter<File>,
+    reader: File,
+    /// Whether `writer` holds records that have not been flushed to the file.
+    dirty: bool,
+    /// The length of the log, in bytes.
+    len: u64,
+    /// The number of bytes of the log occupied by live records.
+    live_bytes: u64,
+}
+
+impl Log {
+    fn create(path: PathBuf) -> Result<Log, io::Error> {
+        let writer = OpenOptions::new()
+            .create(true)
+            .truncate(true)
+            .write(true)
+            .open(&path)?;
+        let reader = File::open(&path)?;
+        Ok(Log {
+            path,
+            writer: BufWriter::new(writer),
+            reader,
+            dirty: false,
+            len: 0,
+            live_bytes: 0,
+        })
+    }
+
+    fn append(&mut self, key: &Row, value: &Value) -> Result<Location, io::Error> {
+        let record = bincode::serialize(&(key, value))
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        self.writer.write_all(&record)?;
+        let location = Location {
+            offset: self.len,
+            len: record.len() as u64,
+        };
+        self.dirty = true;
+        self.len += location.len;
+        self.live_bytes += location.len;
+        Ok(location)
+    }
+
+    fn read(&mut self, location: Location) -> Result<(Row, Value), io::Error> {
+        if self.dirty {
+            self.writer.flush()?;
+            self.dirty = false;
+        }
+        let mut record = vec![0; location.len as usize];
+        self.reader.seek(SeekFrom::Start(location.offset))?;
+        self.reader.read_exact(&mut record)?;
+        bincode::deserialize(&record).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Copies the records at `locations` to a new log that replaces this one,
+    /// updating `locations` to refer to their copies.
+    fn compact<'a, I>(&mut self, locations: I) -> Result<(), io::Error>
+    where
+        I: IntoIterator<Item = &'a mut Location>,
+    {
+        let compacted_path = self.path.with_extension("compacting");
+        let mut compacted = Log::create(compacted_path.clone())?;
+        let mut record = vec![];
+        if self.dirty {
+            self.writer.flush()?;
+            self.dirty = false;
+        }
+        for location in locations {
+            record.resize(location.len as usize, 0);
+            self.reader.seek(SeekFrom::Start(location.offset))?;
+            self.reader.read_exact(&mut record)?;
+            compacted.writer.write_all(&record)?;
+            compacted.dirty = true;
+            location.offset = compacted.len;
+            compacted.len += location.len;
+            compacted.live_bytes += location.len;
+        }
+        fs::rename(&compacted_path, &self.path)?;
+        compacted.path = self.path.clone();
+        *self = compacted;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use expr::GlobalId;
+    use repr::Datum;
+
+    use super::*;
+
+    #[test]
+    fn disk_store_create_failure() {
+        // A regular file where the state directory's parent should be makes
+        // the directory impossible to create.
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("file");
+        File::create(&file).unwrap();
+        let source_id = SourceInstanceId {
+            source_id: GlobalId::User(1),
+            dataflow_id: 0,
+        };
+        let backend = UpsertStateBackend::Disk { memory_keys: 0 };
+        let err = match UpsertState::new(&backend, &file, "src".into(), source_id, 0, None) {
+            Ok(_) => panic!("creating upsert state unexpectedly succeeded"),
+            Err(err) => err,
+        };
+        assert_eq!(err.source_name, "src");
+        match err.error {
+            SourceErrorDetails::Fatal(msg) => assert!(
+                msg.starts_with("creating upsert state in "),
+                "unexpected error: {}",
+                msg
+            ),
+            details => panic!("unexpected error: {:?}", details),
+        }
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn disk_store_write_failure() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut store = DiskStore::new(dir.path().join("store"), 0).unwrap();
+        // Writes to `/dev/full` fail with `ENOSPC`, and an unbuffered writer
+        // surfaces that failure as soon as a value is spilled.
+        let full = OpenOptions::new().write(true).open("/dev/full").unwrap();
+        store.log.writer = BufWriter::with_capacity(0, full);
+
+        let key = Row::pack_slice(&[Datum::Int64(1)]);
+        let value = Ok(Row::pack_slice(&[Datum::String("value")]));
+        let err = store.insert(key, value).unwrap_err();
+        assert!(
+            format!("{:#}", err).starts_with("writing upsert state in "),
+            "unexpected error: {:#}",
+            err
+        );
+    }
+}
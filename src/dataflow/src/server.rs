@@ -11,6 +11,9 @@
 
 use std::collections::HashMap;
 use std::convert::TryInto;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
 use std::sync::Mutex;
 use std::time::{Instant, UNIX_EPOCH};
 
@@ -200,6 +203,9 @@ pub struct Config {
     pub timely_worker: timely::WorkerConfig,
     /// Whether the server is running in experimental mode.
     pub experimental_mode: bool,
+    /// The directory in which upsert sources that spill to disk keep their
+    /// state.
+    pub upsert_state_directory: PathBuf,
 }
 
 /// Initiates a timely dataflow computation, processing materialized commands.
@@ -208,6 +214,21 @@ pub fn serve(config: Config) -> Result<WorkerGuards<()>, String> {
     let workers = config.command_receivers.len();
     assert!(workers > 0);
 
+    // Upsert state is rebuilt from the upstream source whenever a source is
+    // instantiated, so any state left behind by a previous process is garbage.
+    let upsert_state_directory = config.upsert_state_directory;
+    match fs::remove_dir_all(&upsert_state_directory) {
+        Ok(()) => (),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => (),
+        Err(e) => {
+            return Err(format!(
+                "clearing upsert state directory {}: {}",
+                upsert_state_directory.display(),
+                e
+            ))
+        }
+    }
+
     // Construct endpoints for each thread that will receive the coordinator's
     // sequenced command stream.
     //
@@ -239,6 +260,7 @@ pub fn serve(config: Config) -> Result<WorkerGuards<()>, String> {
                     dataflow_tokens: HashMap::new(),
                     caching_tx: None,
                     sink_write_frontiers: HashMap::new(),
+                    upsert_state_directory: upsert_state_directory.clone(),
                 },
                 materialized_logger: None,
                 command_rx,
@@ -38,9 +38,9 @@ use timely::dataflow::{Scope, Stream};
 use timely::progress::Antichain;
 
 use dataflow_types::{KafkaSinkConnector, SinkAsOf};
-use expr::GlobalId;
+use expr::{GlobalId, MirScalarExpr};
 use interchange::avro::{self, Encoder};
-use repr::{Diff, RelationDesc, Row, Timestamp};
+use repr::{Datum, Diff, RelationDesc, Row, RowArena, Timestamp};
 
 use crate::source::timestamp::TimestampBindingRc;
 
@@ -230,10 +230,19 @@ enum SendState {
     Shutdown,
 }
 
+/// An encoded key and value, and the partition they should be produced to, if
+/// any, or the reason the partition could not be determined.
+type EncodedKeyValue = (
+    Option<Vec<u8>>,
+    Option<Vec<u8>>,
+    Result<Option<i32>, String>,
+);
+
 #[derive(Debug)]
 struct EncodedRow {
     key: Option<Vec<u8>>,
     value: Option<Vec<u8>>,
+    partition: Result<Option<i32>, String>,
     count: usize,
 }
 
@@ -258,6 +267,10 @@ where
     let encoder = Encoder::new(key_desc, value_desc, connector.consistency.is_some());
     let key_schema_id = connector.key_schema_id;
     let value_schema_id = connector.value_schema_id;
+    let partition_by = connector
+        .partition_by
+        .clone()
+        .map(|expr| (expr, connector.partition_count));
 
     let encoded_stream = avro_encode_stream(
         stream,
@@ -269,6 +282,7 @@ where
         encoder,
         key_schema_id,
         value_schema_id,
+        partition_by,
         connector.fuel,
         name.clone(),
     );
@@ -296,7 +310,7 @@ where
 /// Updates that are not beyond the given [`SinkAsOf`] and/or the `gate_ts` in
 /// [`KafkaSinkConnector`] will be discarded without producing them.
 pub fn produce_to_kafka<G>(
-    stream: Stream<G, ((Option<Vec<u8>>, Option<Vec<u8>>), Timestamp, Diff)>,
+    stream: Stream<G, (EncodedKeyValue, Timestamp, Diff)>,
     id: GlobalId,
     name: String,
     connector: KafkaSinkConnector,
@@ -391,7 +405,15 @@ where
 
     let mut sink_logic = move |input: &mut FrontieredInputHandle<
         _,
-        ((Option<Vec<u8>>, Option<Vec<u8>>), Timestamp, Diff),
+        (
+            (
+                Option<Vec<u8>>,
+                Option<Vec<u8>>,
+                Result<Option<i32>, String>,
+            ),
+            Timestamp,
+            Diff,
+        ),
         _,
     >| {
         if s.shutdown_flag.load(Ordering::SeqCst) {
@@ -402,7 +424,7 @@ where
         // Queue all pending rows waiting to be sent to kafka
         input.for_each(|_, rows| {
             rows.swap(&mut vector);
-            for ((key, value), time, diff) in vector.drain(..) {
+            for ((key, value, partition), time, diff) in vector.drain(..) {
                 let should_emit = if as_of.strict {
                     as_of.frontier.less_than(&time)
                 } else {
@@ -433,6 +455,7 @@ where
                 rows.push(EncodedRow {
                     key,
                     value,
+                    partition,
                     count: diff,
                 });
                 s.metrics.rows_queued.inc();
@@ -527,6 +550,19 @@ where
                         } else {
                             record
                         };
+                        let record = match &encoded_row.partition {
+                            Ok(Some(partition)) => record.partition(*partition),
+                            Ok(None) => record,
+                            Err(e) => {
+                                // Producing the record to an arbitrary
+                                // partition would silently break the
+                                // partitioning that the user asked for.
+                                error!("unable to produce message in {}: {}", s.name, e);
+                                s.metrics.message_send_errors_counter.inc();
+                                s.shutdown_flag.store(true, Ordering::SeqCst);
+                                return false;
+                            }
+                        };
                         if let Err(retry) = s.send(record) {
                             return retry;
                         }
@@ -650,6 +686,9 @@ where
 ///
 /// Input [`Row`] updates must me compatible with the given [`Encoder`].
 ///
+/// If `partition_by` is given, each update is also assigned the partition that
+/// its key should be produced to. See [`partition_for_key`].
+///
 /// Updates that are not beyond the given [`SinkAsOf`] and/or the `gate_ts` will be discarded
 /// without encoding them.
 ///
@@ -667,9 +706,10 @@ fn avro_encode_stream<G>(
     encoder: Encoder,
     key_schema_id: Option<i32>,
     value_schema_id: i32,
+    partition_by: Option<(MirScalarExpr, i32)>,
     fuel: usize,
     name_prefix: String,
-) -> Stream<G, ((Option<Vec<u8>>, Option<Vec<u8>>), Timestamp, Diff)>
+) -> Stream<G, (EncodedKeyValue, Timestamp, Diff)>
 where
     G: Scope<Timestamp = Timestamp>,
 {
@@ -693,7 +733,15 @@ where
     >,
                                  output: &mut OutputHandle<
         _,
-        ((Option<Vec<u8>>, Option<Vec<u8>>), Timestamp, Diff),
+        (
+            (
+                Option<Vec<u8>>,
+                Option<Vec<u8>>,
+                Result<Option<i32>, String>,
+            ),
+            Timestamp,
+            Diff,
+        ),
         _,
     >| {
         let mut fuel_remaining = fuel;
@@ -739,11 +787,17 @@ where
             records
                 .drain(..num_records_to_drain)
                 .for_each(|((key, value), time, diff)| {
+                    let partition = match (&partition_by, &key) {
+                        (Some((expr, partition_count)), Some(key)) => {
+                            partition_for_key(expr, *partition_count, key).map(Some)
+                        }
+                        _ => Ok(None),
+                    };
                     let key =
                         key.map(|key| encoder.encode_key_unchecked(key_schema_id.unwrap(), key));
                     let value =
                         value.map(|value| encoder.encode_value_unchecked(value_schema_id, value));
-                    session.give(((key, value), time, diff));
+                    session.give(((key, value, partition), time, diff));
                 });
 
             fuel_remaining -= num_records_to_drain;
@@ -771,3 +825,26 @@ where
 
     output_stream
 }
+
+/// Chooses the partition that a record with the given key is produced to, by
+/// evaluating the sink's `PARTITION BY` expression on the key and taking the
+/// result modulo the number of partitions in the topic.
+///
+/// Returns an error if the expression evaluates to `NULL` or an error, as the
+/// record then has no partition.
+fn partition_for_key(expr: &MirScalarExpr, partition_count: i32, key: &Row) -> Result<i32, String> {
+    let datums = key.unpack();
+    let temp_storage = RowArena::new();
+    match expr.eval(&datums, &temp_storage) {
+        Ok(Datum::Int64(n)) => Ok(n.rem_euclid(i64::from(partition_count)) as i32),
+        Ok(Datum::Null) => Err(format!(
+            "PARTITION BY expression evaluated to NULL for key {}",
+            key
+        )),
+        Ok(datum) => unreachable!("PARTITION BY expression evaluated to {:?}", datum),
+        Err(e) => Err(format!(
+            "evaluating PARTITION BY expression for key {}: {}",
+            key, e
+        )),
+    }
+}
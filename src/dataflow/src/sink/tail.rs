@@ -7,6 +7,7 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use std::cmp;
 use std::rc::Rc;
 
 use differential_dataflow::trace::cursor::Cursor;
@@ -29,15 +30,25 @@ pub fn tail<G>(
 ) where
     G: Scope<Timestamp = Timestamp>,
 {
-    let mut errored = false;
+    let TailSinkConnector {
+        tx,
+        emit_progress,
+        object_columns,
+        up_to,
+        ..
+    } = connector;
+    // The sender is dropped once the tail has completed, or once the receiver
+    // has gone away.
+    let mut tx = Some(tx);
     let mut packer = Row::default();
     stream.sink(Pipeline, &format!("tail-{}", id), move |input| {
         input.for_each(|_, batches| {
-            if errored {
+            let sender = match &tx {
+                Some(sender) => sender,
                 // TODO(benesch): we should actually drop the sink if the
                 // receiver has gone away.
-                return;
-            }
+                None => return,
+            };
             let mut results = vec![];
             for batch in batches.iter() {
                 let mut cursor = batch.cursor();
@@ -52,7 +63,8 @@ pub fn tail<G>(
                             } else {
                                 as_of.frontier.less_equal(time)
                             };
-                            if should_emit {
+                            let before_up_to = up_to.map_or(true, |up_to| *time < up_to);
+                            if should_emit && before_up_to {
                                 for _ in 0..diff {
                                     // Add the unpacked timestamp so we can sort by them later.
                                     results.push((*time, row.clone()));
@@ -71,16 +83,18 @@ pub fn tail<G>(
             results.sort_by_key(|(time, _)| *time);
             let mut results: Vec<Row> = results.into_iter().map(|(_, row)| row).collect();
 
-            if connector.emit_progress {
+            if emit_progress {
                 if let Some(upper) = batch_upper(batches.last()) {
                     // The user has requested progress messages and there's at least one
                     // batch. All of the batches might have zero rows, so we do not depend on
                     // results at all. Another benefit of using upper (instead of the largest row
-                    // time) is that the batch's upper may be larger than the row time.
+                    // time) is that the batch's upper may be larger than the row time. Progress
+                    // is never reported beyond `up_to`, as no later updates will be emitted.
+                    let upper = up_to.map_or(upper, |up_to| cmp::min(upper, up_to));
                     packer.push(Datum::Decimal(Significand::new(i128::from(upper))));
                     packer.push(Datum::True);
                     // Fill in the diff column and all table columns with NULL.
-                    for _ in 0..(connector.object_columns + 1) {
+                    for _ in 0..(object_columns + 1) {
                         packer.push(Datum::Null);
                     }
                     results.push(packer.finish_and_reuse());
@@ -89,11 +103,18 @@ pub fn tail<G>(
 
             // TODO(benesch): the lack of backpressure here can result in
             // unbounded memory usage.
-            if connector.tx.send(results).is_err() {
-                errored = true;
-                return;
+            if sender.send(results).is_err() {
+                tx = None;
             }
         });
+
+        // Once the input frontier has passed `up_to`, no further updates can
+        // be emitted. Dropping the sender signals completion to the receiver.
+        if let Some(up_to) = up_to {
+            if !input.frontier().less_than(&up_to) {
+                tx = None;
+            }
+        }
     })
 }
 
@@ -31,7 +31,7 @@ use mz_avro::BlockIter;
 use mz_avro::{AvroRead, Schema, Skip};
 
 use crate::logging::materialized::Logger;
-use crate::source::{NextMessage, SourceMessage, SourceReader};
+use crate::source::{NextMessage, SourceMessage, SourceReader, SourceReaderError};
 
 /// Contains all information necessary to ingest data from file sources
 pub struct FileSourceReader {
@@ -160,7 +160,7 @@ impl SourceReader for FileSourceReader {
         ))
     }
 
-    fn get_next_message(&mut self) -> Result<NextMessage, anyhow::Error> {
+    fn get_next_message(&mut self) -> Result<NextMessage, SourceReaderError> {
         match self.receiver_stream.try_recv() {
             Ok(Ok(record)) => {
                 self.current_file_offset.offset += 1;
@@ -170,12 +170,27 @@ impl SourceReader for FileSourceReader {
                     upstream_time_millis: None,
                     key: None,
                     payload: Some(record),
+                    headers: None,
                 };
                 Ok(NextMessage::Ready(message))
             }
             Ok(Err(e)) => {
                 error!("Failed to read file for {}. Error: {}.", self.id, e);
-                Err(e)
+                // Failing to read the file may be transient, but failing to
+                // make sense of its contents is not.
+                if e.chain().any(|cause| cause.is::<std::io::Error>()) {
+                    Err(SourceReaderError::FileIO(e))
+                } else {
+                    Err(SourceReaderError::Ingestion {
+                        partition: PartitionId::None,
+                        offset: match self.current_file_offset.offset {
+                            0 => None,
+                            _ => Some(self.current_file_offset.into()),
+                        },
+                        error: e,
+                        retryable: false,
+                    })
+                }
             }
             Err(TryRecvError::Empty) => Ok(NextMessage::Pending),
             Err(TryRecvError::Disconnected) => Ok(NextMessage::Finished),
@@ -10,13 +10,14 @@
 use std::cmp;
 use std::collections::{BTreeMap, HashMap, VecDeque};
 use std::convert::TryInto;
+use std::mem;
 use std::sync::Arc;
 use std::time::Duration;
 
 use rdkafka::consumer::base_consumer::PartitionQueue;
 use rdkafka::consumer::{BaseConsumer, Consumer, ConsumerContext};
 use rdkafka::error::KafkaError;
-use rdkafka::message::BorrowedMessage;
+use rdkafka::message::{BorrowedMessage, Headers};
 use rdkafka::topic_partition_list::Offset;
 use rdkafka::{ClientConfig, ClientContext, Message, Statistics, TopicPartitionList};
 use repr::MessagePayload;
@@ -27,11 +28,14 @@ use dataflow_types::{
 };
 use expr::{PartitionId, SourceInstanceId};
 use kafka_util::KafkaAddrs;
+use lazy_static::lazy_static;
 use log::{error, info, log_enabled, warn};
+use prometheus::core::AtomicI64;
+use prometheus::{register_int_gauge_vec, DeleteOnDropGauge, IntGaugeVec};
 use uuid::Uuid;
 
 use crate::logging::materialized::{Logger, MaterializedEvent};
-use crate::source::{NextMessage, SourceMessage, SourceReader};
+use crate::source::{NextMessage, SourceMessage, SourceReader, SourceReaderError};
 
 /// Values recorded from the last rdkafka statistics callback, used to generate a
 /// diff of values for logging
@@ -219,6 +223,8 @@ pub struct KafkaSourceReader {
     logger: Option<Logger>,
     /// Channel to receive Kafka statistics objects from the stats callback
     stats_rx: crossbeam_channel::Receiver<Statistics>,
+    /// Partitions whose high watermark changed since they were last taken
+    high_watermarks: Vec<(PartitionId, i64)>,
 }
 
 impl SourceReader for KafkaSourceReader {
@@ -271,7 +277,7 @@ impl SourceReader for KafkaSourceReader {
     ///
     /// If a message has an offset that is smaller than the next expected offset for this consumer (and this partition)
     /// we skip this message, and seek to the appropriate offset
-    fn get_next_message(&mut self) -> Result<NextMessage, anyhow::Error> {
+    fn get_next_message(&mut self) -> Result<NextMessage, SourceReaderError> {
         // Poll the consumer once. Since we split the consumer's partitions out into separate queues and poll those individually,
         // we expect this poll to always return None - but it's necessary to drive logic that consumes from rdkafka's internal
         // event queue, such as statistics callbacks.
@@ -290,6 +296,19 @@ impl SourceReader for KafkaSourceReader {
 
         // Read any statistics objects generated via the GlueConsumerContext::stats callback
         while let Ok(statistics) = self.stats_rx.try_recv() {
+            // Export each partition's position relative to its high watermark
+            if let Some(topic) = statistics.topics.get(self.topic_name.as_str()) {
+                for part in self.partition_consumers.iter_mut() {
+                    if let Some(stats) = topic.partitions.get(&part.pid) {
+                        let last_offset = *self.last_offsets.get(&part.pid).unwrap_or(&-1);
+                        if part.metrics.record(stats.hi_offset, last_offset) {
+                            self.high_watermarks
+                                .push((PartitionId::Kafka(part.pid), stats.hi_offset));
+                        }
+                    }
+                }
+            }
+
             if let Some(logger) = self.logger.as_mut() {
                 for part in self.partition_consumers.iter_mut() {
                     for (broker, stats) in &statistics.brokers {
@@ -420,6 +439,10 @@ impl SourceReader for KafkaSourceReader {
 
         Ok(next_message)
     }
+
+    fn take_high_watermarks(&mut self) -> Vec<(PartitionId, i64)> {
+        mem::take(&mut self.high_watermarks)
+    }
 }
 
 impl KafkaSourceReader {
@@ -470,6 +493,7 @@ impl KafkaSourceReader {
             start_offsets,
             logger,
             stats_rx,
+            high_watermarks: Vec::new(),
         }
     }
 
@@ -517,8 +541,12 @@ impl KafkaSourceReader {
             .consumer
             .split_partition_queue(&self.topic_name, partition_id)
             .expect("partition known to be valid");
-        self.partition_consumers
-            .push_front(PartitionConsumer::new(partition_id, partition_queue));
+        self.partition_consumers.push_front(PartitionConsumer::new(
+            partition_id,
+            partition_queue,
+            &self.source_name,
+            self.id,
+        ));
         assert_eq!(
             self.consumer
                 .assignment()
@@ -680,6 +708,12 @@ impl<'a> From<&BorrowedMessage<'a>> for SourceMessage {
             offset: kafka_offset.into(),
             upstream_time_millis: msg.timestamp().to_millis(),
             key: msg.key().map(|k| k.to_vec()),
+            headers: msg.headers().map(|headers| {
+                (0..headers.count())
+                    .filter_map(|i| headers.get(i))
+                    .map(|(k, v)| (k.to_owned(), Some(v.to_vec())))
+                    .collect()
+            }),
         }
     }
 }
@@ -694,16 +728,24 @@ struct PartitionConsumer {
     partition_stats: Option<(String, PartitionStats)>,
     /// Memoized Statistics for brokers
     broker_windows: HashMap<String, BrokerRTTWindow>,
+    /// Prometheus metrics derived from the partition's statistics
+    metrics: KafkaPartitionMetrics,
 }
 
 impl PartitionConsumer {
     /// Creates a new partition consumer from underlying Kafka consumer
-    fn new(pid: i32, partition_queue: PartitionQueue<GlueConsumerContext>) -> Self {
+    fn new(
+        pid: i32,
+        partition_queue: PartitionQueue<GlueConsumerContext>,
+        source_name: &str,
+        source_id: SourceInstanceId,
+    ) -> Self {
         PartitionConsumer {
             pid,
             partition_queue,
             partition_stats: None,
             broker_windows: HashMap::new(),
+            metrics: KafkaPartitionMetrics::new(source_name, source_id, pid),
         }
     }
 
@@ -726,6 +768,71 @@ impl PartitionConsumer {
     }
 }
 
+/// Kafka-specific partition metrics, recorded to Prometheus
+struct KafkaPartitionMetrics {
+    /// The partition's high watermark offset on the broker
+    high_watermark: DeleteOnDropGauge<'static, AtomicI64>,
+    /// Number of messages between the next offset to read and the high watermark
+    lag_records: DeleteOnDropGauge<'static, AtomicI64>,
+    /// The high watermark last reported by the broker, or -1 if none has been
+    last_high_watermark: i64,
+}
+
+impl KafkaPartitionMetrics {
+    /// Initialises Kafka metrics for a given (source_id, partition_id)
+    fn new(source_name: &str, source_id: SourceInstanceId, pid: i32) -> Self {
+        const LABELS: &[&str] = &["topic", "source_id", "source_instance", "partition_id"];
+        lazy_static! {
+            static ref HIGH_WATERMARK: IntGaugeVec = register_int_gauge_vec!(
+                "mz_kafka_partition_high_watermark",
+                "The high watermark offset of each Kafka partition, as last reported by the broker.",
+                LABELS
+            )
+            .unwrap();
+            static ref LAG_RECORDS: IntGaugeVec = register_int_gauge_vec!(
+                "mz_kafka_partition_lag_records",
+                "The number of messages in each Kafka partition that have not yet been read.",
+                LABELS
+            )
+            .unwrap();
+        }
+        let labels = &[
+            source_name,
+            &source_id.source_id.to_string(),
+            &source_id.dataflow_id.to_string(),
+            &pid.to_string(),
+        ];
+        KafkaPartitionMetrics {
+            high_watermark: DeleteOnDropGauge::new_with_error_handler(
+                HIGH_WATERMARK.with_label_values(labels),
+                &HIGH_WATERMARK,
+                |e, v| log::debug!("unable to delete metric {}: {}", v.fq_name(), e),
+            ),
+            lag_records: DeleteOnDropGauge::new_with_error_handler(
+                LAG_RECORDS.with_label_values(labels),
+                &LAG_RECORDS,
+                |e, v| log::debug!("unable to delete metric {}: {}", v.fq_name(), e),
+            ),
+            last_high_watermark: -1,
+        }
+    }
+
+    /// Record the partition's high watermark, given the most recently read offset.
+    /// Returns whether the high watermark changed.
+    fn record(&mut self, hi_offset: i64, last_offset: i64) -> bool {
+        // librdkafka reports a high watermark of -1 until it is known.
+        if hi_offset < 0 {
+            return false;
+        }
+        self.high_watermark.set(hi_offset);
+        self.lag_records
+            .set(cmp::max(hi_offset - (last_offset + 1), 0));
+        let changed = hi_offset != self.last_high_watermark;
+        self.last_high_watermark = hi_offset;
+        changed
+    }
+}
+
 /// An implementation of [`ConsumerContext`] that unparks the wrapped thread
 /// when the message queue switches from nonempty to empty.
 struct GlueConsumerContext {
@@ -28,7 +28,7 @@ use dataflow_types::{
 use expr::{PartitionId, SourceInstanceId};
 
 use crate::logging::materialized::Logger;
-use crate::source::{NextMessage, SourceMessage, SourceReader};
+use crate::source::{NextMessage, SourceMessage, SourceReader, SourceReaderError};
 
 lazy_static! {
     static ref MILLIS_BEHIND_LATEST: IntGaugeVec = register_int_gauge_vec!(
@@ -95,6 +95,20 @@ impl KinesisSourceReader {
             })
             .await
     }
+
+    /// Describes an error reading from the stream, which is attributed to the
+    /// last message read, if any.
+    fn ingestion_error(&self, error: anyhow::Error, retryable: bool) -> SourceReaderError {
+        SourceReaderError::Ingestion {
+            partition: PartitionId::None,
+            offset: match self.processed_message_count {
+                0 => None,
+                offset => Some(MzOffset { offset }),
+            },
+            error,
+            retryable,
+        }
+    }
 }
 
 impl SourceReader for KinesisSourceReader {
@@ -129,14 +143,15 @@ impl SourceReader for KinesisSourceReader {
             Err(e) => Err(anyhow!("{}", e)),
         }
     }
-    fn get_next_message(&mut self) -> Result<NextMessage, anyhow::Error> {
+    fn get_next_message(&mut self) -> Result<NextMessage, SourceReaderError> {
         assert_eq!(self.shard_queue.len(), self.shard_set.len());
 
         //TODO move to timestamper
         if self.last_checked_shards.elapsed() >= KINESIS_SHARD_REFRESH_RATE {
             if let Err(e) = block_on(self.update_shard_information()) {
                 error!("{:#?}", e);
-                return Err(anyhow::Error::msg(e.to_string()));
+                // Listing shards may well succeed the next time around.
+                return Err(self.ingestion_error(anyhow::Error::msg(e.to_string()), true));
             }
             self.last_checked_shards = std::time::Instant::now();
         }
@@ -167,7 +182,7 @@ impl SourceReader for KinesisSourceReader {
                         Err(RusotoError::Service(GetRecordsError::ExpiredIterator(e))) => {
                             // todo@jldlaughlin: Will need track source offsets to grab a new iterator.
                             error!("{}", e);
-                            return Err(anyhow::Error::msg(e));
+                            return Err(self.ingestion_error(anyhow::Error::msg(e), false));
                         }
                         Err(RusotoError::Service(
                             GetRecordsError::ProvisionedThroughputExceeded(_),
@@ -190,7 +205,7 @@ impl SourceReader for KinesisSourceReader {
                             // - Unknown (raw HTTP provided)
                             // - Blocking
                             error!("{}", e);
-                            return Err(anyhow!("{}", e));
+                            return Err(self.ingestion_error(anyhow!("{}", e), false));
                         }
                     };
 
@@ -206,6 +221,7 @@ impl SourceReader for KinesisSourceReader {
                             upstream_time_millis: None,
                             key: None,
                             payload: Some(MessagePayload::Data(data)),
+                            headers: None,
                         };
                         self.buffered_messages.push_back(source_message);
                     }
@@ -9,13 +9,13 @@
 
 //! Types related to the creation of dataflow sources.
 
-use dataflow_types::{DataflowError, SourceErrorDetails};
+use dataflow_types::{DataflowError, SourceErrorDetails, SourceErrorProvenance};
 use mz_avro::types::Value;
 use repr::MessagePayload;
 use serde::{Deserialize, Serialize};
 use std::cell::RefCell;
 use std::collections::HashMap;
-use std::convert::TryInto;
+use std::convert::{TryFrom, TryInto};
 use std::fmt::{self, Debug};
 use std::path::PathBuf;
 use std::rc::Rc;
@@ -29,10 +29,12 @@ use timely::dataflow::{
 
 use anyhow::anyhow;
 use async_trait::async_trait;
+use chrono::NaiveDateTime;
 use dataflow_types::{
-    Consistency, ExternalSourceConnector, MzOffset, SourceDataEncoding, SourceError,
+    Consistency, ExternalSourceConnector, IncludedMetadata, MzOffset, SourceDataEncoding,
+    SourceError,
 };
-use expr::{PartitionId, SourceInstanceId};
+use expr::{GlobalId, PartitionId, SourceInstanceId};
 use lazy_static::lazy_static;
 use log::{debug, error};
 use prometheus::core::{AtomicI64, AtomicU64};
@@ -41,7 +43,7 @@ use prometheus::{
     register_uint_gauge_vec, DeleteOnDropCounter, DeleteOnDropGauge, IntCounter, IntCounterVec,
     IntGaugeVec, UIntGauge, UIntGaugeVec,
 };
-use repr::{CachedRecord, CachedRecordIter, Diff, Row, Timestamp};
+use repr::{CachedRecord, CachedRecordIter, Datum, Diff, Row, Timestamp};
 use timely::dataflow::channels::pushers::Tee;
 use timely::dataflow::operators::generic::OutputHandle;
 use timely::dataflow::Scope;
@@ -128,10 +130,14 @@ where
     pub key: K,
     /// The record's value
     pub value: V,
+    /// The partition of the source from which the record originates
+    pub partition: PartitionId,
     /// The position in the source, if such a concept exists (e.g., Kafka offset, file line number)
     pub position: Option<i64>,
     /// The time the record was created in the upstream systsem, as milliseconds since the epoch
     pub upstream_time_millis: Option<i64>,
+    /// The metadata columns requested via `INCLUDE`, if any
+    pub metadata: Row,
 }
 
 /// The data that we send from sources to the decode process
@@ -157,8 +163,14 @@ pub struct DecodeResult {
     pub key: Option<Result<Row, DataflowError>>,
     /// The decoded value
     pub value: Option<Result<Row, DataflowError>>,
+    /// The partition from which the value originates, if the source is partitioned
+    pub partition: Option<String>,
     /// The index of the decoded value in the stream
     pub position: Option<i64>,
+    /// The metadata columns requested via `INCLUDE`, if any
+    pub metadata: Row,
+    /// The raw key and value, retained only if either failed to decode
+    pub raw: Option<(Vec<u8>, Vec<u8>)>,
 }
 
 impl<K, V> SourceOutput<K, V>
@@ -170,14 +182,18 @@ where
     pub fn new(
         key: K,
         value: V,
+        partition: PartitionId,
         position: Option<i64>,
         upstream_time_millis: Option<i64>,
+        metadata: Row,
     ) -> SourceOutput<K, V> {
         SourceOutput {
             key,
             value,
+            partition,
             position,
             upstream_time_millis,
+            metadata,
         }
     }
 }
@@ -323,7 +339,16 @@ pub(crate) trait SourceReader {
     ///
     /// Note that implementers are required to present messages in strictly ascending\
     /// offset order within each partition.
-    fn get_next_message(&mut self) -> Result<NextMessage, anyhow::Error>;
+    fn get_next_message(&mut self) -> Result<NextMessage, SourceReaderError>;
+
+    /// Returns the upstream high watermarks of the partitions whose high
+    /// watermark has changed since this method was last called.
+    ///
+    /// The default implementation, for sources whose upstream does not report
+    /// high watermarks, returns none.
+    fn take_high_watermarks(&mut self) -> Vec<(PartitionId, i64)> {
+        Vec::new()
+    }
 }
 
 fn cache_message(
@@ -381,6 +406,7 @@ fn read_file(path: PathBuf) -> Vec<SourceMessage> {
             offset: MzOffset { offset: r.offset },
             upstream_time_millis: None,
             partition: partition.clone(),
+            headers: None,
         })
         .collect()
 }
@@ -393,6 +419,59 @@ pub(crate) enum NextMessage {
     Finished,
 }
 
+/// An error that stops a [`SourceReader`].
+#[derive(Debug)]
+pub(crate) enum SourceReaderError {
+    /// An I/O error reading the files or objects that back the source.
+    FileIO(anyhow::Error),
+    /// An error reading from the upstream system.
+    Ingestion {
+        /// The partition that was being read.
+        partition: PartitionId,
+        /// The offset of the last message read from the partition, if any.
+        offset: Option<MzOffset>,
+        error: anyhow::Error,
+        /// Whether the read might succeed if it were retried.
+        retryable: bool,
+    },
+}
+
+impl SourceReaderError {
+    /// Describes the error as one of source `source_id`.
+    fn into_details(self, source_id: GlobalId) -> SourceErrorDetails {
+        match self {
+            SourceReaderError::FileIO(e) => SourceErrorDetails::FileIO(e.to_string()),
+            SourceReaderError::Ingestion {
+                partition,
+                offset,
+                error,
+                retryable,
+            } => SourceErrorDetails::Ingestion {
+                provenance: SourceErrorProvenance {
+                    source_id,
+                    offset: offset.map(|offset| upstream_offset(&partition, offset)),
+                    partition: (&partition).into(),
+                    encoding: None,
+                },
+                error: error.to_string(),
+                retryable,
+            },
+        }
+    }
+}
+
+/// Converts `offset` into the offset of the message in the upstream system,
+/// which is what errors report.
+///
+/// Kafka offsets are 0-indexed, unlike `MzOffset`s. Other sources number their
+/// messages (e.g., lines of a file) from 1, just like `MzOffset`s do.
+fn upstream_offset(partition: &PartitionId, offset: MzOffset) -> i64 {
+    match partition {
+        PartitionId::Kafka(_) => offset.offset - 1,
+        PartitionId::None => offset.offset,
+    }
+}
+
 /// Source-agnostic wrapper for messages. Each source must implement a
 /// conversion to Message.
 pub struct SourceMessage {
@@ -408,6 +487,51 @@ pub struct SourceMessage {
     pub key: Option<Vec<u8>>,
     /// Optional payload
     pub payload: Option<MessagePayload>,
+    /// Optional message headers, as key-value pairs
+    ///
+    /// Currently only applies to Kafka
+    pub headers: Option<Vec<(String, Option<Vec<u8>>)>>,
+}
+
+impl SourceMessage {
+    /// Packs the requested metadata about this message into a row, in order.
+    fn pack_metadata(&self, include_metadata: &[IncludedMetadata]) -> Row {
+        let mut row = Row::default();
+        for metadata in include_metadata {
+            match metadata {
+                IncludedMetadata::Partition => match &self.partition {
+                    PartitionId::Kafka(p) => row.push(Datum::Int32(*p)),
+                    PartitionId::None => unreachable!("only Kafka sources support INCLUDE"),
+                },
+                // `MzOffset`s are 1-indexed, but we present the upstream offset.
+                IncludedMetadata::Offset => row.push(Datum::Int64(self.offset.offset - 1)),
+                // The timestamp is set by the producer, so it may be out of
+                // the range of a `timestamp`, in which case it is reported as `NULL`.
+                IncludedMetadata::Timestamp => match self.upstream_time_millis.and_then(|millis| {
+                    NaiveDateTime::from_timestamp_opt(
+                        millis.div_euclid(1_000),
+                        u32::try_from(millis.rem_euclid(1_000) * 1_000_000)
+                            .expect("nanoseconds fit in a u32"),
+                    )
+                }) {
+                    Some(ts) => row.push(Datum::Timestamp(ts)),
+                    None => row.push(Datum::Null),
+                },
+                IncludedMetadata::Headers => row.push_list_with(|row| {
+                    for (key, value) in self.headers.iter().flatten() {
+                        row.push_list_with(|row| {
+                            row.push(Datum::String(key));
+                            row.push(match value {
+                                Some(value) => Datum::Bytes(value),
+                                None => Datum::Null,
+                            });
+                        });
+                    }
+                }),
+            }
+        }
+        row
+    }
 }
 
 impl fmt::Debug for SourceMessage {
@@ -418,6 +542,7 @@ impl fmt::Debug for SourceMessage {
             .field("upstream_time_millis", &self.upstream_time_millis)
             .field("key[present]", &self.key.is_some())
             .field("payload[present]", &self.payload.is_some())
+            .field("headers[present]", &self.headers.is_some())
             .finish()
     }
 }
@@ -877,6 +1002,25 @@ impl SourceMetrics {
             }
         }
     }
+
+    /// Refresh the lag of a partition whose upstream high watermark has changed
+    pub fn record_high_watermark(&mut self, partition: &PartitionId, high_watermark: i64) {
+        if let Some(metric) = self.partition_metrics.get_mut(partition) {
+            metric.record_high_watermark(high_watermark);
+        }
+    }
+
+    /// Update the ingestion rates of every partition, and log any changes to
+    /// their ingestion statistics.
+    pub fn record_partition_ingestion(&mut self) {
+        let now = Instant::now();
+        for (partition, metric) in self.partition_metrics.iter_mut() {
+            metric.update_rates(now);
+            if let Some(logger) = self.logger.as_mut() {
+                metric.record_ingestion_stats(logger, &self.source_name, self.source_id, partition);
+            }
+        }
+    }
 }
 
 impl Drop for SourceMetrics {
@@ -891,11 +1035,48 @@ impl Drop for SourceMetrics {
                     offset: -metric.last_offset,
                     timestamp: -metric.last_timestamp,
                 });
+                let last = &metric.last_ingestion;
+                logger.log(MaterializedEvent::SourceIngestion {
+                    source_name: self.source_name.clone(),
+                    source_id: self.source_id,
+                    partition_id: partition.into(),
+                    offset: -last.offset,
+                    messages: -last.messages,
+                    raw_bytes: -last.raw_bytes,
+                    lag_ms: -last.lag_ms,
+                    messages_per_second: -last.messages_per_second,
+                    raw_bytes_per_second: -last.raw_bytes_per_second,
+                    decoded_bytes: 0,
+                    decoded_bytes_per_second: 0,
+                });
             }
         }
     }
 }
 
+/// The length of the window over which ingestion rates are computed.
+pub(crate) const INGESTION_RATE_WINDOW: Duration = Duration::from_secs(1);
+
+/// Ingestion statistics for a partition, as reported to the
+/// `mz_source_ingestion` system table.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct IngestionStats {
+    /// Offset of the most recently ingested message
+    offset: i64,
+    /// Number of messages ingested
+    messages: i64,
+    /// Number of bytes of message keys and values ingested
+    raw_bytes: i64,
+    /// Time since the upstream timestamp of the most recently ingested message,
+    /// or 0 once every message below the upstream high watermark is ingested
+    lag_ms: i64,
+    /// Messages ingested per second over the last complete rate window
+    messages_per_second: i64,
+    /// Bytes of message keys and values ingested per second over the last
+    /// complete rate window
+    raw_bytes_per_second: i64,
+}
+
 /// Partition-specific metrics, recorded to both Prometheus and a system table
 pub struct PartitionMetrics {
     /// Highest offset that has been received by the source and timestamped
@@ -906,8 +1087,25 @@ pub struct PartitionMetrics {
     closed_ts: DeleteOnDropGauge<'static, AtomicU64>,
     /// Total number of messages that have been received by the source and timestamped
     messages_ingested: DeleteOnDropCounter<'static, AtomicI64>,
+    /// Total number of bytes that have been received by the source and timestamped
+    bytes_ingested: DeleteOnDropCounter<'static, AtomicI64>,
+    /// Time since the upstream timestamp of the most recently ingested message,
+    /// or 0 once every message below the upstream high watermark is ingested
+    lag_ms: DeleteOnDropGauge<'static, AtomicI64>,
+    /// Messages ingested per second over the last complete rate window
+    messages_per_second: DeleteOnDropGauge<'static, AtomicI64>,
+    /// Bytes ingested per second over the last complete rate window
+    bytes_per_second: DeleteOnDropGauge<'static, AtomicI64>,
     last_offset: i64,
     last_timestamp: i64,
+    /// Upstream timestamp of the most recently ingested message that had one
+    upstream_time_millis: Option<i64>,
+    /// Start of the current rate window, and the messages and bytes ingested since
+    window_start: Instant,
+    window_messages: i64,
+    window_bytes: i64,
+    ingestion: IngestionStats,
+    last_ingestion: IngestionStats,
 }
 
 impl PartitionMetrics {
@@ -932,6 +1130,95 @@ impl PartitionMetrics {
         self.last_timestamp = timestamp;
     }
 
+    /// Record the ingestion of a message of `bytes` bytes at `offset`
+    fn record_ingestion(&mut self, offset: i64, bytes: usize, upstream_time_millis: Option<i64>) {
+        let bytes = bytes as i64;
+        self.bytes_ingested.inc_by(bytes);
+        self.window_messages += 1;
+        self.window_bytes += bytes;
+        self.ingestion.offset = offset;
+        self.ingestion.messages += 1;
+        self.ingestion.raw_bytes += bytes;
+        if upstream_time_millis.is_some() {
+            self.upstream_time_millis = upstream_time_millis;
+            self.update_lag();
+        }
+    }
+
+    /// Refresh the lag of the partition, given its upstream high watermark
+    fn record_high_watermark(&mut self, high_watermark: i64) {
+        // A partition whose messages have all been ingested lags its upstream
+        // by nothing, however long ago its last message was written.
+        if self.ingestion.offset >= high_watermark {
+            self.ingestion.lag_ms = 0;
+            self.lag_ms.set(0);
+        } else {
+            self.update_lag();
+        }
+    }
+
+    /// Set the lag to the time elapsed since the upstream timestamp of the
+    /// most recently ingested message, if it had one
+    fn update_lag(&mut self) {
+        if let Some(upstream_time_millis) = self.upstream_time_millis {
+            let now_millis = UNIX_EPOCH
+                .elapsed()
+                .expect("system clock before 1970")
+                .as_millis() as i64;
+            self.ingestion.lag_ms =
+                std::cmp::max(now_millis.saturating_sub(upstream_time_millis), 0);
+            self.lag_ms.set(self.ingestion.lag_ms);
+        }
+    }
+
+    /// Close the current rate window, if it has run for at least
+    /// `INGESTION_RATE_WINDOW`
+    fn update_rates(&mut self, now: Instant) {
+        let elapsed = now.duration_since(self.window_start);
+        if elapsed < INGESTION_RATE_WINDOW {
+            return;
+        }
+        let secs = elapsed.as_secs_f64();
+        self.ingestion.messages_per_second = (self.window_messages as f64 / secs) as i64;
+        self.ingestion.raw_bytes_per_second = (self.window_bytes as f64 / secs) as i64;
+        self.messages_per_second
+            .set(self.ingestion.messages_per_second);
+        self.bytes_per_second
+            .set(self.ingestion.raw_bytes_per_second);
+        self.window_start = now;
+        self.window_messages = 0;
+        self.window_bytes = 0;
+    }
+
+    /// Log the change in ingestion statistics since they were last logged
+    fn record_ingestion_stats(
+        &mut self,
+        logger: &mut Logger,
+        source_name: &str,
+        source_id: SourceInstanceId,
+        partition_id: &PartitionId,
+    ) {
+        let (stats, last) = (self.ingestion, self.last_ingestion);
+        if stats == last {
+            return;
+        }
+        logger.log(MaterializedEvent::SourceIngestion {
+            source_name: source_name.to_string(),
+            source_id,
+            partition_id: partition_id.into(),
+            offset: stats.offset - last.offset,
+            messages: stats.messages - last.messages,
+            raw_bytes: stats.raw_bytes - last.raw_bytes,
+            lag_ms: stats.lag_ms - last.lag_ms,
+            messages_per_second: stats.messages_per_second - last.messages_per_second,
+            raw_bytes_per_second: stats.raw_bytes_per_second - last.raw_bytes_per_second,
+            // Decoded sizes are recorded after decoding, by `DecodeMetrics`.
+            decoded_bytes: 0,
+            decoded_bytes_per_second: 0,
+        });
+        self.last_ingestion = stats;
+    }
+
     /// Initialises partition metrics for a given (source_id, partition_id)
     pub fn new(
         source_name: &str,
@@ -965,6 +1252,31 @@ impl PartitionMetrics {
                 LABELS
             )
             .unwrap();
+            static ref BYTES_INGESTED: IntCounterVec = register_int_counter_vec!(
+                "mz_partition_bytes_ingested",
+                "The number of bytes of message keys and values ingested per partition.",
+                LABELS
+            )
+            .unwrap();
+            static ref LAG_MS: IntGaugeVec = register_int_gauge_vec!(
+                "mz_partition_lag_ms",
+                "The difference between the time the most recent message was ingested and its \
+                upstream timestamp, in milliseconds.",
+                LABELS
+            )
+            .unwrap();
+            static ref MESSAGES_PER_SECOND: IntGaugeVec = register_int_gauge_vec!(
+                "mz_partition_messages_per_second",
+                "The rate at which messages are ingested per partition.",
+                LABELS
+            )
+            .unwrap();
+            static ref BYTES_PER_SECOND: IntGaugeVec = register_int_gauge_vec!(
+                "mz_partition_bytes_per_second",
+                "The rate at which bytes of message keys and values are ingested per partition.",
+                LABELS
+            )
+            .unwrap();
         }
         let labels = &[
             source_name,
@@ -993,8 +1305,34 @@ impl PartitionMetrics {
                 &MESSAGES_INGESTED,
                 |e, v| log::debug!("unable to delete metric {}: {}", v.fq_name(), e),
             ),
+            bytes_ingested: DeleteOnDropCounter::new_with_error_handler(
+                BYTES_INGESTED.with_label_values(labels),
+                &BYTES_INGESTED,
+                |e, v| log::debug!("unable to delete metric {}: {}", v.fq_name(), e),
+            ),
+            lag_ms: DeleteOnDropGauge::new_with_error_handler(
+                LAG_MS.with_label_values(labels),
+                &LAG_MS,
+                |e, v| log::debug!("unable to delete metric {}: {}", v.fq_name(), e),
+            ),
+            messages_per_second: DeleteOnDropGauge::new_with_error_handler(
+                MESSAGES_PER_SECOND.with_label_values(labels),
+                &MESSAGES_PER_SECOND,
+                |e, v| log::debug!("unable to delete metric {}: {}", v.fq_name(), e),
+            ),
+            bytes_per_second: DeleteOnDropGauge::new_with_error_handler(
+                BYTES_PER_SECOND.with_label_values(labels),
+                &BYTES_PER_SECOND,
+                |e, v| log::debug!("unable to delete metric {}: {}", v.fq_name(), e),
+            ),
             last_offset: 0,
             last_timestamp: 0,
+            upstream_time_millis: None,
+            window_start: Instant::now(),
+            window_messages: 0,
+            window_bytes: 0,
+            ingestion: IngestionStats::default(),
+            last_ingestion: IngestionStats::default(),
         }
     }
 }
@@ -1337,6 +1675,8 @@ where
             &consistency_info,
         );
 
+        let include_metadata = source_connector.include_metadata().to_vec();
+
         let mut predecessor = None;
         // Stash messages we cannot yet timestamp here.
         let mut buffer = None;
@@ -1378,8 +1718,10 @@ where
                     output.session(&ts_cap).give(Ok(SourceOutput::new(
                         key,
                         out,
+                        message.partition,
                         Some(message.offset.offset),
                         message.upstream_time_millis,
+                        Row::default(),
                     )));
                 }
                 // Yield to give downstream operators time to handle this data.
@@ -1424,6 +1766,7 @@ where
                         &timer,
                         &mut buffer,
                         &timestamp_histories,
+                        &include_metadata,
                     )
                 } else {
                     match source_reader.get_next_message() {
@@ -1440,6 +1783,7 @@ where
                             &timer,
                             &mut buffer,
                             &timestamp_histories,
+                            &include_metadata,
                         ),
                         Ok(NextMessage::TransientDelay) => {
                             // There was a temporary hiccup in getting messages, check again asap.
@@ -1459,7 +1803,7 @@ where
                             }
                         }
                         Err(e) => {
-                            output.session(&cap).give(Err(e.to_string()));
+                            output.session(&cap).give(Err(e.into_details(id.source_id)));
                             (SourceStatus::Done, MessageProcessing::Stopped)
                         }
                     }
@@ -1470,6 +1814,12 @@ where
             consistency_info
                 .source_metrics
                 .record_partition_offsets(metric_updates);
+            for (partition, high_watermark) in source_reader.take_high_watermarks() {
+                consistency_info
+                    .source_metrics
+                    .record_high_watermark(&partition, high_watermark);
+            }
+            consistency_info.source_metrics.record_partition_ingestion();
 
             // Downgrade capability (if possible) before exiting
             consistency_info.downgrade_capability(cap, &mut timestamp_histories);
@@ -1490,9 +1840,8 @@ where
         }
     });
 
-    let (ok_stream, err_stream) = stream.map_fallible(move |r| {
-        r.map_err(|e| SourceError::new(sql_name.clone(), SourceErrorDetails::FileIO(e)))
-    });
+    let (ok_stream, err_stream) =
+        stream.map_fallible(move |r| r.map_err(|e| SourceError::new(sql_name.clone(), e)));
 
     if active {
         ((ok_stream, err_stream), Some(capability))
@@ -1517,13 +1866,14 @@ fn handle_message(
     cap: &Capability<Timestamp>,
     output: &mut OutputHandle<
         Timestamp,
-        Result<SourceOutput<Vec<u8>, MessagePayload>, String>,
-        Tee<Timestamp, Result<SourceOutput<Vec<u8>, MessagePayload>, String>>,
+        Result<SourceOutput<Vec<u8>, MessagePayload>, SourceErrorDetails>,
+        Tee<Timestamp, Result<SourceOutput<Vec<u8>, MessagePayload>, SourceErrorDetails>>,
     >,
     metric_updates: &mut HashMap<PartitionId, (MzOffset, Timestamp)>,
     timer: &std::time::Instant,
     buffer: &mut Option<SourceMessage>,
     timestamp_bindings: &TimestampBindingRc,
+    include_metadata: &[IncludedMetadata],
 ) -> (SourceStatus, MessageProcessing)
 where
 {
@@ -1554,6 +1904,7 @@ where
         }
         Some(ts) => {
             cache_message(&message, *id, caching_tx, ts, msg_predecessor);
+            let metadata = message.pack_metadata(include_metadata);
             // Note: empty and null payload/keys are currently
             // treated as the same thing.
             let key = message.key.unwrap_or_default();
@@ -1561,18 +1912,21 @@ where
             // Entry for partition_metadata is guaranteed to exist as messages
             // are only processed after we have updated the partition_metadata for a
             // partition and created a partition queue for it.
-            *bytes_read += key.len();
-            *bytes_read += match &out {
-                MessagePayload::Data(bytes) => bytes.len(),
-                MessagePayload::EOF => 0,
-            };
+            let message_bytes = key.len()
+                + match &out {
+                    MessagePayload::Data(bytes) => bytes.len(),
+                    MessagePayload::EOF => 0,
+                };
+            *bytes_read += message_bytes;
             let ts_cap = cap.delayed(&ts);
 
             output.session(&ts_cap).give(Ok(SourceOutput::new(
                 key,
                 out,
+                partition.clone(),
                 Some(offset.offset),
                 message.upstream_time_millis,
+                metadata,
             )));
 
             // Update ingestion metrics
@@ -1584,6 +1938,11 @@ where
                 .unwrap();
             partition_metrics.offset_ingested.set(offset.offset);
             partition_metrics.messages_ingested.inc();
+            partition_metrics.record_ingestion(
+                offset.offset,
+                message_bytes,
+                message.upstream_time_millis,
+            );
 
             metric_updates.insert(partition, (offset, ts));
 
@@ -438,7 +438,7 @@ impl SimpleSource for PostgresSourceReader {
                 Err(ReplicationError::Fatal(e)) => {
                     return Err(SourceError {
                         source_name: self.source_name,
-                        error: SourceErrorDetails::FileIO(e.to_string()),
+                        error: SourceErrorDetails::Fatal(e.to_string()),
                     })
                 }
                 Ok(_) => unreachable!("replication stream cannot exit without an error"),
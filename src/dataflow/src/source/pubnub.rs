@@ -45,7 +45,7 @@ impl SimpleSource for PubNubSourceReader {
             .build()
             .map_err(|e| SourceError {
                 source_name: self.source_name.clone(),
-                error: SourceErrorDetails::FileIO(e),
+                error: SourceErrorDetails::Initialization(e),
             })?;
 
         let mut pubnub = Builder::new()
@@ -58,7 +58,10 @@ impl SimpleSource for PubNubSourceReader {
         let channel: channel::Name = channel.parse().or_else(|_| {
             Err(SourceError {
                 source_name,
-                error: SourceErrorDetails::FileIO(format!("invalid pubnub channel: {}", channel)),
+                error: SourceErrorDetails::Initialization(format!(
+                    "invalid pubnub channel: {}",
+                    channel
+                )),
             })
         })?;
 
@@ -75,7 +78,7 @@ impl SimpleSource for PubNubSourceReader {
 
                     timestamper.insert(row).await.map_err(|e| SourceError {
                         source_name: source_name.clone(),
-                        error: SourceErrorDetails::FileIO(e.to_string()),
+                        error: SourceErrorDetails::Fatal(e.to_string()),
                     })?;
                 }
             }
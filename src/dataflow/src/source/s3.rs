@@ -39,7 +39,7 @@ use dataflow_types::{
 use expr::{PartitionId, SourceInstanceId};
 
 use crate::logging::materialized::Logger;
-use crate::source::{NextMessage, SourceMessage, SourceReader};
+use crate::source::{NextMessage, SourceMessage, SourceReader, SourceReaderError};
 
 use self::metrics::ScanBucketMetrics;
 use self::notifications::{EventType, TestEvent};
@@ -712,7 +712,7 @@ impl SourceReader for S3SourceReader {
         ))
     }
 
-    fn get_next_message(&mut self) -> Result<NextMessage, anyhow::Error> {
+    fn get_next_message(&mut self) -> Result<NextMessage, SourceReaderError> {
         match self.receiver_stream.try_recv() {
             Ok(Ok(InternalMessage { record })) => {
                 self.offset += 1;
@@ -722,6 +722,7 @@ impl SourceReader for S3SourceReader {
                     upstream_time_millis: None,
                     key: None,
                     payload: Some(record),
+                    headers: None,
                 }))
             }
             Ok(Err(e)) => {
@@ -731,7 +732,8 @@ impl SourceReader for S3SourceReader {
                     self.id,
                     e
                 );
-                Err(e)
+                // Errors arise while listing and downloading objects.
+                Err(SourceReaderError::FileIO(e))
             }
             Err(TryRecvError::Empty) => Ok(NextMessage::Pending),
             Err(TryRecvError::Disconnected) => Ok(NextMessage::Finished),
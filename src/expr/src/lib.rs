@@ -40,7 +40,8 @@ pub use relation::{
     compare_columns, AggregateExpr, ColumnOrder, IdGen, JoinImplementation, MirRelationExpr,
     RowSetFinishing,
 };
-pub use scalar::func::{BinaryFunc, NullaryFunc, UnaryFunc, VariadicFunc};
+pub use scalar::func::{build_regex, build_regex_global};
+pub use scalar::func::{BinaryFunc, JsonPath, NullaryFunc, UnaryFunc, VariadicFunc};
 pub use scalar::{like_pattern, EvalError, MirScalarExpr};
 
 /// A [`MirRelationExpr`] that claims to have been optimized, e.g., by an
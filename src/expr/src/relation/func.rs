@@ -9,6 +9,7 @@
 
 #![allow(missing_docs)]
 
+use std::cmp::Ordering;
 use std::fmt;
 use std::fs;
 use std::iter;
@@ -24,12 +25,22 @@ use serde::{Deserialize, Serialize};
 
 use ore::cast::CastFrom;
 use repr::adt::apd;
+use repr::adt::array::ArrayDimension;
+use repr::adt::datetime::Timezone;
 use repr::adt::decimal::{Significand, MAX_DECIMAL_PRECISION};
+use repr::adt::interval::Interval;
+use repr::adt::range::{self, RangeBound};
 use repr::adt::regex::Regex as ReprRegex;
-use repr::{CachedRecordIter, ColumnType, Datum, Diff, RelationType, Row, RowArena, ScalarType};
+use repr::{
+    CachedRecordIter, ColumnName, ColumnType, Datum, Diff, RelationType, Row, RowArena, ScalarType,
+};
 
 use crate::id::GlobalId;
-use crate::scalar::func::jsonb_stringify;
+use crate::scalar::func::{
+    add_timestamp_months, jsonb_stringify, push_regexp_match_array, regexp_split,
+    timezone_timestamp, JsonPath,
+};
+use crate::EvalError;
 
 // TODO(jamii) be careful about overflow in sum/avg
 // see https://timely.zulipchat.com/#narrow/stream/186635-engineering/topic/additional.20work/near/163507435
@@ -94,6 +105,20 @@ where
     Datum::from(x)
 }
 
+fn max_apd<'a, I>(datums: I) -> Datum<'a>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    match datums
+        .into_iter()
+        .filter(|d| !d.is_null())
+        .max_by(|a, b| a.unwrap_apd().cmp(&b.unwrap_apd()))
+    {
+        Some(datum) => datum,
+        None => Datum::Null,
+    }
+}
+
 fn max_bool<'a, I>(datums: I) -> Datum<'a>
 where
     I: IntoIterator<Item = Datum<'a>>,
@@ -216,6 +241,20 @@ where
     Datum::from(x)
 }
 
+fn min_apd<'a, I>(datums: I) -> Datum<'a>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    match datums
+        .into_iter()
+        .filter(|d| !d.is_null())
+        .min_by(|a, b| a.unwrap_apd().cmp(&b.unwrap_apd()))
+    {
+        Some(datum) => datum,
+        None => Datum::Null,
+    }
+}
+
 fn min_bool<'a, I>(datums: I) -> Datum<'a>
 where
     I: IntoIterator<Item = Datum<'a>>,
@@ -404,6 +443,55 @@ where
     })
 }
 
+fn range_agg<'a, I>(datums: I, temp_storage: &'a RowArena) -> Datum<'a>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    let mut ranges = datums
+        .into_iter()
+        .filter(|d| !d.is_null())
+        .filter_map(|d| d.unwrap_range().bounds())
+        .collect::<Vec<_>>();
+    ranges.sort_by(|(l1, _), (l2, _)| range::cmp_bounds(*l1, true, *l2, true));
+
+    // Sweep through the ranges in order of their lower bounds, extending the
+    // last merged range whenever the next range overlaps or abuts it.
+    let mut merged: Vec<(RangeBound, RangeBound)> = vec![];
+    for (lower, upper) in ranges {
+        match merged.last_mut() {
+            Some((_, last_upper))
+                if range::cmp_bounds(lower, true, *last_upper, false) != Ordering::Greater
+                    || (lower.value == last_upper.value
+                        && lower.inclusive != last_upper.inclusive) =>
+            {
+                if range::cmp_bounds(upper, false, *last_upper, false) == Ordering::Greater {
+                    *last_upper = upper;
+                }
+            }
+            _ => merged.push((lower, upper)),
+        }
+    }
+
+    let mut row = Row::default();
+    for (lower, upper) in merged.iter() {
+        row.push_range(*lower, *upper)
+            .expect("merging valid ranges produces valid ranges");
+    }
+    // Per PostgreSQL, empty arrays are represented with zero dimensions.
+    let dims = match merged.len() {
+        0 => vec![],
+        length => vec![ArrayDimension {
+            lower_bound: 1,
+            length,
+        }],
+    };
+    temp_storage.make_datum(|packer| {
+        packer
+            .push_array(&dims, row.iter())
+            .expect("range_agg produces a valid array")
+    })
+}
+
 fn jsonb_object_agg<'a, I>(datums: I, temp_storage: &'a RowArena) -> Datum<'a>
 where
     I: IntoIterator<Item = Datum<'a>>,
@@ -440,6 +528,7 @@ pub enum AggregateFunc {
     MaxFloat32,
     MaxFloat64,
     MaxDecimal,
+    MaxAPD,
     MaxBool,
     MaxString,
     MaxDate,
@@ -450,6 +539,7 @@ pub enum AggregateFunc {
     MinFloat32,
     MinFloat64,
     MinDecimal,
+    MinAPD,
     MinBool,
     MinString,
     MinDate,
@@ -476,6 +566,9 @@ pub enum AggregateFunc {
     /// layer, this function filters out `Datum::Null`, for consistency with
     /// the other aggregate functions.
     JsonbObjectAgg,
+    /// Merges range-typed `Datum`s into a sorted array of disjoint, non-empty
+    /// ranges. Overlapping and adjacent input ranges are combined.
+    RangeAgg,
     /// Accumulates any number of `Datum::Dummy`s into `Datum::Dummy`.
     ///
     /// Useful for removing an expensive aggregation while maintaining the shape
@@ -494,6 +587,7 @@ impl AggregateFunc {
             AggregateFunc::MaxFloat32 => max_float32(datums),
             AggregateFunc::MaxFloat64 => max_float64(datums),
             AggregateFunc::MaxDecimal => max_decimal(datums),
+            AggregateFunc::MaxAPD => max_apd(datums),
             AggregateFunc::MaxBool => max_bool(datums),
             AggregateFunc::MaxString => max_string(datums),
             AggregateFunc::MaxDate => max_date(datums),
@@ -504,6 +598,7 @@ impl AggregateFunc {
             AggregateFunc::MinFloat32 => min_float32(datums),
             AggregateFunc::MinFloat64 => min_float64(datums),
             AggregateFunc::MinDecimal => min_decimal(datums),
+            AggregateFunc::MinAPD => min_apd(datums),
             AggregateFunc::MinBool => min_bool(datums),
             AggregateFunc::MinString => min_string(datums),
             AggregateFunc::MinDate => min_date(datums),
@@ -520,6 +615,7 @@ impl AggregateFunc {
             AggregateFunc::All => all(datums),
             AggregateFunc::JsonbAgg => jsonb_agg(datums, temp_storage),
             AggregateFunc::JsonbObjectAgg => jsonb_object_agg(datums, temp_storage),
+            AggregateFunc::RangeAgg => range_agg(datums, temp_storage),
             AggregateFunc::Dummy => Datum::Dummy,
         }
     }
@@ -559,6 +655,7 @@ impl AggregateFunc {
             AggregateFunc::All => ScalarType::Bool,
             AggregateFunc::JsonbAgg => ScalarType::Jsonb,
             AggregateFunc::JsonbObjectAgg => ScalarType::Jsonb,
+            AggregateFunc::RangeAgg => ScalarType::Array(Box::new(input_type.scalar_type)),
             AggregateFunc::SumInt32 => ScalarType::Int64,
             AggregateFunc::SumInt64 => ScalarType::Decimal(MAX_DECIMAL_PRECISION, 0),
             _ => input_type.scalar_type,
@@ -617,6 +714,110 @@ fn jsonb_array_elements<'a>(
     }
 }
 
+fn jsonb_path_query_dynamic(datums: &[Datum]) -> Result<Vec<(Row, Diff)>, EvalError> {
+    let path = JsonPath::parse(datums[1].unwrap_str())?;
+    let silent = datums.get(3) == Some(&Datum::True);
+    jsonb_path_query(datums[0], &path, datums.get(2).copied(), silent)
+}
+
+fn jsonb_path_query_static(
+    datums: &[Datum],
+    path: &JsonPath,
+) -> Result<Vec<(Row, Diff)>, EvalError> {
+    let silent = datums.get(2) == Some(&Datum::True);
+    jsonb_path_query(datums[0], path, datums.get(1).copied(), silent)
+}
+
+fn jsonb_path_query<'a>(
+    root: Datum<'a>,
+    path: &JsonPath,
+    vars: Option<Datum<'a>>,
+    silent: bool,
+) -> Result<Vec<(Row, Diff)>, EvalError> {
+    match path.query(root, vars) {
+        Ok(items) => Ok(items
+            .into_iter()
+            .map(|item| (Row::pack_slice(&[item]), 1))
+            .collect()),
+        Err(_) if silent => Ok(vec![]),
+        Err(e) => Err(e),
+    }
+}
+
+/// Converts the fields of a jsonb object into a row with the specified
+/// columns, as in `jsonb_to_record`.
+fn jsonb_record_row<'a>(
+    a: Datum<'a>,
+    columns: &[(ColumnName, ScalarType)],
+    timezone: Timezone,
+    temp_storage: &'a RowArena,
+) -> Result<Row, EvalError> {
+    let dict = match a {
+        Datum::Map(dict) => dict,
+        _ => unreachable!("callers ensure jsonb_record_row is called on objects"),
+    };
+    let mut datums = Vec::with_capacity(columns.len());
+    for (name, typ) in columns {
+        let value = match dict.iter().find(|(k, _)| *k == name.as_str()) {
+            None | Some((_, Datum::JsonNull)) => Datum::Null,
+            Some((_, v)) if *typ == ScalarType::Jsonb => v,
+            Some((_, v)) => {
+                // Strings are converted from their contents, while all other
+                // values are converted from their JSON text.
+                let text = match jsonb_stringify(v, temp_storage) {
+                    Datum::String(s) => s,
+                    _ => unreachable!("non-null jsonb values stringify to strings"),
+                };
+                let pgtyp = pgrepr::Type::from(typ);
+                let value = pgrepr::Value::decode_text(&pgtyp, text.as_bytes(), timezone)
+                    .map_err(|e| EvalError::InvalidParameterValue(e.to_string()))?;
+                value.into_datum(temp_storage, &pgtyp).0
+            }
+        };
+        datums.push(value);
+    }
+    Ok(Row::pack_slice(&datums))
+}
+
+fn jsonb_to_record<'a>(
+    a: Datum<'a>,
+    columns: &[(ColumnName, ScalarType)],
+    timezone: Timezone,
+    temp_storage: &'a RowArena,
+) -> Result<Vec<(Row, Diff)>, EvalError> {
+    match a {
+        Datum::Map(_) => Ok(vec![(
+            jsonb_record_row(a, columns, timezone, temp_storage)?,
+            1,
+        )]),
+        _ => Err(EvalError::InvalidParameterValue(
+            "cannot call jsonb_to_record on a non-object".into(),
+        )),
+    }
+}
+
+fn jsonb_to_recordset<'a>(
+    a: Datum<'a>,
+    columns: &[(ColumnName, ScalarType)],
+    timezone: Timezone,
+    temp_storage: &'a RowArena,
+) -> Result<Vec<(Row, Diff)>, EvalError> {
+    match a {
+        Datum::List(list) => list
+            .iter()
+            .map(|elem| match elem {
+                Datum::Map(_) => Ok((jsonb_record_row(elem, columns, timezone, temp_storage)?, 1)),
+                _ => Err(EvalError::InvalidParameterValue(
+                    "argument of jsonb_to_recordset must be an array of objects".into(),
+                )),
+            })
+            .collect(),
+        _ => Err(EvalError::InvalidParameterValue(
+            "cannot call jsonb_to_recordset on a non-array".into(),
+        )),
+    }
+}
+
 fn regexp_extract(a: Datum, r: &AnalyzedRegex) -> Option<(Row, Diff)> {
     let r = r.inner();
     let a = a.unwrap_str();
@@ -628,6 +829,26 @@ fn regexp_extract(a: Datum, r: &AnalyzedRegex) -> Option<(Row, Diff)> {
     Some((Row::pack(datums), 1))
 }
 
+fn regexp_matches(a: Datum, regex: &Regex, global: bool) -> Result<Vec<(Row, Diff)>, EvalError> {
+    let limit = if global { usize::MAX } else { 1 };
+    regex
+        .captures_iter(a.unwrap_str())
+        .take(limit)
+        .map(|captures| {
+            let mut row = Row::default();
+            push_regexp_match_array(&mut row, regex, &captures)?;
+            Ok((row, 1))
+        })
+        .collect()
+}
+
+fn regexp_split_to_table(a: Datum, regex: &Regex) -> Vec<(Row, Diff)> {
+    regexp_split(a.unwrap_str(), regex)
+        .into_iter()
+        .map(|piece| (Row::pack_slice(&[Datum::String(piece)]), 1))
+        .collect()
+}
+
 fn generate_series_int32(start: Datum, stop: Datum) -> Vec<(Row, Diff)> {
     let start = start.unwrap_int32();
     let stop = stop.unwrap_int32();
@@ -644,6 +865,99 @@ fn generate_series_int64(start: Datum, stop: Datum) -> Vec<(Row, Diff)> {
         .collect()
 }
 
+fn generate_series_timestamp(
+    start: Datum,
+    stop: Datum,
+    step: Datum,
+) -> Result<Vec<(Row, Diff)>, EvalError> {
+    let step = step.unwrap_interval();
+    let series = generate_series_by(
+        start.unwrap_timestamp(),
+        stop.unwrap_timestamp(),
+        step,
+        move |dt| {
+            add_timestamp_months(dt, step.months).checked_add_signed(step.duration_as_chrono())
+        },
+    )?;
+    Ok(series
+        .map(|dt| (Row::pack_slice(&[Datum::Timestamp(dt)]), 1))
+        .collect())
+}
+
+/// Like [`generate_series_timestamp`], but the months and whole days of `step`
+/// are added in the local time of `timezone`, as in PostgreSQL, so that a
+/// series stepping by days or months keeps its time of day across daylight
+/// saving time transitions. Any remainder of `step` is added in UTC.
+fn generate_series_timestamptz(
+    start: Datum,
+    stop: Datum,
+    step: Datum,
+    timezone: Timezone,
+) -> Result<Vec<(Row, Diff)>, EvalError> {
+    const NANOS_PER_DAY: i128 = 24 * 60 * 60 * 1_000_000_000;
+    let step = step.unwrap_interval();
+    let days = chrono::Duration::days((step.duration / NANOS_PER_DAY) as i64);
+    let remainder = chrono::Duration::nanoseconds((step.duration % NANOS_PER_DAY) as i64);
+    let series = generate_series_by(
+        start.unwrap_timestamptz(),
+        stop.unwrap_timestamptz(),
+        step,
+        move |utc| {
+            let local = utc.naive_utc() + timezone.offset_at(&utc.naive_utc());
+            let local = add_timestamp_months(local, step.months).checked_add_signed(days)?;
+            let utc = timezone_timestamp(timezone, local)
+                .ok()?
+                .unwrap_timestamptz();
+            utc.checked_add_signed(remainder)
+        },
+    )?;
+    Ok(series
+        .map(|dt| (Row::pack_slice(&[Datum::TimestampTz(dt)]), 1))
+        .collect())
+}
+
+/// Returns the values from `start` to `stop`, inclusive, that are obtained by
+/// repeatedly adding `step` to `start` with `add_step`.
+///
+/// As in PostgreSQL, each value is computed by adding `step` to the previous
+/// value, so the day of the month of a series stepping by months can only
+/// ever decrease, e.g. from January 31 to February 28 to March 28.
+fn generate_series_by<T, F>(
+    start: T,
+    stop: T,
+    step: Interval,
+    add_step: F,
+) -> Result<impl Iterator<Item = T>, EvalError>
+where
+    T: Copy + Ord,
+    F: Fn(T) -> Option<T>,
+{
+    // Like PostgreSQL, determine the direction of the step by treating each
+    // month as 30 days.
+    let span = i128::from(step.months) * 30 * 24 * 60 * 60 * 1_000_000_000 + step.duration;
+    let forward = match span.cmp(&0) {
+        Ordering::Greater => true,
+        Ordering::Less => false,
+        Ordering::Equal => {
+            return Err(EvalError::InvalidParameterValue(
+                "step size cannot equal zero".into(),
+            ))
+        }
+    };
+    let in_range = move |dt: &T| if forward { *dt <= stop } else { *dt >= stop };
+    Ok(iter::successors(Some(start), move |dt| {
+        let next = add_step(*dt)?;
+        // Stop rather than loop forever if the month and duration components
+        // of the step pull in opposite directions.
+        if (next > *dt) == forward {
+            Some(next)
+        } else {
+            None
+        }
+    })
+    .take_while(in_range))
+}
+
 fn unnest_array(a: Datum) -> Vec<(Row, Diff)> {
     a.unwrap_array()
         .elements()
@@ -659,6 +973,22 @@ fn unnest_list(a: Datum) -> Vec<(Row, Diff)> {
         .collect()
 }
 
+fn with_ordinality(rows: Vec<(Row, Diff)>) -> Vec<(Row, Diff)> {
+    // Every copy of a row gets its own ordinal, so rows with a multiplicity
+    // greater than one are expanded into distinct rows.
+    let mut ordinality = 0;
+    let mut out = Vec::with_capacity(rows.len());
+    for (row, diff) in rows {
+        for _ in 0..diff {
+            ordinality += 1;
+            let mut row = row.clone();
+            row.push(Datum::Int64(ordinality));
+            out.push((row, 1));
+        }
+    }
+    out
+}
+
 impl fmt::Display for AggregateFunc {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
@@ -667,6 +997,7 @@ impl fmt::Display for AggregateFunc {
             AggregateFunc::MaxFloat32 => f.write_str("max"),
             AggregateFunc::MaxFloat64 => f.write_str("max"),
             AggregateFunc::MaxDecimal => f.write_str("max"),
+            AggregateFunc::MaxAPD => f.write_str("max"),
             AggregateFunc::MaxBool => f.write_str("max"),
             AggregateFunc::MaxString => f.write_str("max"),
             AggregateFunc::MaxDate => f.write_str("max"),
@@ -677,6 +1008,7 @@ impl fmt::Display for AggregateFunc {
             AggregateFunc::MinFloat32 => f.write_str("min"),
             AggregateFunc::MinFloat64 => f.write_str("min"),
             AggregateFunc::MinDecimal => f.write_str("min"),
+            AggregateFunc::MinAPD => f.write_str("min"),
             AggregateFunc::MinBool => f.write_str("min"),
             AggregateFunc::MinString => f.write_str("min"),
             AggregateFunc::MinDate => f.write_str("min"),
@@ -693,6 +1025,7 @@ impl fmt::Display for AggregateFunc {
             AggregateFunc::All => f.write_str("all"),
             AggregateFunc::JsonbAgg => f.write_str("jsonb_agg"),
             AggregateFunc::JsonbObjectAgg => f.write_str("jsonb_object_agg"),
+            AggregateFunc::RangeAgg => f.write_str("range_agg"),
             AggregateFunc::Dummy => f.write_str("dummy"),
         }
     }
@@ -785,11 +1118,34 @@ pub enum TableFunc {
     JsonbArrayElements {
         stringify: bool,
     },
+    JsonbPathQuery,
+    /// Like `JsonbPathQuery`, but with a path that has been parsed in advance.
+    /// The path argument is omitted from the function's arguments.
+    JsonbPathQueryStatic(JsonPath),
+    /// The record's columns, and the timezone in which to interpret
+    /// timestamps with time zones that do not specify one.
+    JsonbToRecord {
+        columns: Vec<(ColumnName, ScalarType)>,
+        timezone: Timezone,
+    },
+    JsonbToRecordset {
+        columns: Vec<(ColumnName, ScalarType)>,
+        timezone: Timezone,
+    },
     RegexpExtract(AnalyzedRegex),
+    RegexpMatches {
+        regex: ReprRegex,
+        global: bool,
+    },
+    RegexpSplitToTable(ReprRegex),
     CsvExtract(usize),
     GenerateSeriesInt32,
     GenerateSeriesInt64,
-    // TODO(justin): should also possibly have GenerateSeriesTimestamp{,Tz}.
+    GenerateSeriesTimestamp,
+    /// The timezone in whose local time the series steps.
+    GenerateSeriesTimestampTz {
+        timezone: Timezone,
+    },
     Repeat,
     ReadCachedData {
         source: GlobalId,
@@ -801,6 +1157,9 @@ pub enum TableFunc {
     UnnestList {
         el_typ: ScalarType,
     },
+    /// Wraps another table function, appending a column that numbers its
+    /// output rows starting from one, as in `unnest(a) WITH ORDINALITY`.
+    WithOrdinality(Box<TableFunc>),
 }
 
 impl TableFunc {
@@ -808,22 +1167,40 @@ impl TableFunc {
         &'a self,
         datums: Vec<Datum<'a>>,
         temp_storage: &'a RowArena,
-    ) -> Vec<(Row, Diff)> {
+    ) -> Result<Vec<(Row, Diff)>, EvalError> {
         if self.empty_on_null_input() {
             if datums.iter().any(|d| d.is_null()) {
-                return vec![];
+                return Ok(vec![]);
             }
         }
-        match self {
+        Ok(match self {
             TableFunc::JsonbEach { stringify } => jsonb_each(datums[0], temp_storage, *stringify),
             TableFunc::JsonbObjectKeys => jsonb_object_keys(datums[0]),
             TableFunc::JsonbArrayElements { stringify } => {
                 jsonb_array_elements(datums[0], temp_storage, *stringify)
             }
+            TableFunc::JsonbPathQuery => jsonb_path_query_dynamic(&datums)?,
+            TableFunc::JsonbPathQueryStatic(path) => jsonb_path_query_static(&datums, path)?,
+            TableFunc::JsonbToRecord { columns, timezone } => {
+                jsonb_to_record(datums[0], columns, *timezone, temp_storage)?
+            }
+            TableFunc::JsonbToRecordset { columns, timezone } => {
+                jsonb_to_recordset(datums[0], columns, *timezone, temp_storage)?
+            }
             TableFunc::RegexpExtract(a) => regexp_extract(datums[0], a).into_iter().collect(),
+            TableFunc::RegexpMatches { regex, global } => {
+                regexp_matches(datums[0], regex, *global)?
+            }
+            TableFunc::RegexpSplitToTable(regex) => regexp_split_to_table(datums[0], regex),
             TableFunc::CsvExtract(n_cols) => csv_extract(datums[0], *n_cols).into_iter().collect(),
             TableFunc::GenerateSeriesInt32 => generate_series_int32(datums[0], datums[1]),
             TableFunc::GenerateSeriesInt64 => generate_series_int64(datums[0], datums[1]),
+            TableFunc::GenerateSeriesTimestamp => {
+                generate_series_timestamp(datums[0], datums[1], datums[2])?
+            }
+            TableFunc::GenerateSeriesTimestampTz { timezone } => {
+                generate_series_timestamptz(datums[0], datums[1], datums[2], *timezone)?
+            }
             TableFunc::Repeat => repeat(datums[0]),
             TableFunc::ReadCachedData {
                 source,
@@ -850,7 +1227,8 @@ impl TableFunc {
                 .collect::<Vec<(Row, Diff)>>(),
             TableFunc::UnnestArray { .. } => unnest_array(datums[0]),
             TableFunc::UnnestList { .. } => unnest_list(datums[0]),
-        }
+            TableFunc::WithOrdinality(func) => with_ordinality(func.eval(datums, temp_storage)?),
+        })
     }
 
     pub fn output_type(&self) -> RelationType {
@@ -870,15 +1248,31 @@ impl TableFunc {
             TableFunc::JsonbArrayElements { stringify: false } => {
                 vec![ScalarType::Jsonb.nullable(false)]
             }
+            TableFunc::JsonbPathQuery | TableFunc::JsonbPathQueryStatic(_) => {
+                vec![ScalarType::Jsonb.nullable(false)]
+            }
+            TableFunc::JsonbToRecord { columns, .. }
+            | TableFunc::JsonbToRecordset { columns, .. } => columns
+                .iter()
+                .map(|(_, typ)| typ.clone().nullable(true))
+                .collect(),
             TableFunc::RegexpExtract(a) => a
                 .capture_groups_iter()
                 .map(|cg| ScalarType::String.nullable(cg.nullable))
                 .collect(),
+            TableFunc::RegexpMatches { .. } => {
+                vec![ScalarType::Array(Box::new(ScalarType::String)).nullable(false)]
+            }
+            TableFunc::RegexpSplitToTable(_) => vec![ScalarType::String.nullable(false)],
             TableFunc::CsvExtract(n_cols) => iter::repeat(ScalarType::String.nullable(false))
                 .take(*n_cols)
                 .collect(),
             TableFunc::GenerateSeriesInt32 => vec![ScalarType::Int32.nullable(false)],
             TableFunc::GenerateSeriesInt64 => vec![ScalarType::Int64.nullable(false)],
+            TableFunc::GenerateSeriesTimestamp => vec![ScalarType::Timestamp.nullable(false)],
+            TableFunc::GenerateSeriesTimestampTz { .. } => {
+                vec![ScalarType::TimestampTz.nullable(false)]
+            }
             TableFunc::Repeat => vec![],
             TableFunc::ReadCachedData { .. } => vec![
                 ScalarType::String.nullable(true),
@@ -888,6 +1282,11 @@ impl TableFunc {
             ],
             TableFunc::UnnestArray { el_typ } => vec![el_typ.clone().nullable(true)],
             TableFunc::UnnestList { el_typ } => vec![el_typ.clone().nullable(true)],
+            TableFunc::WithOrdinality(func) => {
+                let mut typ = func.output_type().column_types;
+                typ.push(ScalarType::Int64.nullable(false));
+                typ
+            }
         })
     }
 
@@ -896,14 +1295,35 @@ impl TableFunc {
             TableFunc::JsonbEach { .. } => 2,
             TableFunc::JsonbObjectKeys => 1,
             TableFunc::JsonbArrayElements { .. } => 1,
+            TableFunc::JsonbPathQuery => 1,
+            TableFunc::JsonbPathQueryStatic(_) => 1,
+            TableFunc::JsonbToRecord { columns, .. } => columns.len(),
+            TableFunc::JsonbToRecordset { columns, .. } => columns.len(),
             TableFunc::RegexpExtract(a) => a.capture_groups_len(),
+            TableFunc::RegexpMatches { .. } => 1,
+            TableFunc::RegexpSplitToTable(_) => 1,
             TableFunc::CsvExtract(n_cols) => *n_cols,
             TableFunc::GenerateSeriesInt32 => 1,
             TableFunc::GenerateSeriesInt64 => 1,
+            TableFunc::GenerateSeriesTimestamp => 1,
+            TableFunc::GenerateSeriesTimestampTz { .. } => 1,
             TableFunc::Repeat => 0,
             TableFunc::ReadCachedData { .. } => 4,
             TableFunc::UnnestArray { .. } => 1,
             TableFunc::UnnestList { .. } => 1,
+            TableFunc::WithOrdinality(func) => func.output_arity() + 1,
+        }
+    }
+
+    /// Returns the columns of a table function that returns records whose
+    /// shape is determined by a column definition list, like
+    /// `jsonb_to_record`.
+    pub fn record_columns_mut(&mut self) -> Option<&mut Vec<(ColumnName, ScalarType)>> {
+        match self {
+            TableFunc::JsonbToRecord { columns, .. }
+            | TableFunc::JsonbToRecordset { columns, .. } => Some(columns),
+            TableFunc::WithOrdinality(func) => func.record_columns_mut(),
+            _ => None,
         }
     }
 
@@ -917,14 +1337,23 @@ impl TableFunc {
             TableFunc::JsonbEach { .. }
             | TableFunc::JsonbObjectKeys
             | TableFunc::JsonbArrayElements { .. }
+            | TableFunc::JsonbPathQuery
+            | TableFunc::JsonbPathQueryStatic(_)
+            | TableFunc::JsonbToRecord { .. }
+            | TableFunc::JsonbToRecordset { .. }
             | TableFunc::GenerateSeriesInt32
             | TableFunc::GenerateSeriesInt64
+            | TableFunc::GenerateSeriesTimestamp
+            | TableFunc::GenerateSeriesTimestampTz { .. }
             | TableFunc::RegexpExtract(_)
+            | TableFunc::RegexpMatches { .. }
+            | TableFunc::RegexpSplitToTable(_)
             | TableFunc::CsvExtract(_)
             | TableFunc::Repeat
             | TableFunc::ReadCachedData { .. }
             | TableFunc::UnnestArray { .. }
             | TableFunc::UnnestList { .. } => true,
+            TableFunc::WithOrdinality(func) => func.empty_on_null_input(),
         }
     }
 
@@ -936,14 +1365,23 @@ impl TableFunc {
             TableFunc::JsonbEach { .. } => true,
             TableFunc::JsonbObjectKeys => true,
             TableFunc::JsonbArrayElements { .. } => true,
+            TableFunc::JsonbPathQuery => true,
+            TableFunc::JsonbPathQueryStatic(_) => true,
+            TableFunc::JsonbToRecord { .. } => true,
+            TableFunc::JsonbToRecordset { .. } => true,
             TableFunc::RegexpExtract(_) => true,
+            TableFunc::RegexpMatches { .. } => true,
+            TableFunc::RegexpSplitToTable(_) => true,
             TableFunc::CsvExtract(_) => true,
             TableFunc::GenerateSeriesInt32 => true,
             TableFunc::GenerateSeriesInt64 => true,
+            TableFunc::GenerateSeriesTimestamp => true,
+            TableFunc::GenerateSeriesTimestampTz { .. } => true,
             TableFunc::Repeat => false,
             TableFunc::ReadCachedData { .. } => true,
             TableFunc::UnnestArray { .. } => true,
             TableFunc::UnnestList { .. } => true,
+            TableFunc::WithOrdinality(func) => func.preserves_monotonicity(),
         }
     }
 }
@@ -954,16 +1392,31 @@ impl fmt::Display for TableFunc {
             TableFunc::JsonbEach { .. } => f.write_str("jsonb_each"),
             TableFunc::JsonbObjectKeys => f.write_str("jsonb_object_keys"),
             TableFunc::JsonbArrayElements { .. } => f.write_str("jsonb_array_elements"),
+            TableFunc::JsonbPathQuery => f.write_str("jsonb_path_query"),
+            TableFunc::JsonbPathQueryStatic(path) => {
+                write!(f, "jsonb_path_query[{}]", path.as_str())
+            }
+            TableFunc::JsonbToRecord { .. } => f.write_str("jsonb_to_record"),
+            TableFunc::JsonbToRecordset { .. } => f.write_str("jsonb_to_recordset"),
             TableFunc::RegexpExtract(a) => write!(f, "regexp_extract({:?}, _)", a.0),
+            TableFunc::RegexpMatches { regex, global } => {
+                write!(f, "regexp_matches[{}, global={}]", regex.as_str(), global)
+            }
+            TableFunc::RegexpSplitToTable(regex) => {
+                write!(f, "regexp_split_to_table[{}]", regex.as_str())
+            }
             TableFunc::CsvExtract(n_cols) => write!(f, "csv_extract({}, _)", n_cols),
             TableFunc::GenerateSeriesInt32 => f.write_str("generate_series"),
             TableFunc::GenerateSeriesInt64 => f.write_str("generate_series"),
+            TableFunc::GenerateSeriesTimestamp => f.write_str("generate_series"),
+            TableFunc::GenerateSeriesTimestampTz { .. } => f.write_str("generate_series"),
             TableFunc::Repeat => f.write_str("repeat_row"),
             TableFunc::ReadCachedData { source, .. } => {
                 write!(f, "internal_read_cached_data({})", source)
             }
             TableFunc::UnnestArray { .. } => f.write_str("unnest_array"),
             TableFunc::UnnestList { .. } => f.write_str("unnest_list"),
+            TableFunc::WithOrdinality(func) => write!(f, "{}_with_ordinality", func),
         }
     }
 }
@@ -1383,6 +1383,7 @@ impl AggregateExpr {
             | AggregateFunc::MaxFloat32
             | AggregateFunc::MaxFloat64
             | AggregateFunc::MaxDecimal
+            | AggregateFunc::MaxAPD
             | AggregateFunc::MaxBool
             | AggregateFunc::MaxString
             | AggregateFunc::MaxDate
@@ -1393,6 +1394,7 @@ impl AggregateExpr {
             | AggregateFunc::MinFloat32
             | AggregateFunc::MinFloat64
             | AggregateFunc::MinDecimal
+            | AggregateFunc::MinAPD
             | AggregateFunc::MinBool
             | AggregateFunc::MinString
             | AggregateFunc::MinDate
@@ -9,6 +9,7 @@
 
 use std::borrow::Cow;
 use std::cmp::{self, Ordering};
+use std::collections::BTreeSet;
 use std::convert::{TryFrom, TryInto};
 use std::fmt;
 use std::iter;
@@ -21,7 +22,7 @@ use chrono::{
     DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Offset, TimeZone, Timelike,
     Utc,
 };
-use dec::OrderedDecimal;
+use dec::{OrderedDecimal, Rounding};
 use hmac::{Hmac, Mac, NewMac};
 use itertools::Itertools;
 use md5::{Digest, Md5};
@@ -36,12 +37,14 @@ use ore::fmt::FormatBuffer;
 use ore::result::ResultExt;
 use ore::str::StrExt;
 use pgrepr::Type;
-use repr::adt::apd;
+use repr::adt::apd::{self, Apd};
 use repr::adt::array::ArrayDimension;
+use repr::adt::char;
 use repr::adt::datetime::{DateTimeUnits, Timezone};
 use repr::adt::decimal::MAX_DECIMAL_PRECISION;
 use repr::adt::interval::Interval;
 use repr::adt::jsonb::JsonbRef;
+use repr::adt::range::{InvalidRangeError, RangeBound};
 use repr::adt::regex::Regex;
 use repr::{strconv, ColumnName, ColumnType, Datum, Row, RowArena, ScalarType};
 
@@ -50,6 +53,9 @@ use crate::{like_pattern, EvalError, MirScalarExpr};
 
 mod encoding;
 mod format;
+mod jsonpath;
+
+pub use jsonpath::JsonPath;
 
 #[derive(Ord, PartialOrd, Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash)]
 pub enum NullaryFunc {
@@ -122,6 +128,12 @@ fn abs_decimal<'a>(a: Datum<'a>) -> Datum<'a> {
     Datum::from(a.unwrap_decimal().abs())
 }
 
+fn abs_apd<'a>(a: Datum<'a>) -> Datum<'a> {
+    let mut a = a.unwrap_apd();
+    apd::cx_datum().abs(&mut a.0);
+    Datum::APD(a)
+}
+
 fn abs_float32<'a>(a: Datum<'a>) -> Datum<'a> {
     Datum::from(a.unwrap_float32().abs())
 }
@@ -345,6 +357,129 @@ fn cast_apd_to_string<'a>(a: Datum<'a>, temp_storage: &'a RowArena) -> Datum<'a>
     Datum::String(temp_storage.push_string(buf))
 }
 
+/// Converts `n` to a datum, rescaling it to `scale` if specified.
+fn apd_datum_with_scale<'a>(mut n: Apd, scale: Option<u8>) -> Result<Datum<'a>, EvalError> {
+    if let Some(scale) = scale {
+        if apd::rescale(&mut n, scale).is_err() {
+            return Err(EvalError::NumericFieldOverflow);
+        }
+    }
+    Ok(Datum::APD(OrderedDecimal(n)))
+}
+
+fn cast_int32_to_apd<'a>(a: Datum<'a>, scale: Option<u8>) -> Result<Datum<'a>, EvalError> {
+    apd_datum_with_scale(Apd::from(a.unwrap_int32()), scale)
+}
+
+fn cast_int64_to_apd<'a>(a: Datum<'a>, scale: Option<u8>) -> Result<Datum<'a>, EvalError> {
+    apd_datum_with_scale(Apd::from(a.unwrap_int64()), scale)
+}
+
+fn cast_float32_to_apd<'a>(a: Datum<'a>, scale: Option<u8>) -> Result<Datum<'a>, EvalError> {
+    let a = a.unwrap_float32();
+    if a.is_infinite() {
+        return Err(EvalError::InfinityOutOfDomain(
+            "casting real to numeric".to_owned(),
+        ));
+    }
+    // The `Display` implementation for floats produces the shortest decimal
+    // representation that round trips, which is also what PostgreSQL uses.
+    let n = strconv::parse_apd(&a.to_string()).map_err(|_| EvalError::NumericFieldOverflow)?;
+    apd_datum_with_scale(n.0, scale)
+}
+
+fn cast_float64_to_apd<'a>(a: Datum<'a>, scale: Option<u8>) -> Result<Datum<'a>, EvalError> {
+    let a = a.unwrap_float64();
+    if a.is_infinite() {
+        return Err(EvalError::InfinityOutOfDomain(
+            "casting double precision to numeric".to_owned(),
+        ));
+    }
+    // See `cast_float32_to_apd`.
+    let n = strconv::parse_apd(&a.to_string()).map_err(|_| EvalError::NumericFieldOverflow)?;
+    apd_datum_with_scale(n.0, scale)
+}
+
+fn cast_decimal_to_apd<'a>(
+    a: Datum<'a>,
+    from_scale: u8,
+    to_scale: Option<u8>,
+) -> Result<Datum<'a>, EvalError> {
+    let d = a.unwrap_decimal().with_scale(from_scale);
+    // Every decimal fits within an APD's maximum precision.
+    let n = strconv::parse_apd(&d.to_string()).expect("decimal is valid APD");
+    apd_datum_with_scale(n.0, to_scale)
+}
+
+/// Rounds `n` to an integer, with ties rounding away from zero as in
+/// PostgreSQL.
+fn round_apd_to_integer(n: &mut Apd) {
+    let mut cx = apd::cx_datum();
+    cx.set_rounding(Rounding::HalfUp);
+    cx.round(n);
+}
+
+fn cast_apd_to_int32<'a>(a: Datum<'a>) -> Result<Datum<'a>, EvalError> {
+    let mut a = a.unwrap_apd().0;
+    round_apd_to_integer(&mut a);
+    match apd::cx_datum().try_into_i32(a) {
+        Ok(i) => Ok(Datum::from(i)),
+        Err(_) => Err(EvalError::Int32OutOfRange),
+    }
+}
+
+fn cast_apd_to_int64<'a>(a: Datum<'a>) -> Result<Datum<'a>, EvalError> {
+    let mut a = a.unwrap_apd().0;
+    round_apd_to_integer(&mut a);
+    match apd::cx_datum().try_into_i64(a) {
+        Ok(i) => Ok(Datum::from(i)),
+        Err(_) => Err(EvalError::Int64OutOfRange),
+    }
+}
+
+fn cast_apd_to_float64_inner(n: &Apd) -> f64 {
+    if n.is_nan() {
+        f64::NAN
+    } else if n.is_infinite() {
+        if n.is_negative() {
+            f64::NEG_INFINITY
+        } else {
+            f64::INFINITY
+        }
+    } else {
+        n.to_standard_notation_string()
+            .parse()
+            .expect("finite APD values are valid floats")
+    }
+}
+
+fn cast_apd_to_float32<'a>(a: Datum<'a>) -> Result<Datum<'a>, EvalError> {
+    let n = a.unwrap_apd().0;
+    let f = cast_apd_to_float64_inner(&n) as f32;
+    if f.is_infinite() && !n.is_infinite() {
+        Err(EvalError::FloatOverflow)
+    } else {
+        Ok(Datum::from(f))
+    }
+}
+
+fn cast_apd_to_float64<'a>(a: Datum<'a>) -> Datum<'a> {
+    Datum::from(cast_apd_to_float64_inner(&a.unwrap_apd().0))
+}
+
+fn cast_apd_to_decimal<'a>(a: Datum<'a>, scale: u8) -> Result<Datum<'a>, EvalError> {
+    let mut n = a.unwrap_apd().0;
+    if n.is_nan() || n.is_infinite() || apd::rescale(&mut n, scale).is_err() {
+        return Err(EvalError::NumericFieldOverflow);
+    }
+    match strconv::parse_decimal(&n.to_standard_notation_string()) {
+        Ok(d) if d.significand().abs() < 10_i128.pow(u32::from(MAX_DECIMAL_PRECISION)) => {
+            Ok(Datum::from(d.significand()))
+        }
+        _ => Err(EvalError::NumericFieldOverflow),
+    }
+}
+
 fn cast_string_to_bool<'a>(a: Datum<'a>) -> Result<Datum<'a>, EvalError> {
     match strconv::parse_bool(a.unwrap_str())? {
         true => Ok(Datum::True),
@@ -406,6 +541,47 @@ fn cast_string_to_apd<'a>(a: Datum<'a>, scale: Option<u8>) -> Result<Datum<'a>,
     Ok(Datum::APD(d))
 }
 
+fn cast_string_to_char<'a>(
+    a: Datum<'a>,
+    length: Option<usize>,
+    fail_on_len: bool,
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    let length = match length {
+        None => return Ok(a),
+        Some(length) => length,
+    };
+    let s = char::pad_char(a.unwrap_str(), length, fail_on_len).map_err(|_| {
+        EvalError::StringValueTooLong {
+            target_type: "character".into(),
+            length,
+        }
+    })?;
+    Ok(Datum::String(temp_storage.push_string(s)))
+}
+
+fn cast_char_to_string<'a>(a: Datum<'a>) -> Datum<'a> {
+    Datum::String(char::trim_char(a.unwrap_str()))
+}
+
+fn cast_string_to_varchar<'a>(
+    a: Datum<'a>,
+    max_length: Option<usize>,
+    fail_on_len: bool,
+) -> Result<Datum<'a>, EvalError> {
+    let max_length = match max_length {
+        None => return Ok(a),
+        Some(max_length) => max_length,
+    };
+    let s = char::truncate_varchar(a.unwrap_str(), max_length, fail_on_len).map_err(|_| {
+        EvalError::StringValueTooLong {
+            target_type: "character varying".into(),
+            length: max_length,
+        }
+    })?;
+    Ok(Datum::String(s))
+}
+
 fn cast_string_to_date<'a>(a: Datum<'a>) -> Result<Datum<'a>, EvalError> {
     strconv::parse_date(a.unwrap_str())
         .map(Datum::Date)
@@ -417,7 +593,7 @@ fn cast_string_to_array<'a>(
     cast_expr: &'a MirScalarExpr,
     temp_storage: &'a RowArena,
 ) -> Result<Datum<'a>, EvalError> {
-    let datums = strconv::parse_array(
+    let (dims, datums) = strconv::parse_array(
         a.unwrap_str(),
         || Datum::Null,
         |elem_text| {
@@ -428,7 +604,8 @@ fn cast_string_to_array<'a>(
             cast_expr.eval(&[Datum::String(elem_text)], temp_storage)
         },
     )?;
-    array_create_scalar(&datums, temp_storage)
+    let datum = temp_storage.try_make_datum(|packer| packer.push_array(&dims, datums))?;
+    Ok(datum)
 }
 
 fn cast_string_to_list<'a>(
@@ -453,6 +630,31 @@ fn cast_string_to_list<'a>(
     Ok(temp_storage.make_datum(|packer| packer.push_list(parsed_datums)))
 }
 
+fn cast_string_to_range<'a>(
+    a: Datum<'a>,
+    cast_expr: &'a MirScalarExpr,
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    let parts = strconv::parse_range(a.unwrap_str(), |elem_text| {
+        let elem_text = match elem_text {
+            Cow::Owned(s) => temp_storage.push_string(s),
+            Cow::Borrowed(s) => s,
+        };
+        cast_expr.eval(&[Datum::String(elem_text)], temp_storage)
+    })?;
+    let bounds = parts.map(|parts| {
+        let bound = |value: Option<Datum<'a>>, inclusive| match value {
+            Some(value) => RangeBound::new(value, inclusive),
+            None => RangeBound::infinite(),
+        };
+        (
+            bound(parts.lower, parts.lower_inclusive),
+            bound(parts.upper, parts.upper_inclusive),
+        )
+    });
+    range_from_bounds(bounds, temp_storage)
+}
+
 fn cast_string_to_map<'a>(
     a: Datum<'a>,
     map_typ: &ScalarType,
@@ -495,8 +697,8 @@ fn cast_string_to_timestamp<'a>(a: Datum<'a>) -> Result<Datum<'a>, EvalError> {
         .err_into()
 }
 
-fn cast_string_to_timestamptz<'a>(a: Datum<'a>) -> Result<Datum<'a>, EvalError> {
-    strconv::parse_timestamptz(a.unwrap_str())
+fn cast_string_to_timestamptz<'a>(a: Datum<'a>, tz: Timezone) -> Result<Datum<'a>, EvalError> {
+    strconv::parse_timestamptz_in(a.unwrap_str(), tz)
         .map(Datum::TimestampTz)
         .err_into()
 }
@@ -513,6 +715,18 @@ fn cast_string_to_uuid<'a>(a: Datum<'a>) -> Result<Datum<'a>, EvalError> {
         .err_into()
 }
 
+fn cast_string_to_inet<'a>(a: Datum<'a>) -> Result<Datum<'a>, EvalError> {
+    strconv::parse_inet(a.unwrap_str())
+        .map(Datum::Inet)
+        .err_into()
+}
+
+fn cast_string_to_cidr<'a>(a: Datum<'a>) -> Result<Datum<'a>, EvalError> {
+    strconv::parse_cidr(a.unwrap_str())
+        .map(Datum::Inet)
+        .err_into()
+}
+
 fn cast_date_to_timestamp<'a>(a: Datum<'a>) -> Datum<'a> {
     Datum::Timestamp(a.unwrap_date().and_hms(0, 0, 0))
 }
@@ -570,9 +784,14 @@ fn cast_timestamptz_to_timestamp<'a>(a: Datum<'a>) -> Datum<'a> {
     Datum::Timestamp(a.unwrap_timestamptz().naive_utc())
 }
 
-fn cast_timestamptz_to_string<'a>(a: Datum<'a>, temp_storage: &'a RowArena) -> Datum<'a> {
+fn cast_timestamptz_to_string<'a>(
+    a: Datum<'a>,
+    tz: Timezone,
+    temp_storage: &'a RowArena,
+) -> Datum<'a> {
+    let ts = a.unwrap_timestamptz();
     let mut buf = String::new();
-    strconv::format_timestamptz(&mut buf, a.unwrap_timestamptz());
+    strconv::format_timestamptz_local(&mut buf, ts.with_timezone(&tz.offset_at(&ts.naive_utc())));
     Datum::String(temp_storage.push_string(buf))
 }
 
@@ -714,6 +933,17 @@ fn cast_jsonb_to_decimal<'a>(a: Datum<'a>, scale: u8) -> Result<Datum<'a>, EvalE
     }
 }
 
+fn cast_jsonb_to_apd<'a>(a: Datum<'a>, scale: Option<u8>) -> Result<Datum<'a>, EvalError> {
+    match a {
+        Datum::Int64(_) => cast_int64_to_apd(a, scale),
+        Datum::Float64(_) => cast_float64_to_apd(a, scale),
+        _ => Err(EvalError::InvalidJsonbCast {
+            from: jsonb_type(a).into(),
+            to: "numeric".into(),
+        }),
+    }
+}
+
 fn cast_jsonb_to_bool<'a>(a: Datum<'a>) -> Result<Datum<'a>, EvalError> {
     match a {
         Datum::True | Datum::False => Ok(a),
@@ -742,6 +972,12 @@ fn cast_uuid_to_string<'a>(a: Datum<'a>, temp_storage: &'a RowArena) -> Datum<'a
     Datum::String(temp_storage.push_string(buf))
 }
 
+fn cast_inet_to_string<'a>(a: Datum<'a>, temp_storage: &'a RowArena) -> Datum<'a> {
+    // Unlike the output function of either type, casting to text always
+    // includes the netmask length.
+    Datum::String(temp_storage.push_string(a.unwrap_inet().to_string()))
+}
+
 /// Casts between two list types by casting each element of `a` ("list1") using
 /// `cast_expr` and collecting the results into a new list ("list2").
 fn cast_list1_to_list2<'a>(
@@ -895,6 +1131,50 @@ fn round_decimal_binary<'a>(a: Datum<'a>, b: Datum<'a>, a_scale: u8) -> Datum<'a
     Datum::from(decimal.round(round_to).significand())
 }
 
+fn round_apd_with_rounding<'a>(a: Datum<'a>, rounding: Rounding) -> Datum<'a> {
+    let mut a = a.unwrap_apd().0;
+    let mut cx = apd::cx_datum();
+    cx.set_rounding(rounding);
+    cx.round(&mut a);
+    // Rounding can produce negative zero, e.g. `ceil(-0.5)`.
+    apd::munge_apd(&mut a).unwrap();
+    Datum::APD(OrderedDecimal(a))
+}
+
+fn ceil_apd<'a>(a: Datum<'a>) -> Datum<'a> {
+    round_apd_with_rounding(a, Rounding::Ceiling)
+}
+
+fn floor_apd<'a>(a: Datum<'a>) -> Datum<'a> {
+    round_apd_with_rounding(a, Rounding::Floor)
+}
+
+fn round_apd_unary<'a>(a: Datum<'a>) -> Datum<'a> {
+    round_apd_with_rounding(a, Rounding::HalfUp)
+}
+
+fn round_apd_binary<'a>(a: Datum<'a>, b: Datum<'a>) -> Result<Datum<'a>, EvalError> {
+    let mut a = a.unwrap_apd().0;
+    let places = b.unwrap_int32();
+    // Rounding to more places than `a` already has never changes its value.
+    if a.is_nan() || a.is_infinite() || -a.exponent() <= places {
+        return Ok(Datum::APD(OrderedDecimal(a)));
+    }
+    let mut cx = apd::cx_datum();
+    cx.set_rounding(Rounding::HalfUp);
+    let exponent = places.checked_neg().ok_or(EvalError::Int32OutOfRange)?;
+    cx.rescale(&mut a, &Apd::from(exponent));
+    if places < 0 {
+        // Express values rounded to the left of the decimal point without an
+        // exponent, e.g. `1200` rather than `1.2E+3`.
+        cx.rescale(&mut a, &Apd::zero());
+    }
+    if cx.status().invalid_operation() || apd::munge_apd(&mut a).is_err() {
+        return Err(EvalError::NumericFieldOverflow);
+    }
+    Ok(Datum::APD(OrderedDecimal(a)))
+}
+
 fn convert_from<'a>(a: Datum<'a>, b: Datum<'a>) -> Result<Datum<'a>, EvalError> {
     // Convert PostgreSQL-style encoding names[1] to WHATWG-style encoding names[2],
     // which the encoding library uses[3].
@@ -1001,7 +1281,7 @@ fn sub_timestamptz_interval<'a>(a: Datum<'a>, b: Datum<'a>) -> Datum<'a> {
     add_timestamptz_interval(a, Datum::Interval(-b.unwrap_interval()))
 }
 
-fn add_timestamp_months(dt: NaiveDateTime, months: i32) -> NaiveDateTime {
+pub(crate) fn add_timestamp_months(dt: NaiveDateTime, months: i32) -> NaiveDateTime {
     if months == 0 {
         return dt;
     }
@@ -1310,6 +1590,18 @@ fn mod_decimal<'a>(a: Datum<'a>, b: Datum<'a>) -> Result<Datum<'a>, EvalError> {
     }
 }
 
+fn mod_apd<'a>(a: Datum<'a>, b: Datum<'a>) -> Result<Datum<'a>, EvalError> {
+    let mut a = a.unwrap_apd().0;
+    let b = b.unwrap_apd().0;
+    if b.is_zero() {
+        return Err(EvalError::DivisionByZero);
+    }
+    let mut cx = apd::cx_datum();
+    cx.rem(&mut a, &b);
+    apd::munge_apd(&mut a).unwrap();
+    Ok(Datum::APD(OrderedDecimal(a)))
+}
+
 fn neg_int32<'a>(a: Datum<'a>) -> Datum<'a> {
     Datum::from(-a.unwrap_int32())
 }
@@ -1341,6 +1633,24 @@ pub fn neg_interval<'a>(a: Datum<'a>) -> Datum<'a> {
     Datum::from(-a.unwrap_interval())
 }
 
+fn justify_days<'a>(a: Datum<'a>) -> Result<Datum<'a>, EvalError> {
+    a.unwrap_interval()
+        .justify_days()
+        .map(Datum::Interval)
+        .ok_or(EvalError::IntervalOutOfRange)
+}
+
+fn justify_hours<'a>(a: Datum<'a>) -> Datum<'a> {
+    Datum::Interval(a.unwrap_interval().justify_hours())
+}
+
+fn justify_interval<'a>(a: Datum<'a>) -> Result<Datum<'a>, EvalError> {
+    a.unwrap_interval()
+        .justify_interval()
+        .map(Datum::Interval)
+        .ok_or(EvalError::IntervalOutOfRange)
+}
+
 fn sqrt_float64<'a>(a: Datum<'a>) -> Result<Datum, EvalError> {
     let x = a.unwrap_float64();
     if x < 0.0 {
@@ -1469,6 +1779,97 @@ fn power_dec<'a>(a: Datum<'a>, b: Datum<'a>, scale: u8) -> Result<Datum<'a>, Eva
     cast_float64_to_decimal(Datum::from(a.powf(b)), scale)
 }
 
+/// Checks the status of `cx` after computing `n` and, if the computation
+/// neither overflowed nor underflowed, converts `n` to a datum.
+fn checked_apd_datum<'a>(cx: &mut dec::Context<Apd>, mut n: Apd) -> Result<Datum<'a>, EvalError> {
+    let cx_status = cx.status();
+    if cx_status.overflow() {
+        Err(EvalError::FloatOverflow)
+    } else if cx_status.subnormal() {
+        Err(EvalError::FloatUnderflow)
+    } else if apd::munge_apd(&mut n).is_err() {
+        Err(EvalError::NumericFieldOverflow)
+    } else {
+        Ok(Datum::APD(OrderedDecimal(n)))
+    }
+}
+
+fn sqrt_apd<'a>(a: Datum<'a>) -> Result<Datum<'a>, EvalError> {
+    let mut a = a.unwrap_apd().0;
+    if a.is_negative() && !a.is_zero() {
+        return Err(EvalError::NegSqrt);
+    }
+    let mut cx = apd::cx_datum();
+    cx.sqrt(&mut a);
+    checked_apd_datum(&mut cx, a)
+}
+
+fn log_guard_apd(n: &Apd, function_name: &str) -> Result<(), EvalError> {
+    if n.is_zero() {
+        Err(EvalError::ZeroOutOfDomain(function_name.to_owned()))
+    } else if n.is_negative() {
+        Err(EvalError::NegativeOutOfDomain(function_name.to_owned()))
+    } else {
+        Ok(())
+    }
+}
+
+fn ln_apd<'a>(a: Datum<'a>) -> Result<Datum<'a>, EvalError> {
+    let mut a = a.unwrap_apd().0;
+    log_guard_apd(&a, "ln")?;
+    let mut cx = apd::cx_datum();
+    cx.ln(&mut a);
+    checked_apd_datum(&mut cx, a)
+}
+
+fn log10_apd<'a>(a: Datum<'a>) -> Result<Datum<'a>, EvalError> {
+    let mut a = a.unwrap_apd().0;
+    log_guard_apd(&a, "log10")?;
+    let mut cx = apd::cx_datum();
+    cx.log10(&mut a);
+    checked_apd_datum(&mut cx, a)
+}
+
+fn log_base_apd<'a>(a: Datum<'a>, b: Datum<'a>) -> Result<Datum<'a>, EvalError> {
+    let mut base = a.unwrap_apd().0;
+    let mut n = b.unwrap_apd().0;
+    log_guard_apd(&base, "log")?;
+    log_guard_apd(&n, "log")?;
+    let mut cx = apd::cx_datum();
+    cx.ln(&mut base);
+    if base.is_zero() {
+        return Err(EvalError::DivisionByZero);
+    }
+    cx.ln(&mut n);
+    cx.div(&mut n, &base);
+    checked_apd_datum(&mut cx, n)
+}
+
+fn exp_apd<'a>(a: Datum<'a>) -> Result<Datum<'a>, EvalError> {
+    let mut a = a.unwrap_apd().0;
+    let mut cx = apd::cx_datum();
+    cx.exp(&mut a);
+    checked_apd_datum(&mut cx, a)
+}
+
+fn power_apd<'a>(a: Datum<'a>, b: Datum<'a>) -> Result<Datum<'a>, EvalError> {
+    let mut a = a.unwrap_apd().0;
+    let b = b.unwrap_apd().0;
+    if a.is_zero() && b.is_negative() {
+        return Err(EvalError::InvalidParameterValue(
+            "zero raised to a negative power is undefined".to_owned(),
+        ));
+    }
+    let mut cx = apd::cx_datum();
+    cx.pow(&mut a, &b);
+    if cx.status().invalid_operation() {
+        return Err(EvalError::InvalidParameterValue(
+            "a negative number raised to a non-integer power yields a complex result".to_owned(),
+        ));
+    }
+    checked_apd_datum(&mut cx, a)
+}
+
 fn sleep<'a>(a: Datum<'a>) -> Result<Datum<'a>, EvalError> {
     let duration = std::time::Duration::from_secs_f64(a.unwrap_float64());
     thread::sleep(duration);
@@ -1517,6 +1918,93 @@ fn to_char_timestamptz<'a>(a: Datum<'a>, b: Datum<'a>, temp_storage: &'a RowAren
     Datum::String(temp_storage.push_string(fmt.render(a.unwrap_timestamptz())))
 }
 
+fn to_date<'a>(a: Datum<'a>, b: Datum<'a>) -> Result<Datum<'a>, EvalError> {
+    let fmt = DateTimeFormat::compile(b.unwrap_str());
+    let (dt, _offset) = fmt.parse(a.unwrap_str())?;
+    Ok(Datum::Date(dt.date()))
+}
+
+/// Parses `a` according to the format string `b`. If the format string does
+/// not specify a UTC offset, the parsed timestamp is interpreted in the
+/// timezone `tz`.
+fn to_timestamp_with_format<'a>(
+    a: Datum<'a>,
+    b: Datum<'a>,
+    tz: Timezone,
+) -> Result<Datum<'a>, EvalError> {
+    let fmt = DateTimeFormat::compile(b.unwrap_str());
+    match fmt.parse(a.unwrap_str())? {
+        (dt, Some(offset)) => {
+            let utc = dt
+                .checked_sub_signed(Duration::seconds(offset.into()))
+                .ok_or(EvalError::TimestampOutOfRange)?;
+            Ok(DateTime::<Utc>::from_utc(utc, Utc).into())
+        }
+        (dt, None) => timezone_timestamp(tz, dt),
+    }
+}
+
+/// Subtracts `b` from `a`, producing a symbolic result that uses years and
+/// months rather than just days, in the manner of PostgreSQL's `age`
+/// function.
+fn age_timestamp<'a>(a: Datum<'a>, b: Datum<'a>) -> Result<Datum<'a>, EvalError> {
+    let a = a.unwrap_timestamp();
+    let b = b.unwrap_timestamp();
+    let (later, earlier, negate) = if a >= b { (a, b, false) } else { (b, a, true) };
+
+    let mut nanos = i64::from(later.nanosecond()) - i64::from(earlier.nanosecond());
+    let mut seconds = i64::from(later.second()) - i64::from(earlier.second());
+    let mut minutes = i64::from(later.minute()) - i64::from(earlier.minute());
+    let mut hours = i64::from(later.hour()) - i64::from(earlier.hour());
+    let mut days = i64::from(later.day()) - i64::from(earlier.day());
+    let mut months = i64::from(later.month()) - i64::from(earlier.month());
+    let mut years = i64::from(later.year()) - i64::from(earlier.year());
+
+    // Borrow from the next largest field until every field is non-negative.
+    // Days are borrowed using the length of the earlier timestamp's month.
+    if nanos < 0 {
+        nanos += 1_000_000_000;
+        seconds -= 1;
+    }
+    if seconds < 0 {
+        seconds += 60;
+        minutes -= 1;
+    }
+    if minutes < 0 {
+        minutes += 60;
+        hours -= 1;
+    }
+    if hours < 0 {
+        hours += 24;
+        days -= 1;
+    }
+    if days < 0 {
+        days += days_in_month(earlier.date());
+        months -= 1;
+    }
+    if months < 0 {
+        months += 12;
+        years -= 1;
+    }
+
+    let months = i32::try_from(years * 12 + months).map_err(|_| EvalError::IntervalOutOfRange)?;
+    let seconds = days * 86_400 + hours * 3_600 + minutes * 60 + seconds;
+    let interval =
+        Interval::new(months, seconds, nanos).map_err(|_| EvalError::IntervalOutOfRange)?;
+    Ok(Datum::Interval(if negate { -interval } else { interval }))
+}
+
+/// Returns the number of days in the month containing `date`.
+fn days_in_month(date: NaiveDate) -> i64 {
+    let (year, month) = match date.month() {
+        12 => (date.year() + 1, 1),
+        month => (date.year(), month + 1),
+    };
+    let first_of_month = NaiveDate::from_ymd(date.year(), date.month(), 1);
+    let first_of_next = NaiveDate::from_ymd(year, month, 1);
+    (first_of_next - first_of_month).num_days()
+}
+
 fn jsonb_get_int64<'a>(
     a: Datum<'a>,
     b: Datum<'a>,
@@ -1789,25 +2277,251 @@ fn jsonb_delete_string<'a>(a: Datum<'a>, b: Datum<'a>, temp_storage: &'a RowAren
     }
 }
 
-fn ascii<'a>(a: Datum<'a>) -> Datum<'a> {
-    match a.unwrap_str().chars().next() {
-        None => Datum::Int32(0),
-        Some(v) => Datum::Int32(v as i32),
-    }
+/// An update to apply to the value at the end of a jsonb path.
+enum JsonbPathUpdate<'a> {
+    /// Replaces the value, or, if `create_missing` is set and the value does
+    /// not exist, adds it.
+    Set {
+        value: Datum<'a>,
+        create_missing: bool,
+    },
+    /// Inserts a new value before or after the value in an array, or adds it
+    /// to an object.
+    Insert { value: Datum<'a>, after: bool },
+    /// Deletes the value.
+    Delete,
 }
 
-/// A timestamp with both a date and a time component, but not necessarily a
-/// timezone component.
-pub trait TimestampLike: chrono::Datelike + chrono::Timelike + for<'a> Into<Datum<'a>> {
-    fn new(date: NaiveDate, time: NaiveTime) -> Self;
-
-    /// Returns the weekday as a `usize` between 0 and 6, where 0 represents
-    /// Sunday and 6 represents Saturday.
-    fn weekday0(&self) -> usize {
-        self.weekday().num_days_from_sunday() as usize
+fn jsonb_update_path<'a>(
+    json: Datum<'a>,
+    path: Datum<'a>,
+    update: JsonbPathUpdate<'a>,
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    // https://github.com/postgres/postgres/blob/REL_13_STABLE/src/backend/utils/adt/jsonfuncs.c
+    fn update_path<'a>(
+        json: Datum<'a>,
+        path: &[Datum<'a>],
+        level: usize,
+        update: &JsonbPathUpdate<'a>,
+        row: &mut Row,
+    ) -> Result<(), EvalError> {
+        let key = match path[level] {
+            Datum::String(key) => key,
+            _ => {
+                return Err(EvalError::InvalidParameterValue(format!(
+                    "path element at position {} is null",
+                    level + 1
+                )))
+            }
+        };
+        let last = level == path.len() - 1;
+        let new_value = match update {
+            JsonbPathUpdate::Set {
+                value,
+                create_missing: true,
+            }
+            | JsonbPathUpdate::Insert { value, .. }
+                if last =>
+            {
+                Some(*value)
+            }
+            _ => None,
+        };
+        match json {
+            Datum::Map(dict) => {
+                let exists = dict.iter().any(|(k, _)| k == key);
+                if last && exists {
+                    if let JsonbPathUpdate::Insert { .. } = update {
+                        return Err(EvalError::InvalidParameterValue(
+                            "cannot replace existing key".into(),
+                        ));
+                    }
+                }
+                let mut new_value = if exists { None } else { new_value };
+                row.push_dict_with(|row| {
+                    for (k, v) in dict.iter() {
+                        // Keep the keys sorted when adding a missing key.
+                        if let Some(value) = new_value {
+                            if key < k {
+                                row.push(Datum::String(key));
+                                row.push(value);
+                                new_value = None;
+                            }
+                        }
+                        if k != key {
+                            row.push(Datum::String(k));
+                            row.push(v);
+                        } else if !last {
+                            row.push(Datum::String(k));
+                            update_path(v, path, level + 1, update, row)?;
+                        } else if let JsonbPathUpdate::Set { value, .. } = update {
+                            row.push(Datum::String(k));
+                            row.push(*value);
+                        }
+                    }
+                    if let Some(value) = new_value {
+                        row.push(Datum::String(key));
+                        row.push(value);
+                    }
+                    Ok(())
+                })
+            }
+            Datum::List(list) => {
+                let elems = list.iter().collect::<Vec<_>>();
+                let len = elems.len() as i64;
+                let idx: i64 = key.parse().map_err(|_| {
+                    EvalError::InvalidParameterValue(format!(
+                        "path element at position {} is not an integer: \"{}\"",
+                        level + 1,
+                        key
+                    ))
+                })?;
+                // Negative indices count backwards from the end of the array.
+                // An index of -1 after this adjustment indicates a position
+                // before the start of the array.
+                let idx = if idx < 0 {
+                    if -idx > len {
+                        -1
+                    } else {
+                        len + idx
+                    }
+                } else {
+                    cmp::min(idx, len)
+                };
+                row.push_list_with(|row| {
+                    if let Some(value) = new_value {
+                        if idx == -1 || len == 0 {
+                            row.push(value);
+                        }
+                    }
+                    for (i, elem) in elems.iter().enumerate() {
+                        if i as i64 != idx {
+                            row.push(*elem);
+                        } else if !last {
+                            update_path(*elem, path, level + 1, update, row)?;
+                        } else {
+                            match update {
+                                JsonbPathUpdate::Set { value, .. } => row.push(*value),
+                                JsonbPathUpdate::Insert {
+                                    value,
+                                    after: false,
+                                } => {
+                                    row.push(*value);
+                                    row.push(*elem);
+                                }
+                                JsonbPathUpdate::Insert { value, after: true } => {
+                                    row.push(*elem);
+                                    row.push(*value);
+                                }
+                                JsonbPathUpdate::Delete => (),
+                            }
+                        }
+                    }
+                    if let Some(value) = new_value {
+                        if idx == len && len > 0 {
+                            row.push(value);
+                        }
+                    }
+                    Ok(())
+                })
+            }
+            _ => {
+                row.push(json);
+                Ok(())
+            }
+        }
     }
 
-    /// Like [`chrono::Datelike::year_ce`], but works on the ISO week system.
+    match json {
+        Datum::Map(_) | Datum::List(_) => (),
+        _ => {
+            return Err(EvalError::InvalidParameterValue(match update {
+                JsonbPathUpdate::Delete => "cannot delete path in scalar".into(),
+                _ => "cannot set path in scalar".into(),
+            }))
+        }
+    }
+    let path = path.unwrap_array().elements().iter().collect::<Vec<_>>();
+    if path.is_empty() {
+        return Ok(json);
+    }
+    temp_storage.try_make_datum(|row| update_path(json, &path, 0, &update, row))
+}
+
+fn jsonb_delete_path<'a>(
+    a: Datum<'a>,
+    b: Datum<'a>,
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    jsonb_update_path(a, b, JsonbPathUpdate::Delete, temp_storage)
+}
+
+fn jsonb_set<'a>(datums: &[Datum<'a>], temp_storage: &'a RowArena) -> Result<Datum<'a>, EvalError> {
+    let update = JsonbPathUpdate::Set {
+        value: datums[2],
+        create_missing: datums.get(3) != Some(&Datum::False),
+    };
+    jsonb_update_path(datums[0], datums[1], update, temp_storage)
+}
+
+fn jsonb_insert<'a>(
+    datums: &[Datum<'a>],
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    let update = JsonbPathUpdate::Insert {
+        value: datums[2],
+        after: datums.get(3) == Some(&Datum::True),
+    };
+    jsonb_update_path(datums[0], datums[1], update, temp_storage)
+}
+
+fn jsonb_path_exists_dynamic<'a>(datums: &[Datum<'a>]) -> Result<Datum<'a>, EvalError> {
+    let path = JsonPath::parse(datums[1].unwrap_str())?;
+    let silent = datums.get(3) == Some(&Datum::True);
+    jsonb_path_exists(datums[0], &path, datums.get(2).copied(), silent)
+}
+
+fn jsonb_path_exists_static<'a>(
+    datums: &[Datum<'a>],
+    path: &JsonPath,
+) -> Result<Datum<'a>, EvalError> {
+    let silent = datums.get(2) == Some(&Datum::True);
+    jsonb_path_exists(datums[0], path, datums.get(1).copied(), silent)
+}
+
+fn jsonb_path_exists<'a>(
+    root: Datum<'a>,
+    path: &JsonPath,
+    vars: Option<Datum<'a>>,
+    silent: bool,
+) -> Result<Datum<'a>, EvalError> {
+    match path.query(root, vars) {
+        Ok(items) => Ok((!items.is_empty()).into()),
+        Err(_) if silent => Ok(Datum::Null),
+        Err(e) => Err(e),
+    }
+}
+
+fn ascii<'a>(a: Datum<'a>) -> Datum<'a> {
+    match a.unwrap_str().chars().next() {
+        None => Datum::Int32(0),
+        Some(v) => Datum::Int32(v as i32),
+    }
+}
+
+/// A timestamp with both a date and a time component, but not necessarily a
+/// timezone component.
+pub trait TimestampLike: chrono::Datelike + chrono::Timelike + for<'a> Into<Datum<'a>> {
+    fn new(date: NaiveDate, time: NaiveTime) -> Self;
+
+    /// Returns the weekday as a `usize` between 0 and 6, where 0 represents
+    /// Sunday and 6 represents Saturday.
+    fn weekday0(&self) -> usize {
+        self.weekday().num_days_from_sunday() as usize
+    }
+
+    /// Like [`chrono::Datelike::year_ce`], but works on the ISO week system.
     fn iso_year_ce(&self) -> u32 {
         let year = self.iso_week().year();
         if year < 1 {
@@ -1899,6 +2613,10 @@ pub trait TimestampLike: chrono::Datelike + chrono::Timelike + for<'a> Into<Datu
         f64::from(self.weekday().number_from_monday())
     }
 
+    fn extract_isodayofyear(&self) -> f64 {
+        f64::from(self.iso_week().week0() * 7 + self.weekday().number_from_monday())
+    }
+
     fn truncate_microseconds(&self) -> Self {
         let time = NaiveTime::from_hms_micro(
             self.hour(),
@@ -2035,6 +2753,10 @@ pub trait TimestampLike: chrono::Datelike + chrono::Timelike + for<'a> Into<Datu
     /// Returns the abbreviated name of the timezone with the specified
     /// capitalization.
     fn timezone_name(&self, caps: bool) -> &'static str;
+
+    /// Returns the timezone's offset from UTC in seconds, or `None` if the
+    /// timestamp does not have a timezone.
+    fn timezone_offset_seconds(&self) -> Option<i32>;
 }
 
 impl TimestampLike for chrono::NaiveDateTime {
@@ -2069,6 +2791,10 @@ impl TimestampLike for chrono::NaiveDateTime {
     fn timezone_name(&self, _caps: bool) -> &'static str {
         ""
     }
+
+    fn timezone_offset_seconds(&self) -> Option<i32> {
+        None
+    }
 }
 
 impl TimestampLike for chrono::DateTime<chrono::Utc> {
@@ -2107,6 +2833,10 @@ impl TimestampLike for chrono::DateTime<chrono::Utc> {
             "utc"
         }
     }
+
+    fn timezone_offset_seconds(&self) -> Option<i32> {
+        Some(0)
+    }
 }
 
 fn date_part_interval<'a>(a: Datum<'a>, interval: Interval) -> Result<Datum<'a>, EvalError> {
@@ -2170,6 +2900,7 @@ where
         DateTimeUnits::DayOfWeek => Ok(ts.extract_dayofweek().into()),
         DateTimeUnits::DayOfYear => Ok(ts.extract_dayofyear().into()),
         DateTimeUnits::IsoDayOfWeek => Ok(ts.extract_isodayofweek().into()),
+        DateTimeUnits::IsoDayOfYear => Ok(ts.extract_isodayofyear().into()),
         DateTimeUnits::Hour => Ok(ts.extract_hour().into()),
         DateTimeUnits::Minute => Ok(ts.extract_minute().into()),
         DateTimeUnits::Second => Ok(ts.extract_second().into()),
@@ -2179,10 +2910,39 @@ where
         DateTimeUnits::Millennium => Ok(ts.extract_millennium().into()),
         DateTimeUnits::Century => Ok(ts.extract_century().into()),
         DateTimeUnits::Decade => Ok(ts.extract_decade().into()),
-        DateTimeUnits::Timezone
-        | DateTimeUnits::TimezoneHour
-        | DateTimeUnits::TimezoneMinute
-        | DateTimeUnits::IsoDayOfYear => Err(EvalError::UnsupportedDateTimeUnits(units)),
+        DateTimeUnits::Timezone | DateTimeUnits::TimezoneHour | DateTimeUnits::TimezoneMinute => {
+            let offset = ts
+                .timezone_offset_seconds()
+                .ok_or(EvalError::UnsupportedDateTimeUnits(units))?;
+            let n = match units {
+                DateTimeUnits::Timezone => offset,
+                DateTimeUnits::TimezoneHour => offset / 3_600,
+                _ => offset / 60 % 60,
+            };
+            Ok(f64::from(n).into())
+        }
+    }
+}
+
+fn date_part_time<'a>(a: Datum<'a>, time: NaiveTime) -> Result<Datum<'a>, EvalError> {
+    let units = a.unwrap_str();
+    match units.parse() {
+        Ok(units) => date_part_time_inner(units, time),
+        Err(_) => Err(EvalError::UnknownUnits(units.to_owned())),
+    }
+}
+
+fn date_part_time_inner<'a>(units: DateTimeUnits, time: NaiveTime) -> Result<Datum<'a>, EvalError> {
+    let nanos = f64::from(time.nanosecond()) / 1e9;
+    let seconds = f64::from(time.second()) + nanos;
+    match units {
+        DateTimeUnits::Epoch => Ok((f64::from(time.num_seconds_from_midnight()) + nanos).into()),
+        DateTimeUnits::Hour => Ok(f64::from(time.hour()).into()),
+        DateTimeUnits::Minute => Ok(f64::from(time.minute()).into()),
+        DateTimeUnits::Second => Ok(seconds.into()),
+        DateTimeUnits::Milliseconds => Ok((seconds * 1e3).into()),
+        DateTimeUnits::Microseconds => Ok((seconds * 1e6).into()),
+        _ => Err(EvalError::UnsupportedDateTimeUnits(units)),
     }
 }
 
@@ -2249,7 +3009,10 @@ fn timezone_time(tz: Timezone, t: NaiveTime) -> Datum<'static> {
 /// `2020-11-11T17:39:14Z`. A DST observing timezone like `America/New_York` would cause the following DST anomalies:
 /// `2020-11-01T00:59:59` -> `2020-11-01T04:59:59Z` and `2020-11-01T01:00:00` -> `2020-11-01T06:00:00Z`
 /// `2020-03-08T02:59:59` -> `2020-03-08T07:59:59Z` and `2020-03-08T03:00:00` -> `2020-03-08T07:00:00Z`
-fn timezone_timestamp(tz: Timezone, mut dt: NaiveDateTime) -> Result<Datum<'static>, EvalError> {
+pub(crate) fn timezone_timestamp(
+    tz: Timezone,
+    mut dt: NaiveDateTime,
+) -> Result<Datum<'static>, EvalError> {
     let offset = match tz {
         Timezone::FixedOffset(offset) => offset,
         Timezone::Tz(tz) => match tz.offset_from_local_datetime(&dt).latest() {
@@ -2434,7 +3197,9 @@ pub enum BinaryFunc {
     ModFloat32,
     ModFloat64,
     ModDecimal,
+    ModAPD,
     RoundDecimal(u8),
+    RoundAPD,
     Eq,
     NotEq,
     Lt,
@@ -2445,7 +3210,11 @@ pub enum BinaryFunc {
     IsRegexpMatch { case_insensitive: bool },
     ToCharTimestamp,
     ToCharTimestampTz,
+    ToDate,
+    ToTimestampWithFormat(Timezone),
+    AgeTimestamp,
     DatePartInterval,
+    DatePartTime,
     DatePartTimestamp,
     DatePartTimestampTz,
     DateTruncTimestamp,
@@ -2465,6 +3234,7 @@ pub enum BinaryFunc {
     JsonbContainsJsonb,
     JsonbDeleteInt64,
     JsonbDeleteString,
+    JsonbDeletePath,
     MapContainsKey,
     MapGetValue,
     MapGetValues,
@@ -2482,21 +3252,37 @@ pub enum BinaryFunc {
     ListIndex,
     ListLengthMax { max_dim: usize },
     ArrayContains,
+    ArrayContainsArray,
+    ArrayOverlaps,
     ArrayIndex,
     ArrayLength,
     ArrayLower,
     ArrayUpper,
+    ArrayArrayConcat,
+    ArrayElementConcat,
+    ElementArrayConcat,
+    ArrayRemove,
     ListListConcat,
     ListElementConcat,
     ElementListConcat,
+    RangeContainsElem,
+    RangeContainsRange,
+    RangeOverlaps,
+    RangeMerge,
+    InetContains,
+    InetContainsOrEquals,
+    InetSetMasklen,
+    CidrSetMasklen,
     DigestString,
     DigestBytes,
     MzRenderTypemod,
     Encode,
     Decode,
     LogDecimal(u8),
+    LogAPD,
     Power,
     PowerDecimal(u8),
+    PowerAPD,
 }
 
 impl BinaryFunc {
@@ -2567,6 +3353,7 @@ impl BinaryFunc {
             BinaryFunc::ModFloat32 => eager!(mod_float32),
             BinaryFunc::ModFloat64 => eager!(mod_float64),
             BinaryFunc::ModDecimal => eager!(mod_decimal),
+            BinaryFunc::ModAPD => eager!(mod_apd),
             BinaryFunc::Eq => Ok(eager!(eq)),
             BinaryFunc::NotEq => Ok(eager!(not_eq)),
             BinaryFunc::Lt => Ok(eager!(lt)),
@@ -2581,9 +3368,15 @@ impl BinaryFunc {
             }
             BinaryFunc::ToCharTimestamp => Ok(eager!(to_char_timestamp, temp_storage)),
             BinaryFunc::ToCharTimestampTz => Ok(eager!(to_char_timestamptz, temp_storage)),
+            BinaryFunc::ToDate => eager!(to_date),
+            BinaryFunc::ToTimestampWithFormat(tz) => {
+                eager!(|a, b| to_timestamp_with_format(a, b, *tz))
+            }
+            BinaryFunc::AgeTimestamp => eager!(age_timestamp),
             BinaryFunc::DatePartInterval => {
                 eager!(|a, b: Datum| date_part_interval(a, b.unwrap_interval()))
             }
+            BinaryFunc::DatePartTime => eager!(|a, b: Datum| date_part_time(a, b.unwrap_time())),
             BinaryFunc::DatePartTimestamp => {
                 eager!(|a, b: Datum| date_part_timestamp(a, b.unwrap_timestamp()))
             }
@@ -2624,6 +3417,7 @@ impl BinaryFunc {
             BinaryFunc::JsonbContainsJsonb => Ok(eager!(jsonb_contains_jsonb)),
             BinaryFunc::JsonbDeleteInt64 => Ok(eager!(jsonb_delete_int64, temp_storage)),
             BinaryFunc::JsonbDeleteString => Ok(eager!(jsonb_delete_string, temp_storage)),
+            BinaryFunc::JsonbDeletePath => eager!(jsonb_delete_path, temp_storage),
             BinaryFunc::MapContainsKey => Ok(eager!(map_contains_key)),
             BinaryFunc::MapGetValue => Ok(eager!(map_get_value)),
             BinaryFunc::MapGetValues => Ok(eager!(map_get_values, temp_storage)),
@@ -2631,6 +3425,7 @@ impl BinaryFunc {
             BinaryFunc::MapContainsAnyKeys => Ok(eager!(map_contains_any_keys)),
             BinaryFunc::MapContainsMap => Ok(eager!(map_contains_map)),
             BinaryFunc::RoundDecimal(scale) => Ok(eager!(round_decimal_binary, *scale)),
+            BinaryFunc::RoundAPD => eager!(round_apd_binary),
             BinaryFunc::ConvertFrom => eager!(convert_from),
             BinaryFunc::Encode => eager!(encode, temp_storage),
             BinaryFunc::Decode => eager!(decode, temp_storage),
@@ -2644,18 +3439,34 @@ impl BinaryFunc {
             BinaryFunc::ListLengthMax { max_dim } => eager!(list_length_max, *max_dim),
             BinaryFunc::ArrayLength => Ok(eager!(array_length)),
             BinaryFunc::ArrayContains => Ok(eager!(array_contains)),
+            BinaryFunc::ArrayContainsArray => Ok(eager!(array_contains_array)),
+            BinaryFunc::ArrayOverlaps => Ok(eager!(array_overlaps)),
             BinaryFunc::ArrayIndex => Ok(eager!(array_index)),
             BinaryFunc::ArrayLower => Ok(eager!(array_lower)),
             BinaryFunc::ArrayUpper => Ok(eager!(array_upper)),
+            BinaryFunc::ArrayArrayConcat => eager!(array_array_concat, temp_storage),
+            BinaryFunc::ArrayElementConcat => eager!(array_element_concat, temp_storage),
+            BinaryFunc::ElementArrayConcat => eager!(element_array_concat, temp_storage),
+            BinaryFunc::ArrayRemove => eager!(array_remove, temp_storage),
             BinaryFunc::ListListConcat => Ok(eager!(list_list_concat, temp_storage)),
             BinaryFunc::ListElementConcat => Ok(eager!(list_element_concat, temp_storage)),
             BinaryFunc::ElementListConcat => Ok(eager!(element_list_concat, temp_storage)),
+            BinaryFunc::RangeContainsElem => Ok(eager!(range_contains_elem)),
+            BinaryFunc::RangeContainsRange => Ok(eager!(range_contains_range)),
+            BinaryFunc::RangeOverlaps => Ok(eager!(range_overlaps)),
+            BinaryFunc::RangeMerge => eager!(range_merge, temp_storage),
+            BinaryFunc::InetContains => Ok(eager!(inet_contains)),
+            BinaryFunc::InetContainsOrEquals => Ok(eager!(inet_contains_or_equals)),
+            BinaryFunc::InetSetMasklen => eager!(inet_set_masklen),
+            BinaryFunc::CidrSetMasklen => eager!(cidr_set_masklen),
             BinaryFunc::DigestString => eager!(digest_string, temp_storage),
             BinaryFunc::DigestBytes => eager!(digest_bytes, temp_storage),
             BinaryFunc::MzRenderTypemod => Ok(eager!(mz_render_typemod, temp_storage)),
             BinaryFunc::LogDecimal(scale) => eager!(log_base, *scale),
+            BinaryFunc::LogAPD => eager!(log_base_apd),
             BinaryFunc::Power => eager!(power),
             BinaryFunc::PowerDecimal(scale) => eager!(power_dec, *scale),
+            BinaryFunc::PowerAPD => eager!(power_apd),
             BinaryFunc::RepeatString => eager!(repeat_string, temp_storage),
         }
     }
@@ -2676,11 +3487,11 @@ impl BinaryFunc {
                 | DivDecimal
                 | DivAPD
                 | ModDecimal
+                | ModAPD
         );
         match self {
-            And | Or | Eq | NotEq | Lt | Lte | Gt | Gte | ArrayContains => {
-                ScalarType::Bool.nullable(in_nullable)
-            }
+            And | Or | Eq | NotEq | Lt | Lte | Gt | Gte | ArrayContains | ArrayContainsArray
+            | ArrayOverlaps => ScalarType::Bool.nullable(in_nullable),
 
             IsLikePatternMatch { .. } | IsRegexpMatch { .. } => {
                 // The output can be null if the pattern is invalid.
@@ -2713,10 +3524,12 @@ impl BinaryFunc {
             AddInterval | SubInterval | SubTimestamp | SubTimestampTz | MulInterval
             | DivInterval => ScalarType::Interval.nullable(in_nullable),
 
-            AddAPD | SubAPD | MulAPD | DivAPD => {
+            AddAPD | SubAPD | MulAPD | DivAPD | RoundAPD | LogAPD | PowerAPD => {
                 ScalarType::APD { scale: None }.nullable(in_nullable)
             }
 
+            ModAPD => ScalarType::APD { scale: None }.nullable(in_nullable || is_div_mod),
+
             // TODO(benesch): we correctly compute types for decimal scale, but
             // not decimal precision... because nothing actually cares about
             // decimal precision. Should either remove or fix.
@@ -2768,10 +3581,16 @@ impl BinaryFunc {
                 ScalarType::Timestamp.nullable(in_nullable)
             }
 
-            DatePartInterval | DatePartTimestamp | DatePartTimestampTz => {
+            DatePartInterval | DatePartTime | DatePartTimestamp | DatePartTimestampTz => {
                 ScalarType::Float64.nullable(true)
             }
 
+            ToDate => ScalarType::Date.nullable(in_nullable),
+
+            ToTimestampWithFormat(_) => ScalarType::TimestampTz.nullable(in_nullable),
+
+            AgeTimestamp => ScalarType::Interval.nullable(in_nullable),
+
             DateTruncTimestampTz => ScalarType::TimestampTz.nullable(true),
 
             TimezoneTimestamp | TimezoneIntervalTimestamp => {
@@ -2793,11 +3612,21 @@ impl BinaryFunc {
             | JsonbGetPath { stringify: false }
             | JsonbConcat
             | JsonbDeleteInt64
-            | JsonbDeleteString => ScalarType::Jsonb.nullable(true),
+            | JsonbDeleteString
+            | JsonbDeletePath => ScalarType::Jsonb.nullable(true),
 
             JsonbContainsString | JsonbContainsJsonb | MapContainsKey | MapContainsAllKeys
             | MapContainsAnyKeys | MapContainsMap => ScalarType::Bool.nullable(in_nullable),
 
+            RangeContainsElem | RangeContainsRange | RangeOverlaps => {
+                ScalarType::Bool.nullable(in_nullable)
+            }
+            RangeMerge => input1_type.scalar_type.nullable(in_nullable),
+
+            InetContains | InetContainsOrEquals => ScalarType::Bool.nullable(in_nullable),
+            InetSetMasklen => ScalarType::Inet.nullable(in_nullable),
+            CidrSetMasklen => ScalarType::Cidr.nullable(in_nullable),
+
             MapGetValue => input1_type
                 .scalar_type
                 .unwrap_map_value_type()
@@ -2826,6 +3655,10 @@ impl BinaryFunc {
             }
             ListListConcat | ListElementConcat => input1_type.scalar_type.nullable(true),
             ElementListConcat => input2_type.scalar_type.nullable(true),
+            ArrayArrayConcat | ArrayElementConcat | ArrayRemove => {
+                input1_type.scalar_type.nullable(true)
+            }
+            ElementArrayConcat => input2_type.scalar_type.nullable(true),
             DigestString | DigestBytes => ScalarType::Bytes.nullable(true),
             Position => ScalarType::Int32.nullable(in_nullable),
             Encode => ScalarType::String.nullable(in_nullable),
@@ -2846,6 +3679,10 @@ impl BinaryFunc {
                 | BinaryFunc::ListListConcat
                 | BinaryFunc::ListElementConcat
                 | BinaryFunc::ElementListConcat
+                | BinaryFunc::ArrayArrayConcat
+                | BinaryFunc::ArrayElementConcat
+                | BinaryFunc::ElementArrayConcat
+                | BinaryFunc::ArrayRemove
         )
     }
 
@@ -2910,6 +3747,7 @@ impl BinaryFunc {
                 | ModFloat32
                 | ModFloat64
                 | ModDecimal
+                | ModAPD
         )
     }
 
@@ -2964,6 +3802,7 @@ impl BinaryFunc {
             | ModFloat32
             | ModFloat64
             | ModDecimal
+            | ModAPD
             | Eq
             | NotEq
             | Lt
@@ -2978,6 +3817,7 @@ impl BinaryFunc {
             | JsonbContainsString
             | JsonbDeleteInt64
             | JsonbDeleteString
+            | JsonbDeletePath
             | MapContainsKey
             | MapGetValue
             | MapGetValues
@@ -2988,17 +3828,31 @@ impl BinaryFunc {
             | ListIndex
             | IsRegexpMatch { .. }
             | ArrayContains
+            | ArrayContainsArray
+            | ArrayOverlaps
             | ArrayIndex
             | ArrayLength
             | ArrayLower
             | ArrayUpper
+            | ArrayArrayConcat
+            | ArrayElementConcat
+            | ElementArrayConcat
             | ListListConcat
             | ListElementConcat
-            | ElementListConcat => true,
+            | ElementListConcat
+            | RangeContainsElem
+            | RangeContainsRange
+            | RangeOverlaps
+            | InetContains
+            | InetContainsOrEquals => true,
             IsLikePatternMatch { .. }
             | ToCharTimestamp
             | ToCharTimestampTz
+            | ToDate
+            | ToTimestampWithFormat(_)
+            | AgeTimestamp
             | DatePartInterval
+            | DatePartTime
             | DatePartTimestamp
             | DatePartTimestampTz
             | DateTruncTimestamp
@@ -3010,6 +3864,7 @@ impl BinaryFunc {
             | TimezoneIntervalTimestampTz
             | TimezoneIntervalTime
             | RoundDecimal(_)
+            | RoundAPD
             | ConvertFrom
             | Position
             | Right
@@ -3024,9 +3879,15 @@ impl BinaryFunc {
             | Encode
             | Decode
             | LogDecimal(_)
+            | LogAPD
             | Power
             | PowerDecimal(_)
-            | RepeatString => false,
+            | PowerAPD
+            | RepeatString
+            | RangeMerge
+            | ArrayRemove
+            | InetSetMasklen
+            | CidrSetMasklen => false,
         }
     }
 
@@ -3095,6 +3956,7 @@ impl fmt::Display for BinaryFunc {
             BinaryFunc::ModFloat32 => f.write_str("%"),
             BinaryFunc::ModFloat64 => f.write_str("%"),
             BinaryFunc::ModDecimal => f.write_str("%"),
+            BinaryFunc::ModAPD => f.write_str("%"),
             BinaryFunc::Eq => f.write_str("="),
             BinaryFunc::NotEq => f.write_str("!="),
             BinaryFunc::Lt => f.write_str("<"),
@@ -3115,7 +3977,11 @@ impl fmt::Display for BinaryFunc {
             } => f.write_str("~*"),
             BinaryFunc::ToCharTimestamp => f.write_str("tocharts"),
             BinaryFunc::ToCharTimestampTz => f.write_str("tochartstz"),
+            BinaryFunc::ToDate => f.write_str("todate"),
+            BinaryFunc::ToTimestampWithFormat(tz) => write!(f, "totimestamp_{}", tz),
+            BinaryFunc::AgeTimestamp => f.write_str("agets"),
             BinaryFunc::DatePartInterval => f.write_str("date_partiv"),
+            BinaryFunc::DatePartTime => f.write_str("date_partt"),
             BinaryFunc::DatePartTimestamp => f.write_str("date_partts"),
             BinaryFunc::DatePartTimestampTz => f.write_str("date_parttstz"),
             BinaryFunc::DateTruncTimestamp => f.write_str("date_truncts"),
@@ -3138,10 +4004,12 @@ impl fmt::Display for BinaryFunc {
             BinaryFunc::JsonbContainsJsonb | BinaryFunc::MapContainsMap => f.write_str("@>"),
             BinaryFunc::JsonbDeleteInt64 => f.write_str("-"),
             BinaryFunc::JsonbDeleteString => f.write_str("-"),
+            BinaryFunc::JsonbDeletePath => f.write_str("#-"),
             BinaryFunc::MapGetValue | BinaryFunc::MapGetValues => f.write_str("->"),
             BinaryFunc::MapContainsAllKeys => f.write_str("?&"),
             BinaryFunc::MapContainsAnyKeys => f.write_str("?|"),
             BinaryFunc::RoundDecimal(_) => f.write_str("round"),
+            BinaryFunc::RoundAPD => f.write_str("round"),
             BinaryFunc::ConvertFrom => f.write_str("convert_from"),
             BinaryFunc::Position => f.write_str("position"),
             BinaryFunc::Right => f.write_str("right"),
@@ -3152,20 +4020,34 @@ impl fmt::Display for BinaryFunc {
             BinaryFunc::ListIndex => f.write_str("list_index"),
             BinaryFunc::ListLengthMax { .. } => f.write_str("list_length_max"),
             BinaryFunc::ArrayContains => f.write_str("array_contains"),
+            BinaryFunc::ArrayContainsArray => f.write_str("@>"),
+            BinaryFunc::ArrayOverlaps => f.write_str("&&"),
             BinaryFunc::ArrayIndex => f.write_str("array_index"),
             BinaryFunc::ArrayLength => f.write_str("array_length"),
             BinaryFunc::ArrayLower => f.write_str("array_lower"),
             BinaryFunc::ArrayUpper => f.write_str("array_upper"),
+            BinaryFunc::ArrayArrayConcat => f.write_str("||"),
+            BinaryFunc::ArrayElementConcat => f.write_str("||"),
+            BinaryFunc::ElementArrayConcat => f.write_str("||"),
+            BinaryFunc::ArrayRemove => f.write_str("array_remove"),
             BinaryFunc::ListListConcat => f.write_str("||"),
             BinaryFunc::ListElementConcat => f.write_str("||"),
             BinaryFunc::ElementListConcat => f.write_str("||"),
+            BinaryFunc::RangeContainsElem | BinaryFunc::RangeContainsRange => f.write_str("@>"),
+            BinaryFunc::RangeOverlaps => f.write_str("&&"),
+            BinaryFunc::RangeMerge => f.write_str("range_merge"),
+            BinaryFunc::InetContains => f.write_str(">>"),
+            BinaryFunc::InetContainsOrEquals => f.write_str(">>="),
+            BinaryFunc::InetSetMasklen | BinaryFunc::CidrSetMasklen => f.write_str("set_masklen"),
             BinaryFunc::DigestString | BinaryFunc::DigestBytes => f.write_str("digest"),
             BinaryFunc::MzRenderTypemod => f.write_str("mz_render_typemod"),
             BinaryFunc::Encode => f.write_str("encode"),
             BinaryFunc::Decode => f.write_str("decode"),
             BinaryFunc::LogDecimal(_) => f.write_str("log"),
+            BinaryFunc::LogAPD => f.write_str("log"),
             BinaryFunc::Power => f.write_str("power"),
             BinaryFunc::PowerDecimal(_) => f.write_str("power_decimal"),
+            BinaryFunc::PowerAPD => f.write_str("power_apd"),
             BinaryFunc::RepeatString => f.write_str("repeat"),
         }
     }
@@ -3186,14 +4068,19 @@ pub enum UnaryFunc {
     NegDecimal,
     NegAPD,
     NegInterval,
+    JustifyDays,
+    JustifyHours,
+    JustifyInterval,
     SqrtFloat64,
     SqrtDec(u8),
+    SqrtAPD,
     CbrtFloat64,
     AbsInt32,
     AbsInt64,
     AbsFloat32,
     AbsFloat64,
     AbsDecimal,
+    AbsAPD,
     CastBoolToString,
     CastBoolToStringNonstandard,
     CastBoolToInt32,
@@ -3206,8 +4093,10 @@ pub enum UnaryFunc {
     CastOidToInt32,
     CastInt64ToInt32,
     CastInt32ToDecimal,
+    CastInt32ToAPD(Option<u8>),
     CastInt64ToBool,
     CastInt64ToDecimal,
+    CastInt64ToAPD(Option<u8>),
     CastInt64ToFloat32,
     CastInt64ToFloat64,
     CastInt64ToString,
@@ -3216,7 +4105,9 @@ pub enum UnaryFunc {
     CastFloat32ToFloat64,
     CastFloat32ToString,
     CastFloat32ToDecimal(u8),
+    CastFloat32ToAPD(Option<u8>),
     CastFloat64ToDecimal(u8),
+    CastFloat64ToAPD(Option<u8>),
     CastFloat64ToInt32,
     CastFloat64ToInt64,
     CastFloat64ToFloat32,
@@ -3224,7 +4115,18 @@ pub enum UnaryFunc {
     CastDecimalToInt32(u8),
     CastDecimalToInt64(u8),
     CastDecimalToString(u8),
+    CastDecimalToAPD {
+        // The scale of the input decimal.
+        from_scale: u8,
+        // The scale of the output APD, if any.
+        to_scale: Option<u8>,
+    },
     CastAPDToString,
+    CastAPDToInt32,
+    CastAPDToInt64,
+    CastAPDToFloat32,
+    CastAPDToFloat64,
+    CastAPDToDecimal(u8),
     CastSignificandToFloat32,
     CastSignificandToFloat64,
     CastStringToBool,
@@ -3255,12 +4157,33 @@ pub enum UnaryFunc {
         // type.
         cast_expr: Box<MirScalarExpr>,
     },
+    CastStringToRange {
+        // Target range's type.
+        return_ty: ScalarType,
+        // The expression to cast the discovered bounds to the range's element
+        // type.
+        cast_expr: Box<MirScalarExpr>,
+    },
     CastStringToTime,
     CastStringToTimestamp,
-    CastStringToTimestampTz,
+    CastStringToTimestampTz(Timezone),
     CastStringToInterval,
     CastStringToDecimal(u8),
     CastStringToAPD(Option<u8>),
+    CastStringToChar {
+        length: Option<usize>,
+        // Whether to error rather than truncate if the string is too long,
+        // as when assigning to a column.
+        fail_on_len: bool,
+    },
+    CastStringToVarChar {
+        max_length: Option<usize>,
+        // Whether to error rather than truncate if the string is too long,
+        // as when assigning to a column.
+        fail_on_len: bool,
+    },
+    CastCharToString,
+    CastVarCharToString,
     CastStringToUuid,
     CastDateToTimestamp,
     CastDateToTimestampTz,
@@ -3272,7 +4195,7 @@ pub enum UnaryFunc {
     CastTimestampToString,
     CastTimestampTzToDate,
     CastTimestampTzToTimestamp,
-    CastTimestampTzToString,
+    CastTimestampTzToString(Timezone),
     CastIntervalToString,
     CastIntervalToTime,
     CastBytesToString,
@@ -3284,8 +4207,14 @@ pub enum UnaryFunc {
     CastJsonbToFloat32,
     CastJsonbToFloat64,
     CastJsonbToDecimal(u8),
+    CastJsonbToAPD(Option<u8>),
     CastJsonbToBool,
     CastUuidToString,
+    CastStringToInet,
+    CastStringToCidr,
+    CastInetToString,
+    CastInetToCidr,
+    CastCidrToInet,
     CastRecordToString {
         ty: ScalarType,
     },
@@ -3304,15 +4233,20 @@ pub enum UnaryFunc {
     CastMapToString {
         ty: ScalarType,
     },
+    CastRangeToString {
+        ty: ScalarType,
+    },
     CastInPlace {
         return_ty: ScalarType,
     },
     CeilFloat32,
     CeilFloat64,
     CeilDecimal(u8),
+    CeilAPD,
     FloorFloat32,
     FloorFloat64,
     FloorDecimal(u8),
+    FloorAPD,
     Ascii,
     BitLengthBytes,
     BitLengthString,
@@ -3321,6 +4255,12 @@ pub enum UnaryFunc {
     CharLength,
     IsRegexpMatch(Regex),
     RegexpMatch(Regex),
+    RegexpReplace {
+        regex: Regex,
+        replacement: String,
+        global: bool,
+    },
+    RegexpSplitToArray(Regex),
     DatePartInterval(DateTimeUnits),
     DatePartTimestamp(DateTimeUnits),
     DatePartTimestampTz(DateTimeUnits),
@@ -3337,11 +4277,22 @@ pub enum UnaryFunc {
     RoundFloat32,
     RoundFloat64,
     RoundDecimal(u8),
+    RoundAPD,
     TrimWhitespace,
     TrimLeadingWhitespace,
     TrimTrailingWhitespace,
     RecordGet(usize),
     ListLength,
+    RangeLower,
+    RangeUpper,
+    RangeIsEmpty,
+    RangeLowerInc,
+    RangeUpperInc,
+    RangeLowerInf,
+    RangeUpperInf,
+    InetHost,
+    InetMasklen,
+    InetNetwork,
     Upper,
     Lower,
     Cos,
@@ -3353,10 +4304,13 @@ pub enum UnaryFunc {
     Cot,
     Log10,
     Log10Decimal(u8),
+    Log10APD,
     Ln,
     LnDecimal(u8),
+    LnAPD,
     Exp,
     ExpDecimal(u8),
+    ExpAPD,
     Sleep,
     RescaleAPD(u8),
 }
@@ -3383,27 +4337,35 @@ impl UnaryFunc {
             UnaryFunc::NegDecimal => Ok(neg_decimal(a)),
             UnaryFunc::NegAPD => Ok(neg_apd(a)),
             UnaryFunc::NegInterval => Ok(neg_interval(a)),
+            UnaryFunc::JustifyDays => justify_days(a),
+            UnaryFunc::JustifyHours => Ok(justify_hours(a)),
+            UnaryFunc::JustifyInterval => justify_interval(a),
             UnaryFunc::AbsInt32 => Ok(abs_int32(a)),
             UnaryFunc::AbsInt64 => Ok(abs_int64(a)),
             UnaryFunc::AbsFloat32 => Ok(abs_float32(a)),
             UnaryFunc::AbsFloat64 => Ok(abs_float64(a)),
             UnaryFunc::AbsDecimal => Ok(abs_decimal(a)),
+            UnaryFunc::AbsAPD => Ok(abs_apd(a)),
             UnaryFunc::CastBoolToString => Ok(cast_bool_to_string(a)),
             UnaryFunc::CastBoolToStringNonstandard => Ok(cast_bool_to_string_nonstandard(a)),
             UnaryFunc::CastBoolToInt32 => Ok(cast_bool_to_int32(a)),
             UnaryFunc::CastFloat32ToDecimal(scale) => cast_float32_to_decimal(a, *scale),
             UnaryFunc::CastFloat64ToDecimal(scale) => cast_float64_to_decimal(a, *scale),
+            UnaryFunc::CastFloat32ToAPD(scale) => cast_float32_to_apd(a, *scale),
+            UnaryFunc::CastFloat64ToAPD(scale) => cast_float64_to_apd(a, *scale),
             UnaryFunc::CastInt32ToBool => Ok(cast_int32_to_bool(a)),
             UnaryFunc::CastInt32ToFloat32 => Ok(cast_int32_to_float32(a)),
             UnaryFunc::CastInt32ToFloat64 => Ok(cast_int32_to_float64(a)),
             UnaryFunc::CastInt32ToInt64 => Ok(cast_int32_to_int64(a)),
             UnaryFunc::CastInt32ToOid => Ok(a),
             UnaryFunc::CastInt32ToDecimal => Ok(cast_int32_to_decimal(a)),
+            UnaryFunc::CastInt32ToAPD(scale) => cast_int32_to_apd(a, *scale),
             UnaryFunc::CastInt32ToString => Ok(cast_int32_to_string(a, temp_storage)),
             UnaryFunc::CastOidToInt32 => Ok(a),
             UnaryFunc::CastInt64ToInt32 => cast_int64_to_int32(a),
             UnaryFunc::CastInt64ToBool => Ok(cast_int64_to_bool(a)),
             UnaryFunc::CastInt64ToDecimal => Ok(cast_int64_to_decimal(a)),
+            UnaryFunc::CastInt64ToAPD(scale) => cast_int64_to_apd(a, *scale),
             UnaryFunc::CastInt64ToFloat32 => Ok(cast_int64_to_float32(a)),
             UnaryFunc::CastInt64ToFloat64 => Ok(cast_int64_to_float64(a)),
             UnaryFunc::CastInt64ToString => Ok(cast_int64_to_string(a, temp_storage)),
@@ -3427,6 +4389,16 @@ impl UnaryFunc {
             UnaryFunc::CastStringToFloat64 => cast_string_to_float64(a),
             UnaryFunc::CastStringToDecimal(scale) => cast_string_to_decimal(a, *scale),
             UnaryFunc::CastStringToAPD(scale) => cast_string_to_apd(a, *scale),
+            UnaryFunc::CastStringToChar {
+                length,
+                fail_on_len,
+            } => cast_string_to_char(a, *length, *fail_on_len, temp_storage),
+            UnaryFunc::CastStringToVarChar {
+                max_length,
+                fail_on_len,
+            } => cast_string_to_varchar(a, *max_length, *fail_on_len),
+            UnaryFunc::CastCharToString => Ok(cast_char_to_string(a)),
+            UnaryFunc::CastVarCharToString => Ok(a),
             UnaryFunc::CastStringToDate => cast_string_to_date(a),
             UnaryFunc::CastStringToArray { cast_expr, .. } => {
                 cast_string_to_array(a, cast_expr, temp_storage)
@@ -3439,9 +4411,12 @@ impl UnaryFunc {
                 cast_expr,
                 return_ty,
             } => cast_string_to_map(a, return_ty, cast_expr, temp_storage),
+            UnaryFunc::CastStringToRange { cast_expr, .. } => {
+                cast_string_to_range(a, cast_expr, temp_storage)
+            }
             UnaryFunc::CastStringToTime => cast_string_to_time(a),
             UnaryFunc::CastStringToTimestamp => cast_string_to_timestamp(a),
-            UnaryFunc::CastStringToTimestampTz => cast_string_to_timestamptz(a),
+            UnaryFunc::CastStringToTimestampTz(tz) => cast_string_to_timestamptz(a, *tz),
             UnaryFunc::CastStringToInterval => cast_string_to_interval(a),
             UnaryFunc::CastStringToUuid => cast_string_to_uuid(a),
             UnaryFunc::CastStringToJsonb => cast_string_to_jsonb(a, temp_storage),
@@ -3451,7 +4426,16 @@ impl UnaryFunc {
             UnaryFunc::CastDecimalToString(scale) => {
                 Ok(cast_decimal_to_string(a, *scale, temp_storage))
             }
+            UnaryFunc::CastDecimalToAPD {
+                from_scale,
+                to_scale,
+            } => cast_decimal_to_apd(a, *from_scale, *to_scale),
             UnaryFunc::CastAPDToString => Ok(cast_apd_to_string(a, temp_storage)),
+            UnaryFunc::CastAPDToInt32 => cast_apd_to_int32(a),
+            UnaryFunc::CastAPDToInt64 => cast_apd_to_int64(a),
+            UnaryFunc::CastAPDToFloat32 => cast_apd_to_float32(a),
+            UnaryFunc::CastAPDToFloat64 => Ok(cast_apd_to_float64(a)),
+            UnaryFunc::CastAPDToDecimal(scale) => cast_apd_to_decimal(a, *scale),
             UnaryFunc::CastTimeToInterval => cast_time_to_interval(a),
             UnaryFunc::CastTimeToString => Ok(cast_time_to_string(a, temp_storage)),
             UnaryFunc::CastTimestampToDate => Ok(cast_timestamp_to_date(a)),
@@ -3459,7 +4443,9 @@ impl UnaryFunc {
             UnaryFunc::CastTimestampToString => Ok(cast_timestamp_to_string(a, temp_storage)),
             UnaryFunc::CastTimestampTzToDate => Ok(cast_timestamptz_to_date(a)),
             UnaryFunc::CastTimestampTzToTimestamp => Ok(cast_timestamptz_to_timestamp(a)),
-            UnaryFunc::CastTimestampTzToString => Ok(cast_timestamptz_to_string(a, temp_storage)),
+            UnaryFunc::CastTimestampTzToString(tz) => {
+                Ok(cast_timestamptz_to_string(a, *tz, temp_storage))
+            }
             UnaryFunc::CastIntervalToString => Ok(cast_interval_to_string(a, temp_storage)),
             UnaryFunc::CastIntervalToTime => Ok(cast_interval_to_time(a)),
             UnaryFunc::CastBytesToString => Ok(cast_bytes_to_string(a, temp_storage)),
@@ -3470,12 +4456,19 @@ impl UnaryFunc {
             UnaryFunc::CastJsonbToFloat32 => cast_jsonb_to_float32(a),
             UnaryFunc::CastJsonbToFloat64 => cast_jsonb_to_float64(a),
             UnaryFunc::CastJsonbToDecimal(scale) => cast_jsonb_to_decimal(a, *scale),
+            UnaryFunc::CastJsonbToAPD(scale) => cast_jsonb_to_apd(a, *scale),
             UnaryFunc::CastJsonbToBool => cast_jsonb_to_bool(a),
             UnaryFunc::CastUuidToString => Ok(cast_uuid_to_string(a, temp_storage)),
+            UnaryFunc::CastStringToInet => cast_string_to_inet(a),
+            UnaryFunc::CastStringToCidr => cast_string_to_cidr(a),
+            UnaryFunc::CastInetToString => Ok(cast_inet_to_string(a, temp_storage)),
+            UnaryFunc::CastInetToCidr => Ok(inet_network(a)),
+            UnaryFunc::CastCidrToInet => Ok(a),
             UnaryFunc::CastRecordToString { ty }
             | UnaryFunc::CastArrayToString { ty }
             | UnaryFunc::CastListToString { ty }
-            | UnaryFunc::CastMapToString { ty } => {
+            | UnaryFunc::CastMapToString { ty }
+            | UnaryFunc::CastRangeToString { ty } => {
                 Ok(cast_collection_to_string(a, ty, temp_storage))
             }
             UnaryFunc::CastList1ToList2 { cast_expr, .. } => {
@@ -3485,11 +4478,14 @@ impl UnaryFunc {
             UnaryFunc::CeilFloat32 => Ok(ceil_float32(a)),
             UnaryFunc::CeilFloat64 => Ok(ceil_float64(a)),
             UnaryFunc::CeilDecimal(scale) => Ok(ceil_decimal(a, *scale)),
+            UnaryFunc::CeilAPD => Ok(ceil_apd(a)),
             UnaryFunc::FloorFloat32 => Ok(floor_float32(a)),
             UnaryFunc::FloorFloat64 => Ok(floor_float64(a)),
             UnaryFunc::FloorDecimal(scale) => Ok(floor_decimal(a, *scale)),
+            UnaryFunc::FloorAPD => Ok(floor_apd(a)),
             UnaryFunc::SqrtFloat64 => sqrt_float64(a),
             UnaryFunc::SqrtDec(scale) => sqrt_dec(a, *scale),
+            UnaryFunc::SqrtAPD => sqrt_apd(a),
             UnaryFunc::CbrtFloat64 => Ok(cbrt_float64(a)),
             UnaryFunc::Ascii => Ok(ascii(a)),
             UnaryFunc::BitLengthString => bit_length(a.unwrap_str()),
@@ -3499,6 +4495,20 @@ impl UnaryFunc {
             UnaryFunc::CharLength => char_length(a),
             UnaryFunc::IsRegexpMatch(regex) => Ok(is_regexp_match_static(a, &regex)),
             UnaryFunc::RegexpMatch(regex) => regexp_match_static(a, temp_storage, &regex),
+            UnaryFunc::RegexpReplace {
+                regex,
+                replacement,
+                global,
+            } => Ok(regexp_replace_static(
+                a,
+                temp_storage,
+                &regex,
+                replacement,
+                *global,
+            )),
+            UnaryFunc::RegexpSplitToArray(regex) => {
+                regexp_split_to_array_static(a, temp_storage, &regex)
+            }
             UnaryFunc::DatePartInterval(units) => {
                 date_part_interval_inner(*units, a.unwrap_interval())
             }
@@ -3525,11 +4535,22 @@ impl UnaryFunc {
             UnaryFunc::RoundFloat32 => Ok(round_float32(a)),
             UnaryFunc::RoundFloat64 => Ok(round_float64(a)),
             UnaryFunc::RoundDecimal(scale) => Ok(round_decimal_unary(a, *scale)),
+            UnaryFunc::RoundAPD => Ok(round_apd_unary(a)),
             UnaryFunc::TrimWhitespace => Ok(trim_whitespace(a)),
             UnaryFunc::TrimLeadingWhitespace => Ok(trim_leading_whitespace(a)),
             UnaryFunc::TrimTrailingWhitespace => Ok(trim_trailing_whitespace(a)),
             UnaryFunc::RecordGet(i) => Ok(record_get(a, *i)),
             UnaryFunc::ListLength => Ok(list_length(a)),
+            UnaryFunc::RangeLower => Ok(range_lower(a)),
+            UnaryFunc::RangeUpper => Ok(range_upper(a)),
+            UnaryFunc::RangeIsEmpty => Ok(range_is_empty(a)),
+            UnaryFunc::RangeLowerInc => Ok(range_lower_inc(a)),
+            UnaryFunc::RangeUpperInc => Ok(range_upper_inc(a)),
+            UnaryFunc::RangeLowerInf => Ok(range_lower_inf(a)),
+            UnaryFunc::RangeUpperInf => Ok(range_upper_inf(a)),
+            UnaryFunc::InetHost => Ok(inet_host(a, temp_storage)),
+            UnaryFunc::InetMasklen => Ok(inet_masklen(a)),
+            UnaryFunc::InetNetwork => Ok(inet_network(a)),
             UnaryFunc::Upper => Ok(upper(a, temp_storage)),
             UnaryFunc::Lower => Ok(lower(a, temp_storage)),
             UnaryFunc::Cos => cos(a),
@@ -3541,10 +4562,13 @@ impl UnaryFunc {
             UnaryFunc::Cot => cot(a),
             UnaryFunc::Log10 => log(a, f64::log10, "log10"),
             UnaryFunc::Log10Decimal(scale) => log_dec(a, f64::log10, "log10", *scale),
+            UnaryFunc::Log10APD => log10_apd(a),
             UnaryFunc::Ln => log(a, f64::ln, "ln"),
             UnaryFunc::LnDecimal(scale) => log_dec(a, f64::ln, "ln", *scale),
+            UnaryFunc::LnAPD => ln_apd(a),
             UnaryFunc::Exp => exp(a),
             UnaryFunc::ExpDecimal(scale) => exp_dec(a, *scale),
+            UnaryFunc::ExpAPD => exp_apd(a),
             UnaryFunc::Sleep => sleep(a),
             UnaryFunc::RescaleAPD(scale) => rescale_apd(a, *scale),
         }
@@ -3571,10 +4595,18 @@ impl UnaryFunc {
                 ScalarType::Decimal(MAX_DECIMAL_PRECISION, *scale).nullable(true)
             }
             CastStringToAPD(scale) => ScalarType::APD { scale: *scale }.nullable(true),
+            CastStringToChar { length, .. } => {
+                ScalarType::Char { length: *length }.nullable(in_nullable)
+            }
+            CastStringToVarChar { max_length, .. } => ScalarType::VarChar {
+                max_length: *max_length,
+            }
+            .nullable(in_nullable),
+            CastCharToString | CastVarCharToString => ScalarType::String.nullable(in_nullable),
             CastStringToDate => ScalarType::Date.nullable(true),
             CastStringToTime => ScalarType::Time.nullable(true),
             CastStringToTimestamp => ScalarType::Timestamp.nullable(true),
-            CastStringToTimestampTz => ScalarType::TimestampTz.nullable(true),
+            CastStringToTimestampTz(_) => ScalarType::TimestampTz.nullable(true),
             CastStringToInterval | CastTimeToInterval => ScalarType::Interval.nullable(true),
             CastStringToUuid => ScalarType::Uuid.nullable(true),
 
@@ -3591,13 +4623,14 @@ impl UnaryFunc {
             | CastDateToString
             | CastTimeToString
             | CastTimestampToString
-            | CastTimestampTzToString
+            | CastTimestampTzToString(_)
             | CastIntervalToString
             | CastBytesToString
             | CastRecordToString { .. }
             | CastArrayToString { .. }
             | CastListToString { .. }
             | CastMapToString { .. }
+            | CastRangeToString { .. }
             | TrimWhitespace
             | TrimLeadingWhitespace
             | TrimTrailingWhitespace
@@ -3629,6 +4662,22 @@ impl UnaryFunc {
             CastInt32ToDecimal => ScalarType::Decimal(0, 0).nullable(in_nullable),
             CastInt64ToDecimal => ScalarType::Decimal(0, 0).nullable(in_nullable),
 
+            CastInt32ToAPD(scale)
+            | CastInt64ToAPD(scale)
+            | CastFloat32ToAPD(scale)
+            | CastFloat64ToAPD(scale)
+            | CastDecimalToAPD {
+                to_scale: scale, ..
+            } => ScalarType::APD { scale: *scale }.nullable(in_nullable),
+
+            CastAPDToInt32 => ScalarType::Int32.nullable(in_nullable),
+            CastAPDToInt64 => ScalarType::Int64.nullable(in_nullable),
+            CastAPDToFloat32 => ScalarType::Float32.nullable(in_nullable),
+            CastAPDToFloat64 => ScalarType::Float64.nullable(in_nullable),
+            CastAPDToDecimal(scale) => {
+                ScalarType::Decimal(MAX_DECIMAL_PRECISION, *scale).nullable(in_nullable)
+            }
+
             CastInt32ToOid => ScalarType::Oid.nullable(in_nullable),
             CastOidToInt32 => ScalarType::Oid.nullable(in_nullable),
 
@@ -3659,14 +4708,22 @@ impl UnaryFunc {
             CastJsonbToDecimal(scale) => {
                 ScalarType::Decimal(MAX_DECIMAL_PRECISION, *scale).nullable(false)
             }
+            CastJsonbToAPD(scale) => ScalarType::APD { scale: *scale }.nullable(false),
             CastJsonbToBool => ScalarType::Bool.nullable(true),
 
             CastUuidToString => ScalarType::String.nullable(true),
 
+            CastStringToInet => ScalarType::Inet.nullable(in_nullable),
+            CastStringToCidr => ScalarType::Cidr.nullable(in_nullable),
+            CastInetToString => ScalarType::String.nullable(in_nullable),
+            CastInetToCidr => ScalarType::Cidr.nullable(in_nullable),
+            CastCidrToInet => ScalarType::Inet.nullable(in_nullable),
+
             CastList1ToList2 { return_ty, .. }
             | CastStringToArray { return_ty, .. }
             | CastStringToList { return_ty, .. }
             | CastStringToMap { return_ty, .. }
+            | CastStringToRange { return_ty, .. }
             | CastInPlace { return_ty } => (return_ty.clone()).nullable(false),
 
             CeilFloat32 | FloorFloat32 | RoundFloat32 => ScalarType::Float32.nullable(in_nullable),
@@ -3684,8 +4741,11 @@ impl UnaryFunc {
             CbrtFloat64 => ScalarType::Float64.nullable(true),
 
             Not | NegInt32 | NegInt64 | NegFloat32 | NegFloat64 | NegDecimal | NegAPD
-            | NegInterval | AbsInt32 | AbsInt64 | AbsFloat32 | AbsFloat64 | AbsDecimal => {
-                input_type
+            | NegInterval | AbsInt32 | AbsInt64 | AbsFloat32 | AbsFloat64 | AbsDecimal | AbsAPD
+            | CeilAPD | FloorAPD | RoundAPD => input_type,
+
+            JustifyDays | JustifyHours | JustifyInterval => {
+                ScalarType::Interval.nullable(in_nullable)
             }
 
             DatePartInterval(_) | DatePartTimestamp(_) | DatePartTimestampTz(_) => {
@@ -3709,7 +4769,25 @@ impl UnaryFunc {
 
             ListLength => ScalarType::Int64.nullable(true),
 
+            // The bounds of empty ranges and infinite bounds are null.
+            RangeLower | RangeUpper => input_type
+                .scalar_type
+                .unwrap_range_element_type()
+                .clone()
+                .nullable(true),
+            RangeIsEmpty | RangeLowerInc | RangeUpperInc | RangeLowerInf | RangeUpperInf => {
+                ScalarType::Bool.nullable(in_nullable)
+            }
+
+            InetHost => ScalarType::String.nullable(in_nullable),
+            InetMasklen => ScalarType::Int32.nullable(in_nullable),
+            InetNetwork => ScalarType::Cidr.nullable(in_nullable),
+
             RegexpMatch(_) => ScalarType::Array(Box::new(ScalarType::String)).nullable(true),
+            RegexpReplace { .. } => ScalarType::String.nullable(in_nullable),
+            RegexpSplitToArray(_) => {
+                ScalarType::Array(Box::new(ScalarType::String)).nullable(in_nullable)
+            }
 
             Cos => ScalarType::Float64.nullable(in_nullable),
             Cosh => ScalarType::Float64.nullable(in_nullable),
@@ -3720,6 +4798,9 @@ impl UnaryFunc {
             Cot => ScalarType::Float64.nullable(in_nullable),
             Log10 | Ln | Exp => ScalarType::Float64.nullable(in_nullable),
             Log10Decimal(_) | LnDecimal(_) | ExpDecimal(_) => input_type,
+            SqrtAPD | Log10APD | LnAPD | ExpAPD => {
+                ScalarType::APD { scale: None }.nullable(in_nullable)
+            }
             Sleep => ScalarType::TimestampTz.nullable(true),
             RescaleAPD(scale) => ScalarType::APD {
                 scale: Some(*scale),
@@ -3777,9 +4858,13 @@ impl fmt::Display for UnaryFunc {
             UnaryFunc::NegDecimal => f.write_str("-"),
             UnaryFunc::NegAPD => f.write_str("-"),
             UnaryFunc::NegInterval => f.write_str("-"),
+            UnaryFunc::JustifyDays => f.write_str("justify_days"),
+            UnaryFunc::JustifyHours => f.write_str("justify_hours"),
+            UnaryFunc::JustifyInterval => f.write_str("justify_interval"),
             UnaryFunc::AbsInt32 => f.write_str("abs"),
             UnaryFunc::AbsInt64 => f.write_str("abs"),
             UnaryFunc::AbsDecimal => f.write_str("abs"),
+            UnaryFunc::AbsAPD => f.write_str("abs"),
             UnaryFunc::AbsFloat32 => f.write_str("abs"),
             UnaryFunc::AbsFloat64 => f.write_str("abs"),
             UnaryFunc::CastBoolToString => f.write_str("booltostr"),
@@ -3792,10 +4877,12 @@ impl fmt::Display for UnaryFunc {
             UnaryFunc::CastInt32ToOid => f.write_str("i32tooid"),
             UnaryFunc::CastInt32ToString => f.write_str("i32tostr"),
             UnaryFunc::CastInt32ToDecimal => f.write_str("i32todec"),
+            UnaryFunc::CastInt32ToAPD(_) => f.write_str("i32toapd"),
             UnaryFunc::CastOidToInt32 => f.write_str("oidtoi32"),
             UnaryFunc::CastInt64ToInt32 => f.write_str("i64toi32"),
             UnaryFunc::CastInt64ToBool => f.write_str("i64tobool"),
             UnaryFunc::CastInt64ToDecimal => f.write_str("i64todec"),
+            UnaryFunc::CastInt64ToAPD(_) => f.write_str("i64toapd"),
             UnaryFunc::CastInt64ToFloat32 => f.write_str("i64tof32"),
             UnaryFunc::CastInt64ToFloat64 => f.write_str("i64tof64"),
             UnaryFunc::CastInt64ToString => f.write_str("i64tostr"),
@@ -3804,15 +4891,23 @@ impl fmt::Display for UnaryFunc {
             UnaryFunc::CastFloat32ToString => f.write_str("f32tostr"),
             UnaryFunc::CastFloat32ToInt32 => f.write_str("f32toi32"),
             UnaryFunc::CastFloat32ToDecimal(_) => f.write_str("f32todec"),
+            UnaryFunc::CastFloat32ToAPD(_) => f.write_str("f32toapd"),
             UnaryFunc::CastFloat64ToInt32 => f.write_str("f64toi32"),
             UnaryFunc::CastFloat64ToInt64 => f.write_str("f64toi64"),
             UnaryFunc::CastFloat64ToFloat32 => f.write_str("f64tof32"),
             UnaryFunc::CastFloat64ToString => f.write_str("f64tostr"),
             UnaryFunc::CastFloat64ToDecimal(_) => f.write_str("f64todec"),
+            UnaryFunc::CastFloat64ToAPD(_) => f.write_str("f64toapd"),
             UnaryFunc::CastDecimalToInt32(_) => f.write_str("dectoi32"),
             UnaryFunc::CastDecimalToInt64(_) => f.write_str("dectoi64"),
             UnaryFunc::CastDecimalToString(_) => f.write_str("dectostr"),
+            UnaryFunc::CastDecimalToAPD { .. } => f.write_str("dectoapd"),
             UnaryFunc::CastAPDToString => f.write_str("numerictostr"),
+            UnaryFunc::CastAPDToInt32 => f.write_str("apdtoi32"),
+            UnaryFunc::CastAPDToInt64 => f.write_str("apdtoi64"),
+            UnaryFunc::CastAPDToFloat32 => f.write_str("apdtof32"),
+            UnaryFunc::CastAPDToFloat64 => f.write_str("apdtof64"),
+            UnaryFunc::CastAPDToDecimal(_) => f.write_str("apdtodec"),
             UnaryFunc::CastSignificandToFloat32 => f.write_str("dectof32"),
             UnaryFunc::CastSignificandToFloat64 => f.write_str("dectof64"),
             UnaryFunc::CastStringToBool => f.write_str("strtobool"),
@@ -3823,13 +4918,19 @@ impl fmt::Display for UnaryFunc {
             UnaryFunc::CastStringToFloat64 => f.write_str("strtof64"),
             UnaryFunc::CastStringToDecimal(_) => f.write_str("strtodec"),
             UnaryFunc::CastStringToAPD(_) => f.write_str("strtoapd"),
+            UnaryFunc::CastStringToChar { .. } => f.write_str("strtochar"),
+            UnaryFunc::CastStringToVarChar { .. } => f.write_str("strtovarchar"),
+            UnaryFunc::CastCharToString => f.write_str("chartostr"),
+            UnaryFunc::CastVarCharToString => f.write_str("varchartostr"),
             UnaryFunc::CastStringToDate => f.write_str("strtodate"),
             UnaryFunc::CastStringToArray { .. } => f.write_str("strtoarray"),
             UnaryFunc::CastStringToList { .. } => f.write_str("strtolist"),
             UnaryFunc::CastStringToMap { .. } => f.write_str("strtomap"),
+            UnaryFunc::CastStringToRange { .. } => f.write_str("strtorange"),
             UnaryFunc::CastStringToTime => f.write_str("strtotime"),
             UnaryFunc::CastStringToTimestamp => f.write_str("strtots"),
-            UnaryFunc::CastStringToTimestampTz => f.write_str("strtotstz"),
+            UnaryFunc::CastStringToTimestampTz(tz) if tz.is_utc() => f.write_str("strtotstz"),
+            UnaryFunc::CastStringToTimestampTz(tz) => write!(f, "strtotstz_{}", tz),
             UnaryFunc::CastStringToInterval => f.write_str("strtoiv"),
             UnaryFunc::CastStringToUuid => f.write_str("strtouuid"),
             UnaryFunc::CastDateToTimestamp => f.write_str("datetots"),
@@ -3842,7 +4943,8 @@ impl fmt::Display for UnaryFunc {
             UnaryFunc::CastTimestampToString => f.write_str("tstostr"),
             UnaryFunc::CastTimestampTzToDate => f.write_str("tstodate"),
             UnaryFunc::CastTimestampTzToTimestamp => f.write_str("tstztots"),
-            UnaryFunc::CastTimestampTzToString => f.write_str("tstztostr"),
+            UnaryFunc::CastTimestampTzToString(tz) if tz.is_utc() => f.write_str("tstztostr"),
+            UnaryFunc::CastTimestampTzToString(tz) => write!(f, "tstztostr_{}", tz),
             UnaryFunc::CastIntervalToString => f.write_str("ivtostr"),
             UnaryFunc::CastIntervalToTime => f.write_str("ivtotime"),
             UnaryFunc::CastBytesToString => f.write_str("bytestostr"),
@@ -3855,21 +4957,31 @@ impl fmt::Display for UnaryFunc {
             UnaryFunc::CastJsonbToFloat64 => f.write_str("jsonbtof64"),
             UnaryFunc::CastJsonbToBool => f.write_str("jsonbtobool"),
             UnaryFunc::CastJsonbToDecimal(_) => f.write_str("jsonbtodec"),
+            UnaryFunc::CastJsonbToAPD(_) => f.write_str("jsonbtoapd"),
             UnaryFunc::CastUuidToString => f.write_str("uuidtostr"),
+            UnaryFunc::CastStringToInet => f.write_str("strtoinet"),
+            UnaryFunc::CastStringToCidr => f.write_str("strtocidr"),
+            UnaryFunc::CastInetToString => f.write_str("inettostr"),
+            UnaryFunc::CastInetToCidr => f.write_str("inettocidr"),
+            UnaryFunc::CastCidrToInet => f.write_str("cidrtoinet"),
             UnaryFunc::CastRecordToString { .. } => f.write_str("recordtostr"),
             UnaryFunc::CastArrayToString { .. } => f.write_str("arraytostr"),
             UnaryFunc::CastListToString { .. } => f.write_str("listtostr"),
             UnaryFunc::CastList1ToList2 { .. } => f.write_str("list1tolist2"),
             UnaryFunc::CastMapToString { .. } => f.write_str("maptostr"),
+            UnaryFunc::CastRangeToString { .. } => f.write_str("rangetostr"),
             UnaryFunc::CastInPlace { .. } => f.write_str("castinplace"),
             UnaryFunc::CeilFloat32 => f.write_str("ceilf32"),
             UnaryFunc::CeilFloat64 => f.write_str("ceilf64"),
             UnaryFunc::CeilDecimal(_) => f.write_str("ceildec"),
+            UnaryFunc::CeilAPD => f.write_str("ceilapd"),
             UnaryFunc::FloorFloat32 => f.write_str("floorf32"),
             UnaryFunc::FloorFloat64 => f.write_str("floorf64"),
             UnaryFunc::FloorDecimal(_) => f.write_str("floordec"),
+            UnaryFunc::FloorAPD => f.write_str("floorapd"),
             UnaryFunc::SqrtFloat64 => f.write_str("sqrtf64"),
             UnaryFunc::SqrtDec(_) => f.write_str("sqrtdec"),
+            UnaryFunc::SqrtAPD => f.write_str("sqrtapd"),
             UnaryFunc::CbrtFloat64 => f.write_str("cbrtf64"),
             UnaryFunc::Ascii => f.write_str("ascii"),
             UnaryFunc::CharLength => f.write_str("char_length"),
@@ -3879,6 +4991,20 @@ impl fmt::Display for UnaryFunc {
             UnaryFunc::ByteLengthString => f.write_str("byte_length"),
             UnaryFunc::IsRegexpMatch(regex) => write!(f, "{} ~", regex.as_str().quoted()),
             UnaryFunc::RegexpMatch(regex) => write!(f, "regexp_match[{}]", regex.as_str()),
+            UnaryFunc::RegexpReplace {
+                regex,
+                replacement,
+                global,
+            } => write!(
+                f,
+                "regexp_replace[{}, {}, global={}]",
+                regex.as_str(),
+                replacement,
+                global
+            ),
+            UnaryFunc::RegexpSplitToArray(regex) => {
+                write!(f, "regexp_split_to_array[{}]", regex.as_str())
+            }
             UnaryFunc::DatePartInterval(units) => write!(f, "date_part_{}_iv", units),
             UnaryFunc::DatePartTimestamp(units) => write!(f, "date_part_{}_ts", units),
             UnaryFunc::DatePartTimestampTz(units) => write!(f, "date_part_{}_tstz", units),
@@ -3895,11 +5021,22 @@ impl fmt::Display for UnaryFunc {
             UnaryFunc::RoundFloat32 => f.write_str("roundf32"),
             UnaryFunc::RoundFloat64 => f.write_str("roundf64"),
             UnaryFunc::RoundDecimal(_) => f.write_str("roundunary"),
+            UnaryFunc::RoundAPD => f.write_str("roundapd"),
             UnaryFunc::TrimWhitespace => f.write_str("btrim"),
             UnaryFunc::TrimLeadingWhitespace => f.write_str("ltrim"),
             UnaryFunc::TrimTrailingWhitespace => f.write_str("rtrim"),
             UnaryFunc::RecordGet(i) => write!(f, "record_get[{}]", i),
             UnaryFunc::ListLength => f.write_str("list_length"),
+            UnaryFunc::RangeLower => f.write_str("lower"),
+            UnaryFunc::RangeUpper => f.write_str("upper"),
+            UnaryFunc::RangeIsEmpty => f.write_str("isempty"),
+            UnaryFunc::RangeLowerInc => f.write_str("lower_inc"),
+            UnaryFunc::RangeUpperInc => f.write_str("upper_inc"),
+            UnaryFunc::RangeLowerInf => f.write_str("lower_inf"),
+            UnaryFunc::RangeUpperInf => f.write_str("upper_inf"),
+            UnaryFunc::InetHost => f.write_str("host"),
+            UnaryFunc::InetMasklen => f.write_str("masklen"),
+            UnaryFunc::InetNetwork => f.write_str("network"),
             UnaryFunc::Upper => f.write_str("upper"),
             UnaryFunc::Lower => f.write_str("lower"),
             UnaryFunc::Cos => f.write_str("cos"),
@@ -3911,9 +5048,12 @@ impl fmt::Display for UnaryFunc {
             UnaryFunc::Cot => f.write_str("cot"),
             UnaryFunc::Log10 => f.write_str("log10f64"),
             UnaryFunc::Log10Decimal(_) => f.write_str("log10dec"),
+            UnaryFunc::Log10APD => f.write_str("log10apd"),
             UnaryFunc::Ln => f.write_str("lnf64"),
             UnaryFunc::LnDecimal(_) => f.write_str("lndec"),
+            UnaryFunc::LnAPD => f.write_str("lnapd"),
             UnaryFunc::ExpDecimal(_) => f.write_str("expdec"),
+            UnaryFunc::ExpAPD => f.write_str("expapd"),
             UnaryFunc::Exp => f.write_str("expf64"),
             UnaryFunc::Sleep => f.write_str("mz_sleep"),
             UnaryFunc::RescaleAPD(..) => f.write_str("rescale_apd"),
@@ -4119,42 +5259,170 @@ fn regexp_match_static<'a>(
     needle: &regex::Regex,
 ) -> Result<Datum<'a>, EvalError> {
     let mut row = Row::default();
+    match needle.captures(haystack.unwrap_str()) {
+        None => row.push(Datum::Null),
+        Some(captures) => push_regexp_match_array(&mut row, needle, &captures)?,
+    }
+    Ok(temp_storage.push_unary_row(row))
+}
+
+/// Pushes the array that describes one match of `needle`, as returned by
+/// `regexp_match` and `regexp_matches`.
+pub(crate) fn push_regexp_match_array(
+    row: &mut Row,
+    needle: &regex::Regex,
+    captures: &regex::Captures,
+) -> Result<(), EvalError> {
     if needle.captures_len() > 1 {
-        // The regex contains capture groups, so return an array containing the
-        // matched text in each capture group, unless the entire match fails.
-        // Individual capture groups may also be null if that group did not
-        // participate in the match.
-        match needle.captures(haystack.unwrap_str()) {
-            None => row.push(Datum::Null),
-            Some(captures) => row.push_array(
-                &[ArrayDimension {
-                    lower_bound: 1,
-                    length: captures.len() - 1,
-                }],
-                // Skip the 0th capture group, which is the whole match.
-                captures.iter().skip(1).map(|mtch| match mtch {
-                    None => Datum::Null,
-                    Some(mtch) => Datum::String(mtch.as_str()),
-                }),
-            )?,
-        }
+        // The regex contains capture groups, so push an array containing the
+        // matched text in each capture group. Individual capture groups may be
+        // null if that group did not participate in the match.
+        row.push_array(
+            &[ArrayDimension {
+                lower_bound: 1,
+                length: captures.len() - 1,
+            }],
+            // Skip the 0th capture group, which is the whole match.
+            captures.iter().skip(1).map(|mtch| match mtch {
+                None => Datum::Null,
+                Some(mtch) => Datum::String(mtch.as_str()),
+            }),
+        )?;
     } else {
-        // The regex contains no capture groups, so return a one-element array
-        // containing the match, or null if there is no match.
-        match needle.find(haystack.unwrap_str()) {
-            None => row.push(Datum::Null),
-            Some(mtch) => row.push_array(
-                &[ArrayDimension {
-                    lower_bound: 1,
-                    length: 1,
-                }],
-                iter::once(Datum::String(mtch.as_str())),
-            )?,
-        };
+        // The regex contains no capture groups, so push a one-element array
+        // containing the match.
+        row.push_array(
+            &[ArrayDimension {
+                lower_bound: 1,
+                length: 1,
+            }],
+            iter::once(Datum::String(&captures[0])),
+        )?;
+    }
+    Ok(())
+}
+
+fn regexp_replace_dynamic<'a>(
+    datums: &[Datum<'a>],
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    let source = datums[0];
+    let needle = datums[1].unwrap_str();
+    let replacement = datums[2].unwrap_str();
+    let flags = match datums.get(3) {
+        Some(d) => d.unwrap_str(),
+        None => "",
+    };
+    let (needle, global) = build_regex_global(needle, flags)?;
+    Ok(regexp_replace_static(
+        source,
+        temp_storage,
+        &needle,
+        replacement,
+        global,
+    ))
+}
+
+fn regexp_replace_static<'a>(
+    source: Datum<'a>,
+    temp_storage: &'a RowArena,
+    needle: &regex::Regex,
+    replacement: &str,
+    global: bool,
+) -> Datum<'a> {
+    // A limit of zero replaces all matches.
+    let limit = if global { 0 } else { 1 };
+    let replaced = needle.replacen(source.unwrap_str(), limit, |captures: &regex::Captures| {
+        expand_regexp_replacement(captures, replacement)
+    });
+    match replaced {
+        Cow::Borrowed(s) => Datum::String(s),
+        Cow::Owned(s) => Datum::String(temp_storage.push_string(s)),
+    }
+}
+
+/// Expands a PostgreSQL-style replacement string for one match, in which `\1`
+/// through `\9` refer to the text matched by the corresponding capture group,
+/// `\&` refers to the whole match, and `\\` is a literal backslash. Any other
+/// backslash is copied verbatim.
+fn expand_regexp_replacement(captures: &regex::Captures, replacement: &str) -> String {
+    let mut out = String::with_capacity(replacement.len());
+    let mut rest = replacement;
+    while let Some(i) = rest.find('\\') {
+        out.push_str(&rest[..i]);
+        rest = &rest[i + 1..];
+        match rest.as_bytes().first() {
+            Some(b @ b'1'..=b'9') => {
+                if let Some(mtch) = captures.get(usize::from(b - b'0')) {
+                    out.push_str(mtch.as_str());
+                }
+                rest = &rest[1..];
+            }
+            Some(b'&') => {
+                out.push_str(&captures[0]);
+                rest = &rest[1..];
+            }
+            Some(b'\\') => {
+                out.push('\\');
+                rest = &rest[1..];
+            }
+            _ => out.push('\\'),
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+fn regexp_split_to_array_dynamic<'a>(
+    datums: &[Datum<'a>],
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    let haystack = datums[0];
+    let needle = datums[1].unwrap_str();
+    let flags = match datums.get(2) {
+        Some(d) => d.unwrap_str(),
+        None => "",
     };
+    let needle = build_regex(needle, flags)?;
+    regexp_split_to_array_static(haystack, temp_storage, &needle)
+}
+
+fn regexp_split_to_array_static<'a>(
+    haystack: Datum<'a>,
+    temp_storage: &'a RowArena,
+    needle: &regex::Regex,
+) -> Result<Datum<'a>, EvalError> {
+    let pieces = regexp_split(haystack.unwrap_str(), needle);
+    let mut row = Row::default();
+    row.push_array(
+        &[ArrayDimension {
+            lower_bound: 1,
+            length: pieces.len(),
+        }],
+        pieces.into_iter().map(Datum::String),
+    )?;
     Ok(temp_storage.push_unary_row(row))
 }
 
+/// Splits `haystack` at each match of `needle`, as `regexp_split_to_array`
+/// and `regexp_split_to_table` do.
+///
+/// Like PostgreSQL, zero-length matches at the start or end of `haystack`, or
+/// immediately after a previous match, are ignored.
+pub(crate) fn regexp_split<'a>(haystack: &'a str, needle: &regex::Regex) -> Vec<&'a str> {
+    let mut pieces = vec![];
+    let mut start = 0;
+    for mtch in needle.find_iter(haystack) {
+        if mtch.start() == mtch.end() && (mtch.start() == start || mtch.end() == haystack.len()) {
+            continue;
+        }
+        pieces.push(&haystack[start..mtch.start()]);
+        start = mtch.end();
+    }
+    pieces.push(&haystack[start..]);
+    pieces
+}
+
 pub fn build_regex(needle: &str, flags: &str) -> Result<regex::Regex, EvalError> {
     let mut regex = RegexBuilder::new(needle);
     for f in flags.chars() {
@@ -4171,6 +5439,15 @@ pub fn build_regex(needle: &str, flags: &str) -> Result<regex::Regex, EvalError>
     Ok(regex.build()?)
 }
 
+/// Like [`build_regex`], but additionally accepts the `g` flag, which requests
+/// that every match rather than only the first be processed. Returns whether
+/// the flag was present alongside the regex.
+pub fn build_regex_global(needle: &str, flags: &str) -> Result<(regex::Regex, bool), EvalError> {
+    let global = flags.contains('g');
+    let flags: String = flags.chars().filter(|f| *f != 'g').collect();
+    Ok((build_regex(needle, &flags)?, global))
+}
+
 pub fn hmac_string<'a>(
     datums: &[Datum<'a>],
     temp_storage: &'a RowArena,
@@ -4286,9 +5563,10 @@ fn jsonb_build_object<'a>(datums: &[Datum<'a>], temp_storage: &'a RowArena) -> D
 /// For example, if given three 1D arrays of length 2, this function will
 /// construct a 2D array with dimensions 3x2.
 ///
-/// The input datums in `datums` must all be arrays of the same dimensions.
-/// (The arrays must also be of the same element type, but that is checked by
-/// the SQL type system, rather than checked here at runtime.)
+/// The input datums in `datums` must all be arrays of the same dimensions, or
+/// an error is returned. (The arrays must also be of the same element type,
+/// but that is checked by the SQL type system, rather than checked here at
+/// runtime.)
 ///
 /// If all input arrays are zero-dimensional arrays, then the output is a zero-
 /// dimensional array. Otherwise the lower bound of the additional dimension is
@@ -4311,7 +5589,14 @@ fn array_create_multidim<'a>(
         length: datums.len(),
     }];
     if let Some(d) = datums.first() {
-        dims.extend(d.unwrap_array().dims());
+        let inner_dims = d.unwrap_array().dims();
+        if datums[1..]
+            .iter()
+            .any(|d| d.unwrap_array().dims() != inner_dims)
+        {
+            return Err(EvalError::MismatchedArrayDimensions);
+        }
+        dims.extend(inner_dims);
     };
     let elements = datums
         .iter()
@@ -4329,9 +5614,19 @@ fn array_create_multidim<'a>(
 fn array_create_scalar<'a>(
     datums: &[Datum<'a>],
     temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    array_create_scalar_with_lower_bound(datums, 1, temp_storage)
+}
+
+/// Like [`array_create_scalar`], but the lower bound of a non-empty array is
+/// `lower_bound`.
+fn array_create_scalar_with_lower_bound<'a>(
+    datums: &[Datum<'a>],
+    lower_bound: usize,
+    temp_storage: &'a RowArena,
 ) -> Result<Datum<'a>, EvalError> {
     let mut dims = &[ArrayDimension {
-        lower_bound: 1,
+        lower_bound,
         length: datums.len(),
     }][..];
     if datums.is_empty() {
@@ -4415,9 +5710,11 @@ where
         TimestampTz => strconv::format_timestamptz(buf, d.unwrap_timestamptz()),
         Interval => strconv::format_interval(buf, d.unwrap_interval()),
         Bytes => strconv::format_bytes(buf, d.unwrap_bytes()),
-        String => strconv::format_string(buf, d.unwrap_str()),
+        String | Char { .. } | VarChar { .. } => strconv::format_string(buf, d.unwrap_str()),
         Jsonb => strconv::format_jsonb(buf, JsonbRef::from_datum(d)),
         Uuid => strconv::format_uuid(buf, d.unwrap_uuid()),
+        Inet => strconv::format_inet(buf, d.unwrap_inet()),
+        Cidr => strconv::format_cidr(buf, d.unwrap_inet()),
         Record { fields, .. } => {
             let mut fields = fields.iter();
             strconv::format_record(buf, &d.unwrap_list(), |buf, d| {
@@ -4455,9 +5752,132 @@ where
                 stringify_datum(buf.nonnull_buffer(), d, value_type)
             }
         }),
+        Range { element_type } => {
+            let parts = d
+                .unwrap_range()
+                .bounds()
+                .map(|(lower, upper)| strconv::RangeParts {
+                    lower: lower.value,
+                    lower_inclusive: lower.inclusive,
+                    upper: upper.value,
+                    upper_inclusive: upper.inclusive,
+                });
+            strconv::format_range(buf, parts, |buf, d| stringify_datum(buf, d, element_type))
+        }
     }
 }
 
+fn array_slice<'a>(
+    datums: &[Datum<'a>],
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    assert_eq!(
+        datums.len() % 2,
+        1,
+        "expr::scalar::func::array_slice expects an odd number of arguments; 1 for array + 2 \
+        for each start-end pair"
+    );
+
+    let array = datums[0].unwrap_array();
+    let dims: Vec<ArrayDimension> = array.dims().into_iter().collect();
+    let ranges: Vec<(i64, i64)> = datums[1..]
+        .iter()
+        .tuples::<(_, _)>()
+        .map(|(start, end)| (start.unwrap_int64(), end.unwrap_int64()))
+        .collect();
+
+    // Per PostgreSQL, slicing more dimensions than the array has produces an
+    // empty array.
+    if dims.is_empty() || ranges.len() > dims.len() {
+        return array_create_scalar(&[], temp_storage);
+    }
+
+    // Clamp each range to the bounds of its dimension. Dimensions without a
+    // range are taken in their entirety.
+    let mut offsets = Vec::with_capacity(dims.len());
+    let mut new_dims = Vec::with_capacity(dims.len());
+    for (i, dim) in dims.iter().enumerate() {
+        let lower = dim.lower_bound as i64;
+        let upper = lower + dim.length as i64 - 1;
+        let (start, end) = match ranges.get(i) {
+            Some((start, end)) => (cmp::max(*start, lower), cmp::min(*end, upper)),
+            None => (lower, upper),
+        };
+        if start > end {
+            return array_create_scalar(&[], temp_storage);
+        }
+        offsets.push((start - lower) as usize);
+        new_dims.push(ArrayDimension {
+            lower_bound: 1,
+            length: (end - start + 1) as usize,
+        });
+    }
+
+    // Walk the selected region in row-major order.
+    let elements: Vec<Datum> = array.elements().iter().collect();
+    let mut strides = vec![1; dims.len()];
+    for i in (0..dims.len() - 1).rev() {
+        strides[i] = strides[i + 1] * dims[i + 1].length;
+    }
+    let mut indices = vec![0; new_dims.len()];
+    let mut sliced = Vec::new();
+    'outer: loop {
+        let pos: usize = indices
+            .iter()
+            .zip(&offsets)
+            .zip(&strides)
+            .map(|((i, offset), stride)| (i + offset) * stride)
+            .sum();
+        sliced.push(elements[pos]);
+        let mut d = new_dims.len();
+        loop {
+            if d == 0 {
+                break 'outer;
+            }
+            d -= 1;
+            indices[d] += 1;
+            if indices[d] < new_dims[d].length {
+                break;
+            }
+            indices[d] = 0;
+        }
+    }
+
+    let datum = temp_storage.try_make_datum(|packer| packer.push_array(&new_dims, sliced))?;
+    Ok(datum)
+}
+
+fn array_position<'a>(datums: &[Datum<'a>]) -> Result<Datum<'a>, EvalError> {
+    let array = match datums[0] {
+        Datum::Null => return Ok(Datum::Null),
+        d => d.unwrap_array(),
+    };
+    if array.dims().len() > 1 {
+        return Err(EvalError::MultidimensionalArraySearchNotSupported);
+    }
+    let skip = match datums.get(2) {
+        Some(Datum::Null) => {
+            return Err(EvalError::InvalidParameterValue(
+                "initial position must not be null".into(),
+            ))
+        }
+        Some(d) => cmp::max(d.unwrap_int32(), 1) as usize - 1,
+        None => 0,
+    };
+    // Like `array_remove`, this comparison treats NULLs as equal.
+    let search = datums[1];
+    let position = array
+        .elements()
+        .iter()
+        .enumerate()
+        .skip(skip)
+        .find(|(_, e)| *e == search);
+    Ok(match position {
+        Some((i, _)) => Datum::Int32(i as i32 + 1),
+        None => Datum::Null,
+    })
+}
+
 fn list_slice<'a>(datums: &[Datum<'a>], temp_storage: &'a RowArena) -> Datum<'a> {
     // Return value indicates whether this level's slices are empty results.
     fn slice_and_descend(d: Datum, ranges: &[(usize, usize)], row: &mut Row) -> bool {
@@ -4539,6 +5959,52 @@ fn list_length(a: Datum) -> Datum {
     Datum::Int64(a.unwrap_list().iter().count() as i64)
 }
 
+fn range_lower<'a>(a: Datum<'a>) -> Datum<'a> {
+    match a.unwrap_range().lower() {
+        Some(RangeBound {
+            value: Some(value), ..
+        }) => value,
+        _ => Datum::Null,
+    }
+}
+
+fn range_upper<'a>(a: Datum<'a>) -> Datum<'a> {
+    match a.unwrap_range().upper() {
+        Some(RangeBound {
+            value: Some(value), ..
+        }) => value,
+        _ => Datum::Null,
+    }
+}
+
+fn range_is_empty<'a>(a: Datum<'a>) -> Datum<'a> {
+    Datum::from(a.unwrap_range().is_empty())
+}
+
+fn range_lower_inc<'a>(a: Datum<'a>) -> Datum<'a> {
+    Datum::from(a.unwrap_range().lower().map_or(false, |b| b.inclusive))
+}
+
+fn range_upper_inc<'a>(a: Datum<'a>) -> Datum<'a> {
+    Datum::from(a.unwrap_range().upper().map_or(false, |b| b.inclusive))
+}
+
+fn range_lower_inf<'a>(a: Datum<'a>) -> Datum<'a> {
+    Datum::from(
+        a.unwrap_range()
+            .lower()
+            .map_or(false, |b| b.value.is_none()),
+    )
+}
+
+fn range_upper_inf<'a>(a: Datum<'a>) -> Datum<'a> {
+    Datum::from(
+        a.unwrap_range()
+            .upper()
+            .map_or(false, |b| b.value.is_none()),
+    )
+}
+
 fn upper<'a>(a: Datum<'a>, temp_storage: &'a RowArena) -> Datum<'a> {
     Datum::String(temp_storage.push_string(a.unwrap_str().to_owned().to_uppercase()))
 }
@@ -4547,6 +6013,49 @@ fn lower<'a>(a: Datum<'a>, temp_storage: &'a RowArena) -> Datum<'a> {
     Datum::String(temp_storage.push_string(a.unwrap_str().to_owned().to_lowercase()))
 }
 
+fn make_date<'a>(datums: &[Datum<'a>]) -> Result<Datum<'a>, EvalError> {
+    // As in PostgreSQL, negative years indicate years BC. There is no year
+    // zero.
+    let year = match datums[0].unwrap_int32() {
+        0 => return Err(EvalError::DateTimeFieldOutOfRange),
+        year if year < 0 => year + 1,
+        year => year,
+    };
+    let month = u32::try_from(datums[1].unwrap_int32());
+    let day = u32::try_from(datums[2].unwrap_int32());
+    match (month, day) {
+        (Ok(month), Ok(day)) => NaiveDate::from_ymd_opt(year, month, day)
+            .map(Datum::Date)
+            .ok_or(EvalError::DateTimeFieldOutOfRange),
+        _ => Err(EvalError::DateTimeFieldOutOfRange),
+    }
+}
+
+fn make_interval<'a>(datums: &[Datum<'a>]) -> Result<Datum<'a>, EvalError> {
+    // Trailing arguments may be omitted, in which case they default to zero.
+    let int = |i: usize| datums.get(i).map_or(0, |d| i64::from(d.unwrap_int32()));
+    let years = int(0);
+    let months = int(1);
+    let weeks = int(2);
+    let days = int(3);
+    let hours = int(4);
+    let minutes = int(5);
+    let seconds = datums.get(6).map_or(0.0, |d| d.unwrap_float64());
+    if !seconds.is_finite() || seconds.abs() >= i64::MAX as f64 {
+        return Err(EvalError::IntervalOutOfRange);
+    }
+    let months = i32::try_from(years * 12 + months).map_err(|_| EvalError::IntervalOutOfRange)?;
+    let whole_seconds = seconds.trunc() as i64;
+    let nanos = ((seconds - seconds.trunc()) * 1e9).round() as i64;
+    let seconds = (weeks * 7 + days) * 86_400 + hours * 3_600 + minutes * 60;
+    let seconds = seconds
+        .checked_add(whole_seconds)
+        .ok_or(EvalError::IntervalOutOfRange)?;
+    Interval::new(months, seconds, nanos)
+        .map(Datum::Interval)
+        .map_err(|_| EvalError::IntervalOutOfRange)
+}
+
 fn make_timestamp<'a>(datums: &[Datum<'a>]) -> Datum<'a> {
     let year: i32 = match datums[0].unwrap_int64().try_into() {
         Ok(year) => year,
@@ -4685,14 +6194,19 @@ fn array_length<'a>(a: Datum<'a>, b: Datum<'a>) -> Datum<'a> {
 }
 
 fn array_index<'a>(a: Datum<'a>, b: Datum<'a>) -> Datum<'a> {
-    let i = b.unwrap_int64();
-    if i < 1 {
-        return Datum::Null;
-    }
-    a.unwrap_array()
+    let array = a.unwrap_array();
+    let lower_bound = match array.dims().into_iter().next() {
+        Some(dim) => dim.lower_bound as i64,
+        None => return Datum::Null,
+    };
+    let i = match b.unwrap_int64().checked_sub(lower_bound) {
+        Some(i) if i >= 0 => i,
+        _ => return Datum::Null,
+    };
+    array
         .elements()
         .iter()
-        .nth(i as usize - 1)
+        .nth(i as usize)
         .unwrap_or(Datum::Null)
 }
 
@@ -4702,7 +6216,7 @@ fn array_lower<'a>(a: Datum<'a>, b: Datum<'a>) -> Datum<'a> {
         return Datum::Null;
     }
     match a.unwrap_array().dims().into_iter().nth(i as usize - 1) {
-        Some(_) => Datum::Int64(1),
+        Some(dim) => Datum::Int64(dim.lower_bound as i64),
         None => Datum::Null,
     }
 }
@@ -4713,7 +6227,7 @@ fn array_upper<'a>(a: Datum<'a>, b: Datum<'a>) -> Datum<'a> {
         return Datum::Null;
     }
     match a.unwrap_array().dims().into_iter().nth(i as usize - 1) {
-        Some(dim) => Datum::Int64(dim.length as i64),
+        Some(dim) => Datum::Int64((dim.lower_bound + dim.length - 1) as i64),
         None => Datum::Null,
     }
 }
@@ -4756,6 +6270,141 @@ fn array_contains<'a>(a: Datum<'a>, b: Datum<'a>) -> Datum<'a> {
     Datum::from(array.elements().iter().any(|e| e == a))
 }
 
+fn array_contains_array<'a>(a: Datum<'a>, b: Datum<'a>) -> Datum<'a> {
+    let a: BTreeSet<_> = a.unwrap_array().elements().iter().collect();
+    let b = b.unwrap_array().elements();
+    // Per PostgreSQL, NULL elements never compare equal, so an array with a
+    // NULL element is not contained in any array.
+    Datum::from(b.iter().all(|e| !e.is_null() && a.contains(&e)))
+}
+
+fn array_overlaps<'a>(a: Datum<'a>, b: Datum<'a>) -> Datum<'a> {
+    let a: BTreeSet<_> = a.unwrap_array().elements().iter().collect();
+    let b = b.unwrap_array().elements();
+    Datum::from(b.iter().any(|e| !e.is_null() && a.contains(&e)))
+}
+
+fn array_array_concat<'a>(
+    a: Datum<'a>,
+    b: Datum<'a>,
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    if a.is_null() {
+        return Ok(b);
+    } else if b.is_null() {
+        return Ok(a);
+    }
+
+    let a_array = a.unwrap_array();
+    let b_array = b.unwrap_array();
+    let a_dims: Vec<ArrayDimension> = a_array.dims().into_iter().collect();
+    let b_dims: Vec<ArrayDimension> = b_array.dims().into_iter().collect();
+
+    // Per PostgreSQL, concatenating with an empty array returns the other
+    // array unchanged.
+    if a_dims.is_empty() {
+        return Ok(b);
+    } else if b_dims.is_empty() {
+        return Ok(a);
+    }
+
+    // Arrays of the same number of dimensions are concatenated along their
+    // outermost dimension. An array of N dimensions can also be concatenated
+    // with an array of N-1 dimensions, in which case the smaller array becomes
+    // a new element of the outermost dimension of the larger array.
+    // As in PostgreSQL, the lower bound of the outermost dimension is that of
+    // the array with more dimensions, or of the left array if the arrays have
+    // the same number of dimensions.
+    let (lower_bound, outer, inner) = match a_dims.len().cmp(&b_dims.len()) {
+        Ordering::Equal if a_dims[1..] == b_dims[1..] => (
+            a_dims[0].lower_bound,
+            a_dims[0].length + b_dims[0].length,
+            &a_dims[1..],
+        ),
+        Ordering::Less if a_dims[..] == b_dims[1..] => {
+            (b_dims[0].lower_bound, b_dims[0].length + 1, &b_dims[1..])
+        }
+        Ordering::Greater if a_dims[1..] == b_dims[..] => {
+            (a_dims[0].lower_bound, a_dims[0].length + 1, &a_dims[1..])
+        }
+        _ if (a_dims.len() as isize - b_dims.len() as isize).abs() > 1 => {
+            return Err(EvalError::IncompatibleArrayDimensions {
+                dims: Some((a_dims.len(), b_dims.len())),
+            })
+        }
+        _ => return Err(EvalError::IncompatibleArrayDimensions { dims: None }),
+    };
+
+    let mut dims = vec![ArrayDimension {
+        lower_bound,
+        length: outer,
+    }];
+    dims.extend(inner);
+    let elements = a_array.elements().iter().chain(b_array.elements().iter());
+    let datum = temp_storage.try_make_datum(move |packer| packer.push_array(&dims, elements))?;
+    Ok(datum)
+}
+
+/// Returns the lower bound and the elements of the possibly-NULL array `d`,
+/// which must be empty or one-dimensional. The lower bound of an empty array
+/// is one.
+fn one_dimensional_array_elements(d: Datum) -> Result<(usize, Vec<Datum>), EvalError> {
+    if d.is_null() {
+        return Ok((1, vec![]));
+    }
+    let array = d.unwrap_array();
+    let dims: Vec<ArrayDimension> = array.dims().into_iter().collect();
+    match dims.as_slice() {
+        [] => Ok((1, vec![])),
+        [dim] => Ok((dim.lower_bound, array.elements().iter().collect())),
+        _ => Err(EvalError::NotOneDimensionalArray),
+    }
+}
+
+fn array_element_concat<'a>(
+    a: Datum<'a>,
+    b: Datum<'a>,
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    let (lower_bound, mut elements) = one_dimensional_array_elements(a)?;
+    elements.push(b);
+    array_create_scalar_with_lower_bound(&elements, lower_bound, temp_storage)
+}
+
+fn element_array_concat<'a>(
+    a: Datum<'a>,
+    b: Datum<'a>,
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    // Per PostgreSQL, prepending an element keeps the array's lower bound.
+    let (lower_bound, elements) = one_dimensional_array_elements(b)?;
+    let elements: Vec<_> = iter::once(a).chain(elements).collect();
+    array_create_scalar_with_lower_bound(&elements, lower_bound, temp_storage)
+}
+
+fn array_remove<'a>(
+    a: Datum<'a>,
+    b: Datum<'a>,
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    if a.is_null() {
+        return Ok(a);
+    }
+    let array = a.unwrap_array();
+    if array.dims().len() > 1 {
+        return Err(EvalError::MultidimensionalArrayRemovalNotSupported);
+    }
+    let lower_bound = array
+        .dims()
+        .into_iter()
+        .next()
+        .map_or(1, |dim| dim.lower_bound);
+    // Unlike most comparisons, this one treats NULLs as equal, so that
+    // `array_remove(a, NULL)` removes all NULL elements.
+    let elements: Vec<_> = array.elements().iter().filter(|e| *e != b).collect();
+    array_create_scalar_with_lower_bound(&elements, lower_bound, temp_storage)
+}
+
 fn list_list_concat<'a>(a: Datum<'a>, b: Datum<'a>, temp_storage: &'a RowArena) -> Datum<'a> {
     if a.is_null() {
         return b;
@@ -4795,6 +6444,115 @@ fn element_list_concat<'a>(a: Datum<'a>, b: Datum<'a>, temp_storage: &'a RowAren
     })
 }
 
+fn range_contains_elem<'a>(a: Datum<'a>, b: Datum<'a>) -> Datum<'a> {
+    Datum::from(a.unwrap_range().contains_elem(b))
+}
+
+fn range_contains_range<'a>(a: Datum<'a>, b: Datum<'a>) -> Datum<'a> {
+    Datum::from(a.unwrap_range().contains_range(&b.unwrap_range()))
+}
+
+fn range_overlaps<'a>(a: Datum<'a>, b: Datum<'a>) -> Datum<'a> {
+    Datum::from(a.unwrap_range().overlaps(&b.unwrap_range()))
+}
+
+fn range_merge<'a>(
+    a: Datum<'a>,
+    b: Datum<'a>,
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    let bounds = a.unwrap_range().merge_bounds(&b.unwrap_range());
+    range_from_bounds(bounds, temp_storage)
+}
+
+fn inet_host<'a>(a: Datum<'a>, temp_storage: &'a RowArena) -> Datum<'a> {
+    Datum::String(temp_storage.push_string(a.unwrap_inet().addr().to_string()))
+}
+
+fn inet_masklen<'a>(a: Datum<'a>) -> Datum<'a> {
+    Datum::Int32(i32::from(a.unwrap_inet().prefix_len()))
+}
+
+fn inet_network<'a>(a: Datum<'a>) -> Datum<'a> {
+    Datum::Inet(a.unwrap_inet().network())
+}
+
+fn inet_contains<'a>(a: Datum<'a>, b: Datum<'a>) -> Datum<'a> {
+    Datum::from(a.unwrap_inet().contains(&b.unwrap_inet()))
+}
+
+fn inet_contains_or_equals<'a>(a: Datum<'a>, b: Datum<'a>) -> Datum<'a> {
+    Datum::from(a.unwrap_inet().contains_or_equals(&b.unwrap_inet()))
+}
+
+fn inet_set_masklen<'a>(a: Datum<'a>, b: Datum<'a>) -> Result<Datum<'a>, EvalError> {
+    let inet = a.unwrap_inet();
+    let len = b.unwrap_int32();
+    // As in PostgreSQL, a length of -1 requests the maximum length for the
+    // address's family.
+    let prefix_len = match len {
+        -1 => Ok(inet.max_prefix_len()),
+        _ => u8::try_from(len),
+    };
+    match prefix_len.ok().and_then(|l| inet.with_prefix_len(l).ok()) {
+        Some(inet) => Ok(Datum::Inet(inet)),
+        None => Err(EvalError::InvalidParameterValue(format!(
+            "invalid mask length: {}",
+            len
+        ))),
+    }
+}
+
+fn cidr_set_masklen<'a>(a: Datum<'a>, b: Datum<'a>) -> Result<Datum<'a>, EvalError> {
+    Ok(inet_network(inet_set_masklen(a, b)?))
+}
+
+// A null bound indicates that the bound is infinite.
+fn range_create<'a>(
+    datums: &[Datum<'a>],
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    let (lower_inclusive, upper_inclusive) = match datums.get(2) {
+        None => (true, false),
+        Some(Datum::Null) => {
+            return Err(EvalError::InvalidParameterValue(
+                "range constructor flags argument must not be null".into(),
+            ))
+        }
+        Some(flags) => match flags.unwrap_str() {
+            "[)" => (true, false),
+            "[]" => (true, true),
+            "(]" => (false, true),
+            "()" => (false, false),
+            _ => return Err(InvalidRangeError::InvalidFlags.into()),
+        },
+    };
+    let bound = |d: Datum<'a>, inclusive| match d {
+        Datum::Null => RangeBound::infinite(),
+        d => RangeBound::new(d, inclusive),
+    };
+    let bounds = Some((
+        bound(datums[0], lower_inclusive),
+        bound(datums[1], upper_inclusive),
+    ));
+    range_from_bounds(bounds, temp_storage)
+}
+
+/// Packs a range with the specified bounds, or an empty range if `bounds` is
+/// `None`.
+fn range_from_bounds<'a>(
+    bounds: Option<(RangeBound<'a>, RangeBound<'a>)>,
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    temp_storage.try_make_datum(|packer| match bounds {
+        Some((lower, upper)) => packer.push_range(lower, upper).err_into(),
+        None => {
+            packer.push_empty_range();
+            Ok(())
+        }
+    })
+}
+
 fn digest_string<'a>(
     a: Datum<'a>,
     b: Datum<'a>,
@@ -4839,7 +6597,10 @@ fn mz_render_typemod<'a>(
     let mut typmod = typmod.unwrap_int32();
     let typmod_base = 65_536;
 
-    let inner = if matches!(Type::from_oid(oid as u32), Some(Type::Numeric)) && typmod >= 0 {
+    let typ = Type::from_oid(oid as u32);
+    let inner = if matches!(typ, Some(Type::BpChar) | Some(Type::VarChar)) && typmod >= 0 {
+        temp_storage.push_string(format!("({})", typmod - 4))
+    } else if matches!(typ, Some(Type::Numeric)) && typmod >= 0 {
         typmod -= 4;
         if typmod < 0 {
             temp_storage.push_string(format!("({},{})", 65_535, typmod_base + typmod))
@@ -4861,6 +6622,8 @@ fn mz_render_typemod<'a>(
 pub enum VariadicFunc {
     Coalesce,
     Concat,
+    MakeDate,
+    MakeInterval,
     MakeTimestamp,
     PadLeading,
     Substr,
@@ -4881,9 +6644,22 @@ pub enum VariadicFunc {
     RecordCreate {
         field_names: Vec<ColumnName>,
     },
+    RangeCreate {
+        elem_type: ScalarType,
+    },
     ListSlice,
+    ArraySlice,
+    ArrayPosition,
     SplitPart,
     RegexpMatch,
+    RegexpReplace,
+    RegexpSplitToArray,
+    JsonbSet,
+    JsonbInsert,
+    JsonbPathExists,
+    /// Like `JsonbPathExists`, but with a path that has been parsed in advance.
+    /// The path argument is omitted from the function's arguments.
+    JsonbPathExistsStatic(JsonPath),
     HmacString,
     HmacBytes,
 }
@@ -4910,6 +6686,8 @@ impl VariadicFunc {
         match self {
             VariadicFunc::Coalesce => coalesce(datums, temp_storage, exprs),
             VariadicFunc::Concat => Ok(eager!(text_concat_variadic, temp_storage)),
+            VariadicFunc::MakeDate => eager!(make_date),
+            VariadicFunc::MakeInterval => eager!(make_interval),
             VariadicFunc::MakeTimestamp => Ok(eager!(make_timestamp)),
             VariadicFunc::PadLeading => eager!(pad_leading, temp_storage),
             VariadicFunc::Substr => eager!(substr),
@@ -4926,9 +6704,20 @@ impl VariadicFunc {
             VariadicFunc::ListCreate { .. } | VariadicFunc::RecordCreate { .. } => {
                 Ok(eager!(list_create, temp_storage))
             }
+            VariadicFunc::RangeCreate { .. } => eager!(range_create, temp_storage),
             VariadicFunc::ListSlice => Ok(eager!(list_slice, temp_storage)),
+            VariadicFunc::ArraySlice => eager!(array_slice, temp_storage),
+            VariadicFunc::ArrayPosition => eager!(array_position),
             VariadicFunc::SplitPart => eager!(split_part),
             VariadicFunc::RegexpMatch => eager!(regexp_match_dynamic, temp_storage),
+            VariadicFunc::RegexpReplace => eager!(regexp_replace_dynamic, temp_storage),
+            VariadicFunc::RegexpSplitToArray => {
+                eager!(regexp_split_to_array_dynamic, temp_storage)
+            }
+            VariadicFunc::JsonbSet => eager!(jsonb_set, temp_storage),
+            VariadicFunc::JsonbInsert => eager!(jsonb_insert, temp_storage),
+            VariadicFunc::JsonbPathExists => eager!(jsonb_path_exists_dynamic),
+            VariadicFunc::JsonbPathExistsStatic(path) => eager!(jsonb_path_exists_static, path),
             VariadicFunc::HmacString => eager!(hmac_string, temp_storage),
             VariadicFunc::HmacBytes => eager!(hmac_bytes, temp_storage),
         }
@@ -4949,11 +6738,15 @@ impl VariadicFunc {
                 input_types.into_first().nullable(true)
             }
             Concat => ScalarType::String.nullable(true),
+            MakeDate => ScalarType::Date.nullable(true),
+            MakeInterval => ScalarType::Interval.nullable(true),
             MakeTimestamp => ScalarType::Timestamp.nullable(true),
             PadLeading => ScalarType::String.nullable(true),
             Substr => ScalarType::String.nullable(true),
             Replace => ScalarType::String.nullable(true),
             JsonbBuildArray | JsonbBuildObject => ScalarType::Jsonb.nullable(true),
+            JsonbSet | JsonbInsert => ScalarType::Jsonb.nullable(true),
+            JsonbPathExists | JsonbPathExistsStatic(_) => ScalarType::Bool.nullable(true),
             ArrayCreate { elem_type } => {
                 debug_assert!(
                     input_types.iter().all(|t| t.scalar_type == *elem_type),
@@ -4977,6 +6770,8 @@ impl VariadicFunc {
                 .nullable(false)
             }
             ListSlice { .. } => input_types[0].scalar_type.clone().nullable(true),
+            ArraySlice => input_types[0].scalar_type.clone().nullable(true),
+            ArrayPosition => ScalarType::Int32.nullable(true),
             RecordCreate { field_names } => ScalarType::Record {
                 fields: field_names
                     .clone()
@@ -4987,8 +6782,14 @@ impl VariadicFunc {
                 custom_name: None,
             }
             .nullable(true),
+            RangeCreate { elem_type } => ScalarType::Range {
+                element_type: Box::new(elem_type.clone()),
+            }
+            .nullable(false),
             SplitPart => ScalarType::String.nullable(true),
             RegexpMatch => ScalarType::Array(Box::new(ScalarType::String)).nullable(true),
+            RegexpReplace => ScalarType::String.nullable(true),
+            RegexpSplitToArray => ScalarType::Array(Box::new(ScalarType::String)).nullable(true),
             HmacString | HmacBytes => ScalarType::Bytes.nullable(true),
         }
     }
@@ -5005,6 +6806,8 @@ impl VariadicFunc {
                 | VariadicFunc::RecordCreate { .. }
                 | VariadicFunc::ArrayCreate { .. }
                 | VariadicFunc::ArrayToString { .. }
+                | VariadicFunc::ArrayPosition
+                | VariadicFunc::RangeCreate { .. }
         )
     }
 }
@@ -5014,6 +6817,8 @@ impl fmt::Display for VariadicFunc {
         match self {
             VariadicFunc::Coalesce => f.write_str("coalesce"),
             VariadicFunc::Concat => f.write_str("concat"),
+            VariadicFunc::MakeDate => f.write_str("makedate"),
+            VariadicFunc::MakeInterval => f.write_str("makeinterval"),
             VariadicFunc::MakeTimestamp => f.write_str("makets"),
             VariadicFunc::PadLeading => f.write_str("lpad"),
             VariadicFunc::Substr => f.write_str("substr"),
@@ -5024,9 +6829,20 @@ impl fmt::Display for VariadicFunc {
             VariadicFunc::ArrayToString { .. } => f.write_str("array_to_string"),
             VariadicFunc::ListCreate { .. } => f.write_str("list_create"),
             VariadicFunc::RecordCreate { .. } => f.write_str("record_create"),
+            VariadicFunc::RangeCreate { .. } => f.write_str("range_create"),
             VariadicFunc::ListSlice => f.write_str("list_slice"),
+            VariadicFunc::ArraySlice => f.write_str("array_slice"),
+            VariadicFunc::ArrayPosition => f.write_str("array_position"),
             VariadicFunc::SplitPart => f.write_str("split_string"),
             VariadicFunc::RegexpMatch => f.write_str("regexp_match"),
+            VariadicFunc::RegexpReplace => f.write_str("regexp_replace"),
+            VariadicFunc::RegexpSplitToArray => f.write_str("regexp_split_to_array"),
+            VariadicFunc::JsonbSet => f.write_str("jsonb_set"),
+            VariadicFunc::JsonbInsert => f.write_str("jsonb_insert"),
+            VariadicFunc::JsonbPathExists => f.write_str("jsonb_path_exists"),
+            VariadicFunc::JsonbPathExistsStatic(path) => {
+                write!(f, "jsonb_path_exists[{}]", path.as_str())
+            }
             VariadicFunc::HmacString | VariadicFunc::HmacBytes => f.write_str("hmac"),
         }
     }
@@ -15,10 +15,12 @@ use std::convert::TryFrom;
 use std::fmt;
 
 use aho_corasick::AhoCorasickBuilder;
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
 use enum_iterator::IntoEnumIterator;
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 
 use crate::scalar::func::TimestampLike;
+use crate::EvalError;
 
 /// The raw tokens that can appear in a format string. Many of these tokens
 /// overlap, in which case the longest matching token should be selected.
@@ -495,6 +497,84 @@ enum DateTimeField {
     TimezoneOffset,
 }
 
+impl DateTimeField {
+    /// Returns the canonical format pattern for the field, for use in error
+    /// messages.
+    fn name(&self) -> &'static str {
+        match self {
+            DateTimeField::Hour12 => "HH12",
+            DateTimeField::Hour24 => "HH24",
+            DateTimeField::Minute => "MI",
+            DateTimeField::Second => "SS",
+            DateTimeField::Millisecond => "MS",
+            DateTimeField::Microsecond => "US",
+            DateTimeField::SecondsPastMidnight => "SSSS",
+            DateTimeField::Meridiem { dots: false, .. } => "AM",
+            DateTimeField::Meridiem { dots: true, .. } => "A.M.",
+            DateTimeField::Year1 => "Y",
+            DateTimeField::Year2 => "YY",
+            DateTimeField::Year3 => "YYY",
+            DateTimeField::Year4 { separator: false } => "YYYY",
+            DateTimeField::Year4 { separator: true } => "Y,YYY",
+            DateTimeField::IsoYear1 => "I",
+            DateTimeField::IsoYear2 => "IY",
+            DateTimeField::IsoYear3 => "IYY",
+            DateTimeField::IsoYear4 => "IYYY",
+            DateTimeField::Era { dots: false, .. } => "AD",
+            DateTimeField::Era { dots: true, .. } => "A.D.",
+            DateTimeField::MonthName { abbrev: true, .. } => "MON",
+            DateTimeField::MonthName { abbrev: false, .. } => "MONTH",
+            DateTimeField::MonthOfYear => "MM",
+            DateTimeField::DayName { abbrev: true, .. } => "DY",
+            DateTimeField::DayName { abbrev: false, .. } => "DAY",
+            DateTimeField::DayOfWeek => "D",
+            DateTimeField::IsoDayOfWeek => "ID",
+            DateTimeField::DayOfMonth => "DD",
+            DateTimeField::DayOfYear => "DDD",
+            DateTimeField::IsoDayOfYear => "IDDD",
+            DateTimeField::WeekOfMonth => "W",
+            DateTimeField::WeekOfYear => "WW",
+            DateTimeField::IsoWeekOfYear => "IW",
+            DateTimeField::Century => "CC",
+            DateTimeField::JulianDay => "J",
+            DateTimeField::Quarter => "Q",
+            DateTimeField::MonthInRomanNumerals { .. } => "RM",
+            DateTimeField::Timezone { .. } => "TZ",
+            DateTimeField::TimezoneHours => "TZH",
+            DateTimeField::TimezoneMinutes => "TZM",
+            DateTimeField::TimezoneOffset => "OF",
+        }
+    }
+
+    /// Returns the maximum number of digits the field consumes when parsing,
+    /// if the field is immediately followed by another field. Returns `None`
+    /// for non-numeric fields and for numeric fields of unbounded width.
+    fn max_width(&self) -> Option<usize> {
+        match self {
+            DateTimeField::Year1
+            | DateTimeField::DayOfWeek
+            | DateTimeField::WeekOfMonth
+            | DateTimeField::Quarter => Some(1),
+            DateTimeField::Hour12
+            | DateTimeField::Hour24
+            | DateTimeField::Minute
+            | DateTimeField::Second
+            | DateTimeField::Year2
+            | DateTimeField::MonthOfYear
+            | DateTimeField::DayOfMonth
+            | DateTimeField::WeekOfYear
+            | DateTimeField::Century
+            | DateTimeField::TimezoneHours
+            | DateTimeField::TimezoneMinutes => Some(2),
+            DateTimeField::Millisecond | DateTimeField::Year3 | DateTimeField::DayOfYear => Some(3),
+            DateTimeField::Year4 { .. } => Some(4),
+            DateTimeField::SecondsPastMidnight => Some(5),
+            DateTimeField::Microsecond => Some(6),
+            _ => None,
+        }
+    }
+}
+
 /// An element of a date-time format string.
 #[derive(Debug)]
 enum DateTimeFormatNode {
@@ -729,7 +809,9 @@ impl DateTimeFormatNode {
                         3
                     ),
                     DateTimeField::IsoWeekOfYear => write_num!(ts.iso_week().week(), 2),
-                    DateTimeField::JulianDay => write_num!(ts.num_days_from_ce() + 1_721_425),
+                    DateTimeField::JulianDay => {
+                        write_num!(ts.num_days_from_ce() + JULIAN_DAY_OF_CE)
+                    }
                     DateTimeField::Minute => write_num!(ts.minute(), 2),
                     DateTimeField::MonthOfYear => write_num!(ts.month(), 2),
                     DateTimeField::MonthName {
@@ -881,4 +963,386 @@ impl DateTimeFormat {
         }
         out
     }
+
+    /// Parses `s` according to the format string, in the manner of
+    /// PostgreSQL's `to_timestamp` and `to_date` functions.
+    ///
+    /// Returns the parsed date and time, along with the offset from UTC in
+    /// seconds if the format string specifies one via the `TZH` or `TZM`
+    /// fields.
+    ///
+    /// As in PostgreSQL, parsing is forgiving: whitespace before fields is
+    /// ignored, a separator in the format string matches any separator in the
+    /// input, and trailing input is ignored.
+    pub fn parse(&self, s: &str) -> Result<(NaiveDateTime, Option<i32>), EvalError> {
+        let mut parsed = ParsedDateTime::default();
+        let mut s = s;
+        for (i, node) in self.0.iter().enumerate() {
+            match node {
+                DateTimeFormatNode::Literal(ch) => parse_literal(&mut s, *ch),
+                DateTimeFormatNode::Field { field, ordinal, .. } => {
+                    // Numeric fields that are immediately followed by another
+                    // field can only be delimited by their maximum width.
+                    let max_width = match self.0.get(i + 1) {
+                        Some(DateTimeFormatNode::Field { .. }) => field.max_width(),
+                        _ => None,
+                    };
+                    parsed.parse_field(&mut s, field, *ordinal, max_width)?;
+                }
+            }
+        }
+        parsed.finish()
+    }
+}
+
+/// The components of a date-time accumulated while parsing a string with a
+/// [`DateTimeFormat`].
+#[derive(Debug, Default)]
+struct ParsedDateTime {
+    year: Option<i64>,
+    bc: bool,
+    century: Option<i64>,
+    month: Option<i64>,
+    day: Option<i64>,
+    day_of_year: Option<i64>,
+    week_of_month: Option<i64>,
+    week_of_year: Option<i64>,
+    julian_day: Option<i64>,
+    hour: Option<i64>,
+    hour12: bool,
+    pm: Option<bool>,
+    minute: Option<i64>,
+    second: Option<i64>,
+    nanosecond: i64,
+    seconds_past_midnight: Option<i64>,
+    tz_negative: bool,
+    tz_hours: Option<i64>,
+    tz_minutes: Option<i64>,
+}
+
+impl ParsedDateTime {
+    fn parse_field(
+        &mut self,
+        s: &mut &str,
+        field: &DateTimeField,
+        ordinal: OrdinalMode,
+        max_width: Option<usize>,
+    ) -> Result<(), EvalError> {
+        *s = s.trim_start();
+        match field {
+            DateTimeField::Meridiem { dots, .. } => {
+                let words: &[&str] = if *dots {
+                    &["a.m.", "p.m."]
+                } else {
+                    &["am", "pm"]
+                };
+                self.pm = Some(parse_word(s, words, field)? == 1);
+            }
+            DateTimeField::Era { dots, .. } => {
+                let words: &[&str] = if *dots {
+                    &["a.d.", "b.c."]
+                } else {
+                    &["ad", "bc"]
+                };
+                self.bc = parse_word(s, words, field)? == 1;
+            }
+            DateTimeField::MonthName { abbrev: true, .. } => {
+                self.month = Some(parse_word(s, &MONTHS_ABBREV_NO_CAPS, field)? as i64 + 1);
+            }
+            DateTimeField::MonthName { abbrev: false, .. } => {
+                self.month = Some(parse_word(s, &MONTHS_NO_CAPS, field)? as i64 + 1);
+            }
+            DateTimeField::MonthInRomanNumerals { .. } => {
+                self.month = Some(parse_word(s, &MONTHS_ROMAN_NO_CAPS, field)? as i64 + 1);
+            }
+            DateTimeField::DayName { abbrev, .. } => {
+                // The day name is validated but otherwise ignored, as it
+                // cannot contribute any information beyond the date.
+                let words = if *abbrev {
+                    &WEEKDAYS_ABBREV_NO_CAPS
+                } else {
+                    &WEEKDAYS_NO_CAPS
+                };
+                parse_word(s, words, field)?;
+            }
+            DateTimeField::IsoYear1
+            | DateTimeField::IsoYear2
+            | DateTimeField::IsoYear3
+            | DateTimeField::IsoYear4
+            | DateTimeField::IsoDayOfWeek
+            | DateTimeField::IsoDayOfYear
+            | DateTimeField::IsoWeekOfYear
+            | DateTimeField::Timezone { .. }
+            | DateTimeField::TimezoneOffset => {
+                return Err(EvalError::UnsupportedDateTimeFormatField(
+                    field.name().into(),
+                ));
+            }
+            DateTimeField::TimezoneHours => {
+                if let Some(rest) = s.strip_prefix('-') {
+                    self.tz_negative = true;
+                    *s = rest;
+                } else if let Some(rest) = s.strip_prefix('+') {
+                    *s = rest;
+                }
+                self.tz_hours = Some(parse_number(s, field, max_width)?.0);
+            }
+            _ => {
+                let (n, digits) = parse_number(s, field, max_width)?;
+                match field {
+                    DateTimeField::Hour12 => {
+                        self.hour = Some(n);
+                        self.hour12 = true;
+                    }
+                    DateTimeField::Hour24 => self.hour = Some(n),
+                    DateTimeField::Minute => self.minute = Some(n),
+                    DateTimeField::Second => self.second = Some(n),
+                    DateTimeField::Millisecond => {
+                        // Fractional fields are scaled by the number of
+                        // digits present, so that "SS.MS" parses "1.5" as
+                        // one and a half seconds.
+                        if digits > 3 {
+                            return Err(EvalError::DateTimeFieldOutOfRange);
+                        }
+                        self.nanosecond += n * 10_i64.pow(3 - digits as u32) * 1_000_000;
+                    }
+                    DateTimeField::Microsecond => {
+                        if digits > 6 {
+                            return Err(EvalError::DateTimeFieldOutOfRange);
+                        }
+                        self.nanosecond += n * 10_i64.pow(6 - digits as u32) * 1_000;
+                    }
+                    DateTimeField::SecondsPastMidnight => self.seconds_past_midnight = Some(n),
+                    DateTimeField::Year1 | DateTimeField::Year2 | DateTimeField::Year3 => {
+                        self.year = Some(if digits < 4 {
+                            adjust_partial_year(n)
+                        } else {
+                            n
+                        });
+                    }
+                    DateTimeField::Year4 { separator } => {
+                        let mut n = n;
+                        if *separator {
+                            if let Some(rest) = s.strip_prefix(',') {
+                                *s = rest;
+                                let (m, _) = parse_number(s, field, Some(3))?;
+                                n = n
+                                    .checked_mul(1000)
+                                    .and_then(|n| n.checked_add(m))
+                                    .ok_or(EvalError::DateTimeFieldOutOfRange)?;
+                            }
+                        }
+                        self.year = Some(n);
+                    }
+                    DateTimeField::MonthOfYear => self.month = Some(n),
+                    DateTimeField::DayOfMonth => self.day = Some(n),
+                    DateTimeField::DayOfYear => self.day_of_year = Some(n),
+                    DateTimeField::WeekOfMonth => self.week_of_month = Some(n),
+                    DateTimeField::WeekOfYear => self.week_of_year = Some(n),
+                    DateTimeField::Century => self.century = Some(n),
+                    DateTimeField::JulianDay => self.julian_day = Some(n),
+                    DateTimeField::TimezoneMinutes => self.tz_minutes = Some(n),
+                    // As in PostgreSQL, the day of the week and the quarter
+                    // are ignored.
+                    _ => (),
+                }
+                if ordinal != OrdinalMode::None {
+                    if let Some(suffix) = s.get(..2) {
+                        if suffix.chars().all(|c| c.is_ascii_alphabetic()) {
+                            *s = &s[2..];
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn finish(self) -> Result<(NaiveDateTime, Option<i32>), EvalError> {
+        let mut year = match (self.year, self.century) {
+            (Some(year), _) => year,
+            (None, Some(century)) => century
+                .checked_sub(1)
+                .and_then(|c| c.checked_mul(100))
+                .and_then(|y| y.checked_add(1))
+                .ok_or(EvalError::DateTimeFieldOutOfRange)?,
+            (None, None) => 1,
+        };
+        if self.bc {
+            year = 1 - year;
+        }
+        let year = i32::try_from(year).map_err(|_| EvalError::DateTimeFieldOutOfRange)?;
+
+        let day_of_year = match (self.day_of_year, self.month, self.day) {
+            (Some(day_of_year), _, _) => Some(day_of_year),
+            (None, None, None) => self.week_of_year.map(first_day_of_week).transpose()?,
+            _ => None,
+        };
+        let date = if let Some(julian_day) = self.julian_day {
+            i32::try_from(julian_day - i64::from(JULIAN_DAY_OF_CE))
+                .ok()
+                .and_then(NaiveDate::from_num_days_from_ce_opt)
+        } else if let Some(day_of_year) = day_of_year {
+            u32::try_from(day_of_year)
+                .ok()
+                .and_then(|day_of_year| NaiveDate::from_yo_opt(year, day_of_year))
+        } else {
+            let month = self.month.unwrap_or(1);
+            let day = match (self.day, self.week_of_month) {
+                (Some(day), _) => day,
+                (None, Some(week)) => first_day_of_week(week)?,
+                (None, None) => 1,
+            };
+            match (u32::try_from(month), u32::try_from(day)) {
+                (Ok(month), Ok(day)) => NaiveDate::from_ymd_opt(year, month, day),
+                _ => None,
+            }
+        };
+        let date = date.ok_or(EvalError::DateTimeFieldOutOfRange)?;
+
+        let (hour, minute, second) = match self.seconds_past_midnight {
+            Some(secs) => (secs / 3600, secs / 60 % 60, secs % 60),
+            None => {
+                let mut hour = self.hour.unwrap_or(0);
+                if self.hour12 && !(1..=12).contains(&hour) {
+                    return Err(EvalError::InvalidHour12(hour));
+                }
+                if let Some(pm) = self.pm {
+                    hour = hour % 12 + if pm { 12 } else { 0 };
+                }
+                (hour, self.minute.unwrap_or(0), self.second.unwrap_or(0))
+            }
+        };
+        let time = match (
+            u32::try_from(hour),
+            u32::try_from(minute),
+            u32::try_from(second),
+            u32::try_from(self.nanosecond),
+        ) {
+            (Ok(hour), Ok(minute), Ok(second), Ok(nano)) => {
+                NaiveTime::from_hms_nano_opt(hour, minute, second, nano)
+            }
+            _ => None,
+        };
+        let time = time.ok_or(EvalError::DateTimeFieldOutOfRange)?;
+
+        let offset = if self.tz_hours.is_some() || self.tz_minutes.is_some() {
+            let secs = self
+                .tz_hours
+                .unwrap_or(0)
+                .checked_mul(3600)
+                .and_then(|h| {
+                    let m = self.tz_minutes.unwrap_or(0).checked_mul(60)?;
+                    h.checked_add(m)
+                })
+                .ok_or(EvalError::DateTimeFieldOutOfRange)?;
+            let secs = if self.tz_negative { -secs } else { secs };
+            Some(i32::try_from(secs).map_err(|_| EvalError::DateTimeFieldOutOfRange)?)
+        } else {
+            None
+        };
+
+        Ok((date.and_time(time), offset))
+    }
+}
+
+/// The Julian day number of 0001-01-01 CE.
+const JULIAN_DAY_OF_CE: i32 = 1_721_425;
+
+/// Returns the first day, counting from one, of the one-based week `week` of
+/// a month or year.
+fn first_day_of_week(week: i64) -> Result<i64, EvalError> {
+    week.checked_sub(1)
+        .and_then(|w| w.checked_mul(7))
+        .and_then(|d| d.checked_add(1))
+        .ok_or(EvalError::DateTimeFieldOutOfRange)
+}
+
+/// Adjusts a year with fewer than four digits to the nearest plausible year,
+/// following PostgreSQL's rules.
+fn adjust_partial_year(year: i64) -> i64 {
+    match year {
+        0..=69 => year + 2000,
+        70..=99 => year + 1900,
+        100..=519 => year + 2000,
+        520..=999 => year + 1000,
+        _ => year,
+    }
+}
+
+/// Consumes a literal character from the format string. Whitespace in the
+/// format string matches any amount of whitespace in the input. Any other
+/// separator matches any one separator in the input, if present, while an
+/// alphanumeric character unconditionally consumes one input character.
+fn parse_literal(s: &mut &str, ch: char) {
+    if ch.is_whitespace() {
+        *s = s.trim_start();
+    } else if ch.is_alphanumeric() {
+        let mut chars = s.chars();
+        chars.next();
+        *s = chars.as_str();
+    } else {
+        *s = s.trim_start();
+        let mut chars = s.chars();
+        if let Some(c) = chars.next() {
+            if !c.is_alphanumeric() && !c.is_whitespace() {
+                *s = chars.as_str();
+            }
+        }
+    }
+}
+
+/// Consumes a run of up to `max_width` ASCII digits, or an unbounded run if
+/// `max_width` is `None`. Returns the value of the digits and the number of
+/// digits consumed.
+fn parse_number(
+    s: &mut &str,
+    field: &DateTimeField,
+    max_width: Option<usize>,
+) -> Result<(i64, usize), EvalError> {
+    let mut len = s.bytes().take_while(|b| b.is_ascii_digit()).count();
+    if let Some(max_width) = max_width {
+        len = len.min(max_width);
+    }
+    if len == 0 {
+        return Err(invalid_value(s, field));
+    }
+    let n = s[..len]
+        .parse()
+        .map_err(|_| EvalError::DateTimeFieldOutOfRange)?;
+    *s = &s[len..];
+    Ok((n, len))
+}
+
+/// Consumes the longest word in `words` that prefixes `s`, ignoring case, and
+/// returns its index.
+fn parse_word(s: &mut &str, words: &[&str], field: &DateTimeField) -> Result<usize, EvalError> {
+    let mut best: Option<(usize, usize)> = None;
+    for (i, word) in words.iter().enumerate() {
+        let matches = s
+            .get(..word.len())
+            .map_or(false, |prefix| prefix.eq_ignore_ascii_case(word));
+        if matches && best.map_or(true, |(_, len)| word.len() > len) {
+            best = Some((i, word.len()));
+        }
+    }
+    match best {
+        Some((i, len)) => {
+            *s = &s[len..];
+            Ok(i)
+        }
+        None => Err(invalid_value(s, field)),
+    }
+}
+
+fn invalid_value(s: &str, field: &DateTimeField) -> EvalError {
+    let len = match s.find(|c: char| !c.is_alphanumeric()) {
+        Some(0) => s.chars().next().map_or(0, |c| c.len_utf8()),
+        Some(len) => len,
+        None => s.len(),
+    };
+    EvalError::InvalidDateTimeFormatValue {
+        value: s[..len].into(),
+        field: field.name().into(),
+    }
 }
@@ -0,0 +1,1290 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! The SQL/JSON path language.
+//!
+//! https://www.postgresql.org/docs/current/functions-json.html#FUNCTIONS-SQLJSON-PATH
+//!
+//! Paths are evaluated against the native jsonb representation, i.e.,
+//! [`Datum`]s. The supported subset of the language includes the `lax` and
+//! `strict` modes, member, wildcard, and array accessors, filter expressions,
+//! variables, arithmetic, and the `type`, `size`, `double`, `abs`, `floor`, and
+//! `ceiling` item methods. The recursive wildcard member accessor (`.**`) and
+//! the datetime and `keyvalue` item methods are not supported.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::iter::Peekable;
+use std::str::CharIndices;
+
+use ordered_float::OrderedFloat;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use repr::Datum;
+
+use crate::scalar::func::build_regex;
+use crate::EvalError;
+
+/// The maximum depth of the expression tree of a path.
+///
+/// Both parsing and evaluation recurse over the expression tree, so an
+/// unbounded depth would let a path like `((((...))))` or `1 + 1 + ... + 1`
+/// overflow the stack.
+const MAX_NESTING_DEPTH: usize = 64;
+
+/// A parsed SQL/JSON path expression.
+///
+/// Two paths are considered equal iff their source text is identical, and the
+/// [`PartialOrd`], [`Ord`], and [`Hash`] implementations are similarly based
+/// upon the source text. Paths serialize to their source text and are
+/// reparsed during deserialization.
+#[derive(Debug, Clone)]
+pub struct JsonPath {
+    source: String,
+    strict: bool,
+    expr: Expr,
+}
+
+#[derive(Debug, Clone)]
+enum Expr {
+    /// `$`
+    Root,
+    /// `@`
+    Current,
+    /// `last`
+    Last,
+    /// `$name`
+    Variable(String),
+    Literal(Literal),
+    Accessor(Box<Expr>, Accessor),
+    Unary(UnaryOp, Box<Expr>),
+    Binary(BinaryOp, Box<Expr>, Box<Expr>),
+    // The remaining variants are predicates, whose results are three-valued
+    // booleans.
+    Compare(CmpOp, Box<Expr>, Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+    IsUnknown(Box<Expr>),
+    Exists(Box<Expr>),
+    LikeRegex(Box<Expr>, regex::Regex),
+    StartsWith(Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+    fn is_predicate(&self) -> bool {
+        matches!(
+            self,
+            Expr::Compare(..)
+                | Expr::And(..)
+                | Expr::Or(..)
+                | Expr::Not(..)
+                | Expr::IsUnknown(..)
+                | Expr::Exists(..)
+                | Expr::LikeRegex(..)
+                | Expr::StartsWith(..)
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Literal {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+}
+
+#[derive(Debug, Clone)]
+enum Accessor {
+    /// `.key`
+    Member(String),
+    /// `.*`
+    MemberWildcard,
+    /// `[*]`
+    ArrayWildcard,
+    /// `[subscript, from to to, ...]`
+    Array(Vec<(Expr, Option<Expr>)>),
+    /// `? (predicate)`
+    Filter(Box<Expr>),
+    /// `.method()`
+    Method(Method),
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Method {
+    Type,
+    Size,
+    Double,
+    Abs,
+    Floor,
+    Ceiling,
+}
+
+impl Method {
+    fn from_name(name: &str) -> Option<Method> {
+        match name {
+            "type" => Some(Method::Type),
+            "size" => Some(Method::Size),
+            "double" => Some(Method::Double),
+            "abs" => Some(Method::Abs),
+            "floor" => Some(Method::Floor),
+            "ceiling" => Some(Method::Ceiling),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Method {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Method::Type => ".type()",
+            Method::Size => ".size()",
+            Method::Double => ".double()",
+            Method::Abs => ".abs()",
+            Method::Floor => ".floor()",
+            Method::Ceiling => ".ceiling()",
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum UnaryOp {
+    Plus,
+    Minus,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Mod,
+}
+
+impl fmt::Display for BinaryOp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            BinaryOp::Add => "+",
+            BinaryOp::Sub => "-",
+            BinaryOp::Mul => "*",
+            BinaryOp::Div => "/",
+            BinaryOp::Mod => "%",
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CmpOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Root,
+    Current,
+    Variable(String),
+    Ident(String),
+    String(String),
+    Number(f64),
+    Dot,
+    LBracket,
+    RBracket,
+    LParen,
+    RParen,
+    Comma,
+    Question,
+    Star,
+    Plus,
+    Minus,
+    Slash,
+    Percent,
+    Cmp(CmpOp),
+    And,
+    Or,
+    Not,
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Token::Root => f.write_str("$"),
+            Token::Current => f.write_str("@"),
+            Token::Variable(name) => write!(f, "${}", name),
+            Token::Ident(name) => f.write_str(name),
+            Token::String(s) => write!(f, "\"{}\"", s),
+            Token::Number(n) => write!(f, "{}", n),
+            Token::Dot => f.write_str("."),
+            Token::LBracket => f.write_str("["),
+            Token::RBracket => f.write_str("]"),
+            Token::LParen => f.write_str("("),
+            Token::RParen => f.write_str(")"),
+            Token::Comma => f.write_str(","),
+            Token::Question => f.write_str("?"),
+            Token::Star => f.write_str("*"),
+            Token::Plus => f.write_str("+"),
+            Token::Minus => f.write_str("-"),
+            Token::Slash => f.write_str("/"),
+            Token::Percent => f.write_str("%"),
+            Token::Cmp(CmpOp::Eq) => f.write_str("=="),
+            Token::Cmp(CmpOp::Ne) => f.write_str("!="),
+            Token::Cmp(CmpOp::Lt) => f.write_str("<"),
+            Token::Cmp(CmpOp::Le) => f.write_str("<="),
+            Token::Cmp(CmpOp::Gt) => f.write_str(">"),
+            Token::Cmp(CmpOp::Ge) => f.write_str(">="),
+            Token::And => f.write_str("&&"),
+            Token::Or => f.write_str("||"),
+            Token::Not => f.write_str("!"),
+        }
+    }
+}
+
+fn syntax_error(near: &str) -> EvalError {
+    EvalError::InvalidJsonPath(format!("syntax error at or near \"{}\"", near))
+}
+
+fn is_ident_start(c: char) -> bool {
+    c.is_alphabetic() || c == '_'
+}
+
+fn lex(s: &str) -> Result<Vec<Token>, EvalError> {
+    let mut tokens = vec![];
+    let mut chars = s.char_indices().peekable();
+    while let Some((_, c)) = chars.next() {
+        let token = match c {
+            _ if c.is_whitespace() => continue,
+            '$' => match chars.peek() {
+                Some((_, c)) if is_ident_start(*c) => {
+                    let (_, c) = chars.next().unwrap();
+                    Token::Variable(lex_ident(c, &mut chars))
+                }
+                Some((_, '"')) => {
+                    chars.next();
+                    Token::Variable(lex_string(&mut chars)?)
+                }
+                _ => Token::Root,
+            },
+            '@' => Token::Current,
+            '"' => Token::String(lex_string(&mut chars)?),
+            '.' => Token::Dot,
+            '[' => Token::LBracket,
+            ']' => Token::RBracket,
+            '(' => Token::LParen,
+            ')' => Token::RParen,
+            ',' => Token::Comma,
+            '?' => Token::Question,
+            '*' => Token::Star,
+            '+' => Token::Plus,
+            '-' => Token::Minus,
+            '/' => Token::Slash,
+            '%' => Token::Percent,
+            '=' => match chars.next() {
+                Some((_, '=')) => Token::Cmp(CmpOp::Eq),
+                _ => return Err(syntax_error("=")),
+            },
+            '!' => match chars.peek() {
+                Some((_, '=')) => {
+                    chars.next();
+                    Token::Cmp(CmpOp::Ne)
+                }
+                _ => Token::Not,
+            },
+            '<' => match chars.peek() {
+                Some((_, '=')) => {
+                    chars.next();
+                    Token::Cmp(CmpOp::Le)
+                }
+                Some((_, '>')) => {
+                    chars.next();
+                    Token::Cmp(CmpOp::Ne)
+                }
+                _ => Token::Cmp(CmpOp::Lt),
+            },
+            '>' => match chars.peek() {
+                Some((_, '=')) => {
+                    chars.next();
+                    Token::Cmp(CmpOp::Ge)
+                }
+                _ => Token::Cmp(CmpOp::Gt),
+            },
+            '&' => match chars.next() {
+                Some((_, '&')) => Token::And,
+                _ => return Err(syntax_error("&")),
+            },
+            '|' => match chars.next() {
+                Some((_, '|')) => Token::Or,
+                _ => return Err(syntax_error("|")),
+            },
+            _ if c.is_ascii_digit() => Token::Number(lex_number(c, &mut chars)?),
+            _ if is_ident_start(c) => Token::Ident(lex_ident(c, &mut chars)),
+            _ => return Err(syntax_error(&c.to_string())),
+        };
+        tokens.push(token);
+    }
+    Ok(tokens)
+}
+
+fn lex_ident(first: char, chars: &mut Peekable<CharIndices>) -> String {
+    let mut ident = first.to_string();
+    while let Some((_, c)) = chars.peek() {
+        if !is_ident_start(*c) && !c.is_ascii_digit() {
+            break;
+        }
+        ident.push(*c);
+        chars.next();
+    }
+    ident
+}
+
+fn lex_number(first: char, chars: &mut Peekable<CharIndices>) -> Result<f64, EvalError> {
+    let mut number = first.to_string();
+    let mut seen_dot = false;
+    let mut seen_exp = false;
+    while let Some((_, c)) = chars.peek() {
+        let c = *c;
+        if c.is_ascii_digit() {
+            number.push(c);
+        } else if c == '.' && !seen_dot && !seen_exp {
+            // Only treat the dot as a decimal point if it is followed by a
+            // digit, so that e.g. `1.type()` lexes as an item method call.
+            let mut lookahead = chars.clone();
+            lookahead.next();
+            match lookahead.peek() {
+                Some((_, c)) if c.is_ascii_digit() => (),
+                _ => break,
+            }
+            seen_dot = true;
+            number.push(c);
+        } else if (c == 'e' || c == 'E') && !seen_exp {
+            seen_exp = true;
+            number.push(c);
+            chars.next();
+            if let Some((_, sign @ '+')) | Some((_, sign @ '-')) = chars.peek().copied() {
+                number.push(sign);
+                chars.next();
+            }
+            continue;
+        } else {
+            break;
+        }
+        chars.next();
+    }
+    number.parse().map_err(|_| syntax_error(&number))
+}
+
+fn lex_string(chars: &mut Peekable<CharIndices>) -> Result<String, EvalError> {
+    let mut s = String::new();
+    loop {
+        match chars.next() {
+            None => {
+                return Err(EvalError::InvalidJsonPath(
+                    "unexpected end of quoted string".into(),
+                ))
+            }
+            Some((_, '"')) => return Ok(s),
+            Some((_, '\\')) => match chars.next() {
+                Some((_, 'b')) => s.push('\u{8}'),
+                Some((_, 'f')) => s.push('\u{c}'),
+                Some((_, 'n')) => s.push('\n'),
+                Some((_, 'r')) => s.push('\r'),
+                Some((_, 't')) => s.push('\t'),
+                Some((_, 'u')) => {
+                    let mut code = String::new();
+                    for _ in 0..4 {
+                        match chars.next() {
+                            Some((_, c)) => code.push(c),
+                            None => break,
+                        }
+                    }
+                    match u32::from_str_radix(&code, 16).ok().and_then(char::from_u32) {
+                        Some(c) => s.push(c),
+                        None => {
+                            return Err(EvalError::InvalidJsonPath(format!(
+                                "invalid Unicode escape sequence \"\\u{}\"",
+                                code
+                            )))
+                        }
+                    }
+                }
+                Some((_, c)) => s.push(c),
+                None => {
+                    return Err(EvalError::InvalidJsonPath(
+                        "unexpected end of quoted string".into(),
+                    ))
+                }
+            },
+            Some((_, c)) => s.push(c),
+        }
+    }
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    /// The depth of the expression tree at the current position.
+    depth: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next_token(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn consume(&mut self, token: &Token) -> bool {
+        if self.peek() == Some(token) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn consume_keyword(&mut self, keyword: &str) -> bool {
+        match self.peek() {
+            Some(Token::Ident(ident)) if ident == keyword => {
+                self.pos += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn expect(&mut self, token: &Token) -> Result<(), EvalError> {
+        if self.consume(token) {
+            Ok(())
+        } else {
+            Err(self.error_at(self.pos))
+        }
+    }
+
+    fn expect_keyword(&mut self, keyword: &str) -> Result<(), EvalError> {
+        if self.consume_keyword(keyword) {
+            Ok(())
+        } else {
+            Err(self.error_at(self.pos))
+        }
+    }
+
+    fn expect_string(&mut self) -> Result<String, EvalError> {
+        match self.peek() {
+            Some(Token::String(s)) => {
+                let s = s.clone();
+                self.pos += 1;
+                Ok(s)
+            }
+            _ => Err(self.error_at(self.pos)),
+        }
+    }
+
+    fn error_at(&self, pos: usize) -> EvalError {
+        match self.tokens.get(pos) {
+            Some(token) => syntax_error(&token.to_string()),
+            None => EvalError::InvalidJsonPath("syntax error at end".into()),
+        }
+    }
+
+    /// Notes that the expression being parsed is nested one level deeper,
+    /// erroring if that exceeds [`MAX_NESTING_DEPTH`].
+    ///
+    /// Callers must restore `depth` once they have built their expression.
+    fn nest(&mut self) -> Result<(), EvalError> {
+        self.depth += 1;
+        if self.depth > MAX_NESTING_DEPTH {
+            return Err(eval_error(format!(
+                "jsonpath expression exceeds the maximum nesting depth of {}",
+                MAX_NESTING_DEPTH
+            )));
+        }
+        Ok(())
+    }
+
+    /// Ensures that the expression that ends just before the current token is
+    /// a predicate.
+    fn predicate(&self, expr: Expr) -> Result<Expr, EvalError> {
+        if expr.is_predicate() {
+            Ok(expr)
+        } else {
+            Err(self.error_at(self.pos.saturating_sub(1)))
+        }
+    }
+
+    fn parse_expr(&mut self) -> Result<Expr, EvalError> {
+        let depth = self.depth;
+        let mut expr = self.parse_and()?;
+        while self.consume(&Token::Or) {
+            self.nest()?;
+            let left = self.predicate(expr)?;
+            let right = self.parse_and()?;
+            let right = self.predicate(right)?;
+            expr = Expr::Or(Box::new(left), Box::new(right));
+        }
+        self.depth = depth;
+        Ok(expr)
+    }
+
+    fn parse_and(&mut self) -> Result<Expr, EvalError> {
+        let depth = self.depth;
+        let mut expr = self.parse_not()?;
+        while self.consume(&Token::And) {
+            self.nest()?;
+            let left = self.predicate(expr)?;
+            let right = self.parse_not()?;
+            let right = self.predicate(right)?;
+            expr = Expr::And(Box::new(left), Box::new(right));
+        }
+        self.depth = depth;
+        Ok(expr)
+    }
+
+    fn parse_not(&mut self) -> Result<Expr, EvalError> {
+        if self.consume(&Token::Not) {
+            let depth = self.depth;
+            self.nest()?;
+            self.expect(&Token::LParen)?;
+            let expr = self.parse_expr()?;
+            let expr = self.predicate(expr)?;
+            self.expect(&Token::RParen)?;
+            self.depth = depth;
+            return Ok(Expr::Not(Box::new(expr)));
+        }
+        let expr = self.parse_comparison()?;
+        if self.consume_keyword("is") {
+            self.expect_keyword("unknown")?;
+            let expr = self.predicate(expr)?;
+            return Ok(Expr::IsUnknown(Box::new(expr)));
+        }
+        Ok(expr)
+    }
+
+    fn parse_comparison(&mut self) -> Result<Expr, EvalError> {
+        let left = self.parse_additive()?;
+        if let Some(Token::Cmp(op)) = self.peek() {
+            let op = *op;
+            self.pos += 1;
+            let right = self.parse_additive()?;
+            Ok(Expr::Compare(op, Box::new(left), Box::new(right)))
+        } else if self.consume_keyword("like_regex") {
+            let pattern = self.expect_string()?;
+            let flags = if self.consume_keyword("flag") {
+                self.expect_string()?
+            } else {
+                String::new()
+            };
+            let regex = build_regex(&pattern, &flags)?;
+            Ok(Expr::LikeRegex(Box::new(left), regex))
+        } else if self.consume_keyword("starts") {
+            self.expect_keyword("with")?;
+            let right = self.parse_additive()?;
+            Ok(Expr::StartsWith(Box::new(left), Box::new(right)))
+        } else {
+            Ok(left)
+        }
+    }
+
+    fn parse_additive(&mut self) -> Result<Expr, EvalError> {
+        let depth = self.depth;
+        let mut expr = self.parse_multiplicative()?;
+        loop {
+            let op = match self.peek() {
+                Some(Token::Plus) => BinaryOp::Add,
+                Some(Token::Minus) => BinaryOp::Sub,
+                _ => break,
+            };
+            self.pos += 1;
+            self.nest()?;
+            let right = self.parse_multiplicative()?;
+            expr = Expr::Binary(op, Box::new(expr), Box::new(right));
+        }
+        self.depth = depth;
+        Ok(expr)
+    }
+
+    fn parse_multiplicative(&mut self) -> Result<Expr, EvalError> {
+        let depth = self.depth;
+        let mut expr = self.parse_unary()?;
+        loop {
+            let op = match self.peek() {
+                Some(Token::Star) => BinaryOp::Mul,
+                Some(Token::Slash) => BinaryOp::Div,
+                Some(Token::Percent) => BinaryOp::Mod,
+                _ => break,
+            };
+            self.pos += 1;
+            self.nest()?;
+            let right = self.parse_unary()?;
+            expr = Expr::Binary(op, Box::new(expr), Box::new(right));
+        }
+        self.depth = depth;
+        Ok(expr)
+    }
+
+    fn parse_unary(&mut self) -> Result<Expr, EvalError> {
+        // Every operand, and so every parenthesized, filtered, or subscripted
+        // expression, is parsed via this method.
+        let depth = self.depth;
+        self.nest()?;
+        let expr = if self.consume(&Token::Minus) {
+            Expr::Unary(UnaryOp::Minus, Box::new(self.parse_unary()?))
+        } else if self.consume(&Token::Plus) {
+            Expr::Unary(UnaryOp::Plus, Box::new(self.parse_unary()?))
+        } else {
+            self.parse_accessors()?
+        };
+        self.depth = depth;
+        Ok(expr)
+    }
+
+    fn parse_accessors(&mut self) -> Result<Expr, EvalError> {
+        let depth = self.depth;
+        let mut expr = self.parse_primary()?;
+        loop {
+            let accessor = if self.consume(&Token::Dot) {
+                match self.next_token() {
+                    Some(Token::Star) => Accessor::MemberWildcard,
+                    Some(Token::String(key)) => Accessor::Member(key),
+                    Some(Token::Ident(name)) if self.consume(&Token::LParen) => {
+                        let method = match Method::from_name(&name) {
+                            Some(method) => method,
+                            None => return Err(self.error_at(self.pos - 2)),
+                        };
+                        self.expect(&Token::RParen)?;
+                        Accessor::Method(method)
+                    }
+                    Some(Token::Ident(key)) => Accessor::Member(key),
+                    _ => return Err(self.error_at(self.pos - 1)),
+                }
+            } else if self.consume(&Token::LBracket) {
+                if self.consume(&Token::Star) {
+                    self.expect(&Token::RBracket)?;
+                    Accessor::ArrayWildcard
+                } else {
+                    let mut subscripts = vec![];
+                    loop {
+                        let from = self.parse_additive()?;
+                        let to = if self.consume_keyword("to") {
+                            Some(self.parse_additive()?)
+                        } else {
+                            None
+                        };
+                        subscripts.push((from, to));
+                        if !self.consume(&Token::Comma) {
+                            break;
+                        }
+                    }
+                    self.expect(&Token::RBracket)?;
+                    Accessor::Array(subscripts)
+                }
+            } else if self.consume(&Token::Question) {
+                self.expect(&Token::LParen)?;
+                let expr = self.parse_expr()?;
+                let expr = self.predicate(expr)?;
+                self.expect(&Token::RParen)?;
+                Accessor::Filter(Box::new(expr))
+            } else {
+                break;
+            };
+            expr = Expr::Accessor(Box::new(expr), accessor);
+            self.nest()?;
+        }
+        self.depth = depth;
+        Ok(expr)
+    }
+
+    fn parse_primary(&mut self) -> Result<Expr, EvalError> {
+        match self.next_token() {
+            Some(Token::Root) => Ok(Expr::Root),
+            Some(Token::Current) => Ok(Expr::Current),
+            Some(Token::Variable(name)) => Ok(Expr::Variable(name)),
+            Some(Token::Number(n)) => Ok(Expr::Literal(Literal::Number(n))),
+            Some(Token::String(s)) => Ok(Expr::Literal(Literal::String(s))),
+            Some(Token::Ident(ident)) => match ident.as_str() {
+                "true" => Ok(Expr::Literal(Literal::Bool(true))),
+                "false" => Ok(Expr::Literal(Literal::Bool(false))),
+                "null" => Ok(Expr::Literal(Literal::Null)),
+                "last" => Ok(Expr::Last),
+                "exists" => {
+                    self.expect(&Token::LParen)?;
+                    let expr = self.parse_expr()?;
+                    self.expect(&Token::RParen)?;
+                    Ok(Expr::Exists(Box::new(expr)))
+                }
+                _ => Err(self.error_at(self.pos - 1)),
+            },
+            Some(Token::LParen) => {
+                let expr = self.parse_expr()?;
+                self.expect(&Token::RParen)?;
+                Ok(expr)
+            }
+            _ => Err(self.error_at(self.pos - 1)),
+        }
+    }
+}
+
+/// The state against which an expression is evaluated.
+#[derive(Clone, Copy)]
+struct Context<'a> {
+    strict: bool,
+    root: Datum<'a>,
+    vars: Option<Datum<'a>>,
+    /// The item that `@` refers to, if within a filter expression.
+    current: Option<Datum<'a>>,
+    /// The index of the last element of the array that `last` refers to, if
+    /// within an array subscript.
+    last: Option<i64>,
+}
+
+fn eval_error(msg: impl Into<String>) -> EvalError {
+    EvalError::JsonPath(msg.into())
+}
+
+/// Constructs a jsonb number, using the integer representation for integral
+/// values, as the jsonb parser does.
+fn number<'a>(n: f64) -> Datum<'a> {
+    if n.fract() == 0.0 && n >= i64::MIN as f64 && n < i64::MAX as f64 {
+        Datum::Int64(n as i64)
+    } else {
+        Datum::Float64(OrderedFloat(n))
+    }
+}
+
+fn as_number(d: Datum) -> Option<f64> {
+    match d {
+        Datum::Float64(n) => Some(n.into_inner()),
+        Datum::Int64(n) => Some(n as f64),
+        _ => None,
+    }
+}
+
+fn as_bool(d: Datum) -> Option<bool> {
+    match d {
+        Datum::True => Some(true),
+        Datum::False => Some(false),
+        _ => None,
+    }
+}
+
+fn type_name(d: Datum) -> &'static str {
+    match d {
+        Datum::Map(_) => "object",
+        Datum::List(_) => "array",
+        Datum::String(_) => "string",
+        Datum::Float64(_) | Datum::Int64(_) => "number",
+        Datum::True | Datum::False => "boolean",
+        _ => "null",
+    }
+}
+
+/// In lax mode, replaces each array in `items` with its elements.
+fn unwrap_arrays(items: Vec<Datum>, strict: bool) -> Vec<Datum> {
+    if strict {
+        return items;
+    }
+    let mut out = vec![];
+    for item in items {
+        match item {
+            Datum::List(list) => out.extend(list.iter()),
+            _ => out.push(item),
+        }
+    }
+    out
+}
+
+impl JsonPath {
+    /// Parses a SQL/JSON path expression.
+    pub fn parse(s: &str) -> Result<JsonPath, EvalError> {
+        let mut parser = Parser {
+            tokens: lex(s)?,
+            pos: 0,
+            depth: 0,
+        };
+        let strict = if parser.consume_keyword("strict") {
+            true
+        } else {
+            parser.consume_keyword("lax");
+            false
+        };
+        let expr = parser.parse_expr()?;
+        if parser.peek().is_some() {
+            return Err(parser.error_at(parser.pos));
+        }
+        Ok(JsonPath {
+            source: s.into(),
+            strict,
+            expr,
+        })
+    }
+
+    /// Returns the source text of the path.
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    /// Evaluates the path against the jsonb value `root`, returning the
+    /// resulting sequence of jsonb values.
+    ///
+    /// If present, `vars` must be a jsonb object whose fields supply the values
+    /// of the variables that the path references.
+    pub fn query<'a>(
+        &'a self,
+        root: Datum<'a>,
+        vars: Option<Datum<'a>>,
+    ) -> Result<Vec<Datum<'a>>, EvalError> {
+        match vars {
+            None | Some(Datum::Map(_)) => (),
+            Some(_) => return Err(eval_error("\"vars\" argument is not an object")),
+        }
+        let cx = Context {
+            strict: self.strict,
+            root,
+            vars,
+            current: None,
+            last: None,
+        };
+        eval(&self.expr, cx)
+    }
+}
+
+impl PartialEq for JsonPath {
+    fn eq(&self, other: &JsonPath) -> bool {
+        self.source == other.source
+    }
+}
+
+impl Eq for JsonPath {}
+
+impl PartialOrd for JsonPath {
+    fn partial_cmp(&self, other: &JsonPath) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for JsonPath {
+    fn cmp(&self, other: &JsonPath) -> Ordering {
+        self.source.cmp(&other.source)
+    }
+}
+
+impl Hash for JsonPath {
+    fn hash<H: Hasher>(&self, hasher: &mut H) {
+        self.source.hash(hasher)
+    }
+}
+
+impl Serialize for JsonPath {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.source)
+    }
+}
+
+impl<'de> Deserialize<'de> for JsonPath {
+    fn deserialize<D>(deserializer: D) -> Result<JsonPath, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let source = String::deserialize(deserializer)?;
+        JsonPath::parse(&source).map_err(de::Error::custom)
+    }
+}
+
+fn eval<'a>(expr: &'a Expr, cx: Context<'a>) -> Result<Vec<Datum<'a>>, EvalError> {
+    match expr {
+        Expr::Root => Ok(vec![cx.root]),
+        Expr::Current => match cx.current {
+            Some(current) => Ok(vec![current]),
+            None => Err(eval_error("@ is not allowed in root expressions")),
+        },
+        Expr::Last => match cx.last {
+            Some(last) => Ok(vec![number(last as f64)]),
+            None => Err(eval_error("LAST is allowed only in array subscripts")),
+        },
+        Expr::Variable(name) => {
+            let value = match cx.vars {
+                Some(Datum::Map(vars)) => vars
+                    .iter()
+                    .find(|(k, _)| *k == name.as_str())
+                    .map(|(_, v)| v),
+                _ => None,
+            };
+            match value {
+                Some(value) => Ok(vec![value]),
+                None => Err(eval_error(format!(
+                    "could not find jsonpath variable \"{}\"",
+                    name
+                ))),
+            }
+        }
+        Expr::Literal(literal) => Ok(vec![match literal {
+            Literal::Null => Datum::JsonNull,
+            Literal::Bool(b) => Datum::from(*b),
+            Literal::Number(n) => number(*n),
+            Literal::String(s) => Datum::String(s),
+        }]),
+        Expr::Accessor(base, accessor) => {
+            let items = eval(base, cx)?;
+            eval_accessor(accessor, items, cx)
+        }
+        Expr::Unary(op, expr) => {
+            let items = unwrap_arrays(eval(expr, cx)?, cx.strict);
+            items
+                .into_iter()
+                .map(|item| match (op, as_number(item)) {
+                    (UnaryOp::Plus, Some(n)) => Ok(number(n)),
+                    (UnaryOp::Minus, Some(n)) => Ok(number(-n)),
+                    (UnaryOp::Plus, None) => Err(eval_error(
+                        "operand of unary jsonpath operator + is not a numeric value",
+                    )),
+                    (UnaryOp::Minus, None) => Err(eval_error(
+                        "operand of unary jsonpath operator - is not a numeric value",
+                    )),
+                })
+                .collect()
+        }
+        Expr::Binary(op, left, right) => {
+            let left = eval_operand(left, cx, *op, "left")?;
+            let right = eval_operand(right, cx, *op, "right")?;
+            let result = match op {
+                BinaryOp::Add => left + right,
+                BinaryOp::Sub => left - right,
+                BinaryOp::Mul => left * right,
+                BinaryOp::Div | BinaryOp::Mod if right == 0.0 => {
+                    return Err(EvalError::DivisionByZero)
+                }
+                BinaryOp::Div => left / right,
+                BinaryOp::Mod => left % right,
+            };
+            Ok(vec![number(result)])
+        }
+        _ => Ok(vec![match eval_predicate(expr, cx) {
+            Some(true) => Datum::True,
+            Some(false) => Datum::False,
+            None => Datum::JsonNull,
+        }]),
+    }
+}
+
+/// Evaluates an operand of a binary arithmetic operator, which must produce
+/// a single number.
+fn eval_operand<'a>(
+    expr: &'a Expr,
+    cx: Context<'a>,
+    op: BinaryOp,
+    side: &str,
+) -> Result<f64, EvalError> {
+    let items = unwrap_arrays(eval(expr, cx)?, cx.strict);
+    match items.as_slice() {
+        [item] => as_number(*item),
+        _ => None,
+    }
+    .ok_or_else(|| {
+        eval_error(format!(
+            "{} operand of jsonpath operator {} is not a single numeric value",
+            side, op
+        ))
+    })
+}
+
+fn eval_accessor<'a>(
+    accessor: &'a Accessor,
+    items: Vec<Datum<'a>>,
+    cx: Context<'a>,
+) -> Result<Vec<Datum<'a>>, EvalError> {
+    let mut out = vec![];
+    match accessor {
+        Accessor::Member(key) => {
+            for item in unwrap_arrays(items, cx.strict) {
+                match item {
+                    Datum::Map(map) => match map.iter().find(|(k, _)| *k == key.as_str()) {
+                        Some((_, v)) => out.push(v),
+                        None if cx.strict => {
+                            return Err(eval_error(format!(
+                                "JSON object does not contain key \"{}\"",
+                                key
+                            )))
+                        }
+                        None => (),
+                    },
+                    _ if cx.strict => {
+                        return Err(eval_error(
+                            "jsonpath member accessor can only be applied to an object",
+                        ))
+                    }
+                    _ => (),
+                }
+            }
+        }
+        Accessor::MemberWildcard => {
+            for item in unwrap_arrays(items, cx.strict) {
+                match item {
+                    Datum::Map(map) => out.extend(map.iter().map(|(_, v)| v)),
+                    _ if cx.strict => {
+                        return Err(eval_error(
+                            "jsonpath wildcard member accessor can only be applied to an object",
+                        ))
+                    }
+                    _ => (),
+                }
+            }
+        }
+        Accessor::ArrayWildcard => {
+            for item in items {
+                match item {
+                    Datum::List(list) => out.extend(list.iter()),
+                    _ if cx.strict => {
+                        return Err(eval_error(
+                            "jsonpath wildcard array accessor can only be applied to an array",
+                        ))
+                    }
+                    _ => out.push(item),
+                }
+            }
+        }
+        Accessor::Array(subscripts) => {
+            for item in items {
+                // In lax mode, non-array items are treated as single-element
+                // arrays.
+                let elems: Vec<_> = match item {
+                    Datum::List(list) => list.iter().collect(),
+                    _ if cx.strict => {
+                        return Err(eval_error(
+                            "jsonpath array accessor can only be applied to an array",
+                        ))
+                    }
+                    _ => vec![item],
+                };
+                let len = elems.len() as i64;
+                let cx = Context {
+                    last: Some(len - 1),
+                    ..cx
+                };
+                for (from, to) in subscripts {
+                    let from = eval_subscript(from, cx)?;
+                    let to = match to {
+                        Some(to) => eval_subscript(to, cx)?,
+                        None => from,
+                    };
+                    if cx.strict && (from < 0 || from > to || to >= len) {
+                        return Err(eval_error("jsonpath array subscript is out of bounds"));
+                    }
+                    let from = from.max(0);
+                    let to = to.min(len - 1);
+                    if from <= to {
+                        out.extend(&elems[from as usize..=to as usize]);
+                    }
+                }
+            }
+        }
+        Accessor::Filter(predicate) => {
+            for item in unwrap_arrays(items, cx.strict) {
+                let cx = Context {
+                    current: Some(item),
+                    ..cx
+                };
+                if eval_predicate(predicate, cx) == Some(true) {
+                    out.push(item);
+                }
+            }
+        }
+        Accessor::Method(Method::Type) => {
+            out.extend(items.into_iter().map(|item| Datum::String(type_name(item))));
+        }
+        Accessor::Method(Method::Size) => {
+            for item in items {
+                match item {
+                    Datum::List(list) => out.push(number(list.iter().count() as f64)),
+                    _ if cx.strict => {
+                        return Err(eval_error(
+                            "jsonpath item method .size() can only be applied to an array",
+                        ))
+                    }
+                    _ => out.push(number(1.0)),
+                }
+            }
+        }
+        Accessor::Method(Method::Double) => {
+            for item in unwrap_arrays(items, cx.strict) {
+                let n = match item {
+                    Datum::String(s) => match s.trim().parse::<f64>() {
+                        Ok(n) if n.is_finite() => n,
+                        _ => {
+                            return Err(eval_error(
+                                "string argument of jsonpath item method .double() is not a valid representation of a double precision number",
+                            ))
+                        }
+                    },
+                    _ => match as_number(item) {
+                        Some(n) => n,
+                        None => {
+                            return Err(eval_error(
+                                "jsonpath item method .double() can only be applied to a string or numeric value",
+                            ))
+                        }
+                    },
+                };
+                out.push(number(n));
+            }
+        }
+        Accessor::Method(method) => {
+            for item in unwrap_arrays(items, cx.strict) {
+                let n = match as_number(item) {
+                    Some(n) => n,
+                    None => {
+                        return Err(eval_error(format!(
+                            "jsonpath item method {} can only be applied to a numeric value",
+                            method
+                        )))
+                    }
+                };
+                out.push(number(match method {
+                    Method::Abs => n.abs(),
+                    Method::Floor => n.floor(),
+                    Method::Ceiling => n.ceil(),
+                    Method::Type | Method::Size | Method::Double => unreachable!(),
+                }));
+            }
+        }
+    }
+    Ok(out)
+}
+
+fn eval_subscript<'a>(expr: &'a Expr, cx: Context<'a>) -> Result<i64, EvalError> {
+    let items = eval(expr, cx)?;
+    match items.as_slice() {
+        [item] => match as_number(*item) {
+            Some(n) if n.is_finite() => Ok(n.trunc() as i64),
+            _ => Err(eval_error(
+                "jsonpath array subscript is not a single numeric value",
+            )),
+        },
+        _ => Err(eval_error(
+            "jsonpath array subscript is not a single numeric value",
+        )),
+    }
+}
+
+/// Evaluates a predicate, returning `None` if its result is unknown.
+fn eval_predicate<'a>(expr: &'a Expr, cx: Context<'a>) -> Option<bool> {
+    match expr {
+        Expr::And(left, right) => match (eval_predicate(left, cx), eval_predicate(right, cx)) {
+            (Some(false), _) | (_, Some(false)) => Some(false),
+            (Some(true), Some(true)) => Some(true),
+            _ => None,
+        },
+        Expr::Or(left, right) => match (eval_predicate(left, cx), eval_predicate(right, cx)) {
+            (Some(true), _) | (_, Some(true)) => Some(true),
+            (Some(false), Some(false)) => Some(false),
+            _ => None,
+        },
+        Expr::Not(expr) => eval_predicate(expr, cx).map(|b| !b),
+        Expr::IsUnknown(expr) => Some(eval_predicate(expr, cx).is_none()),
+        Expr::Exists(expr) => eval(expr, cx).ok().map(|items| !items.is_empty()),
+        Expr::Compare(op, left, right) => {
+            let left = unwrap_arrays(eval(left, cx).ok()?, cx.strict);
+            let right = unwrap_arrays(eval(right, cx).ok()?, cx.strict);
+            any_true(
+                cx.strict,
+                left.iter()
+                    .flat_map(|l| right.iter().map(move |r| compare(*op, *l, *r))),
+            )
+        }
+        Expr::LikeRegex(expr, regex) => {
+            let items = unwrap_arrays(eval(expr, cx).ok()?, cx.strict);
+            any_true(
+                cx.strict,
+                items.into_iter().map(|item| match item {
+                    Datum::String(s) => Some(regex.is_match(s)),
+                    _ => None,
+                }),
+            )
+        }
+        Expr::StartsWith(expr, prefix) => {
+            let prefix = match eval(prefix, cx).ok()?.as_slice() {
+                [Datum::String(prefix)] => *prefix,
+                _ => return None,
+            };
+            let items = unwrap_arrays(eval(expr, cx).ok()?, cx.strict);
+            any_true(
+                cx.strict,
+                items.into_iter().map(|item| match item {
+                    Datum::String(s) => Some(s.starts_with(prefix)),
+                    _ => None,
+                }),
+            )
+        }
+        _ => unreachable!("parser only permits predicates here"),
+    }
+}
+
+/// Combines the results of applying a predicate to each item of a sequence.
+///
+/// The predicate holds if it holds for any item. In lax mode, the search stops
+/// at the first item for which the predicate holds, while in strict mode, the
+/// result is unknown if the result for any item is unknown.
+fn any_true(strict: bool, results: impl Iterator<Item = Option<bool>>) -> Option<bool> {
+    let mut found = false;
+    let mut unknown = false;
+    for result in results {
+        match result {
+            Some(true) if !strict => return Some(true),
+            Some(true) => found = true,
+            Some(false) => (),
+            None if strict => return None,
+            None => unknown = true,
+        }
+    }
+    if found {
+        Some(true)
+    } else if unknown {
+        None
+    } else {
+        Some(false)
+    }
+}
+
+/// Compares two jsonb values, returning `None` if they are not comparable.
+fn compare(op: CmpOp, left: Datum, right: Datum) -> Option<bool> {
+    let ordering = if let (Some(l), Some(r)) = (as_number(left), as_number(right)) {
+        l.partial_cmp(&r)?
+    } else if let (Datum::String(l), Datum::String(r)) = (left, right) {
+        l.cmp(r)
+    } else if let (Some(l), Some(r)) = (as_bool(left), as_bool(right)) {
+        l.cmp(&r)
+    } else if left == Datum::JsonNull && right == Datum::JsonNull {
+        Ordering::Equal
+    } else if left == Datum::JsonNull || right == Datum::JsonNull {
+        // Null is not equal to any non-null value, but is also not ordered
+        // with respect to any non-null value.
+        return Some(op == CmpOp::Ne);
+    } else {
+        return None;
+    };
+    Some(match op {
+        CmpOp::Eq => ordering == Ordering::Equal,
+        CmpOp::Ne => ordering != Ordering::Equal,
+        CmpOp::Lt => ordering == Ordering::Less,
+        CmpOp::Le => ordering != Ordering::Greater,
+        CmpOp::Gt => ordering == Ordering::Greater,
+        CmpOp::Ge => ordering != Ordering::Less,
+    })
+}
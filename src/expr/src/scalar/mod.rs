@@ -17,12 +17,13 @@ use ore::collections::CollectionExt;
 use ore::str::separated;
 use repr::adt::array::InvalidArrayError;
 use repr::adt::datetime::DateTimeUnits;
+use repr::adt::range::InvalidRangeError;
 use repr::adt::regex::Regex;
 use repr::strconv::{ParseError, ParseHexError};
 use repr::{ColumnType, Datum, RelationType, Row, RowArena, ScalarType};
 
 use self::func::{BinaryFunc, NullaryFunc, UnaryFunc, VariadicFunc};
-use crate::scalar::func::parse_timezone;
+use crate::scalar::func::{parse_timezone, JsonPath};
 
 pub mod func;
 pub mod like_pattern;
@@ -583,6 +584,62 @@ impl MirScalarExpr {
                             }
                         };
                     }
+                } else if *func == VariadicFunc::RegexpReplace {
+                    if exprs[1..].iter().all(|e| e.is_literal()) {
+                        let needle = exprs[1].as_literal_str().unwrap();
+                        let replacement = exprs[2].as_literal_str().unwrap().to_owned();
+                        let flags = match exprs.len() {
+                            4 => exprs[3].as_literal_str().unwrap(),
+                            _ => "",
+                        };
+                        *e = match func::build_regex_global(needle, flags) {
+                            Ok((regex, global)) => {
+                                mem::take(exprs)
+                                    .into_first()
+                                    .call_unary(UnaryFunc::RegexpReplace {
+                                        regex: Regex(regex),
+                                        replacement,
+                                        global,
+                                    })
+                            }
+                            Err(err) => {
+                                MirScalarExpr::literal(Err(err), e.typ(&relation_type).scalar_type)
+                            }
+                        };
+                    }
+                } else if *func == VariadicFunc::RegexpSplitToArray {
+                    if exprs[1..].iter().all(|e| e.is_literal()) {
+                        let needle = exprs[1].as_literal_str().unwrap();
+                        let flags = match exprs.len() {
+                            3 => exprs[2].as_literal_str().unwrap(),
+                            _ => "",
+                        };
+                        *e = match func::build_regex(needle, flags) {
+                            Ok(regex) => mem::take(exprs)
+                                .into_first()
+                                .call_unary(UnaryFunc::RegexpSplitToArray(Regex(regex))),
+                            Err(err) => {
+                                MirScalarExpr::literal(Err(err), e.typ(&relation_type).scalar_type)
+                            }
+                        };
+                    }
+                } else if *func == VariadicFunc::JsonbPathExists {
+                    if exprs[1].is_literal() {
+                        let path = exprs[1].as_literal_str().unwrap();
+                        *e = match JsonPath::parse(path) {
+                            Ok(path) => {
+                                let mut exprs = mem::take(exprs);
+                                exprs.remove(1);
+                                MirScalarExpr::CallVariadic {
+                                    func: VariadicFunc::JsonbPathExistsStatic(path),
+                                    exprs,
+                                }
+                            }
+                            Err(err) => {
+                                MirScalarExpr::literal(Err(err), e.typ(&relation_type).scalar_type)
+                            }
+                        };
+                    }
                 }
             }
             MirScalarExpr::If { cond, then, els } => {
@@ -889,17 +946,31 @@ pub enum EvalError {
     Int64OutOfRange,
     IntervalOutOfRange,
     TimestampOutOfRange,
+    DateTimeFieldOutOfRange,
     InvalidBase64Equals,
     InvalidBase64Symbol(char),
     InvalidBase64EndSequence,
     InvalidTimezone(String),
     InvalidTimezoneInterval,
     InvalidTimezoneConversion,
+    InvalidDateTimeFormatValue {
+        value: String,
+        field: String,
+    },
+    InvalidHour12(i64),
     InvalidDimension {
         max_dim: usize,
         val: i64,
     },
     InvalidArray(InvalidArrayError),
+    IncompatibleArrayDimensions {
+        dims: Option<(usize, usize)>,
+    },
+    MismatchedArrayDimensions,
+    MultidimensionalArrayRemovalNotSupported,
+    MultidimensionalArraySearchNotSupported,
+    NotOneDimensionalArray,
+    InvalidRange(InvalidRangeError),
     InvalidEncodingName(String),
     InvalidHashAlgorithm(String),
     InvalidByteSequence {
@@ -910,12 +981,19 @@ pub enum EvalError {
         from: String,
         to: String,
     },
+    InvalidJsonPath(String),
+    JsonPath(String),
     InvalidRegex(String),
     InvalidRegexFlag(char),
     InvalidParameterValue(String),
     NegSqrt,
+    StringValueTooLong {
+        target_type: String,
+        length: usize,
+    },
     UnknownUnits(String),
     UnsupportedDateTimeUnits(DateTimeUnits),
+    UnsupportedDateTimeFormatField(String),
     UnterminatedLikeEscapeSequence,
     Parse(ParseError),
     ParseHex(ParseHexError),
@@ -937,6 +1015,7 @@ impl fmt::Display for EvalError {
             EvalError::Int64OutOfRange => f.write_str("bigint out of range"),
             EvalError::IntervalOutOfRange => f.write_str("interval out of range"),
             EvalError::TimestampOutOfRange => f.write_str("timestamp out of range"),
+            EvalError::DateTimeFieldOutOfRange => f.write_str("date/time field value out of range"),
             EvalError::InvalidBase64Equals => {
                 f.write_str("unexpected \"=\" while decoding base64 sequence")
             }
@@ -949,17 +1028,41 @@ impl fmt::Display for EvalError {
             EvalError::InvalidJsonbCast { from, to } => {
                 write!(f, "cannot cast jsonb {} to type {}", from, to)
             }
+            EvalError::InvalidJsonPath(e) => write!(f, "{} of jsonpath input", e),
+            EvalError::JsonPath(e) => f.write_str(e),
             EvalError::InvalidTimezone(tz) => write!(f, "invalid time zone '{}'", tz),
             EvalError::InvalidTimezoneInterval => {
                 f.write_str("timezone interval must not contain months or years")
             }
             EvalError::InvalidTimezoneConversion => f.write_str("invalid timezone conversion"),
+            EvalError::InvalidDateTimeFormatValue { value, field } => {
+                write!(f, "invalid value \"{}\" for \"{}\"", value, field)
+            }
+            EvalError::InvalidHour12(hour) => {
+                write!(f, "hour \"{}\" is invalid for the 12-hour clock", hour)
+            }
             EvalError::InvalidDimension { max_dim, val } => write!(
                 f,
                 "invalid dimension: {}; must use value within [1, {}]",
                 val, max_dim
             ),
             EvalError::InvalidArray(e) => e.fmt(f),
+            EvalError::IncompatibleArrayDimensions { .. } => {
+                f.write_str("cannot concatenate incompatible arrays")
+            }
+            EvalError::MismatchedArrayDimensions => f.write_str(
+                "multidimensional arrays must have array expressions with matching dimensions",
+            ),
+            EvalError::MultidimensionalArrayRemovalNotSupported => {
+                f.write_str("removing elements from multidimensional arrays is not supported")
+            }
+            EvalError::MultidimensionalArraySearchNotSupported => {
+                f.write_str("searching for elements in multidimensional arrays is not supported")
+            }
+            EvalError::NotOneDimensionalArray => {
+                f.write_str("argument must be empty or one-dimensional array")
+            }
+            EvalError::InvalidRange(e) => e.fmt(f),
             EvalError::InvalidEncodingName(name) => write!(f, "invalid encoding name '{}'", name),
             EvalError::InvalidHashAlgorithm(alg) => write!(f, "invalid hash algorithm '{}'", alg),
             EvalError::InvalidByteSequence {
@@ -974,10 +1077,19 @@ impl fmt::Display for EvalError {
             EvalError::InvalidRegex(e) => write!(f, "invalid regular expression: {}", e),
             EvalError::InvalidRegexFlag(c) => write!(f, "invalid regular expression flag: {}", c),
             EvalError::InvalidParameterValue(s) => f.write_str(s),
+            EvalError::StringValueTooLong {
+                target_type,
+                length,
+            } => write!(f, "value too long for type {}({})", target_type, length),
             EvalError::UnknownUnits(units) => write!(f, "unknown units '{}'", units),
             EvalError::UnsupportedDateTimeUnits(units) => {
                 write!(f, "unsupported timestamp units '{}'", units)
             }
+            EvalError::UnsupportedDateTimeFormatField(field) => write!(
+                f,
+                "formatting field \"{}\" is only supported in to_char",
+                field
+            ),
             EvalError::UnterminatedLikeEscapeSequence => {
                 f.write_str("unterminated escape sequence in LIKE")
             }
@@ -1002,7 +1114,19 @@ impl fmt::Display for EvalError {
 
 impl EvalError {
     pub fn detail(&self) -> Option<String> {
-        None
+        match self {
+            EvalError::IncompatibleArrayDimensions { dims: None } => Some(
+                "Arrays with differing element dimensions are not compatible for concatenation."
+                    .into(),
+            ),
+            EvalError::IncompatibleArrayDimensions {
+                dims: Some((a_dims, b_dims)),
+            } => Some(format!(
+                "Arrays of {} and {} dimensions are not compatible for concatenation.",
+                a_dims, b_dims
+            )),
+            _ => None,
+        }
     }
 
     pub fn hint(&self) -> Option<String> {
@@ -1010,6 +1134,9 @@ impl EvalError {
             EvalError::InvalidBase64EndSequence => Some(
                 "Input data is missing padding, is truncated, or is otherwise corrupted.".into(),
             ),
+            EvalError::InvalidHour12(_) => {
+                Some("Use the 24-hour clock, or give an hour between 1 and 12.".into())
+            }
             _ => None,
         }
     }
@@ -1035,6 +1162,12 @@ impl From<InvalidArrayError> for EvalError {
     }
 }
 
+impl From<InvalidRangeError> for EvalError {
+    fn from(e: InvalidRangeError) -> EvalError {
+        EvalError::InvalidRange(e)
+    }
+}
+
 impl From<regex::Error> for EvalError {
     fn from(e: regex::Error) -> EvalError {
         EvalError::InvalidRegex(e.to_string())
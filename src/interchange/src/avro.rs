@@ -24,7 +24,8 @@ pub use self::encode::{
 };
 pub use self::envelope_debezium::{DebeziumDecodeState, DebeziumDeduplicationStrategy};
 pub use self::schema::{
-    parse_schema, validate_key_schema, validate_value_schema, ConfluentAvroResolver,
+    evolve_reader_schema, parse_schema, validate_key_schema, validate_value_schema,
+    ConfluentAvroResolver,
 };
 
 use self::decode::{AvroFlatDecoder, AvroStringDecoder, OptionalRecordDecoder, RowWrapper};
@@ -53,7 +54,8 @@ mod tests {
 
     use mz_avro::types::{DecimalValue, Value};
     use repr::adt::decimal::Significand;
-    use repr::{ColumnName, ColumnType, Datum, RelationDesc, ScalarType};
+    use repr::adt::range::RangeBound;
+    use repr::{ColumnName, ColumnType, Datum, RelationDesc, Row, ScalarType};
 
     use super::*;
 
@@ -97,6 +99,13 @@ mod tests {
         let date_time = NaiveDateTime::new(date, NaiveTime::from_hms(1, 1, 1));
         let bytes: Vec<u8> = vec![0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1];
         let string = String::from("test");
+        let mut range = Row::default();
+        range
+            .push_range(
+                RangeBound::new(Datum::Int32(1), true),
+                RangeBound::infinite(),
+            )
+            .unwrap();
 
         // Simple transformations from primitive Avro Schema types
         // to Avro Values.
@@ -145,6 +154,13 @@ mod tests {
                 Datum::String(&string),
                 Value::String(string.clone()),
             ),
+            (
+                ScalarType::Range {
+                    element_type: Box::new(ScalarType::Int32),
+                },
+                range.unpack_first(),
+                Value::String("[1,)".into()),
+            ),
         ];
         for (typ, datum, expected) in valid_pairings {
             let desc = RelationDesc::empty().with_named_column("column1", typ.nullable(false));
@@ -158,4 +174,45 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_evolve_reader_schema() -> anyhow::Result<()> {
+        let old = r#"{
+            "type": "record",
+            "name": "row",
+            "fields": [
+                {"name": "a", "type": "long"},
+                {"name": "b", "type": ["null", "string"]}
+            ]
+        }"#;
+        let new = r#"{
+            "type": "record",
+            "name": "row",
+            "fields": [
+                {"name": "a", "type": "long"},
+                {"name": "b", "type": "string", "default": ""},
+                {"name": "c", "type": "string"}
+            ]
+        }"#;
+        let evolved = super::evolve_reader_schema(old, new)?;
+        let columns = super::validate_value_schema(&evolved, EnvelopeType::None)?;
+        assert_eq!(
+            columns,
+            vec![
+                ("a".into(), ScalarType::Int64.nullable(false)),
+                ("b".into(), ScalarType::String.nullable(true)),
+                ("c".into(), ScalarType::String.nullable(true)),
+            ]
+        );
+
+        // Changing the type of a field is not a compatible change.
+        let incompatible = r#"{
+            "type": "record",
+            "name": "row",
+            "fields": [{"name": "a", "type": "boolean"}]
+        }"#;
+        assert!(super::evolve_reader_schema(old, incompatible).is_err());
+
+        Ok(())
+    }
 }
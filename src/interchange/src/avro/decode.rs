@@ -14,6 +14,7 @@ use std::fmt;
 use std::io::Read;
 use std::rc::Rc;
 
+use dec::OrderedDecimal;
 use ordered_float::OrderedFloat;
 use uuid::Uuid;
 
@@ -23,7 +24,8 @@ use mz_avro::{
     AvroRead, AvroRecordAccess, GeneralDeserializer, StatefulAvroDecodable, ValueDecoder,
     ValueOrReader,
 };
-use repr::adt::decimal::Significand;
+use repr::adt::apd;
+use repr::adt::decimal::{Significand, MAX_DECIMAL_PRECISION};
 use repr::adt::jsonb::JsonbPacker;
 use repr::{Datum, Row};
 
@@ -450,8 +452,8 @@ impl<'a> AvroDecode for AvroFlatDecoder<'a> {
     #[inline]
     fn decimal<'b, R: AvroRead>(
         self,
-        _precision: usize,
-        _scale: usize,
+        precision: usize,
+        scale: usize,
         r: ValueOrReader<'b, &'b [u8], R>,
     ) -> Result<Self::Out, AvroError> {
         let buf = match r {
@@ -462,10 +464,19 @@ impl<'a> AvroDecode for AvroFlatDecoder<'a> {
                 &self.buf
             }
         };
-        self.packer.push(Datum::Decimal(
-            Significand::from_twos_complement_be(buf)
-                .map_err(|e| DecodeError::Custom(e.to_string()))?,
-        ));
+        // See `validate_schema_2` for the mapping of decimal precisions to
+        // types.
+        if precision > MAX_DECIMAL_PRECISION as usize {
+            self.packer.push(Datum::APD(OrderedDecimal(
+                apd::twos_complement_be_to_apd(buf, scale)
+                    .map_err(|e| DecodeError::Custom(e.to_string()))?,
+            )));
+        } else {
+            self.packer.push(Datum::Decimal(
+                Significand::from_twos_complement_be(buf)
+                    .map_err(|e| DecodeError::Custom(e.to_string()))?,
+            ));
+        }
         Ok(())
     }
 
@@ -14,8 +14,9 @@ use byteorder::{NetworkEndian, WriteBytesExt};
 use chrono::Timelike;
 use itertools::Itertools;
 use lazy_static::lazy_static;
+use repr::adt::apd::{self, APD_AGG_MAX_PRECISION, APD_DATUM_MAX_PRECISION};
 use repr::adt::jsonb::JsonbRef;
-use repr::{ColumnName, ColumnType, Datum, RelationDesc, Row, ScalarType};
+use repr::{strconv, ColumnName, ColumnType, Datum, RelationDesc, Row, ScalarType};
 use serde_json::json;
 
 use mz_avro::types::{DecimalValue, Value};
@@ -292,12 +293,52 @@ impl<'a> mz_avro::types::ToAvro for TypedDatum<'a> {
                     buf
                 }),
                 ScalarType::Bytes => Value::Bytes(Vec::from(datum.unwrap_bytes())),
-                ScalarType::String => Value::String(datum.unwrap_str().to_owned()),
+                ScalarType::String | ScalarType::Char { .. } | ScalarType::VarChar { .. } => {
+                    Value::String(datum.unwrap_str().to_owned())
+                }
                 ScalarType::Jsonb => Value::Json(JsonbRef::from_datum(datum).to_serde_json()),
                 ScalarType::Uuid => Value::Uuid(datum.unwrap_uuid()),
+                ScalarType::Inet => {
+                    let mut buf = String::new();
+                    strconv::format_inet(&mut buf, datum.unwrap_inet());
+                    Value::String(buf)
+                }
+                ScalarType::Cidr => {
+                    let mut buf = String::new();
+                    strconv::format_cidr(&mut buf, datum.unwrap_inet());
+                    Value::String(buf)
+                }
                 ScalarType::Array(_t) => unimplemented!("array types"),
                 ScalarType::List { .. } => unimplemented!("list types"),
                 ScalarType::Map { .. } => unimplemented!("map types"),
+                // Avro has no range type, so ranges are written in their text
+                // format, just like network addresses.
+                ScalarType::Range { element_type } => {
+                    let mut buf = String::new();
+                    let parts =
+                        datum
+                            .unwrap_range()
+                            .bounds()
+                            .map(|(lower, upper)| strconv::RangeParts {
+                                lower: lower.value,
+                                lower_inclusive: lower.inclusive,
+                                upper: upper.value,
+                                upper_inclusive: upper.inclusive,
+                            });
+                    strconv::format_range(&mut buf, parts, |buf, d| match &**element_type {
+                        ScalarType::Int32 => strconv::format_int32(buf, d.unwrap_int32()),
+                        ScalarType::Int64 => strconv::format_int64(buf, d.unwrap_int64()),
+                        ScalarType::Date => strconv::format_date(buf, d.unwrap_date()),
+                        ScalarType::Timestamp => {
+                            strconv::format_timestamp(buf, d.unwrap_timestamp())
+                        }
+                        ScalarType::TimestampTz => {
+                            strconv::format_timestamptz(buf, d.unwrap_timestamptz())
+                        }
+                        _ => unreachable!("invalid range element type {:?}", element_type),
+                    });
+                    Value::String(buf)
+                }
                 ScalarType::Record { fields, .. } => {
                     let list = datum.unwrap_list();
                     let fields = fields
@@ -312,7 +353,17 @@ impl<'a> mz_avro::types::ToAvro for TypedDatum<'a> {
                         .collect();
                     Value::Record(fields)
                 }
-                ScalarType::APD { .. } => unreachable!(),
+                ScalarType::APD { scale } => {
+                    let (precision, scale) = apd_precision_and_scale(*scale);
+                    let n = datum.unwrap_apd().0;
+                    Value::Decimal(DecimalValue {
+                        unscaled: apd::apd_to_twos_complement_be(n, scale).unwrap_or_else(|e| {
+                            panic!("cannot encode {} as an Avro decimal: {}", n, e)
+                        }),
+                        precision,
+                        scale: scale.into(),
+                    })
+                }
             };
             if typ.nullable {
                 val = Value::Union {
@@ -404,7 +455,9 @@ pub(super) fn build_row_schema_fields<F: FnMut() -> String>(
                 "logicalType": "duration"
             }),
             ScalarType::Bytes => json!("bytes"),
-            ScalarType::String => json!("string"),
+            ScalarType::String | ScalarType::Char { .. } | ScalarType::VarChar { .. } => {
+                json!("string")
+            }
             ScalarType::Jsonb => json!({
                 "type": "string",
                 "connect.name": "io.debezium.data.Json",
@@ -413,6 +466,7 @@ pub(super) fn build_row_schema_fields<F: FnMut() -> String>(
                 "type": "string",
                 "logicalType": "uuid",
             }),
+            ScalarType::Inet | ScalarType::Cidr | ScalarType::Range { .. } => json!("string"),
             ScalarType::Array(_t) => unimplemented!("array types"),
             ScalarType::List { .. } => unimplemented!("list types"),
             ScalarType::Map { .. } => unimplemented!("map types"),
@@ -437,8 +491,14 @@ pub(super) fn build_row_schema_fields<F: FnMut() -> String>(
                     })
                 }
             }
-            ScalarType::APD { .. } => {
-                unreachable!("TBD: how to determine the scale of these values")
+            ScalarType::APD { scale } => {
+                let (precision, scale) = apd_precision_and_scale(*scale);
+                json!({
+                    "type": "bytes",
+                    "logicalType": "decimal",
+                    "precision": precision,
+                    "scale": scale,
+                })
             }
         };
         if typ.nullable {
@@ -452,6 +512,19 @@ pub(super) fn build_row_schema_fields<F: FnMut() -> String>(
     fields
 }
 
+/// Returns the precision and scale of the Avro decimal that encodes
+/// [`ScalarType::APD`] values of the given scale.
+///
+/// Values without a fixed scale are encoded with the largest scale an APD
+/// datum can have, which leaves the largest APD datum needing more than
+/// [`APD_DATUM_MAX_PRECISION`] digits of precision.
+fn apd_precision_and_scale(scale: Option<u8>) -> (usize, u8) {
+    match scale {
+        Some(scale) => (APD_DATUM_MAX_PRECISION, scale),
+        None => (APD_AGG_MAX_PRECISION, APD_DATUM_MAX_PRECISION as u8),
+    }
+}
+
 /// Builds the JSON for the row schema, which can be independently useful.
 pub(super) fn build_row_schema_json(
     columns: &[(ColumnName, ColumnType)],
@@ -20,6 +20,7 @@ use byteorder::{BigEndian, ByteOrder};
 use mz_avro::error::Error as AvroError;
 use mz_avro::schema::{resolve_schemas, Schema, SchemaNode, SchemaPiece, SchemaPieceOrNamed};
 use ore::retry::Retry;
+use repr::adt::apd::APD_DATUM_MAX_PRECISION;
 use repr::adt::decimal::MAX_DECIMAL_PRECISION;
 use repr::{ColumnName, ColumnType, RelationDesc, ScalarType};
 
@@ -156,6 +157,149 @@ pub fn validate_value_schema(
     validate_schema_1(row_schema)
 }
 
+/// Prepares `new`, a newer version of the Avro schema `old`, for use as the
+/// reader schema of a source that currently reads with `old`.
+///
+/// Fields that `new` adds to the records of `old` are made nullable with a
+/// default of `null`, so that records written with older schemas, which lack
+/// those fields, remain readable. Fields that are nullable in `old` stay
+/// nullable. Returns an error if data written with `old` cannot be read with
+/// the resulting schema.
+pub fn evolve_reader_schema(old: &str, new: &str) -> anyhow::Result<String> {
+    let mut old_json: serde_json::Value = serde_json::from_str(old)?;
+    let mut new_json: serde_json::Value = serde_json::from_str(new)?;
+
+    // Maps the full name of each record in `old` to the nullability of each
+    // of its fields.
+    let mut old_records = HashMap::new();
+    visit_records(&mut old_json, "", &mut |name, fields| {
+        let fields: HashMap<_, _> = fields
+            .iter()
+            .filter_map(|field| {
+                let name = field.get("name")?.as_str()?.to_owned();
+                let nullable = match field.get("type") {
+                    Some(serde_json::Value::Array(variants)) => {
+                        variants.iter().any(|v| *v == "null")
+                    }
+                    _ => false,
+                };
+                Some((name, nullable))
+            })
+            .collect();
+        old_records.insert(name.to_owned(), fields);
+    });
+
+    visit_records(&mut new_json, "", &mut |name, fields| {
+        if let Some(old_fields) = old_records.get(name) {
+            for field in fields {
+                let field_name = field.get("name").and_then(|n| n.as_str());
+                let make_nullable = match field_name.map(|n| old_fields.get(n)) {
+                    Some(None) | Some(Some(true)) => true,
+                    None | Some(Some(false)) => false,
+                };
+                if make_nullable {
+                    make_field_nullable(field);
+                }
+            }
+        }
+    });
+
+    let evolved = new_json.to_string();
+    let writer_schema = parse_schema(old)?;
+    let reader_schema = parse_schema(&evolved)?;
+    resolve_schemas(&writer_schema, &reader_schema).map_err(|e| {
+        anyhow!(
+            "new schema cannot read data written with the current schema: {}",
+            e
+        )
+    })?;
+    Ok(evolved)
+}
+
+/// Invokes `f` with the full name and the fields of each record defined in
+/// the JSON representation of an Avro schema, innermost records first.
+fn visit_records(
+    schema: &mut serde_json::Value,
+    namespace: &str,
+    f: &mut dyn FnMut(&str, &mut Vec<serde_json::Value>),
+) {
+    match schema {
+        serde_json::Value::Array(variants) => {
+            for variant in variants {
+                visit_records(variant, namespace, f);
+            }
+        }
+        serde_json::Value::Object(object) => {
+            let ty = object
+                .get("type")
+                .and_then(|t| t.as_str())
+                .map(String::from);
+            match ty.as_deref() {
+                Some("record") | Some("error") => {
+                    let name = object
+                        .get("name")
+                        .and_then(|n| n.as_str())
+                        .unwrap_or_default();
+                    let (full_name, namespace) = match name.rfind('.') {
+                        Some(i) => (name.to_owned(), name[..i].to_owned()),
+                        None => {
+                            let namespace = object
+                                .get("namespace")
+                                .and_then(|n| n.as_str())
+                                .unwrap_or(namespace)
+                                .to_owned();
+                            if namespace.is_empty() {
+                                (name.to_owned(), namespace)
+                            } else {
+                                (format!("{}.{}", namespace, name), namespace)
+                            }
+                        }
+                    };
+                    if let Some(serde_json::Value::Array(fields)) = object.get_mut("fields") {
+                        for field in fields.iter_mut() {
+                            if let Some(ty) = field.get_mut("type") {
+                                visit_records(ty, &namespace, f);
+                            }
+                        }
+                        f(&full_name, fields);
+                    }
+                }
+                Some("array") => {
+                    if let Some(items) = object.get_mut("items") {
+                        visit_records(items, namespace, f);
+                    }
+                }
+                Some("map") => {
+                    if let Some(values) = object.get_mut("values") {
+                        visit_records(values, namespace, f);
+                    }
+                }
+                _ => (),
+            }
+        }
+        _ => (),
+    }
+}
+
+/// Makes the JSON representation of an Avro record field nullable, with a
+/// default of `null`.
+fn make_field_nullable(field: &mut serde_json::Value) {
+    let field = match field.as_object_mut() {
+        Some(field) => field,
+        None => return,
+    };
+    let mut variants = match field.remove("type") {
+        Some(serde_json::Value::Array(variants)) => variants,
+        Some(ty) => vec![ty],
+        None => vec![],
+    };
+    // A default must match the first variant of a union.
+    variants.retain(|v| *v != "null");
+    variants.insert(0, serde_json::Value::from("null"));
+    field.insert("type".into(), serde_json::Value::Array(variants));
+    field.insert("default".into(), serde_json::Value::Null);
+}
+
 fn validate_schema_1(schema: SchemaNode) -> anyhow::Result<Vec<(ColumnName, ColumnType)>> {
     match schema.inner {
         SchemaPiece::Record { fields, .. } => {
@@ -248,13 +392,20 @@ fn validate_schema_2(
         SchemaPiece::Decimal {
             precision, scale, ..
         } => {
-            if *precision > MAX_DECIMAL_PRECISION as usize {
+            if *precision > APD_DATUM_MAX_PRECISION {
                 bail!(
                     "decimals with precision greater than {} are not supported",
-                    MAX_DECIMAL_PRECISION
+                    APD_DATUM_MAX_PRECISION
                 )
+            } else if *precision > MAX_DECIMAL_PRECISION as usize {
+                // Decimals too wide for the fixed-precision representation
+                // are decoded as APDs instead.
+                ScalarType::APD {
+                    scale: Some(*scale as u8),
+                }
+            } else {
+                ScalarType::Decimal(*precision as u8, *scale as u8)
             }
-            ScalarType::Decimal(*precision as u8, *scale as u8)
         }
         SchemaPiece::Bytes | SchemaPiece::Fixed { .. } => ScalarType::Bytes,
         SchemaPiece::String | SchemaPiece::Enum { .. } => ScalarType::String,
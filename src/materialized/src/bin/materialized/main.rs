@@ -89,9 +89,9 @@ struct Args {
     #[structopt(long, hidden = true)]
     safe: bool,
 
-    /// Enable persistent tables. Has to be used with --experimental.
-    #[structopt(long)]
-    persistent_tables: bool,
+    /// Disable persistent tables.
+    #[structopt(long, hidden = true)]
+    disable_persistent_tables: bool,
 
     // === Timely worker configuration. ===
     /// Number of dataflow worker threads.
@@ -399,26 +399,13 @@ fn run(args: Args) -> Result<(), anyhow::Error> {
         None
     };
 
-    let persistence = if args.experimental && args.persistent_tables {
-        let traces_path = data_directory.join("table_traces");
-        let wals_path = data_directory.join("table_wals");
-        fs::create_dir_all(&traces_path).with_context(|| {
-            format!(
-                "trying to create traces directory: {}",
-                traces_path.display()
-            )
-        })?;
-        fs::create_dir_all(&wals_path)
-            .with_context(|| format!("trying to create wal directory: {}", wals_path.display()))?;
-
-        Some(coord::PersistenceConfig {
-            traces_path,
-            wals_path,
-        })
-    } else if args.persistent_tables {
-        bail!("cannot specify --persistent-tables without --experimental");
-    } else {
+    let persistence = if args.disable_persistent_tables {
         None
+    } else {
+        let path = data_directory.join("persist");
+        fs::create_dir_all(&path)
+            .with_context(|| format!("trying to create persist directory: {}", path.display()))?;
+        Some(coord::PersistenceConfig { path })
     };
 
     // If --disable-telemetry is present, disable telemetry. Otherwise, if a
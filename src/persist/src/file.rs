@@ -0,0 +1,566 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! File-backed implementations for durable persistence on a local disk.
+
+use std::convert::TryInto;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::os::unix::io::AsRawFd;
+use std::path::{Path, PathBuf};
+
+use crate::error::Error;
+use crate::storage::{Blob, Buffer, SeqNo};
+
+/// The name of the lock file in each directory managed by this module.
+const LOCK_FILENAME: &str = "LOCK";
+
+/// The name of the log file in a [FileBuffer] directory.
+const BUFFER_FILENAME: &str = "buffer";
+
+/// The size of the [FileBuffer] log file header, which holds the [SeqNo] of
+/// the first entry in the log.
+const BUFFER_HEADER_LEN: u64 = 8;
+
+/// The size of the header of each [FileBuffer] entry, which holds the length
+/// and checksum of the entry's payload.
+const ENTRY_HEADER_LEN: u64 = 8;
+
+/// A file-backed implementation of [Buffer].
+///
+/// Entries are appended to a single log file and fsync'd before
+/// [Buffer::write_sync] returns. Each entry is prefixed with its length and a
+/// CRC32 checksum of its contents, so that an entry torn by a crash during a
+/// write can be detected and discarded when the buffer is reopened.
+pub struct FileBuffer {
+    base_dir: PathBuf,
+    file: File,
+    // The SeqNo of the first entry in the log file.
+    seqno_offset: u64,
+    // The SeqNo of the next entry to be written.
+    next_seqno: u64,
+    // The length of the valid portion of the log file.
+    len: u64,
+    _lock: LockFile,
+}
+
+impl FileBuffer {
+    /// Opens the buffer stored in `base_dir`, creating it if necessary.
+    ///
+    /// Returns an error if the directory is in use by another buffer, in this
+    /// process or any other.
+    pub fn new<P: AsRef<Path>>(base_dir: P) -> Result<Self, Error> {
+        let base_dir = base_dir.as_ref().to_path_buf();
+        fs::create_dir_all(&base_dir)?;
+        let lock = LockFile::new(&base_dir)?;
+
+        let path = base_dir.join(BUFFER_FILENAME);
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .open(&path)?;
+        if file.metadata()?.len() < BUFFER_HEADER_LEN {
+            // Either a new log or a crash while writing the header of a new
+            // log, which means nothing was ever written to it.
+            file.set_len(0)?;
+            file.write_all(&0u64.to_le_bytes())?;
+            file.sync_all()?;
+            sync_dir(&base_dir)?;
+        }
+
+        let mut buf = Vec::new();
+        file.seek(SeekFrom::Start(0))?;
+        file.read_to_end(&mut buf)?;
+        let seqno_offset = read_u64(&buf[..BUFFER_HEADER_LEN as usize]);
+        let mut entries = 0;
+        let len = scan_entries(&buf, |_| {
+            entries += 1;
+            Ok(())
+        })?;
+        if len < buf.len() as u64 {
+            // The tail of the log is a torn write from a crash. It was never
+            // acknowledged, so it's safe to discard.
+            file.set_len(len)?;
+            file.sync_all()?;
+        }
+        file.seek(SeekFrom::Start(len))?;
+
+        Ok(FileBuffer {
+            base_dir,
+            file,
+            seqno_offset,
+            next_seqno: seqno_offset + entries,
+            len,
+            _lock: lock,
+        })
+    }
+}
+
+impl Buffer for FileBuffer {
+    fn write_sync(&mut self, buf: Vec<u8>) -> Result<SeqNo, Error> {
+        if buf.len() > u32::MAX as usize {
+            return Err(format!("buffer entry too large: {} bytes", buf.len()).into());
+        }
+        let mut entry = Vec::with_capacity(ENTRY_HEADER_LEN as usize + buf.len());
+        encode_entry(&mut entry, &buf);
+
+        let res = self
+            .file
+            .seek(SeekFrom::Start(self.len))
+            .and_then(|_| self.file.write_all(&entry))
+            .and_then(|_| self.file.sync_data());
+        if let Err(e) = res {
+            // Don't leave a partial entry behind for the next write to append
+            // to. If this also fails, the torn entry will be discarded when the
+            // buffer is reopened.
+            let _ = self.file.set_len(self.len);
+            let _ = self.file.seek(SeekFrom::Start(self.len));
+            return Err(e.into());
+        }
+        self.len += entry.len() as u64;
+        let seqno = SeqNo(self.next_seqno);
+        self.next_seqno += 1;
+        Ok(seqno)
+    }
+
+    fn snapshot<F>(&self, mut logic: F) -> Result<(), Error>
+    where
+        F: FnMut(SeqNo, &[u8]) -> Result<(), Error>,
+    {
+        let mut buf = Vec::with_capacity(self.len as usize);
+        let mut file = &self.file;
+        file.seek(SeekFrom::Start(0))?;
+        file.take(self.len).read_to_end(&mut buf)?;
+
+        let mut seqno = self.seqno_offset;
+        scan_entries(&buf, |entry| {
+            logic(SeqNo(seqno), entry)?;
+            seqno += 1;
+            Ok(())
+        })?;
+        Ok(())
+    }
+
+    fn truncate(&mut self, upper: SeqNo) -> Result<(), Error> {
+        if upper.0 <= self.seqno_offset {
+            return Ok(());
+        }
+        let upper = std::cmp::min(upper.0, self.next_seqno);
+
+        // Copy the entries that remain into a new log and atomically swap it
+        // in for the old one.
+        let mut contents = upper.to_le_bytes().to_vec();
+        let mut seqno = self.seqno_offset;
+        self.snapshot(|_, entry| {
+            if seqno >= upper {
+                encode_entry(&mut contents, entry);
+            }
+            seqno += 1;
+            Ok(())
+        })?;
+        let path = self.base_dir.join(BUFFER_FILENAME);
+        write_atomic(&self.base_dir, &path, &contents)?;
+
+        self.file = OpenOptions::new().read(true).write(true).open(&path)?;
+        self.seqno_offset = upper;
+        self.len = contents.len() as u64;
+        Ok(())
+    }
+}
+
+/// A file-backed implementation of [Blob].
+///
+/// Each key is stored as a file in a single directory. Values are written to
+/// a temporary file, fsync'd, and then renamed into place, so a crash never
+/// leaves behind a partially written value.
+pub struct FileBlob {
+    base_dir: PathBuf,
+    _lock: LockFile,
+}
+
+impl FileBlob {
+    /// Opens the blob stored in `base_dir`, creating it if necessary.
+    ///
+    /// Returns an error if the directory is in use by another blob, in this
+    /// process or any other.
+    pub fn new<P: AsRef<Path>>(base_dir: P) -> Result<Self, Error> {
+        let base_dir = base_dir.as_ref().to_path_buf();
+        fs::create_dir_all(&base_dir)?;
+        let lock = LockFile::new(&base_dir)?;
+        Ok(FileBlob {
+            base_dir,
+            _lock: lock,
+        })
+    }
+
+    fn path(&self, key: &str) -> Result<PathBuf, Error> {
+        // Keys are used directly as file names, so they must not be able to
+        // escape the directory or collide with the files this module manages.
+        if key.is_empty()
+            || key == LOCK_FILENAME
+            || key.starts_with('.')
+            || key.contains(std::path::is_separator)
+        {
+            return Err(format!("invalid blob key: {}", key).into());
+        }
+        Ok(self.base_dir.join(key))
+    }
+}
+
+impl Blob for FileBlob {
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>, Error> {
+        match fs::read(self.path(key)?) {
+            Ok(value) => Ok(Some(value)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn set(&mut self, key: &str, value: Vec<u8>, allow_overwrite: bool) -> Result<(), Error> {
+        let path = self.path(key)?;
+        if !allow_overwrite && path.exists() {
+            return Err(format!("not allowed to overwrite: {}", key).into());
+        }
+        write_atomic(&self.base_dir, &path, &value)
+    }
+
+    fn delete(&mut self, key: &str) -> Result<(), Error> {
+        match fs::remove_file(self.path(key)?) {
+            Ok(()) => sync_dir(&self.base_dir),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+/// An exclusive advisory lock on a directory, held until dropped.
+///
+/// The lock is released by the operating system if the process exits, so a
+/// crash never leaves a directory permanently locked.
+struct LockFile {
+    _file: File,
+}
+
+impl LockFile {
+    fn new(base_dir: &Path) -> Result<Self, Error> {
+        let path = base_dir.join(LOCK_FILENAME);
+        let file = OpenOptions::new().write(true).create(true).open(&path)?;
+        // SAFETY: flock is safe to call with any file descriptor.
+        let ret = unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX | libc::LOCK_NB) };
+        if ret != 0 {
+            let err = io::Error::last_os_error();
+            if err.kind() == io::ErrorKind::WouldBlock {
+                return Err(format!("{} is in use by another process", base_dir.display()).into());
+            }
+            return Err(err.into());
+        }
+        Ok(LockFile { _file: file })
+    }
+}
+
+/// Calls `logic` with the payload of each valid entry in an encoded
+/// [FileBuffer] log, returning the length of the valid prefix of the log.
+///
+/// An incomplete or corrupt final entry marks the end of the log. A corrupt
+/// entry anywhere else is an error.
+fn scan_entries<F>(buf: &[u8], mut logic: F) -> Result<u64, Error>
+where
+    F: FnMut(&[u8]) -> Result<(), Error>,
+{
+    let mut offset = BUFFER_HEADER_LEN as usize;
+    while offset < buf.len() {
+        let header_end = offset + ENTRY_HEADER_LEN as usize;
+        if header_end > buf.len() {
+            break;
+        }
+        let len_bytes = &buf[offset..offset + 4];
+        let checksum = read_u32(&buf[offset + 4..header_end]);
+        let entry_end = header_end + read_u32(len_bytes) as usize;
+        if entry_end > buf.len() {
+            break;
+        }
+        let entry = &buf[header_end..entry_end];
+        if entry_checksum(len_bytes, entry) != checksum {
+            if entry_end == buf.len() {
+                break;
+            }
+            return Err(format!("corrupt buffer entry at offset {}", offset).into());
+        }
+        logic(entry)?;
+        offset = entry_end;
+    }
+    Ok(offset as u64)
+}
+
+/// Appends the encoding of a [FileBuffer] entry to `buf`.
+///
+/// The payload must be no more than `u32::MAX` bytes long.
+fn encode_entry(buf: &mut Vec<u8>, entry: &[u8]) {
+    let len_bytes = (entry.len() as u32).to_le_bytes();
+    buf.extend_from_slice(&len_bytes);
+    buf.extend_from_slice(&entry_checksum(&len_bytes, entry).to_le_bytes());
+    buf.extend_from_slice(entry);
+}
+
+/// Computes the checksum of a [FileBuffer] entry.
+///
+/// The length is included so that a run of zeros, which some filesystems can
+/// leave behind after a crash, is not mistaken for a valid empty entry.
+fn entry_checksum(len_bytes: &[u8], entry: &[u8]) -> u32 {
+    let mut hasher = crc32fast::Hasher::new();
+    hasher.update(len_bytes);
+    hasher.update(entry);
+    hasher.finalize()
+}
+
+/// Durably replaces the contents of `path`, which must be in `base_dir`.
+fn write_atomic(base_dir: &Path, path: &Path, contents: &[u8]) -> Result<(), Error> {
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| Error::from(format!("invalid path: {}", path.display())))?;
+    let tmp_path = base_dir.join(format!(".{}.tmp", file_name.to_string_lossy()));
+    let mut tmp = File::create(&tmp_path)?;
+    tmp.write_all(contents)?;
+    tmp.sync_all()?;
+    fs::rename(&tmp_path, path)?;
+    sync_dir(base_dir)
+}
+
+/// Fsyncs a directory, making any renames, creations, and deletions of its
+/// entries durable.
+fn sync_dir(dir: &Path) -> Result<(), Error> {
+    File::open(dir)?.sync_all()?;
+    Ok(())
+}
+
+fn read_u32(buf: &[u8]) -> u32 {
+    u32::from_le_bytes(buf.try_into().expect("4 bytes"))
+}
+
+fn read_u64(buf: &[u8]) -> u64 {
+    u64::from_le_bytes(buf.try_into().expect("8 bytes"))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::persister::{Meta, Persister, Snapshot, Write as _};
+    use crate::storage::StoragePersister;
+    use crate::Id;
+
+    use super::*;
+
+    fn entries(buffer: &FileBuffer) -> Result<Vec<(u64, Vec<u8>)>, Error> {
+        let mut entries = Vec::new();
+        buffer.snapshot(|seqno, buf| {
+            entries.push((seqno.0, buf.to_vec()));
+            Ok(())
+        })?;
+        Ok(entries)
+    }
+
+    fn file_len(path: &Path) -> u64 {
+        fs::metadata(path).expect("file exists").len()
+    }
+
+    #[test]
+    fn file_buffer() -> Result<(), Error> {
+        let dir = tempfile::tempdir()?;
+        let mut buffer = FileBuffer::new(dir.path())?;
+        assert_eq!(buffer.write_sync(b"a".to_vec())?, SeqNo(0));
+        assert_eq!(buffer.write_sync(b"bb".to_vec())?, SeqNo(1));
+        assert_eq!(buffer.write_sync(b"ccc".to_vec())?, SeqNo(2));
+        assert_eq!(
+            entries(&buffer)?,
+            vec![
+                (0, b"a".to_vec()),
+                (1, b"bb".to_vec()),
+                (2, b"ccc".to_vec())
+            ]
+        );
+
+        buffer.truncate(SeqNo(2))?;
+        assert_eq!(entries(&buffer)?, vec![(2, b"ccc".to_vec())]);
+        assert_eq!(buffer.write_sync(b"d".to_vec())?, SeqNo(3));
+
+        // Reopening sees the same entries and continues the sequence.
+        drop(buffer);
+        let mut buffer = FileBuffer::new(dir.path())?;
+        assert_eq!(
+            entries(&buffer)?,
+            vec![(2, b"ccc".to_vec()), (3, b"d".to_vec())]
+        );
+        assert_eq!(buffer.write_sync(b"e".to_vec())?, SeqNo(4));
+        buffer.truncate(SeqNo(5))?;
+        assert_eq!(entries(&buffer)?, vec![]);
+        Ok(())
+    }
+
+    #[test]
+    fn file_buffer_lock() -> Result<(), Error> {
+        let dir = tempfile::tempdir()?;
+        let buffer = FileBuffer::new(dir.path())?;
+        assert!(FileBuffer::new(dir.path()).is_err());
+        drop(buffer);
+        FileBuffer::new(dir.path())?;
+        Ok(())
+    }
+
+    #[test]
+    fn file_buffer_torn_write() -> Result<(), Error> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join(BUFFER_FILENAME);
+        let mut buffer = FileBuffer::new(dir.path())?;
+        buffer.write_sync(b"first".to_vec())?;
+        let good_len = file_len(&path);
+        buffer.write_sync(b"second".to_vec())?;
+        let full_len = file_len(&path);
+        drop(buffer);
+
+        // Simulate a crash at every point during the write of the second
+        // entry, including partway through its header.
+        for len in good_len..full_len {
+            let file = OpenOptions::new().write(true).open(&path)?;
+            file.set_len(len)?;
+            drop(file);
+
+            let mut buffer = FileBuffer::new(dir.path())?;
+            assert_eq!(entries(&buffer)?, vec![(0, b"first".to_vec())]);
+            assert_eq!(file_len(&path), good_len);
+            // The torn entry's SeqNo is reused, since it was never
+            // acknowledged.
+            assert_eq!(buffer.write_sync(b"second".to_vec())?, SeqNo(1));
+            assert_eq!(file_len(&path), full_len);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn file_buffer_corruption() -> Result<(), Error> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join(BUFFER_FILENAME);
+        let mut buffer = FileBuffer::new(dir.path())?;
+        buffer.write_sync(b"first".to_vec())?;
+        buffer.write_sync(b"second".to_vec())?;
+        drop(buffer);
+
+        // A garbled final entry is indistinguishable from a torn write, and is
+        // discarded.
+        let mut contents = fs::read(&path)?;
+        let last = contents.len() - 1;
+        contents[last] ^= 0xff;
+        fs::write(&path, &contents)?;
+        let buffer = FileBuffer::new(dir.path())?;
+        assert_eq!(entries(&buffer)?, vec![(0, b"first".to_vec())]);
+        drop(buffer);
+
+        // A garbled entry followed by valid entries is an error, because those
+        // entries were acknowledged.
+        let mut buffer = FileBuffer::new(dir.path())?;
+        buffer.write_sync(b"second".to_vec())?;
+        drop(buffer);
+        let mut contents = fs::read(&path)?;
+        contents[BUFFER_HEADER_LEN as usize + ENTRY_HEADER_LEN as usize] ^= 0xff;
+        fs::write(&path, &contents)?;
+        assert!(FileBuffer::new(dir.path()).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn file_buffer_crash_during_truncate() -> Result<(), Error> {
+        let dir = tempfile::tempdir()?;
+        let mut buffer = FileBuffer::new(dir.path())?;
+        buffer.write_sync(b"a".to_vec())?;
+        buffer.write_sync(b"b".to_vec())?;
+        drop(buffer);
+
+        // A crash before the rename leaves behind a partially written
+        // temporary file, which doesn't affect the log.
+        let tmp_path = dir.path().join(format!(".{}.tmp", BUFFER_FILENAME));
+        fs::write(&tmp_path, b"garbage")?;
+        let mut buffer = FileBuffer::new(dir.path())?;
+        assert_eq!(
+            entries(&buffer)?,
+            vec![(0, b"a".to_vec()), (1, b"b".to_vec())]
+        );
+        buffer.truncate(SeqNo(1))?;
+        assert_eq!(entries(&buffer)?, vec![(1, b"b".to_vec())]);
+        Ok(())
+    }
+
+    #[test]
+    fn file_blob() -> Result<(), Error> {
+        let dir = tempfile::tempdir()?;
+        let mut blob = FileBlob::new(dir.path())?;
+        assert_eq!(blob.get("k0")?, None);
+        blob.set("k0", b"v0".to_vec(), false)?;
+        assert_eq!(blob.get("k0")?, Some(b"v0".to_vec()));
+        assert!(blob.set("k0", b"v1".to_vec(), false).is_err());
+        blob.set("k0", b"v1".to_vec(), true)?;
+        assert_eq!(blob.get("k0")?, Some(b"v1".to_vec()));
+        blob.delete("k0")?;
+        assert_eq!(blob.get("k0")?, None);
+        blob.delete("k0")?;
+
+        for key in &["", LOCK_FILENAME, ".hidden", "a/b", "../escape"] {
+            assert!(blob.set(key, vec![], true).is_err());
+        }
+
+        // A second blob can't share the directory until the first is dropped.
+        blob.set("k1", b"v1".to_vec(), false)?;
+        assert!(FileBlob::new(dir.path()).is_err());
+        drop(blob);
+        let blob = FileBlob::new(dir.path())?;
+        assert_eq!(blob.get("k1")?, Some(b"v1".to_vec()));
+        Ok(())
+    }
+
+    #[test]
+    fn file_blob_crash_during_set() -> Result<(), Error> {
+        let dir = tempfile::tempdir()?;
+        let mut blob = FileBlob::new(dir.path())?;
+        blob.set("k", b"old".to_vec(), false)?;
+
+        // A crash before the rename leaves behind a truncated temporary file,
+        // but the previous value is intact.
+        fs::write(dir.path().join(".k.tmp"), b"ne")?;
+        drop(blob);
+        let mut blob = FileBlob::new(dir.path())?;
+        assert_eq!(blob.get("k")?, Some(b"old".to_vec()));
+        blob.set("k", b"new".to_vec(), true)?;
+        assert_eq!(blob.get("k")?, Some(b"new".to_vec()));
+        Ok(())
+    }
+
+    #[test]
+    fn file_storage_persister_restart() -> Result<(), Error> {
+        let dir = tempfile::tempdir()?;
+        let update = |key: &str, ts| ((key.as_bytes().to_vec(), vec![]), ts, 1);
+        let open = || -> Result<_, Error> {
+            StoragePersister::new(
+                FileBuffer::new(dir.path().join("buffer"))?,
+                FileBlob::new(dir.path().join("blob"))?,
+            )
+        };
+
+        let mut p = open()?;
+        let (mut write, _) = p.create_or_load(Id(1))?.into_inner();
+        write.write_sync(&[update("a", 1), update("b", 2)])?;
+        drop((p, write));
+
+        let mut p = open()?;
+        let (_, meta) = p.create_or_load(Id(1))?.into_inner();
+        let mut snap = meta.snapshot()?;
+        let mut actual = Vec::new();
+        while snap.read(&mut actual) {}
+        actual.sort();
+        assert_eq!(actual, vec![update("a", 1), update("b", 2)]);
+        Ok(())
+    }
+}
@@ -12,6 +12,7 @@
 #![warn(missing_docs)]
 
 pub mod error;
+pub mod file;
 pub mod mem;
 pub mod operators;
 pub mod persister;
@@ -23,7 +24,6 @@ use crate::error::Error;
 use crate::persister::Persister;
 
 // TODO
-// - Finish implementing StoragePersister.
 // - Should we hard-code the Key, Val, Time, Diff types everywhere or introduce
 //   them as type parameters? Materialize will only be using one combination of
 //   them (two with `()` vals?) but the generality might make things easier to
@@ -87,6 +87,14 @@ impl<P: Persister> PersistManager<P> {
     pub fn destroy(&mut self, id: Id) -> Result<(), Error> {
         self.persister.lock()?.destroy(id)
     }
+
+    /// A wrapper for [Persister::write_atomic].
+    pub fn write_atomic(
+        &mut self,
+        updates: &[(Id, Vec<((Vec<u8>, Vec<u8>), u64, isize)>)],
+    ) -> Result<(), Error> {
+        self.persister.lock()?.write_atomic(updates)
+    }
 }
 
 /// An exclusivity token needed to construct persistence [operators].
@@ -98,7 +106,12 @@ pub struct Token<W, M> {
 }
 
 impl<W, M> Token<W, M> {
-    fn into_inner(self) -> (W, M) {
+    /// Consumes this token, returning the underlying [persister::Write] and
+    /// [persister::Meta] handles.
+    ///
+    /// This is for users that persist data outside of a dataflow, like the
+    /// coordinator does for tables.
+    pub fn into_inner(self) -> (W, M) {
         (self.write, self.meta)
     }
 }
@@ -14,32 +14,50 @@ use std::sync::{Arc, Mutex};
 
 use crate::error::Error;
 use crate::persister::{Meta, Persister, Snapshot, Write};
-use crate::storage::{Blob, Buffer};
+use crate::storage::{Blob, Buffer, SeqNo};
 use crate::{Id, Token};
 
 /// An in-memory implementation of [Buffer].
 pub struct MemBuffer {
+    // The SeqNo of the first entry in dataz.
+    seqno_offset: u64,
     dataz: Vec<Vec<u8>>,
 }
 
 impl MemBuffer {
     /// Constructs a new, empty MemBuffer.
     pub fn new() -> Self {
-        MemBuffer { dataz: Vec::new() }
+        MemBuffer {
+            seqno_offset: 0,
+            dataz: Vec::new(),
+        }
     }
 }
 
 impl Buffer for MemBuffer {
-    fn write_sync(&mut self, buf: Vec<u8>) -> Result<(), Error> {
+    fn write_sync(&mut self, buf: Vec<u8>) -> Result<SeqNo, Error> {
+        let seqno = SeqNo(self.seqno_offset + self.dataz.len() as u64);
         self.dataz.push(buf);
-        Ok(())
+        Ok(seqno)
     }
 
     fn snapshot<F>(&self, mut logic: F) -> Result<(), Error>
     where
-        F: FnMut(&[u8]) -> Result<(), Error>,
+        F: FnMut(SeqNo, &[u8]) -> Result<(), Error>,
     {
-        self.dataz.iter().map(|x| logic(&x[..])).collect()
+        self.dataz
+            .iter()
+            .enumerate()
+            .map(|(idx, x)| logic(SeqNo(self.seqno_offset + idx as u64), &x[..]))
+            .collect()
+    }
+
+    fn truncate(&mut self, upper: SeqNo) -> Result<(), Error> {
+        let upper = upper.0.saturating_sub(self.seqno_offset) as usize;
+        let upper = std::cmp::min(upper, self.dataz.len());
+        self.dataz.drain(..upper);
+        self.seqno_offset += upper as u64;
+        Ok(())
     }
 }
 
@@ -58,8 +76,8 @@ impl MemBlob {
 }
 
 impl Blob for MemBlob {
-    fn get(&self, key: &str) -> Result<Option<&Vec<u8>>, Error> {
-        Ok(self.dataz.get(key))
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>, Error> {
+        Ok(self.dataz.get(key).cloned())
     }
 
     fn set(&mut self, key: &str, value: Vec<u8>, allow_overwrite: bool) -> Result<(), Error> {
@@ -72,6 +90,11 @@ impl Blob for MemBlob {
         };
         Ok(())
     }
+
+    fn delete(&mut self, key: &str) -> Result<(), Error> {
+        self.dataz.remove(key);
+        Ok(())
+    }
 }
 
 /// An in-memory implementation of [Persister].
@@ -129,12 +152,28 @@ impl Persister for MemPersister {
         }
         Ok(())
     }
+
+    fn write_atomic(
+        &mut self,
+        updates: &[(Id, Vec<((Vec<u8>, Vec<u8>), u64, isize)>)],
+    ) -> Result<(), Error> {
+        // Check every stream up front so that a failure writes nothing.
+        for (id, _) in updates {
+            if !self.dataz.contains_key(id) {
+                return Err(format!("internal error: {:?} not registered", id).into());
+            }
+        }
+        for (id, updates) in updates {
+            self.dataz[id].dataz.lock()?.extend_from_slice(updates);
+        }
+        Ok(())
+    }
 }
 
 /// An in-memory implementation of [Write] and [Meta].
 #[derive(Clone, Debug)]
 pub struct MemStream {
-    dataz: Arc<Mutex<Vec<((String, String), u64, isize)>>>,
+    dataz: Arc<Mutex<Vec<((Vec<u8>, Vec<u8>), u64, isize)>>>,
 }
 
 impl MemStream {
@@ -146,7 +185,7 @@ impl MemStream {
 }
 
 impl Write for MemStream {
-    fn write_sync(&mut self, updates: &[((String, String), u64, isize)]) -> Result<(), Error> {
+    fn write_sync(&mut self, updates: &[((Vec<u8>, Vec<u8>), u64, isize)]) -> Result<(), Error> {
         self.dataz.lock()?.extend_from_slice(&updates);
         Ok(())
     }
@@ -168,11 +207,11 @@ impl Meta for MemStream {
 
 /// An in-memory implementation of [Snapshot].
 pub struct MemSnapshot {
-    dataz: Vec<((String, String), u64, isize)>,
+    dataz: Vec<((Vec<u8>, Vec<u8>), u64, isize)>,
 }
 
 impl Snapshot for MemSnapshot {
-    fn read<E: Extend<((String, String), u64, isize)>>(&mut self, buf: &mut E) -> bool {
+    fn read<E: Extend<((Vec<u8>, Vec<u8>), u64, isize)>>(&mut self, buf: &mut E) -> bool {
         buf.extend(self.dataz.drain(..));
         false
     }
@@ -27,7 +27,7 @@ pub trait PersistentUnorderedInput<G: Scope<Timestamp = u64>> {
         token: Token<W, M>,
     ) -> (
         (PersistentUnorderedHandle, ActivateCapability<G::Timestamp>),
-        Stream<G, (String, u64, isize)>,
+        Stream<G, (Vec<u8>, u64, isize)>,
     );
 }
 
@@ -40,7 +40,7 @@ where
         token: Token<W, M>,
     ) -> (
         (PersistentUnorderedHandle, ActivateCapability<G::Timestamp>),
-        Stream<G, (String, u64, isize)>,
+        Stream<G, (Vec<u8>, u64, isize)>,
     ) {
         let ((handle, cap), stream) = self.new_unordered_input();
         let (write, meta) = token.into_inner();
@@ -68,7 +68,7 @@ where
 /// A persistent equivalent of [UnorderedHandle].
 pub struct PersistentUnorderedHandle {
     write: Box<dyn Write>,
-    handle: UnorderedHandle<u64, (String, u64, isize)>,
+    handle: UnorderedHandle<u64, (Vec<u8>, u64, isize)>,
 }
 
 impl PersistentUnorderedHandle {
@@ -91,17 +91,17 @@ pub struct PersistentUnorderedSession<'b> {
         AutoflushSession<
             'b,
             u64,
-            (String, u64, isize),
-            Counter<u64, (String, u64, isize), Tee<u64, (String, u64, isize)>>,
+            (Vec<u8>, u64, isize),
+            Counter<u64, (Vec<u8>, u64, isize), Tee<u64, (Vec<u8>, u64, isize)>>,
         >,
     >,
 }
 
 impl<'b> PersistentUnorderedSession<'b> {
     /// Transmits a single record after synchronously persisting it.
-    pub fn give(&mut self, data: (String, u64, isize)) {
+    pub fn give(&mut self, data: (Vec<u8>, u64, isize)) {
         self.write
-            .write_sync(&[((data.0.clone(), String::new()), data.1, data.2)])
+            .write_sync(&[((data.0.clone(), Vec::new()), data.1, data.2)])
             .expect("TODO");
         self.session.give(data);
     }
@@ -131,7 +131,7 @@ mod tests {
             });
             let mut session = handle.session(cap);
             for i in 1..=5 {
-                session.give((i.to_string(), i, 1));
+                session.give((i.to_string().into_bytes(), i, 1));
             }
             p.into_inner()
         });
@@ -146,7 +146,7 @@ mod tests {
             });
             let mut session = handle.session(cap);
             for i in 6..=9 {
-                session.give((i.to_string(), i, 1));
+                session.give((i.to_string().into_bytes(), i, 1));
             }
             recv
         });
@@ -157,7 +157,9 @@ mod tests {
             .flat_map(|(_, xs)| xs.into_iter().map(|x| x.0))
             .collect::<Vec<_>>();
         actual.sort();
-        let expected = (1usize..=9usize).map(|x| x.to_string()).collect::<Vec<_>>();
+        let expected = (1usize..=9usize)
+            .map(|x| x.to_string().into_bytes())
+            .collect::<Vec<_>>();
         assert_eq!(actual, expected);
 
         Ok(())
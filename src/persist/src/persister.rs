@@ -15,7 +15,7 @@ use crate::{Id, Token};
 /// An abstraction for a writer of (Key, Value, Time, Diff) updates.
 pub trait Write {
     /// Synchronously writes (Key, Value, Time, Diff) updates.
-    fn write_sync(&mut self, updates: &[((String, String), u64, isize)]) -> Result<(), Error>;
+    fn write_sync(&mut self, updates: &[((Vec<u8>, Vec<u8>), u64, isize)]) -> Result<(), Error>;
 }
 
 /// An isolated, consistent read of previously written (Key, Value, Time, Diff)
@@ -24,7 +24,7 @@ pub trait Snapshot {
     /// A partial read of the data in the snapshot.
     ///
     /// Returns true if read needs to be called again for more data.
-    fn read<E: Extend<((String, String), u64, isize)>>(&mut self, buf: &mut E) -> bool;
+    fn read<E: Extend<((Vec<u8>, Vec<u8>), u64, isize)>>(&mut self, buf: &mut E) -> bool;
 }
 
 /// A handle for a persisted stream.
@@ -61,4 +61,13 @@ pub trait Persister: Sized {
     ///
     /// TODO: Should this live on Meta?
     fn destroy(&mut self, id: Id) -> Result<(), Error>;
+
+    /// Synchronously writes (Key, Value, Time, Diff) updates to several
+    /// streams at once.
+    ///
+    /// Either all of the updates become durable or none of them do.
+    fn write_atomic(
+        &mut self,
+        updates: &[(Id, Vec<((Vec<u8>, Vec<u8>), u64, isize)>)],
+    ) -> Result<(), Error>;
 }
@@ -9,26 +9,590 @@
 
 //! Abstractions over files, cloud storage, etc used in persistence.
 
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::convert::TryInto;
+use std::sync::{Arc, Mutex};
+
 use crate::error::Error;
+use crate::persister::{Meta, Persister, Snapshot, Write};
+use crate::{Id, Token};
 
 /// An abstraction over a `bytes key->bytes value` store.
 pub trait Blob {
-    /// Returns a reference to the value corresponding to the key.
-    fn get(&self, key: &str) -> Result<Option<&Vec<u8>>, Error>;
+    /// Returns the value corresponding to the key.
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>, Error>;
 
     /// Inserts a key-value pair into the map.
     fn set(&mut self, key: &str, value: Vec<u8>, allow_overwrite: bool) -> Result<(), Error>;
+
+    /// Removes a key from the map.
+    ///
+    /// Succeeds if the key does not exist.
+    fn delete(&mut self, key: &str) -> Result<(), Error>;
 }
 
+/// The position of an entry in a [Buffer].
+#[derive(Debug, Default, Clone, Copy, PartialOrd, Ord, PartialEq, Eq, Hash)]
+pub struct SeqNo(pub u64);
+
 /// An abstraction over an append-only bytes log.
 pub trait Buffer {
-    /// Synchronously appends an entry.
-    fn write_sync(&mut self, buf: Vec<u8>) -> Result<(), Error>;
+    /// Synchronously appends an entry, returning its position in the log.
+    fn write_sync(&mut self, buf: Vec<u8>) -> Result<SeqNo, Error>;
 
-    /// Returns a consistent snapshot of all previously written entries.
+    /// Returns a consistent snapshot of all previously written entries that
+    /// have not been truncated.
     fn snapshot<F>(&self, logic: F) -> Result<(), Error>
     where
-        F: FnMut(&[u8]) -> Result<(), Error>;
+        F: FnMut(SeqNo, &[u8]) -> Result<(), Error>;
+
+    /// Removes all entries with a position less than `upper`.
+    fn truncate(&mut self, upper: SeqNo) -> Result<(), Error>;
+}
+
+/// The number of [Buffer] entries written between compactions.
+const COMPACTION_THRESHOLD: usize = 1024;
+
+type Updates = Vec<((Vec<u8>, Vec<u8>), u64, isize)>;
+
+/// The [Blob] key under which the [StoragePersister] metadata is stored.
+const META_KEY: &str = "META";
+
+/// An implementation of [Persister] in terms of a [Buffer] and [Blob].
+///
+/// Writes are immediately appended to the buffer. Periodically, the contents of
+/// the buffer are compacted into one consolidated batch per stream, which is
+/// stored in the blob, after which the buffer is truncated. The blob metadata
+/// is always written last, so a crash at any point leaves behind a consistent
+/// view of the data.
+pub struct StoragePersister<U, L> {
+    registered: HashSet<Id>,
+    inner: Arc<Mutex<StorageInner<U, L>>>,
+}
+
+impl<U: Buffer, L: Blob> StoragePersister<U, L> {
+    /// Constructs a StoragePersister, recovering any data previously written to
+    /// the given buffer and blob.
+    pub fn new(buffer: U, blob: L) -> Result<Self, Error> {
+        let meta = match blob.get(META_KEY)? {
+            Some(buf) => StorageMeta::decode(&buf)?,
+            None => StorageMeta::default(),
+        };
+        let inner = StorageInner {
+            buffer,
+            blob,
+            meta,
+            entries_since_compaction: 0,
+        };
+        Ok(StoragePersister {
+            registered: HashSet::new(),
+            inner: Arc::new(Mutex::new(inner)),
+        })
+    }
+}
+
+impl<U: Buffer + 'static, L: Blob + 'static> Persister for StoragePersister<U, L> {
+    type Write = StorageStream<U, L>;
+    type Meta = StorageStream<U, L>;
+
+    fn create_or_load(&mut self, id: Id) -> Result<Token<Self::Write, Self::Meta>, Error> {
+        if self.registered.contains(&id) {
+            return Err(format!("internal error: {:?} already registered", id).into());
+        }
+        self.registered.insert(id);
+        let mut inner = self.inner.lock()?;
+        if !inner.meta.streams.contains_key(&id) {
+            let mut meta = inner.meta.clone();
+            meta.streams.insert(id, StreamMeta::default());
+            inner.write_meta(meta)?;
+        }
+        let s = StorageStream {
+            id,
+            inner: self.inner.clone(),
+        };
+        Ok(Token {
+            write: s.clone(),
+            meta: s,
+        })
+    }
+
+    fn destroy(&mut self, id: Id) -> Result<(), Error> {
+        let mut inner = self.inner.lock()?;
+        let mut meta = inner.meta.clone();
+        let stream = match meta.streams.remove(&id) {
+            Some(stream) => stream,
+            None => return Err(format!("internal error: {:?} not registered", id).into()),
+        };
+        inner.write_meta(meta)?;
+        // Any updates for this stream still in the buffer are dropped during
+        // the next compaction.
+        if let Some(version) = stream.batch {
+            inner.blob.delete(&batch_key(id, version))?;
+        }
+        Ok(())
+    }
+
+    fn write_atomic(
+        &mut self,
+        updates: &[(Id, Vec<((Vec<u8>, Vec<u8>), u64, isize)>)],
+    ) -> Result<(), Error> {
+        let mut inner = self.inner.lock()?;
+        for (id, _) in updates {
+            if !inner.meta.streams.contains_key(id) {
+                return Err(format!("internal error: {:?} not registered", id).into());
+            }
+        }
+        // A single buffer entry is written atomically, so it carries the
+        // updates for every stream.
+        let entry = encode_entry(updates.iter().map(|(id, updates)| (*id, &updates[..])));
+        inner.append(entry)
+    }
+}
+
+/// An implementation of [Write] and [Meta] for [StoragePersister].
+pub struct StorageStream<U, L> {
+    id: Id,
+    inner: Arc<Mutex<StorageInner<U, L>>>,
+}
+
+// The derived Clone doesn't work because of the type parameters.
+impl<U, L> Clone for StorageStream<U, L> {
+    fn clone(&self) -> Self {
+        StorageStream {
+            id: self.id,
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<U: Buffer, L: Blob> Write for StorageStream<U, L> {
+    fn write_sync(&mut self, updates: &[((Vec<u8>, Vec<u8>), u64, isize)]) -> Result<(), Error> {
+        let mut inner = self.inner.lock()?;
+        inner.append(encode_entry(Some((self.id, updates))))
+    }
+}
+
+impl<U: Buffer, L: Blob> Meta for StorageStream<U, L> {
+    type Snapshot = StorageSnapshot;
+
+    fn snapshot(&self) -> Result<Self::Snapshot, Error> {
+        let inner = self.inner.lock()?;
+        let mut dataz = inner.read_streams()?.remove(&self.id).unwrap_or_default();
+        let since = inner.meta.streams.get(&self.id).map_or(0, |s| s.since);
+        consolidate(&mut dataz, since);
+        Ok(StorageSnapshot { dataz })
+    }
+
+    fn allow_compaction(&mut self, ts: u64) -> Result<(), Error> {
+        let mut inner = self.inner.lock()?;
+        let mut meta = inner.meta.clone();
+        match meta.streams.get_mut(&self.id) {
+            Some(stream) if stream.since < ts => stream.since = ts,
+            Some(_) => return Ok(()),
+            None => return Err(format!("internal error: {:?} not registered", self.id).into()),
+        }
+        inner.write_meta(meta)
+    }
+}
+
+/// An implementation of [Snapshot] for [StoragePersister].
+pub struct StorageSnapshot {
+    dataz: Vec<((Vec<u8>, Vec<u8>), u64, isize)>,
+}
+
+impl Snapshot for StorageSnapshot {
+    fn read<E: Extend<((Vec<u8>, Vec<u8>), u64, isize)>>(&mut self, buf: &mut E) -> bool {
+        buf.extend(self.dataz.drain(..));
+        false
+    }
+}
+
+struct StorageInner<U, L> {
+    buffer: U,
+    blob: L,
+    meta: StorageMeta,
+    entries_since_compaction: usize,
+}
+
+impl<U: Buffer, L: Blob> StorageInner<U, L> {
+    /// Durably replaces the metadata, committing whatever it references.
+    fn write_meta(&mut self, meta: StorageMeta) -> Result<(), Error> {
+        self.blob.set(META_KEY, meta.encode(), true)?;
+        self.meta = meta;
+        Ok(())
+    }
+
+    /// Durably appends an entry to the buffer, compacting if enough entries
+    /// have accumulated.
+    fn append(&mut self, entry: Vec<u8>) -> Result<(), Error> {
+        self.buffer.write_sync(entry)?;
+        self.entries_since_compaction += 1;
+        if self.entries_since_compaction >= COMPACTION_THRESHOLD {
+            self.compact()?;
+        }
+        Ok(())
+    }
+
+    /// Reads the unconsolidated contents of every live stream from the blob
+    /// batches and the untruncated portion of the buffer.
+    fn read_streams(&self) -> Result<HashMap<Id, Updates>, Error> {
+        let mut streams = HashMap::new();
+        for (id, stream) in self.meta.streams.iter() {
+            let mut dataz = Vec::new();
+            if let Some(version) = stream.batch {
+                let key = batch_key(*id, version);
+                let buf = self
+                    .blob
+                    .get(&key)?
+                    .ok_or_else(|| Error::from(format!("missing batch: {}", key)))?;
+                decode_updates(&mut Decoder::new(&buf), &mut dataz)?;
+            }
+            streams.insert(*id, dataz);
+        }
+        let compacted_upper = self.meta.compacted_upper;
+        self.buffer.snapshot(|seqno, buf| {
+            if seqno < compacted_upper {
+                // Already reflected in the blob batches, but the buffer was not
+                // truncated before a restart.
+                return Ok(());
+            }
+            let mut decoder = Decoder::new(buf);
+            for _ in 0..decoder.u64()? {
+                let id = Id(decoder.u64()?);
+                match streams.get_mut(&id) {
+                    Some(dataz) => decode_updates(&mut decoder, dataz)?,
+                    // The stream was destroyed, but its updates must still be
+                    // decoded to find those of the next stream.
+                    None => decode_updates(&mut decoder, &mut Vec::new())?,
+                }
+            }
+            Ok(())
+        })?;
+        Ok(streams)
+    }
+
+    /// Moves the contents of the buffer into consolidated blob batches and
+    /// truncates the buffer.
+    fn compact(&mut self) -> Result<(), Error> {
+        let mut upper = self.meta.compacted_upper;
+        self.buffer.snapshot(|seqno, _| {
+            upper = SeqNo(seqno.0 + 1);
+            Ok(())
+        })?;
+        let streams = self.read_streams()?;
+
+        let mut meta = self.meta.clone();
+        let version = meta.next_batch_version;
+        meta.next_batch_version += 1;
+        meta.compacted_upper = upper;
+        for (id, mut dataz) in streams {
+            let stream = meta.streams.get_mut(&id).expect("stream read from meta");
+            consolidate(&mut dataz, stream.since);
+            let mut buf = Vec::new();
+            encode_updates(&mut buf, &dataz);
+            // A crash between writing batches and committing the metadata can
+            // leave behind an uncommitted batch with this version, so allow
+            // overwriting it.
+            self.blob.set(&batch_key(id, version), buf, true)?;
+            stream.batch = Some(version);
+        }
+
+        let old = self.meta.clone();
+        self.write_meta(meta)?;
+        for (id, stream) in old.streams {
+            if let Some(version) = stream.batch {
+                self.blob.delete(&batch_key(id, version))?;
+            }
+        }
+        self.buffer.truncate(upper)?;
+        self.entries_since_compaction = 0;
+        Ok(())
+    }
+}
+
+/// The durable metadata of a [StoragePersister].
+#[derive(Clone, Debug, Default)]
+struct StorageMeta {
+    /// All buffer entries before this position are reflected in the batches.
+    compacted_upper: SeqNo,
+    /// The version to use for the next set of batches.
+    next_batch_version: u64,
+    /// The live streams.
+    streams: BTreeMap<Id, StreamMeta>,
 }
 
-// TODO: Implement [Persister] in terms of a [Buffer] and [Blob].
+#[derive(Clone, Debug, Default)]
+struct StreamMeta {
+    /// The compaction frontier of the stream.
+    since: u64,
+    /// The version of the stream's batch in the blob, if any.
+    batch: Option<u64>,
+}
+
+impl StorageMeta {
+    fn encode(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        buf.extend_from_slice(&self.compacted_upper.0.to_le_bytes());
+        buf.extend_from_slice(&self.next_batch_version.to_le_bytes());
+        buf.extend_from_slice(&(self.streams.len() as u64).to_le_bytes());
+        for (id, stream) in self.streams.iter() {
+            buf.extend_from_slice(&id.0.to_le_bytes());
+            buf.extend_from_slice(&stream.since.to_le_bytes());
+            match stream.batch {
+                Some(version) => {
+                    buf.push(1);
+                    buf.extend_from_slice(&version.to_le_bytes());
+                }
+                None => buf.push(0),
+            }
+        }
+        buf
+    }
+
+    fn decode(buf: &[u8]) -> Result<Self, Error> {
+        let mut decoder = Decoder::new(buf);
+        let compacted_upper = SeqNo(decoder.u64()?);
+        let next_batch_version = decoder.u64()?;
+        let mut streams = BTreeMap::new();
+        for _ in 0..decoder.u64()? {
+            let id = Id(decoder.u64()?);
+            let since = decoder.u64()?;
+            let batch = match decoder.bytes(1)? {
+                [0] => None,
+                [1] => Some(decoder.u64()?),
+                tag => return Err(format!("invalid batch tag: {:?}", tag).into()),
+            };
+            streams.insert(id, StreamMeta { since, batch });
+        }
+        Ok(StorageMeta {
+            compacted_upper,
+            next_batch_version,
+            streams,
+        })
+    }
+}
+
+fn batch_key(id: Id, version: u64) -> String {
+    format!("batch-{}-{}", id.0, version)
+}
+
+/// Advances the times of `updates` to `since` and sums the diffs of identical
+/// updates, removing any that sum to zero.
+fn consolidate(updates: &mut Vec<((Vec<u8>, Vec<u8>), u64, isize)>, since: u64) {
+    for (_, ts, _) in updates.iter_mut() {
+        if *ts < since {
+            *ts = since;
+        }
+    }
+    updates.sort_by(|a, b| (&a.0, a.1).cmp(&(&b.0, b.1)));
+    let mut consolidated: Vec<((Vec<u8>, Vec<u8>), u64, isize)> = Vec::with_capacity(updates.len());
+    for (kv, ts, diff) in updates.drain(..) {
+        match consolidated.last_mut() {
+            Some((prev_kv, prev_ts, prev_diff)) if *prev_kv == kv && *prev_ts == ts => {
+                *prev_diff += diff;
+            }
+            _ => consolidated.push((kv, ts, diff)),
+        }
+    }
+    consolidated.retain(|(_, _, diff)| *diff != 0);
+    *updates = consolidated;
+}
+
+/// Encodes a buffer entry holding the updates for each of the given streams.
+fn encode_entry<'a, I>(writes: I) -> Vec<u8>
+where
+    I: IntoIterator<Item = (Id, &'a [((Vec<u8>, Vec<u8>), u64, isize)])>,
+{
+    let mut buf = Vec::new();
+    let mut count = 0u64;
+    buf.extend_from_slice(&count.to_le_bytes());
+    for (id, updates) in writes {
+        buf.extend_from_slice(&id.0.to_le_bytes());
+        encode_updates(&mut buf, updates);
+        count += 1;
+    }
+    buf[..8].copy_from_slice(&count.to_le_bytes());
+    buf
+}
+
+fn encode_updates(buf: &mut Vec<u8>, updates: &[((Vec<u8>, Vec<u8>), u64, isize)]) {
+    buf.extend_from_slice(&(updates.len() as u64).to_le_bytes());
+    for ((key, val), ts, diff) in updates {
+        buf.extend_from_slice(&(key.len() as u64).to_le_bytes());
+        buf.extend_from_slice(key);
+        buf.extend_from_slice(&(val.len() as u64).to_le_bytes());
+        buf.extend_from_slice(val);
+        buf.extend_from_slice(&ts.to_le_bytes());
+        buf.extend_from_slice(&(*diff as i64).to_le_bytes());
+    }
+}
+
+fn decode_updates(
+    decoder: &mut Decoder,
+    updates: &mut Vec<((Vec<u8>, Vec<u8>), u64, isize)>,
+) -> Result<(), Error> {
+    for _ in 0..decoder.u64()? {
+        let key_len = decoder.usize()?;
+        let key = decoder.bytes(key_len)?.to_vec();
+        let val_len = decoder.usize()?;
+        let val = decoder.bytes(val_len)?.to_vec();
+        let ts = decoder.u64()?;
+        let diff = decoder.u64()? as i64 as isize;
+        updates.push(((key, val), ts, diff));
+    }
+    Ok(())
+}
+
+/// A cursor over an encoded buffer entry or blob value.
+struct Decoder<'a> {
+    buf: &'a [u8],
+}
+
+impl<'a> Decoder<'a> {
+    fn new(buf: &'a [u8]) -> Self {
+        Decoder { buf }
+    }
+
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8], Error> {
+        if self.buf.len() < len {
+            return Err(format!(
+                "unexpected end of input: wanted {} bytes, have {}",
+                len,
+                self.buf.len()
+            )
+            .into());
+        }
+        let (ret, rest) = self.buf.split_at(len);
+        self.buf = rest;
+        Ok(ret)
+    }
+
+    fn u64(&mut self) -> Result<u64, Error> {
+        let bytes = self.bytes(8)?;
+        Ok(u64::from_le_bytes(bytes.try_into().expect("8 bytes")))
+    }
+
+    fn usize(&mut self) -> Result<usize, Error> {
+        let len = self.u64()?;
+        len.try_into()
+            .map_err(|_| Error::from(format!("invalid length: {}", len)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mem::{MemBlob, MemBuffer};
+
+    use super::*;
+
+    fn read<M: Meta>(meta: &M) -> Result<Vec<((Vec<u8>, Vec<u8>), u64, isize)>, Error> {
+        let mut snap = meta.snapshot()?;
+        let mut buf = Vec::new();
+        while snap.read(&mut buf) {}
+        buf.sort();
+        Ok(buf)
+    }
+
+    fn update(key: &str, ts: u64, diff: isize) -> ((Vec<u8>, Vec<u8>), u64, isize) {
+        ((key.as_bytes().to_vec(), Vec::new()), ts, diff)
+    }
+
+    #[test]
+    fn storage_persister() -> Result<(), Error> {
+        let mut p = StoragePersister::new(MemBuffer::new(), MemBlob::new())?;
+        let (mut w1, mut m1) = p.create_or_load(Id(1))?.into_inner();
+        let (mut w2, m2) = p.create_or_load(Id(2))?.into_inner();
+        assert!(p.create_or_load(Id(1)).is_err());
+
+        w1.write_sync(&[update("a", 1, 1), update("b", 2, 1)])?;
+        w2.write_sync(&[update("c", 1, 1)])?;
+        w1.write_sync(&[update("a", 3, -1)])?;
+        assert_eq!(
+            read(&m1)?,
+            vec![update("a", 1, 1), update("a", 3, -1), update("b", 2, 1)]
+        );
+        assert_eq!(read(&m2)?, vec![update("c", 1, 1)]);
+
+        // Compaction forwards times to the since and consolidates.
+        m1.allow_compaction(3)?;
+        assert_eq!(read(&m1)?, vec![update("b", 3, 1)]);
+        p.inner.lock()?.compact()?;
+        assert_eq!(read(&m1)?, vec![update("b", 3, 1)]);
+        assert_eq!(read(&m2)?, vec![update("c", 1, 1)]);
+
+        // Writes after a compaction are merged with the compacted batch.
+        w1.write_sync(&[update("d", 4, 1)])?;
+        assert_eq!(read(&m1)?, vec![update("b", 3, 1), update("d", 4, 1)]);
+
+        p.destroy(Id(2))?;
+        assert!(p.destroy(Id(2)).is_err());
+        p.inner.lock()?.compact()?;
+        assert_eq!(read(&m1)?, vec![update("b", 3, 1), update("d", 4, 1)]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn storage_persister_restart() -> Result<(), Error> {
+        let mut p = StoragePersister::new(MemBuffer::new(), MemBlob::new())?;
+        let (mut w, mut m) = p.create_or_load(Id(1))?.into_inner();
+        w.write_sync(&[update("a", 1, 1)])?;
+        m.allow_compaction(2)?;
+        p.inner.lock()?.compact()?;
+        w.write_sync(&[update("b", 3, 1)])?;
+        drop((w, m));
+
+        // Simulate a restart by reconstructing the persister over the same
+        // buffer and blob.
+        let inner = Arc::try_unwrap(p.inner)
+            .unwrap_or_else(|_| panic!("outstanding handles"))
+            .into_inner()?;
+        let mut p = StoragePersister::new(inner.buffer, inner.blob)?;
+        let (_, m) = p.create_or_load(Id(1))?.into_inner();
+        assert_eq!(read(&m)?, vec![update("a", 2, 1), update("b", 3, 1)]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn storage_persister_write_atomic() -> Result<(), Error> {
+        let mut p = StoragePersister::new(MemBuffer::new(), MemBlob::new())?;
+        let (_, m1) = p.create_or_load(Id(1))?.into_inner();
+        let (_, m2) = p.create_or_load(Id(2))?.into_inner();
+
+        p.write_atomic(&[
+            (Id(1), vec![update("a", 1, 1)]),
+            (Id(2), vec![update("b", 1, 1)]),
+        ])?;
+        assert_eq!(read(&m1)?, vec![update("a", 1, 1)]);
+        assert_eq!(read(&m2)?, vec![update("b", 1, 1)]);
+
+        // A write that names an unregistered stream writes nothing.
+        assert!(p
+            .write_atomic(&[
+                (Id(1), vec![update("c", 2, 1)]),
+                (Id(3), vec![update("d", 2, 1)]),
+            ])
+            .is_err());
+        assert_eq!(read(&m1)?, vec![update("a", 1, 1)]);
+
+        // Entries for several streams survive the destruction of one of them.
+        p.destroy(Id(1))?;
+        assert_eq!(read(&m2)?, vec![update("b", 1, 1)]);
+        p.inner.lock()?.compact()?;
+        assert_eq!(read(&m2)?, vec![update("b", 1, 1)]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn decode_truncated() {
+        let updates = [update("a", 1, 1)];
+        let mut buf = encode_entry(Some((Id(1), &updates[..])));
+        buf.truncate(buf.len() - 1);
+        let mut decoder = Decoder::new(&buf);
+        assert_eq!(decoder.u64().unwrap(), 1);
+        assert_eq!(decoder.u64().unwrap(), 1);
+        assert!(decode_updates(&mut decoder, &mut Vec::new()).is_err());
+    }
+}
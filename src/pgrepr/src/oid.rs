@@ -63,5 +63,18 @@ pub const FUNC_MZ_SLEEP_OID: u32 = 16_434;
 pub const FUNC_MZ_SESSION_ID_OID: u32 = 16_435;
 pub const FUNC_MZ_UPTIME_OID: u32 = 16_436;
 pub const FUNC_MZ_WORKERS_OID: u32 = 16_437;
-pub const TYPE_APD_OID: u32 = 16_438;
 pub const FUNC_LIST_EQ_OID: u32 = 16_439;
+pub const FUNC_RANGE_AGG_OID: u32 = 16_440;
+pub const FUNC_JSONB_SET_3_OID: u32 = 16_441;
+pub const FUNC_JSONB_INSERT_3_OID: u32 = 16_442;
+pub const FUNC_JSONB_PATH_EXISTS_2_OID: u32 = 16_443;
+pub const FUNC_JSONB_PATH_EXISTS_3_OID: u32 = 16_444;
+pub const FUNC_JSONB_PATH_QUERY_2_OID: u32 = 16_445;
+pub const FUNC_JSONB_PATH_QUERY_3_OID: u32 = 16_446;
+pub const FUNC_MAKE_INTERVAL_1_OID: u32 = 16_447;
+pub const FUNC_MAKE_INTERVAL_2_OID: u32 = 16_448;
+pub const FUNC_MAKE_INTERVAL_3_OID: u32 = 16_449;
+pub const FUNC_MAKE_INTERVAL_4_OID: u32 = 16_450;
+pub const FUNC_MAKE_INTERVAL_5_OID: u32 = 16_451;
+pub const FUNC_MAKE_INTERVAL_6_OID: u32 = 16_452;
+pub const FUNC_MZ_AVG_PROMOTION_APD_OID: u32 = 16_453;
@@ -18,16 +18,22 @@ pub enum Type {
     Array(Box<Type>),
     /// A boolean value.
     Bool,
+    /// A fixed-length, blank-padded string.
+    BpChar,
     /// A byte array, i.e., a variable-length binary string.
     Bytea,
     /// A date.
     Date,
+    /// A network address, with the length of its netmask.
+    Cidr,
     /// A 4-byte floating point number.
     Float4,
     /// An 8-byte floating point number.
     Float8,
     /// A 4-byte signed integer.
     Int4,
+    /// A host address, with the length of the netmask of its subnet.
+    Inet,
     /// An 8-byte signed integer.
     Int8,
     /// A time interval.
@@ -45,6 +51,8 @@ pub enum Type {
     Numeric,
     /// An object identifier.
     Oid,
+    /// A contiguous range of values.
+    Range(Box<Type>),
     /// A sequence of heterogeneous values.
     Record(Vec<Type>),
     /// A variable-length string.
@@ -57,8 +65,8 @@ pub enum Type {
     TimestampTz,
     /// A universally unique identifier.
     Uuid,
-    /// Refactored numeric type using `rust-dec`
-    APD,
+    /// A variable-length string with an optional limit.
+    VarChar,
 }
 
 lazy_static! {
@@ -82,43 +90,37 @@ lazy_static! {
         postgres_types::Kind::Pseudo,
         "mz_catalog".to_owned(),
     );
-
-    /// Placeholder for `rust-dec`-backed numeric implementation.
-    pub static ref APD: postgres_types::Type = postgres_types::Type::new(
-        "apd".to_owned(),
-        oid::TYPE_APD_OID,
-        postgres_types::Kind::Simple,
-        "mz_catalog".to_owned(),
-    );
 }
 
 impl Type {
     /// Returns the type corresponding to the provided OID, if the OID is known.
     pub fn from_oid(oid: u32) -> Option<Type> {
-        match oid {
-            oid::TYPE_APD_OID => return Some(Type::APD),
-            _ => {}
-        }
-
         let ty = postgres_types::Type::from_oid(oid)?;
         match ty {
             postgres_types::Type::BOOL => Some(Type::Bool),
             postgres_types::Type::BYTEA => Some(Type::Bytea),
+            postgres_types::Type::CIDR => Some(Type::Cidr),
             postgres_types::Type::DATE => Some(Type::Date),
             postgres_types::Type::FLOAT4 => Some(Type::Float4),
             postgres_types::Type::FLOAT8 => Some(Type::Float8),
             postgres_types::Type::INT2 => Some(Type::Int4),
             postgres_types::Type::INT4 => Some(Type::Int4),
+            postgres_types::Type::INET => Some(Type::Inet),
             postgres_types::Type::INT8 => Some(Type::Int8),
             postgres_types::Type::INTERVAL => Some(Type::Interval),
             postgres_types::Type::JSONB => Some(Type::Jsonb),
             postgres_types::Type::NUMERIC => Some(Type::Numeric),
             postgres_types::Type::OID => Some(Type::Oid),
-            // TODO(petrosagg): char and bpchar values should not be stored as Text. see #6757
-            postgres_types::Type::TEXT
-            | postgres_types::Type::BPCHAR
-            | postgres_types::Type::CHAR
-            | postgres_types::Type::VARCHAR => Some(Type::Text),
+            postgres_types::Type::INT4_RANGE => Some(Type::Range(Box::new(Type::Int4))),
+            postgres_types::Type::INT8_RANGE => Some(Type::Range(Box::new(Type::Int8))),
+            postgres_types::Type::DATE_RANGE => Some(Type::Range(Box::new(Type::Date))),
+            postgres_types::Type::TS_RANGE => Some(Type::Range(Box::new(Type::Timestamp))),
+            postgres_types::Type::TSTZ_RANGE => Some(Type::Range(Box::new(Type::TimestampTz))),
+            postgres_types::Type::BPCHAR => Some(Type::BpChar),
+            postgres_types::Type::VARCHAR => Some(Type::VarChar),
+            // The single-byte "char" type has no counterpart of its own, so it
+            // is decoded as text rather than as a one-character bpchar.
+            postgres_types::Type::TEXT | postgres_types::Type::CHAR => Some(Type::Text),
             postgres_types::Type::TIME => Some(Type::Time),
             postgres_types::Type::TIMESTAMP => Some(Type::Timestamp),
             postgres_types::Type::TIMESTAMPTZ => Some(Type::TimestampTz),
@@ -132,10 +134,13 @@ impl Type {
             Type::Array(t) => match &**t {
                 Type::Array(_) => unreachable!(),
                 Type::Bool => &postgres_types::Type::BOOL_ARRAY,
+                Type::BpChar => &postgres_types::Type::BPCHAR_ARRAY,
                 Type::Bytea => &postgres_types::Type::BYTEA_ARRAY,
+                Type::Cidr => &postgres_types::Type::CIDR_ARRAY,
                 Type::Date => &postgres_types::Type::DATE_ARRAY,
                 Type::Float4 => &postgres_types::Type::FLOAT4_ARRAY,
                 Type::Float8 => &postgres_types::Type::FLOAT8_ARRAY,
+                Type::Inet => &postgres_types::Type::INET_ARRAY,
                 Type::Int4 => &postgres_types::Type::INT4_ARRAY,
                 Type::Int8 => &postgres_types::Type::INT8_ARRAY,
                 Type::Interval => &postgres_types::Type::INTERVAL_ARRAY,
@@ -144,19 +149,30 @@ impl Type {
                 Type::Map { .. } => unreachable!(),
                 Type::Numeric => &postgres_types::Type::NUMERIC_ARRAY,
                 Type::Oid => &postgres_types::Type::OID_ARRAY,
+                Type::Range(t) => match &**t {
+                    Type::Int4 => &postgres_types::Type::INT4_RANGE_ARRAY,
+                    Type::Int8 => &postgres_types::Type::INT8_RANGE_ARRAY,
+                    Type::Date => &postgres_types::Type::DATE_RANGE_ARRAY,
+                    Type::Timestamp => &postgres_types::Type::TS_RANGE_ARRAY,
+                    Type::TimestampTz => &postgres_types::Type::TSTZ_RANGE_ARRAY,
+                    _ => unreachable!(),
+                },
                 Type::Record(_) => &postgres_types::Type::RECORD_ARRAY,
                 Type::Text => &postgres_types::Type::TEXT_ARRAY,
                 Type::Time => &postgres_types::Type::TIME_ARRAY,
                 Type::Timestamp => &postgres_types::Type::TIMESTAMP_ARRAY,
                 Type::TimestampTz => &postgres_types::Type::TIMESTAMPTZ_ARRAY,
                 Type::Uuid => &postgres_types::Type::UUID_ARRAY,
-                Type::APD => unreachable!(),
+                Type::VarChar => &postgres_types::Type::VARCHAR_ARRAY,
             },
             Type::Bool => &postgres_types::Type::BOOL,
+            Type::BpChar => &postgres_types::Type::BPCHAR,
             Type::Bytea => &postgres_types::Type::BYTEA,
+            Type::Cidr => &postgres_types::Type::CIDR,
             Type::Date => &postgres_types::Type::DATE,
             Type::Float4 => &postgres_types::Type::FLOAT4,
             Type::Float8 => &postgres_types::Type::FLOAT8,
+            Type::Inet => &postgres_types::Type::INET,
             Type::Int4 => &postgres_types::Type::INT4,
             Type::Int8 => &postgres_types::Type::INT8,
             Type::Interval => &postgres_types::Type::INTERVAL,
@@ -165,13 +181,21 @@ impl Type {
             Type::Map { .. } => &MAP,
             Type::Numeric => &postgres_types::Type::NUMERIC,
             Type::Oid => &postgres_types::Type::OID,
+            Type::Range(t) => match &**t {
+                Type::Int4 => &postgres_types::Type::INT4_RANGE,
+                Type::Int8 => &postgres_types::Type::INT8_RANGE,
+                Type::Date => &postgres_types::Type::DATE_RANGE,
+                Type::Timestamp => &postgres_types::Type::TS_RANGE,
+                Type::TimestampTz => &postgres_types::Type::TSTZ_RANGE,
+                _ => unreachable!(),
+            },
             Type::Record(_) => &postgres_types::Type::RECORD,
             Type::Text => &postgres_types::Type::TEXT,
             Type::Time => &postgres_types::Type::TIME,
             Type::Timestamp => &postgres_types::Type::TIMESTAMP,
             Type::TimestampTz => &postgres_types::Type::TIMESTAMPTZ,
             Type::Uuid => &postgres_types::Type::UUID,
-            Type::APD => &APD,
+            Type::VarChar => &postgres_types::Type::VARCHAR,
         }
     }
 
@@ -181,6 +205,7 @@ impl Type {
         // SQL standard name.
         match self.inner() {
             &postgres_types::Type::BOOL_ARRAY => "boolean[]",
+            &postgres_types::Type::BPCHAR_ARRAY => "character[]",
             &postgres_types::Type::BYTEA_ARRAY => "bytea[]",
             &postgres_types::Type::DATE_ARRAY => "date[]",
             &postgres_types::Type::FLOAT4_ARRAY => "real[]",
@@ -191,18 +216,26 @@ impl Type {
             &postgres_types::Type::JSONB_ARRAY => "jsonb[]",
             &postgres_types::Type::NUMERIC_ARRAY => "numeric[]",
             &postgres_types::Type::OID_ARRAY => "oid[]",
+            &postgres_types::Type::INT4_RANGE_ARRAY => "int4range[]",
+            &postgres_types::Type::INT8_RANGE_ARRAY => "int8range[]",
+            &postgres_types::Type::DATE_RANGE_ARRAY => "daterange[]",
+            &postgres_types::Type::TS_RANGE_ARRAY => "tsrange[]",
+            &postgres_types::Type::TSTZ_RANGE_ARRAY => "tstzrange[]",
             &postgres_types::Type::RECORD_ARRAY => "record[]",
             &postgres_types::Type::TEXT_ARRAY => "text[]",
             &postgres_types::Type::TIME_ARRAY => "time[]",
             &postgres_types::Type::TIMESTAMP_ARRAY => "timestamp[]",
             &postgres_types::Type::TIMESTAMPTZ_ARRAY => "timestamp with time zone[]",
             &postgres_types::Type::UUID_ARRAY => "uuid[]",
+            &postgres_types::Type::VARCHAR_ARRAY => "character varying[]",
             &postgres_types::Type::BOOL => "boolean",
+            &postgres_types::Type::BPCHAR => "character",
             &postgres_types::Type::FLOAT4 => "real",
             &postgres_types::Type::FLOAT8 => "double precision",
             &postgres_types::Type::INT4 => "integer",
             &postgres_types::Type::INT8 => "bigint",
             &postgres_types::Type::TIMESTAMPTZ => "timestamp with time zone",
+            &postgres_types::Type::VARCHAR => "character varying",
             other => other.name(),
         }
     }
@@ -220,10 +253,13 @@ impl Type {
         match self {
             Type::Array(_) => -1,
             Type::Bool => 1,
+            Type::BpChar => -1,
             Type::Bytea => -1,
+            Type::Cidr => -1,
             Type::Date => 4,
             Type::Float4 => 4,
             Type::Float8 => 8,
+            Type::Inet => -1,
             Type::Int4 => 4,
             Type::Int8 => 8,
             Type::Interval => 16,
@@ -232,30 +268,34 @@ impl Type {
             Type::Map { .. } => -1,
             Type::Numeric => -1,
             Type::Oid => 4,
+            Type::Range(_) => -1,
             Type::Record(_) => -1,
             Type::Text => -1,
             Type::Time => 4,
             Type::Timestamp => 8,
             Type::TimestampTz => 8,
             Type::Uuid => 16,
-            Type::APD => 16,
+            Type::VarChar => -1,
         }
     }
 
     /// Provides a [`ScalarType`] from `self`, but without necessarily
     /// associating any meaningful values within the returned type.
     ///
-    /// For example `Type::Numeric` returns `ScalarType::Decimal(0,0)`, meaning
-    /// that its precision and scale need to be associated with values from
+    /// For example `Type::Numeric` returns `ScalarType::APD { scale: None }`,
+    /// meaning that its scale needs to be associated with values from
     /// elsewhere.
     pub fn to_scalar_type_lossy(&self) -> ScalarType {
         match self {
             Type::Array(t) => ScalarType::Array(Box::new(t.to_scalar_type_lossy())),
             Type::Bool => ScalarType::Bool,
+            Type::BpChar => ScalarType::Char { length: None },
             Type::Bytea => ScalarType::Bytes,
+            Type::Cidr => ScalarType::Cidr,
             Type::Date => ScalarType::Date,
             Type::Float4 => ScalarType::Float32,
             Type::Float8 => ScalarType::Float64,
+            Type::Inet => ScalarType::Inet,
             Type::Int4 => ScalarType::Int32,
             Type::Int8 => ScalarType::Int64,
             Type::Interval => ScalarType::Interval,
@@ -268,8 +308,11 @@ impl Type {
                 value_type: Box::new(value_type.to_scalar_type_lossy()),
                 custom_oid: None,
             },
-            Type::Numeric => ScalarType::Decimal(0, 0),
+            Type::Numeric => ScalarType::APD { scale: None },
             Type::Oid => ScalarType::Oid,
+            Type::Range(t) => ScalarType::Range {
+                element_type: Box::new(t.to_scalar_type_lossy()),
+            },
             Type::Record(_) => ScalarType::Record {
                 fields: vec![],
                 custom_oid: None,
@@ -280,7 +323,7 @@ impl Type {
             Type::Timestamp => ScalarType::Timestamp,
             Type::TimestampTz => ScalarType::TimestampTz,
             Type::Uuid => ScalarType::Uuid,
-            Type::APD => ScalarType::APD { scale: None },
+            Type::VarChar => ScalarType::VarChar { max_length: None },
         }
     }
 }
@@ -291,10 +334,13 @@ impl From<&ScalarType> for Type {
             ScalarType::Array(t) => Type::Array(Box::new(From::from(&**t))),
             ScalarType::Bool => Type::Bool,
             ScalarType::Bytes => Type::Bytea,
+            ScalarType::Char { .. } => Type::BpChar,
+            ScalarType::Cidr => Type::Cidr,
             ScalarType::Date => Type::Date,
             ScalarType::Decimal(_, _) => Type::Numeric,
             ScalarType::Float64 => Type::Float8,
             ScalarType::Float32 => Type::Float4,
+            ScalarType::Inet => Type::Inet,
             ScalarType::Int32 => Type::Int4,
             ScalarType::Int64 => Type::Int8,
             ScalarType::Interval => Type::Interval,
@@ -306,6 +352,9 @@ impl From<&ScalarType> for Type {
                 value_type: Box::new(From::from(&**value_type)),
             },
             ScalarType::Oid => Type::Oid,
+            ScalarType::Range { element_type } => {
+                Type::Range(Box::new(From::from(&**element_type)))
+            }
             ScalarType::Record { fields, .. } => Type::Record(
                 fields
                     .iter()
@@ -317,7 +366,8 @@ impl From<&ScalarType> for Type {
             ScalarType::Timestamp => Type::Timestamp,
             ScalarType::TimestampTz => Type::TimestampTz,
             ScalarType::Uuid => Type::Uuid,
-            ScalarType::APD { .. } => Type::APD,
+            ScalarType::VarChar { .. } => Type::VarChar,
+            ScalarType::APD { .. } => Type::Numeric,
         }
     }
 }
@@ -8,13 +8,14 @@
 // by the Apache License, Version 2.0.
 
 use std::collections::BTreeMap;
-use std::convert::TryInto;
+use std::convert::{TryFrom, TryInto};
 use std::error::Error;
 use std::io;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
 use std::str;
 
 use bytes::{BufMut, BytesMut};
-use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Utc};
 use dec::OrderedDecimal;
 use postgres_types::{FromSql, IsNull, ToSql, Type as PgType};
 use repr::ColumnType;
@@ -23,12 +24,15 @@ use uuid::Uuid;
 use ore::fmt::FormatBuffer;
 use repr::adt::apd::{self, Apd};
 use repr::adt::array::ArrayDimension;
+use repr::adt::datetime::Timezone;
 use repr::adt::decimal::MAX_DECIMAL_PRECISION;
+use repr::adt::inet::Inet;
 use repr::adt::jsonb::JsonbRef;
-use repr::strconv::{self, Nestable};
+use repr::adt::range::{InvalidRangeError, RangeBound};
+use repr::strconv::{self, Nestable, RangeParts};
 use repr::{ColumnName, Datum, RelationType, Row, RowArena, ScalarType};
 
-use crate::{Format, Interval, Jsonb, Numeric, Type};
+use crate::{APDNumeric, Format, Interval, Jsonb, Numeric, Type};
 
 pub mod interval;
 pub mod jsonb;
@@ -47,14 +51,20 @@ pub enum Value {
     },
     /// A boolean value.
     Bool(bool),
+    /// A fixed-length, blank-padded string.
+    BpChar(String),
     /// A byte array, i.e., a variable-length binary string.
     Bytea(Vec<u8>),
+    /// A network address, with the length of its netmask.
+    Cidr(Inet),
     /// A date.
     Date(NaiveDate),
     /// A 4-byte floating point number.
     Float4(f32),
     /// An 8-byte floating point number.
     Float8(f64),
+    /// A host address, with the length of the netmask of its subnet.
+    Inet(Inet),
     /// A 4-byte signed integer.
     Int4(i32),
     /// An 8-byte signed integer.
@@ -69,6 +79,8 @@ pub enum Value {
     Map(BTreeMap<String, Option<Value>>),
     /// An arbitrary precision number.
     Numeric(Numeric),
+    /// A contiguous range of values, or `None` if the range is empty.
+    Range(Option<RangeParts<Box<Value>>>),
     /// A sequence of heterogeneous values.
     Record(Vec<Option<Value>>),
     /// A time.
@@ -76,11 +88,16 @@ pub enum Value {
     /// A date and time, without a timezone.
     Timestamp(NaiveDateTime),
     /// A date and time, with a timezone.
-    TimestampTz(DateTime<Utc>),
+    ///
+    /// The offset is the one the session's timezone observed at that instant,
+    /// and is only used when formatting the value.
+    TimestampTz(DateTime<FixedOffset>),
     /// A variable-length string.
     Text(String),
     /// A universally unique identifier.
     Uuid(Uuid),
+    /// A variable-length string with an optional limit.
+    VarChar(String),
     /// Refactored numeric using `rust-dec`.
     APD(OrderedDecimal<Apd>),
 }
@@ -90,7 +107,8 @@ impl Value {
     ///
     /// The conversion happens in the obvious manner, except that `Datum::Null`
     /// is converted to `None` to align with how PostgreSQL handles NULL.
-    pub fn from_datum(datum: Datum, typ: &ScalarType) -> Option<Value> {
+    /// Timestamps with time zones are converted to the local time of `tz`.
+    pub fn from_datum(datum: Datum, typ: &ScalarType, tz: Timezone) -> Option<Value> {
         match (datum, typ) {
             (Datum::Null, _) => None,
             (Datum::True, ScalarType::Bool) => Some(Value::Bool(true)),
@@ -112,27 +130,33 @@ impl Value {
             (Datum::Date(d), ScalarType::Date) => Some(Value::Date(d)),
             (Datum::Time(t), ScalarType::Time) => Some(Value::Time(t)),
             (Datum::Timestamp(ts), ScalarType::Timestamp) => Some(Value::Timestamp(ts)),
-            (Datum::TimestampTz(ts), ScalarType::TimestampTz) => Some(Value::TimestampTz(ts)),
+            (Datum::TimestampTz(ts), ScalarType::TimestampTz) => Some(Value::TimestampTz(
+                ts.with_timezone(&tz.offset_at(&ts.naive_utc())),
+            )),
             (Datum::Interval(iv), ScalarType::Interval) => Some(Value::Interval(Interval(iv))),
             (Datum::Bytes(b), ScalarType::Bytes) => Some(Value::Bytea(b.to_vec())),
             (Datum::String(s), ScalarType::String) => Some(Value::Text(s.to_owned())),
+            (Datum::String(s), ScalarType::Char { .. }) => Some(Value::BpChar(s.to_owned())),
+            (Datum::String(s), ScalarType::VarChar { .. }) => Some(Value::VarChar(s.to_owned())),
             (_, ScalarType::Jsonb) => {
                 Some(Value::Jsonb(Jsonb(JsonbRef::from_datum(datum).to_owned())))
             }
             (Datum::Uuid(u), ScalarType::Uuid) => Some(Value::Uuid(u)),
+            (Datum::Inet(inet), ScalarType::Inet) => Some(Value::Inet(inet)),
+            (Datum::Inet(inet), ScalarType::Cidr) => Some(Value::Cidr(inet)),
             (Datum::Array(array), ScalarType::Array(elem_type)) => {
                 let dims = array.dims().into_iter().collect();
                 let elements = array
                     .elements()
                     .iter()
-                    .map(|elem| Value::from_datum(elem, elem_type))
+                    .map(|elem| Value::from_datum(elem, elem_type, tz))
                     .collect();
                 Some(Value::Array { dims, elements })
             }
             (Datum::List(list), ScalarType::List { element_type, .. }) => {
                 let elements = list
                     .iter()
-                    .map(|elem| Value::from_datum(elem, element_type))
+                    .map(|elem| Value::from_datum(elem, element_type, tz))
                     .collect();
                 Some(Value::List(elements))
             }
@@ -140,14 +164,28 @@ impl Value {
                 let fields = record
                     .iter()
                     .zip(fields)
-                    .map(|(e, (_name, ty))| Value::from_datum(e, &ty.scalar_type))
+                    .map(|(e, (_name, ty))| Value::from_datum(e, &ty.scalar_type, tz))
                     .collect();
                 Some(Value::Record(fields))
             }
+            (Datum::Range(range), ScalarType::Range { element_type }) => {
+                let bound = |b: RangeBound| {
+                    b.value
+                        .and_then(|v| Value::from_datum(v, element_type, tz))
+                        .map(Box::new)
+                };
+                let parts = range.bounds().map(|(lower, upper)| RangeParts {
+                    lower: bound(lower),
+                    lower_inclusive: lower.inclusive,
+                    upper: bound(upper),
+                    upper_inclusive: upper.inclusive,
+                });
+                Some(Value::Range(parts))
+            }
             (Datum::Map(dict), ScalarType::Map { value_type, .. }) => {
                 let entries = dict
                     .iter()
-                    .map(|(k, v)| (k.to_owned(), Value::from_datum(v, value_type)))
+                    .map(|(k, v)| (k.to_owned(), Value::from_datum(v, value_type, tz)))
                     .collect();
                 Some(Value::Map(entries))
             }
@@ -229,6 +267,22 @@ impl Value {
                 Datum::from(d.0.significand()),
                 ScalarType::Decimal(MAX_DECIMAL_PRECISION, d.0.scale()),
             ),
+            Value::Range(parts) => {
+                let elem_pg_type = match typ {
+                    Type::Range(t) => &*t,
+                    _ => panic!("Value::Range should have type Type::Range. Found {:?}", typ),
+                };
+                let (_, elem_type) = null_datum(&elem_pg_type);
+                let mut row = Row::default();
+                push_range(&mut row, parts.as_ref())
+                    .expect("range bounds validated when the value was decoded");
+                (
+                    buf.push_unary_row(row),
+                    ScalarType::Range {
+                        element_type: Box::new(elem_type),
+                    },
+                )
+            }
             Value::Record(_) => {
                 // This situation is handled gracefully by Value::decode; if we
                 // wind up here it's a programming error.
@@ -236,10 +290,23 @@ impl Value {
             }
             Value::Time(t) => (Datum::Time(t), ScalarType::Time),
             Value::Timestamp(ts) => (Datum::Timestamp(ts), ScalarType::Timestamp),
-            Value::TimestampTz(ts) => (Datum::TimestampTz(ts), ScalarType::TimestampTz),
+            Value::TimestampTz(ts) => (
+                Datum::TimestampTz(ts.with_timezone(&Utc)),
+                ScalarType::TimestampTz,
+            ),
             Value::Interval(iv) => (Datum::Interval(iv.0), ScalarType::Interval),
             Value::Text(s) => (Datum::String(buf.push_string(s)), ScalarType::String),
+            Value::BpChar(s) => (
+                Datum::String(buf.push_string(s)),
+                ScalarType::Char { length: None },
+            ),
+            Value::VarChar(s) => (
+                Datum::String(buf.push_string(s)),
+                ScalarType::VarChar { max_length: None },
+            ),
             Value::Uuid(u) => (Datum::Uuid(u), ScalarType::Uuid),
+            Value::Inet(inet) => (Datum::Inet(inet), ScalarType::Inet),
+            Value::Cidr(inet) => (Datum::Inet(inet), ScalarType::Cidr),
             Value::APD(n) => (
                 Datum::APD(n),
                 ScalarType::APD {
@@ -291,15 +358,27 @@ impl Value {
                 Some(elem) => elem.encode_text(buf.nonnull_buffer()),
             }),
             Value::Numeric(n) => strconv::format_decimal(buf, &n.0),
+            Value::Range(parts) => strconv::format_range(
+                buf,
+                parts.as_ref().map(|parts| RangeParts {
+                    lower: parts.lower.as_deref(),
+                    lower_inclusive: parts.lower_inclusive,
+                    upper: parts.upper.as_deref(),
+                    upper_inclusive: parts.upper_inclusive,
+                }),
+                |buf, elem| elem.encode_text(buf),
+            ),
             Value::Record(elems) => strconv::format_record(buf, elems, |buf, elem| match elem {
                 None => buf.write_null(),
                 Some(elem) => elem.encode_text(buf.nonnull_buffer()),
             }),
-            Value::Text(s) => strconv::format_string(buf, s),
+            Value::Text(s) | Value::BpChar(s) | Value::VarChar(s) => strconv::format_string(buf, s),
             Value::Time(t) => strconv::format_time(buf, *t),
             Value::Timestamp(ts) => strconv::format_timestamp(buf, *ts),
-            Value::TimestampTz(ts) => strconv::format_timestamptz(buf, *ts),
+            Value::TimestampTz(ts) => strconv::format_timestamptz_local(buf, *ts),
             Value::Uuid(u) => strconv::format_uuid(buf, *u),
+            Value::Inet(inet) => strconv::format_inet(buf, *inet),
+            Value::Cidr(inet) => strconv::format_cidr(buf, *inet),
             Value::APD(d) => strconv::format_apd(buf, d),
         }
     }
@@ -347,6 +426,35 @@ impl Value {
                 Ok(postgres_types::IsNull::No)
             }
             Value::Numeric(n) => n.to_sql(&PgType::NUMERIC, buf),
+            Value::Range(parts) => {
+                // The flags used by PostgreSQL's binary range format are the
+                // same as those used by `Row`'s encoding of ranges.
+                let elem_type = match ty {
+                    Type::Range(elem_type) => elem_type,
+                    _ => unreachable!(),
+                };
+                match parts {
+                    None => buf.put_u8(RANGE_EMPTY),
+                    Some(parts) => {
+                        let mut flags = 0;
+                        if parts.lower.is_none() {
+                            flags |= RANGE_LB_INF;
+                        } else if parts.lower_inclusive {
+                            flags |= RANGE_LB_INC;
+                        }
+                        if parts.upper.is_none() {
+                            flags |= RANGE_UB_INF;
+                        } else if parts.upper_inclusive {
+                            flags |= RANGE_UB_INC;
+                        }
+                        buf.put_u8(flags);
+                        for bound in parts.lower.iter().chain(parts.upper.iter()) {
+                            encode_element(buf, Some(bound), elem_type)?;
+                        }
+                    }
+                }
+                Ok(postgres_types::IsNull::No)
+            }
             Value::Record(fields) => {
                 let nfields = pg_len("record field length", fields.len())?;
                 buf.put_i32(nfields);
@@ -361,15 +469,21 @@ impl Value {
                 Ok(postgres_types::IsNull::No)
             }
             Value::Text(s) => s.to_sql(&PgType::TEXT, buf),
+            Value::BpChar(s) => s.to_sql(&PgType::BPCHAR, buf),
+            Value::VarChar(s) => s.to_sql(&PgType::VARCHAR, buf),
             Value::Time(t) => t.to_sql(&PgType::TIME, buf),
             Value::Timestamp(ts) => ts.to_sql(&PgType::TIMESTAMP, buf),
             Value::TimestampTz(ts) => ts.to_sql(&PgType::TIMESTAMPTZ, buf),
             Value::Uuid(u) => u.to_sql(&PgType::UUID, buf),
-            Value::APD(_) => {
-                // for now just use text encoding
-                self.encode_text(buf);
+            Value::Inet(inet) => {
+                encode_binary_inet(buf, inet, false);
                 Ok(postgres_types::IsNull::No)
             }
+            Value::Cidr(inet) => {
+                encode_binary_inet(buf, inet, true);
+                Ok(postgres_types::IsNull::No)
+            }
+            Value::APD(n) => APDNumeric(*n).to_sql(&PgType::NUMERIC, buf),
         }
         .expect("encode_binary should never trigger a to_sql failure");
         if let IsNull::Yes = is_null {
@@ -379,21 +493,28 @@ impl Value {
     }
 
     /// Deserializes a value of type `ty` from `raw` using the specified
-    /// `format`.
+    /// `format`. Timestamps with time zones that do not specify a timezone are
+    /// interpreted as local times in `tz`.
     pub fn decode(
         format: Format,
         ty: &Type,
         raw: &[u8],
+        tz: Timezone,
     ) -> Result<Value, Box<dyn Error + Sync + Send>> {
         match format {
-            Format::Text => Value::decode_text(ty, raw),
-            Format::Binary => Value::decode_binary(ty, raw),
+            Format::Text => Value::decode_text(ty, raw, tz),
+            Format::Binary => Value::decode_binary(ty, raw, tz),
         }
     }
 
     /// Deserializes a value of type `ty` from `raw` using the [text encoding
-    /// format](Format::Text).
-    pub fn decode_text(ty: &Type, raw: &[u8]) -> Result<Value, Box<dyn Error + Sync + Send>> {
+    /// format](Format::Text). Timestamps with time zones that do not specify a
+    /// timezone are interpreted as local times in `tz`.
+    pub fn decode_text(
+        ty: &Type,
+        raw: &[u8],
+        tz: Timezone,
+    ) -> Result<Value, Box<dyn Error + Sync + Send>> {
         let raw = str::from_utf8(raw)?;
         Ok(match ty {
             Type::Array(_) => return Err("input of array types is not implemented".into()),
@@ -410,29 +531,43 @@ impl Value {
                 raw,
                 matches!(**elem_type, Type::List(..)),
                 || None,
-                |elem_text| Value::decode_text(elem_type, elem_text.as_bytes()).map(Some),
+                |elem_text| Value::decode_text(elem_type, elem_text.as_bytes(), tz).map(Some),
             )?),
             Type::Map { value_type } => Value::Map(strconv::parse_map(
                 raw,
                 matches!(**value_type, Type::Map { .. }),
-                |elem_text| Value::decode_text(value_type, elem_text.as_bytes()).map(Some),
+                |elem_text| Value::decode_text(value_type, elem_text.as_bytes(), tz).map(Some),
             )?),
-            Type::Numeric => Value::Numeric(Numeric(strconv::parse_decimal(raw)?)),
+            Type::Numeric => Value::APD(strconv::parse_apd(raw)?),
+            Type::Range(elem_type) => {
+                let parts = strconv::parse_range(raw, |elem_text| {
+                    Value::decode_text(elem_type, elem_text.as_bytes(), tz).map(Box::new)
+                })?;
+                Value::range_from_parts(parts)?
+            }
             Type::Record(_) => {
                 return Err("input of anonymous composite types is not implemented".into())
             }
             Type::Text => Value::Text(raw.to_owned()),
+            Type::BpChar => Value::BpChar(raw.to_owned()),
+            Type::VarChar => Value::VarChar(raw.to_owned()),
             Type::Time => Value::Time(strconv::parse_time(raw)?),
             Type::Timestamp => Value::Timestamp(strconv::parse_timestamp(raw)?),
-            Type::TimestampTz => Value::TimestampTz(strconv::parse_timestamptz(raw)?),
+            Type::TimestampTz => Value::TimestampTz(strconv::parse_timestamptz_in(raw, tz)?.into()),
             Type::Uuid => Value::Uuid(Uuid::parse_str(raw)?),
-            Type::APD => Value::APD(strconv::parse_apd(raw)?),
+            Type::Inet => Value::Inet(strconv::parse_inet(raw)?),
+            Type::Cidr => Value::Cidr(strconv::parse_cidr(raw)?),
         })
     }
 
     /// Deserializes a value of type `ty` from `raw` using the [binary encoding
-    /// format](Format::Binary).
-    pub fn decode_binary(ty: &Type, raw: &[u8]) -> Result<Value, Box<dyn Error + Sync + Send>> {
+    /// format](Format::Binary). Lists and maps use the text encoding, and so
+    /// interpret their timestamps with time zones as in [`Value::decode_text`].
+    pub fn decode_binary(
+        ty: &Type,
+        raw: &[u8],
+        tz: Timezone,
+    ) -> Result<Value, Box<dyn Error + Sync + Send>> {
         match ty {
             Type::Array(_) => Err("input of array types is not implemented".into()),
             Type::Bool => bool::from_sql(ty.inner(), raw).map(Value::Bool),
@@ -444,18 +579,159 @@ impl Value {
             Type::Int8 => i64::from_sql(ty.inner(), raw).map(Value::Int8),
             Type::Interval => Interval::from_sql(ty.inner(), raw).map(Value::Interval),
             Type::Jsonb => Jsonb::from_sql(ty.inner(), raw).map(Value::Jsonb),
-            Type::List(_) => Value::decode_text(ty, raw), // just using the text encoding for now
-            Type::Map { .. } => Value::decode_text(ty, raw), // just using the text encoding for now
-            Type::Numeric => Numeric::from_sql(ty.inner(), raw).map(Value::Numeric),
+            Type::List(_) => Value::decode_text(ty, raw, tz), // just using the text encoding for now
+            Type::Map { .. } => Value::decode_text(ty, raw, tz), // just using the text encoding for now
+            Type::Numeric => APDNumeric::from_sql(ty.inner(), raw).map(|n| Value::APD(n.0)),
+            Type::Range(elem_type) => Value::decode_binary_range(elem_type, raw, tz),
             Type::Record(_) => Err("input of anonymous composite types is not implemented".into()),
             Type::Text => String::from_sql(ty.inner(), raw).map(Value::Text),
+            Type::BpChar => String::from_sql(ty.inner(), raw).map(Value::BpChar),
+            Type::VarChar => String::from_sql(ty.inner(), raw).map(Value::VarChar),
             Type::Time => NaiveTime::from_sql(ty.inner(), raw).map(Value::Time),
             Type::Timestamp => NaiveDateTime::from_sql(ty.inner(), raw).map(Value::Timestamp),
-            Type::TimestampTz => DateTime::<Utc>::from_sql(ty.inner(), raw).map(Value::TimestampTz),
+            Type::TimestampTz => {
+                DateTime::<FixedOffset>::from_sql(ty.inner(), raw).map(Value::TimestampTz)
+            }
             Type::Uuid => Uuid::from_sql(ty.inner(), raw).map(Value::Uuid),
-            Type::APD => unreachable!(),
+            Type::Inet => decode_binary_inet(raw).map(Value::Inet),
+            Type::Cidr => {
+                let inet = decode_binary_inet(raw)?;
+                if !inet.is_network() {
+                    return Err("invalid cidr: value has bits set to right of mask".into());
+                }
+                Ok(Value::Cidr(inet))
+            }
+        }
+    }
+
+    fn decode_binary_range(
+        elem_type: &Type,
+        mut raw: &[u8],
+        tz: Timezone,
+    ) -> Result<Value, Box<dyn Error + Sync + Send>> {
+        let flags = match raw.split_first() {
+            Some((flags, rest)) => {
+                raw = rest;
+                *flags
+            }
+            None => return Err("invalid range: missing flags".into()),
+        };
+        if flags & RANGE_EMPTY != 0 {
+            return Value::range_from_parts(None);
+        }
+        let mut decode_bound = |infinite: bool| -> Result<_, Box<dyn Error + Sync + Send>> {
+            if infinite {
+                return Ok(None);
+            }
+            if raw.len() < 4 {
+                return Err("invalid range: truncated bound".into());
+            }
+            let (len, rest) = raw.split_at(4);
+            let len = i32::from_be_bytes(len.try_into().unwrap());
+            let len = usize::try_from(len).map_err(|_| "invalid range: null bound")?;
+            if rest.len() < len {
+                return Err("invalid range: truncated bound".into());
+            }
+            let (bound, rest) = rest.split_at(len);
+            raw = rest;
+            Ok(Some(Box::new(Value::decode_binary(elem_type, bound, tz)?)))
+        };
+        let lower = decode_bound(flags & RANGE_LB_INF != 0)?;
+        let upper = decode_bound(flags & RANGE_UB_INF != 0)?;
+        Value::range_from_parts(Some(RangeParts {
+            lower_inclusive: lower.is_some() && flags & RANGE_LB_INC != 0,
+            lower,
+            upper_inclusive: upper.is_some() && flags & RANGE_UB_INC != 0,
+            upper,
+        }))
+    }
+
+    /// Constructs a range value from its parts, validating that the parts
+    /// describe a well-formed range.
+    fn range_from_parts(
+        parts: Option<RangeParts<Box<Value>>>,
+    ) -> Result<Value, Box<dyn Error + Sync + Send>> {
+        push_range(&mut Row::default(), parts.as_ref())?;
+        Ok(Value::Range(parts))
+    }
+}
+
+// The flags of PostgreSQL's binary range format.
+const RANGE_EMPTY: u8 = 0x01;
+const RANGE_LB_INC: u8 = 0x02;
+const RANGE_UB_INC: u8 = 0x04;
+const RANGE_LB_INF: u8 = 0x08;
+const RANGE_UB_INF: u8 = 0x10;
+
+/// Pushes the range described by `parts` onto `row`.
+fn push_range(
+    row: &mut Row,
+    parts: Option<&RangeParts<Box<Value>>>,
+) -> Result<(), InvalidRangeError> {
+    fn bound(value: &Option<Box<Value>>, inclusive: bool) -> RangeBound<'static> {
+        let value = match value.as_deref() {
+            None => return RangeBound::infinite(),
+            Some(Value::Int4(i)) => Datum::Int32(*i),
+            Some(Value::Int8(i)) => Datum::Int64(*i),
+            Some(Value::Date(d)) => Datum::Date(*d),
+            Some(Value::Timestamp(ts)) => Datum::Timestamp(*ts),
+            Some(Value::TimestampTz(ts)) => Datum::TimestampTz(ts.with_timezone(&Utc)),
+            Some(value) => unreachable!("unsupported range element {:?}", value),
+        };
+        RangeBound::new(value, inclusive)
+    }
+
+    match parts {
+        None => {
+            row.push_empty_range();
+            Ok(())
         }
+        Some(parts) => row.push_range(
+            bound(&parts.lower, parts.lower_inclusive),
+            bound(&parts.upper, parts.upper_inclusive),
+        ),
+    }
+}
+
+// The address families of PostgreSQL's binary `inet` and `cidr` formats.
+const PGSQL_AF_INET: u8 = 2;
+const PGSQL_AF_INET6: u8 = 3;
+
+/// Writes `inet` in PostgreSQL's binary `inet` format, which is shared by
+/// `cidr`: the address family, the netmask length, whether the value is a
+/// `cidr`, the number of address bytes, and then the address bytes
+/// themselves.
+fn encode_binary_inet(buf: &mut BytesMut, inet: &Inet, is_cidr: bool) {
+    let (family, octets) = match inet.addr() {
+        IpAddr::V4(addr) => (PGSQL_AF_INET, addr.octets().to_vec()),
+        IpAddr::V6(addr) => (PGSQL_AF_INET6, addr.octets().to_vec()),
+    };
+    buf.put_u8(family);
+    buf.put_u8(inet.prefix_len());
+    buf.put_u8(is_cidr.into());
+    buf.put_u8(u8::try_from(octets.len()).expect("address length fits in u8"));
+    buf.put_slice(&octets);
+}
+
+/// Reads a value in PostgreSQL's binary `inet` format.
+fn decode_binary_inet(raw: &[u8]) -> Result<Inet, Box<dyn Error + Sync + Send>> {
+    let (family, bits, nb, octets) = match raw {
+        [family, bits, _is_cidr, nb, octets @ ..] => (*family, *bits, *nb, octets),
+        _ => return Err("invalid inet: truncated header".into()),
+    };
+    if usize::from(nb) != octets.len() {
+        return Err("invalid inet: invalid address length".into());
     }
+    let addr = match family {
+        PGSQL_AF_INET => IpAddr::V4(Ipv4Addr::from(
+            <[u8; 4]>::try_from(octets).map_err(|_| "invalid inet: invalid address length")?,
+        )),
+        PGSQL_AF_INET6 => IpAddr::V6(Ipv6Addr::from(
+            <[u8; 16]>::try_from(octets).map_err(|_| "invalid inet: invalid address length")?,
+        )),
+        _ => return Err("invalid inet: invalid address family".into()),
+    };
+    Ok(Inet::new(addr, bits)?)
 }
 
 fn encode_element(buf: &mut BytesMut, elem: Option<&Value>, ty: &Type) -> Result<(), io::Error> {
@@ -504,14 +780,23 @@ pub fn null_datum(ty: &Type) -> (Datum<'static>, ScalarType) {
                 custom_oid: None,
             }
         }
-        Type::Numeric => ScalarType::Decimal(MAX_DECIMAL_PRECISION, 0),
-        Type::APD => ScalarType::APD { scale: None },
+        Type::Numeric => ScalarType::APD { scale: None },
         Type::Oid => ScalarType::Oid,
+        Type::Range(t) => {
+            let (_, elem_type) = null_datum(t);
+            ScalarType::Range {
+                element_type: Box::new(elem_type),
+            }
+        }
         Type::Text => ScalarType::String,
+        Type::BpChar => ScalarType::Char { length: None },
+        Type::VarChar => ScalarType::VarChar { max_length: None },
         Type::Time => ScalarType::Time,
         Type::Timestamp => ScalarType::Timestamp,
         Type::TimestampTz => ScalarType::TimestampTz,
         Type::Uuid => ScalarType::Uuid,
+        Type::Inet => ScalarType::Inet,
+        Type::Cidr => ScalarType::Cidr,
         Type::Record(fields) => {
             let fields = fields
                 .iter()
@@ -548,9 +833,9 @@ pub fn null_datum(ty: &Type) -> (Datum<'static>, ScalarType) {
 ///
 /// Calling this function is equivalent to mapping [`Value::from_datum`] over
 /// every datum in `row`.
-pub fn values_from_row(row: Row, typ: &RelationType) -> Vec<Option<Value>> {
+pub fn values_from_row(row: Row, typ: &RelationType, tz: Timezone) -> Vec<Option<Value>> {
     row.iter()
         .zip(typ.column_types.iter())
-        .map(|(col, typ)| Value::from_datum(col, &typ.scalar_type))
+        .map(|(col, typ)| Value::from_datum(col, &typ.scalar_type, tz))
         .collect()
 }
@@ -15,9 +15,18 @@ use std::fmt;
 
 use byteorder::{NetworkEndian, ReadBytesExt};
 use bytes::{BufMut, BytesMut};
+use dec::OrderedDecimal;
 use postgres_types::{to_sql_checked, FromSql, IsNull, ToSql, Type};
 
+use repr::adt::apd::Apd;
 use repr::adt::decimal::{Decimal, Significand};
+use repr::strconv;
+
+const NUMERIC_POS: u16 = 0x0000;
+const NUMERIC_NEG: u16 = 0x4000;
+const NUMERIC_NAN: u16 = 0xC000;
+const NUMERIC_PINF: u16 = 0xD000;
+const NUMERIC_NINF: u16 = 0xF000;
 
 /// A wrapper for the `repr` crate's [`Decimal`](repr::adt::decimal::Decimal)
 /// type that can be serialized to and deserialized from the PostgreSQL binasry
@@ -142,3 +151,159 @@ impl<'a> FromSql<'a> for Numeric {
         matches!(*ty, Type::NUMERIC)
     }
 }
+
+/// A wrapper for the `repr` crate's [`Apd`](repr::adt::apd::Apd) type that can
+/// be serialized to and deserialized from the PostgreSQL binary format.
+#[derive(Debug)]
+pub struct APDNumeric(pub OrderedDecimal<Apd>);
+
+impl fmt::Display for APDNumeric {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        (self.0).0.fmt(f)
+    }
+}
+
+impl ToSql for APDNumeric {
+    fn to_sql(
+        &self,
+        _: &Type,
+        out: &mut BytesMut,
+    ) -> Result<IsNull, Box<dyn Error + 'static + Send + Sync>> {
+        let n = &(self.0).0;
+        if n.is_nan() || n.is_infinite() {
+            let sign = if n.is_nan() {
+                NUMERIC_NAN
+            } else if n.is_negative() {
+                NUMERIC_NINF
+            } else {
+                NUMERIC_PINF
+            };
+            out.put_u16(0);
+            out.put_i16(0);
+            out.put_u16(sign);
+            out.put_u16(0);
+            return Ok(IsNull::No);
+        }
+
+        // PostgreSQL stores numerics as base-10000 digits, so regroup the
+        // decimal digits of the standard notation into groups of four,
+        // aligned on the decimal point.
+        let s = n.to_standard_notation_string();
+        let (sign, s) = match s.strip_prefix('-') {
+            Some(s) => (NUMERIC_NEG, s),
+            None => (NUMERIC_POS, s.as_str()),
+        };
+        let (int_part, frac_part) = match s.find('.') {
+            Some(i) => (&s[..i], &s[i + 1..]),
+            None => (s, ""),
+        };
+        let int_part = int_part.trim_start_matches('0');
+        let scale = frac_part.len() as u16;
+
+        let mut buf = String::new();
+        for _ in 0..(4 - int_part.len() % 4) % 4 {
+            buf.push('0');
+        }
+        buf.push_str(int_part);
+        let int_groups = buf.len() / 4;
+        buf.push_str(frac_part);
+        for _ in 0..(4 - frac_part.len() % 4) % 4 {
+            buf.push('0');
+        }
+
+        let mut digits = buf
+            .as_bytes()
+            .chunks(4)
+            .map(|chunk| std::str::from_utf8(chunk).unwrap().parse::<i16>().unwrap())
+            .collect::<Vec<_>>();
+        let mut weight = int_groups as i16 - 1;
+
+        // Leading and trailing zero digits are implied by the weight and
+        // scale, respectively.
+        let leading_zeroes = digits.iter().take_while(|d| **d == 0).count();
+        digits.drain(..leading_zeroes);
+        weight -= leading_zeroes as i16;
+        let trailing_zeroes = digits.iter().rev().take_while(|d| **d == 0).count();
+        digits.truncate(digits.len() - trailing_zeroes);
+        if digits.is_empty() {
+            weight = 0;
+        }
+
+        out.put_u16(digits.len() as u16);
+        out.put_i16(weight);
+        out.put_u16(sign);
+        out.put_u16(scale);
+        for digit in digits.iter() {
+            out.put_i16(*digit);
+        }
+
+        Ok(IsNull::No)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        matches!(*ty, Type::NUMERIC)
+    }
+
+    to_sql_checked!();
+}
+
+impl<'a> FromSql<'a> for APDNumeric {
+    fn from_sql(_: &Type, mut raw: &'a [u8]) -> Result<APDNumeric, Box<dyn Error + Sync + Send>> {
+        let ndigits = raw.read_u16::<NetworkEndian>()?;
+        let weight = raw.read_i16::<NetworkEndian>()?;
+        let sign = raw.read_u16::<NetworkEndian>()?;
+        let scale = raw.read_u16::<NetworkEndian>()?;
+        let mut digits = Vec::new();
+        for _ in 0..ndigits {
+            digits.push(raw.read_u16::<NetworkEndian>()?);
+        }
+
+        let mut s = match sign {
+            NUMERIC_POS => String::new(),
+            NUMERIC_NEG => String::from("-"),
+            NUMERIC_NAN => return Ok(APDNumeric(strconv::parse_apd("NaN")?)),
+            NUMERIC_PINF => return Ok(APDNumeric(strconv::parse_apd("Infinity")?)),
+            NUMERIC_NINF => return Ok(APDNumeric(strconv::parse_apd("-Infinity")?)),
+            _ => return Err("bad sign in numeric".into()),
+        };
+
+        // Expand the base-10000 digits into decimal digits, with the decimal
+        // point after the digit of weight zero.
+        let mut int_part = String::from("0");
+        let mut frac_part = String::new();
+        for (i, digit) in digits.iter().enumerate() {
+            if *digit >= 10_000 {
+                return Err("bad digit in numeric".into());
+            }
+            let digit_weight = weight as i32 - i as i32;
+            if digit_weight >= 0 {
+                int_part.push_str(&format!("{:04}", digit));
+            } else {
+                for _ in frac_part.len()..(-digit_weight as usize - 1) * 4 {
+                    frac_part.push('0');
+                }
+                frac_part.push_str(&format!("{:04}", digit));
+            }
+        }
+        if ndigits > 0 && weight >= ndigits as i16 {
+            for _ in 0..(weight - ndigits as i16 + 1) * 4 {
+                int_part.push('0');
+            }
+        }
+        frac_part.truncate(scale as usize);
+        while frac_part.len() < scale as usize {
+            frac_part.push('0');
+        }
+
+        s.push_str(&int_part);
+        if !frac_part.is_empty() {
+            s.push('.');
+            s.push_str(&frac_part);
+        }
+        Ok(APDNumeric(strconv::parse_apd(&s)?))
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        matches!(*ty, Type::NUMERIC)
+    }
+}
@@ -17,6 +17,8 @@ use itertools::Itertools;
 use postgres::error::SqlState;
 
 use coord::session::TransactionStatus as CoordTransactionStatus;
+use repr::adt::apd::APD_DATUM_MAX_PRECISION;
+use repr::adt::datetime::Timezone;
 use repr::{ColumnName, Datum, RelationDesc, RelationType, Row, RowArena, ScalarType};
 
 // Pgwire protocol versions are represented as 32-bit integers, where the
@@ -342,8 +344,10 @@ impl ErrorResponse {
             CoordError::Eval(_) => SqlState::INTERNAL_ERROR,
             CoordError::IdExhaustionError => SqlState::INTERNAL_ERROR,
             CoordError::InvalidParameterType(_) => SqlState::INVALID_PARAMETER_VALUE,
+            CoordError::InvalidParameterValue { .. } => SqlState::INVALID_PARAMETER_VALUE,
             CoordError::OperationProhibitsTransaction(_) => SqlState::ACTIVE_SQL_TRANSACTION,
             CoordError::OperationRequiresTransaction(_) => SqlState::NO_ACTIVE_SQL_TRANSACTION,
+            CoordError::Persistence(_) => SqlState::INTERNAL_ERROR,
             CoordError::ReadOnlyTransaction => SqlState::READ_ONLY_SQL_TRANSACTION,
             CoordError::ReadOnlyParameter(_) => SqlState::CANT_CHANGE_RUNTIME_PARAM,
             CoordError::RelationOutsideTimeDomain { .. } => SqlState::INVALID_TRANSACTION_STATE,
@@ -439,6 +443,7 @@ pub struct FieldDescription {
 pub fn encode_copy_row_binary(
     row: Row,
     typ: &RelationType,
+    tz: Timezone,
     out: &mut Vec<u8>,
 ) -> Result<(), io::Error> {
     const NULL_BYTES: [u8; 4] = (-1i32).to_be_bytes();
@@ -456,7 +461,7 @@ pub fn encode_copy_row_binary(
     for (field, typ) in row
         .iter()
         .zip(&typ.column_types)
-        .map(|(datum, typ)| (pgrepr::Value::from_datum(datum, &typ.scalar_type), typ))
+        .map(|(datum, typ)| (pgrepr::Value::from_datum(datum, &typ.scalar_type, tz), typ))
     {
         match field {
             None => out.extend(&NULL_BYTES),
@@ -483,12 +488,16 @@ pub fn encode_copy_row_binary(
 pub fn encode_copy_row_text(
     row: Row,
     typ: &RelationType,
+    tz: Timezone,
     out: &mut Vec<u8>,
 ) -> Result<(), io::Error> {
     let delim = b'\t';
     let null = b"\\N";
     let mut buf = BytesMut::new();
-    for (idx, field) in pgrepr::values_from_row(row, typ).into_iter().enumerate() {
+    for (idx, field) in pgrepr::values_from_row(row, typ, tz)
+        .into_iter()
+        .enumerate()
+    {
         if idx > 0 {
             out.push(delim);
         }
@@ -760,6 +769,7 @@ pub fn decode_copy_text_format(
     column_types: &Vec<pgrepr::Type>,
     delimiter: &Option<String>,
     null: &Option<String>,
+    tz: Timezone,
 ) -> Result<Vec<Row>, io::Error> {
     let mut rows = Vec::new();
     let null = if let Some(null) = null {
@@ -782,7 +792,7 @@ pub fn decode_copy_text_format(
             }
             let raw_value = parser.consume_raw_value()?;
             if let Some(raw_value) = raw_value {
-                match pgrepr::Value::decode_text(&typ, raw_value) {
+                match pgrepr::Value::decode_text(&typ, raw_value, tz) {
                     Ok(value) => row.push(value.into_datum(&buf, &typ).0),
                     Err(err) => {
                         let msg = format!("unable to decode column: {}", err);
@@ -823,6 +833,17 @@ pub fn encode_row_description(
                     ScalarType::Decimal(precision, scale) => {
                         ((i32::from(*precision) << 16) | i32::from(*scale)) + 4
                     }
+                    ScalarType::APD { scale: Some(scale) } => {
+                        (((APD_DATUM_MAX_PRECISION as i32) << 16) | i32::from(*scale)) + 4
+                    }
+                    // Character types store their length + 4 (the size of
+                    // the varlena header) in the type_mod field.
+                    ScalarType::Char {
+                        length: Some(length),
+                    }
+                    | ScalarType::VarChar {
+                        max_length: Some(length),
+                    } => i32::try_from(*length).unwrap() + 4,
                     _ => -1,
                 },
                 format: *format,
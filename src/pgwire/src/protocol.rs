@@ -37,6 +37,7 @@ use dataflow_types::PeekResponse;
 use ore::cast::CastFrom;
 use ore::netio::AsyncReady;
 use ore::str::StrExt;
+use repr::adt::datetime::Timezone;
 use repr::{Datum, RelationDesc, RelationType, Row, RowArena};
 use sql::ast::display::AstDisplay;
 use sql::ast::{FetchDirection, Ident, Raw, Statement};
@@ -172,8 +173,13 @@ where
     // Construct session.
     let mut session = Session::new(conn.id(), user);
     for (name, value) in params {
-        let _ = session.vars_mut().set(&name, &value);
+        let _ = session.vars_mut().set(&name, &value, false);
     }
+    // Startup parameters are not part of any transaction, so they take effect
+    // immediately.
+    session
+        .vars_mut()
+        .end_transaction(&EndTransactionAction::Commit);
 
     // Register session with coordinator.
     let (mut coord_client, startup) = match coord_client.startup(session).await {
@@ -578,11 +584,12 @@ where
             return self.aborted_txn_error().await;
         }
         let buf = RowArena::new();
+        let tz = self.coord_client.session().vars().timezone();
         let mut params: Vec<(Datum, repr::ScalarType)> = Vec::new();
         for (raw_param, typ, format) in izip!(raw_params, param_types, param_formats) {
             match raw_param {
                 None => params.push(pgrepr::null_datum(typ)),
-                Some(bytes) => match pgrepr::Value::decode(format, typ, &bytes) {
+                Some(bytes) => match pgrepr::Value::decode(format, typ, &bytes, tz) {
                     Ok(param) => params.push(param.into_datum(&buf, typ)),
                     Err(err) => {
                         let msg = format!("unable to decode parameter: {}", err);
@@ -1019,6 +1026,7 @@ where
                     }
                 }
             }
+            ExecuteResponse::SetTransaction => command_complete!("SET"),
             ExecuteResponse::SetVariable { name } => {
                 // This code is somewhat awkwardly structured because we
                 // can't hold `var` across an await point.
@@ -1244,9 +1252,14 @@ where
                     //  let mut batch_rows = batch_rows;
                     // Drain panics if it's > len, so cap it.
                     let drain_rows = cmp::min(want_rows, batch_rows.len());
+                    let tz = self.coord_client.session().vars().timezone();
                     self.conn
                         .send_all(batch_rows.drain(..drain_rows).map(|row| {
-                            BackendMessage::DataRow(pgrepr::values_from_row(row, row_desc.typ()))
+                            BackendMessage::DataRow(pgrepr::values_from_row(
+                                row,
+                                row_desc.typ(),
+                                tz,
+                            ))
                         }))
                         .await?;
                     total_sent_rows += drain_rows;
@@ -1302,7 +1315,7 @@ where
         mut stream: RowBatchStream,
     ) -> Result<State, io::Error> {
         let (encode_fn, encode_format): (
-            fn(Row, &RelationType, &mut Vec<u8>) -> Result<(), std::io::Error>,
+            fn(Row, &RelationType, Timezone, &mut Vec<u8>) -> Result<(), std::io::Error>,
             pgrepr::Format,
         ) = match format {
             CopyFormat::Text => (message::encode_copy_row_text, pgrepr::Format::Text),
@@ -1318,6 +1331,7 @@ where
         };
 
         let typ = row_desc.typ();
+        let tz = self.coord_client.session().vars().timezone();
         let column_formats = iter::repeat(encode_format)
             .take(typ.column_types.len())
             .collect();
@@ -1381,7 +1395,7 @@ where
                     Some(rows) => {
                         count += rows.len();
                         for row in rows {
-                            encode_fn(row, typ, &mut out)?;
+                            encode_fn(row, typ, tz, &mut out)?;
                             self.conn
                                 .send(BackendMessage::CopyData(mem::take(&mut out)))
                                 .await?;
@@ -1487,6 +1501,7 @@ where
                 &column_types,
                 &params.delimiter,
                 &params.null,
+                self.coord_client.session().vars().timezone(),
             ) {
                 Ok(rows) => rows,
                 Err(e) => {
@@ -24,8 +24,25 @@ use sql_parser::impl_display;
 
 pub enum PgScalarType {
     Simple(PgType),
-    Numeric { precision: u16, scale: u16 },
-    NumericArray { precision: u16, scale: u16 },
+    Numeric {
+        precision: u16,
+        scale: u16,
+    },
+    NumericArray {
+        precision: u16,
+        scale: u16,
+    },
+    /// A `bpchar` or `varchar` type with a declared length.
+    Char {
+        typ: PgType,
+        length: i32,
+    },
+    /// An array of `bpchar` or `varchar` elements with a declared length.
+    /// `typ` is the type of the elements.
+    CharArray {
+        typ: PgType,
+        length: i32,
+    },
 }
 
 impl AstDisplay for PgScalarType {
@@ -48,6 +65,18 @@ impl AstDisplay for PgScalarType {
                 f.write_str(scale);
                 f.write_str(")[]");
             }
+            Self::Char { typ, length } => {
+                f.write_str(typ);
+                f.write_str("(");
+                f.write_str(length);
+                f.write_str(")");
+            }
+            Self::CharArray { typ, length } => {
+                f.write_str(typ);
+                f.write_str("(");
+                f.write_str(length);
+                f.write_str(")[]");
+            }
         }
     }
 }
@@ -220,6 +249,31 @@ pub async fn publication_info(
                             PgScalarType::NumericArray { scale, precision }
                         }
                     }
+                    typ @ PgType::BPCHAR
+                    | typ @ PgType::VARCHAR
+                    | typ @ PgType::BPCHAR_ARRAY
+                    | typ @ PgType::VARCHAR_ARRAY => {
+                        let modifier: i32 = row.get("modifier");
+                        // The modifier of a character type is its length
+                        // plus the size of the varlena header, or -1 if the
+                        // type was declared without a length.
+                        if modifier < 0 {
+                            PgScalarType::Simple(typ)
+                        } else {
+                            let length = modifier - 4;
+                            match typ {
+                                PgType::BPCHAR_ARRAY => PgScalarType::CharArray {
+                                    typ: PgType::BPCHAR,
+                                    length,
+                                },
+                                PgType::VARCHAR_ARRAY => PgScalarType::CharArray {
+                                    typ: PgType::VARCHAR,
+                                    length,
+                                },
+                                typ => PgScalarType::Char { typ, length },
+                            }
+                        }
+                    }
                     other => PgScalarType::Simple(other),
                 };
                 let not_null: bool = row.get("not_null");
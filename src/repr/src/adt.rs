@@ -19,8 +19,11 @@
 
 pub mod apd;
 pub mod array;
+pub mod char;
 pub mod datetime;
 pub mod decimal;
+pub mod inet;
 pub mod interval;
 pub mod jsonb;
+pub mod range;
 pub mod regex;
@@ -13,6 +13,7 @@
 //! [`rust-dec`]: https://github.com/MaterializeInc/rust-dec/
 
 use std::convert::TryFrom;
+use std::iter;
 
 use anyhow::bail;
 use dec::{Context, Decimal};
@@ -89,6 +90,87 @@ pub fn twos_complement_be_to_i128(input: &[u8]) -> Result<i128, anyhow::Error> {
     Ok(significand)
 }
 
+/// Parses an [`Apd`] with the given scale from a buffer storing the two's
+/// complement representation of its significand in big-endian byte order.
+///
+/// Unlike [`twos_complement_be_to_i128`], this supports values of up to
+/// [`APD_DATUM_MAX_PRECISION`] digits.
+pub fn twos_complement_be_to_apd(input: &[u8], scale: usize) -> Result<Apd, anyhow::Error> {
+    let negative = !input.is_empty() && (input[0] & 0x80) != 0;
+    let mut cx = cx_datum();
+    let base = Apd::from(256);
+    let mut n = Apd::zero();
+    for byte in input {
+        // The magnitude of a negative number is one more than the value of
+        // its inverted bits.
+        let byte = if negative { !*byte } else { *byte };
+        cx.mul(&mut n, &base);
+        cx.add(&mut n, &Apd::from(i32::from(byte)));
+    }
+    if negative {
+        cx.add(&mut n, &Apd::from(1));
+        cx.neg(&mut n);
+    }
+    cx.scaleb(&mut n, &Apd::from(-i32::try_from(scale)?));
+    let status = cx.status();
+    if status.overflow() || status.invalid_operation() {
+        bail!(
+            "decimal value exceeds maximum precision {}",
+            APD_DATUM_MAX_PRECISION
+        );
+    }
+    munge_apd(&mut n)?;
+    Ok(n)
+}
+
+/// Returns the two's complement representation of the significand of `n`,
+/// rescaled to `scale`, in big-endian byte order.
+///
+/// This is the inverse of [`twos_complement_be_to_apd`]. Errors if `n` is NaN
+/// or infinite, as neither has such a representation.
+pub fn apd_to_twos_complement_be(mut n: Apd, scale: u8) -> Result<Vec<u8>, anyhow::Error> {
+    if n.is_nan() || n.is_infinite() {
+        bail!("{} has no two's complement representation", n);
+    }
+    if -n.exponent() > i32::from(scale) {
+        rescale(&mut n, scale)?;
+    }
+    let s = n.to_standard_notation_string();
+    let frac_digits = s.find('.').map(|i| s.len() - i - 1).unwrap_or(0);
+    let digits = s
+        .bytes()
+        .filter(u8::is_ascii_digit)
+        .chain(iter::repeat(b'0').take(usize::from(scale) - frac_digits));
+    // Accumulate the magnitude as little-endian base-256 digits.
+    let mut buf = vec![];
+    for digit in digits {
+        let mut carry = u32::from(digit - b'0');
+        for byte in buf.iter_mut() {
+            let v = u32::from(*byte) * 10 + carry;
+            *byte = v as u8;
+            carry = v >> 8;
+        }
+        if carry > 0 {
+            buf.push(carry as u8);
+        }
+    }
+    // Leave room for the sign bit.
+    if buf.last().map_or(true, |byte| byte & 0x80 != 0) {
+        buf.push(0);
+    }
+    if n.is_negative() {
+        // Invert the bits and add one.
+        let mut carry = true;
+        for byte in buf.iter_mut() {
+            let (v, overflow) = (!*byte).overflowing_add(u8::from(carry));
+            *byte = v;
+            carry = overflow;
+        }
+    }
+    buf.reverse();
+    Ok(buf)
+}
+
 /// Returns `n`'s precision, i.e. the total number of digits represented by `n`
 /// in standard notation not including a zero in the "one's place" in (-1,1).
 fn get_precision(n: &Apd) -> u32 {
@@ -164,3 +246,70 @@ pub fn rescale(n: &mut Apd, scale: u8) -> Result<(), anyhow::Error> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_twos_complement_be_to_apd() {
+        fn check(input: &[u8], scale: usize, expected: &str) {
+            let n = twos_complement_be_to_apd(input, scale).unwrap();
+            assert_eq!(n.to_standard_notation_string(), expected);
+        }
+        check(&[], 0, "0");
+        check(&[0x01], 0, "1");
+        check(&[0xff], 0, "-1");
+        check(&[0xba], 0, "-70");
+        check(&[0x04, 0xd2], 2, "12.34");
+        check(&[0xfb, 0x2e], 2, "-12.34");
+        // The largest 39-digit value, which exceeds the range of an `i128`.
+        check(
+            &[
+                0x02, 0xf0, 0x50, 0xfe, 0x93, 0x89, 0x43, 0xac, 0xc4, 0x5f, 0x65, 0x56, 0x7f, 0xff,
+                0xff, 0xff, 0xff,
+            ],
+            0,
+            "999999999999999999999999999999999999999",
+        );
+    }
+
+    #[test]
+    fn test_apd_to_twos_complement_be() {
+        fn check(s: &str, scale: u8, expected: &[u8]) {
+            let n = cx_datum().parse(s).unwrap();
+            let buf = apd_to_twos_complement_be(n, scale).unwrap();
+            assert_eq!(buf, expected);
+            assert_eq!(
+                twos_complement_be_to_apd(&buf, usize::from(scale))
+                    .unwrap()
+                    .to_standard_notation_string(),
+                s
+            );
+        }
+        check("0", 0, &[0x00]);
+        check("1", 0, &[0x01]);
+        check("-1", 0, &[0xff]);
+        check("-70", 0, &[0xba]);
+        check("128", 0, &[0x00, 0x80]);
+        check("-128", 0, &[0xff, 0x80]);
+        check("12.34", 2, &[0x04, 0xd2]);
+        check("-12.34", 2, &[0xfb, 0x2e]);
+        check(
+            "999999999999999999999999999999999999999",
+            0,
+            &[
+                0x02, 0xf0, 0x50, 0xfe, 0x93, 0x89, 0x43, 0xac, 0xc4, 0x5f, 0x65, 0x56, 0x7f, 0xff,
+                0xff, 0xff, 0xff,
+            ],
+        );
+
+        // Values are padded or rounded to the requested scale.
+        let n = cx_datum().parse("1.5").unwrap();
+        assert_eq!(apd_to_twos_complement_be(n, 2).unwrap(), &[0x00, 0x96]);
+        let n = cx_datum().parse("1.26").unwrap();
+        assert_eq!(apd_to_twos_complement_be(n, 1).unwrap(), &[0x0d]);
+
+        assert!(apd_to_twos_complement_be(Apd::nan(), 0).is_err());
+    }
+}
@@ -0,0 +1,107 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Functions related to Materialize's character types, `char(n)` and
+//! `varchar(n)`.
+//!
+//! Both types are represented as [`Datum::String`](crate::Datum::String).
+//! Values of type `char(n)` are stored blank-padded to their full length.
+
+use std::error::Error;
+use std::fmt;
+
+/// The maximum length of a `char(n)` or `varchar(n)` type, in characters.
+pub const MAX_LENGTH: usize = 10_485_760;
+
+/// An error indicating that a string does not fit in a character type of the
+/// requested length.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StringTooLongError;
+
+impl fmt::Display for StringTooLongError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("value too long for type")
+    }
+}
+
+impl Error for StringTooLongError {}
+
+/// Truncates `s` to at most `length` characters.
+///
+/// If `fail_on_len` is true, truncating any character other than a space is an
+/// error, as it is when assigning a string to a character column in
+/// PostgreSQL.
+fn truncate(s: &str, length: usize, fail_on_len: bool) -> Result<&str, StringTooLongError> {
+    match s.char_indices().nth(length) {
+        None => Ok(s),
+        Some((i, _)) => {
+            let (head, tail) = s.split_at(i);
+            if fail_on_len && tail.chars().any(|c| c != ' ') {
+                Err(StringTooLongError)
+            } else {
+                Ok(head)
+            }
+        }
+    }
+}
+
+/// Converts `s` to a value of type `char(length)`, truncating it or padding it
+/// with spaces as necessary.
+///
+/// See [`truncate`] for the meaning of `fail_on_len`.
+pub fn pad_char(s: &str, length: usize, fail_on_len: bool) -> Result<String, StringTooLongError> {
+    let s = truncate(s, length, fail_on_len)?;
+    let mut out = String::with_capacity(length);
+    out.push_str(s);
+    for _ in s.chars().count()..length {
+        out.push(' ');
+    }
+    Ok(out)
+}
+
+/// Strips the trailing spaces from a value of type `char(n)`, which are not
+/// semantically significant.
+pub fn trim_char(s: &str) -> &str {
+    s.trim_end_matches(' ')
+}
+
+/// Converts `s` to a value of type `varchar(max_length)`, truncating it as
+/// necessary.
+///
+/// See [`truncate`] for the meaning of `fail_on_len`.
+pub fn truncate_varchar(
+    s: &str,
+    max_length: usize,
+    fail_on_len: bool,
+) -> Result<&str, StringTooLongError> {
+    truncate(s, max_length, fail_on_len)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pad_char() {
+        assert_eq!(pad_char("ab", 4, true), Ok("ab  ".into()));
+        assert_eq!(pad_char("abcd", 2, false), Ok("ab".into()));
+        assert_eq!(pad_char("abcd", 2, true), Err(StringTooLongError));
+        assert_eq!(pad_char("ab   ", 3, true), Ok("ab ".into()));
+        assert_eq!(pad_char("éé", 3, true), Ok("éé ".into()));
+        assert_eq!(trim_char("ab  "), "ab");
+    }
+
+    #[test]
+    fn test_truncate_varchar() {
+        assert_eq!(truncate_varchar("abc", 5, true), Ok("abc"));
+        assert_eq!(truncate_varchar("abcdef", 3, false), Ok("abc"));
+        assert_eq!(truncate_varchar("abcdef", 3, true), Err(StringTooLongError));
+        assert_eq!(truncate_varchar("abc  ", 3, true), Ok("abc"));
+    }
+}
@@ -16,7 +16,7 @@ use std::convert::TryInto;
 use std::fmt;
 use std::str::FromStr;
 
-use chrono::{FixedOffset, NaiveDate, NaiveTime};
+use chrono::{FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Offset, TimeZone};
 use chrono_tz::Tz;
 use serde::{Deserialize, Serialize};
 
@@ -312,6 +312,28 @@ mod fixed_offset_serde {
     }
 }
 
+impl Timezone {
+    /// The UTC timezone.
+    pub const UTC: Timezone = Timezone::Tz(Tz::UTC);
+
+    /// Reports whether the timezone is always at a zero offset from UTC.
+    pub fn is_utc(&self) -> bool {
+        match self {
+            Timezone::FixedOffset(offset) => offset.local_minus_utc() == 0,
+            Timezone::Tz(tz) => matches!(tz, Tz::UTC | Tz::Etc__UTC),
+        }
+    }
+
+    /// Returns the offset from UTC that the timezone observes at the instant
+    /// `utc`.
+    pub fn offset_at(&self, utc: &NaiveDateTime) -> FixedOffset {
+        match self {
+            Timezone::FixedOffset(offset) => *offset,
+            Timezone::Tz(tz) => tz.offset_from_utc_datetime(utc).fix(),
+        }
+    }
+}
+
 impl PartialOrd for Timezone {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(self.cmp(other))
@@ -0,0 +1,219 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Functions related to Materialize's network address types, `inet` and
+//! `cidr`.
+//!
+//! Both types are represented as [`Datum::Inet`](crate::Datum::Inet). Values
+//! of type `cidr` additionally never have any bits set to the right of their
+//! netmask.
+
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+/// An IPv4 or IPv6 host address together with the length of the netmask of
+/// its subnet, as in PostgreSQL's `inet` type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Inet {
+    addr: IpAddr,
+    prefix_len: u8,
+}
+
+/// An error indicating that a netmask length is invalid for the family of its
+/// address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidPrefixLenError;
+
+impl fmt::Display for InvalidPrefixLenError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("invalid mask length")
+    }
+}
+
+impl Error for InvalidPrefixLenError {}
+
+impl Inet {
+    /// Constructs a new `Inet` from an address and the length of its netmask.
+    ///
+    /// Returns an error if `prefix_len` exceeds the number of bits in `addr`.
+    pub fn new(addr: IpAddr, prefix_len: u8) -> Result<Inet, InvalidPrefixLenError> {
+        if prefix_len > max_prefix_len(&addr) {
+            return Err(InvalidPrefixLenError);
+        }
+        Ok(Inet { addr, prefix_len })
+    }
+
+    /// Returns the host address.
+    pub fn addr(&self) -> IpAddr {
+        self.addr
+    }
+
+    /// Returns the length of the netmask, in bits.
+    pub fn prefix_len(&self) -> u8 {
+        self.prefix_len
+    }
+
+    /// Returns the number of bits in the address, i.e. 32 for IPv4 addresses
+    /// and 128 for IPv6 addresses.
+    pub fn max_prefix_len(&self) -> u8 {
+        max_prefix_len(&self.addr)
+    }
+
+    /// Reports whether no bits are set to the right of the netmask, as is
+    /// required of `cidr` values.
+    pub fn is_network(&self) -> bool {
+        self.addr_bits() & !self.mask() == 0
+    }
+
+    /// Returns the network part of the address, i.e. the address with all
+    /// bits to the right of the netmask cleared.
+    pub fn network(&self) -> Inet {
+        Inet {
+            addr: self.with_addr_bits(self.addr_bits() & self.mask()),
+            prefix_len: self.prefix_len,
+        }
+    }
+
+    /// Returns a copy of `self` with the netmask length set to `prefix_len`.
+    pub fn with_prefix_len(&self, prefix_len: u8) -> Result<Inet, InvalidPrefixLenError> {
+        Inet::new(self.addr, prefix_len)
+    }
+
+    /// Reports whether the subnet of `self` strictly contains the subnet of
+    /// `other`.
+    pub fn contains(&self, other: &Inet) -> bool {
+        self.prefix_len < other.prefix_len && self.contains_or_equals(other)
+    }
+
+    /// Reports whether the subnet of `self` contains or is equal to the
+    /// subnet of `other`.
+    pub fn contains_or_equals(&self, other: &Inet) -> bool {
+        self.is_same_family(other)
+            && self.prefix_len <= other.prefix_len
+            && self.addr_bits() & self.mask() == other.addr_bits() & self.mask()
+    }
+
+    fn is_same_family(&self, other: &Inet) -> bool {
+        self.addr.is_ipv4() == other.addr.is_ipv4()
+    }
+
+    /// Returns the bits of the address, right-aligned.
+    fn addr_bits(&self) -> u128 {
+        match self.addr {
+            IpAddr::V4(addr) => u128::from(u32::from(addr)),
+            IpAddr::V6(addr) => u128::from(addr),
+        }
+    }
+
+    /// Returns an address of the same family as `self` with the given bits.
+    fn with_addr_bits(&self, bits: u128) -> IpAddr {
+        match self.addr {
+            IpAddr::V4(_) => IpAddr::V4(Ipv4Addr::from(
+                u32::try_from(bits).expect("IPv4 address bits fit in u32"),
+            )),
+            IpAddr::V6(_) => IpAddr::V6(Ipv6Addr::from(bits)),
+        }
+    }
+
+    /// Returns the netmask, aligned like the result of `addr_bits`.
+    fn mask(&self) -> u128 {
+        mask_bits(self.max_prefix_len(), self.prefix_len)
+    }
+}
+
+fn max_prefix_len(addr: &IpAddr) -> u8 {
+    match addr {
+        IpAddr::V4(_) => 32,
+        IpAddr::V6(_) => 128,
+    }
+}
+
+/// Returns a mask whose `prefix_len` highest bits out of `width` are set.
+fn mask_bits(width: u8, prefix_len: u8) -> u128 {
+    let all = if width == 128 {
+        u128::MAX
+    } else {
+        (1 << width) - 1
+    };
+    match all.checked_shr(u32::from(prefix_len)) {
+        Some(host) => all & !host,
+        None => all,
+    }
+}
+
+/// Orders network addresses as PostgreSQL does: IPv4 addresses sort before
+/// IPv6 addresses, and otherwise addresses are compared first by their common
+/// network part, then by the length of their netmask, and finally by their
+/// full address.
+impl Ord for Inet {
+    fn cmp(&self, other: &Inet) -> Ordering {
+        self.addr
+            .is_ipv6()
+            .cmp(&other.addr.is_ipv6())
+            .then_with(|| {
+                let mask = mask_bits(self.max_prefix_len(), self.prefix_len.min(other.prefix_len));
+                (self.addr_bits() & mask).cmp(&(other.addr_bits() & mask))
+            })
+            .then_with(|| self.prefix_len.cmp(&other.prefix_len))
+            .then_with(|| self.addr_bits().cmp(&other.addr_bits()))
+    }
+}
+
+impl PartialOrd for Inet {
+    fn partial_cmp(&self, other: &Inet) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Formats the address followed by its netmask length, as PostgreSQL's
+/// `text(inet)` function does.
+impl fmt::Display for Inet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}/{}", self.addr, self.prefix_len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inet(s: &str, prefix_len: u8) -> Inet {
+        Inet::new(s.parse().unwrap(), prefix_len).unwrap()
+    }
+
+    #[test]
+    fn test_network() {
+        assert_eq!(inet("192.168.1.5", 24).network(), inet("192.168.1.0", 24));
+        assert_eq!(inet("192.168.1.5", 0).network(), inet("0.0.0.0", 0));
+        assert_eq!(inet("192.168.1.5", 32).network(), inet("192.168.1.5", 32));
+        assert_eq!(inet("2001:db8::1", 32).network(), inet("2001:db8::", 32));
+        assert!(inet("10.0.0.0", 8).is_network());
+        assert!(!inet("10.0.0.1", 8).is_network());
+        assert!(Inet::new("10.0.0.1".parse().unwrap(), 33).is_err());
+    }
+
+    #[test]
+    fn test_contains() {
+        assert!(inet("10.0.0.0", 8).contains(&inet("10.1.2.3", 32)));
+        assert!(!inet("10.0.0.0", 8).contains(&inet("10.0.0.0", 8)));
+        assert!(inet("10.0.0.0", 8).contains_or_equals(&inet("10.0.0.0", 8)));
+        assert!(!inet("10.0.0.0", 8).contains(&inet("11.0.0.0", 16)));
+        assert!(!inet("0.0.0.0", 0).contains(&inet("::1", 128)));
+    }
+
+    #[test]
+    fn test_cmp() {
+        assert!(inet("10.0.0.0", 8) < inet("10.0.0.0", 16));
+        assert!(inet("10.1.0.0", 8) < inet("10.0.0.0", 16));
+        assert!(inet("255.255.255.255", 32) < inet("::", 0));
+    }
+}
@@ -9,6 +9,7 @@
 
 //! A time interval abstract data type.
 
+use std::convert::TryFrom;
 use std::fmt::{self, Write};
 
 use anyhow::bail;
@@ -30,6 +31,8 @@ pub struct Interval {
     pub duration: i128,
 }
 
+const NANOS_PER_DAY: i128 = 24 * 60 * 60 * 1_000_000_000;
+
 impl Default for Interval {
     fn default() -> Self {
         Self {
@@ -277,6 +280,49 @@ impl Interval {
             + Duration::nanoseconds(self.nanoseconds() as i64)
     }
 
+    /// Converts each 30-day period of the interval's duration into a month, in
+    /// the manner of PostgreSQL's `justify_days` function.
+    ///
+    /// Returns `None` if the resulting number of months overflows.
+    pub fn justify_days(&self) -> Option<Interval> {
+        let whole_months = self.duration / NANOS_PER_DAY / 30;
+        let mut months = self.months.checked_add(i32::try_from(whole_months).ok()?)?;
+        let mut duration = self.duration - whole_months * 30 * NANOS_PER_DAY;
+        let days = duration / NANOS_PER_DAY;
+        if months > 0 && days < 0 {
+            duration += 30 * NANOS_PER_DAY;
+            months -= 1;
+        } else if months < 0 && days > 0 {
+            duration -= 30 * NANOS_PER_DAY;
+            months += 1;
+        }
+        Some(Interval { months, duration })
+    }
+
+    /// Converts each 24-hour period of the interval's duration into a day, in
+    /// the manner of PostgreSQL's `justify_hours` function.
+    ///
+    /// `Interval` does not distinguish between days and 24-hour periods, so
+    /// every interval is already in this form and is returned unchanged.
+    pub fn justify_hours(&self) -> Interval {
+        *self
+    }
+
+    /// Like [`Interval::justify_days`], but additionally ensures that the
+    /// months and the duration have the same sign, in the manner of
+    /// PostgreSQL's `justify_interval` function.
+    pub fn justify_interval(&self) -> Option<Interval> {
+        let mut i = self.justify_days()?;
+        if i.months > 0 && i.duration < 0 {
+            i.duration += 30 * NANOS_PER_DAY;
+            i.months -= 1;
+        } else if i.months < 0 && i.duration > 0 {
+            i.duration -= 30 * NANOS_PER_DAY;
+            i.months += 1;
+        }
+        Some(i)
+    }
+
     /// Truncate the "tail" of the interval, removing all time units less than `f`.
     /// # Arguments
     /// - `f`: Round the interval down to the specified time unit.
@@ -647,4 +693,33 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn test_interval_justify() {
+        const DAY: i64 = 24 * 60 * 60;
+
+        // (input, justify_days, justify_interval), as (months, seconds).
+        let test_cases = [
+            ((0, 35 * DAY), (1, 5 * DAY), (1, 5 * DAY)),
+            ((0, -35 * DAY), (-1, -5 * DAY), (-1, -5 * DAY)),
+            ((1, -DAY), (0, 29 * DAY), (0, 29 * DAY)),
+            ((-1, DAY), (0, -29 * DAY), (0, -29 * DAY)),
+            ((1, -3600), (1, -3600), (0, 30 * DAY - 3600)),
+            ((-1, 3600), (-1, 3600), (0, -30 * DAY + 3600)),
+            ((12, 65 * DAY), (14, 5 * DAY), (14, 5 * DAY)),
+        ];
+
+        for (input, days, interval) in test_cases.iter() {
+            let i = Interval::new(input.0, input.1, 0).unwrap();
+            assert_eq!(
+                i.justify_days().unwrap(),
+                Interval::new(days.0, days.1, 0).unwrap()
+            );
+            assert_eq!(i.justify_hours(), i);
+            assert_eq!(
+                i.justify_interval().unwrap(),
+                Interval::new(interval.0, interval.1, 0).unwrap()
+            );
+        }
+    }
 }
@@ -0,0 +1,412 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! A range data type.
+//!
+//! The semantics of ranges follow the [PostgreSQL range types]. In particular,
+//! ranges over discrete element types (i.e., `int4`, `int8` and `date`) are
+//! always stored in their canonical form, with an inclusive lower bound and an
+//! exclusive upper bound, so that equal ranges have equal encodings.
+//!
+//! [PostgreSQL range types]: https://www.postgresql.org/docs/current/rangetypes.html
+
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+use serde::{Deserialize, Serialize};
+
+use crate::row::DatumList;
+use crate::{Datum, ScalarType};
+
+/// The range contains no points.
+pub(crate) const EMPTY: u8 = 1 << 0;
+/// The lower bound is inclusive.
+pub(crate) const LB_INC: u8 = 1 << 1;
+/// The upper bound is inclusive.
+pub(crate) const UB_INC: u8 = 1 << 2;
+/// The lower bound is infinite.
+pub(crate) const LB_INF: u8 = 1 << 3;
+/// The upper bound is infinite.
+pub(crate) const UB_INF: u8 = 1 << 4;
+
+/// Reports whether ranges may be constructed over elements of type `ty`.
+///
+/// These are the element types of PostgreSQL's built-in range types, except
+/// `numeric`.
+pub fn is_valid_element_type(ty: &ScalarType) -> bool {
+    matches!(
+        ty,
+        ScalarType::Int32
+            | ScalarType::Int64
+            | ScalarType::Date
+            | ScalarType::Timestamp
+            | ScalarType::TimestampTz
+    )
+}
+
+/// A contiguous range of values of some element type.
+#[derive(Clone, Copy, Debug)]
+pub struct Range<'a> {
+    /// The `EMPTY`, `*_INC` and `*_INF` flags that describe the range.
+    pub(crate) flags: u8,
+    /// The finite bounds of the range, lower bound first.
+    pub(crate) bounds: DatumList<'a>,
+}
+
+/// One bound of a [`Range`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct RangeBound<'a> {
+    /// The value of the bound, or `None` if the bound is infinite.
+    pub value: Option<Datum<'a>>,
+    /// Whether the bound itself is contained in the range.
+    ///
+    /// Infinite bounds are never inclusive.
+    pub inclusive: bool,
+}
+
+impl<'a> RangeBound<'a> {
+    /// Constructs a new finite bound.
+    pub fn new(value: Datum<'a>, inclusive: bool) -> RangeBound<'a> {
+        RangeBound {
+            value: Some(value),
+            inclusive,
+        }
+    }
+
+    /// Constructs a new infinite bound.
+    pub fn infinite() -> RangeBound<'a> {
+        RangeBound {
+            value: None,
+            inclusive: false,
+        }
+    }
+}
+
+impl<'a> Range<'a> {
+    /// Reports whether the range contains no points.
+    pub fn is_empty(&self) -> bool {
+        self.flags & EMPTY != 0
+    }
+
+    /// Returns the lower and upper bounds of the range, or `None` if the range
+    /// is empty.
+    pub fn bounds(&self) -> Option<(RangeBound<'a>, RangeBound<'a>)> {
+        if self.is_empty() {
+            return None;
+        }
+        let mut values = self.bounds.iter();
+        let lower = if self.flags & LB_INF != 0 {
+            RangeBound::infinite()
+        } else {
+            RangeBound::new(values.next().unwrap(), self.flags & LB_INC != 0)
+        };
+        let upper = if self.flags & UB_INF != 0 {
+            RangeBound::infinite()
+        } else {
+            RangeBound::new(values.next().unwrap(), self.flags & UB_INC != 0)
+        };
+        Some((lower, upper))
+    }
+
+    /// Returns the lower bound of the range, or `None` if the range is empty.
+    pub fn lower(&self) -> Option<RangeBound<'a>> {
+        self.bounds().map(|(lower, _)| lower)
+    }
+
+    /// Returns the upper bound of the range, or `None` if the range is empty.
+    pub fn upper(&self) -> Option<RangeBound<'a>> {
+        self.bounds().map(|(_, upper)| upper)
+    }
+
+    /// Reports whether `elem` lies within the range.
+    pub fn contains_elem(&self, elem: Datum<'a>) -> bool {
+        let (lower, upper) = match self.bounds() {
+            None => return false,
+            Some(bounds) => bounds,
+        };
+        if let Some(value) = lower.value {
+            match elem.cmp(&value) {
+                Ordering::Less => return false,
+                Ordering::Equal if !lower.inclusive => return false,
+                _ => (),
+            }
+        }
+        if let Some(value) = upper.value {
+            match elem.cmp(&value) {
+                Ordering::Greater => return false,
+                Ordering::Equal if !upper.inclusive => return false,
+                _ => (),
+            }
+        }
+        true
+    }
+
+    /// Reports whether every point in `other` also lies within this range.
+    pub fn contains_range(&self, other: &Range<'a>) -> bool {
+        let (lower2, upper2) = match other.bounds() {
+            None => return true,
+            Some(bounds) => bounds,
+        };
+        let (lower1, upper1) = match self.bounds() {
+            None => return false,
+            Some(bounds) => bounds,
+        };
+        cmp_bounds(lower1, true, lower2, true) != Ordering::Greater
+            && cmp_bounds(upper1, false, upper2, false) != Ordering::Less
+    }
+
+    /// Reports whether this range and `other` have any points in common.
+    pub fn overlaps(&self, other: &Range<'a>) -> bool {
+        let ((lower1, upper1), (lower2, upper2)) = match (self.bounds(), other.bounds()) {
+            (Some(b1), Some(b2)) => (b1, b2),
+            _ => return false,
+        };
+        (cmp_bounds(lower1, true, lower2, true) != Ordering::Less
+            && cmp_bounds(lower1, true, upper2, false) != Ordering::Greater)
+            || (cmp_bounds(lower2, true, lower1, true) != Ordering::Less
+                && cmp_bounds(lower2, true, upper1, false) != Ordering::Greater)
+    }
+
+    /// Reports whether this range and `other` do not overlap but share a
+    /// boundary, so that their union is contiguous.
+    pub fn is_adjacent(&self, other: &Range<'a>) -> bool {
+        let ((lower1, upper1), (lower2, upper2)) = match (self.bounds(), other.bounds()) {
+            (Some(b1), Some(b2)) => (b1, b2),
+            _ => return false,
+        };
+        let touches = |upper: RangeBound, lower: RangeBound| match (upper.value, lower.value) {
+            (Some(u), Some(l)) => u == l && upper.inclusive != lower.inclusive,
+            _ => false,
+        };
+        touches(upper1, lower2) || touches(upper2, lower1)
+    }
+
+    /// Returns the bounds of the smallest range that contains both this range
+    /// and `other`, or `None` if both ranges are empty.
+    ///
+    /// Any gap between the two ranges is included in the result.
+    pub fn merge_bounds(&self, other: &Range<'a>) -> Option<(RangeBound<'a>, RangeBound<'a>)> {
+        match (self.bounds(), other.bounds()) {
+            (None, None) => None,
+            (Some(b), None) | (None, Some(b)) => Some(b),
+            (Some((lower1, upper1)), Some((lower2, upper2))) => {
+                let lower = match cmp_bounds(lower1, true, lower2, true) {
+                    Ordering::Greater => lower2,
+                    _ => lower1,
+                };
+                let upper = match cmp_bounds(upper1, false, upper2, false) {
+                    Ordering::Less => upper2,
+                    _ => upper1,
+                };
+                Some((lower, upper))
+            }
+        }
+    }
+
+    /// Returns the bounds of the union of this range and `other`, or `None` if
+    /// both ranges are empty.
+    ///
+    /// Returns an error if the union would not be contiguous.
+    pub fn union_bounds(
+        &self,
+        other: &Range<'a>,
+    ) -> Result<Option<(RangeBound<'a>, RangeBound<'a>)>, InvalidRangeError> {
+        if !self.is_empty()
+            && !other.is_empty()
+            && !self.overlaps(other)
+            && !self.is_adjacent(other)
+        {
+            return Err(InvalidRangeError::DiscontiguousUnion);
+        }
+        Ok(self.merge_bounds(other))
+    }
+
+    /// Returns the bounds of the intersection of this range and `other`, or
+    /// `None` if the intersection is empty.
+    pub fn intersection_bounds(
+        &self,
+        other: &Range<'a>,
+    ) -> Option<(RangeBound<'a>, RangeBound<'a>)> {
+        if !self.overlaps(other) {
+            return None;
+        }
+        let (lower1, upper1) = self.bounds().unwrap();
+        let (lower2, upper2) = other.bounds().unwrap();
+        let lower = match cmp_bounds(lower1, true, lower2, true) {
+            Ordering::Less => lower2,
+            _ => lower1,
+        };
+        let upper = match cmp_bounds(upper1, false, upper2, false) {
+            Ordering::Greater => upper2,
+            _ => upper1,
+        };
+        Some((lower, upper))
+    }
+}
+
+/// Compares two range bounds, each of which may be either a lower or an upper
+/// bound.
+///
+/// This is a port of PostgreSQL's `range_cmp_bounds`. Infinite lower bounds
+/// sort before, and infinite upper bounds after, every finite bound. When two
+/// finite bounds have the same value, an exclusive lower bound sorts after,
+/// and an exclusive upper bound before, an inclusive bound.
+pub fn cmp_bounds(b1: RangeBound, lower1: bool, b2: RangeBound, lower2: bool) -> Ordering {
+    match (b1.value, b2.value) {
+        (None, None) if lower1 == lower2 => Ordering::Equal,
+        (None, _) if lower1 => Ordering::Less,
+        (None, _) => Ordering::Greater,
+        (_, None) if lower2 => Ordering::Greater,
+        (_, None) => Ordering::Less,
+        (Some(v1), Some(v2)) => match v1.cmp(&v2) {
+            Ordering::Equal => match (b1.inclusive, b2.inclusive) {
+                (false, false) if lower1 == lower2 => Ordering::Equal,
+                (false, _) if lower1 => Ordering::Greater,
+                (false, _) => Ordering::Less,
+                (_, false) if lower2 => Ordering::Less,
+                (_, false) => Ordering::Greater,
+                (true, true) => Ordering::Equal,
+            },
+            o => o,
+        },
+    }
+}
+
+// Equality and hashing can consider the encoded representation directly, as
+// the canonicalization performed by `Row::push_range` ensures that equal
+// ranges are encoded identically.
+
+impl PartialEq for Range<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.flags == other.flags && self.bounds == other.bounds
+    }
+}
+
+impl Eq for Range<'_> {}
+
+impl Hash for Range<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.flags.hash(state);
+        self.bounds.hash(state);
+    }
+}
+
+/// Ranges sort as in PostgreSQL: empty ranges sort first, and non-empty ranges
+/// sort by their lower bound, then by their upper bound.
+impl Ord for Range<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.bounds(), other.bounds()) {
+            (None, None) => Ordering::Equal,
+            (None, Some(_)) => Ordering::Less,
+            (Some(_), None) => Ordering::Greater,
+            (Some((lower1, upper1)), Some((lower2, upper2))) => {
+                cmp_bounds(lower1, true, lower2, true)
+                    .then_with(|| cmp_bounds(upper1, false, upper2, false))
+            }
+        }
+    }
+}
+
+impl PartialOrd for Range<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// An error that can occur when constructing a range.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd, Serialize, Deserialize)]
+pub enum InvalidRangeError {
+    /// The lower bound of the range is greater than its upper bound.
+    MisorderedBounds,
+    /// A bound of a range over a discrete type could not be canonicalized
+    /// because it would overflow its element type.
+    CanonicalizationOverflow,
+    /// The union of two ranges would not be contiguous.
+    DiscontiguousUnion,
+    /// The bound flags passed to a range constructor were not one of `[]`,
+    /// `[)`, `(]` or `()`.
+    InvalidFlags,
+}
+
+impl fmt::Display for InvalidRangeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InvalidRangeError::MisorderedBounds => {
+                f.write_str("range lower bound must be less than or equal to range upper bound")
+            }
+            InvalidRangeError::CanonicalizationOverflow => f.write_str("range bound out of range"),
+            InvalidRangeError::DiscontiguousUnion => {
+                f.write_str("result of range union would not be contiguous")
+            }
+            InvalidRangeError::InvalidFlags => f.write_str("invalid range bound flags"),
+        }
+    }
+}
+
+impl Error for InvalidRangeError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        None
+    }
+}
+
+/// Converts a pair of bounds into canonical form.
+///
+/// Bounds over discrete element types are rewritten to be inclusive below and
+/// exclusive above. Returns `Ok(None)` if the bounds describe an empty range.
+pub(crate) fn canonicalize<'a>(
+    mut lower: RangeBound<'a>,
+    mut upper: RangeBound<'a>,
+) -> Result<Option<(RangeBound<'a>, RangeBound<'a>)>, InvalidRangeError> {
+    if let (Some(l), Some(u)) = (lower.value, upper.value) {
+        if l > u {
+            return Err(InvalidRangeError::MisorderedBounds);
+        }
+    }
+    if let Some(value) = lower.value {
+        if !lower.inclusive {
+            if let Some(succ) = discrete_successor(value)? {
+                lower = RangeBound::new(succ, true);
+            }
+        }
+    }
+    if let Some(value) = upper.value {
+        if upper.inclusive {
+            if let Some(succ) = discrete_successor(value)? {
+                upper = RangeBound::new(succ, false);
+            }
+        }
+    }
+    if let (Some(l), Some(u)) = (lower.value, upper.value) {
+        // Canonicalization can push the lower bound past the upper bound,
+        // e.g., `(1,1)` becomes `[2,1)`, but only when the range is empty.
+        match l.cmp(&u) {
+            Ordering::Greater => return Ok(None),
+            Ordering::Equal if !(lower.inclusive && upper.inclusive) => return Ok(None),
+            _ => (),
+        }
+    }
+    Ok(Some((lower, upper)))
+}
+
+/// Returns the value immediately following `datum`, if `datum` belongs to a
+/// discrete element type.
+fn discrete_successor(datum: Datum) -> Result<Option<Datum>, InvalidRangeError> {
+    let succ = match datum {
+        Datum::Int32(i) => i.checked_add(1).map(Datum::Int32),
+        Datum::Int64(i) => i.checked_add(1).map(Datum::Int64),
+        Datum::Date(d) => d.succ_opt().map(Datum::Date),
+        _ => return Ok(None),
+    };
+    match succ {
+        None => Err(InvalidRangeError::CanonicalizationOverflow),
+        succ => Ok(succ),
+    }
+}
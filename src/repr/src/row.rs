@@ -13,8 +13,9 @@ use std::cmp::Ordering;
 use std::convert::{TryFrom, TryInto};
 use std::fmt;
 use std::mem::{size_of, transmute};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
 
-use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use chrono::{DateTime, Datelike, NaiveDate, NaiveDateTime, NaiveTime, Timelike, Utc};
 use dec::OrderedDecimal;
 use ordered_float::OrderedFloat;
 use serde::{Deserialize, Serialize};
@@ -27,7 +28,9 @@ use crate::adt::array::{
     Array, ArrayDimension, ArrayDimensions, InvalidArrayError, MAX_ARRAY_DIMENSIONS,
 };
 use crate::adt::decimal::Significand;
+use crate::adt::inet::Inet;
 use crate::adt::interval::Interval;
+use crate::adt::range::{self, InvalidRangeError, Range, RangeBound};
 use crate::Datum;
 use fmt::Debug;
 
@@ -215,6 +218,8 @@ enum Tag {
     JsonNull,
     Dummy,
     APD,
+    Range,
+    Inet,
 }
 
 // --------------------------------------------------------------------------------
@@ -385,9 +390,266 @@ unsafe fn read_datum<'a>(data: &'a [u8], offset: &mut usize) -> Datum<'a> {
             let d = Apd::from_raw_parts(digits, exponent, bits, lsu_u8);
             Datum::APD(OrderedDecimal(d))
         }
+        Tag::Range => {
+            // See the comment in `Row::push_range` for details on the encoding
+            // of ranges.
+            let flags = read_copy::<u8>(data, offset);
+            let bytes = read_untagged_bytes(data, offset);
+            Datum::Range(Range {
+                flags,
+                bounds: DatumList { data: bytes },
+            })
+        }
+        Tag::Inet => {
+            // See the comment in `push_datum` for details on the encoding of
+            // network addresses.
+            let addr = match read_copy::<u8>(data, offset) {
+                4 => {
+                    let mut octets = [0; 4];
+                    octets.copy_from_slice(&data[*offset..*offset + 4]);
+                    *offset += 4;
+                    IpAddr::V4(Ipv4Addr::from(octets))
+                }
+                _ => {
+                    let mut octets = [0; 16];
+                    octets.copy_from_slice(&data[*offset..*offset + 16]);
+                    *offset += 16;
+                    IpAddr::V6(Ipv6Addr::from(octets))
+                }
+            };
+            let prefix_len = read_copy::<u8>(data, offset);
+            Datum::Inet(Inet::new(addr, prefix_len).expect("prefix length verified when written"))
+        }
+    }
+}
+
+/// Checks that `data` is a valid encoding of a sequence of datums.
+///
+/// Unlike [`read_datum`], this function is safe to call on arbitrary bytes.
+/// It returns the tags of the datums it checked, in order.
+fn validate_datums(data: &[u8]) -> Result<Vec<Tag>, String> {
+    let mut offset = 0;
+    let mut tags = vec![];
+    while offset < data.len() {
+        tags.push(validate_datum(data, &mut offset)?);
+    }
+    Ok(tags)
+}
+
+/// Checks that a valid datum starts at byte `offset`, returning its tag.
+///
+/// Updates `offset` to point to the first byte after the end of the datum.
+fn validate_datum(data: &[u8], offset: &mut usize) -> Result<Tag, String> {
+    let tag = match take(data, offset, 1)?[0] {
+        b if b <= Tag::Inet as u8 => {
+            // SAFETY: `Tag` is a fieldless enum, so its discriminants run
+            // from zero to `Tag::Inet`, and it is one byte wide, as
+            // `test_assumptions` checks.
+            unsafe { transmute::<u8, Tag>(b) }
+        }
+        b => return Err(format!("invalid datum tag {}", b)),
+    };
+    match tag {
+        Tag::Null | Tag::False | Tag::True | Tag::JsonNull | Tag::Dummy => (),
+        Tag::Int32 | Tag::Float32 => {
+            take(data, offset, 4)?;
+        }
+        Tag::Int64 | Tag::Float64 => {
+            take(data, offset, 8)?;
+        }
+        Tag::Interval => {
+            take(data, offset, size_of::<i32>() + size_of::<i128>())?;
+        }
+        Tag::Decimal => {
+            take(data, offset, size_of::<Significand>())?;
+        }
+        // chrono's date and time types are plain integers, so any bytes of
+        // the right length can be read as one. That need not be a valid
+        // date or time, though, so the value is checked after it is read.
+        Tag::Date => {
+            let d = read_checked::<NaiveDate>(data, offset)?;
+            if NaiveDate::from_num_days_from_ce_opt(d.num_days_from_ce()) != Some(d) {
+                return Err("invalid date".into());
+            }
+        }
+        Tag::Time => {
+            let t = read_checked::<NaiveTime>(data, offset)?;
+            validate_time(t)?;
+        }
+        Tag::Timestamp => {
+            let t = read_checked::<NaiveDateTime>(data, offset)?;
+            validate_timestamp(t)?;
+        }
+        Tag::TimestampTz => {
+            let t = read_checked::<DateTime<Utc>>(data, offset)?;
+            validate_timestamp(t.naive_utc())?;
+        }
+        Tag::BytesTiny
+        | Tag::BytesShort
+        | Tag::BytesLong
+        | Tag::BytesHuge
+        | Tag::StringTiny
+        | Tag::StringShort
+        | Tag::StringLong
+        | Tag::StringHuge => {
+            let len = match tag {
+                Tag::BytesTiny | Tag::StringTiny => usize::from(take(data, offset, 1)?[0]),
+                Tag::BytesShort | Tag::StringShort => {
+                    usize::from(read_checked::<u16>(data, offset)?)
+                }
+                Tag::BytesLong | Tag::StringLong => {
+                    usize::try_from(read_checked::<u32>(data, offset)?)
+                        .map_err(|_| "datum length overflows usize".to_string())?
+                }
+                _ => read_checked::<usize>(data, offset)?,
+            };
+            let bytes = take(data, offset, len)?;
+            if let Tag::StringTiny | Tag::StringShort | Tag::StringLong | Tag::StringHuge = tag {
+                std::str::from_utf8(bytes).map_err(|e| format!("invalid string: {}", e))?;
+            }
+        }
+        Tag::Uuid => {
+            if take_untagged_bytes(data, offset)?.len() != size_of::<Uuid>() {
+                return Err("invalid uuid length".into());
+            }
+        }
+        Tag::Array => {
+            let ndims = take(data, offset, 1)?[0];
+            if ndims > MAX_ARRAY_DIMENSIONS {
+                return Err(format!("invalid number of array dimensions {}", ndims));
+            }
+            let mut cardinality = 0;
+            for i in 0..ndims {
+                let _lower_bound = read_checked::<usize>(data, offset)?;
+                let length = read_checked::<usize>(data, offset)?;
+                cardinality = match i {
+                    0 => length,
+                    _ => cardinality
+                        .checked_mul(length)
+                        .ok_or_else(|| "array cardinality overflows usize".to_string())?,
+                };
+            }
+            let elements = validate_datums(take_untagged_bytes(data, offset)?)?;
+            if elements.len() != cardinality {
+                return Err("array cardinality does not match its dimensions".into());
+            }
+        }
+        Tag::List => {
+            validate_datums(take_untagged_bytes(data, offset)?)?;
+        }
+        Tag::Dict => {
+            let tags = validate_datums(take_untagged_bytes(data, offset)?)?;
+            if tags.len() % 2 != 0 {
+                return Err("map has a key without a value".into());
+            }
+            for key in tags.iter().step_by(2) {
+                match key {
+                    Tag::StringTiny | Tag::StringShort | Tag::StringLong | Tag::StringHuge => (),
+                    _ => return Err("map key is not a string".into()),
+                }
+            }
+        }
+        Tag::APD => {
+            // See the comment in `push_datum` for details on the encoding of
+            // decimal values.
+            let digits = u32::from(take(data, offset, 1)?[0]);
+            take(data, offset, 1)?; // exponent
+            let bits = take(data, offset, 1)?[0];
+            if digits == 0 || digits as usize > apd::APD_DATUM_MAX_PRECISION {
+                return Err(format!("invalid number of decimal digits {}", digits));
+            }
+            // Only the sign, infinity and NaN flags are ever set.
+            if bits & 0x0f != 0 {
+                return Err("invalid decimal flags".into());
+            }
+            let lsu_u8_len = Apd::digits_to_lsu_elements_len(digits) * 2;
+            let lsu = take(data, offset, lsu_u8_len)?;
+            // Each element of the decimal's coefficient holds three digits.
+            if lsu
+                .chunks(2)
+                .any(|b| u16::from_le_bytes([b[0], b[1]]) > 999)
+            {
+                return Err("invalid decimal coefficient".into());
+            }
+        }
+        Tag::Range => {
+            let flags = take(data, offset, 1)?[0];
+            let nbounds = validate_datums(take_untagged_bytes(data, offset)?)?.len();
+            let expected = if flags & range::EMPTY != 0 {
+                0
+            } else {
+                usize::from(flags & range::LB_INF == 0) + usize::from(flags & range::UB_INF == 0)
+            };
+            if nbounds != expected {
+                return Err("range bounds do not match its flags".into());
+            }
+        }
+        Tag::Inet => {
+            let addr = match take(data, offset, 1)?[0] {
+                4 => IpAddr::V4(Ipv4Addr::from(read_checked::<[u8; 4]>(data, offset)?)),
+                6 => IpAddr::V6(Ipv6Addr::from(read_checked::<[u8; 16]>(data, offset)?)),
+                b => return Err(format!("invalid address family {}", b)),
+            };
+            let prefix_len = take(data, offset, 1)?[0];
+            Inet::new(addr, prefix_len).map_err(|e| e.to_string())?;
+        }
+    }
+    Ok(tag)
+}
+
+/// Returns the `n` bytes starting at byte `offset`, or an error if `data` is
+/// too short.
+///
+/// Updates `offset` to point to the first byte after the end of the returned
+/// bytes.
+fn take<'a>(data: &'a [u8], offset: &mut usize, n: usize) -> Result<&'a [u8], String> {
+    match offset.checked_add(n) {
+        Some(end) if end <= data.len() => {
+            let bytes = &data[*offset..end];
+            *offset = end;
+            Ok(bytes)
+        }
+        _ => Err("unexpected end of row".into()),
+    }
+}
+
+/// Like [`take`], but for a byte slice written by `push_untagged_bytes`.
+fn take_untagged_bytes<'a>(data: &'a [u8], offset: &mut usize) -> Result<&'a [u8], String> {
+    let len = read_checked::<usize>(data, offset)?;
+    take(data, offset, len)
+}
+
+/// Like [`read_copy`], but checks that `data` is long enough.
+///
+/// Only call this for types for which every bit pattern is a valid value.
+fn read_checked<T>(data: &[u8], offset: &mut usize) -> Result<T, String>
+where
+    T: Copy,
+{
+    let bytes = take(data, offset, size_of::<T>())?;
+    // SAFETY: `bytes` holds `size_of::<T>()` bytes, and the caller promises
+    // that any bytes are a valid `T`.
+    Ok(unsafe { (bytes.as_ptr() as *const T).read_unaligned() })
+}
+
+fn validate_time(t: NaiveTime) -> Result<(), String> {
+    match NaiveTime::from_num_seconds_from_midnight_opt(
+        t.num_seconds_from_midnight(),
+        t.nanosecond(),
+    ) {
+        Some(u) if u == t => Ok(()),
+        _ => Err("invalid time".into()),
     }
 }
 
+fn validate_timestamp(t: NaiveDateTime) -> Result<(), String> {
+    let d = t.date();
+    if NaiveDate::from_num_days_from_ce_opt(d.num_days_from_ce()) != Some(d) {
+        return Err("invalid timestamp".into());
+    }
+    validate_time(t.time())
+}
+
 // --------------------------------------------------------------------------------
 // writing data
 
@@ -562,6 +824,32 @@ fn push_datum<T: Bytes>(data: &mut T, datum: Datum) {
             data.push(bits);
             data.extend_from_slice(lsu);
         }
+        Datum::Range(range) => {
+            // See the comment in `Row::push_range` for details on the encoding
+            // of ranges.
+            data.push(Tag::Range as u8);
+            data.push(range.flags);
+            push_untagged_bytes(data, &range.bounds.data);
+        }
+        Datum::Inet(inet) => {
+            // Network addresses are encoded as follows.
+            //
+            // u8      address family, either 4 or 6
+            // u8      address octets, 4 or 16 of them depending on the family
+            // u8      netmask length
+            data.push(Tag::Inet as u8);
+            match inet.addr() {
+                IpAddr::V4(addr) => {
+                    data.push(4);
+                    data.extend_from_slice(&addr.octets());
+                }
+                IpAddr::V6(addr) => {
+                    data.push(6);
+                    data.extend_from_slice(&addr.octets());
+                }
+            }
+            data.push(inet.prefix_len());
+        }
     }
 }
 
@@ -612,6 +900,11 @@ pub fn datum_size(datum: &Datum) -> usize {
         Datum::JsonNull => 1,
         Datum::Dummy => 1,
         Datum::APD(_) => 1 + size_of::<OrderedDecimal<Apd>>(),
+        Datum::Range(range) => 1 + size_of::<u8>() + size_of::<usize>() + range.bounds.data.len(),
+        Datum::Inet(inet) => match inet.addr() {
+            IpAddr::V4(_) => 3 + 4,
+            IpAddr::V6(_) => 3 + 16,
+        },
     }
 }
 
@@ -739,6 +1032,16 @@ impl Row {
         Row { data: data.into() }
     }
 
+    /// Creates a new row from supplied bytes, checking that they are a valid
+    /// row encoding.
+    ///
+    /// This is the safe counterpart to [`Row::from_bytes_unchecked`], for
+    /// bytes that come from outside the process, like persisted data.
+    pub fn from_bytes_checked(data: Vec<u8>) -> Result<Self, String> {
+        validate_datums(&data)?;
+        Ok(Row { data: data.into() })
+    }
+
     /// Pack a slice of `Datum`s into a `Row`.
     ///
     /// This method has the advantage over `pack` that it can determine the required
@@ -937,6 +1240,68 @@ impl Row {
         Ok(())
     }
 
+    /// Pushes a range with the specified bounds.
+    ///
+    /// The bounds are canonicalized before they are written, so ranges over
+    /// discrete element types always have an inclusive lower bound and an
+    /// exclusive upper bound, and bounds that contain no points produce an
+    /// empty range. Returns an error if the lower bound is greater than the
+    /// upper bound or if canonicalization overflows. If an error occurs, the
+    /// packer's state will be unchanged.
+    pub fn push_range<'a>(
+        &mut self,
+        lower: RangeBound<'a>,
+        upper: RangeBound<'a>,
+    ) -> Result<(), InvalidRangeError> {
+        // Ranges are encoded as follows.
+        //
+        // u8      flags
+        // usize   bound data size in bytes
+        // u8      bound data, where the finite bounds are encoded lower
+        //         bound first
+        //
+        // Infinite bounds are recorded only in the flags.
+
+        let (lower, upper) = match range::canonicalize(lower, upper)? {
+            None => {
+                self.push_empty_range();
+                return Ok(());
+            }
+            Some(bounds) => bounds,
+        };
+
+        let mut flags = 0;
+        match lower.value {
+            None => flags |= range::LB_INF,
+            Some(_) if lower.inclusive => flags |= range::LB_INC,
+            Some(_) => (),
+        }
+        match upper.value {
+            None => flags |= range::UB_INF,
+            Some(_) if upper.inclusive => flags |= range::UB_INC,
+            Some(_) => (),
+        }
+
+        self.data.push(Tag::Range as u8);
+        self.data.push(flags);
+        let off = self.data.len();
+        push_copy!(&mut self.data, 0, usize); // dummy length fixed up below
+        for value in lower.value.iter().chain(upper.value.iter()) {
+            self.push(*value);
+        }
+        let len = self.data.len() - off - size_of::<usize>();
+        self.data[off..off + size_of::<usize>()].copy_from_slice(&len.to_le_bytes());
+
+        Ok(())
+    }
+
+    /// Pushes a range that contains no points.
+    pub fn push_empty_range(&mut self) {
+        self.data.push(Tag::Range as u8);
+        self.data.push(range::EMPTY);
+        push_copy!(&mut self.data, 0, usize);
+    }
+
     /// Convenience function to push a `DatumList` from an iter of `Datum`s
     ///
     /// See [`Row::push_dict_with`] if you need to be able to handle errors
@@ -1571,6 +1936,56 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_from_bytes_checked() {
+        let mut row = Row::pack_slice(&[
+            Datum::Null,
+            Datum::True,
+            Datum::Int64(42),
+            Datum::Date(NaiveDate::from_ymd(2021, 5, 1)),
+            Datum::TimestampTz(DateTime::from_utc(NaiveDateTime::from_timestamp(0, 0), Utc)),
+            Datum::String("hello"),
+            Datum::Inet(Inet::new("10.0.0.1".parse().unwrap(), 8).unwrap()),
+            Datum::Inet(Inet::new("::1".parse().unwrap(), 128).unwrap()),
+        ]);
+        row.push_dict_with(|row| {
+            row.push(Datum::String("a"));
+            row.push_list_with(|row| row.push(Datum::Int32(1)));
+        });
+        row.push_array(
+            &[ArrayDimension {
+                lower_bound: 1,
+                length: 2,
+            }],
+            vec![Datum::Int32(1), Datum::Int32(2)],
+        )
+        .unwrap();
+        row.push_range(
+            RangeBound::new(Datum::Int32(1), true),
+            RangeBound::infinite(),
+        )
+        .unwrap();
+        let data = row.data().to_vec();
+        assert_eq!(Row::from_bytes_checked(data.clone()), Ok(row));
+
+        // A row cut off in the middle of a datum is invalid.
+        assert!(Row::from_bytes_checked(data[..data.len() - 1].to_vec()).is_err());
+
+        let invalid = vec![
+            // A tag that does not exist.
+            vec![255],
+            // A string that is not UTF-8.
+            vec![Tag::StringTiny as u8, 1, 0xff],
+            // An address family that does not exist.
+            vec![Tag::Inet as u8, 5, 0, 0, 0, 0, 0],
+            // A netmask that is too long for an IPv4 address.
+            vec![Tag::Inet as u8, 4, 10, 0, 0, 1, 33],
+        ];
+        for data in invalid {
+            assert!(Row::from_bytes_checked(data.clone()).is_err(), "{:?}", data);
+        }
+    }
 }
 
 #[cfg(test)]
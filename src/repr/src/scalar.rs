@@ -21,7 +21,9 @@ use uuid::Uuid;
 use crate::adt::apd::Apd;
 use crate::adt::array::Array;
 use crate::adt::decimal::Significand;
+use crate::adt::inet::Inet;
 use crate::adt::interval::Interval;
+use crate::adt::range::Range;
 use crate::{ColumnName, ColumnType, DatumList, DatumMap};
 
 /// A single value.
@@ -77,6 +79,10 @@ pub enum Datum<'a> {
     JsonNull,
     /// A universally unique identifier.
     Uuid(Uuid),
+    /// A contiguous range of datums.
+    Range(Range<'a>),
+    /// A network address, with the length of its netmask.
+    Inet(Inet),
     /// A placeholder value.
     ///
     /// Dummy values are never meant to be observed. Many operations on `Datum`
@@ -305,6 +311,19 @@ impl<'a> Datum<'a> {
         }
     }
 
+    /// Unwraps the network address value within this datum.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the datum is not [`Datum::Inet`].
+    #[track_caller]
+    pub fn unwrap_inet(&self) -> Inet {
+        match self {
+            Datum::Inet(inet) => *inet,
+            _ => panic!("Datum::unwrap_inet called on {:?}", self),
+        }
+    }
+
     /// Unwraps the array value within this datum.
     ///
     /// # Panics
@@ -344,6 +363,19 @@ impl<'a> Datum<'a> {
         }
     }
 
+    /// Unwraps the range value within this datum.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the datum is not [`Datum::Range`].
+    #[track_caller]
+    pub fn unwrap_range(&self) -> Range<'a> {
+        match self {
+            Datum::Range(range) => *range,
+            _ => panic!("Datum::unwrap_range called on {:?}", self),
+        }
+    }
+
     /// Unwraps the apd value within this datum.
     ///
     /// # Panics
@@ -409,7 +441,9 @@ impl<'a> Datum<'a> {
                     (Datum::Decimal(_), _) => false,
                     (Datum::Bytes(_), ScalarType::Bytes) => true,
                     (Datum::Bytes(_), _) => false,
-                    (Datum::String(_), ScalarType::String) => true,
+                    (Datum::String(_), ScalarType::String)
+                    | (Datum::String(_), ScalarType::Char { .. })
+                    | (Datum::String(_), ScalarType::VarChar { .. }) => true,
                     (Datum::String(_), _) => false,
                     (Datum::Uuid(_), ScalarType::Uuid) => true,
                     (Datum::Uuid(_), _) => false,
@@ -437,6 +471,14 @@ impl<'a> Datum<'a> {
                     (Datum::JsonNull, _) => false,
                     (Datum::APD(_), ScalarType::APD { .. }) => true,
                     (Datum::APD(_), _) => false,
+                    (Datum::Range(range), ScalarType::Range { element_type }) => range
+                        .bounds
+                        .iter()
+                        .all(|e| is_instance_of_scalar(e, element_type)),
+                    (Datum::Range(_), _) => false,
+                    (Datum::Inet(_), ScalarType::Inet) => true,
+                    (Datum::Inet(inet), ScalarType::Cidr) => inet.is_network(),
+                    (Datum::Inet(_), _) => false,
                 }
             }
         }
@@ -568,6 +610,12 @@ impl From<Uuid> for Datum<'static> {
     }
 }
 
+impl From<Inet> for Datum<'static> {
+    fn from(inet: Inet) -> Datum<'static> {
+        Datum::Inet(inet)
+    }
+}
+
 impl<'a, T> From<Option<T>> for Datum<'a>
 where
     Datum<'a>: From<T>,
@@ -652,6 +700,21 @@ impl fmt::Display for Datum<'_> {
                 f.write_str("}")
             }
             Datum::APD(n) => write!(f, "{}", n.0.to_standard_notation_string()),
+            Datum::Range(range) => match range.bounds() {
+                None => f.write_str("empty"),
+                Some((lower, upper)) => {
+                    f.write_str(if lower.inclusive { "[" } else { "(" })?;
+                    if let Some(value) = lower.value {
+                        write!(f, "{}", value)?;
+                    }
+                    f.write_str(", ")?;
+                    if let Some(value) = upper.value {
+                        write!(f, "{}", value)?;
+                    }
+                    f.write_str(if upper.inclusive { "]" } else { ")" })
+                }
+            },
+            Datum::Inet(inet) => write!(f, "{}", inet),
             Datum::JsonNull => f.write_str("json_null"),
             Datum::Dummy => f.write_str("dummy"),
         }
@@ -699,6 +762,20 @@ pub enum ScalarType {
     Bytes,
     /// The type of [`Datum::String`].
     String,
+    /// Stored as [`Datum::String`], but expresses a fixed-width, blank-padded
+    /// string.
+    ///
+    /// Values are padded with spaces to the specified length. If the length
+    /// is `None`, values are neither padded nor truncated, as in PostgreSQL's
+    /// `bpchar` type.
+    Char {
+        length: Option<usize>,
+    },
+    /// Stored as [`Datum::String`], but can optionally express a limit on the
+    /// string's length.
+    VarChar {
+        max_length: Option<usize>,
+    },
     /// The type of a datum that may represent any valid JSON value.
     ///
     /// Valid datum variants for this type are:
@@ -749,6 +826,17 @@ pub enum ScalarType {
     APD {
         scale: Option<u8>,
     },
+    /// The type of [`Datum::Range`].
+    ///
+    /// The bounds of the range are of the specified type.
+    Range {
+        element_type: Box<ScalarType>,
+    },
+    /// The type of [`Datum::Inet`].
+    Inet,
+    /// Stored as [`Datum::Inet`], but never has any bits set to the right of
+    /// the netmask.
+    Cidr,
 }
 
 impl<'a> ScalarType {
@@ -776,6 +864,30 @@ impl<'a> ScalarType {
         }
     }
 
+    /// Returns the length of a [`ScalarType::Char`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the scalar type is not [`ScalarType::Char`].
+    pub fn unwrap_char_length(&self) -> Option<usize> {
+        match self {
+            ScalarType::Char { length } => *length,
+            _ => panic!("ScalarType::unwrap_char_length called on {:?}", self),
+        }
+    }
+
+    /// Returns the maximum length of a [`ScalarType::VarChar`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the scalar type is not [`ScalarType::VarChar`].
+    pub fn unwrap_varchar_max_length(&self) -> Option<usize> {
+        match self {
+            ScalarType::VarChar { max_length } => *max_length,
+            _ => panic!("ScalarType::unwrap_varchar_max_length called on {:?}", self),
+        }
+    }
+
     /// Returns the [`ScalarType`] of elements in a [`ScalarType::List`].
     ///
     /// # Panics
@@ -818,6 +930,18 @@ impl<'a> ScalarType {
         }
     }
 
+    /// Returns the [`ScalarType`] of the bounds of a [`ScalarType::Range`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if called on anything other than a [`ScalarType::Range`].
+    pub fn unwrap_range_element_type(&self) -> &ScalarType {
+        match self {
+            ScalarType::Range { element_type } => &**element_type,
+            _ => panic!("ScalarType::unwrap_range_element_type called on {:?}", self),
+        }
+    }
+
     /// Returns the [`ScalarType`] of values in a [`ScalarType::Map`].
     ///
     /// # Panics
@@ -846,6 +970,16 @@ impl<'a> ScalarType {
         matches!(self, ScalarType::List { .. } | ScalarType::Array(_))
     }
 
+    /// Returns a copy of `self` with any length constraint on a
+    /// [`ScalarType::Char`] or [`ScalarType::VarChar`] removed.
+    pub fn without_length(&self) -> ScalarType {
+        match self {
+            ScalarType::Char { .. } => ScalarType::Char { length: None },
+            ScalarType::VarChar { .. } => ScalarType::VarChar { max_length: None },
+            t => t.clone(),
+        }
+    }
+
     pub fn is_custom_type(&self) -> bool {
         use ScalarType::*;
         match self {
@@ -886,7 +1020,11 @@ impl PartialEq for ScalarType {
             | (Uuid, Uuid)
             | (Jsonb, Jsonb)
             | (Oid, Oid)
-            | (APD { .. }, APD { .. }) => true,
+            | (APD { .. }, APD { .. })
+            | (Char { .. }, Char { .. })
+            | (VarChar { .. }, VarChar { .. })
+            | (Inet, Inet)
+            | (Cidr, Cidr) => true,
             (
                 List {
                     element_type: element_l,
@@ -921,6 +1059,7 @@ impl PartialEq for ScalarType {
                     custom_oid: oid_r,
                 },
             ) => value_l.eq(value_r) && oid_l == oid_r,
+            (Range { element_type: l }, Range { element_type: r }) => l.eq(r),
 
             (Bool, _)
             | (Int32, _)
@@ -935,6 +1074,8 @@ impl PartialEq for ScalarType {
             | (Interval, _)
             | (Bytes, _)
             | (String, _)
+            | (Char { .. }, _)
+            | (VarChar { .. }, _)
             | (Jsonb, _)
             | (Uuid, _)
             | (Array(_), _)
@@ -942,7 +1083,10 @@ impl PartialEq for ScalarType {
             | (Record { .. }, _)
             | (Oid, _)
             | (Map { .. }, _)
-            | (APD { .. }, _) => false,
+            | (APD { .. }, _)
+            | (Range { .. }, _)
+            | (Inet, _)
+            | (Cidr, _) => false,
         }
     }
 }
@@ -1003,6 +1147,14 @@ impl Hash for ScalarType {
                 custom_oid.hash(state);
             }
             APD { .. } => state.write_u8(19),
+            Range { element_type } => {
+                state.write_u8(20);
+                element_type.hash(state);
+            }
+            Char { .. } => state.write_u8(21),
+            VarChar { .. } => state.write_u8(22),
+            Inet => state.write_u8(23),
+            Cidr => state.write_u8(24),
         }
     }
 }
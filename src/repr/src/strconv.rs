@@ -27,10 +27,11 @@ use std::borrow::Cow;
 use std::collections::BTreeMap;
 use std::error::Error;
 use std::fmt;
+use std::net::{IpAddr, Ipv4Addr};
 use std::num::FpCategory;
 
 use chrono::offset::{Offset, TimeZone};
-use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, NaiveTime, Timelike, Utc};
+use chrono::{DateTime, Duration, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Timelike, Utc};
 use dec::OrderedDecimal;
 use fast_float::FastFloat;
 use lazy_static::lazy_static;
@@ -48,6 +49,7 @@ use crate::adt::apd::{self, Apd, APD_DATUM_MAX_PRECISION};
 use crate::adt::array::ArrayDimension;
 use crate::adt::datetime::{self, DateTimeField, ParsedDateTime};
 use crate::adt::decimal::Decimal;
+use crate::adt::inet::Inet;
 use crate::adt::interval::Interval;
 use crate::adt::jsonb::{Jsonb, JsonbRef};
 
@@ -256,7 +258,9 @@ where
 /// <time zone interval> ::=
 ///     <sign> <hours value> <colon> <minutes value>
 /// ```
-fn parse_timestamp_string(s: &str) -> Result<(NaiveDate, NaiveTime, datetime::Timezone), String> {
+fn parse_timestamp_string(
+    s: &str,
+) -> Result<(NaiveDate, NaiveTime, Option<datetime::Timezone>), String> {
     if s.is_empty() {
         return Err("timestamp string is empty".into());
     }
@@ -269,7 +273,7 @@ fn parse_timestamp_string(s: &str) -> Result<(NaiveDate, NaiveTime, datetime::Ti
         return Ok((
             NaiveDate::from_ymd(1970, 1, 1),
             NaiveTime::from_hms(0, 0, 0),
-            Default::default(),
+            Some(Default::default()),
         ));
     }
 
@@ -280,9 +284,9 @@ fn parse_timestamp_string(s: &str) -> Result<(NaiveDate, NaiveTime, datetime::Ti
     let t: NaiveTime = pdt.compute_time()?;
 
     let offset = if tz_string.is_empty() {
-        Default::default()
+        None
     } else {
-        tz_string.parse()?
+        Some(tz_string.parse()?)
     };
 
     Ok((d, t, offset))
@@ -349,11 +353,20 @@ where
 
 /// Parses a `DateTime<Utc>` from `s`. See `expr::scalar::func::timezone_timestamp` for timezone anomaly considerations.
 pub fn parse_timestamptz(s: &str) -> Result<DateTime<Utc>, ParseError> {
+    parse_timestamptz_in(s, Default::default())
+}
+
+/// Like [`parse_timestamptz`], but interprets a timestamp that does not specify
+/// a timezone as a local time in `default_tz` rather than in UTC.
+pub fn parse_timestamptz_in(
+    s: &str,
+    default_tz: datetime::Timezone,
+) -> Result<DateTime<Utc>, ParseError> {
     parse_timestamp_string(s)
         .and_then(|(date, time, timezone)| {
             use datetime::Timezone::*;
             let mut dt = date.and_time(time);
-            let offset = match timezone {
+            let offset = match timezone.unwrap_or(default_tz) {
                 FixedOffset(offset) => offset,
                 Tz(tz) => match tz.offset_from_local_datetime(&dt).latest() {
                     Some(offset) => offset.fix(),
@@ -375,12 +388,32 @@ pub fn parse_timestamptz(s: &str) -> Result<DateTime<Utc>, ParseError> {
 
 /// Writes a [`DateTime<Utc>`] timestamp to `buf`.
 pub fn format_timestamptz<F>(buf: &mut F, ts: DateTime<Utc>) -> Nestable
+where
+    F: FormatBuffer,
+{
+    format_timestamptz_local(buf, ts.with_timezone(&FixedOffset::east(0)))
+}
+
+/// Writes a [`DateTime<FixedOffset>`] timestamp to `buf` as a local time
+/// followed by its offset from UTC, e.g. `2021-01-01 00:00:00-05`.
+pub fn format_timestamptz_local<F>(buf: &mut F, ts: DateTime<FixedOffset>) -> Nestable
 where
     F: FormatBuffer,
 {
     write!(buf, "{}", ts.format("%Y-%m-%d %H:%M:%S"));
     format_nanos_to_micros(buf, ts.timestamp_subsec_nanos());
-    write!(buf, "+00");
+    // Like PostgreSQL, omit the minutes and seconds of the offset when they
+    // are zero.
+    let offset = ts.offset().local_minus_utc();
+    let sign = if offset < 0 { '-' } else { '+' };
+    let offset = offset.abs();
+    write!(buf, "{}{:02}", sign, offset / 3600);
+    if offset % 3600 != 0 {
+        write!(buf, ":{:02}", offset / 60 % 60);
+        if offset % 60 != 0 {
+            write!(buf, ":{:02}", offset % 60);
+        }
+    }
     // This always needs escaping because of the whitespace
     Nestable::MayNeedEscaping
 }
@@ -444,7 +477,7 @@ pub fn parse_apd(s: &str) -> Result<OrderedDecimal<Apd>, ParseError> {
     let mut n = match cx.parse(s.trim()) {
         Ok(n) => n,
         Err(..) => {
-            return Err(ParseError::invalid_input_syntax("apd", s));
+            return Err(ParseError::invalid_input_syntax("numeric", s));
         }
     };
 
@@ -452,14 +485,14 @@ pub fn parse_apd(s: &str) -> Result<OrderedDecimal<Apd>, ParseError> {
 
     // Check for values that can only be generated by invalid syntax.
     if (n.is_infinite() && !cx_status.overflow()) || (n.is_nan() && n.is_negative()) {
-        return Err(ParseError::invalid_input_syntax("apd", s));
+        return Err(ParseError::invalid_input_syntax("numeric", s));
     }
 
     // Process value; only errors if value is out of range of APD's max precision.
     let out_of_range = apd::munge_apd(&mut n).is_err();
 
     if cx_status.overflow() || cx_status.subnormal() || out_of_range {
-        Err(ParseError::out_of_range("apd", s).with_details(format!(
+        Err(ParseError::out_of_range("numeric", s).with_details(format!(
             "exceeds maximum precision {}",
             APD_DATUM_MAX_PRECISION
         )))
@@ -599,6 +632,118 @@ where
     Nestable::Yes
 }
 
+/// Splits a network address into its address and its netmask length, if any.
+fn split_inet(s: &str) -> Result<(&str, Option<u8>), String> {
+    match s.find('/') {
+        None => Ok((s, None)),
+        Some(i) => match s[i + 1..].parse() {
+            Ok(prefix_len) => Ok((&s[..i], Some(prefix_len))),
+            Err(_) => Err("invalid mask length".into()),
+        },
+    }
+}
+
+/// Parses an `inet` value, i.e. an IPv4 or IPv6 host address optionally
+/// followed by the length of its netmask.
+///
+/// If the netmask length is omitted, the address is assumed to describe a
+/// single host.
+pub fn parse_inet(s: &str) -> Result<Inet, ParseError> {
+    parse_inet_inner(s.trim())
+        .map_err(|details| ParseError::invalid_input_syntax("inet", s).with_details(details))
+}
+
+fn parse_inet_inner(s: &str) -> Result<Inet, String> {
+    let (addr, prefix_len) = split_inet(s)?;
+    let addr: IpAddr = addr
+        .parse()
+        .map_err(|e: std::net::AddrParseError| e.to_string())?;
+    let prefix_len = prefix_len.unwrap_or(match addr {
+        IpAddr::V4(_) => 32,
+        IpAddr::V6(_) => 128,
+    });
+    Inet::new(addr, prefix_len).map_err(|e| e.to_string())
+}
+
+/// Parses a `cidr` value, i.e. an IPv4 or IPv6 network address optionally
+/// followed by the length of its netmask.
+///
+/// As in PostgreSQL, IPv4 addresses may omit trailing zero octets. If the
+/// netmask length is omitted, it is inferred from the address using the old
+/// classful network numbering system, widened as necessary to cover all
+/// specified octets.
+pub fn parse_cidr(s: &str) -> Result<Inet, ParseError> {
+    parse_cidr_inner(s.trim())
+        .map_err(|details| ParseError::invalid_input_syntax("cidr", s).with_details(details))
+}
+
+fn parse_cidr_inner(s: &str) -> Result<Inet, String> {
+    let (addr, prefix_len) = split_inet(s)?;
+    let (addr, prefix_len) = if addr.contains(':') {
+        let addr = addr
+            .parse()
+            .map_err(|e: std::net::AddrParseError| e.to_string())?;
+        (IpAddr::V6(addr), prefix_len.unwrap_or(128))
+    } else {
+        let mut octets = [0; 4];
+        let mut n: u8 = 0;
+        for octet in addr.split('.') {
+            if n == 4 {
+                return Err("too many octets".into());
+            }
+            octets[usize::from(n)] = octet
+                .parse()
+                .map_err(|_| format!("invalid octet {:?}", octet))?;
+            n += 1;
+        }
+        let prefix_len = match prefix_len {
+            Some(prefix_len) => prefix_len,
+            None => {
+                let classful_prefix_len = match octets[0] {
+                    240..=255 => 32,
+                    224..=239 => 8,
+                    192..=223 => 24,
+                    128..=191 => 16,
+                    _ => 8,
+                };
+                match classful_prefix_len.max(n * 8) {
+                    8 if octets[0] == 224 => 4,
+                    prefix_len => prefix_len,
+                }
+            }
+        };
+        (IpAddr::V4(Ipv4Addr::from(octets)), prefix_len)
+    };
+    let inet = Inet::new(addr, prefix_len).map_err(|e| e.to_string())?;
+    if !inet.is_network() {
+        return Err("value has bits set to right of mask".into());
+    }
+    Ok(inet)
+}
+
+/// Writes an `inet` value, omitting the netmask length if the value describes
+/// a single host.
+pub fn format_inet<F>(buf: &mut F, inet: Inet) -> Nestable
+where
+    F: FormatBuffer,
+{
+    write!(buf, "{}", inet.addr());
+    if inet.prefix_len() != inet.max_prefix_len() {
+        write!(buf, "/{}", inet.prefix_len());
+    }
+    Nestable::Yes
+}
+
+/// Writes a `cidr` value. Unlike [`format_inet`], the netmask length is always
+/// written.
+pub fn format_cidr<F>(buf: &mut F, inet: Inet) -> Nestable
+where
+    F: FormatBuffer,
+{
+    write!(buf, "{}", inet);
+    Nestable::Yes
+}
+
 fn format_nanos_to_micros<F>(buf: &mut F, nanos: u32)
 where
     F: FormatBuffer,
@@ -619,11 +764,15 @@ where
     }
 }
 
+/// Parses a one-dimensional array, returning its dimensions and its elements.
+///
+/// As in PostgreSQL, the array may be preceded by a decoration that specifies
+/// the bounds of its dimension, as in `[2:3]={a,b}`.
 pub fn parse_array<'a, T, E>(
     s: &'a str,
     make_null: impl FnMut() -> T,
     gen_elem: impl FnMut(Cow<'a, str>) -> Result<T, E>,
-) -> Result<Vec<T>, ParseError>
+) -> Result<(Vec<ArrayDimension>, Vec<T>), ParseError>
 where
     E: fmt::Display,
 {
@@ -635,13 +784,19 @@ fn parse_array_inner<'a, T, E>(
     s: &'a str,
     mut make_null: impl FnMut() -> T,
     mut gen_elem: impl FnMut(Cow<'a, str>) -> Result<T, E>,
-) -> Result<Vec<T>, String>
+) -> Result<(Vec<ArrayDimension>, Vec<T>), String>
 where
     E: fmt::Display,
 {
     let mut elems = vec![];
     let buf = &mut LexBuf::new(s);
 
+    let bounds = if buf.consume('[') {
+        Some(lex_array_bounds(buf)?)
+    } else {
+        None
+    };
+
     if !buf.consume('{') {
         bail!("malformed array literal: missing opening left brace");
     }
@@ -679,7 +834,59 @@ where
         bail!("malformed array literal: junk after closing right brace");
     }
 
-    Ok(elems)
+    let lower_bound = match bounds {
+        Some((lower, upper)) => {
+            if upper - lower + 1 != elems.len() as i64 {
+                bail!("specified array dimensions do not match array contents");
+            }
+            lower as usize
+        }
+        None => 1,
+    };
+    // Per PostgreSQL, empty arrays are represented with zero dimensions, not
+    // one dimension of zero length.
+    let dims = if elems.is_empty() {
+        vec![]
+    } else {
+        vec![ArrayDimension {
+            lower_bound,
+            length: elems.len(),
+        }]
+    };
+
+    Ok((dims, elems))
+}
+
+/// Lexes the bounds in an array dimension decoration, like `[2:3]=`, after its
+/// opening bracket.
+fn lex_array_bounds(buf: &mut LexBuf) -> Result<(i64, i64), String> {
+    let mut lex_bound = |buf: &mut LexBuf| {
+        let bound = buf.take_while(|ch| ch.is_ascii_digit() || ch == '-');
+        bound
+            .parse::<i64>()
+            .map_err(|_| format!("invalid array dimension bound: \"{}\"", bound))
+    };
+    let lower = lex_bound(buf)?;
+    if !buf.consume(':') {
+        bail!("array dimensions must specify a lower and an upper bound");
+    }
+    let upper = lex_bound(buf)?;
+    if !buf.consume(']') {
+        bail!("missing \"]\" after array dimensions");
+    }
+    if !buf.consume('=') {
+        if buf.peek() == Some('[') {
+            bail!("parsing multi-dimensional arrays is not supported");
+        }
+        bail!("missing \"=\" after array dimensions");
+    }
+    if lower < 1 {
+        bail!("array lower bounds less than 1 are not supported");
+    }
+    if upper < lower {
+        bail!("upper bound cannot be less than lower bound");
+    }
+    Ok((lower, upper))
 }
 
 pub fn parse_list<'a, T, E>(
@@ -1030,6 +1237,19 @@ pub fn format_array<F, T>(
 where
     F: FormatBuffer,
 {
+    // Per PostgreSQL, arrays whose lower bounds are not all one are preceded
+    // by a decoration that specifies the bounds of each dimension.
+    if dims.iter().any(|dim| dim.lower_bound != 1) {
+        for dim in dims {
+            write!(
+                buf,
+                "[{}:{}]",
+                dim.lower_bound,
+                dim.lower_bound + dim.length - 1
+            );
+        }
+        buf.write_char('=');
+    }
     format_array_inner(buf, dims, &mut elems.into_iter(), &mut format_elem);
     Nestable::Yes
 }
@@ -1088,6 +1308,160 @@ where
     Nestable::Yes
 }
 
+/// The constituent parts of a non-empty range.
+///
+/// Infinite bounds are represented as `None`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RangeParts<T> {
+    pub lower: Option<T>,
+    pub lower_inclusive: bool,
+    pub upper: Option<T>,
+    pub upper_inclusive: bool,
+}
+
+/// Parses a range from `s`.
+///
+/// Returns `None` if `s` describes the empty range. The text of each finite
+/// bound is passed to `gen_elem` to produce the bound value. Note that the
+/// bounds are not canonicalized, nor are they checked for ordering.
+pub fn parse_range<'a, T, E>(
+    s: &'a str,
+    gen_elem: impl FnMut(Cow<'a, str>) -> Result<T, E>,
+) -> Result<Option<RangeParts<T>>, ParseError>
+where
+    E: fmt::Display,
+{
+    parse_range_inner(s, gen_elem)
+        .map_err(|details| ParseError::invalid_input_syntax("range", s).with_details(details))
+}
+
+fn parse_range_inner<'a, T, E>(
+    s: &'a str,
+    mut gen_elem: impl FnMut(Cow<'a, str>) -> Result<T, E>,
+) -> Result<Option<RangeParts<T>>, String>
+where
+    E: fmt::Display,
+{
+    if s.trim().eq_ignore_ascii_case("empty") {
+        return Ok(None);
+    }
+
+    let buf = &mut LexBuf::new(s);
+    let mut gen = |elem| gen_elem(elem).map_err(|e| e.to_string());
+
+    buf.take_while(|ch| ch.is_ascii_whitespace());
+    let lower_inclusive = match buf.next() {
+        Some('[') => true,
+        Some('(') => false,
+        _ => bail!("missing left parenthesis or bracket"),
+    };
+
+    let lower = match lex_range_bound(buf)? {
+        Some(elem) => Some(gen(elem)?),
+        None => None,
+    };
+    if !buf.consume(',') {
+        bail!("missing comma after lower bound");
+    }
+    let upper = match lex_range_bound(buf)? {
+        Some(elem) => Some(gen(elem)?),
+        None => None,
+    };
+
+    let upper_inclusive = match buf.next() {
+        Some(']') => true,
+        Some(')') => false,
+        _ => bail!("missing right parenthesis or bracket"),
+    };
+    buf.take_while(|ch| ch.is_ascii_whitespace());
+    if buf.next().is_some() {
+        bail!("junk after right parenthesis or bracket");
+    }
+
+    // Infinite bounds are never inclusive.
+    Ok(Some(RangeParts {
+        lower_inclusive: lower_inclusive && lower.is_some(),
+        lower,
+        upper_inclusive: upper_inclusive && upper.is_some(),
+        upper,
+    }))
+}
+
+// Result of `None` indicates the bound is infinite.
+fn lex_range_bound<'a>(buf: &mut LexBuf<'a>) -> Result<Option<Cow<'a, str>>, String> {
+    let is_special_char = |c| matches!(c, ',' | ')' | ']' | '"' | '\\');
+    buf.take_while(|ch| ch.is_ascii_whitespace());
+    let bound = match buf.peek() {
+        Some('"') => Some(lex_quoted_element(buf)?),
+        Some(',') | Some(')') | Some(']') => None,
+        Some(_) => {
+            let s = buf.take_while(|ch| !is_special_char(ch));
+            if !matches!(buf.peek(), Some('"') | Some('\\')) {
+                Some(Cow::Borrowed(s.trim_end()))
+            } else {
+                let mut s = s.to_string();
+                loop {
+                    match buf.next() {
+                        Some('\\') => match buf.next() {
+                            Some(c) => s.push(c),
+                            None => bail!("unterminated element"),
+                        },
+                        Some('"') => bail!("malformed literal; must escape special character '\"'"),
+                        Some(',') | Some(')') | Some(']') => {
+                            buf.prev();
+                            break;
+                        }
+                        Some(c) => s.push(c),
+                        None => bail!("unexpected end of input"),
+                    }
+                }
+                Some(Cow::Owned(s))
+            }
+        }
+        None => bail!("unexpected end of input"),
+    };
+    buf.take_while(|ch| ch.is_ascii_whitespace());
+    Ok(bound)
+}
+
+/// Writes a range to `buf`.
+///
+/// A `range` of `None` is written as the empty range. The finite bounds of the
+/// range are formatted with `format_elem`.
+pub fn format_range<F, T>(
+    buf: &mut F,
+    range: Option<RangeParts<T>>,
+    mut format_elem: impl FnMut(&mut F, T) -> Nestable,
+) -> Nestable
+where
+    F: FormatBuffer,
+{
+    let range = match range {
+        None => {
+            buf.write_str("empty");
+            return Nestable::MayNeedEscaping;
+        }
+        Some(range) => range,
+    };
+
+    buf.write_char(if range.lower_inclusive { '[' } else { '(' });
+    if let Some(lower) = range.lower {
+        let start = buf.len();
+        if let Nestable::MayNeedEscaping = format_elem(buf, lower) {
+            escape_elem::<_, RangeElementEscaper>(buf, start);
+        }
+    }
+    buf.write_char(',');
+    if let Some(upper) = range.upper {
+        let start = buf.len();
+        if let Nestable::MayNeedEscaping = format_elem(buf, upper) {
+            escape_elem::<_, RangeElementEscaper>(buf, start);
+        }
+    }
+    buf.write_char(if range.upper_inclusive { ']' } else { ')' });
+    Nestable::MayNeedEscaping
+}
+
 pub trait ElementEscaper {
     fn needs_escaping(elem: &[u8]) -> bool;
     fn escape_char(c: u8) -> u8;
@@ -1145,7 +1519,23 @@ impl ElementEscaper for RecordElementEscaper {
     }
 }
 
-/// Escapes a list, record, or map element in place.
+struct RangeElementEscaper;
+
+impl ElementEscaper for RangeElementEscaper {
+    fn needs_escaping(elem: &[u8]) -> bool {
+        elem.is_empty()
+            || elem.iter().any(|c| {
+                matches!(c, b'(' | b')' | b'[' | b']' | b',' | b'"' | b'\\')
+                    || c.is_ascii_whitespace()
+            })
+    }
+
+    fn escape_char(_: u8) -> u8 {
+        b'\\'
+    }
+}
+
+/// Escapes a list, record, range, or map element in place.
 ///
 /// The element must start at `start` and extend to the end of the buffer. The
 /// buffer will be resized if escaping is necessary to account for the
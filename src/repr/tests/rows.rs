@@ -7,6 +7,7 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use std::net::IpAddr;
 use std::ops::Add;
 
 use chrono::TimeZone;
@@ -14,6 +15,8 @@ use proptest::prelude::*;
 use uuid::Uuid;
 
 use repr::adt::decimal::Significand;
+use repr::adt::inet::Inet;
+use repr::adt::range::RangeBound;
 use repr::adt::{array::ArrayDimension, interval::Interval};
 use repr::{Datum, Row};
 
@@ -44,6 +47,7 @@ enum PropertizedDatum {
 
     JsonNull,
     Uuid(Uuid),
+    Inet(Inet),
     Dummy,
 }
 
@@ -67,6 +71,7 @@ fn arb_datum() -> BoxedStrategy<PropertizedDatum> {
         ".*".prop_map(PropertizedDatum::String),
         Just(PropertizedDatum::JsonNull),
         Just(PropertizedDatum::Uuid(Uuid::nil())),
+        arb_inet().prop_map(PropertizedDatum::Inet),
         Just(PropertizedDatum::Dummy)
     ];
     leaf.prop_recursive(3, 8, 16, |inner| {
@@ -169,6 +174,22 @@ fn arb_significand() -> BoxedStrategy<Significand> {
     any::<i128>().prop_map(|v| Significand::new(v)).boxed()
 }
 
+fn arb_inet() -> BoxedStrategy<Inet> {
+    prop_oneof![
+        (any::<u32>(), 0..=32_u8).prop_map(|(addr, prefix_len)| Inet::new(
+            IpAddr::V4(addr.into()),
+            prefix_len
+        )
+        .unwrap()),
+        (any::<u128>(), 0..=128_u8).prop_map(|(addr, prefix_len)| Inet::new(
+            IpAddr::V6(addr.into()),
+            prefix_len
+        )
+        .unwrap()),
+    ]
+    .boxed()
+}
+
 impl<'a> Into<Datum<'a>> for &'a PropertizedDatum {
     fn into(self) -> Datum<'a> {
         use PropertizedDatum::*;
@@ -201,6 +222,7 @@ impl<'a> Into<Datum<'a>> for &'a PropertizedDatum {
             }
             JsonNull => Datum::JsonNull,
             Uuid(u) => Datum::from(*u),
+            Inet(inet) => Datum::from(*inet),
             Dummy => Datum::Dummy,
         }
     }
@@ -242,4 +264,29 @@ proptest! {
         let unpacked = row.unpack();
         assert_eq!(datums, unpacked);
     }
+
+    #[test]
+    fn range_packing_preserves_membership(
+        lower in -10..10i32,
+        upper in -10..10i32,
+        lower_inclusive in any::<bool>(),
+        upper_inclusive in any::<bool>(),
+        elem in -12..12i32,
+    ) {
+        let (lower, upper) = (lower.min(upper), lower.max(upper));
+        let mut row = Row::default();
+        row.push_range(
+            RangeBound::new(Datum::Int32(lower), lower_inclusive),
+            RangeBound::new(Datum::Int32(upper), upper_inclusive),
+        ).unwrap();
+        let range = row.unpack_first().unwrap_range();
+        let expected = (elem > lower || (lower_inclusive && elem == lower))
+            && (elem < upper || (upper_inclusive && elem == upper));
+        assert_eq!(range.contains_elem(Datum::Int32(elem)), expected);
+        if let Some((lower, upper)) = range.bounds() {
+            // Ranges over discrete types are canonicalized to `[)`.
+            assert!(lower.inclusive);
+            assert!(!upper.inclusive);
+        }
+    }
 }
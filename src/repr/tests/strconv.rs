@@ -9,7 +9,7 @@
 
 use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
 
-use repr::adt::datetime::DateTimeField;
+use repr::adt::datetime::{DateTimeField, Timezone};
 use repr::adt::interval::Interval;
 use repr::strconv;
 
@@ -246,6 +246,41 @@ fn test_parse_timestamptz() {
     }
 }
 
+#[test]
+fn test_parse_timestamptz_in() {
+    let tz: Timezone = "America/New_York".parse().unwrap();
+    let test_cases = [
+        // Timestamps without a timezone are local to the default timezone.
+        ("2021-01-01 00:00:00", "2021-01-01T05:00:00Z"),
+        ("2021-07-01 00:00:00", "2021-07-01T04:00:00Z"),
+        // Explicit timezones take precedence over the default.
+        ("2021-01-01 00:00:00+00", "2021-01-01T00:00:00Z"),
+        ("2021-01-01 00:00:00 Asia/Kolkata", "2020-12-31T18:30:00Z"),
+        ("epoch", "1970-01-01T00:00:00Z"),
+    ];
+    for (input, expected) in test_cases.iter() {
+        let actual = strconv::parse_timestamptz_in(input, tz).unwrap();
+        let expected: DateTime<Utc> = expected.parse().unwrap();
+        assert_eq!(actual, expected, "parsing {}", input);
+    }
+}
+
+#[test]
+fn test_format_timestamptz_local() {
+    let ts = Utc.ymd(2021, 1, 1).and_hms_micro(5, 0, 0, 500);
+    let test_cases = [
+        (0, "2021-01-01 05:00:00.0005+00"),
+        (-5 * 3600, "2021-01-01 00:00:00.0005-05"),
+        (5 * 3600 + 30 * 60, "2021-01-01 10:30:00.0005+05:30"),
+        (-(3600 + 60 + 1), "2021-01-01 03:58:59.0005-01:01:01"),
+    ];
+    for (offset, expected) in test_cases.iter() {
+        let mut buf = String::new();
+        strconv::format_timestamptz_local(&mut buf, ts.with_timezone(&FixedOffset::east(*offset)));
+        assert_eq!(buf, *expected);
+    }
+}
+
 #[test]
 fn test_parse_timestamptz_errors() {
     run_test_parse_timestamptz_errors(
@@ -447,3 +482,167 @@ fn miri_test_format_list() {
         r#"{a,"a\"b","",NULL,"NULL",nUlL,"  spaces ","a,b","\\","a\\b\"c\\d\""}"#
     );
 }
+
+#[test]
+fn test_parse_range() {
+    fn parse(s: &str) -> Option<strconv::RangeParts<i32>> {
+        strconv::parse_range(s, |elem| strconv::parse_int32(&elem)).unwrap()
+    }
+
+    assert_eq!(parse("empty"), None);
+    assert_eq!(parse(" EMPTY "), None);
+    assert_eq!(
+        parse("[1,5)"),
+        Some(strconv::RangeParts {
+            lower: Some(1),
+            lower_inclusive: true,
+            upper: Some(5),
+            upper_inclusive: false,
+        })
+    );
+    assert_eq!(
+        parse(" ( 1 , \"5\" ] "),
+        Some(strconv::RangeParts {
+            lower: Some(1),
+            lower_inclusive: false,
+            upper: Some(5),
+            upper_inclusive: true,
+        })
+    );
+    // Infinite bounds are never inclusive.
+    assert_eq!(
+        parse("[,]"),
+        Some(strconv::RangeParts {
+            lower: None,
+            lower_inclusive: false,
+            upper: None,
+            upper_inclusive: false,
+        })
+    );
+
+    for (s, e) in &[
+        (
+            "1,5)",
+            "invalid input syntax for type range: missing left parenthesis or bracket: \"1,5)\"",
+        ),
+        (
+            "[1 5)",
+            "invalid input syntax for type range: missing comma after lower bound: \"[1 5)\"",
+        ),
+        (
+            "[1,5",
+            "invalid input syntax for type range: unexpected end of input: \"[1,5\"",
+        ),
+        (
+            "[1,5)x",
+            "invalid input syntax for type range: junk after right parenthesis or bracket: \"[1,5)x\"",
+        ),
+    ] {
+        let actual = strconv::parse_range(s, |elem| strconv::parse_int32(&elem))
+            .unwrap_err()
+            .to_string();
+        assert_eq!(&actual, e);
+    }
+}
+
+#[test]
+fn miri_test_format_range() {
+    let mut out = String::new();
+    strconv::format_range(&mut out, None::<strconv::RangeParts<&str>>, |buf, el| {
+        strconv::format_string(buf, el)
+    });
+    assert_eq!(out, "empty");
+
+    let mut out = String::new();
+    strconv::format_range(
+        &mut out,
+        Some(strconv::RangeParts {
+            lower: Some("a b"),
+            lower_inclusive: true,
+            upper: None,
+            upper_inclusive: false,
+        }),
+        |buf, el| strconv::format_string(buf, el),
+    );
+    assert_eq!(out, r#"["a b",)"#);
+
+    let mut out = String::new();
+    strconv::format_range(
+        &mut out,
+        Some(strconv::RangeParts {
+            lower: Some(1),
+            lower_inclusive: false,
+            upper: Some(5),
+            upper_inclusive: true,
+        }),
+        |buf, el| strconv::format_int32(buf, el),
+    );
+    assert_eq!(out, "(1,5]");
+}
+
+#[test]
+fn test_parse_format_inet() {
+    for (s, expected) in &[
+        ("192.168.1.5", "192.168.1.5"),
+        ("192.168.1.5/32", "192.168.1.5"),
+        (" 192.168.1.5/24 ", "192.168.1.5/24"),
+        ("::1", "::1"),
+        ("2001:db8::1/64", "2001:db8::1/64"),
+    ] {
+        let mut out = String::new();
+        strconv::format_inet(&mut out, strconv::parse_inet(s).unwrap());
+        assert_eq!(&out, expected);
+    }
+
+    for (s, e) in &[
+        (
+            "192.168.1",
+            "invalid input syntax for type inet: invalid IP address syntax: \"192.168.1\"",
+        ),
+        (
+            "192.168.1.5/33",
+            "invalid input syntax for type inet: invalid mask length: \"192.168.1.5/33\"",
+        ),
+        (
+            "192.168.1.5/x",
+            "invalid input syntax for type inet: invalid mask length: \"192.168.1.5/x\"",
+        ),
+    ] {
+        assert_eq!(&strconv::parse_inet(s).unwrap_err().to_string(), e);
+    }
+}
+
+#[test]
+fn test_parse_format_cidr() {
+    for (s, expected) in &[
+        ("192.168.100.128/25", "192.168.100.128/25"),
+        ("192.168/24", "192.168.0.0/24"),
+        ("192.168.1", "192.168.1.0/24"),
+        ("192.168", "192.168.0.0/24"),
+        ("128.1", "128.1.0.0/16"),
+        ("128", "128.0.0.0/16"),
+        ("10.1.2", "10.1.2.0/24"),
+        ("10", "10.0.0.0/8"),
+        ("10.1.2.3", "10.1.2.3/32"),
+        ("224", "224.0.0.0/4"),
+        ("2001:4f8:3:ba::/64", "2001:4f8:3:ba::/64"),
+        ("::1", "::1/128"),
+    ] {
+        let mut out = String::new();
+        strconv::format_cidr(&mut out, strconv::parse_cidr(s).unwrap());
+        assert_eq!(&out, expected);
+    }
+
+    for (s, e) in &[
+        (
+            "192.168.1.5/24",
+            "invalid input syntax for type cidr: value has bits set to right of mask: \"192.168.1.5/24\"",
+        ),
+        (
+            "1.2.3.4.5",
+            "invalid input syntax for type cidr: too many octets: \"1.2.3.4.5\"",
+        ),
+    ] {
+        assert_eq!(&strconv::parse_cidr(s).unwrap_err().to_string(), e);
+    }
+}
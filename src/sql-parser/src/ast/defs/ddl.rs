@@ -206,7 +206,9 @@ impl<T: AstInfo> Format<T> {
 pub enum Envelope {
     None,
     Debezium(DbzMode),
-    Upsert,
+    /// The key of each record is taken from its message key or, if specified,
+    /// from the listed columns of its value.
+    Upsert(Option<Vec<Ident>>),
     CdcV2,
 }
 
@@ -227,8 +229,13 @@ impl AstDisplay for Envelope {
                 f.write_str("DEBEZIUM");
                 f.write_node(mode);
             }
-            Self::Upsert => {
+            Self::Upsert(key) => {
                 f.write_str("UPSERT");
+                if let Some(key) = key {
+                    f.write_str(" (KEY (");
+                    f.write_node(&display::comma_separated(key));
+                    f.write_str("))");
+                }
             }
             Self::CdcV2 => {
                 f.write_str("MATERIALIZE");
@@ -238,6 +245,46 @@ impl AstDisplay for Envelope {
 }
 impl_display!(Envelope);
 
+/// A piece of per-message metadata requested via `INCLUDE` in `CREATE SOURCE`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SourceIncludeMetadata {
+    pub ty: SourceIncludeMetadataType,
+    pub alias: Option<Ident>,
+}
+
+impl AstDisplay for SourceIncludeMetadata {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_node(&self.ty);
+        if let Some(alias) = &self.alias {
+            f.write_str(" AS ");
+            f.write_node(alias);
+        }
+    }
+}
+impl_display!(SourceIncludeMetadata);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SourceIncludeMetadataType {
+    Key,
+    Partition,
+    Offset,
+    Timestamp,
+    Headers,
+}
+
+impl AstDisplay for SourceIncludeMetadataType {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        match self {
+            Self::Key => f.write_str("KEY"),
+            Self::Partition => f.write_str("PARTITION"),
+            Self::Offset => f.write_str("OFFSET"),
+            Self::Timestamp => f.write_str("TIMESTAMP"),
+            Self::Headers => f.write_str("HEADERS"),
+        }
+    }
+}
+impl_display!(SourceIncludeMetadataType);
+
 impl<T: AstInfo> AstDisplay for Format<T> {
     fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
         match self {
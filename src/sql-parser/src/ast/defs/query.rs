@@ -23,7 +23,7 @@ use std::hash::Hash;
 use std::mem;
 
 use crate::ast::display::{self, AstDisplay, AstFormatter};
-use crate::ast::{Expr, FunctionArgs, Ident, SqlOption, UnresolvedObjectName};
+use crate::ast::{ColumnDef, Expr, FunctionArgs, Ident, SqlOption, UnresolvedObjectName};
 
 // This represents the metadata that lives next to an AST, as we take it through
 // various stages in the planning process.
@@ -411,6 +411,13 @@ pub enum TableFactor<T: AstInfo> {
         name: UnresolvedObjectName,
         args: FunctionArgs<T>,
         alias: Option<TableAlias>,
+        /// The column definition list, like `(a int, b text)` in
+        /// `jsonb_to_record(j) AS x (a int, b text)`, which describes the
+        /// columns of functions that return records.
+        column_defs: Vec<ColumnDef<T>>,
+        /// Whether `WITH ORDINALITY` was specified, which appends a column
+        /// numbering the rows produced by the function.
+        with_ordinality: bool,
     },
     Derived {
         lateral: bool,
@@ -437,15 +444,29 @@ impl<T: AstInfo> AstDisplay for TableFactor<T> {
                     f.write_node(alias);
                 }
             }
-            TableFactor::Function { name, args, alias } => {
+            TableFactor::Function {
+                name,
+                args,
+                alias,
+                column_defs,
+                with_ordinality,
+            } => {
                 f.write_node(name);
                 f.write_str("(");
                 f.write_node(args);
                 f.write_str(")");
+                if *with_ordinality {
+                    f.write_str(" WITH ORDINALITY");
+                }
                 if let Some(alias) = alias {
                     f.write_str(" AS ");
                     f.write_node(alias);
                 }
+                if !column_defs.is_empty() {
+                    f.write_str(" (");
+                    f.write_node(&display::comma_separated(column_defs));
+                    f.write_str(")");
+                }
             }
             TableFactor::Derived {
                 lateral,
@@ -22,8 +22,8 @@ use std::fmt;
 
 use crate::ast::display::{self, AstDisplay, AstFormatter};
 use crate::ast::{
-    AstInfo, ColumnDef, Connector, CreateSourceFormat, DataType, Envelope, Expr, Format, Ident,
-    Query, TableConstraint, UnresolvedObjectName, Value,
+    AstInfo, ColumnDef, Connector, CreateSourceFormat, CsrSeed, DataType, Envelope, Expr, Format,
+    Ident, Query, SourceIncludeMetadata, TableConstraint, UnresolvedObjectName, Value,
 };
 
 /// A top-level statement (SELECT, INSERT, CREATE, etc.)
@@ -47,6 +47,7 @@ pub enum Statement<T: AstInfo> {
     CreateRole(CreateRoleStatement),
     AlterObjectRename(AlterObjectRenameStatement),
     AlterIndexOptions(AlterIndexOptionsStatement),
+    AlterSourceRefreshSchema(AlterSourceRefreshSchemaStatement),
     Discard(DiscardStatement),
     DropDatabase(DropDatabaseStatement),
     DropObjects(DropObjectsStatement),
@@ -102,6 +103,7 @@ impl<T: AstInfo> AstDisplay for Statement<T> {
             Statement::CreateType(stmt) => f.write_node(stmt),
             Statement::AlterObjectRename(stmt) => f.write_node(stmt),
             Statement::AlterIndexOptions(stmt) => f.write_node(stmt),
+            Statement::AlterSourceRefreshSchema(stmt) => f.write_node(stmt),
             Statement::Discard(stmt) => f.write_node(stmt),
             Statement::DropDatabase(stmt) => f.write_node(stmt),
             Statement::DropObjects(stmt) => f.write_node(stmt),
@@ -357,6 +359,7 @@ pub struct CreateSourceStatement<T: AstInfo> {
     pub connector: Connector,
     pub with_options: Vec<SqlOption<T>>,
     pub format: CreateSourceFormat<T>,
+    pub include_metadata: Vec<SourceIncludeMetadata>,
     pub envelope: Envelope,
     pub if_not_exists: bool,
     pub materialized: bool,
@@ -387,6 +390,10 @@ impl<T: AstInfo> AstDisplay for CreateSourceStatement<T> {
             f.write_str(")");
         }
         f.write_node(&self.format);
+        if !self.include_metadata.is_empty() {
+            f.write_str(" INCLUDE ");
+            f.write_node(&display::comma_separated(&self.include_metadata));
+        }
         match self.envelope {
             Envelope::None => (),
             _ => {
@@ -404,6 +411,7 @@ pub struct CreateSinkStatement<T: AstInfo> {
     pub name: UnresolvedObjectName,
     pub from: UnresolvedObjectName,
     pub connector: Connector,
+    pub partition_by: Option<Expr<T>>,
     pub with_options: Vec<SqlOption<T>>,
     pub format: Option<Format<T>>,
     pub envelope: Option<Envelope>,
@@ -423,6 +431,10 @@ impl<T: AstInfo> AstDisplay for CreateSinkStatement<T> {
         f.write_node(&self.from);
         f.write_str(" INTO ");
         f.write_node(&self.connector);
+        if let Some(partition_by) = &self.partition_by {
+            f.write_str(" PARTITION BY ");
+            f.write_node(partition_by);
+        }
         if !self.with_options.is_empty() {
             f.write_str(" WITH (");
             f.write_node(&display::comma_separated(&self.with_options));
@@ -847,6 +859,32 @@ impl AstDisplay for AlterIndexOptionsStatement {
 
 impl_display!(AlterIndexOptionsStatement);
 
+/// `ALTER SOURCE ... REFRESH SCHEMA`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AlterSourceRefreshSchemaStatement {
+    pub source_name: UnresolvedObjectName,
+    pub if_exists: bool,
+    /// The schema to adopt. Filled in during purification with the latest
+    /// schema in the source's schema registry, if not specified.
+    pub seed: Option<CsrSeed>,
+}
+
+impl AstDisplay for AlterSourceRefreshSchemaStatement {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("ALTER SOURCE ");
+        if self.if_exists {
+            f.write_str("IF EXISTS ");
+        }
+        f.write_node(&self.source_name);
+        f.write_str(" REFRESH SCHEMA");
+        if let Some(seed) = &self.seed {
+            f.write_str(" ");
+            f.write_node(seed);
+        }
+    }
+}
+impl_display!(AlterSourceRefreshSchemaStatement);
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct DiscardStatement {
     pub target: DiscardTarget,
@@ -1230,15 +1268,16 @@ impl_display!(RollbackStatement);
 /// `TAIL`
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct TailStatement<T: AstInfo> {
-    pub name: UnresolvedObjectName,
+    pub relation: TailRelation<T>,
     pub options: Vec<WithOption>,
     pub as_of: Option<Expr<T>>,
+    pub up_to: Option<Expr<T>>,
 }
 
 impl<T: AstInfo> AstDisplay for TailStatement<T> {
     fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
         f.write_str("TAIL ");
-        f.write_node(&self.name);
+        f.write_node(&self.relation);
         if !self.options.is_empty() {
             f.write_str(" WITH (");
             f.write_node(&display::comma_separated(&self.options));
@@ -1248,10 +1287,37 @@ impl<T: AstInfo> AstDisplay for TailStatement<T> {
             f.write_str(" AS OF ");
             f.write_node(as_of);
         }
+        if let Some(up_to) = &self.up_to {
+            f.write_str(" UP TO ");
+            f.write_node(up_to);
+        }
     }
 }
 impl_display_t!(TailStatement);
 
+/// The relation tailed by a `TAIL` statement.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum TailRelation<T: AstInfo> {
+    /// A named table, source, or view.
+    Name(UnresolvedObjectName),
+    /// A parenthesized query.
+    Query(Query<T>),
+}
+
+impl<T: AstInfo> AstDisplay for TailRelation<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        match self {
+            TailRelation::Name(name) => f.write_node(name),
+            TailRelation::Query(query) => {
+                f.write_str("(");
+                f.write_node(query);
+                f.write_str(")");
+            }
+        }
+    }
+}
+impl_display_t!(TailRelation);
+
 /// `EXPLAIN ...`
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ExplainStatement<T: AstInfo> {
@@ -1468,6 +1534,7 @@ pub enum TransactionIsolationLevel {
     ReadCommitted,
     RepeatableRead,
     Serializable,
+    StrictSerializable,
 }
 
 impl AstDisplay for TransactionIsolationLevel {
@@ -1478,6 +1545,7 @@ impl AstDisplay for TransactionIsolationLevel {
             ReadCommitted => "READ COMMITTED",
             RepeatableRead => "REPEATABLE READ",
             Serializable => "SERIALIZABLE",
+            StrictSerializable => "STRICT SERIALIZABLE",
         })
     }
 }
@@ -1501,26 +1501,29 @@ impl<'a> Parser<'a> {
         Ok(format)
     }
 
+    fn parse_csr_seed(&mut self) -> Result<Option<CsrSeed>, ParserError> {
+        if !self.parse_keyword(SEED) {
+            return Ok(None);
+        }
+        let key_schema = if self.parse_keyword(KEY) {
+            self.expect_keyword(SCHEMA)?;
+            Some(self.parse_literal_string()?)
+        } else {
+            None
+        };
+        self.expect_keywords(&[VALUE, SCHEMA])?;
+        let value_schema = self.parse_literal_string()?;
+        Ok(Some(CsrSeed {
+            key_schema,
+            value_schema,
+        }))
+    }
+
     fn parse_avro_schema(&mut self) -> Result<AvroSchema<Raw>, ParserError> {
         let avro_schema = if self.parse_keywords(&[CONFLUENT, SCHEMA, REGISTRY]) {
             let url = self.parse_literal_string()?;
 
-            let seed = if self.parse_keyword(SEED) {
-                let key_schema = if self.parse_keyword(KEY) {
-                    self.expect_keyword(SCHEMA)?;
-                    Some(self.parse_literal_string()?)
-                } else {
-                    None
-                };
-                self.expect_keywords(&[VALUE, SCHEMA])?;
-                let value_schema = self.parse_literal_string()?;
-                Some(CsrSeed {
-                    key_schema,
-                    value_schema,
-                })
-            } else {
-                None
-            };
+            let seed = self.parse_csr_seed()?;
 
             // Look ahead to avoid erroring on `WITH SNAPSHOT`; we only want to
             // accept `WITH (...)` here.
@@ -1581,7 +1584,15 @@ impl<'a> Parser<'a> {
             };
             Envelope::Debezium(debezium_mode)
         } else if self.parse_keyword(UPSERT) {
-            Envelope::Upsert
+            let key = if self.consume_token(&Token::LParen) {
+                self.expect_keyword(KEY)?;
+                let key = self.parse_parenthesized_column_list(Mandatory)?;
+                self.expect_token(&Token::RParen)?;
+                Some(key)
+            } else {
+                None
+            };
+            Envelope::Upsert(key)
         } else if self.parse_keyword(MATERIALIZE) {
             Envelope::CdcV2
         } else {
@@ -1628,9 +1639,14 @@ impl<'a> Parser<'a> {
             Some(_) => unreachable!("parse_one_of_keywords returns None for this"),
             None => CreateSourceFormat::None,
         };
+        let include_metadata = if self.parse_keyword(INCLUDE) {
+            self.parse_comma_separated(Parser::parse_source_include_metadata)?
+        } else {
+            vec![]
+        };
         let envelope = if self.parse_keyword(ENVELOPE) {
             let envelope = self.parse_envelope()?;
-            if matches!(envelope, Envelope::Upsert) {
+            if matches!(envelope, Envelope::Upsert(None)) {
                 // TODO: remove support for explicit UPSERT FORMAT after a period of deprecation
                 if self.parse_keyword(FORMAT) {
                     warn!("UPSERT FORMAT has been deprecated, use the new KEY FORMAT syntax");
@@ -1656,12 +1672,36 @@ impl<'a> Parser<'a> {
             connector,
             with_options,
             format,
+            include_metadata,
             envelope,
             if_not_exists,
             materialized,
         }))
     }
 
+    fn parse_source_include_metadata(&mut self) -> Result<SourceIncludeMetadata, ParserError> {
+        let ty = match self.parse_one_of_keywords(&[KEY, PARTITION, OFFSET, TIMESTAMP, HEADERS]) {
+            Some(KEY) => SourceIncludeMetadataType::Key,
+            Some(PARTITION) => SourceIncludeMetadataType::Partition,
+            Some(OFFSET) => SourceIncludeMetadataType::Offset,
+            Some(TIMESTAMP) => SourceIncludeMetadataType::Timestamp,
+            Some(HEADERS) => SourceIncludeMetadataType::Headers,
+            _ => {
+                return self.expected(
+                    self.peek_pos(),
+                    "KEY, PARTITION, OFFSET, TIMESTAMP, or HEADERS",
+                    self.peek_token(),
+                )
+            }
+        };
+        let alias = if self.parse_keyword(AS) {
+            Some(self.parse_identifier()?)
+        } else {
+            None
+        };
+        Ok(SourceIncludeMetadata { ty, alias })
+    }
+
     fn parse_create_sink(&mut self) -> Result<Statement<Raw>, ParserError> {
         self.expect_keyword(SINK)?;
         let if_not_exists = self.parse_if_not_exists()?;
@@ -1670,6 +1710,11 @@ impl<'a> Parser<'a> {
         let from = self.parse_object_name()?;
         self.expect_keyword(INTO)?;
         let connector = self.parse_connector()?;
+        let partition_by = if self.parse_keywords(&[PARTITION, BY]) {
+            Some(self.parse_expr()?)
+        } else {
+            None
+        };
         let mut with_options = vec![];
         if self.parse_keyword(WITH) {
             if let Some(Token::LParen) = self.next_token() {
@@ -1704,6 +1749,7 @@ impl<'a> Parser<'a> {
             name,
             from,
             connector,
+            partition_by,
             with_options,
             format,
             envelope,
@@ -2392,6 +2438,18 @@ impl<'a> Parser<'a> {
             }
         }
 
+        // We support `ALTER SOURCE ... REFRESH SCHEMA`
+        if object_type == ObjectType::Source && self.parse_keywords(&[REFRESH, SCHEMA]) {
+            let seed = self.parse_csr_seed()?;
+            return Ok(Statement::AlterSourceRefreshSchema(
+                AlterSourceRefreshSchemaStatement {
+                    source_name: name,
+                    if_exists,
+                    seed,
+                },
+            ));
+        }
+
         self.expect_keywords(&[RENAME, TO])?;
         let to_item_name = self.parse_identifier()?;
 
@@ -2778,6 +2836,51 @@ impl<'a> Parser<'a> {
         }
     }
 
+    /// Parse the alias of a table function. In addition to the forms accepted
+    /// by `parse_optional_table_alias`, the alias may be followed by a column
+    /// definition list, like in `... FROM jsonb_to_record(j) AS t (a int)`.
+    fn parse_table_function_alias(
+        &mut self,
+    ) -> Result<(Option<TableAlias>, Vec<ColumnDef<Raw>>), ParserError> {
+        let name = match self.parse_optional_alias(Keyword::is_reserved_in_table_alias)? {
+            Some(name) => name,
+            None => return Ok((None, vec![])),
+        };
+        let mut columns = vec![];
+        let mut column_defs = vec![];
+        if self.consume_token(&Token::LParen) {
+            loop {
+                let column_name = self.parse_identifier()?;
+                match self.peek_token() {
+                    Some(Token::Comma) | Some(Token::RParen) => columns.push(column_name),
+                    _ => column_defs.push(ColumnDef {
+                        name: column_name,
+                        data_type: self.parse_data_type()?,
+                        collation: None,
+                        options: vec![],
+                    }),
+                }
+                if !columns.is_empty() && !column_defs.is_empty() {
+                    return parser_err!(
+                        self,
+                        self.peek_prev_pos(),
+                        "column aliases cannot be mixed with column definitions"
+                    );
+                }
+                if !self.consume_token(&Token::Comma) {
+                    break;
+                }
+            }
+            self.expect_token(&Token::RParen)?;
+        }
+        let alias = TableAlias {
+            name,
+            columns,
+            strict: false,
+        };
+        Ok((Some(alias), column_defs))
+    }
+
     /// Parse a possibly qualified, possibly quoted identifier, e.g.
     /// `foo` or `myschema."table"`
     fn parse_object_name(&mut self) -> Result<UnresolvedObjectName, ParserError> {
@@ -3477,10 +3580,15 @@ impl<'a> Parser<'a> {
             } else {
                 let name = self.parse_object_name()?;
                 self.expect_token(&Token::LParen)?;
+                let args = self.parse_optional_args()?;
+                let with_ordinality = self.parse_keywords(&[WITH, ORDINALITY]);
+                let (alias, column_defs) = self.parse_table_function_alias()?;
                 return Ok(TableFactor::Function {
                     name,
-                    args: self.parse_optional_args()?,
-                    alias: self.parse_optional_table_alias()?,
+                    args,
+                    alias,
+                    column_defs,
+                    with_ordinality,
                 });
             }
         }
@@ -3559,10 +3667,15 @@ impl<'a> Parser<'a> {
         } else {
             let name = self.parse_object_name()?;
             if self.consume_token(&Token::LParen) {
+                let args = self.parse_optional_args()?;
+                let with_ordinality = self.parse_keywords(&[WITH, ORDINALITY]);
+                let (alias, column_defs) = self.parse_table_function_alias()?;
                 Ok(TableFactor::Function {
                     name,
-                    args: self.parse_optional_args()?,
-                    alias: self.parse_optional_table_alias()?,
+                    args,
+                    alias,
+                    column_defs,
+                    with_ordinality,
                 })
             } else {
                 Ok(TableFactor::Table {
@@ -3739,6 +3852,8 @@ impl<'a> Parser<'a> {
                     TransactionIsolationLevel::RepeatableRead
                 } else if self.parse_keyword(SERIALIZABLE) {
                     TransactionIsolationLevel::Serializable
+                } else if self.parse_keywords(&[STRICT, SERIALIZABLE]) {
+                    TransactionIsolationLevel::StrictSerializable
                 } else {
                     self.expected(self.peek_pos(), "isolation level", self.peek_token())?
                 };
@@ -3786,13 +3901,25 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_tail(&mut self) -> Result<Statement<Raw>, ParserError> {
-        let name = self.parse_object_name()?;
+        let relation = if self.consume_token(&Token::LParen) {
+            let query = self.parse_query()?;
+            self.expect_token(&Token::RParen)?;
+            TailRelation::Query(query)
+        } else {
+            TailRelation::Name(self.parse_object_name()?)
+        };
         let options = self.parse_opt_with_options()?;
         let as_of = self.parse_optional_as_of()?;
+        let up_to = if self.parse_keywords(&[UP, TO]) {
+            Some(self.parse_expr()?)
+        } else {
+            None
+        };
         Ok(Statement::Tail(TailStatement {
-            name,
+            relation,
             options,
             as_of,
+            up_to,
         }))
     }
 
@@ -18,8 +18,8 @@ use crate::ast::visit::{self, Visit};
 use crate::ast::visit_mut::{self, VisitMut};
 use crate::ast::{
     AstInfo, CreateIndexStatement, CreateSinkStatement, CreateSourceStatement,
-    CreateTableStatement, CreateViewStatement, Expr, Ident, Query, Raw, Statement,
-    UnresolvedObjectName, ViewDefinition,
+    CreateTableStatement, CreateViewStatement, Expr, Ident, Query, Raw, Select, SelectItem,
+    Statement, TableAlias, TableFactor, TableWithJoins, UnresolvedObjectName, ViewDefinition,
 };
 use crate::names::FullName;
 
@@ -98,6 +98,95 @@ pub fn create_stmt_rename_refs(
     Ok(())
 }
 
+/// Rewrites the references to the item `name` in a view's `CREATE` statement
+/// so that they read only `columns` of the item, in that order.
+///
+/// This lets a view keep the columns it was planned with after columns are
+/// added to the item, e.g. by `ALTER SOURCE ... REFRESH SCHEMA`. Each reference
+/// is replaced by a subquery that selects `columns` from the item and has the
+/// reference's alias, or the item's name if it had none.
+pub fn create_stmt_pin_columns(
+    create_stmt: &mut Statement<Raw>,
+    name: FullName,
+    columns: &[Ident],
+) {
+    if let Statement::CreateView(CreateViewStatement {
+        definition: ViewDefinition { query, .. },
+        ..
+    }) = create_stmt
+    {
+        let mut v = ColumnPinner {
+            name: UnresolvedObjectName::from(name),
+            columns,
+        };
+        v.visit_query_mut(query);
+    }
+}
+
+struct ColumnPinner<'a> {
+    name: UnresolvedObjectName,
+    columns: &'a [Ident],
+}
+
+impl<'a> ColumnPinner<'a> {
+    /// Shortens column references qualified by the item's schema, e.g.
+    /// `public.src.a`, to refer to the subquery's alias, e.g. `src.a`.
+    fn maybe_unqualify(&self, name: &mut Vec<Ident>) {
+        let item = &self.name.0[self.name.0.len() - 1];
+        if name.len() > 1 && self.name.0.ends_with(name) {
+            *name = vec![item.clone()];
+        }
+    }
+}
+
+impl<'a, 'ast> VisitMut<'ast, Raw> for ColumnPinner<'a> {
+    fn visit_table_factor_mut(&mut self, table_factor: &'ast mut TableFactor<Raw>) {
+        match table_factor {
+            TableFactor::Table { name, alias } if name.name().0 == self.name.0 => {
+                let item = &self.name.0[self.name.0.len() - 1];
+                let alias = alias.take().unwrap_or_else(|| TableAlias {
+                    name: item.clone(),
+                    columns: vec![],
+                    strict: false,
+                });
+                let mut select = Select::default().from(TableWithJoins {
+                    relation: TableFactor::Table {
+                        name: name.clone(),
+                        alias: None,
+                    },
+                    joins: vec![],
+                });
+                for column in self.columns {
+                    select = select.project(SelectItem::Expr {
+                        expr: Expr::Identifier(vec![column.clone()]),
+                        alias: None,
+                    });
+                }
+                *table_factor = TableFactor::Derived {
+                    lateral: false,
+                    subquery: Box::new(Query::select(select)),
+                    alias: Some(alias),
+                };
+            }
+            _ => visit_mut::visit_table_factor_mut(self, table_factor),
+        }
+    }
+
+    fn visit_expr_mut(&mut self, e: &'ast mut Expr<Raw>) {
+        match e {
+            Expr::Identifier(id) => {
+                // The last ID component is a column name.
+                let mut qualifier = id[..id.len() - 1].to_vec();
+                self.maybe_unqualify(&mut qualifier);
+                qualifier.push(id[id.len() - 1].clone());
+                *id = qualifier;
+            }
+            Expr::QualifiedWildcard(id) => self.maybe_unqualify(id),
+            _ => visit_mut::visit_expr_mut(self, e),
+        }
+    }
+}
+
 /// Rewrites `query`'s references of `from` to `to` or errors if too ambiguous.
 fn rewrite_query(from: FullName, to: String, query: &mut Query<Raw>) -> Result<(), String> {
     let from_ident = Ident::new(from.item.clone());
@@ -130,9 +130,9 @@ pub trait Catalog: fmt::Debug + ExprHumanizer {
 
     /// Returns a lossy `ScalarType` associated with `id` if one exists.
     ///
-    /// For example `pg_catalog.numeric` returns `ScalarType::Decimal(0,0)`,
-    /// meaning that its precision and scale need to be associated with values
-    /// from elsewhere.
+    /// For example `pg_catalog.numeric` returns `ScalarType::APD { scale: None }`,
+    /// meaning that its scale needs to be associated with values from
+    /// elsewhere.
     fn try_get_lossy_scalar_type_by_id(&self, id: &GlobalId) -> Option<ScalarType>;
 
     /// Finds a name like `name` that is not already in use.
@@ -22,6 +22,9 @@ use lazy_static::lazy_static;
 
 use ore::collections::CollectionExt;
 use pgrepr::oid;
+use repr::adt::datetime::DateTimeUnits;
+use repr::adt::range;
+use repr::adt::regex::Regex;
 use repr::{ColumnName, Datum, RelationType, ScalarBaseType, ScalarType};
 use sql_parser::ast::{Expr, Raw, UnresolvedObjectName};
 
@@ -67,8 +70,10 @@ pub enum TypeCategory {
     Bool,
     DateTime,
     List,
+    NetworkAddress,
     Numeric,
     Pseudo,
+    Range,
     String,
     Timespan,
     UserDefined,
@@ -80,8 +85,8 @@ impl TypeCategory {
     /// SELECT array_agg(typname), typcategory
     /// FROM pg_catalog.pg_type
     /// WHERE typname IN (
-    ///  'bool', 'bytea', 'date', 'float4', 'float8', 'int4', 'int8', 'interval', 'jsonb',
-    ///  'numeric', 'text', 'time', 'timestamp', 'timestamptz'
+    ///  'bool', 'bytea', 'cidr', 'date', 'float4', 'float8', 'inet', 'int4', 'int8',
+    ///  'interval', 'jsonb', 'numeric', 'text', 'time', 'timestamp', 'timestamptz'
     /// )
     /// GROUP BY typcategory
     /// ORDER BY typcategory;
@@ -102,11 +107,15 @@ impl TypeCategory {
             | ScalarType::Int64
             | ScalarType::Oid
             | ScalarType::APD { .. } => Self::Numeric,
+            ScalarType::Inet | ScalarType::Cidr => Self::NetworkAddress,
             ScalarType::Interval => Self::Timespan,
             ScalarType::List { .. } => Self::List,
-            ScalarType::String => Self::String,
+            ScalarType::String | ScalarType::Char { .. } | ScalarType::VarChar { .. } => {
+                Self::String
+            }
             ScalarType::Record { .. } => Self::Pseudo,
             ScalarType::Map { .. } => Self::Pseudo,
+            ScalarType::Range { .. } => Self::Range,
         }
     }
 
@@ -114,10 +123,13 @@ impl TypeCategory {
         match param {
             ParamType::Any
             | ParamType::ArrayAny
+            | ParamType::ArrayElementAny
             | ParamType::ListAny
             | ParamType::ListElementAny
             | ParamType::NonVecAny
-            | ParamType::MapAny => Self::Pseudo,
+            | ParamType::MapAny
+            | ParamType::RangeAny
+            | ParamType::RangeElementAny => Self::Pseudo,
             ParamType::DecimalAny => Self::Numeric,
             ParamType::Plain(t) => Self::from_type(t),
         }
@@ -132,9 +144,10 @@ impl TypeCategory {
     /// ```
     fn preferred_type(&self) -> Option<ScalarType> {
         match self {
-            Self::Array | Self::List | Self::Pseudo | Self::UserDefined => None,
+            Self::Array | Self::List | Self::Pseudo | Self::Range | Self::UserDefined => None,
             Self::Bool => Some(ScalarType::Bool),
             Self::DateTime => Some(ScalarType::TimestampTz),
+            Self::NetworkAddress => Some(ScalarType::Inet),
             Self::Numeric => Some(ScalarType::Float64),
             Self::String => Some(ScalarType::String),
             Self::Timespan => Some(ScalarType::Interval),
@@ -410,12 +423,16 @@ impl ParamList {
     ///
     /// Polymorphic type consistency constraints include:
     /// - All arguments passed to `ArrayAny` must be `ScalarType::Array`s with
-    ///   the same types of elements.
+    ///   the same types of elements. All arguments passed to `ArrayElementAny`
+    ///   must also be of these elements' type.
     /// - All arguments passed to `ListAny` must be `ScalarType::List`s with the
     ///   same types of elements. All arguments passed to `ListElementAny` must
     ///   also be of these elements' type.
     /// - All arguments passed to `MapAny` must be `ScalarType::Map`s with the
     ///   same type of value in each key, value pair.
+    /// - All arguments passed to `RangeAny` must be `ScalarType::Range`s with
+    ///   the same type of elements. All arguments passed to `RangeElementAny`
+    ///   must also be of these elements' type.
     ///
     /// Returns `Some` if the constraints were successfully resolved, or `None`
     /// otherwise.
@@ -579,6 +596,14 @@ impl ParamList {
                         return None;
                     }
                 }
+                (ParamType::ArrayElementAny, Some(t), None) => {
+                    constrained_type = Some(ScalarType::Array(Box::new(t.clone())));
+                }
+                (ParamType::ArrayElementAny, Some(t), Some(constrained_array)) => {
+                    if !complex_base_eq(t, &constrained_array.unwrap_array_element_type()) {
+                        return None;
+                    }
+                }
                 (ParamType::ListAny, Some(typ), None) | (ParamType::MapAny, Some(typ), None) => {
                     constrained_type = Some(typ.clone());
                     custom_oid_lock = typ.is_custom_type();
@@ -651,6 +676,27 @@ impl ParamList {
                         }
                     }
                 }
+                (ParamType::RangeAny, Some(typ), None) => {
+                    constrained_type = Some(typ.clone());
+                }
+                (ParamType::RangeAny, Some(typ), Some(constrained)) => {
+                    if typ != constrained {
+                        return None;
+                    }
+                }
+                (ParamType::RangeElementAny, Some(t), None) => {
+                    if !range::is_valid_element_type(t) {
+                        return None;
+                    }
+                    constrained_type = Some(ScalarType::Range {
+                        element_type: Box::new(t.clone()),
+                    });
+                }
+                (ParamType::RangeElementAny, Some(t), Some(constrained_range)) => {
+                    if t != constrained_range.unwrap_range_element_type() {
+                        return None;
+                    }
+                }
                 (ParamType::NonVecAny, Some(t), None) => {
                     constrained_type = Some(t.clone());
                 }
@@ -725,6 +771,10 @@ pub enum ParamType {
     /// A polymorphic pseudotype permitting any array type.  For more details,
     /// see [`ParamList::resolve_polymorphic_types`].
     ArrayAny,
+    /// A polymorphic pseudotype permitting any type that can be the element of
+    /// a `ScalarType::Array`, i.e. any non-array type. For more details, see
+    /// [`ParamList::resolve_polymorphic_types`].
+    ArrayElementAny,
     /// A polymorphic pseudotype permitting a `ScalarType::List` of any element
     /// type.  For more details, see [`ParamList::resolve_polymorphic_types`].
     ListAny,
@@ -739,6 +789,14 @@ pub enum ParamType {
     /// A polymorphic pseudotype permitting a `ScalarType::Map` of any non-nested
     /// value type. For more details, see [`ParamList::resolve_polymorphic_types`].
     MapAny,
+    /// A polymorphic pseudotype permitting a `ScalarType::Range` of any
+    /// element type. For more details, see
+    /// [`ParamList::resolve_polymorphic_types`].
+    RangeAny,
+    /// A polymorphic pseudotype permitting any type that can be the element of
+    /// a `ScalarType::Range`. For more details, see
+    /// [`ParamList::resolve_polymorphic_types`].
+    RangeElementAny,
     /// A standard parameter that accepts arguments that match its embedded
     /// `ScalarType`.
     Plain(ScalarType),
@@ -752,10 +810,13 @@ impl ParamType {
 
         match self {
             ArrayAny => matches!(t, Array(..)),
+            ArrayElementAny => !matches!(t, Array(..)),
             ListAny => matches!(t, List { .. }),
             Any | ListElementAny => true,
             NonVecAny => !t.is_vec(),
             MapAny => matches!(t, Map { .. }),
+            RangeAny => matches!(t, Range { .. }),
+            RangeElementAny => range::is_valid_element_type(t),
             DecimalAny => {
                 typeconv::can_cast(ecx, CastContext::Implicit, t, &ScalarType::Decimal(0, 0))
             }
@@ -785,7 +846,8 @@ impl ParamType {
     fn is_polymorphic(&self) -> bool {
         use ParamType::*;
         match self {
-            ArrayAny | ListAny | MapAny | ListElementAny | NonVecAny => true,
+            ArrayAny | ArrayElementAny | ListAny | MapAny | ListElementAny | NonVecAny
+            | RangeAny | RangeElementAny => true,
             Any | DecimalAny | Plain(_) => false,
         }
     }
@@ -805,11 +867,14 @@ impl ParamType {
             },
             ParamType::Any => postgres_types::Type::ANY.oid(),
             ParamType::ArrayAny => postgres_types::Type::ANYARRAY.oid(),
+            ParamType::ArrayElementAny => postgres_types::Type::ANYELEMENT.oid(),
             ParamType::DecimalAny => postgres_types::Type::NUMERIC.oid(),
             ParamType::ListAny => pgrepr::LIST.oid(),
             ParamType::ListElementAny => postgres_types::Type::ANYELEMENT.oid(),
             ParamType::MapAny => pgrepr::MAP.oid(),
             ParamType::NonVecAny => postgres_types::Type::ANYNONARRAY.oid(),
+            ParamType::RangeAny => postgres_types::Type::ANYRANGE.oid(),
+            ParamType::RangeElementAny => postgres_types::Type::ANYELEMENT.oid(),
         }
     }
 }
@@ -1154,13 +1219,21 @@ fn coerce_args_to_types(
             ParamType::Plain(ty) => do_convert(arg, ty)?,
 
             // Polymorphic pseudotypes. Convert based on constrained type.
-            ParamType::ArrayAny | ParamType::ListAny | ParamType::MapAny => {
+            ParamType::ArrayAny | ParamType::ListAny | ParamType::MapAny | ParamType::RangeAny => {
                 do_convert(arg, &get_constrained_ty())?
             }
+            ParamType::ArrayElementAny => {
+                let constrained_array = get_constrained_ty();
+                do_convert(arg, &constrained_array.unwrap_array_element_type())?
+            }
             ParamType::ListElementAny => {
                 let constrained_list = get_constrained_ty();
                 do_convert(arg, &constrained_list.unwrap_list_element_type())?
             }
+            ParamType::RangeElementAny => {
+                let constrained_range = get_constrained_ty();
+                do_convert(arg, &constrained_range.unwrap_range_element_type())?
+            }
             ParamType::NonVecAny => {
                 let ty = get_constrained_ty();
                 assert!(!ty.is_vec());
@@ -1280,15 +1353,50 @@ lazy_static! {
                 params!(Int32) => UnaryFunc::AbsInt32, 1397;
                 params!(Int64) => UnaryFunc::AbsInt64, 1396;
                 params!(DecimalAny) => UnaryFunc::AbsDecimal, 1398;
+                params!(APD{scale:None}) => UnaryFunc::AbsAPD, 13980;
                 params!(Float32) => UnaryFunc::AbsFloat32, 1394;
                 params!(Float64) => UnaryFunc::AbsFloat64, 1395;
             },
+            "age" => Scalar {
+                params!(Timestamp, Timestamp) => BinaryFunc::AgeTimestamp, 2058;
+                params!(TimestampTz, TimestampTz) => Operation::binary(|ecx, a, b| {
+                    // The difference is computed between the session's local
+                    // times, so that e.g. months are measured by the calendar
+                    // the user sees.
+                    let a = typeconv::plan_cast("age", ecx, CastContext::Explicit, a, &ScalarType::Timestamp)?;
+                    let b = typeconv::plan_cast("age", ecx, CastContext::Explicit, b, &ScalarType::Timestamp)?;
+                    Ok(a.call_binary(b, BinaryFunc::AgeTimestamp))
+                }), 1199;
+                params!(Timestamp) => Operation::unary(|ecx, ts| {
+                    Ok(plan_current_date(ecx, "age")?.call_binary(ts, BinaryFunc::AgeTimestamp))
+                }), 2059;
+                params!(TimestampTz) => Operation::unary(|ecx, ts| {
+                    let ts = typeconv::plan_cast("age", ecx, CastContext::Explicit, ts, &ScalarType::Timestamp)?;
+                    Ok(plan_current_date(ecx, "age")?.call_binary(ts, BinaryFunc::AgeTimestamp))
+                }), 1200;
+            },
+            "array_append" => Scalar {
+                params!(ArrayAny, ArrayElementAny) => BinaryFunc::ArrayElementConcat, 378;
+            },
+            "array_cat" => Scalar {
+                params!(ArrayAny, ArrayAny) => BinaryFunc::ArrayArrayConcat, 383;
+            },
             "array_length" => Scalar {
                 params![ArrayAny, Int64] => BinaryFunc::ArrayLength, 2176;
             },
             "array_lower" => Scalar {
                 params!(ArrayAny, Int64) => BinaryFunc::ArrayLower, 2091;
             },
+            "array_position" => Scalar {
+                params!(ArrayAny, ArrayElementAny) => VariadicFunc::ArrayPosition, 3277;
+                params!(ArrayAny, ArrayElementAny, Int32) => VariadicFunc::ArrayPosition, 3278;
+            },
+            "array_prepend" => Scalar {
+                params!(ArrayElementAny, ArrayAny) => BinaryFunc::ElementArrayConcat, 379;
+            },
+            "array_remove" => Scalar {
+                params!(ArrayAny, ArrayElementAny) => BinaryFunc::ArrayRemove, 3167;
+            },
             "array_to_string" => Scalar {
                 params!(ArrayAny, String) => Operation::variadic(array_to_string), 395;
                 params!(ArrayAny, String, String) => Operation::variadic(array_to_string), 384;
@@ -1303,6 +1411,7 @@ lazy_static! {
                 params!(Int64) => Operation::nullary(|_ecx| catalog_name_only!("avg")), 2100;
                 params!(Int32) => Operation::nullary(|_ecx| catalog_name_only!("avg")), 2101;
                 params!(DecimalAny) => Operation::nullary(|_ecx| catalog_name_only!("avg")), 2103;
+                params!(APD{scale:None}) => Operation::nullary(|_ecx| catalog_name_only!("avg")), 21030;
                 params!(Float32) => Operation::nullary(|_ecx| catalog_name_only!("avg")), 2104;
                 params!(Float64) => Operation::nullary(|_ecx| catalog_name_only!("avg")), 2105;
                 params!(Interval) => Operation::nullary(|_ecx| catalog_name_only!("avg")), 2106;
@@ -1325,6 +1434,7 @@ lazy_static! {
                     let (_, s) = ecx.scalar_type(&e).unwrap_decimal_parts();
                     Ok(e.call_unary(UnaryFunc::CeilDecimal(s)))
                 }), 1711;
+                params!(APD{scale:None}) => UnaryFunc::CeilAPD, 17110;
             },
             "char_length" => Scalar {
                 params!(String) => UnaryFunc::CharLength, 1381;
@@ -1386,12 +1496,29 @@ lazy_static! {
             },
             "date_part" => Scalar {
                 params!(String, Interval) => BinaryFunc::DatePartInterval, 1172;
+                params!(String, Time) => BinaryFunc::DatePartTime, 1385;
                 params!(String, Timestamp) => BinaryFunc::DatePartTimestamp, 2021;
                 params!(String, TimestampTz) => BinaryFunc::DatePartTimestampTz, 1171;
             },
             "date_trunc" => Scalar {
                 params!(String, Timestamp) => BinaryFunc::DateTruncTimestamp, 2020;
-                params!(String, TimestampTz) => BinaryFunc::DateTruncTimestampTz, 1217;
+                params!(String, TimestampTz) => Operation::binary(|ecx, units, ts| {
+                    // Truncation happens in the session's local time, so that
+                    // e.g. `date_trunc('day', ...)` finds local midnight.
+                    let tz = ecx.qcx.scx.pcx.timezone;
+                    Ok(if tz.is_utc() {
+                        units.call_binary(ts, BinaryFunc::DateTruncTimestampTz)
+                    } else {
+                        let ts = ts.call_unary(UnaryFunc::TimezoneTimestampTz(tz));
+                        units
+                            .call_binary(ts, BinaryFunc::DateTruncTimestamp)
+                            .call_unary(UnaryFunc::TimezoneTimestamp(tz))
+                    })
+                }), 1217;
+            },
+            "daterange" => Scalar {
+                params!(Date, Date) => VariadicFunc::RangeCreate { elem_type: Date }, 3941;
+                params!(Date, Date, String) => VariadicFunc::RangeCreate { elem_type: Date }, 3942;
             },
             "digest" => Scalar {
                 params!(String, String) => BinaryFunc::DigestString, 44154;
@@ -1403,6 +1530,7 @@ lazy_static! {
                     let (_, s) = ecx.scalar_type(&e).unwrap_decimal_parts();
                     Ok(e.call_unary(UnaryFunc::ExpDecimal(s)))
                 }), 1732;
+                params!(APD{scale:None}) => UnaryFunc::ExpAPD, 17320;
             },
             "floor" => Scalar {
                 params!(Float32) => UnaryFunc::FloorFloat32, oid::FUNC_FLOOR_F32_OID;
@@ -1411,6 +1539,7 @@ lazy_static! {
                     let (_, s) = ecx.scalar_type(&e).unwrap_decimal_parts();
                     Ok(e.call_unary(UnaryFunc::FloorDecimal(s)))
                 }), 1712;
+                params!(APD{scale:None}) => UnaryFunc::FloorAPD, 17120;
             },
             "format_type" => Scalar {
                 params!(Oid, Int32) => sql_op!(
@@ -1420,10 +1549,24 @@ lazy_static! {
                     END"
                 ), 1081;
             },
+            "host" => Scalar {
+                params!(Inet) => UnaryFunc::InetHost, 699;
+            },
             "hmac" => Scalar {
                 params!(String, String, String) => VariadicFunc::HmacString, 44156;
                 params!(Bytes, Bytes, String) => VariadicFunc::HmacBytes, 44157;
             },
+            "int4range" => Scalar {
+                params!(Int32, Int32) => VariadicFunc::RangeCreate { elem_type: Int32 }, 3840;
+                params!(Int32, Int32, String) => VariadicFunc::RangeCreate { elem_type: Int32 }, 3841;
+            },
+            "int8range" => Scalar {
+                params!(Int64, Int64) => VariadicFunc::RangeCreate { elem_type: Int64 }, 3945;
+                params!(Int64, Int64, String) => VariadicFunc::RangeCreate { elem_type: Int64 }, 3946;
+            },
+            "isempty" => Scalar {
+                params!(RangeAny) => UnaryFunc::RangeIsEmpty, 3850;
+            },
             "jsonb_array_length" => Scalar {
                 params!(Jsonb) => UnaryFunc::JsonbArrayLength, 3207;
             },
@@ -1450,15 +1593,37 @@ lazy_static! {
                     })
                 }), 3273;
             },
+            "jsonb_insert" => Scalar {
+                params!(Jsonb, Plain(Array(Box::new(String))), Jsonb) => VariadicFunc::JsonbInsert, oid::FUNC_JSONB_INSERT_3_OID;
+                params!(Jsonb, Plain(Array(Box::new(String))), Jsonb, Bool) => VariadicFunc::JsonbInsert, 3579;
+            },
+            "jsonb_path_exists" => Scalar {
+                params!(Jsonb, String) => VariadicFunc::JsonbPathExists, oid::FUNC_JSONB_PATH_EXISTS_2_OID;
+                params!(Jsonb, String, Jsonb) => VariadicFunc::JsonbPathExists, oid::FUNC_JSONB_PATH_EXISTS_3_OID;
+                params!(Jsonb, String, Jsonb, Bool) => VariadicFunc::JsonbPathExists, 4005;
+            },
             "jsonb_pretty" => Scalar {
                 params!(Jsonb) => UnaryFunc::JsonbPretty, 3306;
             },
+            "jsonb_set" => Scalar {
+                params!(Jsonb, Plain(Array(Box::new(String))), Jsonb) => VariadicFunc::JsonbSet, oid::FUNC_JSONB_SET_3_OID;
+                params!(Jsonb, Plain(Array(Box::new(String))), Jsonb, Bool) => VariadicFunc::JsonbSet, 3304;
+            },
             "jsonb_strip_nulls" => Scalar {
                 params!(Jsonb) => UnaryFunc::JsonbStripNulls, 3262;
             },
             "jsonb_typeof" => Scalar {
                 params!(Jsonb) => UnaryFunc::JsonbTypeof, 3210;
             },
+            "justify_days" => Scalar {
+                params!(Interval) => UnaryFunc::JustifyDays, 1295;
+            },
+            "justify_hours" => Scalar {
+                params!(Interval) => UnaryFunc::JustifyHours, 1175;
+            },
+            "justify_interval" => Scalar {
+                params!(Interval) => UnaryFunc::JustifyInterval, 2711;
+            },
             "length" => Scalar {
                 params!(Bytes) => UnaryFunc::ByteLengthBytes, 2010;
                 params!(String) => UnaryFunc::CharLength, 1317;
@@ -1470,6 +1635,7 @@ lazy_static! {
                     let (_, s) = ecx.scalar_type(&e).unwrap_decimal_parts();
                     Ok(e.call_unary(UnaryFunc::LnDecimal(s)))
                 }), 1734;
+                params!(APD{scale:None}) => UnaryFunc::LnAPD, 17340;
             },
             "log10" => Scalar {
                 params!(Float64) => UnaryFunc::Log10, 1194;
@@ -1477,6 +1643,7 @@ lazy_static! {
                     let (_, s) = ecx.scalar_type(&e).unwrap_decimal_parts();
                     Ok(e.call_unary(UnaryFunc::Log10Decimal(s)))
                 }), 1481;
+                params!(APD{scale:None}) => UnaryFunc::Log10APD, 14810;
             },
             "log" => Scalar {
                 params!(Float64) => UnaryFunc::Log10, 1340;
@@ -1484,13 +1651,22 @@ lazy_static! {
                     let (_, s) = ecx.scalar_type(&e).unwrap_decimal_parts();
                     Ok(e.call_unary(UnaryFunc::Log10Decimal(s)))
                 }), 1741;
+                params!(APD{scale:None}) => UnaryFunc::Log10APD, 17410;
                 params!(DecimalAny, DecimalAny) => Operation::binary(|ecx, lhs, rhs| {
                     let (_, s) = ecx.scalar_type(&lhs).unwrap_decimal_parts();
                     Ok(lhs.call_binary(rhs, BinaryFunc::LogDecimal(s)))
                 }), 1736;
+                params!(APD{scale:None}, APD{scale:None}) => BinaryFunc::LogAPD, 17360;
             },
             "lower" => Scalar {
                 params!(String) => UnaryFunc::Lower, 870;
+                params!(RangeAny) => UnaryFunc::RangeLower, 3848;
+            },
+            "lower_inc" => Scalar {
+                params!(RangeAny) => UnaryFunc::RangeLowerInc, 3851;
+            },
+            "lower_inf" => Scalar {
+                params!(RangeAny) => UnaryFunc::RangeLowerInf, 3853;
             },
             "lpad" => Scalar {
                 params!(String, Int64) => VariadicFunc::PadLeading, 879;
@@ -1500,13 +1676,32 @@ lazy_static! {
                 params!(String) => UnaryFunc::TrimLeadingWhitespace, 881;
                 params!(String, String) => BinaryFunc::TrimLeading, 875;
             },
+            "make_date" => Scalar {
+                params!(Int32, Int32, Int32) => VariadicFunc::MakeDate, 3846;
+            },
+            "make_interval" => Scalar {
+                params!(Int32) => VariadicFunc::MakeInterval, oid::FUNC_MAKE_INTERVAL_1_OID;
+                params!(Int32, Int32) => VariadicFunc::MakeInterval, oid::FUNC_MAKE_INTERVAL_2_OID;
+                params!(Int32, Int32, Int32) => VariadicFunc::MakeInterval, oid::FUNC_MAKE_INTERVAL_3_OID;
+                params!(Int32, Int32, Int32, Int32) => VariadicFunc::MakeInterval, oid::FUNC_MAKE_INTERVAL_4_OID;
+                params!(Int32, Int32, Int32, Int32, Int32) => VariadicFunc::MakeInterval, oid::FUNC_MAKE_INTERVAL_5_OID;
+                params!(Int32, Int32, Int32, Int32, Int32, Int32) => VariadicFunc::MakeInterval, oid::FUNC_MAKE_INTERVAL_6_OID;
+                params!(Int32, Int32, Int32, Int32, Int32, Int32, Float64) => VariadicFunc::MakeInterval, 3464;
+            },
             "make_timestamp" => Scalar {
                 params!(Int64, Int64, Int64, Int64, Int64, Float64) => VariadicFunc::MakeTimestamp, 3461;
             },
+            "masklen" => Scalar {
+                params!(Inet) => UnaryFunc::InetMasklen, 697;
+            },
             "mod" => Scalar {
                 params!(DecimalAny, DecimalAny) => Operation::nullary(|_ecx| catalog_name_only!("mod")), 1728;
                 params!(Int32, Int32) => Operation::nullary(|_ecx| catalog_name_only!("mod")), 941;
                 params!(Int64, Int64) => Operation::nullary(|_ecx| catalog_name_only!("mod")), 947;
+                params!(APD{scale:None}, APD{scale:None}) => Operation::nullary(|_ecx| catalog_name_only!("mod")), 17280;
+            },
+            "network" => Scalar {
+                params!(Inet) => UnaryFunc::InetNetwork, 683;
             },
             "now" => Scalar {
                 params!() => Operation::nullary(|ecx| plan_current_timestamp(ecx, "now")), 1299;
@@ -1567,6 +1762,7 @@ lazy_static! {
             "pow" => Scalar {
                 params!(Float64, Float64) => Operation::nullary(|_ecx| catalog_name_only!("pow")), 1346;
                 params!(DecimalAny, DecimalAny) => Operation::nullary(|_ecx| catalog_name_only!("pow")), 1738;
+                params!(APD{scale:None}, APD{scale:None}) => Operation::nullary(|_ecx| catalog_name_only!("pow")), 17380;
             },
             "power" => Scalar {
                 params!(Float64, Float64) => BinaryFunc::Power, 1368;
@@ -1574,6 +1770,10 @@ lazy_static! {
                     let (_, s) = ecx.scalar_type(&lhs).unwrap_decimal_parts();
                     Ok(lhs.call_binary(rhs, BinaryFunc::PowerDecimal(s)))
                 }), 2169;
+                params!(APD{scale:None}, APD{scale:None}) => BinaryFunc::PowerAPD, 21690;
+            },
+            "range_merge" => Scalar {
+                params!(RangeAny, RangeAny) => BinaryFunc::RangeMerge, 4057;
             },
             "repeat" => Scalar {
                 params!(String, Int32) => BinaryFunc::RepeatString, 1622;
@@ -1582,6 +1782,14 @@ lazy_static! {
                 params!(String, String) => VariadicFunc::RegexpMatch, 3396;
                 params!(String, String, String) => VariadicFunc::RegexpMatch, 3397;
             },
+            "regexp_replace" => Scalar {
+                params!(String, String, String) => VariadicFunc::RegexpReplace, 2284;
+                params!(String, String, String, String) => VariadicFunc::RegexpReplace, 2285;
+            },
+            "regexp_split_to_array" => Scalar {
+                params!(String, String) => VariadicFunc::RegexpSplitToArray, 2767;
+                params!(String, String, String) => VariadicFunc::RegexpSplitToArray, 2768;
+            },
             "replace" => Scalar {
                 params!(String, String, String) => VariadicFunc::Replace, 2087;
             },
@@ -1599,11 +1807,17 @@ lazy_static! {
                     let (_, s) = ecx.scalar_type(&lhs).unwrap_decimal_parts();
                     Ok(lhs.call_binary(rhs, BinaryFunc::RoundDecimal(s)))
                 }), 1707;
+                params!(APD{scale:None}) => UnaryFunc::RoundAPD, 17080;
+                params!(APD{scale:None}, Int32) => BinaryFunc::RoundAPD, 17070;
             },
             "rtrim" => Scalar {
                 params!(String) => UnaryFunc::TrimTrailingWhitespace, 882;
                 params!(String, String) => BinaryFunc::TrimTrailing, 876;
             },
+            "set_masklen" => Scalar {
+                params!(Inet, Int32) => BinaryFunc::InetSetMasklen, 605;
+                params!(Cidr, Int32) => BinaryFunc::CidrSetMasklen, 635;
+            },
             "sin" => Scalar {
                 params!(Float64) => UnaryFunc::Sin, 1604;
             },
@@ -1615,6 +1829,7 @@ lazy_static! {
             },
             "stddev" => Scalar {
                 params!(DecimalAny) => Operation::nullary(|_ecx| catalog_name_only!("stddev")), 2159;
+                params!(APD{scale:None}) => Operation::nullary(|_ecx| catalog_name_only!("stddev")), 21590;
                 params!(Float32) => Operation::nullary(|_ecx| catalog_name_only!("stddev")), 2157;
                 params!(Float64) => Operation::nullary(|_ecx| catalog_name_only!("stddev")), 2158;
                 params!(Int32) => Operation::nullary(|_ecx| catalog_name_only!("stddev")), 2155;
@@ -1622,6 +1837,7 @@ lazy_static! {
             },
             "stddev_pop" => Scalar {
                 params!(DecimalAny) => Operation::nullary(|_ecx| catalog_name_only!("stddev_pop")), 2729;
+                params!(APD{scale:None}) => Operation::nullary(|_ecx| catalog_name_only!("stddev_pop")), 27290;
                 params!(Float32) => Operation::nullary(|_ecx| catalog_name_only!("stddev_pop")), 2727;
                 params!(Float64) => Operation::nullary(|_ecx| catalog_name_only!("stddev_pop")), 2728;
                 params!(Int32) => Operation::nullary(|_ecx| catalog_name_only!("stddev_pop")), 2725;
@@ -1629,6 +1845,7 @@ lazy_static! {
             },
             "stddev_samp" => Scalar {
                 params!(DecimalAny) => Operation::nullary(|_ecx| catalog_name_only!("stddev_samp")), 2717;
+                params!(APD{scale:None}) => Operation::nullary(|_ecx| catalog_name_only!("stddev_samp")), 27170;
                 params!(Float32) => Operation::nullary(|_ecx| catalog_name_only!("stddev_samp")), 2715;
                 params!(Float64) => Operation::nullary(|_ecx| catalog_name_only!("stddev_samp")), 2716;
                 params!(Int32) => Operation::nullary(|_ecx| catalog_name_only!("stddev_samp")), 2713;
@@ -1648,6 +1865,7 @@ lazy_static! {
                     let (_, s) = ecx.scalar_type(&e).unwrap_decimal_parts();
                     Ok(e.call_unary(UnaryFunc::SqrtDec(s)))
                 }), 1730;
+                params!(APD{scale:None}) => UnaryFunc::SqrtAPD, 17300;
             },
             "tan" => Scalar {
                 params!(Float64) => UnaryFunc::Tan, 1606;
@@ -1667,7 +1885,15 @@ lazy_static! {
             },
             "to_char" => Scalar {
                 params!(Timestamp, String) => BinaryFunc::ToCharTimestamp, 2049;
-                params!(TimestampTz, String) => BinaryFunc::ToCharTimestampTz, 1770;
+                params!(TimestampTz, String) => Operation::binary(|ecx, ts, fmt| {
+                    let tz = ecx.qcx.scx.pcx.timezone;
+                    Ok(if tz.is_utc() {
+                        ts.call_binary(fmt, BinaryFunc::ToCharTimestampTz)
+                    } else {
+                        ts.call_unary(UnaryFunc::TimezoneTimestampTz(tz))
+                            .call_binary(fmt, BinaryFunc::ToCharTimestamp)
+                    })
+                }), 1770;
             },
             // > Returns the value as json or jsonb. Arrays and composites
             // > are converted (recursively) to arrays and objects;
@@ -1679,17 +1905,42 @@ lazy_static! {
             // > valid json or jsonb value.
             //
             // https://www.postgresql.org/docs/current/functions-json.html
+            "to_date" => Scalar {
+                params!(String, String) => BinaryFunc::ToDate, 1780;
+            },
             "to_jsonb" => Scalar {
                 params!(Any) => Operation::unary(|ecx, e| Ok(typeconv::to_jsonb(ecx, e))), 3787;
             },
             "to_timestamp" => Scalar {
                 params!(Float64) => UnaryFunc::ToTimestamp, 1158;
+                params!(String, String) => Operation::binary(|ecx, s, fmt| {
+                    // Timestamps without an explicit UTC offset are
+                    // interpreted in the session's timezone.
+                    let tz = ecx.qcx.scx.pcx.timezone;
+                    Ok(s.call_binary(fmt, BinaryFunc::ToTimestampWithFormat(tz)))
+                }), 1778;
+            },
+            "tsrange" => Scalar {
+                params!(Timestamp, Timestamp) => VariadicFunc::RangeCreate { elem_type: Timestamp }, 3933;
+                params!(Timestamp, Timestamp, String) => VariadicFunc::RangeCreate { elem_type: Timestamp }, 3934;
+            },
+            "tstzrange" => Scalar {
+                params!(TimestampTz, TimestampTz) => VariadicFunc::RangeCreate { elem_type: TimestampTz }, 3937;
+                params!(TimestampTz, TimestampTz, String) => VariadicFunc::RangeCreate { elem_type: TimestampTz }, 3938;
             },
             "upper" => Scalar {
                 params!(String) => UnaryFunc::Upper, 871;
+                params!(RangeAny) => UnaryFunc::RangeUpper, 3849;
+            },
+            "upper_inc" => Scalar {
+                params!(RangeAny) => UnaryFunc::RangeUpperInc, 3852;
+            },
+            "upper_inf" => Scalar {
+                params!(RangeAny) => UnaryFunc::RangeUpperInf, 3854;
             },
             "variance" => Scalar {
                 params!(DecimalAny) => Operation::nullary(|_ecx| catalog_name_only!("variance")), 2153;
+                params!(APD{scale:None}) => Operation::nullary(|_ecx| catalog_name_only!("variance")), 21530;
                 params!(Float32) => Operation::nullary(|_ecx| catalog_name_only!("variance")), 2151;
                 params!(Float64) => Operation::nullary(|_ecx| catalog_name_only!("variance")), 2152;
                 params!(Int32) => Operation::nullary(|_ecx| catalog_name_only!("variance")), 2149;
@@ -1697,6 +1948,7 @@ lazy_static! {
             },
             "var_pop" => Scalar {
                 params!(DecimalAny) => Operation::nullary(|_ecx| catalog_name_only!("var_pop")), 2723;
+                params!(APD{scale:None}) => Operation::nullary(|_ecx| catalog_name_only!("var_pop")), 27230;
                 params!(Float32) => Operation::nullary(|_ecx| catalog_name_only!("var_pop")), 2721;
                 params!(Float64) => Operation::nullary(|_ecx| catalog_name_only!("var_pop")), 2722;
                 params!(Int32) => Operation::nullary(|_ecx| catalog_name_only!("var_pop")), 2719;
@@ -1704,6 +1956,7 @@ lazy_static! {
             },
             "var_samp" => Scalar {
                 params!(DecimalAny) => Operation::nullary(|_ecx| catalog_name_only!("var_samp")), 2646;
+                params!(APD{scale:None}) => Operation::nullary(|_ecx| catalog_name_only!("var_samp")), 26460;
                 params!(Float32) => Operation::nullary(|_ecx| catalog_name_only!("var_samp")), 2644;
                 params!(Float64) => Operation::nullary(|_ecx| catalog_name_only!("var_samp")), 2645;
                 params!(Int32) => Operation::nullary(|_ecx| catalog_name_only!("var_samp")), 2642;
@@ -1744,6 +1997,7 @@ lazy_static! {
                 params!(Float32) => AggregateFunc::MaxFloat32, 2119;
                 params!(Float64) => AggregateFunc::MaxFloat64, 2120;
                 params!(DecimalAny) => AggregateFunc::MaxDecimal, 2130;
+                params!(APD{scale:None}) => AggregateFunc::MaxAPD, 21300;
                 params!(String) => AggregateFunc::MaxString, 2129;
                 params!(Date) => AggregateFunc::MaxDate, 2122;
                 params!(Timestamp) => AggregateFunc::MaxTimestamp, 2126;
@@ -1756,6 +2010,7 @@ lazy_static! {
                 params!(Float32) => AggregateFunc::MinFloat32, 2135;
                 params!(Float64) => AggregateFunc::MinFloat64, 2136;
                 params!(DecimalAny) => AggregateFunc::MinDecimal, 2146;
+                params!(APD{scale:None}) => AggregateFunc::MinAPD, 21460;
                 params!(String) => AggregateFunc::MinString, 2145;
                 params!(Date) => AggregateFunc::MinDate, 2138;
                 params!(Timestamp) => AggregateFunc::MinTimestamp, 2142;
@@ -1792,6 +2047,9 @@ lazy_static! {
                     Ok((e, AggregateFunc::JsonbObjectAgg))
                 }), 3270;
             },
+            "range_agg" => Aggregate {
+                params!(RangeAny) => AggregateFunc::RangeAgg, oid::FUNC_RANGE_AGG_OID;
+            },
             "string_agg" => Aggregate {
                 params!(Any, String) => Operation::binary(|_ecx, _lhs, _rhs| unsupported!("string_agg")), 3538;
             },
@@ -1827,6 +2085,22 @@ lazy_static! {
                         column_names: vec![Some("generate_series".into())],
                     })
                 }), 1069;
+                params!(Timestamp, Timestamp, Interval) => Operation::variadic(move |_ecx, exprs| {
+                    Ok(TableFuncPlan {
+                        func: TableFunc::GenerateSeriesTimestamp,
+                        exprs,
+                        column_names: vec![Some("generate_series".into())],
+                    })
+                }), 938;
+                params!(TimestampTz, TimestampTz, Interval) => Operation::variadic(move |ecx, exprs| {
+                    Ok(TableFuncPlan {
+                        func: TableFunc::GenerateSeriesTimestampTz {
+                            timezone: ecx.qcx.scx.pcx.timezone,
+                        },
+                        exprs,
+                        column_names: vec![Some("generate_series".into())],
+                    })
+                }), 939;
             },
             "jsonb_array_elements" => Table {
                 params!(Jsonb) => Operation::unary(move |_ecx, jsonb| {
@@ -1873,6 +2147,57 @@ lazy_static! {
                     })
                 }), 3931;
             },
+            "jsonb_path_query" => Table {
+                params!(Jsonb, String) => Operation::variadic(move |_ecx, exprs| {
+                    plan_jsonb_path_query(exprs)
+                }), oid::FUNC_JSONB_PATH_QUERY_2_OID;
+                params!(Jsonb, String, Jsonb) => Operation::variadic(move |_ecx, exprs| {
+                    plan_jsonb_path_query(exprs)
+                }), oid::FUNC_JSONB_PATH_QUERY_3_OID;
+                params!(Jsonb, String, Jsonb, Bool) => Operation::variadic(move |_ecx, exprs| {
+                    plan_jsonb_path_query(exprs)
+                }), 4006;
+            },
+            "jsonb_to_record" => Table {
+                params!(Jsonb) => Operation::unary(move |ecx, jsonb| {
+                    Ok(TableFuncPlan {
+                        func: TableFunc::JsonbToRecord {
+                            columns: vec![],
+                            timezone: ecx.qcx.scx.pcx.timezone,
+                        },
+                        exprs: vec![jsonb],
+                        column_names: vec![],
+                    })
+                }), 3490;
+            },
+            "jsonb_to_recordset" => Table {
+                params!(Jsonb) => Operation::unary(move |ecx, jsonb| {
+                    Ok(TableFuncPlan {
+                        func: TableFunc::JsonbToRecordset {
+                            columns: vec![],
+                            timezone: ecx.qcx.scx.pcx.timezone,
+                        },
+                        exprs: vec![jsonb],
+                        column_names: vec![],
+                    })
+                }), 3491;
+            },
+            "regexp_matches" => Table {
+                params!(String, String) => Operation::variadic(move |_ecx, exprs| {
+                    plan_regexp_matches(exprs)
+                }), 2763;
+                params!(String, String, String) => Operation::variadic(move |_ecx, exprs| {
+                    plan_regexp_matches(exprs)
+                }), 2764;
+            },
+            "regexp_split_to_table" => Table {
+                params!(String, String) => Operation::variadic(move |_ecx, exprs| {
+                    plan_regexp_split_to_table(exprs)
+                }), 2765;
+                params!(String, String, String) => Operation::variadic(move |_ecx, exprs| {
+                    plan_regexp_split_to_table(exprs)
+                }), 2766;
+            },
             "encode" => Scalar {
                 params!(Bytes, String) => BinaryFunc::Encode, 1946;
             },
@@ -2048,6 +2373,7 @@ lazy_static! {
                 params!(Float32) => Operation::identity(), oid::FUNC_MZ_AVG_PROMOTION_F32_OID;
                 params!(Float64) => Operation::identity(), oid::FUNC_MZ_AVG_PROMOTION_F64_OID;
                 params!(DecimalAny) => Operation::identity(), oid::FUNC_MZ_AVG_PROMOTION_DECIMAL_OID;
+                params!(APD{scale:None}) => Operation::identity(), oid::FUNC_MZ_AVG_PROMOTION_APD_OID;
                 params!(Int32) => Operation::unary(|ecx, e| {
                       typeconv::plan_cast(
                           "internal.avg_promotion", ecx, CastContext::Explicit,
@@ -2094,6 +2420,14 @@ fn plan_current_timestamp(ecx: &ExprContext, name: &str) -> Result<HirScalarExpr
     }
 }
 
+/// Plans midnight of the current date in the session's timezone, as a
+/// timestamp.
+fn plan_current_date(ecx: &ExprContext, name: &str) -> Result<HirScalarExpr, anyhow::Error> {
+    let now = plan_current_timestamp(ecx, name)?;
+    let now = typeconv::plan_cast(name, ecx, CastContext::Explicit, now, &ScalarType::Timestamp)?;
+    Ok(now.call_unary(UnaryFunc::DateTruncTimestamp(DateTimeUnits::Day)))
+}
+
 fn mz_cluster_id(ecx: &ExprContext) -> Result<HirScalarExpr, anyhow::Error> {
     Ok(HirScalarExpr::literal(
         Datum::from(ecx.catalog().config().cluster_id),
@@ -2134,6 +2468,70 @@ fn pg_postmaster_start_time(ecx: &ExprContext) -> Result<HirScalarExpr, anyhow::
     ))
 }
 
+/// Extracts the literal pattern and flags from the arguments of a
+/// set-returning regex function, whose regex must be known at plan time.
+fn plan_regex_args(
+    name: &str,
+    mut exprs: Vec<HirScalarExpr>,
+) -> Result<(HirScalarExpr, String, String), anyhow::Error> {
+    let flags = match exprs.len() {
+        3 => match exprs.pop().unwrap().into_literal_string() {
+            None => bail!("{} requires a string literal as its third argument", name),
+            Some(flags) => flags,
+        },
+        _ => String::new(),
+    };
+    let needle = match exprs.pop().unwrap().into_literal_string() {
+        None => bail!("{} requires a string literal as its second argument", name),
+        Some(needle) => needle,
+    };
+    Ok((exprs.into_element(), needle, flags))
+}
+
+fn plan_regexp_matches(exprs: Vec<HirScalarExpr>) -> Result<TableFuncPlan, anyhow::Error> {
+    let (haystack, needle, flags) = plan_regex_args("regexp_matches", exprs)?;
+    let (regex, global) = expr::build_regex_global(&needle, &flags)?;
+    Ok(TableFuncPlan {
+        func: TableFunc::RegexpMatches {
+            regex: Regex(regex),
+            global,
+        },
+        exprs: vec![haystack],
+        column_names: vec![Some("regexp_matches".into())],
+    })
+}
+
+fn plan_regexp_split_to_table(exprs: Vec<HirScalarExpr>) -> Result<TableFuncPlan, anyhow::Error> {
+    let (haystack, needle, flags) = plan_regex_args("regexp_split_to_table", exprs)?;
+    let regex = expr::build_regex(&needle, &flags)?;
+    Ok(TableFuncPlan {
+        func: TableFunc::RegexpSplitToTable(Regex(regex)),
+        exprs: vec![haystack],
+        column_names: vec![Some("regexp_split_to_table".into())],
+    })
+}
+
+fn plan_jsonb_path_query(mut exprs: Vec<HirScalarExpr>) -> Result<TableFuncPlan, anyhow::Error> {
+    // Parse literal paths once, rather than once per row. Paths that fail to
+    // parse are left for evaluation to report.
+    let path = exprs[1]
+        .clone()
+        .into_literal_string()
+        .and_then(|path| expr::JsonPath::parse(&path).ok());
+    let func = match path {
+        Some(path) => {
+            exprs.remove(1);
+            TableFunc::JsonbPathQueryStatic(path)
+        }
+        None => TableFunc::JsonbPathQuery,
+    };
+    Ok(TableFuncPlan {
+        func,
+        exprs,
+        column_names: vec![Some("jsonb_path_query".into())],
+    })
+}
+
 fn array_to_string(
     ecx: &ExprContext,
     exprs: Vec<HirScalarExpr>,
@@ -2297,6 +2695,7 @@ lazy_static! {
                     let (lexpr, rexpr) = rescale_decimals_to_same(ecx, lhs, rhs);
                     Ok(lexpr.call_binary(rexpr, ModDecimal))
                 }), 1762;
+                params!(APD{scale:None}, APD{scale:None}) => ModAPD, 17620;
             },
 
             // ILIKE
@@ -2375,6 +2774,9 @@ lazy_static! {
                 params!(ListAny, ListAny) => ListListConcat, oid::OP_CONCAT_LIST_LIST_OID;
                 params!(ListAny, ListElementAny) => ListElementConcat, oid::OP_CONCAT_LIST_ELEMENT_OID;
                 params!(ListElementAny, ListAny) => ElementListConcat, oid::OP_CONCAT_ELEMENY_LIST_OID;
+                params!(ArrayAny, ArrayAny) => ArrayArrayConcat, 375;
+                params!(ArrayAny, ArrayElementAny) => ArrayElementConcat, 349;
+                params!(ArrayElementAny, ArrayAny) => ElementArrayConcat, 374;
             },
 
             //JSON and MAP
@@ -2394,6 +2796,9 @@ lazy_static! {
             "#>>" => Scalar {
                 params!(Jsonb, Plain(Array(Box::new(String)))) => JsonbGetPath { stringify: true }, 3206;
             },
+            "#-" => Scalar {
+                params!(Jsonb, Plain(Array(Box::new(String)))) => JsonbDeletePath, 3287;
+            },
             "@>" => Scalar {
                 params!(Jsonb, Jsonb) => JsonbContainsJsonb, 3246;
                 params!(Jsonb, String) => Operation::binary(|_ecx, lhs, rhs| {
@@ -2407,6 +2812,9 @@ lazy_static! {
                           .call_binary(rhs, JsonbContainsJsonb))
                 }), oid::OP_CONTAINS_STRING_JSONB_OID;
                 params!(MapAny, MapAny) => MapContainsMap, oid::OP_CONTAINS_MAP_MAP_OID;
+                params!(RangeAny, RangeElementAny) => RangeContainsElem, 3889;
+                params!(RangeAny, RangeAny) => RangeContainsRange, 3890;
+                params!(ArrayAny, ArrayAny) => ArrayContainsArray, 2751;
             },
             "<@" => Scalar {
                 params!(Jsonb, Jsonb) =>  Operation::binary(|_ecx, lhs, rhs| {
@@ -2428,6 +2836,37 @@ lazy_static! {
                 params!(MapAny, MapAny) => Operation::binary(|_ecx, lhs, rhs| {
                     Ok(rhs.call_binary(lhs, MapContainsMap))
                 }), oid::OP_CONTAINED_MAP_MAP_OID;
+                params!(RangeElementAny, RangeAny) => Operation::binary(|_ecx, lhs, rhs| {
+                    Ok(rhs.call_binary(lhs, RangeContainsElem))
+                }), 3891;
+                params!(RangeAny, RangeAny) => Operation::binary(|_ecx, lhs, rhs| {
+                    Ok(rhs.call_binary(lhs, RangeContainsRange))
+                }), 3892;
+                params!(ArrayAny, ArrayAny) => Operation::binary(|_ecx, lhs, rhs| {
+                    Ok(rhs.call_binary(lhs, ArrayContainsArray))
+                }), 2752;
+            },
+            "&&" => Scalar {
+                params!(RangeAny, RangeAny) => RangeOverlaps, 3888;
+                params!(ArrayAny, ArrayAny) => ArrayOverlaps, 2750;
+            },
+
+            // INET
+            "<<" => Scalar {
+                params!(Inet, Inet) => Operation::binary(|_ecx, lhs, rhs| {
+                    Ok(rhs.call_binary(lhs, InetContains))
+                }), 931;
+            },
+            "<<=" => Scalar {
+                params!(Inet, Inet) => Operation::binary(|_ecx, lhs, rhs| {
+                    Ok(rhs.call_binary(lhs, InetContainsOrEquals))
+                }), 932;
+            },
+            ">>" => Scalar {
+                params!(Inet, Inet) => InetContains, 933;
+            },
+            ">>=" => Scalar {
+                params!(Inet, Inet) => InetContainsOrEquals, 934;
             },
             "?" => Scalar {
                 params!(Jsonb, String) => JsonbContainsString, 3247;
@@ -2461,6 +2900,8 @@ lazy_static! {
                 params!(Timestamp, Timestamp) => BinaryFunc::Lt, 2062;
                 params!(TimestampTz, TimestampTz) => BinaryFunc::Lt, 1322;
                 params!(Uuid, Uuid) => BinaryFunc::Lt, 2974;
+                params!(Inet, Inet) => BinaryFunc::Lt, 1203;
+                params!(RangeAny, RangeAny) => BinaryFunc::Lt, 3884;
                 params!(Interval, Interval) => BinaryFunc::Lt, 1332;
                 params!(Bytes, Bytes) => BinaryFunc::Lt, 1957;
                 params!(String, String) => BinaryFunc::Lt, 664;
@@ -2486,6 +2927,8 @@ lazy_static! {
                 params!(Timestamp, Timestamp) => BinaryFunc::Lte, 2063;
                 params!(TimestampTz, TimestampTz) => BinaryFunc::Lte, 1323;
                 params!(Uuid, Uuid) => BinaryFunc::Lte, 2976;
+                params!(Inet, Inet) => BinaryFunc::Lte, 1204;
+                params!(RangeAny, RangeAny) => BinaryFunc::Lte, 3885;
                 params!(Interval, Interval) => BinaryFunc::Lte, 1333;
                 params!(Bytes, Bytes) => BinaryFunc::Lte, 1958;
                 params!(String, String) => BinaryFunc::Lte, 665;
@@ -2511,6 +2954,8 @@ lazy_static! {
                 params!(Timestamp, Timestamp) => BinaryFunc::Gt, 2064;
                 params!(TimestampTz, TimestampTz) => BinaryFunc::Gt, 1324;
                 params!(Uuid, Uuid) => BinaryFunc::Gt, 2975;
+                params!(Inet, Inet) => BinaryFunc::Gt, 1205;
+                params!(RangeAny, RangeAny) => BinaryFunc::Gt, 3887;
                 params!(Interval, Interval) => BinaryFunc::Gt, 1334;
                 params!(Bytes, Bytes) => BinaryFunc::Gt, 1959;
                 params!(String, String) => BinaryFunc::Gt, 666;
@@ -2536,6 +2981,8 @@ lazy_static! {
                 params!(Timestamp, Timestamp) => BinaryFunc::Gte, 2065;
                 params!(TimestampTz, TimestampTz) => BinaryFunc::Gte, 1325;
                 params!(Uuid, Uuid) => BinaryFunc::Gte, 2977;
+                params!(Inet, Inet) => BinaryFunc::Gte, 1206;
+                params!(RangeAny, RangeAny) => BinaryFunc::Gte, 3886;
                 params!(Interval, Interval) => BinaryFunc::Gte, 1335;
                 params!(Bytes, Bytes) => BinaryFunc::Gte, 1960;
                 params!(String, String) => BinaryFunc::Gte, 667;
@@ -2561,6 +3008,8 @@ lazy_static! {
                 params!(Timestamp, Timestamp) => BinaryFunc::Eq, 2060;
                 params!(TimestampTz, TimestampTz) => BinaryFunc::Eq, 1320;
                 params!(Uuid, Uuid) => BinaryFunc::Eq, 2972;
+                params!(Inet, Inet) => BinaryFunc::Eq, 1201;
+                params!(RangeAny, RangeAny) => BinaryFunc::Eq, 3882;
                 params!(Interval, Interval) => BinaryFunc::Eq, 1330;
                 params!(Bytes, Bytes) => BinaryFunc::Eq, 1955;
                 params!(String, String) => BinaryFunc::Eq, 98;
@@ -2587,6 +3036,8 @@ lazy_static! {
                 params!(Timestamp, Timestamp) => BinaryFunc::NotEq, 2061;
                 params!(TimestampTz, TimestampTz) => BinaryFunc::NotEq, 1321;
                 params!(Uuid, Uuid) => BinaryFunc::NotEq, 2973;
+                params!(Inet, Inet) => BinaryFunc::NotEq, 1202;
+                params!(RangeAny, RangeAny) => BinaryFunc::NotEq, 3883;
                 params!(Interval, Interval) => BinaryFunc::NotEq, 1331;
                 params!(Bytes, Bytes) => BinaryFunc::NotEq, 1956;
                 params!(String, String) => BinaryFunc::NotEq, 531;
@@ -18,7 +18,10 @@ use std::time::SystemTime;
 
 use anyhow::bail;
 use log::{debug, error, info, warn};
+use openssl::pkey::PKey;
+use openssl::x509::X509;
 use ore::collections::CollectionExt;
+use ore::str::StrExt;
 use rdkafka::client::ClientContext;
 use rdkafka::consumer::{BaseConsumer, Consumer, ConsumerContext};
 use rdkafka::{Offset, TopicPartitionList};
@@ -27,6 +30,7 @@ use tokio::task;
 use tokio::time::Duration;
 
 use ccsr::tls::{Certificate, Identity};
+use repr::strconv;
 use sql_parser::ast::Value;
 
 enum ValType {
@@ -170,6 +174,8 @@ pub fn extract_config(
             Config::string("sasl_kerberos_min_time_before_relogin"),
             Config::string("sasl_kerberos_principal"),
             Config::string("sasl_kerberos_service_name"),
+            Config::string("sasl_oauthbearer_config"),
+            Config::new("enable_sasl_oauthbearer_unsecure_jwt", ValType::Boolean),
             // For historical reasons, we allow `sasl_mechanisms` to be lowercase or
             // mixed case, while librdkafka requires all uppercase (e.g., `PLAIN`,
             // not `plain`).
@@ -183,22 +189,185 @@ pub fn extract_config(
     )
 }
 
+/// Validates the security options returned by [`extract_config`] before they
+/// are handed to librdkafka, which otherwise reports most misconfigurations
+/// only once it attempts to connect.
+///
+/// # Errors
+///
+/// - `security_protocol` or `sasl_mechanisms` has an unknown value.
+/// - SASL options are provided without a SASL `security_protocol`.
+/// - SSL certificate or key files do not contain PEM-encoded data, or the key
+///   does not match the certificate.
+/// - The credentials that the SASL mechanism requires are missing, or the
+///   Kerberos keytab is not a readable file.
+pub fn validate_security_config(options: &BTreeMap<String, String>) -> Result<(), anyhow::Error> {
+    let security_protocol = options
+        .get("security.protocol")
+        .map(|p| p.as_str())
+        .unwrap_or("plaintext");
+    let (uses_ssl, uses_sasl) = match security_protocol.to_lowercase().as_str() {
+        "plaintext" => (false, false),
+        "ssl" => (true, false),
+        "sasl_plaintext" => (false, true),
+        "sasl_ssl" => (true, true),
+        _ => bail!(
+            "Invalid WITH option security_protocol='{}': must be one of 'plaintext', \
+             'ssl', 'sasl_plaintext', or 'sasl_ssl'",
+            security_protocol
+        ),
+    };
+
+    if uses_ssl {
+        validate_ssl_config(options)?;
+    }
+
+    if !uses_sasl {
+        // The SSL options are also used to connect to the schema registry,
+        // but the SASL options only ever apply to Kafka.
+        if let Some(key) = options.keys().find(|k| k.starts_with("sasl.")) {
+            bail!(
+                "{} requires security_protocol='sasl_plaintext' or 'sasl_ssl'",
+                key.replace(".", "_")
+            );
+        }
+        return Ok(());
+    }
+
+    let mechanism = options
+        .get("sasl.mechanisms")
+        .map(|m| m.as_str())
+        .unwrap_or("GSSAPI");
+    match mechanism {
+        "GSSAPI" => {
+            if let Some(path) = options.get("sasl.kerberos.keytab") {
+                read_file("sasl_kerberos_keytab", path)?;
+            }
+        }
+        "PLAIN" | "SCRAM-SHA-256" | "SCRAM-SHA-512" => {
+            if !options.contains_key("sasl.username") || !options.contains_key("sasl.password") {
+                bail!(
+                    "sasl_mechanisms='{}' requires both sasl_username and sasl_password",
+                    mechanism
+                );
+            }
+        }
+        "OAUTHBEARER" => {
+            // Materialize does not install a token refresh callback, so
+            // librdkafka's unsecured JWT support is the only way to obtain a
+            // token.
+            if options
+                .get("enable.sasl.oauthbearer.unsecure.jwt")
+                .map(|v| v.as_str())
+                != Some("true")
+            {
+                bail!(
+                    "sasl_mechanisms='OAUTHBEARER' requires \
+                     enable_sasl_oauthbearer_unsecure_jwt=true"
+                );
+            }
+            match options.get("sasl.oauthbearer.config") {
+                Some(config) if config.contains("principal=") => (),
+                _ => bail!(
+                    "sasl_oauthbearer_config must specify a principal, e.g. 'principal=admin'"
+                ),
+            }
+        }
+        _ => bail!(
+            "Invalid WITH option sasl_mechanisms='{}': must be one of 'GSSAPI', 'PLAIN', \
+             'SCRAM-SHA-256', 'SCRAM-SHA-512', or 'OAUTHBEARER'",
+            mechanism
+        ),
+    }
+    Ok(())
+}
+
+/// Validates the certificates and key that librdkafka will use to establish
+/// an SSL connection.
+fn validate_ssl_config(options: &BTreeMap<String, String>) -> Result<(), anyhow::Error> {
+    // The CA location may also name a directory of certificates, which
+    // librdkafka validates itself.
+    if let Some(path) = options.get("ssl.ca.location") {
+        let metadata = match std::fs::metadata(path) {
+            Ok(metadata) => metadata,
+            Err(e) => bail!("unable to read ssl_ca_location {}: {}", path.quoted(), e),
+        };
+        if !metadata.is_dir() {
+            let pem = read_file("ssl_ca_location", path)?;
+            if X509::stack_from_pem(&pem).is_err() {
+                bail!(
+                    "ssl_ca_location {} does not contain a PEM-encoded certificate",
+                    path.quoted()
+                );
+            }
+        }
+    }
+
+    let cert_path = options.get("ssl.certificate.location");
+    let key_path = options.get("ssl.key.location");
+    let (cert_path, key_path) = match (cert_path, key_path) {
+        (Some(cert_path), Some(key_path)) => (cert_path, key_path),
+        (None, None) => return Ok(()),
+        (_, _) => bail!("ssl_certificate_location and ssl_key_location must be specified together"),
+    };
+    let cert = match X509::from_pem(&read_file("ssl_certificate_location", cert_path)?) {
+        Ok(cert) => cert,
+        Err(_) => bail!(
+            "ssl_certificate_location {} does not contain a PEM-encoded certificate",
+            cert_path.quoted()
+        ),
+    };
+    let key_pem = read_file("ssl_key_location", key_path)?;
+    let key = match options.get("ssl.key.password") {
+        Some(password) => PKey::private_key_from_pem_passphrase(&key_pem, password.as_bytes()),
+        None => PKey::private_key_from_pem(&key_pem),
+    };
+    let key = match key {
+        Ok(key) => key,
+        Err(_) => bail!(
+            "ssl_key_location {} does not contain a PEM-encoded private key, \
+             or ssl_key_password is incorrect",
+            key_path.quoted()
+        ),
+    };
+    if !cert.public_key()?.public_eq(&key) {
+        bail!("ssl_key_location does not contain the private key for ssl_certificate_location");
+    }
+    Ok(())
+}
+
+/// Reads the file at `path`, which was provided as the WITH option `option`.
+fn read_file(option: &str, path: &str) -> Result<Vec<u8>, anyhow::Error> {
+    let mut buf = Vec::new();
+    match File::open(path).and_then(|mut f| f.read_to_end(&mut buf)) {
+        Ok(_) => Ok(buf),
+        Err(e) => bail!("unable to read {} {}: {}", option, path.quoted(), e),
+    }
+}
+
 /// Create a new `rdkafka::ClientConfig` with the provided
 /// [`options`](https://github.com/edenhill/librdkafka/blob/master/CONFIGURATION.md),
 /// and test its ability to create an `rdkafka::consumer::BaseConsumer`.
 ///
-/// Expected to test the output of `extract_security_config`.
+/// Expected to test the output of `extract_security_config`. The options are
+/// first checked with [`validate_security_config`], and then the consumer
+/// fetches metadata from the broker, for `topic` if provided or for the whole
+/// cluster otherwise.
 ///
 /// # Errors
 ///
+/// - The options fail [`validate_security_config`].
 /// - `librdkafka` cannot create a BaseConsumer using the provided `options`.
 ///   For example, when using Kerberos auth, and the named principal does not
 ///   exist.
+/// - The consumer fails to connect or authenticate to the broker.
 pub async fn create_consumer(
     broker: &str,
-    topic: &str,
+    topic: Option<&str>,
     options: &BTreeMap<String, String>,
 ) -> Result<Arc<BaseConsumer<KafkaErrCheckContext>>, anyhow::Error> {
+    validate_security_config(options)?;
+
     let mut config = rdkafka::ClientConfig::new();
     config.set("bootstrap.servers", broker);
     for (k, v) in options {
@@ -209,22 +378,29 @@ pub async fn create_consumer(
         Ok(consumer) => {
             let consumer: Arc<BaseConsumer<KafkaErrCheckContext>> = Arc::new(consumer);
             let context = consumer.context().clone();
-            let topic = String::from(topic);
+            let topic = topic.map(String::from);
             // Wait for a metadata request for up to one second. This greatly
             // increases the probability that we'll see a connection error if
             // e.g. the hostname was mistyped. librdkafka doesn't expose a
             // better API for asking whether a connection succeeded or failed,
             // unfortunately.
-            task::spawn_blocking({
+            let fetch_result = task::spawn_blocking({
                 let consumer = consumer.clone();
                 move || {
-                    let _ = consumer.fetch_metadata(Some(&topic), Duration::from_secs(1));
+                    consumer
+                        .fetch_metadata(topic.as_deref(), Duration::from_secs(1))
+                        .map(|_| ())
                 }
             })
             .await?;
             let error = context.error.lock().expect("lock poisoned");
             if let Some(error) = &*error {
-                bail!("librdkafka: {}", error)
+                // Lead with the failed metadata request, if any, as it is the
+                // same error that the source or sink would hit later on.
+                match fetch_result {
+                    Ok(()) => bail!("librdkafka: {}", error),
+                    Err(e) => bail!("{:#}: librdkafka: {}", anyhow::Error::new(e), error),
+                }
             }
             Ok(consumer)
         }
@@ -253,7 +429,7 @@ pub async fn create_consumer(
 }
 
 /// Returns start offsets for the partitions of `topic` and the provided
-/// `kafka_time_offset` option.
+/// `kafka_time_offset` or `start_timestamp` option.
 ///
 /// For each partition, the returned offset is the earliest offset whose
 /// timestamp is greater than or equal to the given timestamp for the
@@ -265,40 +441,34 @@ pub async fn create_consumer(
 /// * Negative numbers will be translated to a timestamp in millis
 ///   before now (e.g. `-10` means 10 millis ago)
 ///
-/// If `kafka_time_offset` has not been configured, an empty Option is
-/// returned.
+/// The provided `start_timestamp` option must be a string containing either
+/// a timestamp (e.g. `'2021-03-01 00:00'`), which is interpreted as UTC unless
+/// it specifies a time zone, or a negative interval relative to now (e.g.
+/// `'-1 day'`).
+///
+/// If neither option has been configured, an empty Option is returned.
 pub async fn lookup_start_offsets(
     consumer: Arc<BaseConsumer<KafkaErrCheckContext>>,
     topic: &str,
     with_options: &BTreeMap<String, Value>,
 ) -> Result<Option<Vec<i64>>, anyhow::Error> {
-    let time_offset = with_options.get("kafka_time_offset");
-    if time_offset.is_none() {
-        return Ok(None);
-    } else if with_options.contains_key("start_offset") {
-        bail!("`start_offset` and `kafka_time_offset` cannot be set at the same time.")
-    }
-
-    // Validate and resolve `kafka_time_offset`.
-    let time_offset = match time_offset.unwrap() {
-        Value::Number(s) => match s.parse::<i64>() {
-            // Timestamp in millis *before* now (e.g. -10 means 10 millis ago)
-            Ok(ts) if ts < 0 => {
-                let now: i64 = SystemTime::now()
-                    .duration_since(SystemTime::UNIX_EPOCH)?
-                    .as_millis()
-                    .try_into()?;
-                let ts = now - ts.abs();
-                if ts <= 0 {
-                    bail!("Relative `kafka_time_offset` must be smaller than current system timestamp")
-                }
-                ts
-            }
-            // Timestamp in millis (e.g. 1622659034343)
-            Ok(ts) => ts,
-            _ => bail!("`kafka_time_offset` must be a number"),
-        },
-        _ => bail!("`kafka_time_offset` must be a number"),
+    let time_offset = match (
+        with_options.get("kafka_time_offset"),
+        with_options.get("start_timestamp"),
+    ) {
+        (None, None) => return Ok(None),
+        (Some(_), Some(_)) => {
+            bail!("`kafka_time_offset` and `start_timestamp` cannot be set at the same time.")
+        }
+        (Some(_), None) if with_options.contains_key("start_offset") => {
+            bail!("`start_offset` and `kafka_time_offset` cannot be set at the same time.")
+        }
+        (None, Some(_)) if with_options.contains_key("start_offset") => {
+            bail!("`start_offset` and `start_timestamp` cannot be set at the same time.")
+        }
+        (Some(time_offset), None) => parse_kafka_time_offset(time_offset)?,
+        (None, Some(Value::String(s))) => parse_start_timestamp(s)?,
+        (None, Some(_)) => bail!("`start_timestamp` must be a string"),
     };
 
     // Lookup offsets
@@ -344,6 +514,58 @@ pub async fn lookup_start_offsets(
     .await?
 }
 
+/// Validates and resolves `kafka_time_offset` to a timestamp in millis.
+fn parse_kafka_time_offset(time_offset: &Value) -> Result<i64, anyhow::Error> {
+    match time_offset {
+        Value::Number(s) => match s.parse::<i64>() {
+            // Timestamp in millis *before* now (e.g. -10 means 10 millis ago)
+            Ok(ts) if ts < 0 => {
+                let ts = now_millis()? - ts.abs();
+                if ts <= 0 {
+                    bail!("Relative `kafka_time_offset` must be smaller than current system timestamp")
+                }
+                Ok(ts)
+            }
+            // Timestamp in millis (e.g. 1622659034343)
+            Ok(ts) => Ok(ts),
+            _ => bail!("`kafka_time_offset` must be a number"),
+        },
+        _ => bail!("`kafka_time_offset` must be a number"),
+    }
+}
+
+/// Validates and resolves `start_timestamp` to a timestamp in millis.
+fn parse_start_timestamp(s: &str) -> Result<i64, anyhow::Error> {
+    if let Ok(ts) = strconv::parse_timestamptz(s) {
+        let ts = ts.timestamp_millis();
+        if ts < 0 {
+            bail!("`start_timestamp` must not be before the Unix epoch")
+        }
+        return Ok(ts);
+    }
+    match strconv::parse_interval(s) {
+        Ok(interval) if interval.as_seconds() < 0.0 => {
+            let ts = now_millis()? + (interval.as_seconds() * 1_000.0) as i64;
+            if ts <= 0 {
+                bail!("Relative `start_timestamp` must not be before the Unix epoch")
+            }
+            Ok(ts)
+        }
+        Ok(_) => bail!("Relative `start_timestamp` must be a negative interval"),
+        Err(_) => bail!(
+            "`start_timestamp` must be a timestamp or an interval: {}",
+            s.quoted()
+        ),
+    }
+}
+
+fn now_millis() -> Result<i64, anyhow::Error> {
+    Ok(SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)?
+        .as_millis()
+        .try_into()?)
+}
+
 // Kafka supports bulk lookup of watermarks, but it is not exposed in rdkafka.
 // If that ever changes, we will want to first collect all pids that have no
 // offset for a given timestamp and then do a single request (instead of doing
@@ -200,6 +200,8 @@ pub fn create_statement(
                     ref mut name,
                     args,
                     alias,
+                    column_defs,
+                    with_ordinality: _,
                 } => {
                     if let Err(e) = normalize_function_name(self.scx, name) {
                         self.err = Some(e);
@@ -217,6 +219,9 @@ pub fn create_statement(
                     if let Some(alias) = alias {
                         self.visit_table_alias_mut(alias);
                     }
+                    for column_def in column_defs {
+                        self.visit_column_def_mut(column_def);
+                    }
                 }
                 // We only need special behavior for `TableFactor::Table` and
                 // `TableFactor::Function`. Just visit the other types of table
@@ -260,6 +265,7 @@ pub fn create_statement(
             connector: _,
             with_options: _,
             format: _,
+            include_metadata: _,
             envelope: _,
             if_not_exists,
             materialized,
@@ -296,6 +302,7 @@ pub fn create_statement(
             name,
             from,
             connector: _,
+            partition_by,
             with_options: _,
             format: _,
             envelope: _,
@@ -305,6 +312,13 @@ pub fn create_statement(
         }) => {
             *name = allocate_name(name)?;
             *from = resolve_item(from)?;
+            if let Some(partition_by) = partition_by {
+                let mut normalizer = QueryNormalizer::new(scx);
+                normalizer.visit_expr_mut(partition_by);
+                if let Some(err) = normalizer.err {
+                    return Err(err.into());
+                }
+            }
             *if_not_exists = false;
         }
 
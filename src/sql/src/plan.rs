@@ -34,9 +34,13 @@ use serde::{Deserialize, Serialize};
 
 use ::expr::{GlobalId, RowSetFinishing};
 use dataflow_types::{SinkConnectorBuilder, SinkEnvelope, SourceConnector};
+use repr::adt::datetime::Timezone;
 use repr::{ColumnName, RelationDesc, Row, ScalarType, Timestamp};
 
-use crate::ast::{ExplainOptions, ExplainStage, Expr, FetchDirection, ObjectType, Raw, Statement};
+use crate::ast::{
+    ExplainOptions, ExplainStage, Expr, FetchDirection, ObjectType, Raw, Statement,
+    TransactionAccessMode, TransactionIsolationLevel,
+};
 use crate::names::{DatabaseSpecifier, FullName, SchemaName};
 
 pub(crate) mod error;
@@ -84,7 +88,8 @@ pub enum Plan {
     ShowAllVariables,
     ShowVariable(ShowVariablePlan),
     SetVariable(SetVariablePlan),
-    StartTransaction,
+    StartTransaction(StartTransactionPlan),
+    SetTransaction(SetTransactionPlan),
     CommitTransaction,
     AbortTransaction,
     Peek(PeekPlan),
@@ -98,6 +103,7 @@ pub enum Plan {
     AlterIndexSetOptions(AlterIndexSetOptionsPlan),
     AlterIndexResetOptions(AlterIndexResetOptionsPlan),
     AlterItemRename(AlterItemRenamePlan),
+    AlterSourceRefreshSchema(AlterSourceRefreshSchemaPlan),
     Declare(DeclarePlan),
     Fetch(FetchPlan),
     Close(ClosePlan),
@@ -127,6 +133,9 @@ pub struct CreateSourcePlan {
     pub source: Source,
     pub if_not_exists: bool,
     pub materialized: bool,
+    /// The name of the companion source that presents the records this source
+    /// fails to decode, if dead-letter handling is enabled.
+    pub dead_letter_name: Option<FullName>,
 }
 
 #[derive(Debug)]
@@ -209,6 +218,19 @@ pub struct ShowVariablePlan {
 pub struct SetVariablePlan {
     pub name: String,
     pub value: String,
+    pub local: bool,
+}
+
+#[derive(Debug)]
+pub struct StartTransactionPlan {
+    pub access: Option<TransactionAccessMode>,
+    pub isolation_level: Option<TransactionIsolationLevel>,
+}
+
+#[derive(Debug)]
+pub struct SetTransactionPlan {
+    pub access: Option<TransactionAccessMode>,
+    pub isolation_level: Option<TransactionIsolationLevel>,
 }
 
 #[derive(Debug)]
@@ -221,15 +243,28 @@ pub struct PeekPlan {
 
 #[derive(Debug)]
 pub struct TailPlan {
-    pub id: GlobalId,
+    pub from: TailFrom,
     pub with_snapshot: bool,
     pub ts: Option<Timestamp>,
+    pub up_to: Option<Timestamp>,
     pub copy_to: Option<CopyFormat>,
     pub emit_progress: bool,
     pub object_columns: usize,
     pub desc: RelationDesc,
 }
 
+/// The relation that a [`TailPlan`] observes.
+#[derive(Debug)]
+pub enum TailFrom {
+    /// An existing table, source, or view.
+    Id(GlobalId),
+    /// A query that must be rendered into a transient dataflow.
+    Query {
+        expr: ::expr::MirRelationExpr,
+        desc: RelationDesc,
+    },
+}
+
 #[derive(Debug)]
 pub struct SendRowsPlan {
     pub rows: Vec<Row>,
@@ -290,6 +325,16 @@ pub struct AlterItemRenamePlan {
     pub object_type: ObjectType,
 }
 
+#[derive(Debug)]
+pub struct AlterSourceRefreshSchemaPlan {
+    pub id: GlobalId,
+    /// The source, as redefined to read with the refreshed schema.
+    pub source: Source,
+    /// The position in the refreshed source of each column of the source as
+    /// it was before the refresh.
+    pub column_mapping: Vec<usize>,
+}
+
 #[derive(Debug)]
 pub struct DeclarePlan {
     pub name: String,
@@ -432,12 +477,16 @@ impl Params {
 #[derive(Ord, PartialOrd, Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash, Copy)]
 pub struct PlanContext {
     pub wall_time: DateTime<Utc>,
+    /// The session's `TimeZone`, which determines how `timestamptz` values
+    /// are converted to and from local time.
+    pub timezone: Timezone,
 }
 
 impl Default for PlanContext {
     fn default() -> PlanContext {
         PlanContext {
             wall_time: Utc::now(),
+            timezone: Timezone::UTC,
         }
     }
 }
@@ -364,6 +364,7 @@ pub enum AggregateFunc {
     MaxFloat32,
     MaxFloat64,
     MaxDecimal,
+    MaxAPD,
     MaxBool,
     MaxString,
     MaxDate,
@@ -374,6 +375,7 @@ pub enum AggregateFunc {
     MinFloat32,
     MinFloat64,
     MinDecimal,
+    MinAPD,
     MinBool,
     MinString,
     MinDate,
@@ -396,6 +398,9 @@ pub enum AggregateFunc {
     JsonbAgg,
     /// Aggregates pairs of JSON-typed `Datum`s into a JSON object.
     JsonbObjectAgg,
+    /// Merges range-typed `Datum`s into a sorted array of disjoint, non-empty
+    /// ranges. Overlapping and adjacent input ranges are combined.
+    RangeAgg,
     /// Accumulates any number of `Datum::Dummy`s into `Datum::Dummy`.
     ///
     /// Useful for removing an expensive aggregation while maintaining the shape
@@ -412,6 +417,7 @@ impl AggregateFunc {
             AggregateFunc::MaxFloat32 => expr::AggregateFunc::MaxFloat32,
             AggregateFunc::MaxFloat64 => expr::AggregateFunc::MaxFloat64,
             AggregateFunc::MaxDecimal => expr::AggregateFunc::MaxDecimal,
+            AggregateFunc::MaxAPD => expr::AggregateFunc::MaxAPD,
             AggregateFunc::MaxBool => expr::AggregateFunc::MaxBool,
             AggregateFunc::MaxString => expr::AggregateFunc::MaxString,
             AggregateFunc::MaxDate => expr::AggregateFunc::MaxDate,
@@ -422,6 +428,7 @@ impl AggregateFunc {
             AggregateFunc::MinFloat32 => expr::AggregateFunc::MinFloat32,
             AggregateFunc::MinFloat64 => expr::AggregateFunc::MinFloat64,
             AggregateFunc::MinDecimal => expr::AggregateFunc::MinDecimal,
+            AggregateFunc::MinAPD => expr::AggregateFunc::MinAPD,
             AggregateFunc::MinBool => expr::AggregateFunc::MinBool,
             AggregateFunc::MinString => expr::AggregateFunc::MinString,
             AggregateFunc::MinDate => expr::AggregateFunc::MinDate,
@@ -438,6 +445,7 @@ impl AggregateFunc {
             AggregateFunc::All => expr::AggregateFunc::All,
             AggregateFunc::JsonbAgg => expr::AggregateFunc::JsonbAgg,
             AggregateFunc::JsonbObjectAgg => expr::AggregateFunc::JsonbObjectAgg,
+            AggregateFunc::RangeAgg => expr::AggregateFunc::RangeAgg,
             AggregateFunc::Dummy => expr::AggregateFunc::Dummy,
         }
     }
@@ -465,6 +473,7 @@ impl AggregateFunc {
             AggregateFunc::All => ScalarType::Bool,
             AggregateFunc::JsonbAgg => ScalarType::Jsonb,
             AggregateFunc::JsonbObjectAgg => ScalarType::Jsonb,
+            AggregateFunc::RangeAgg => ScalarType::Array(Box::new(input_type.scalar_type)),
             AggregateFunc::SumInt32 => ScalarType::Int64,
             AggregateFunc::SumInt64 => {
                 ScalarType::Decimal(repr::adt::decimal::MAX_DECIMAL_PRECISION, 0)
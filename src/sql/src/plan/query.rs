@@ -36,7 +36,7 @@ use sql_parser::ast::display::{AstDisplay, AstFormatter};
 use sql_parser::ast::fold::Fold;
 use sql_parser::ast::visit::{self, Visit};
 use sql_parser::ast::{
-    AstInfo, Cte, DataType, Distinct, Expr, Function, FunctionArgs, Ident, InsertSource,
+    AstInfo, ColumnDef, Cte, DataType, Distinct, Expr, Function, FunctionArgs, Ident, InsertSource,
     JoinConstraint, JoinOperator, Limit, OrderByExpr, Query, Raw, RawName, Select, SelectItem,
     SetExpr, SetOperator, Statement, TableAlias, TableFactor, TableWithJoins, UnresolvedObjectName,
     Value, Values,
@@ -44,6 +44,7 @@ use sql_parser::ast::{
 
 use ::expr::{GlobalId, Id, RowSetFinishing};
 use repr::adt::apd::APD_DATUM_MAX_PRECISION;
+use repr::adt::char;
 use repr::adt::decimal::{Decimal, MAX_DECIMAL_PRECISION};
 use repr::{
     strconv, ColumnName, ColumnType, Datum, RelationDesc, RelationType, RowArena, ScalarType,
@@ -57,7 +58,7 @@ use crate::normalize;
 use crate::plan::error::PlanError;
 use crate::plan::expr::{
     AbstractColumnType, AbstractExpr, AggregateExpr, BinaryFunc, CoercibleScalarExpr, ColumnOrder,
-    ColumnRef, HirRelationExpr, HirScalarExpr, JoinKind, UnaryFunc, VariadicFunc,
+    ColumnRef, HirRelationExpr, HirScalarExpr, JoinKind, TableFunc, UnaryFunc, VariadicFunc,
 };
 use crate::plan::plan_utils;
 use crate::plan::scope::{Scope, ScopeItem, ScopeItemName};
@@ -832,6 +833,33 @@ pub fn plan_index_exprs<'a>(
     Ok((out, qcx.ids.into_iter().collect()))
 }
 
+/// Plans the `PARTITION BY` expression of a Kafka sink against the columns of
+/// the sink's key.
+///
+/// The expression is cast to `int8`, so that the sink can choose a partition
+/// by taking the result modulo the partition count of the topic.
+pub fn plan_sink_partition_expr(
+    scx: &StatementContext,
+    key_desc: &RelationDesc,
+    mut expr: Expr<Raw>,
+) -> Result<(::expr::MirScalarExpr, Vec<GlobalId>), anyhow::Error> {
+    let scope = Scope::from_source(None, key_desc.iter_names(), Some(Scope::empty(None)));
+    let mut qcx = QueryContext::root(scx, QueryLifetime::Static);
+    transform_ast::transform_expr(scx, &mut expr)?;
+    let expr = resolve_names_expr(&mut qcx, expr)?;
+    let ecx = &ExprContext {
+        qcx: &qcx,
+        name: "PARTITION BY",
+        scope: &scope,
+        relation_type: key_desc.typ(),
+        allow_aggregates: false,
+        allow_subqueries: false,
+    };
+    let expr =
+        plan_expr(ecx, &expr)?.cast_to(ecx.name, ecx, CastContext::Implicit, &ScalarType::Int64)?;
+    Ok((expr.lower_uncorrelated()?, qcx.ids.into_iter().collect()))
+}
+
 fn plan_expr_or_col_index(
     ecx: &ExprContext,
     e: &Expr<Aug>,
@@ -1719,7 +1747,13 @@ fn plan_table_factor(
             (expr, scope)
         }
 
-        TableFactor::Function { name, args, alias } => {
+        TableFactor::Function {
+            name,
+            args,
+            alias,
+            column_defs,
+            with_ordinality,
+        } => {
             let ecx = &ExprContext {
                 qcx: &qcx,
                 name: "FROM table function",
@@ -1728,7 +1762,14 @@ fn plan_table_factor(
                 allow_aggregates: false,
                 allow_subqueries: true,
             };
-            plan_table_function(ecx, &name, alias.as_ref(), args)?
+            plan_table_function(
+                ecx,
+                &name,
+                alias.as_ref(),
+                column_defs,
+                *with_ordinality,
+                args,
+            )?
         }
 
         TableFactor::Derived {
@@ -1772,6 +1813,8 @@ fn plan_table_function(
     ecx: &ExprContext,
     name: &UnresolvedObjectName,
     alias: Option<&TableAlias>,
+    column_defs: &[ColumnDef<Aug>],
+    with_ordinality: bool,
     args: &FunctionArgs<Aug>,
 ) -> Result<(HirRelationExpr, Scope), anyhow::Error> {
     if *name == UnresolvedObjectName::unqualified("values") {
@@ -1789,7 +1832,33 @@ fn plan_table_function(
         FunctionArgs::Args(args) => plan_exprs(ecx, args)?,
     };
     let name = normalize::unresolved_object_name(name.clone())?;
-    let tf = func::select_impl(ecx, FuncSpec::Func(&name), impls, args)?;
+    let mut tf = func::select_impl(ecx, FuncSpec::Func(&name), impls, args)?;
+    // Functions that return records, like `jsonb_to_record`, learn the names
+    // and types of their output columns from the column definition list.
+    match tf.func.record_columns_mut() {
+        Some(_) if column_defs.is_empty() => {
+            bail!("a column definition list is required for functions returning \"record\"")
+        }
+        Some(columns) => {
+            for column_def in column_defs {
+                let column_name = normalize::column_name(column_def.name.clone());
+                let typ = scalar_type_from_sql(ecx.qcx.scx, &column_def.data_type)?;
+                tf.column_names.push(Some(column_name.clone()));
+                columns.push((column_name, typ));
+            }
+        }
+        None if !column_defs.is_empty() => {
+            bail!("a column definition list is only allowed for functions returning \"record\"")
+        }
+        None => (),
+    }
+    if with_ordinality {
+        if !column_defs.is_empty() {
+            bail!("WITH ORDINALITY cannot be used with a column definition list");
+        }
+        tf.func = TableFunc::WithOrdinality(Box::new(tf.func));
+        tf.column_names.push(Some(ColumnName::from("ordinality")));
+    }
     let call = HirRelationExpr::CallTable {
         func: tf.func,
         exprs: tf.exprs,
@@ -1804,7 +1873,7 @@ fn plan_table_function(
         Some(ecx.qcx.outer_scope.clone()),
     );
     let mut scope = plan_table_alias(scope, alias)?;
-    if let Some(alias) = alias {
+    if let Some(alias) = alias.filter(|_| column_defs.is_empty()) {
         if let [item] = &mut *scope.items {
             // Strange special case for table functions that ouput one column.
             // If a table alias is provided but not a column alias, the column
@@ -1818,6 +1887,9 @@ fn plan_table_function(
             //     SELECT x FROM t AS x
             //
             // would return a single column of type record(int).
+            //
+            // Functions with a column definition list are exempt, as in
+            // PostgreSQL.
             item.names.push(ScopeItemName {
                 table_name: None,
                 column_name: Some(normalize::column_name(alias.name.clone())),
@@ -2447,13 +2519,13 @@ pub fn plan_expr<'a>(
                 0,
                 "subscript expression must contain at least one position"
             );
-            if positions.len() > 1 {
-                ecx.require_experimental_mode("layered/multidimensional slicing")?;
-            }
             let expr = plan_expr(ecx, expr)?.type_as_any(ecx)?;
             let ty = ecx.scalar_type(&expr);
-            match &ty {
+            let func = match &ty {
                 ScalarType::List { .. } => {
+                    if positions.len() > 1 {
+                        ecx.require_experimental_mode("layered/multidimensional slicing")?;
+                    }
                     let pos_len = positions.len();
                     let n_dims = ty.unwrap_list_n_dims();
                     if pos_len > n_dims {
@@ -2464,7 +2536,9 @@ pub fn plan_expr<'a>(
                             if n_dims == 1 { "" } else { "s" }
                         )
                     }
+                    VariadicFunc::ListSlice
                 }
+                ScalarType::Array(_) => VariadicFunc::ArraySlice,
                 ty => bail!("cannot subscript type {}", ecx.humanize_scalar_type(&ty)),
             };
 
@@ -2498,11 +2572,7 @@ pub fn plan_expr<'a>(
                 exprs.push(end);
             }
 
-            HirScalarExpr::CallVariadic {
-                func: VariadicFunc::ListSlice,
-                exprs,
-            }
-            .into()
+            HirScalarExpr::CallVariadic { func, exprs }.into()
         }
 
         // Subqueries.
@@ -3092,29 +3162,28 @@ pub fn scalar_type_from_sql(
             match scx.catalog.try_get_lossy_scalar_type_by_id(&item.id()) {
                 Some(t) => match t {
                     ScalarType::APD { .. } => {
-                        let (_, scale) =
-                            unwrap_numeric_typ_mod(typ_mod, APD_DATUM_MAX_PRECISION as u8, "apd")?;
+                        let (_, scale) = unwrap_numeric_typ_mod(
+                            typ_mod,
+                            APD_DATUM_MAX_PRECISION as u8,
+                            "numeric",
+                        )?;
                         ScalarType::APD { scale }
                     }
-                    ScalarType::Decimal(..) => {
-                        let (precision, scale) =
-                            unwrap_numeric_typ_mod(typ_mod, MAX_DECIMAL_PRECISION, "numeric")?;
-                        ScalarType::Decimal(
-                            precision.unwrap_or(MAX_DECIMAL_PRECISION),
-                            scale.unwrap_or(0),
-                        )
+                    ScalarType::Char { .. } => {
+                        let length = unwrap_char_typ_mod(&name.raw_name().item, typ_mod)?;
+                        ScalarType::Char { length }
                     }
-                    ScalarType::String => {
-                        // TODO(justin): we should look up in the catalog to see
-                        // if this type is actually a length-parameterized
-                        // string.
-                        match name.raw_name().item.as_str() {
-                            n @ "bpchar" | n @ "char" | n @ "varchar" => {
-                                validate_typ_mod(n, &typ_mod, &[("length", 1, 10_485_760)])?
-                            }
-                            _ => {}
+                    ScalarType::VarChar { .. } => {
+                        let max_length = unwrap_char_typ_mod(&name.raw_name().item, typ_mod)?;
+                        ScalarType::VarChar { max_length }
+                    }
+                    ScalarType::String if name.raw_name().item == "char" => {
+                        // As in PostgreSQL, `char` without a length is
+                        // `char(1)`.
+                        let length = unwrap_char_typ_mod("char", typ_mod)?;
+                        ScalarType::Char {
+                            length: Some(length.unwrap_or(1)),
                         }
-                        ScalarType::String
                     }
                     t => {
                         validate_typ_mod(&name.to_string(), &typ_mod, &[])?;
@@ -3131,11 +3200,10 @@ pub fn scalar_type_from_sql(
 }
 
 /// Returns the first two values provided as typ_mods as `u8`, which are
-/// appropriate values to associate with `ScalarType::Decimal`'s values,
-/// precision and scale.
+/// appropriate values to associate with a numeric type's precision and scale.
 ///
 /// Note that this function assumes you have already determined that
-/// `data_type.name` should resolve to `ScalarType::Decimal`.
+/// `data_type.name` should resolve to `ScalarType::APD`.
 pub fn unwrap_numeric_typ_mod(
     typ_mod: &[u64],
     max: u8,
@@ -3171,6 +3239,13 @@ pub fn unwrap_numeric_typ_mod(
     Ok((precision, scale))
 }
 
+/// Returns the length specified by the type modifier of a character type,
+/// i.e. `char(n)` or `varchar(n)`, if any.
+fn unwrap_char_typ_mod(name: &str, typ_mod: &[u64]) -> Result<Option<usize>, anyhow::Error> {
+    validate_typ_mod(name, typ_mod, &[("length", 1, char::MAX_LENGTH as u64)])?;
+    Ok(typ_mod.first().map(|length| *length as usize))
+}
+
 fn validate_typ_mod(
     name: &str,
     typ_mod: &[u64],
@@ -3202,7 +3277,7 @@ pub fn scalar_type_from_pg(ty: &pgrepr::Type) -> Result<ScalarType, anyhow::Erro
         pgrepr::Type::Int8 => Ok(ScalarType::Int64),
         pgrepr::Type::Float4 => Ok(ScalarType::Float32),
         pgrepr::Type::Float8 => Ok(ScalarType::Float64),
-        pgrepr::Type::Numeric => Ok(ScalarType::Decimal(0, 0)),
+        pgrepr::Type::Numeric => Ok(ScalarType::APD { scale: None }),
         pgrepr::Type::Date => Ok(ScalarType::Date),
         pgrepr::Type::Time => Ok(ScalarType::Time),
         pgrepr::Type::Timestamp => Ok(ScalarType::Timestamp),
@@ -3210,8 +3285,12 @@ pub fn scalar_type_from_pg(ty: &pgrepr::Type) -> Result<ScalarType, anyhow::Erro
         pgrepr::Type::Interval => Ok(ScalarType::Interval),
         pgrepr::Type::Bytea => Ok(ScalarType::Bytes),
         pgrepr::Type::Text => Ok(ScalarType::String),
+        pgrepr::Type::BpChar => Ok(ScalarType::Char { length: None }),
+        pgrepr::Type::VarChar => Ok(ScalarType::VarChar { max_length: None }),
         pgrepr::Type::Jsonb => Ok(ScalarType::Jsonb),
         pgrepr::Type::Uuid => Ok(ScalarType::Uuid),
+        pgrepr::Type::Inet => Ok(ScalarType::Inet),
+        pgrepr::Type::Cidr => Ok(ScalarType::Cidr),
         pgrepr::Type::Array(t) => Ok(ScalarType::Array(Box::new(scalar_type_from_pg(t)?))),
         pgrepr::Type::List(l) => Ok(ScalarType::List {
             element_type: Box::new(scalar_type_from_pg(l)?),
@@ -3221,11 +3300,13 @@ pub fn scalar_type_from_pg(ty: &pgrepr::Type) -> Result<ScalarType, anyhow::Erro
             bail!("internal error: can't convert from pg record to materialize record")
         }
         pgrepr::Type::Oid => Ok(ScalarType::Oid),
+        pgrepr::Type::Range(t) => Ok(ScalarType::Range {
+            element_type: Box::new(scalar_type_from_pg(t)?),
+        }),
         pgrepr::Type::Map { value_type } => Ok(ScalarType::Map {
             value_type: Box::new(scalar_type_from_pg(value_type)?),
             custom_oid: None,
         }),
-        pgrepr::Type::APD => Ok(ScalarType::APD { scale: None }),
     }
 }
 
@@ -119,6 +119,9 @@ pub fn describe(
         Statement::DropObjects(stmt) => ddl::describe_drop_objects(&scx, stmt)?,
         Statement::AlterObjectRename(stmt) => ddl::describe_alter_object_rename(&scx, stmt)?,
         Statement::AlterIndexOptions(stmt) => ddl::describe_alter_index_options(&scx, stmt)?,
+        Statement::AlterSourceRefreshSchema(stmt) => {
+            ddl::describe_alter_source_refresh_schema(&scx, stmt)?
+        }
 
         // `SHOW` statements.
         Statement::ShowColumns(stmt) => show::show_columns(&scx, stmt)?.describe()?,
@@ -204,6 +207,9 @@ pub fn plan(
         Statement::DropObjects(stmt) => ddl::plan_drop_objects(scx, stmt),
         Statement::AlterIndexOptions(stmt) => ddl::plan_alter_index_options(scx, stmt),
         Statement::AlterObjectRename(stmt) => ddl::plan_alter_object_rename(scx, stmt),
+        Statement::AlterSourceRefreshSchema(stmt) => {
+            ddl::plan_alter_source_refresh_schema(scx, stmt)
+        }
 
         // DML statements.
         Statement::Insert(stmt) => dml::plan_insert(scx, stmt, params),
@@ -211,7 +217,7 @@ pub fn plan(
         Statement::Delete(stmt) => dml::plan_delete(scx, stmt, params),
         Statement::Select(stmt) => dml::plan_select(scx, stmt, params, None),
         Statement::Explain(stmt) => dml::plan_explain(scx, stmt, params),
-        Statement::Tail(stmt) => dml::plan_tail(scx, stmt, None),
+        Statement::Tail(stmt) => dml::plan_tail(scx, stmt, params, None),
         Statement::Copy(stmt) => dml::plan_copy(scx, stmt),
 
         // `SHOW` statements.
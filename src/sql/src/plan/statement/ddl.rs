@@ -28,13 +28,13 @@ use reqwest::Url;
 
 use dataflow_types::{
     AvroEncoding, AvroOcfEncoding, AvroOcfSinkConnectorBuilder, Consistency, CsvEncoding,
-    DataEncoding, DebeziumMode, ExternalSourceConnector, FileSourceConnector,
+    DataEncoding, DebeziumMode, ExternalSourceConnector, FileSourceConnector, IncludedMetadata,
     KafkaSinkConnectorBuilder, KafkaSourceConnector, KinesisSourceConnector,
     PostgresSourceConnector, ProtobufEncoding, PubNubSourceConnector, RegexEncoding,
     S3SourceConnector, SinkConnectorBuilder, SinkEnvelope, SourceConnector, SourceDataEncoding,
-    SourceEnvelope,
+    SourceEnvelope, UpsertStateBackend, UpsertStyle,
 };
-use expr::{GlobalId, MirRelationExpr, TableFunc, UnaryFunc};
+use expr::{GlobalId, MirRelationExpr, MirScalarExpr, TableFunc, UnaryFunc};
 use interchange::avro::{self, DebeziumDeduplicationStrategy, Encoder};
 use interchange::envelopes;
 use ore::collections::CollectionExt;
@@ -45,13 +45,15 @@ use sql_parser::ast::CreateSourceFormat;
 
 use crate::ast::display::AstDisplay;
 use crate::ast::{
-    AlterIndexOptionsList, AlterIndexOptionsStatement, AlterObjectRenameStatement, AvroSchema,
-    ColumnOption, Compression, Connector, CreateDatabaseStatement, CreateIndexStatement,
-    CreateRoleOption, CreateRoleStatement, CreateSchemaStatement, CreateSinkStatement,
-    CreateSourceStatement, CreateTableStatement, CreateTypeAs, CreateTypeStatement,
-    CreateViewStatement, CreateViewsDefinitions, CreateViewsStatement, DataType, DbzMode,
-    DropDatabaseStatement, DropObjectsStatement, Envelope, Expr, Format, Ident, IfExistsBehavior,
-    ObjectType, Raw, SqlOption, Statement, UnresolvedObjectName, Value, ViewDefinition, WithOption,
+    AlterIndexOptionsList, AlterIndexOptionsStatement, AlterObjectRenameStatement,
+    AlterSourceRefreshSchemaStatement, AvroSchema, ColumnOption, Compression, Connector,
+    CreateDatabaseStatement, CreateIndexStatement, CreateRoleOption, CreateRoleStatement,
+    CreateSchemaStatement, CreateSinkStatement, CreateSourceStatement, CreateTableStatement,
+    CreateTypeAs, CreateTypeStatement, CreateViewStatement, CreateViewsDefinitions,
+    CreateViewsStatement, DataType, DbzMode, DropDatabaseStatement, DropObjectsStatement, Envelope,
+    Expr, Format, Ident, IfExistsBehavior, ObjectType, Raw, SourceIncludeMetadata,
+    SourceIncludeMetadataType, SqlOption, Statement, UnresolvedObjectName, Value, ViewDefinition,
+    WithOption,
 };
 use crate::catalog::{CatalogItem, CatalogItemType};
 use crate::kafka_util;
@@ -63,11 +65,11 @@ use crate::plan::query::{resolve_names_data_type, QueryLifetime};
 use crate::plan::statement::{StatementContext, StatementDesc};
 use crate::plan::{
     self, plan_utils, query, AlterIndexResetOptionsPlan, AlterIndexSetOptionsPlan,
-    AlterItemRenamePlan, AlterNoopPlan, CreateDatabasePlan, CreateIndexPlan, CreateRolePlan,
-    CreateSchemaPlan, CreateSinkPlan, CreateSourcePlan, CreateTablePlan, CreateTypePlan,
-    CreateViewPlan, CreateViewsPlan, DropDatabasePlan, DropItemsPlan, DropRolesPlan,
-    DropSchemaPlan, HirRelationExpr, Index, IndexOption, IndexOptionName, Params, Plan, Sink,
-    Source, Table, Type, TypeInner, View,
+    AlterItemRenamePlan, AlterNoopPlan, AlterSourceRefreshSchemaPlan, CreateDatabasePlan,
+    CreateIndexPlan, CreateRolePlan, CreateSchemaPlan, CreateSinkPlan, CreateSourcePlan,
+    CreateTablePlan, CreateTypePlan, CreateViewPlan, CreateViewsPlan, DropDatabasePlan,
+    DropItemsPlan, DropRolesPlan, DropSchemaPlan, HirRelationExpr, Index, IndexOption,
+    IndexOptionName, Params, Plan, Sink, Source, Table, Type, TypeInner, View,
 };
 use crate::pure::Schema;
 
@@ -389,6 +391,7 @@ pub fn plan_create_source(
         if_not_exists,
         materialized,
         format,
+        include_metadata,
     } = &stmt;
 
     let with_options_original = with_options;
@@ -396,6 +399,11 @@ pub fn plan_create_source(
 
     let mut consistency = Consistency::RealTime;
     let mut ts_frequency = scx.catalog.config().timestamp_frequency;
+    let mut dead_letter = false;
+
+    if !include_metadata.is_empty() && !matches!(connector, Connector::Kafka { .. }) {
+        unsupported!("INCLUDE for non-Kafka sources");
+    }
 
     let (external_connector, encoding) = match connector {
         Connector::Kafka { broker, topic, .. } => {
@@ -457,6 +465,26 @@ pub fn plan_create_source(
                 unsupported!("BYO source caching")
             }
 
+            if enable_caching && !include_metadata.is_empty() {
+                unsupported!("INCLUDE with source caching")
+            }
+            let include_key = include_metadata
+                .iter()
+                .any(|item| item.ty == SourceIncludeMetadataType::Key);
+            let connector_include_metadata = include_metadata
+                .iter()
+                .filter_map(|item| included_metadata(item.ty))
+                .collect();
+
+            dead_letter = match with_options.remove("dead_letter") {
+                None => false,
+                Some(Value::Boolean(b)) => b,
+                Some(_) => bail!("dead_letter must be a boolean"),
+            };
+            if enable_caching && dead_letter {
+                unsupported!("dead_letter with source caching")
+            }
+
             let connector = ExternalSourceConnector::Kafka(KafkaSourceConnector {
                 addrs: broker.parse()?,
                 topic: topic.clone(),
@@ -466,6 +494,8 @@ pub fn plan_create_source(
                 cluster_id: scx.catalog.config().cluster_id,
                 enable_caching,
                 cached_files: None,
+                include_key,
+                include_metadata: connector_include_metadata,
             });
             let encoding = get_encoding(format, envelope, with_options_original, col_names)?;
 
@@ -648,6 +678,17 @@ pub fn plan_create_source(
         }
     };
 
+    let upsert_value_key = match &envelope {
+        sql_parser::ast::Envelope::Upsert(Some(key)) => Some(key.clone()),
+        _ => None,
+    };
+
+    let upsert_state = extract_upsert_state_options(&mut with_options)?;
+    if upsert_state.is_some() && !matches!(envelope, sql_parser::ast::Envelope::Upsert(_)) {
+        bail!("upsert_state is only valid with ENVELOPE UPSERT");
+    }
+    let upsert_state = upsert_state.unwrap_or(UpsertStateBackend::Memory);
+
     // TODO (materialize#2537): cleanup format validation
     // Avro format validation is different for the Debezium envelope
     // vs the Upsert envelope.
@@ -664,7 +705,7 @@ pub fn plan_create_source(
     // a schema used to decode records.
 
     // TODO: remove bails as more support for upsert is added.
-    let envelope = match &envelope {
+    let mut envelope = match &envelope {
         sql_parser::ast::Envelope::None => SourceEnvelope::None,
         sql_parser::ast::Envelope::Debezium(mode) => {
             let is_avro = match encoding.value_ref() {
@@ -727,21 +768,32 @@ pub fn plan_create_source(
             }
             SourceEnvelope::Debezium(dedup_strat, mode)
         }
-        sql_parser::ast::Envelope::Upsert => match connector {
+        sql_parser::ast::Envelope::Upsert(None) => match connector {
             // Currently the get_encoding function rewrites Formats with either a CSR config to be a
             // KeyValueDecoding, no other formats make sense.
             //
             // TODO(bwm): move key/value canonicalization entirely into the purify step, and turn
             // this and the related code in `get_encoding` into internal errors.
             Connector::Kafka { .. } => match format {
-                CreateSourceFormat::KeyValue { .. } => SourceEnvelope::Upsert,
+                CreateSourceFormat::KeyValue { .. } => {
+                    SourceEnvelope::Upsert(UpsertStyle::Default, upsert_state)
+                }
                 CreateSourceFormat::Bare(Format::Avro(AvroSchema::CsrUrl { .. })) => {
-                    SourceEnvelope::Upsert
+                    SourceEnvelope::Upsert(UpsertStyle::Default, upsert_state)
                 }
                 _ => unsupported!(format!("upsert requires a key/value format: {:?}", format)),
             },
-            _ => unsupported!("upsert envelope for non-Kafka sources"),
+            _ => unsupported!("upsert envelope for non-Kafka sources without an explicit key"),
         },
+        sql_parser::ast::Envelope::Upsert(Some(_)) => {
+            if let CreateSourceFormat::KeyValue { .. } = format {
+                bail!(
+                    "ENVELOPE UPSERT (KEY (..)) cannot be combined with KEY FORMAT .. VALUE FORMAT"
+                );
+            }
+            // The key columns are resolved once the columns of the source are known.
+            SourceEnvelope::Upsert(UpsertStyle::ValueColumns(vec![]), upsert_state)
+        }
         sql_parser::ast::Envelope::CdcV2 => {
             scx.require_experimental_mode("ENVELOPE MATERIALIZE")?;
             if let Connector::AvroOcf { .. } = connector {
@@ -757,7 +809,11 @@ pub fn plan_create_source(
         }
     };
 
-    if matches!(envelope, SourceEnvelope::Upsert) {
+    if dead_letter && !matches!(envelope, SourceEnvelope::None) {
+        unsupported!("dead_letter with ENVELOPE other than NONE");
+    }
+
+    if matches!(envelope, SourceEnvelope::Upsert(UpsertStyle::Default, _)) {
         match &encoding {
             SourceDataEncoding::Single(_) => {
                 unsupported!("upsert envelopes must have a key")
@@ -826,9 +882,52 @@ pub fn plan_create_source(
         }
     }
 
+    if let Some(key) = upsert_value_key {
+        let key = key
+            .into_iter()
+            .map(normalize::column_name)
+            .collect::<Vec<_>>();
+        let mut uniq = HashSet::new();
+        for col in key.iter() {
+            if !uniq.insert(col) {
+                bail!("Repeated column name in upsert key: {}", col);
+            }
+        }
+        let key_indices = key
+            .iter()
+            .map(|col| -> anyhow::Result<usize> {
+                let name_idx = bare_desc
+                    .get_by_name(col)
+                    .map(|(idx, _type)| idx)
+                    .ok_or_else(|| anyhow!("No such column in upsert key: {}", col))?;
+                if bare_desc.get_unambiguous_name(name_idx).is_none() {
+                    bail!("Ambiguous column in upsert key: {}", col);
+                }
+                Ok(name_idx)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if !ignore_source_keys {
+            bare_desc = bare_desc.with_key(key_indices.clone());
+        }
+        if let SourceEnvelope::Upsert(style, _) = &mut envelope {
+            *style = UpsertStyle::ValueColumns(key_indices);
+        }
+    }
+
+    if !include_metadata.is_empty() {
+        bare_desc = plan_include_metadata(bare_desc, &encoding, &envelope, include_metadata)?;
+    }
+
     let if_not_exists = *if_not_exists;
     let materialized = *materialized;
     let name = scx.allocate_name(normalize::unresolved_object_name(name.clone())?);
+    let dead_letter_name = if dead_letter {
+        let mut dead_letter_name = name.clone();
+        dead_letter_name.item += "_errors";
+        Some(dead_letter_name)
+    } else {
+        None
+    };
     let create_sql = normalize::create_statement(&scx, Statement::CreateSource(stmt))?;
 
     let (expr, column_names) = plan_source_envelope(&bare_desc, &envelope, post_transform_key)?;
@@ -858,9 +957,76 @@ pub fn plan_create_source(
         source,
         if_not_exists,
         materialized,
+        dead_letter_name,
     }))
 }
 
+/// Adds the columns requested by `INCLUDE` to the description of a source:
+/// the decoded key columns, if requested, are prepended, and the remaining
+/// metadata columns are appended in the order in which they were specified.
+fn plan_include_metadata(
+    bare_desc: RelationDesc,
+    encoding: &SourceDataEncoding,
+    envelope: &SourceEnvelope,
+    include_metadata: &[SourceIncludeMetadata],
+) -> Result<RelationDesc, anyhow::Error> {
+    if !matches!(envelope, SourceEnvelope::None) {
+        unsupported!("INCLUDE with ENVELOPE other than NONE");
+    }
+
+    let mut seen = HashSet::new();
+    let mut key_desc = RelationDesc::empty();
+    let mut metadata_desc = RelationDesc::empty();
+    for item in include_metadata {
+        if !seen.insert(item.ty) {
+            bail!("INCLUDE {} specified more than once", item.ty);
+        }
+        let alias = item.alias.clone().map(normalize::column_name);
+        match included_metadata(item.ty) {
+            None => {
+                let key = match encoding {
+                    SourceDataEncoding::KeyValue { key, .. } => key,
+                    SourceDataEncoding::Single(_) => {
+                        bail!("INCLUDE KEY requires specifying KEY FORMAT .. VALUE FORMAT")
+                    }
+                };
+                // Messages without a key produce NULLs in the key columns.
+                let desc = key.key_desc()?;
+                key_desc = RelationDesc::from_names_and_types(
+                    desc.iter()
+                        .map(|(name, typ)| (name.cloned(), typ.clone().nullable(true))),
+                );
+                if let Some(alias) = alias {
+                    if key_desc.arity() != 1 {
+                        bail!(
+                            "INCLUDE KEY AS requires a single-column key, but the key has {} columns",
+                            key_desc.arity()
+                        );
+                    }
+                    key_desc = key_desc.with_names(iter::once(Some(alias)));
+                }
+            }
+            Some(metadata) => {
+                let name = alias.unwrap_or_else(|| metadata.default_name().into());
+                metadata_desc = metadata_desc.with_named_column(name, metadata.column_type());
+            }
+        }
+    }
+    Ok(key_desc.concat(bare_desc).concat(metadata_desc))
+}
+
+/// Maps an `INCLUDE` item onto the connector metadata it requests. The key is
+/// not connector metadata, as it is produced by the decoding step.
+fn included_metadata(ty: SourceIncludeMetadataType) -> Option<IncludedMetadata> {
+    match ty {
+        SourceIncludeMetadataType::Key => None,
+        SourceIncludeMetadataType::Partition => Some(IncludedMetadata::Partition),
+        SourceIncludeMetadataType::Offset => Some(IncludedMetadata::Offset),
+        SourceIncludeMetadataType::Timestamp => Some(IncludedMetadata::Timestamp),
+        SourceIncludeMetadataType::Headers => Some(IncludedMetadata::Headers),
+    }
+}
+
 fn get_encoding<T: sql_parser::ast::AstInfo>(
     format: &CreateSourceFormat<Raw>,
     envelope: &Envelope,
@@ -885,7 +1051,7 @@ fn get_encoding<T: sql_parser::ast::AstInfo>(
 
     let requires_keyvalue = matches!(
         envelope,
-        Envelope::Debezium(DbzMode::Upsert) | Envelope::Upsert
+        Envelope::Debezium(DbzMode::Upsert) | Envelope::Upsert(None)
     );
     let is_keyvalue = matches!(encoding, SourceDataEncoding::KeyValue { .. });
     if requires_keyvalue && !is_keyvalue {
@@ -1159,6 +1325,8 @@ fn kafka_sink_builder(
     value_desc: RelationDesc,
     topic_suffix_nonce: String,
     root_dependencies: &[&dyn CatalogItem],
+    partition_by: Option<MirScalarExpr>,
+    envelope: SinkEnvelope,
 ) -> Result<SinkConnectorBuilder, anyhow::Error> {
     let (schema_registry_url, ccsr_with_options) = match format {
         Some(Format::Avro(AvroSchema::CsrUrl {
@@ -1274,6 +1442,9 @@ fn kafka_sink_builder(
         topic_suffix_nonce,
         partition_count,
         replication_factor,
+        // Upsert sinks only need to retain the latest record for each key.
+        compact: envelope == SinkEnvelope::Upsert,
+        partition_by,
         fuel: 10000,
         consistency_value_schema,
         config_options,
@@ -1326,6 +1497,7 @@ pub fn plan_create_sink(
         name,
         from,
         connector,
+        partition_by,
         with_options,
         format,
         envelope,
@@ -1336,7 +1508,10 @@ pub fn plan_create_sink(
 
     let envelope = match envelope {
         None | Some(Envelope::Debezium(sql_parser::ast::DbzMode::Plain)) => SinkEnvelope::Debezium,
-        Some(Envelope::Upsert) => SinkEnvelope::Upsert,
+        Some(Envelope::Upsert(None)) => SinkEnvelope::Upsert,
+        Some(Envelope::Upsert(Some(_))) => {
+            bail!("ENVELOPE UPSERT (KEY (..)) is not supported for sinks; specify the KEY of the sink connector instead")
+        }
         Some(Envelope::CdcV2) => unsupported!("CDCv2 sinks"),
         Some(Envelope::Debezium(sql_parser::ast::DbzMode::Upsert)) => {
             unsupported!("UPSERT doesn't make sense for sinks")
@@ -1415,6 +1590,20 @@ pub fn plan_create_sink(
         return Err(PlanError::UpsertSinkWithoutKey.into());
     }
 
+    let (partition_by, partition_by_depends_on) = match partition_by {
+        None => (None, vec![]),
+        Some(expr) => {
+            if !matches!(connector, Connector::Kafka { .. }) {
+                bail!("PARTITION BY is only supported for Kafka sinks");
+            }
+            let (key_desc, _indices) = key_desc_and_indices
+                .as_ref()
+                .ok_or_else(|| anyhow!("PARTITION BY requires the sink to specify a KEY"))?;
+            let (expr, depends_on) = query::plan_sink_partition_expr(scx, key_desc, expr)?;
+            (Some(expr), depends_on)
+        }
+    };
+
     let value_desc = match envelope {
         SinkEnvelope::Debezium => envelopes::dbz_desc(desc.clone()),
         SinkEnvelope::Upsert => desc.clone(),
@@ -1431,6 +1620,7 @@ pub fn plan_create_sink(
     depends_on.extend(from.uses());
 
     let root_user_dependencies = get_root_dependencies(scx, &depends_on);
+    depends_on.extend(partition_by_depends_on);
 
     let connector_builder = match connector {
         Connector::File { .. } => unsupported!("file sinks"),
@@ -1445,6 +1635,8 @@ pub fn plan_create_sink(
             value_desc,
             suffix_nonce,
             &root_user_dependencies,
+            partition_by,
+            envelope,
         )?,
         Connector::Kinesis { .. } => unsupported!("Kinesis sinks"),
         Connector::AvroOcf { path } => {
@@ -1754,6 +1946,42 @@ pub fn plan_create_type(
     }))
 }
 
+/// The number of values a disk-spilling upsert source holds in memory, unless
+/// `upsert_state_memory_keys` says otherwise.
+const DEFAULT_UPSERT_STATE_MEMORY_KEYS: usize = 100_000;
+
+/// Extracts the `upsert_state` and `upsert_state_memory_keys` options, which
+/// select where an upsert source keeps the latest value of each key. Returns
+/// `None` if neither option is present.
+fn extract_upsert_state_options(
+    with_options: &mut BTreeMap<String, Value>,
+) -> Result<Option<UpsertStateBackend>, anyhow::Error> {
+    let memory_keys = match with_options.remove("upsert_state_memory_keys") {
+        None => None,
+        Some(Value::Number(n)) => match n.parse::<usize>() {
+            Ok(n) => Some(n),
+            _ => bail!("upsert_state_memory_keys must be a nonnegative integer"),
+        },
+        Some(_) => bail!("upsert_state_memory_keys must be a nonnegative integer"),
+    };
+    match with_options.remove("upsert_state") {
+        None if memory_keys.is_some() => {
+            bail!("upsert_state_memory_keys requires upsert_state = 'disk'")
+        }
+        None => Ok(None),
+        Some(Value::String(s)) if s == "memory" => {
+            if memory_keys.is_some() {
+                bail!("upsert_state_memory_keys requires upsert_state = 'disk'");
+            }
+            Ok(Some(UpsertStateBackend::Memory))
+        }
+        Some(Value::String(s)) if s == "disk" => Ok(Some(UpsertStateBackend::Disk {
+            memory_keys: memory_keys.unwrap_or(DEFAULT_UPSERT_STATE_MEMORY_KEYS),
+        })),
+        Some(_) => bail!("upsert_state must be 'memory' or 'disk'"),
+    }
+}
+
 fn extract_timestamp_frequency_option(
     default: Duration,
     with_options: &mut BTreeMap<String, Value>,
@@ -1982,8 +2210,25 @@ pub fn plan_drop_item(
     if object_type != catalog_entry.item_type() {
         bail!("{} is not of type {}", catalog_entry.name(), object_type);
     }
+    if let Ok(SourceConnector::DeadLetters(source_id)) = catalog_entry.source_connector() {
+        bail!(
+            "cannot drop {}: it is dropped along with source {}",
+            catalog_entry.name(),
+            scx.catalog.get_item_by_id(source_id).name()
+        );
+    }
     if !cascade {
+        // A source's dead-letter companion is dropped along with it, so it is
+        // only the companion's dependents that prevent the source's drop.
+        let mut used_by = vec![];
         for id in catalog_entry.used_by() {
+            let dep = scx.catalog.get_item_by_id(id);
+            match dep.source_connector() {
+                Ok(SourceConnector::DeadLetters(_)) => used_by.extend(dep.used_by()),
+                _ => used_by.push(*id),
+            }
+        }
+        for id in &used_by {
             let dep = scx.catalog.get_item_by_id(id);
             match object_type {
                 ObjectType::Type => bail!(
@@ -2137,3 +2382,115 @@ pub fn plan_alter_object_rename(
         object_type,
     }))
 }
+
+pub fn describe_alter_source_refresh_schema(
+    _: &StatementContext,
+    _: AlterSourceRefreshSchemaStatement,
+) -> Result<StatementDesc, anyhow::Error> {
+    Ok(StatementDesc::new(None))
+}
+
+pub fn plan_alter_source_refresh_schema(
+    scx: &StatementContext,
+    AlterSourceRefreshSchemaStatement {
+        source_name,
+        if_exists,
+        seed,
+    }: AlterSourceRefreshSchemaStatement,
+) -> Result<Plan, anyhow::Error> {
+    let entry = match scx.resolve_item(source_name.clone()) {
+        Ok(entry) => entry,
+        Err(_) if if_exists => {
+            return Ok(Plan::AlterNoop(AlterNoopPlan {
+                object_type: ObjectType::Source,
+            }));
+        }
+        Err(err) => return Err(err.into()),
+    };
+    if entry.item_type() != CatalogItemType::Source {
+        bail!("{} is a {} not a source", source_name, entry.item_type())
+    }
+    let current_schema = match entry.source_connector()? {
+        SourceConnector::External { encoding, .. } => match encoding.value_ref() {
+            DataEncoding::Avro(AvroEncoding {
+                schema,
+                schema_registry_config: Some(_),
+                ..
+            }) => Some(schema),
+            _ => None,
+        },
+        SourceConnector::Local | SourceConnector::DeadLetters(_) => None,
+    };
+    let (current_schema, seed) = match (current_schema, seed) {
+        (Some(current_schema), Some(seed)) => (current_schema, seed),
+        _ => bail!(
+            "ALTER SOURCE ... REFRESH SCHEMA requires a Kafka source with \
+             FORMAT AVRO USING CONFLUENT SCHEMA REGISTRY"
+        ),
+    };
+    let value_schema = avro::evolve_reader_schema(current_schema, &seed.value_schema)?;
+    if serde_json::from_str::<serde_json::Value>(&value_schema)?
+        == serde_json::from_str::<serde_json::Value>(current_schema)?
+    {
+        return Ok(Plan::AlterNoop(AlterNoopPlan {
+            object_type: ObjectType::Source,
+        }));
+    }
+
+    // Redefine the source as though it had been created with the refreshed
+    // schema.
+    let mut stmt = match crate::parse::parse(entry.create_sql())?.into_element() {
+        Statement::CreateSource(stmt) => stmt,
+        _ => unreachable!("source definitions are CREATE SOURCE statements"),
+    };
+    let formats = match &mut stmt.format {
+        CreateSourceFormat::None => vec![],
+        CreateSourceFormat::Bare(format) => vec![format],
+        CreateSourceFormat::KeyValue { key, value } => vec![key, value],
+    };
+    for format in formats {
+        if let Format::Avro(AvroSchema::CsrUrl {
+            seed: Some(current_seed),
+            ..
+        }) = format
+        {
+            if seed.key_schema.is_some() && seed.key_schema != current_seed.key_schema {
+                bail!("ALTER SOURCE ... REFRESH SCHEMA cannot change the key schema of a source");
+            }
+            current_seed.value_schema = value_schema.clone();
+        }
+    }
+    let source = match plan_create_source(scx, stmt)? {
+        Plan::CreateSource(CreateSourcePlan { source, .. }) => source,
+        _ => unreachable!("plan_create_source always returns a CreateSource plan"),
+    };
+
+    // Each existing column must survive the refresh, with its type intact, so
+    // that the views and indexes that depend on the source can continue to
+    // refer to it.
+    let old_desc = entry.desc()?;
+    let new_desc = RelationDesc::new(source.expr.typ(), source.column_names.clone());
+    let mut new_columns = new_desc.iter().enumerate();
+    let mut column_mapping = Vec::with_capacity(old_desc.arity());
+    for (name, typ) in old_desc.iter() {
+        let name = name.map(|n| n.as_str()).unwrap_or("?column?");
+        match new_columns.find(|(_, (new_name, _))| new_name.map(|n| n.as_str()) == Some(name)) {
+            Some((i, (_, new_typ))) if new_typ == typ => column_mapping.push(i),
+            Some((_, (_, new_typ))) => bail!(
+                "refreshed schema changes the type of column {} from {} to {}",
+                name.quoted(),
+                scx.humanize_column_type(typ),
+                scx.humanize_column_type(new_typ)
+            ),
+            None => bail!("refreshed schema removes column {}", name.quoted()),
+        }
+    }
+
+    Ok(Plan::AlterSourceRefreshSchema(
+        AlterSourceRefreshSchemaPlan {
+            id: entry.id(),
+            source,
+            column_mapping,
+        },
+    ))
+}
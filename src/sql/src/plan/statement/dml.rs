@@ -24,7 +24,7 @@ use repr::{RelationDesc, ScalarType};
 use crate::ast::{
     CopyDirection, CopyRelation, CopyStatement, CopyTarget, CreateViewStatement, DeleteStatement,
     ExplainStage, ExplainStatement, Explainee, Ident, InsertStatement, Query, Raw, SelectStatement,
-    Statement, TailStatement, UnresolvedObjectName, UpdateStatement, ViewDefinition,
+    Statement, TailRelation, TailStatement, UnresolvedObjectName, UpdateStatement, ViewDefinition,
 };
 use crate::catalog::CatalogItemType;
 use crate::plan::query;
@@ -32,7 +32,7 @@ use crate::plan::query::QueryLifetime;
 use crate::plan::statement::{StatementContext, StatementDesc};
 use crate::plan::{
     CopyFormat, CopyFromPlan, CopyParams, ExplainPlan, InsertPlan, Params, PeekPlan, PeekWhen,
-    Plan, TailPlan,
+    Plan, TailFrom, TailPlan,
 };
 
 // TODO(benesch): currently, describing a `SELECT` or `INSERT` query
@@ -257,9 +257,16 @@ with_options! {
 
 pub fn describe_tail(
     scx: &StatementContext,
-    TailStatement { name, options, .. }: TailStatement<Raw>,
+    TailStatement {
+        relation, options, ..
+    }: TailStatement<Raw>,
 ) -> Result<StatementDesc, anyhow::Error> {
-    let sql_object = scx.resolve_item(name)?;
+    let relation_desc = match relation {
+        TailRelation::Name(name) => scx.resolve_item(name)?.desc()?.clone(),
+        TailRelation::Query(query) => {
+            query::plan_root_query(scx, query, QueryLifetime::Static)?.desc
+        }
+    };
     let options = TailOptions::try_from(options)?;
     let progress = options.progress.unwrap_or(false);
     const MAX_U64_DIGITS: u8 = 20;
@@ -271,7 +278,7 @@ pub fn describe_tail(
         desc = desc.with_named_column("progressed", ScalarType::Bool.nullable(false));
     }
     desc = desc.with_named_column("diff", ScalarType::Int64.nullable(true));
-    for (name, ty) in sql_object.desc()?.iter() {
+    for (name, ty) in relation_desc.iter() {
         let mut ty = ty.clone();
         if progress {
             ty.nullable = true;
@@ -284,38 +291,67 @@ pub fn describe_tail(
 pub fn plan_tail(
     scx: &StatementContext,
     TailStatement {
-        name,
+        relation,
         options,
         as_of,
+        up_to,
     }: TailStatement<Raw>,
+    params: &Params,
     copy_to: Option<CopyFormat>,
 ) -> Result<Plan, anyhow::Error> {
-    let entry = scx.resolve_item(name)?;
-    let ts = as_of.map(|e| query::eval_as_of(scx, e)).transpose()?;
-    let options = TailOptions::try_from(options)?;
-    let desc = entry.desc()?.clone();
-
-    match entry.item_type() {
-        CatalogItemType::Table | CatalogItemType::Source | CatalogItemType::View => {
-            Ok(Plan::Tail(TailPlan {
-                id: entry.id(),
-                ts,
-                with_snapshot: options.snapshot.unwrap_or(true),
-                copy_to,
-                emit_progress: options.progress.unwrap_or(false),
-                object_columns: entry.desc()?.arity(),
+    let (from, desc) = match relation {
+        TailRelation::Name(name) => {
+            let entry = scx.resolve_item(name)?;
+            match entry.item_type() {
+                CatalogItemType::Table | CatalogItemType::Source | CatalogItemType::View => {
+                    (TailFrom::Id(entry.id()), entry.desc()?.clone())
+                }
+                CatalogItemType::Func
+                | CatalogItemType::Index
+                | CatalogItemType::Sink
+                | CatalogItemType::Type => bail!(
+                    "'{}' cannot be tailed because it is a {}",
+                    entry.name(),
+                    entry.item_type(),
+                ),
+            }
+        }
+        TailRelation::Query(query) => {
+            let query::PlannedQuery {
+                mut expr,
                 desc,
-            }))
+                finishing,
+                ..
+            } = query::plan_root_query(scx, query, QueryLifetime::Static)?;
+            expr.bind_parameters(&params)?;
+            // As with views, the finishing is applied to the dataflow itself,
+            // as a tail has no single point at which it could be applied.
+            expr.finish(finishing);
+            let from = TailFrom::Query {
+                expr: expr.lower(),
+                desc: desc.clone(),
+            };
+            (from, desc)
+        }
+    };
+    let ts = as_of.map(|e| query::eval_as_of(scx, e)).transpose()?;
+    let up_to = up_to.map(|e| query::eval_as_of(scx, e)).transpose()?;
+    if let (Some(ts), Some(up_to)) = (ts, up_to) {
+        if up_to < ts {
+            bail!("UP TO ({}) must not be less than AS OF ({})", up_to, ts);
         }
-        CatalogItemType::Func
-        | CatalogItemType::Index
-        | CatalogItemType::Sink
-        | CatalogItemType::Type => bail!(
-            "'{}' cannot be tailed because it is a {}",
-            entry.name(),
-            entry.item_type(),
-        ),
     }
+    let options = TailOptions::try_from(options)?;
+    Ok(Plan::Tail(TailPlan {
+        from,
+        ts,
+        up_to,
+        with_snapshot: options.snapshot.unwrap_or(true),
+        copy_to,
+        emit_progress: options.progress.unwrap_or(false),
+        object_columns: desc.arity(),
+        desc,
+    }))
 }
 
 pub fn describe_table(
@@ -401,7 +437,12 @@ pub fn plan_copy(
                 &Params::empty(),
                 Some(copy_params.format),
             )?),
-            CopyRelation::Tail(stmt) => Ok(plan_tail(scx, stmt, Some(copy_params.format))?),
+            CopyRelation::Tail(stmt) => Ok(plan_tail(
+                scx,
+                stmt,
+                &Params::empty(),
+                Some(copy_params.format),
+            )?),
         },
         (CopyDirection::From, CopyTarget::Stdin) => match relation {
             CopyRelation::Table { name, columns } => {
@@ -14,9 +14,10 @@
 
 use crate::ast::{
     CommitStatement, RollbackStatement, SetTransactionStatement, StartTransactionStatement,
+    TransactionAccessMode, TransactionIsolationLevel, TransactionMode,
 };
 use crate::plan::statement::{StatementContext, StatementDesc};
-use crate::plan::Plan;
+use crate::plan::{Plan, SetTransactionPlan, StartTransactionPlan};
 
 pub fn describe_start_transaction(
     _: &StatementContext,
@@ -27,23 +28,50 @@ pub fn describe_start_transaction(
 
 pub fn plan_start_transaction(
     _: &StatementContext,
-    _: StartTransactionStatement,
+    StartTransactionStatement { modes }: StartTransactionStatement,
 ) -> Result<Plan, anyhow::Error> {
-    Ok(Plan::StartTransaction)
+    let (access, isolation_level) = plan_transaction_modes(modes);
+    Ok(Plan::StartTransaction(StartTransactionPlan {
+        access,
+        isolation_level,
+    }))
 }
 
 pub fn describe_set_transaction(
     _: &StatementContext,
     _: SetTransactionStatement,
 ) -> Result<StatementDesc, anyhow::Error> {
-    unsupported!("SET TRANSACTION")
+    Ok(StatementDesc::new(None))
 }
 
 pub fn plan_set_transaction(
     _: &StatementContext,
-    _: SetTransactionStatement,
+    SetTransactionStatement { modes }: SetTransactionStatement,
 ) -> Result<Plan, anyhow::Error> {
-    unsupported!("SET TRANSACTION")
+    let (access, isolation_level) = plan_transaction_modes(modes);
+    Ok(Plan::SetTransaction(SetTransactionPlan {
+        access,
+        isolation_level,
+    }))
+}
+
+/// Extracts the access mode and isolation level from a list of transaction
+/// modes. If a mode is specified more than once, the last occurrence wins.
+fn plan_transaction_modes(
+    modes: Vec<TransactionMode>,
+) -> (
+    Option<TransactionAccessMode>,
+    Option<TransactionIsolationLevel>,
+) {
+    let mut access = None;
+    let mut isolation_level = None;
+    for mode in modes {
+        match mode {
+            TransactionMode::AccessMode(mode) => access = Some(mode),
+            TransactionMode::IsolationLevel(level) => isolation_level = Some(level),
+        }
+    }
+    (access, isolation_level)
 }
 
 pub fn describe_rollback(
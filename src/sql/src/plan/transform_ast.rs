@@ -389,6 +389,8 @@ impl Desugarer {
                                 columns: vec![binding.clone()],
                                 strict: true,
                             }),
+                            column_defs: vec![],
+                            with_ordinality: false,
                         },
                         joins: vec![],
                     })
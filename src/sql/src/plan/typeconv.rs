@@ -127,6 +127,10 @@ lazy_static! {
                 let (_, s) = to_type.unwrap_decimal_parts();
                 Some(move |e: HirScalarExpr| rescale_decimal(e.call_unary(CastInt32ToDecimal), 0, s))
             }),
+            (Int32, APD) => Implicit: CastTemplate::new(|_ecx, _ccx, _from_type, to_type| {
+                let s = to_type.unwrap_apd_scale();
+                Some(move |e: HirScalarExpr| e.call_unary(CastInt32ToAPD(s)))
+            }),
             (Int32, String) => Assignment: CastInt32ToString,
 
             // INT64
@@ -136,6 +140,10 @@ lazy_static! {
                 let (_, s) = to_type.unwrap_decimal_parts();
                 Some(move |e: HirScalarExpr| rescale_decimal(e.call_unary(CastInt64ToDecimal), 0, s))
             }),
+            (Int64, APD) => Implicit: CastTemplate::new(|_ecx, _ccx, _from_type, to_type| {
+                let s = to_type.unwrap_apd_scale();
+                Some(move |e: HirScalarExpr| e.call_unary(CastInt64ToAPD(s)))
+            }),
             (Int64, Float32) => Implicit: CastInt64ToFloat32,
             (Int64, Float64) => Implicit: CastInt64ToFloat64,
             (Int64, String) => Assignment: CastInt64ToString,
@@ -152,6 +160,10 @@ lazy_static! {
                 let (_, s) = to_type.unwrap_decimal_parts();
                 Some(move |e: HirScalarExpr| e.call_unary(CastFloat32ToDecimal(s)))
             }),
+            (Float32, APD) => Assignment: CastTemplate::new(|_ecx, _ccx, _from_type, to_type| {
+                let s = to_type.unwrap_apd_scale();
+                Some(move |e: HirScalarExpr| e.call_unary(CastFloat32ToAPD(s)))
+            }),
             (Float32, String) => Assignment: CastFloat32ToString,
 
             // FLOAT64
@@ -162,6 +174,10 @@ lazy_static! {
                 let (_, s) = to_type.unwrap_decimal_parts();
                 Some(move |e: HirScalarExpr| e.call_unary(CastFloat64ToDecimal(s)))
             }),
+            (Float64, APD) => Assignment: CastTemplate::new(|_ecx, _ccx, _from_type, to_type| {
+                let s = to_type.unwrap_apd_scale();
+                Some(move |e: HirScalarExpr| e.call_unary(CastFloat64ToAPD(s)))
+            }),
             (Float64, String) => Assignment: CastFloat64ToString,
 
             // DECIMAL
@@ -193,6 +209,11 @@ lazy_static! {
                 let (_, t) = to_type.unwrap_decimal_parts();
                 Some(move |e: HirScalarExpr| rescale_decimal(e, f, t))
             }),
+            (Decimal, APD) => Implicit: CastTemplate::new(|_ecx, _ccx, from_type, to_type| {
+                let (_, from_scale) = from_type.unwrap_decimal_parts();
+                let to_scale = to_type.unwrap_apd_scale();
+                Some(move |e: HirScalarExpr| e.call_unary(CastDecimalToAPD { from_scale, to_scale }))
+            }),
             (Decimal, String) => Assignment: CastTemplate::new(|_ecx, _ccx, from_type, _to_type| {
                 let (_, s) = from_type.unwrap_decimal_parts();
                 Some(move |e: HirScalarExpr| e.call_unary(CastDecimalToString(s)))
@@ -200,7 +221,14 @@ lazy_static! {
 
             // DATE
             (Date, Timestamp) => Implicit: CastDateToTimestamp,
-            (Date, TimestampTz) => Implicit: CastDateToTimestampTz,
+            (Date, TimestampTz) => Implicit: CastTemplate::new(|ecx, _ccx, _from_type, _to_type| {
+                let tz = ecx.qcx.scx.pcx.timezone;
+                Some(move |e: HirScalarExpr| if tz.is_utc() {
+                    e.call_unary(CastDateToTimestampTz)
+                } else {
+                    e.call_unary(CastDateToTimestamp).call_unary(TimezoneTimestamp(tz))
+                })
+            }),
             (Date, String) => Assignment: CastDateToString,
 
             // TIME
@@ -209,13 +237,37 @@ lazy_static! {
 
             // TIMESTAMP
             (Timestamp, Date) => Assignment: CastTimestampToDate,
-            (Timestamp, TimestampTz) => Implicit: CastTimestampToTimestampTz,
+            (Timestamp, TimestampTz) => Implicit: CastTemplate::new(|ecx, _ccx, _from_type, _to_type| {
+                let tz = ecx.qcx.scx.pcx.timezone;
+                Some(move |e: HirScalarExpr| if tz.is_utc() {
+                    e.call_unary(CastTimestampToTimestampTz)
+                } else {
+                    e.call_unary(TimezoneTimestamp(tz))
+                })
+            }),
             (Timestamp, String) => Assignment: CastTimestampToString,
 
             // TIMESTAMPTZ
-            (TimestampTz, Date) => Assignment: CastTimestampTzToDate,
-            (TimestampTz, Timestamp) => Assignment: CastTimestampTzToTimestamp,
-            (TimestampTz, String) => Assignment: CastTimestampTzToString,
+            (TimestampTz, Date) => Assignment: CastTemplate::new(|ecx, _ccx, _from_type, _to_type| {
+                let tz = ecx.qcx.scx.pcx.timezone;
+                Some(move |e: HirScalarExpr| if tz.is_utc() {
+                    e.call_unary(CastTimestampTzToDate)
+                } else {
+                    e.call_unary(TimezoneTimestampTz(tz)).call_unary(CastTimestampToDate)
+                })
+            }),
+            (TimestampTz, Timestamp) => Assignment: CastTemplate::new(|ecx, _ccx, _from_type, _to_type| {
+                let tz = ecx.qcx.scx.pcx.timezone;
+                Some(move |e: HirScalarExpr| if tz.is_utc() {
+                    e.call_unary(CastTimestampTzToTimestamp)
+                } else {
+                    e.call_unary(TimezoneTimestampTz(tz))
+                })
+            }),
+            (TimestampTz, String) => Assignment: CastTemplate::new(|ecx, _ccx, _from_type, _to_type| {
+                let tz = ecx.qcx.scx.pcx.timezone;
+                Some(move |e: HirScalarExpr| e.call_unary(CastTimestampTzToString(tz)))
+            }),
 
             // INTERVAL
             (Interval, Time) => Assignment: CastIntervalToTime,
@@ -242,11 +294,30 @@ lazy_static! {
             (String, Date) => Explicit: CastStringToDate,
             (String, Time) => Explicit: CastStringToTime,
             (String, Timestamp) => Explicit: CastStringToTimestamp,
-            (String, TimestampTz) => Explicit: CastStringToTimestampTz,
+            (String, TimestampTz) => Explicit: CastTemplate::new(|ecx, _ccx, _from_type, _to_type| {
+                let tz = ecx.qcx.scx.pcx.timezone;
+                Some(move |e: HirScalarExpr| e.call_unary(CastStringToTimestampTz(tz)))
+            }),
             (String, Interval) => Explicit: CastStringToInterval,
             (String, Bytes) => Explicit: CastStringToBytes,
             (String, Jsonb) => Explicit: CastStringToJsonb,
             (String, Uuid) => Explicit: CastStringToUuid,
+            (String, Inet) => Explicit: CastStringToInet,
+            (String, Cidr) => Explicit: CastStringToCidr,
+            (String, Char) => Implicit: CastTemplate::new(|_ecx, ccx, _from_type, to_type| {
+                let length = to_type.unwrap_char_length();
+                Some(move |e: HirScalarExpr| e.call_unary(CastStringToChar {
+                    length,
+                    fail_on_len: ccx != CastContext::Explicit,
+                }))
+            }),
+            (String, VarChar) => Implicit: CastTemplate::new(|_ecx, ccx, _from_type, to_type| {
+                let max_length = to_type.unwrap_varchar_max_length();
+                Some(move |e: HirScalarExpr| e.call_unary(CastStringToVarChar {
+                    max_length,
+                    fail_on_len: ccx != CastContext::Explicit,
+                }))
+            }),
             (String, Array) => Explicit: CastTemplate::new(|ecx, ccx, from_type, to_type| {
                 let return_ty = to_type.clone();
                 let to_el_type = to_type.unwrap_array_element_type();
@@ -274,6 +345,37 @@ lazy_static! {
                     cast_expr: Box::new(cast_expr),
                 }))
             }),
+            (String, Range) => Explicit: CastTemplate::new(|ecx, ccx, from_type, to_type| {
+                let return_ty = to_type.clone();
+                let to_el_type = to_type.unwrap_range_element_type();
+                let cast_expr = plan_hypothetical_cast(ecx, ccx, from_type, to_el_type)?;
+                Some(|e: HirScalarExpr| e.call_unary(UnaryFunc::CastStringToRange {
+                    return_ty,
+                    cast_expr: Box::new(cast_expr),
+                }))
+            }),
+
+            // CHAR
+            (Char, String) => Implicit: CastCharToString,
+            (Char, VarChar) => Implicit: CastTemplate::new(|_ecx, ccx, _from_type, to_type| {
+                let max_length = to_type.unwrap_varchar_max_length();
+                Some(move |e: HirScalarExpr| {
+                    e.call_unary(CastCharToString).call_unary(CastStringToVarChar {
+                        max_length,
+                        fail_on_len: ccx != CastContext::Explicit,
+                    })
+                })
+            }),
+
+            // VARCHAR
+            (VarChar, String) => Implicit: CastVarCharToString,
+            (VarChar, Char) => Implicit: CastTemplate::new(|_ecx, ccx, _from_type, to_type| {
+                let length = to_type.unwrap_char_length();
+                Some(move |e: HirScalarExpr| e.call_unary(CastStringToChar {
+                    length,
+                    fail_on_len: ccx != CastContext::Explicit,
+                }))
+            }),
 
             // RECORD
             (Record, String) => Assignment: CastTemplate::new(|_ecx, _ccx, from_type, _to_type| {
@@ -309,6 +411,12 @@ lazy_static! {
                 Some(|e: HirScalarExpr| e.call_unary(CastMapToString { ty }))
             }),
 
+            // RANGE
+            (Range, String) => Assignment: CastTemplate::new(|_ecx, _ccx, from_type, _to_type| {
+                let ty = from_type.clone();
+                Some(|e: HirScalarExpr| e.call_unary(CastRangeToString { ty }))
+            }),
+
             // JSONB
             (Jsonb, Bool) => Explicit: CastJsonbToBool,
             (Jsonb, Int32) => Explicit: CastJsonbToInt32,
@@ -319,12 +427,32 @@ lazy_static! {
                 let (_, s) = to_type.unwrap_decimal_parts();
                 Some(move |e: HirScalarExpr| e.call_unary(CastJsonbToDecimal(s)))
             }),
+            (Jsonb, APD) => Explicit: CastTemplate::new(|_ecx, _ccx, _from_type, to_type| {
+                let s = to_type.unwrap_apd_scale();
+                Some(move |e: HirScalarExpr| e.call_unary(CastJsonbToAPD(s)))
+            }),
             (Jsonb, String) => Assignment: CastJsonbToString,
 
             // UUID
             (Uuid, String) => Assignment: CastUuidToString,
 
+            // INET
+            (Inet, Cidr) => Assignment: CastInetToCidr,
+            (Inet, String) => Assignment: CastInetToString,
+
+            // CIDR
+            (Cidr, Inet) => Implicit: CastCidrToInet,
+            (Cidr, String) => Assignment: CastInetToString,
+
             // APD
+            (APD, Int32) => Assignment: CastAPDToInt32,
+            (APD, Int64) => Assignment: CastAPDToInt64,
+            (APD, Float32) => Implicit: CastAPDToFloat32,
+            (APD, Float64) => Implicit: CastAPDToFloat64,
+            (APD, Decimal) => Assignment: CastTemplate::new(|_ecx, _ccx, _from_type, to_type| {
+                let (_, s) = to_type.unwrap_decimal_parts();
+                Some(move |e: HirScalarExpr| e.call_unary(CastAPDToDecimal(s)))
+            }),
             (APD, String) => Assignment: CastAPDToString
         }
     };
@@ -359,19 +487,40 @@ fn get_cast(
     }
 
     if from == to {
-        return if let ScalarType::APD {
-            scale: Some(ref scale),
-        } = to
-        {
+        return match to {
             // All APD variations should be treated as equal to one another
             // except in the case where we want to cast an APD to another APD with a
             // specified scale (e.g. during an insert).
-            let scale = *scale;
-            Some(Box::new(move |e: HirScalarExpr| {
-                e.call_unary(UnaryFunc::RescaleAPD(scale))
-            }))
-        } else {
-            Some(Box::new(|expr| expr))
+            ScalarType::APD { scale: Some(scale) } => {
+                let scale = *scale;
+                Some(Box::new(move |e: HirScalarExpr| {
+                    e.call_unary(UnaryFunc::RescaleAPD(scale))
+                }))
+            }
+            // Likewise for character types whose length changes.
+            ScalarType::Char {
+                length: Some(length),
+            } if from.unwrap_char_length() != Some(*length) => {
+                let length = Some(*length);
+                Some(Box::new(move |e: HirScalarExpr| {
+                    e.call_unary(UnaryFunc::CastStringToChar {
+                        length,
+                        fail_on_len: ccx != Explicit,
+                    })
+                }))
+            }
+            ScalarType::VarChar {
+                max_length: Some(max_length),
+            } if from.unwrap_varchar_max_length() != Some(*max_length) => {
+                let max_length = Some(*max_length);
+                Some(Box::new(move |e: HirScalarExpr| {
+                    e.call_unary(UnaryFunc::CastStringToVarChar {
+                        max_length,
+                        fail_on_len: ccx != Explicit,
+                    })
+                }))
+            }
+            _ => Some(Box::new(|expr| expr)),
         };
     }
 
@@ -387,7 +536,10 @@ fn get_cast(
         }
     }
 
-    let imp = VALID_CASTS.get(&(from.into(), to.into()))?;
+    let imp = match VALID_CASTS.get(&(from.into(), to.into())) {
+        Some(imp) => imp,
+        None => return get_cast_via_string(ecx, ccx, from, to),
+    };
     let template = match (ccx, imp.context) {
         (Explicit, Implicit) | (Explicit, Assignment) | (Explicit, Explicit) => Some(&imp.template),
         (Assignment, Implicit) | (Assignment, Assignment) => Some(&imp.template),
@@ -397,6 +549,27 @@ fn get_cast(
     template.and_then(|template| (template.0)(ecx, ccx, from, to))
 }
 
+/// Gets a cast between a character type and a type that has no direct cast to
+/// or from that character type.
+///
+/// As in PostgreSQL, such casts are performed via `text`, e.g. casting
+/// `char(n)` to `int4` casts the value to `text` and then to `int4`.
+fn get_cast_via_string(
+    ecx: &ExprContext,
+    ccx: CastContext,
+    from: &ScalarType,
+    to: &ScalarType,
+) -> Option<Cast> {
+    let is_char =
+        |t: &ScalarType| matches!(t, ScalarType::Char { .. } | ScalarType::VarChar { .. });
+    if is_char(from) == is_char(to) || *from == ScalarType::String || *to == ScalarType::String {
+        return None;
+    }
+    let to_string = get_cast(ecx, ccx, from, &ScalarType::String)?;
+    let from_string = get_cast(ecx, ccx, &ScalarType::String, to)?;
+    Some(Box::new(move |e| from_string(to_string(e))))
+}
+
 pub fn rescale_decimal(expr: HirScalarExpr, s1: u8, s2: u8) -> HirScalarExpr {
     match s1.cmp(&s2) {
         Ordering::Less => {
@@ -448,9 +621,11 @@ pub fn to_jsonb(ecx: &ExprContext, expr: HirScalarExpr) -> HirScalarExpr {
         Int32 => plan_cast("to_jsonb", ecx, CastContext::Explicit, expr, &Int64)
             .expect("cast known to exist")
             .call_unary(UnaryFunc::CastJsonbOrNullToJsonb),
-        Float32 | Decimal(..) => plan_cast("to_jsonb", ecx, CastContext::Explicit, expr, &Float64)
-            .expect("cast known to exist")
-            .call_unary(UnaryFunc::CastJsonbOrNullToJsonb),
+        Float32 | Decimal(..) | APD { .. } => {
+            plan_cast("to_jsonb", ecx, CastContext::Explicit, expr, &Float64)
+                .expect("cast known to exist")
+                .call_unary(UnaryFunc::CastJsonbOrNullToJsonb)
+        }
         Record { fields, .. } => {
             let mut exprs = vec![];
             for (i, (name, _ty)) in fields.iter().enumerate() {
@@ -484,13 +659,19 @@ fn guess_compatible_cast_type(types: &[ScalarType]) -> Option<&ScalarType> {
         ScalarType::Int32 => 0,
         ScalarType::Int64 => 1,
         ScalarType::Decimal(_, _) => 2,
-        ScalarType::Float32 => 3,
-        ScalarType::Float64 => 4,
+        ScalarType::APD { .. } => 3,
+        ScalarType::Float32 => 4,
+        ScalarType::Float64 => 5,
         // [`TypeCategory::DateTime`]
-        ScalarType::Date => 5,
-        ScalarType::Timestamp => 6,
-        ScalarType::TimestampTz => 7,
-        _ => 8,
+        ScalarType::Date => 6,
+        ScalarType::Timestamp => 7,
+        ScalarType::TimestampTz => 8,
+        // [`TypeCategory::String`]
+        ScalarType::Char { .. } => 9,
+        ScalarType::VarChar { .. } => 10,
+        // [`TypeCategory::NetworkAddress`]
+        ScalarType::Cidr => 11,
+        _ => 12,
     })
 }
 
@@ -529,6 +710,17 @@ pub fn guess_best_common_type(
     }
 
     if known_types.iter().all(|t| *t == known_types[0]) {
+        // Character types of differing lengths have no common length.
+        let lengths_differ = known_types.iter().any(|t| match (t, &known_types[0]) {
+            (ScalarType::Char { length: l }, ScalarType::Char { length: r }) => l != r,
+            (ScalarType::VarChar { max_length: l }, ScalarType::VarChar { max_length: r }) => {
+                l != r
+            }
+            _ => false,
+        });
+        if lengths_differ {
+            return Some(known_types[0].without_length());
+        }
         return Some(known_types[0].clone());
     }
 
@@ -543,6 +735,10 @@ pub fn guess_best_common_type(
                 }
             }
             return Some(ScalarType::Decimal(38, max_s));
+        } else if let ScalarType::APD { .. } = btt {
+            // As in PostgreSQL, numerics of differing scales have no common
+            // scale.
+            return Some(ScalarType::APD { scale: None });
         } else {
             return Some(btt.clone());
         }
@@ -564,8 +760,17 @@ pub fn plan_coerce<'a>(
         LiteralNull => HirScalarExpr::literal_null(coerce_to.clone()),
 
         LiteralString(s) => {
+            // Any length constraint of a character type is enforced by the
+            // cast that follows the coercion, whose context determines
+            // whether overlong strings are truncated or rejected.
             let lit = HirScalarExpr::literal(Datum::String(&s), ScalarType::String);
-            plan_cast("string literal", ecx, CastContext::Explicit, lit, coerce_to)?
+            plan_cast(
+                "string literal",
+                ecx,
+                CastContext::Explicit,
+                lit,
+                &coerce_to.without_length(),
+            )?
         }
 
         LiteralRecord(exprs) => {
@@ -670,10 +875,6 @@ pub fn plan_cast<D>(
 where
     D: fmt::Display,
 {
-    if let ScalarType::APD { .. } = cast_to {
-        ecx.require_experimental_mode("APD")?;
-    }
-
     let from_typ = ecx.scalar_type(&expr);
     match get_cast(ecx, ccx, &from_typ, cast_to) {
         Some(cast) => Ok(cast(expr)),
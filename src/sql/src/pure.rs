@@ -24,16 +24,18 @@ use tokio::time::Duration;
 use uuid::Uuid;
 
 use dataflow_types::{ExternalSourceConnector, PostgresSourceConnector, SourceConnector};
+use ore::collections::CollectionExt;
 use repr::strconv;
 use sql_parser::ast::{
-    display::AstDisplay, AvroSchema, Connector, CreateSourceFormat, CreateSourceStatement,
-    CreateViewsDefinitions, CreateViewsSourceTarget, CreateViewsStatement, CsrSeed, DbzMode,
-    Envelope, Expr, Format, Ident, Query, Raw, RawName, Select, SelectItem, SetExpr, Statement,
-    TableFactor, TableWithJoins, UnresolvedObjectName, Value, ViewDefinition,
+    display::AstDisplay, AlterSourceRefreshSchemaStatement, AvroSchema, Connector,
+    CreateSinkStatement, CreateSourceFormat, CreateSourceStatement, CreateViewsDefinitions,
+    CreateViewsSourceTarget, CreateViewsStatement, CsrSeed, DbzMode, Envelope, Expr, Format, Ident,
+    Query, Raw, RawName, Select, SelectItem, SetExpr, Statement, TableFactor, TableWithJoins,
+    UnresolvedObjectName, Value, ViewDefinition,
 };
 use sql_parser::parser::parse_columns;
 
-use crate::catalog::Catalog;
+use crate::catalog::{Catalog, CatalogItemType};
 use crate::kafka_util;
 use crate::normalize;
 
@@ -70,6 +72,22 @@ pub fn purify(
         Err(anyhow!("SQL statement does not refer to a source"))
     };
 
+    // Similarly, if we're dealing with an ALTER SOURCE ... REFRESH SCHEMA
+    // statement we need the definition of the source. Errors resolving the
+    // source are left for planning to report.
+    let refreshed_source_sql = match &stmt {
+        Statement::AlterSourceRefreshSchema(AlterSourceRefreshSchemaStatement {
+            source_name,
+            seed: None,
+            ..
+        }) => normalize::unresolved_object_name(source_name.clone())
+            .ok()
+            .and_then(|name| catalog.resolve_item(&name).ok())
+            .filter(|item| item.item_type() == CatalogItemType::Source)
+            .map(|item| item.create_sql().to_owned()),
+        _ => None,
+    };
+
     async {
         if let Statement::CreateSource(CreateSourceStatement {
             col_names,
@@ -93,9 +111,12 @@ pub fn purify(
                     // Verify that the provided security options are valid and then test them.
                     config_options = kafka_util::extract_config(&mut with_options_map)?;
                     let consumer =
-                        kafka_util::create_consumer(&broker, &topic, &config_options).await?;
+                        kafka_util::create_consumer(&broker, Some(topic.as_str()), &config_options)
+                            .await?;
 
-                    // Translate `kafka_time_offset` to `start_offset`.
+                    // Translate `kafka_time_offset` or `start_timestamp` to
+                    // `start_offset`, so that the resolved offsets are
+                    // recorded in the catalog and restarts are deterministic.
                     match kafka_util::lookup_start_offsets(
                         consumer.clone(),
                         &topic,
@@ -104,10 +125,11 @@ pub fn purify(
                     .await?
                     {
                         Some(start_offsets) => {
-                            // Drop `kafka_time_offset`
+                            // Drop `kafka_time_offset` and `start_timestamp`
                             with_options.retain(|val| match val {
                                 sql_parser::ast::SqlOption::Value { name, .. } => {
                                     name.as_str() != "kafka_time_offset"
+                                        && name.as_str() != "start_timestamp"
                                 }
                                 _ => true,
                             });
@@ -197,6 +219,26 @@ pub fn purify(
             )
             .await?;
         }
+        if let Statement::CreateSink(CreateSinkStatement {
+            connector: Connector::Kafka { broker, .. },
+            with_options,
+            ..
+        }) = &stmt
+        {
+            // Verify that the provided security options are valid and then
+            // test them. The sink's topic need not exist yet, so this fetches
+            // metadata for the whole cluster.
+            let config_options = kafka_util::extract_config(&mut normalize::options(with_options))?;
+            kafka_util::create_consumer(broker, None, &config_options).await?;
+        }
+        if let Statement::AlterSourceRefreshSchema(AlterSourceRefreshSchemaStatement {
+            seed, ..
+        }) = &mut stmt
+        {
+            if let Some(create_sql) = refreshed_source_sql {
+                *seed = fetch_refreshed_seed(&create_sql).await?;
+            }
+        }
         if let Statement::CreateViews(CreateViewsStatement { definitions, .. }) = &mut stmt {
             if let CreateViewsDefinitions::Source {
                 name: source_name,
@@ -337,6 +379,9 @@ pub fn purify(
                         bail!("cannot generate views from {} sources", connector.name())
                     }
                     SourceConnector::Local => bail!("cannot generate views from local sources"),
+                    SourceConnector::DeadLetters(_) => {
+                        bail!("cannot generate views from dead-letter sources")
+                    }
                 }
             }
         }
@@ -364,7 +409,7 @@ async fn purify_format(
     // TODO(bwm): We should either make this the semantics everywhere, or deprecate
     // this.
     if matches!(connector, Connector::Kafka { .. })
-        && matches!(envelope, Envelope::Upsert)
+        && matches!(envelope, Envelope::Upsert(None))
         && format.is_simple()
     {
         let value = format.value().map(|f| f.clone());
@@ -501,6 +546,46 @@ async fn purify_format_single(
     Ok(())
 }
 
+/// Fetches the latest schemas from the schema registry for the source defined
+/// by `create_sql`, or returns `None` if the source does not use the schema
+/// registry.
+async fn fetch_refreshed_seed(create_sql: &str) -> Result<Option<CsrSeed>, anyhow::Error> {
+    let CreateSourceStatement {
+        mut connector,
+        mut col_names,
+        format,
+        envelope,
+        with_options,
+        ..
+    } = match crate::parse::parse(create_sql)?.into_element() {
+        Statement::CreateSource(stmt) => stmt,
+        _ => return Ok(None),
+    };
+    let mut format = match format {
+        CreateSourceFormat::None => return Ok(None),
+        CreateSourceFormat::Bare(format) => format,
+        CreateSourceFormat::KeyValue { value, .. } => value,
+    };
+    match (&connector, &mut format) {
+        (Connector::Kafka { .. }, Format::Avro(AvroSchema::CsrUrl { seed, .. })) => *seed = None,
+        _ => return Ok(None),
+    }
+    let config_options = kafka_util::extract_config(&mut normalize::options(&with_options))?;
+    purify_format_single(
+        &mut format,
+        &mut connector,
+        &envelope,
+        &mut col_names,
+        None,
+        &config_options,
+    )
+    .await?;
+    match format {
+        Format::Avro(AvroSchema::CsrUrl { seed, .. }) => Ok(seed),
+        _ => unreachable!("purification does not change the format"),
+    }
+}
+
 #[derive(Debug)]
 pub struct Schema {
     pub key_schema: Option<String>,
@@ -37,7 +37,7 @@ use std::str;
 use std::time::Duration;
 
 use anyhow::{anyhow, bail};
-use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime};
 use fallible_iterator::FallibleIterator;
 use lazy_static::lazy_static;
 use md5::{Digest, Md5};
@@ -50,7 +50,9 @@ use tokio_postgres::types::Type as PgType;
 use tokio_postgres::{NoTls, Row, SimpleQueryMessage};
 use uuid::Uuid;
 
-use pgrepr::{Interval, Jsonb, Numeric, Value};
+use pgrepr::{APDNumeric, Interval, Jsonb, Value};
+use repr::adt::apd::{self, Apd};
+use repr::adt::datetime::Timezone;
 use repr::ColumnName;
 use sql::ast::Statement;
 
@@ -307,13 +309,27 @@ impl<'a> FromSql<'a> for Slt {
             PgType::INT8 => Self(Value::Int8(types::int8_from_sql(raw)?)),
             PgType::INTERVAL => Self(Value::Interval(Interval::from_sql(ty, raw)?)),
             PgType::JSONB => Self(Value::Jsonb(Jsonb::from_sql(ty, raw)?)),
-            PgType::NUMERIC => Self(Value::Numeric(Numeric::from_sql(ty, raw)?)),
+            PgType::NUMERIC => Self(Value::APD(APDNumeric::from_sql(ty, raw)?.0)),
             PgType::OID => Self(Value::Int4(types::oid_from_sql(raw)? as i32)),
-            PgType::TEXT => Self(Value::Text(types::text_from_sql(raw)?.to_string())),
+            PgType::TEXT | PgType::BPCHAR | PgType::VARCHAR => {
+                Self(Value::Text(types::text_from_sql(raw)?.to_string()))
+            }
             PgType::TIME => Self(Value::Time(NaiveTime::from_sql(ty, raw)?)),
             PgType::TIMESTAMP => Self(Value::Timestamp(NaiveDateTime::from_sql(ty, raw)?)),
-            PgType::TIMESTAMPTZ => Self(Value::TimestampTz(DateTime::<Utc>::from_sql(ty, raw)?)),
+            PgType::TIMESTAMPTZ => Self(Value::TimestampTz(DateTime::<FixedOffset>::from_sql(
+                ty, raw,
+            )?)),
             PgType::UUID => Self(Value::Uuid(Uuid::from_sql(ty, raw)?)),
+            PgType::INET => Self(Value::decode_binary(
+                &pgrepr::Type::Inet,
+                raw,
+                Timezone::UTC,
+            )?),
+            PgType::CIDR => Self(Value::decode_binary(
+                &pgrepr::Type::Cidr,
+                raw,
+                Timezone::UTC,
+            )?),
             PgType::RECORD => {
                 let num_fields = read_be_i32(&mut raw)?;
                 let mut tuple = vec![];
@@ -356,13 +372,17 @@ impl<'a> FromSql<'a> for Slt {
                         elements,
                     })
                 }
+                PgKind::Range(_) => {
+                    let typ = pgrepr::Type::from_oid(ty.oid()).ok_or("unknown range type")?;
+                    Self(Value::decode_binary(&typ, raw, Timezone::UTC)?)
+                }
                 _ => unreachable!(),
             },
         })
     }
     fn accepts(ty: &PgType) -> bool {
         match ty.kind() {
-            PgKind::Array(_) | PgKind::Composite(_) => return true,
+            PgKind::Array(_) | PgKind::Composite(_) | PgKind::Range(_) => return true,
             _ => {}
         }
         matches!(
@@ -381,10 +401,14 @@ impl<'a> FromSql<'a> for Slt {
                 | PgType::OID
                 | PgType::RECORD
                 | PgType::TEXT
+                | PgType::BPCHAR
+                | PgType::VARCHAR
                 | PgType::TIME
                 | PgType::TIMESTAMP
                 | PgType::TIMESTAMPTZ
                 | PgType::UUID
+                | PgType::INET
+                | PgType::CIDR
         )
     }
 }
@@ -419,13 +443,25 @@ where
     T::from_sql_nullable(type_, value)
 }
 
+/// Formats `n` in standard notation, rounded to `scale` decimal places if
+/// specified and possible.
+fn format_apd(n: Apd, scale: Option<u8>) -> String {
+    if let Some(scale) = scale {
+        let mut rounded = n;
+        if apd::rescale(&mut rounded, scale).is_ok() {
+            return rounded.to_standard_notation_string();
+        }
+    }
+    n.to_standard_notation_string()
+}
+
 fn format_datum(d: Slt, typ: &Type, mode: Mode, col: usize) -> String {
     match (typ, d.0) {
         (Type::Bool, Value::Bool(b)) => b.to_string(),
 
         (Type::Integer, Value::Int4(i)) => i.to_string(),
         (Type::Integer, Value::Int8(i)) => i.to_string(),
-        (Type::Integer, Value::Numeric(d)) => format!("{:.0}", d),
+        (Type::Integer, Value::APD(d)) => format_apd(d.0, Some(0)),
         (Type::Integer, Value::Float4(f)) => format!("{:.0}", f.trunc()),
         (Type::Integer, Value::Float8(f)) => format!("{:.0}", f.trunc()),
         // This is so wrong, but sqlite needs it.
@@ -442,9 +478,9 @@ fn format_datum(d: Slt, typ: &Type, mode: Mode, col: usize) -> String {
             Mode::Standard => format!("{:.3}", f),
             Mode::Cockroach => format!("{}", f),
         },
-        (Type::Real, Value::Numeric(d)) => match mode {
-            Mode::Standard => format!("{:.3}", d),
-            Mode::Cockroach => format!("{}", d),
+        (Type::Real, Value::APD(d)) => match mode {
+            Mode::Standard => format_apd(d.0, Some(3)),
+            Mode::Cockroach => format_apd(d.0, None),
         },
 
         (Type::Text, Value::Text(s)) => {
@@ -455,7 +491,7 @@ fn format_datum(d: Slt, typ: &Type, mode: Mode, col: usize) -> String {
             }
         }
         (Type::Text, Value::Bool(b)) => b.to_string(),
-        (Type::Text, Value::Numeric(d)) => format!("{:.0}", d),
+        (Type::Text, Value::APD(d)) => format_apd(d.0, Some(0)),
         (Type::Text, Value::Float4(f)) => format!("{:.3}", f),
         (Type::Text, Value::Float8(f)) => format!("{:.3}", f),
         // Bytes are printed as text iff they are valid UTF-8. This
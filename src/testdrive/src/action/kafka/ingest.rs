@@ -14,6 +14,7 @@ use std::time::Duration;
 use async_trait::async_trait;
 use byteorder::{NetworkEndian, WriteBytesExt};
 use futures::stream::{FuturesUnordered, StreamExt};
+use rdkafka::message::OwnedHeaders;
 use rdkafka::producer::FutureRecord;
 use serde::de::DeserializeOwned;
 
@@ -29,6 +30,7 @@ pub struct IngestAction {
     format: Format,
     key_format: Option<Format>,
     timestamp: Option<i64>,
+    headers: Vec<(String, String)>,
     publish: bool,
     corrupt_keys: bool,
     corrupt_values: bool,
@@ -206,6 +208,19 @@ pub fn build_ingest(mut cmd: BuiltinCommand) -> Result<IngestAction, String> {
         None => None,
     };
     let timestamp = cmd.args.opt_parse("timestamp")?;
+    let headers = match cmd.args.opt_string("headers") {
+        None => vec![],
+        Some(headers) => headers
+            .split(',')
+            .map(|header| {
+                let mut parts = header.splitn(2, ':');
+                match (parts.next(), parts.next()) {
+                    (Some(key), Some(value)) => Ok((key.to_owned(), value.to_owned())),
+                    _ => Err(format!("header must be of the form KEY:VALUE: {}", header)),
+                }
+            })
+            .collect::<Result<_, _>>()?,
+    };
     let corrupt_keys = cmd.args.opt_parse("corrupt-keys")?.unwrap_or(false);
     let corrupt_values = cmd.args.opt_parse("corrupt-values")?.unwrap_or(false);
     let publish = cmd.args.opt_bool("publish")?.unwrap_or(false);
@@ -217,6 +232,7 @@ pub fn build_ingest(mut cmd: BuiltinCommand) -> Result<IngestAction, String> {
         format,
         key_format,
         timestamp,
+        headers,
         publish,
         corrupt_keys,
         corrupt_values,
@@ -314,6 +330,15 @@ impl Action for IngestAction {
                     if let Some(timestamp) = self.timestamp {
                         record = record.timestamp(timestamp);
                     }
+                    if !self.headers.is_empty() {
+                        let headers = self
+                            .headers
+                            .iter()
+                            .fold(OwnedHeaders::new(), |headers, (k, v)| {
+                                headers.add(k, v.as_str())
+                            });
+                        record = record.headers(headers);
+                    }
                     producer.send(record, timeout).await
                 });
             }
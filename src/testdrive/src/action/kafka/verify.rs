@@ -15,6 +15,7 @@ use async_trait::async_trait;
 use byteorder::{BigEndian, ByteOrder};
 use rdkafka::consumer::{Consumer, StreamConsumer};
 use rdkafka::message::Message;
+use rdkafka::{Offset, TopicPartitionList};
 use tokio::pin;
 use tokio_stream::StreamExt;
 
@@ -30,6 +31,7 @@ pub struct VerifyAction {
     sink: String,
     consistency: Option<SinkConsistencyFormat>,
     sort_messages: bool,
+    partition: Option<i32>,
     expected_messages: Vec<String>,
     context: Context,
 }
@@ -44,12 +46,14 @@ pub fn build_verify(mut cmd: BuiltinCommand, context: Context) -> Result<VerifyA
     };
 
     let sort_messages = cmd.args.opt_bool("sort-messages")?.unwrap_or(false);
+    let partition = cmd.args.opt_parse("partition")?;
     let expected_messages = cmd.input;
     cmd.args.done()?;
     Ok(VerifyAction {
         sink,
         consistency,
         sort_messages,
+        partition,
         expected_messages,
         context,
     })
@@ -139,9 +143,20 @@ impl Action for VerifyAction {
         let consumer: StreamConsumer = config
             .create()
             .map_err(|e| format!("creating kafka consumer: {}", e))?;
-        consumer
-            .subscribe(&[&topic])
-            .map_err(|e| format!("subscribing: {}", e.to_string()))?;
+        match self.partition {
+            // Read only the requested partition, from its beginning.
+            Some(partition) => {
+                let mut tpl = TopicPartitionList::new();
+                tpl.add_partition_offset(&topic, partition, Offset::Beginning)
+                    .map_err(|e| format!("assigning partition: {}", e.to_string()))?;
+                consumer
+                    .assign(&tpl)
+                    .map_err(|e| format!("assigning partition: {}", e.to_string()))?;
+            }
+            None => consumer
+                .subscribe(&[&topic])
+                .map_err(|e| format!("subscribing: {}", e.to_string()))?,
+        }
 
         // Wait up to 15 seconds for each message.
         let message_stream = consumer
@@ -0,0 +1,112 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::cmp;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use rdkafka::admin::ResourceSpecifier;
+use rdkafka::producer::Producer;
+
+use ore::collections::CollectionExt;
+
+use crate::action::{Action, State};
+use crate::parser::BuiltinCommand;
+
+pub struct VerifyTopicAction {
+    sink: String,
+    partition_count: Option<usize>,
+    compaction: Option<bool>,
+}
+
+pub fn build_verify_topic(mut cmd: BuiltinCommand) -> Result<VerifyTopicAction, String> {
+    let sink = cmd.args.string("sink")?;
+    let partition_count = cmd.args.opt_parse("partition-count")?;
+    let compaction = cmd.args.opt_bool("compaction")?;
+    cmd.args.done()?;
+    Ok(VerifyTopicAction {
+        sink,
+        partition_count,
+        compaction,
+    })
+}
+
+#[async_trait]
+impl Action for VerifyTopicAction {
+    async fn undo(&self, _state: &mut State) -> Result<(), String> {
+        Ok(())
+    }
+
+    async fn redo(&self, state: &mut State) -> Result<(), String> {
+        let topic: String = state
+            .pgclient
+            .query_one(
+                "SELECT topic FROM mz_catalog_names JOIN mz_kafka_sinks ON global_id = sink_id WHERE name = $1",
+                &[&self.sink],
+            )
+            .await
+            .map_err(|e| format!("retrieving topic name: {}", e))?
+            .get("topic");
+
+        println!("Verifying configuration of Kafka topic {}", topic);
+
+        if let Some(expected) = self.partition_count {
+            let metadata = state
+                .kafka_producer
+                .client()
+                .fetch_metadata(
+                    Some(&topic),
+                    Some(cmp::max(state.default_timeout, Duration::from_secs(1))),
+                )
+                .map_err(|e| e.to_string())?;
+            if metadata.topics().len() != 1 {
+                return Err("metadata fetch returned no topics".to_string());
+            }
+            let actual = metadata.topics().into_element().partitions().len();
+            if actual != expected {
+                return Err(format!(
+                    "topic {} has {} partitions when exactly {} was expected",
+                    topic, actual, expected
+                ));
+            }
+        }
+
+        if let Some(expected) = self.compaction {
+            let configs = state
+                .kafka_admin
+                .describe_configs(&[ResourceSpecifier::Topic(&topic)], &state.kafka_admin_opts)
+                .await
+                .map_err(|e| format!("describing topic configuration: {}", e))?;
+            if configs.len() != 1 {
+                return Err(format!(
+                    "describing topic configuration returned {} results, but exactly one result was expected",
+                    configs.len()
+                ));
+            }
+            let config = configs
+                .into_element()
+                .map_err(|e| format!("describing topic configuration: {}", e))?;
+            let policy = config
+                .entries
+                .iter()
+                .find(|entry| entry.name == "cleanup.policy")
+                .and_then(|entry| entry.value.as_deref())
+                .unwrap_or("");
+            let actual = policy.split(',').any(|policy| policy.trim() == "compact");
+            if actual != expected {
+                return Err(format!(
+                    "topic {} has cleanup.policy {:?}, but compaction={} was expected",
+                    topic, policy, expected
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
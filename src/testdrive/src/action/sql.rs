@@ -112,12 +112,32 @@ impl Action for SqlAction {
             // to return an empty result. The original result would thus be lost.
             Fetch(_) => false,
             // DDL statements should always provide the expected result on the first try
-            CreateDatabase(_) | CreateSchema(_) | CreateSource(_) | CreateSink(_)
-            | CreateView(_) | CreateViews(_) | CreateTable(_) | CreateIndex(_) | CreateType(_)
-            | CreateRole(_) | AlterObjectRename(_) | AlterIndexOptions(_) | Discard(_)
-            | DropDatabase(_) | DropObjects(_) | SetVariable(_) | ShowDatabases(_)
-            | ShowObjects(_) | ShowIndexes(_) | ShowColumns(_) | ShowCreateView(_)
-            | ShowCreateSource(_) | ShowCreateTable(_) | ShowCreateSink(_) | ShowCreateIndex(_)
+            CreateDatabase(_)
+            | CreateSchema(_)
+            | CreateSource(_)
+            | CreateSink(_)
+            | CreateView(_)
+            | CreateViews(_)
+            | CreateTable(_)
+            | CreateIndex(_)
+            | CreateType(_)
+            | CreateRole(_)
+            | AlterObjectRename(_)
+            | AlterIndexOptions(_)
+            | AlterSourceRefreshSchema(_)
+            | Discard(_)
+            | DropDatabase(_)
+            | DropObjects(_)
+            | SetVariable(_)
+            | ShowDatabases(_)
+            | ShowObjects(_)
+            | ShowIndexes(_)
+            | ShowColumns(_)
+            | ShowCreateView(_)
+            | ShowCreateSource(_)
+            | ShowCreateTable(_)
+            | ShowCreateSink(_)
+            | ShowCreateIndex(_)
             | ShowVariable(_) => false,
             _ => true,
         };
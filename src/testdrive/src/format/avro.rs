@@ -114,6 +114,17 @@ pub fn from_json(json: &JsonValue, schema: SchemaNode) -> Result<Value, String>
                 Ok(Value::Fixed(*size, bytes))
             }
         }
+        (JsonValue::Array(items), SchemaPiece::Bytes) => {
+            let bytes = match items
+                .iter()
+                .map(|x| x.as_i64().and_then(|x| u8::try_from(x).ok()))
+                .collect::<Option<Vec<u8>>>()
+            {
+                Some(bytes) => bytes,
+                None => return Err("bytes were not represented by byte array".into()),
+            };
+            Ok(Value::Bytes(bytes))
+        }
         (JsonValue::String(s), SchemaPiece::Json) => {
             let j = serde_json::from_str(s).map_err(|e| e.to_string())?;
             Ok(Value::Json(j))
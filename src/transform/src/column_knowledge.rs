@@ -222,6 +222,7 @@ impl ColumnKnowledge {
                         | AggregateFunc::MaxFloat32
                         | AggregateFunc::MaxFloat64
                         | AggregateFunc::MaxDecimal
+                        | AggregateFunc::MaxAPD
                         | AggregateFunc::MaxBool
                         | AggregateFunc::MaxString
                         | AggregateFunc::MaxDate
@@ -232,6 +233,7 @@ impl ColumnKnowledge {
                         | AggregateFunc::MinFloat32
                         | AggregateFunc::MinFloat64
                         | AggregateFunc::MinDecimal
+                        | AggregateFunc::MinAPD
                         | AggregateFunc::MinBool
                         | AggregateFunc::MinString
                         | AggregateFunc::MinDate
@@ -49,7 +49,7 @@ impl ReduceElision {
                 keys.iter()
                     .all(|k| group_key.contains(&expr::MirScalarExpr::Column(*k)))
             }) {
-                use expr::{AggregateFunc, UnaryFunc, VariadicFunc};
+                use expr::{AggregateFunc, BinaryFunc, UnaryFunc, VariadicFunc};
                 use repr::Datum;
                 let map_scalars = aggregates
                     .iter()
@@ -82,6 +82,29 @@ impl ReduceElision {
                             exprs: vec![a.expr.clone()],
                         },
 
+                        // RangeAgg takes a range as input, but must output an
+                        // array of the non-empty input ranges.
+                        AggregateFunc::RangeAgg => {
+                            let elem_type = a.expr.typ(&input_type).scalar_type;
+                            let is_null_or_empty =
+                                a.expr.clone().call_unary(UnaryFunc::IsNull).call_binary(
+                                    a.expr.clone().call_unary(UnaryFunc::RangeIsEmpty),
+                                    BinaryFunc::Or,
+                                );
+                            is_null_or_empty.if_then_else(
+                                MirScalarExpr::CallVariadic {
+                                    func: VariadicFunc::ArrayCreate {
+                                        elem_type: elem_type.clone(),
+                                    },
+                                    exprs: vec![],
+                                },
+                                MirScalarExpr::CallVariadic {
+                                    func: VariadicFunc::ArrayCreate { elem_type },
+                                    exprs: vec![a.expr.clone()],
+                                },
+                            )
+                        }
+
                         // All other variants should return the argument to the aggregation.
                         _ => a.expr.clone(),
                     })
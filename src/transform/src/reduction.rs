@@ -458,7 +458,7 @@ impl FoldConstants {
                     .map(|expr| expr.eval(&datums, &temp_storage))
                     .collect::<Result<Vec<_>, _>>()?,
                 &temp_storage,
-            );
+            )?;
             for (output_row, diff2) in output_rows {
                 row_packer.extend(input_row.clone().into_iter().chain(output_row.into_iter()));
                 new_rows.push((row_packer.finish_and_reuse(), diff2 * *diff))